@@ -107,6 +107,7 @@ impl Monitor {
 pub enum AlertModelType {
 	Classifier,
 	Regressor,
+	Ranker,
 }
 
 impl From<modelfox_model::ModelInnerReader<'_>> for AlertModelType {
@@ -115,6 +116,7 @@ impl From<modelfox_model::ModelInnerReader<'_>> for AlertModelType {
 		match mir {
 			BinaryClassifier(_) | MulticlassClassifier(_) => AlertModelType::Classifier,
 			Regressor(_) => AlertModelType::Regressor,
+			Ranker(_) => AlertModelType::Ranker,
 		}
 	}
 }
@@ -323,6 +323,8 @@ pub async fn insert_or_update_production_metrics_for_monitor_event(
 			class_name,
 			..
 		}) => NumberOrString::String(class_name),
+		// Ranking metrics can only be computed over whole query groups, so production metrics are not computed for rankers.
+		PredictOutput::Ranking(_) => return Ok(()),
 	};
 	let row = sqlx::query(
 		"
@@ -24,7 +24,7 @@ pub struct PredictionMonitorEvent {
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct PredictOptions {
-	pub threshold: f32,
+	pub threshold: Option<f32>,
 	#[serde(alias = "computeFeatureContributions")]
 	pub compute_feature_contributions: bool,
 }
@@ -45,6 +45,7 @@ pub enum PredictOutput {
 	Regression(RegressionPredictOutput),
 	BinaryClassification(BinaryClassificationPredictOutput),
 	MulticlassClassification(MulticlassClassificationPredictOutput),
+	Ranking(RankingPredictOutput),
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -66,6 +67,11 @@ pub struct MulticlassClassificationPredictOutput {
 	pub probabilities: HashMap<String, f32>,
 }
 
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RankingPredictOutput {
+	pub score: f32,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(untagged)]
 pub enum NumberOrString {
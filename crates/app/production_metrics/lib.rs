@@ -42,6 +42,9 @@ pub enum ProductionPredictionMetrics {
 	BinaryClassification(BinaryClassificationProductionPredictionMetrics),
 	#[serde(rename = "multiclass_classification")]
 	MulticlassClassification(MulticlassClassificationProductionPredictionMetrics),
+	/// Production metrics are not computed for rankers because ranking metrics can only be computed over whole query groups.
+	#[serde(rename = "ranking")]
+	Ranking,
 }
 
 #[derive(serde::Deserialize)]
@@ -119,6 +122,7 @@ impl ProductionPredictionMetrics {
 					),
 				)
 			}
+			modelfox_model::ModelInnerReader::Ranker(_) => ProductionPredictionMetrics::Ranking,
 		}
 	}
 
@@ -127,6 +131,7 @@ impl ProductionPredictionMetrics {
 			ProductionPredictionMetrics::Regression(s) => s.update(value),
 			ProductionPredictionMetrics::BinaryClassification(s) => s.update(value),
 			ProductionPredictionMetrics::MulticlassClassification(s) => s.update(value),
+			ProductionPredictionMetrics::Ranking => {}
 		}
 	}
 
@@ -147,6 +152,7 @@ impl ProductionPredictionMetrics {
 					s.merge(other)
 				}
 			}
+			ProductionPredictionMetrics::Ranking => {}
 		}
 	}
 
@@ -161,6 +167,7 @@ impl ProductionPredictionMetrics {
 			ProductionPredictionMetrics::MulticlassClassification(s) => s
 				.finalize()
 				.map(ProductionPredictionMetricsOutput::MulticlassClassification),
+			ProductionPredictionMetrics::Ranking => None,
 		}
 	}
 }
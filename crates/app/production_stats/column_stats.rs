@@ -1,7 +1,6 @@
 use super::number_stats::{NumberStats, NumberStatsOutput};
 use fnv::{FnvBuildHasher, FnvHashMap, FnvHashSet};
 use indexmap::{IndexMap, IndexSet};
-use itertools::Itertools;
 use modelfox_text::{NGram, NGramRef, NGramType, Tokenizer};
 use num::ToPrimitive;
 
@@ -175,6 +174,9 @@ impl ProductionColumnStats {
 					modelfox_model::ModelInnerReader::MulticlassClassifier(
 						multiclass_classifier,
 					) => multiclass_classifier.read().train_column_stats(),
+					modelfox_model::ModelInnerReader::Ranker(ranker) => {
+						ranker.read().train_column_stats()
+					}
 				};
 
 				// Get the stats from the model for this text column
@@ -200,6 +202,15 @@ impl ProductionColumnStats {
 					.map(|ngram_type| match ngram_type {
 						modelfox_model::NGramTypeReader::Unigram(_) => NGramType::Unigram,
 						modelfox_model::NGramTypeReader::Bigram(_) => NGramType::Bigram,
+						modelfox_model::NGramTypeReader::CharacterTrigram(_) => {
+							NGramType::CharacterTrigram
+						}
+						modelfox_model::NGramTypeReader::CharacterQuadgram(_) => {
+							NGramType::CharacterQuadgram
+						}
+						modelfox_model::NGramTypeReader::CharacterPentagram(_) => {
+							NGramType::CharacterPentagram
+						}
 					})
 					.collect();
 
@@ -501,23 +512,7 @@ impl TextProductionColumnStats {
 
 		let mut ngrams_for_row = FnvHashSet::default();
 
-		let unigram_iter = ngram_types.contains(&NGramType::Unigram).then(|| {
-			tokenizer
-				.tokenize(value)
-				.map(modelfox_text::NGramRef::Unigram)
-		});
-
-		let bigram_iter = ngram_types.contains(&NGramType::Bigram).then(|| {
-			tokenizer
-				.tokenize(value)
-				.tuple_windows()
-				.map(|(token_a, token_b)| modelfox_text::NGramRef::Bigram(token_a, token_b))
-		});
-
-		let ngram_iter = unigram_iter
-			.into_iter()
-			.flatten()
-			.chain(bigram_iter.into_iter().flatten());
+		let ngram_iter = tokenizer.ngrams(value, ngram_types.iter());
 
 		for ngram in ngram_iter {
 			if tracked_ngrams.contains(&ngram) {
@@ -49,6 +49,7 @@ impl ProductionStats {
 			modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
 				multiclass_classifier.read().train_column_stats()
 			}
+			modelfox_model::ModelInnerReader::Ranker(ranker) => ranker.read().train_column_stats(),
 		};
 		let column_stats = train_column_stats
 			.iter()
@@ -11,6 +11,9 @@ pub enum ProductionPredictionStats {
 	BinaryClassification(ClassificationProductionPredictionStats),
 	#[serde(rename = "multiclass_classification")]
 	MulticlassClassification(ClassificationProductionPredictionStats),
+	/// The scores of a ranker are only meaningful relative to the other examples in the same query group, so prediction stats are not computed for rankers.
+	#[serde(rename = "ranking")]
+	Ranking,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
@@ -28,6 +31,7 @@ pub enum ProductionPredictionStatsOutput {
 	Regression(RegressionProductionPredictionStatsOutput),
 	BinaryClassification(ClassificationProductionPredictionStatsOutput),
 	MulticlassClassification(ClassificationProductionPredictionStatsOutput),
+	Ranking,
 }
 
 #[derive(Debug)]
@@ -67,6 +71,7 @@ impl ProductionPredictionStats {
 					),
 				)
 			}
+			modelfox_model::ModelInnerReader::Ranker(_) => ProductionPredictionStats::Ranking,
 		}
 	}
 
@@ -75,6 +80,7 @@ impl ProductionPredictionStats {
 			ProductionPredictionStats::Regression(stats) => stats.update(value),
 			ProductionPredictionStats::BinaryClassification(stats) => stats.update(value),
 			ProductionPredictionStats::MulticlassClassification(stats) => stats.update(value),
+			ProductionPredictionStats::Ranking => {}
 		}
 	}
 
@@ -95,6 +101,7 @@ impl ProductionPredictionStats {
 					this.merge(other)
 				}
 			}
+			ProductionPredictionStats::Ranking => {}
 		}
 	}
 
@@ -109,6 +116,7 @@ impl ProductionPredictionStats {
 			ProductionPredictionStats::MulticlassClassification(stats) => {
 				ProductionPredictionStatsOutput::MulticlassClassification(stats.finalize())
 			}
+			ProductionPredictionStats::Ranking => ProductionPredictionStatsOutput::Ranking,
 		}
 	}
 }
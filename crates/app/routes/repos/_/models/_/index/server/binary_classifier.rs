@@ -1,4 +1,6 @@
-use crate::common::{FeatureImportancesSection, TrainingSummarySection};
+use crate::common::{
+	FeatureImportancesSection, FeatureInteractionsSection, TrainingSummarySection,
+};
 use modelfox_app_ui::metrics_row::MetricsRow;
 use modelfox_charts::{
 	components::LineChart,
@@ -11,6 +13,7 @@ use pinwheel::prelude::*;
 
 pub struct BinaryClassifier {
 	pub feature_importances_section: Option<FeatureImportancesSection>,
+	pub feature_interactions_section: Option<FeatureInteractionsSection>,
 	pub id: String,
 	pub training_metrics_section: BinaryClassifierMetricsSection,
 	pub training_summary_section: TrainingSummarySection,
@@ -29,6 +32,7 @@ impl Component for BinaryClassifier {
 			.child(self.training_summary_section)
 			.child(self.training_metrics_section)
 			.child(self.feature_importances_section)
+			.child(self.feature_interactions_section)
 			.into_node()
 	}
 }
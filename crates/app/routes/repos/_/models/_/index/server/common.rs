@@ -15,6 +15,13 @@ pub struct TrainingSummarySection {
 	pub test_row_count: usize,
 	pub comparison_row_count: usize,
 	pub overall_row_count: usize,
+	pub linear_weights: Option<LinearWeights>,
+}
+
+/// This struct counts the weights of a linear model, which can be set to exactly zero by L1 regularization.
+pub struct LinearWeights {
+	pub n_nonzero_weights: usize,
+	pub n_weights: usize,
 }
 
 impl Component for TrainingSummarySection {
@@ -37,7 +44,15 @@ impl Component for TrainingSummarySection {
 					.child(b().child(self.comparison_metric_type_name))
 					.child(" was chosen. The best model is a ")
 					.child(b().child(self.chosen_model_type_name))
-					.child("."),
+					.child(".")
+					.child(self.linear_weights.map(|linear_weights| {
+						fragment()
+							.child(" It has ")
+							.child(b().child(linear_weights.n_nonzero_weights.to_string()))
+							.child(" non-zero weights out of ")
+							.child(b().child(linear_weights.n_weights.to_string()))
+							.child(".")
+					})),
 			)
 			.into_node()
 	}
@@ -148,3 +163,50 @@ impl Component for FeatureImportancesTable {
 			.into_node()
 	}
 }
+
+pub struct FeatureInteractionsSection {
+	pub rows: Vec<FeatureInteraction>,
+}
+
+pub struct FeatureInteraction {
+	pub feature_name_a: String,
+	pub feature_name_b: String,
+	pub value: f32,
+}
+
+impl Component for FeatureInteractionsSection {
+	fn into_node(self) -> Node {
+		let description = "The table below shows the pairs of features whose effects on the model's output depend most on each other, measured by the mean absolute SHAP interaction value over the test dataset.";
+		ui::S2::new()
+			.child(ui::H2::new("Feature Interactions"))
+			.child(ui::P::new().child(description))
+			.child(
+				ui::Table::new()
+					.width("100%".to_owned())
+					.child(
+						ui::TableHeader::new()
+							.child(ui::TableHeaderCell::new().child("Feature Name"))
+							.child(ui::TableHeaderCell::new().child("Feature Name"))
+							.child(ui::TableHeaderCell::new().child("Feature Interaction Value")),
+					)
+					.child(ui::TableBody::new().children(self.rows.iter().map(
+						|feature_interaction| {
+							ui::TableRow::new()
+								.child(
+									ui::TableCell::new()
+										.child(feature_interaction.feature_name_a.clone()),
+								)
+								.child(
+									ui::TableCell::new()
+										.child(feature_interaction.feature_name_b.clone()),
+								)
+								.child(
+									ui::TableCell::new()
+										.child(ui::format_float(feature_interaction.value)),
+								)
+						},
+					))),
+			)
+			.into_node()
+	}
+}
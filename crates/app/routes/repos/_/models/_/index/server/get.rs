@@ -1,9 +1,12 @@
 use crate::{
-	common::{FeatureImportance, FeatureImportancesSection, TrainingSummarySection},
+	common::{
+		FeatureImportance, FeatureImportancesSection, FeatureInteraction,
+		FeatureInteractionsSection, LinearWeights, TrainingSummarySection,
+	},
 	page::{
 		BinaryClassifier, BinaryClassifierMetricsSection, Inner, MulticlassClassifier,
-		MulticlassClassifierClassMetrics, MulticlassClassifierMetricsSection, Page, Regressor,
-		RegressorMetricsSection,
+		MulticlassClassifierClassMetrics, MulticlassClassifierMetricsSection, Page, Ranker,
+		RankerMetricsSection, Regressor, RegressorMetricsSection,
 	},
 };
 use anyhow::{bail, Result};
@@ -54,6 +57,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	let model = modelfox_model::from_bytes(&bytes)?;
 	let summary_section = compute_summary_section(model);
 	let feature_importances_section = compute_feature_importances_section(model);
+	let feature_interactions_section = compute_feature_interactions_section(model);
 	let inner = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => {
 			let regressor = regressor.read();
@@ -83,6 +87,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				},
 				training_summary_section: summary_section,
 				feature_importances_section,
+				feature_interactions_section,
 				warning,
 			})
 		}
@@ -122,6 +127,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				},
 				training_summary_section: summary_section,
 				feature_importances_section,
+				feature_interactions_section,
 			})
 		}
 		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
@@ -163,6 +169,33 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				},
 				training_summary_section: summary_section,
 				feature_importances_section,
+				feature_interactions_section,
+				warning,
+			})
+		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			let ranker = ranker.read();
+			let warning = if ranker.baseline_metrics().ndcg() > ranker.test_metrics().ndcg() {
+				Some("Baseline NDCG is higher! Your model performs worse than if it kept the examples in each query group in their original order.".into())
+			} else {
+				None
+			};
+			let losses_chart_series = match ranker.model() {
+				modelfox_model::RankingModelReader::Tree(model) => {
+					let model = model.read();
+					model.losses().map(|losses| losses.iter().collect())
+				}
+			};
+			Inner::Ranker(Ranker {
+				id: model_id.to_string(),
+				training_metrics_section: RankerMetricsSection {
+					ndcg: ranker.test_metrics().ndcg(),
+					baseline_ndcg: ranker.baseline_metrics().ndcg(),
+					losses_chart_series,
+				},
+				training_summary_section: summary_section,
+				feature_importances_section,
+				feature_interactions_section,
 				warning,
 			})
 		}
@@ -185,6 +218,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 
 fn compute_summary_section(model: modelfox_model::ModelReader) -> TrainingSummarySection {
 	let chosen_model_type_name = model_type_name(model);
+	let linear_weights = compute_linear_weights(model);
 	match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => {
 			let regressor = regressor.read();
@@ -200,6 +234,7 @@ fn compute_summary_section(model: modelfox_model::ModelReader) -> TrainingSummar
 					- regressor.train_row_count().to_usize().unwrap()
 					- regressor.test_row_count().to_usize().unwrap(),
 				overall_row_count: regressor.overall_row_count().to_usize().unwrap(),
+				linear_weights,
 			}
 		}
 		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
@@ -216,6 +251,7 @@ fn compute_summary_section(model: modelfox_model::ModelReader) -> TrainingSummar
 					- binary_classifier.train_row_count().to_usize().unwrap()
 					- binary_classifier.test_row_count().to_usize().unwrap(),
 				overall_row_count: binary_classifier.overall_row_count().to_usize().unwrap(),
+				linear_weights,
 			}
 		}
 		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
@@ -242,11 +278,66 @@ fn compute_summary_section(model: modelfox_model::ModelReader) -> TrainingSummar
 					.overall_row_count()
 					.to_usize()
 					.unwrap(),
+				linear_weights,
+			}
+		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			let ranker = ranker.read();
+			TrainingSummarySection {
+				chosen_model_type_name,
+				column_count: ranker.overall_column_stats().len() + 1,
+				comparison_metric_type_name: ranking_comparison_type_name(
+					&ranker.comparison_metric(),
+				),
+				train_row_count: ranker.train_row_count().to_usize().unwrap(),
+				test_row_count: ranker.test_row_count().to_usize().unwrap(),
+				comparison_row_count: ranker.overall_row_count().to_usize().unwrap()
+					- ranker.train_row_count().to_usize().unwrap()
+					- ranker.test_row_count().to_usize().unwrap(),
+				overall_row_count: ranker.overall_row_count().to_usize().unwrap(),
+				linear_weights,
 			}
 		}
 	}
 }
 
+fn compute_linear_weights(model: modelfox_model::ModelReader) -> Option<LinearWeights> {
+	let (n_nonzero_weights, n_weights) = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => match regressor.read().model() {
+			modelfox_model::RegressionModelReader::Linear(model) => {
+				let model = modelfox_linear::Regressor::from_reader(model.read().model());
+				(model.n_nonzero_weights(), model.weights.len())
+			}
+			modelfox_model::RegressionModelReader::Tree(_) => return None,
+		},
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			match binary_classifier.read().model() {
+				modelfox_model::BinaryClassificationModelReader::Linear(model) => {
+					let model =
+						modelfox_linear::BinaryClassifier::from_reader(model.read().model());
+					(model.n_nonzero_weights(), model.weights.len())
+				}
+				modelfox_model::BinaryClassificationModelReader::Tree(_) => return None,
+			}
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			match multiclass_classifier.read().model() {
+				modelfox_model::MulticlassClassificationModelReader::Linear(model) => {
+					let model =
+						modelfox_linear::MulticlassClassifier::from_reader(model.read().model());
+					(model.n_nonzero_weights(), model.weights.len())
+				}
+				modelfox_model::MulticlassClassificationModelReader::Tree(_) => return None,
+			}
+		}
+		modelfox_model::ModelInnerReader::Ranker(_) => return None,
+	};
+	Some(LinearWeights {
+		n_nonzero_weights,
+		n_weights,
+	})
+}
+
 fn regression_comparison_type_name(
 	comparison_metric: &modelfox_model::RegressionComparisonMetricReader,
 ) -> String {
@@ -284,6 +375,19 @@ fn multiclass_classification_comparison_type_name(
 	}
 }
 
+fn ranking_comparison_type_name(
+	comparison_metric: &modelfox_model::RankingComparisonMetricReader,
+) -> String {
+	match comparison_metric {
+		modelfox_model::RankingComparisonMetricReader::Ndcg(_) => {
+			"Normalized Discounted Cumulative Gain".to_owned()
+		}
+		modelfox_model::RankingComparisonMetricReader::Map(_) => {
+			"Mean Average Precision".to_owned()
+		}
+	}
+}
+
 fn model_type_name(model: modelfox_model::ModelReader) -> String {
 	match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => match regressor.read().model() {
@@ -310,6 +414,11 @@ fn model_type_name(model: modelfox_model::ModelReader) -> String {
 				}
 			}
 		}
+		modelfox_model::ModelInnerReader::Ranker(model) => match model.read().model() {
+			modelfox_model::RankingModelReader::Tree(_) => {
+				"Gradient Boosted Tree Ranker".to_owned()
+			}
+		},
 	}
 }
 
@@ -326,6 +435,9 @@ fn compute_feature_importances_section(
 		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
 			multiclass_classifier.read().overall_column_stats().len()
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			ranker.read().overall_column_stats().len()
+		}
 	};
 	let (mut feature_importances, n_features) = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => match regressor.read().model() {
@@ -488,6 +600,33 @@ fn compute_feature_importances_section(
 				}
 			}
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => match ranker.read().model() {
+			modelfox_model::RankingModelReader::Tree(inner_model) => {
+				let inner_model = inner_model.read();
+				let feature_names = compute_feature_names(inner_model.feature_groups().iter());
+				let feature_importance_values = inner_model
+					.feature_importances()
+					.iter()
+					.map(|value| Finite::new(value).ok())
+					.collect::<Option<Vec<FiniteF32>>>()?;
+				let mut feature_importances = zip!(feature_names, feature_importance_values)
+					.map(
+						|(feature_name, feature_importance_value)| FeatureImportance {
+							feature_importance_value,
+							feature_name,
+						},
+					)
+					.collect::<Vec<_>>();
+				feature_importances.sort_by(|a, b| {
+					a.feature_importance_value
+						.partial_cmp(&b.feature_importance_value)
+						.unwrap()
+						.reverse()
+				});
+				let n_features = feature_importances.len();
+				(feature_importances, n_features)
+			}
+		},
 	};
 	let feature_importances_table_rows = feature_importances
 		.iter()
@@ -507,6 +646,37 @@ fn compute_feature_importances_section(
 	})
 }
 
+fn compute_feature_interactions_section(
+	model: modelfox_model::ModelReader,
+) -> Option<FeatureInteractionsSection> {
+	let explanations = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			regressor.read().explanations().flatten()
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			binary_classifier.read().explanations().flatten()
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			multiclass_classifier.read().explanations().flatten()
+		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => ranker.read().explanations().flatten(),
+	}?;
+	let rows = explanations
+		.feature_interactions()
+		.flatten()?
+		.iter()
+		.map(|feature_interaction| FeatureInteraction {
+			feature_name_a: feature_interaction.feature_name_a().to_owned(),
+			feature_name_b: feature_interaction.feature_name_b().to_owned(),
+			value: feature_interaction.value(),
+		})
+		.collect::<Vec<_>>();
+	if rows.is_empty() {
+		return None;
+	}
+	Some(FeatureInteractionsSection { rows })
+}
+
 fn compute_feature_names<'a>(
 	feature_groups: impl Iterator<Item = modelfox_model::FeatureGroupReader<'a>>,
 ) -> Vec<String> {
@@ -514,11 +684,21 @@ fn compute_feature_names<'a>(
 		.flat_map(|feature_group| match feature_group {
 			modelfox_model::FeatureGroupReader::Identity(feature_group) => {
 				let feature_group = feature_group.read();
-				vec![feature_group.source_column_name().to_owned()]
+				let mut feature_names = vec![feature_group.source_column_name().to_owned()];
+				if feature_group.missing_indicator().unwrap_or(false) {
+					feature_names
+						.push(format!("{} is missing", feature_group.source_column_name()));
+				}
+				feature_names
 			}
 			modelfox_model::FeatureGroupReader::Normalized(feature_group) => {
 				let feature_group = feature_group.read();
-				vec![feature_group.source_column_name().to_owned()]
+				let mut feature_names = vec![feature_group.source_column_name().to_owned()];
+				if feature_group.missing_indicator().unwrap_or(false) {
+					feature_names
+						.push(format!("{} is missing", feature_group.source_column_name()));
+				}
+				feature_names
 			}
 			modelfox_model::FeatureGroupReader::OneHotEncoded(feature_group) => {
 				let feature_group = feature_group.read();
@@ -558,6 +738,123 @@ fn compute_feature_names<'a>(
 					})
 					.collect()
 			}
+			modelfox_model::FeatureGroupReader::HashedBagOfWords(feature_group) => {
+				let feature_group = feature_group.read();
+				feature_group
+					.buckets()
+					.iter()
+					.enumerate()
+					.map(|(bucket, entry)| {
+						let ngrams = entry
+							.ngrams()
+							.iter()
+							.map(|ngram| ngram.to_string())
+							.collect::<Vec<_>>();
+						hashed_feature_name(
+							feature_group.source_column_name(),
+							"contains",
+							bucket,
+							&ngrams,
+						)
+					})
+					.collect()
+			}
+			modelfox_model::FeatureGroupReader::HashedEnum(feature_group) => {
+				let feature_group = feature_group.read();
+				feature_group
+					.buckets()
+					.iter()
+					.enumerate()
+					.map(|(bucket, entry)| {
+						let values = entry
+							.values()
+							.iter()
+							.map(|value| value.to_owned())
+							.collect::<Vec<_>>();
+						hashed_feature_name(
+							feature_group.source_column_name(),
+							"=",
+							bucket,
+							&values,
+						)
+					})
+					.collect()
+			}
+			modelfox_model::FeatureGroupReader::Interaction(feature_group) => {
+				let feature_group = feature_group.read();
+				let feature_names_a = interaction_source_feature_names(feature_group.source_a());
+				let feature_names_b = interaction_source_feature_names(feature_group.source_b());
+				feature_names_a
+					.iter()
+					.flat_map(|feature_name_a| {
+						feature_names_b.iter().map(move |feature_name_b| {
+							format!("{} * {}", feature_name_a, feature_name_b)
+						})
+					})
+					.collect()
+			}
+			modelfox_model::FeatureGroupReader::Polynomial(feature_group) => {
+				let feature_group = feature_group.read();
+				(2..=feature_group.degree())
+					.map(|power| format!("{} ^ {}", feature_group.source_column_name(), power))
+					.collect()
+			}
+			modelfox_model::FeatureGroupReader::BinnedNumber(feature_group) => {
+				let feature_group = feature_group.read();
+				let source_column_name = feature_group.source_column_name();
+				let thresholds = feature_group.thresholds().iter().collect::<Vec<_>>();
+				(0..thresholds.len() + 1)
+					.map(|bin| {
+						let start = bin.checked_sub(1).map(|index| thresholds[index]);
+						let end = thresholds.get(bin);
+						match (start, end) {
+							(None, None) => source_column_name.to_owned(),
+							(None, Some(end)) => format!("{} < {}", source_column_name, end),
+							(Some(start), None) => format!("{} >= {}", source_column_name, start),
+							(Some(start), Some(end)) => {
+								format!("{} <= {} < {}", start, source_column_name, end)
+							}
+						}
+					})
+					.collect()
+			}
 		})
 		.collect()
 }
+
+fn interaction_source_feature_names(
+	source: modelfox_model::InteractionFeatureGroupSourceReader,
+) -> Vec<String> {
+	match source {
+		modelfox_model::InteractionFeatureGroupSourceReader::Normalized(feature_group) => {
+			vec![feature_group.read().source_column_name().to_owned()]
+		}
+		modelfox_model::InteractionFeatureGroupSourceReader::OneHotEncoded(feature_group) => {
+			let feature_group = feature_group.read();
+			vec!["OOV"]
+				.into_iter()
+				.chain(feature_group.variants().iter())
+				.map(|variant| format!("{} = {}", feature_group.source_column_name(), variant))
+				.collect()
+		}
+	}
+}
+
+fn hashed_feature_name(
+	source_column_name: &str,
+	relation: &str,
+	bucket: usize,
+	values: &[String],
+) -> String {
+	if values.is_empty() {
+		format!("{} bucket {}", source_column_name, bucket)
+	} else {
+		format!(
+			"{} {} {} (bucket {})",
+			source_column_name,
+			relation,
+			values.join(" | "),
+			bucket
+		)
+	}
+}
@@ -3,6 +3,7 @@ mod common;
 mod get;
 mod multiclass_classifier;
 mod page;
+mod ranker;
 mod regressor;
 
 use futures::FutureExt;
@@ -1,4 +1,6 @@
-use crate::common::{FeatureImportancesSection, TrainingSummarySection};
+use crate::common::{
+	FeatureImportancesSection, FeatureInteractionsSection, TrainingSummarySection,
+};
 use modelfox_app_ui::colors::{BASELINE_COLOR, TRAINING_COLOR};
 use modelfox_charts::{
 	components::LineChart,
@@ -15,6 +17,7 @@ pub struct MulticlassClassifier {
 	pub training_summary_section: TrainingSummarySection,
 	pub training_metrics_section: MulticlassClassifierMetricsSection,
 	pub feature_importances_section: Option<FeatureImportancesSection>,
+	pub feature_interactions_section: Option<FeatureInteractionsSection>,
 }
 
 impl Component for MulticlassClassifier {
@@ -29,6 +32,7 @@ impl Component for MulticlassClassifier {
 			.child(self.training_summary_section)
 			.child(self.training_metrics_section)
 			.child(self.feature_importances_section)
+			.child(self.feature_interactions_section)
 			.into_node()
 	}
 }
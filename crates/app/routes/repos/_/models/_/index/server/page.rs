@@ -3,6 +3,7 @@ pub use crate::{
 	multiclass_classifier::{
 		MulticlassClassifier, MulticlassClassifierClassMetrics, MulticlassClassifierMetricsSection,
 	},
+	ranker::{Ranker, RankerMetricsSection},
 	regressor::{Regressor, RegressorMetricsSection},
 };
 use modelfox_app_layouts::document::Document;
@@ -19,6 +20,7 @@ pub enum Inner {
 	Regressor(Regressor),
 	BinaryClassifier(BinaryClassifier),
 	MulticlassClassifier(MulticlassClassifier),
+	Ranker(Ranker),
 }
 
 impl Component for Page {
@@ -27,6 +29,7 @@ impl Component for Page {
 			Inner::Regressor(inner) => inner.into_node(),
 			Inner::BinaryClassifier(inner) => inner.into_node(),
 			Inner::MulticlassClassifier(inner) => inner.into_node(),
+			Inner::Ranker(inner) => inner.into_node(),
 		};
 		Document::new()
 			.client("modelfox_app_model_index_client")
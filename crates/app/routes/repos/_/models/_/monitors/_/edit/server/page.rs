@@ -34,6 +34,8 @@ impl Component for Page {
 					value: "rmse".to_owned(),
 				},
 			],
+			// Production metrics are not computed for rankers, so there are no metrics to monitor.
+			AlertModelType::Ranker => Vec::new(),
 		};
 		let email = self
 			.monitor
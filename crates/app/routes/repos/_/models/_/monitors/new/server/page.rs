@@ -30,6 +30,8 @@ impl Component for Page {
 					value: "rmse".to_owned(),
 				},
 			],
+			// Production metrics are not computed for rankers, so there are no metrics to monitor.
+			AlertModelType::Ranker => Vec::new(),
 		};
 		Document::new()
 			.client("modelfox_app_new_monitor_client")
@@ -11,7 +11,7 @@ use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
 use modelfox_app_ui::predict::{
 	compute_feature_contributions_chart_series, compute_input_table,
 	BinaryClassificationPredictOutput, MulticlassClassificationPredictOutput, PredictOutput,
-	PredictOutputInner, RegressionPredictOutput,
+	PredictOutputInner, RankingPredictOutput, RegressionPredictOutput,
 };
 use modelfox_core::predict::{PredictInputValue, PredictOptions};
 use modelfox_id::Id;
@@ -136,6 +136,17 @@ fn compute_predict_output(
 				probability: output.probability,
 			})
 		}
+		modelfox_core::predict::PredictOutput::Ranking(output) => {
+			let feature_contributions = output.feature_contributions.unwrap();
+			let feature_contributions_chart_series = compute_feature_contributions_chart_series(
+				"output".to_owned(),
+				feature_contributions,
+			);
+			PredictOutputInner::Ranking(RankingPredictOutput {
+				feature_contributions_chart_series,
+				score: output.score,
+			})
+		}
 	};
 	PredictOutput { inner, input_table }
 }
@@ -171,6 +182,10 @@ fn compute_form(
 			let multiclass_classifier = multiclass_classifier.read();
 			multiclass_classifier.overall_column_stats()
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			let ranker = ranker.read();
+			ranker.overall_column_stats()
+		}
 	};
 	let fields = column_stats
 		.iter()
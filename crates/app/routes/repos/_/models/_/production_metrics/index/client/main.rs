@@ -4,9 +4,14 @@ use web_sys as dom;
 
 pub fn main() {
 	modelfox_ui::client_start();
-	ui::select_field_submit_on_change("date_window_select_field".to_owned());
 	let window = dom::window().unwrap();
 	let document = window.document().unwrap();
+	if document
+		.get_element_by_id("date_window_select_field")
+		.is_some()
+	{
+		ui::select_field_submit_on_change("date_window_select_field".to_owned());
+	}
 	if document.get_element_by_id("mse").is_some() {
 		hydrate::<modelfox_charts::components::LineChart>("mse");
 	}
@@ -2,8 +2,9 @@ use crate::page::{
 	AccuracyChart, AccuracyChartEntry, BinaryClassificationOverallProductionMetrics,
 	BinaryClassifierProductionMetrics, ClassMetricsTableEntry, Inner, MeanSquaredErrorChart,
 	MeanSquaredErrorChartEntry, MulticlassClassificationOverallProductionMetrics,
-	MulticlassClassifierProductionMetrics, Page, RegressionProductionMetrics,
-	RegressorProductionMetrics, TrainingProductionMetrics, TrueValuesCountChartEntry,
+	MulticlassClassifierProductionMetrics, Page, RankerProductionMetrics,
+	RegressionProductionMetrics, RegressorProductionMetrics, TrainingProductionMetrics,
+	TrueValuesCountChartEntry,
 };
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
@@ -343,6 +344,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				overall,
 			})
 		}
+		modelfox_model::ModelInnerReader::Ranker(_) => Inner::Ranker(RankerProductionMetrics),
 	};
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::ProductionMetrics).await?;
@@ -1,4 +1,4 @@
-pub use crate::{binary_classifier::*, multiclass_classifier::*, regressor::*};
+pub use crate::{binary_classifier::*, multiclass_classifier::*, ranker::*, regressor::*};
 use modelfox_app_layouts::{
 	document::Document,
 	model_layout::{ModelLayout, ModelLayoutInfo},
@@ -15,6 +15,7 @@ pub enum Inner {
 	Regressor(RegressorProductionMetrics),
 	BinaryClassifier(BinaryClassifierProductionMetrics),
 	MulticlassClassifier(MulticlassClassifierProductionMetrics),
+	Ranker(RankerProductionMetrics),
 }
 
 pub struct TrueValuesCountChartEntry {
@@ -49,6 +50,7 @@ impl Component for Page {
 			Inner::Regressor(inner) => inner.into_node(),
 			Inner::BinaryClassifier(inner) => inner.into_node(),
 			Inner::MulticlassClassifier(inner) => inner.into_node(),
+			Inner::Ranker(inner) => inner.into_node(),
 		};
 		Document::new()
 			.client("modelfox_app_production_metrics_index_client")
@@ -0,0 +1,13 @@
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+pub struct RankerProductionMetrics;
+
+impl Component for RankerProductionMetrics {
+	fn into_node(self) -> Node {
+		ui::S1::new()
+			.child(ui::H1::new("Production Metrics"))
+			.child(ui::P::new().child("Production metrics are not computed for rankers. Ranking metrics like NDCG can only be computed over whole query groups, and true values are logged one prediction at a time."))
+			.into_node()
+	}
+}
@@ -184,6 +184,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				PredictOutput::Regression(output) => output.value.to_string(),
 				PredictOutput::BinaryClassification(output) => output.class_name,
 				PredictOutput::MulticlassClassification(output) => output.class_name,
+				PredictOutput::Ranking(output) => output.score.to_string(),
 			};
 			PredictionTableRow {
 				id,
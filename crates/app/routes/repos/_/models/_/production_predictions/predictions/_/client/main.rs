@@ -32,4 +32,12 @@ pub fn main() {
 			"multiclass_classification_feature_contributions",
 		);
 	}
+	if document
+		.get_element_by_id("ranking_feature_contributions")
+		.is_some()
+	{
+		hydrate::<modelfox_charts::components::FeatureContributionsChart>(
+			"ranking_feature_contributions",
+		);
+	}
 }
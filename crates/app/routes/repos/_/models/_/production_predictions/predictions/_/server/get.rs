@@ -14,7 +14,7 @@ use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
 use modelfox_app_ui::predict::{
 	compute_feature_contributions_chart_series, compute_input_table,
 	BinaryClassificationPredictOutput, MulticlassClassificationPredictOutput, PredictOutput,
-	PredictOutputInner, RegressionPredictOutput,
+	PredictOutputInner, RankingPredictOutput, RegressionPredictOutput,
 };
 use modelfox_core::predict::{PredictInput, PredictOptions};
 use modelfox_id::Id;
@@ -132,6 +132,17 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				probability: output.probability,
 			})
 		}
+		modelfox_core::predict::PredictOutput::Ranking(output) => {
+			let feature_contributions = output.feature_contributions.unwrap();
+			let feature_contributions_chart_series = compute_feature_contributions_chart_series(
+				"output".to_owned(),
+				feature_contributions,
+			);
+			PredictOutputInner::Ranking(RankingPredictOutput {
+				feature_contributions_chart_series,
+				score: output.score,
+			})
+		}
 	};
 	let page = Page {
 		id,
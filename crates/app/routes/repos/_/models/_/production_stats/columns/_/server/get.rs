@@ -96,6 +96,10 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			let multiclass_classifier = multiclass_classifier.read();
 			multiclass_classifier.overall_row_count()
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			let ranker = ranker.read();
+			ranker.overall_row_count()
+		}
 	};
 	let overall_column_stats = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => {
@@ -110,6 +114,10 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 			let multiclass_classifier = multiclass_classifier.read();
 			multiclass_classifier.overall_column_stats()
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			let ranker = ranker.read();
+			ranker.overall_column_stats()
+		}
 	};
 	let train_column_stats = overall_column_stats
 		.iter()
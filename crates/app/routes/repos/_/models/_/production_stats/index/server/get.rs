@@ -3,7 +3,7 @@ use crate::{
 	page::{
 		BinaryClassifier, ClassifierChartEntry, Inner, MulticlassClassifier, Page,
 		PredictionCountChartEntry, ProductionTrainingHistogram, ProductionTrainingQuantiles,
-		Quantiles, Regressor, RegressorChartEntry,
+		Quantiles, Ranker, Regressor, RegressorChartEntry,
 	},
 };
 use anyhow::{bail, Result};
@@ -108,6 +108,12 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				search_params,
 			))
 		}
+		ProductionPredictionStatsOutput::Ranking => Inner::Ranker(compute_ranker(
+			production_stats,
+			date_window,
+			date_window_interval,
+			timezone,
+		)),
 	};
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::ProductionStats).await?;
@@ -419,6 +425,36 @@ fn compute_multiclass_classifier(
 	}
 }
 
+fn compute_ranker(
+	production_stats: GetProductionStatsOutput,
+	date_window: DateWindow,
+	date_window_interval: DateWindowInterval,
+	timezone: Tz,
+) -> Ranker {
+	let prediction_count_chart = production_stats
+		.intervals
+		.iter()
+		.map(|interval| PredictionCountChartEntry {
+			count: interval.row_count,
+			label: format_date_window_interval(
+				interval.start_date,
+				&date_window_interval,
+				timezone,
+			),
+		})
+		.collect::<Vec<_>>();
+	let overall_column_stats_table = compute_overall_column_stats_table(
+		production_stats.overall.column_stats,
+		production_stats.overall.row_count,
+	);
+	Ranker {
+		date_window,
+		date_window_interval,
+		prediction_count_chart,
+		overall_column_stats_table,
+	}
+}
+
 fn alert_message(count: u64, absent_count: u64, invalid_count: u64) -> Option<String> {
 	let invalid_ratio = invalid_count.to_f32().unwrap() / count.to_f32().unwrap();
 	let absent_ratio = absent_count.to_f32().unwrap() / count.to_f32().unwrap();
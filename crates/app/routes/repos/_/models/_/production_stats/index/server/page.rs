@@ -2,6 +2,7 @@ pub use crate::{
 	binary_classifier::*,
 	common::{ClassifierChartEntry, PredictionCountChartEntry, ProductionTrainingHistogram},
 	multiclass_classifier::*,
+	ranker::*,
 	regressor::*,
 };
 use modelfox_app_layouts::{
@@ -20,6 +21,7 @@ pub enum Inner {
 	Regressor(Regressor),
 	BinaryClassifier(BinaryClassifier),
 	MulticlassClassifier(MulticlassClassifier),
+	Ranker(Ranker),
 }
 
 impl Component for Page {
@@ -28,6 +30,7 @@ impl Component for Page {
 			Inner::Regressor(inner) => inner.into_node(),
 			Inner::BinaryClassifier(inner) => inner.into_node(),
 			Inner::MulticlassClassifier(inner) => inner.into_node(),
+			Inner::Ranker(inner) => inner.into_node(),
 		};
 		Document::new()
 			.client("modelfox_app_production_stats_index_client")
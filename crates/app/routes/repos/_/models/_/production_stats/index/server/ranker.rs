@@ -0,0 +1,29 @@
+use crate::common::{
+	ColumnStatsTable, DateWindowSelectForm, PredictionCountChart, PredictionCountChartEntry,
+};
+use modelfox_app_date_window::{DateWindow, DateWindowInterval};
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+pub struct Ranker {
+	pub date_window: DateWindow,
+	pub date_window_interval: DateWindowInterval,
+	pub prediction_count_chart: Vec<PredictionCountChartEntry>,
+	pub overall_column_stats_table: ColumnStatsTable,
+}
+
+impl Component for Ranker {
+	fn into_node(self) -> Node {
+		ui::S1::new()
+			.child(ui::H1::new("Production Stats"))
+			.child(DateWindowSelectForm {
+				date_window: self.date_window,
+			})
+			.child(ui::Card::new().child(PredictionCountChart {
+				chart_data: self.prediction_count_chart,
+				date_window_interval: self.date_window_interval,
+			}))
+			.child(self.overall_column_stats_table)
+			.into_node()
+	}
+}
@@ -5,6 +5,13 @@ pub fn hyperparameters_for_grid_item(
 		modelfox_model::ModelTrainOptionsReader::Linear(hyperparameters) => {
 			let hyperparameters = hyperparameters.read();
 			vec![
+				(
+					"l1_regularization".to_owned(),
+					hyperparameters
+						.l1_regularization()
+						.map(|l1_regularization| l1_regularization.to_string())
+						.unwrap_or_else(|| "None".to_owned()),
+				),
 				(
 					"l2_regularization".to_owned(),
 					hyperparameters.l2_regularization().to_string(),
@@ -13,6 +20,20 @@ pub fn hyperparameters_for_grid_item(
 					"learning_rate".to_owned(),
 					hyperparameters.learning_rate().to_string(),
 				),
+				(
+					"learning_rate_schedule".to_owned(),
+					hyperparameters
+						.learning_rate_schedule()
+						.map(learning_rate_schedule_description)
+						.unwrap_or_else(|| "None".to_owned()),
+				),
+				(
+					"optimizer".to_owned(),
+					hyperparameters
+						.optimizer()
+						.map(optimizer_description)
+						.unwrap_or_else(|| "None".to_owned()),
+				),
 				(
 					"max_epochs".to_owned(),
 					hyperparameters.max_epochs().to_string(),
@@ -51,6 +72,20 @@ pub fn hyperparameters_for_grid_item(
 		modelfox_model::ModelTrainOptionsReader::Tree(hyperparameters) => {
 			let hyperparameters = hyperparameters.read();
 			vec![
+				(
+					"bagging_fraction".to_owned(),
+					hyperparameters
+						.bagging_fraction()
+						.map(|bagging_fraction| bagging_fraction.to_string())
+						.unwrap_or_else(|| "None".to_owned()),
+				),
+				(
+					"bagging_frequency".to_owned(),
+					hyperparameters
+						.bagging_frequency()
+						.map(|bagging_frequency| bagging_frequency.to_string())
+						.unwrap_or_else(|| "None".to_owned()),
+				),
 				(
 					"binned_features_layout".to_owned(),
 					match hyperparameters.binned_features_layout() {
@@ -87,6 +122,60 @@ pub fn hyperparameters_for_grid_item(
 						})
 						.unwrap_or_else(|| "None".to_string()),
 				),
+				(
+					"dart:drop_rate".to_owned(),
+					hyperparameters
+						.dart_options()
+						.flatten()
+						.map(|options| options.drop_rate().to_string())
+						.unwrap_or_else(|| "None".to_string()),
+				),
+				(
+					"dart:max_drop".to_owned(),
+					hyperparameters
+						.dart_options()
+						.flatten()
+						.map(|options| options.max_drop().to_string())
+						.unwrap_or_else(|| "None".to_string()),
+				),
+				(
+					"dart:skip_drop".to_owned(),
+					hyperparameters
+						.dart_options()
+						.flatten()
+						.map(|options| options.skip_drop().to_string())
+						.unwrap_or_else(|| "None".to_string()),
+				),
+				(
+					"feature_fraction_per_node".to_owned(),
+					hyperparameters
+						.feature_fraction_per_node()
+						.map(|feature_fraction| feature_fraction.to_string())
+						.unwrap_or_else(|| "None".to_owned()),
+				),
+				(
+					"feature_fraction_per_tree".to_owned(),
+					hyperparameters
+						.feature_fraction_per_tree()
+						.map(|feature_fraction| feature_fraction.to_string())
+						.unwrap_or_else(|| "None".to_owned()),
+				),
+				(
+					"goss:top_rate".to_owned(),
+					hyperparameters
+						.goss_options()
+						.flatten()
+						.map(|options| options.top_rate().to_string())
+						.unwrap_or_else(|| "None".to_string()),
+				),
+				(
+					"goss:other_rate".to_owned(),
+					hyperparameters
+						.goss_options()
+						.flatten()
+						.map(|options| options.other_rate().to_string())
+						.unwrap_or_else(|| "None".to_string()),
+				),
 				(
 					"l2_regularization_for_continuous_splits".to_owned(),
 					hyperparameters
@@ -142,6 +231,13 @@ pub fn hyperparameters_for_grid_item(
 					"min_sum_hessians_per_node".to_owned(),
 					hyperparameters.min_sum_hessians_per_node().to_string(),
 				),
+				(
+					"seed".to_owned(),
+					hyperparameters
+						.seed()
+						.map(|seed| seed.to_string())
+						.unwrap_or_else(|| "None".to_owned()),
+				),
 				(
 					"smoothing_factor_for_discrete_bin_sorting".to_owned(),
 					hyperparameters
@@ -152,3 +248,41 @@ pub fn hyperparameters_for_grid_item(
 		}
 	}
 }
+
+fn optimizer_description(optimizer: modelfox_model::LinearOptimizerReader) -> String {
+	match optimizer {
+		modelfox_model::LinearOptimizerReader::Sgd(_) => "sgd".to_owned(),
+		modelfox_model::LinearOptimizerReader::Momentum(optimizer) => {
+			format!("momentum (momentum: {})", optimizer.read().momentum())
+		}
+		modelfox_model::LinearOptimizerReader::Adam(optimizer) => {
+			let optimizer = optimizer.read();
+			format!(
+				"adam (beta_1: {}, beta_2: {}, epsilon: {})",
+				optimizer.beta_1(),
+				optimizer.beta_2(),
+				optimizer.epsilon()
+			)
+		}
+	}
+}
+
+fn learning_rate_schedule_description(
+	learning_rate_schedule: modelfox_model::LinearLearningRateScheduleReader,
+) -> String {
+	match learning_rate_schedule {
+		modelfox_model::LinearLearningRateScheduleReader::Constant(_) => "constant".to_owned(),
+		modelfox_model::LinearLearningRateScheduleReader::InverseTimeDecay(
+			learning_rate_schedule,
+		) => format!(
+			"inverse_time_decay (decay_rate: {})",
+			learning_rate_schedule.read().decay_rate()
+		),
+		modelfox_model::LinearLearningRateScheduleReader::ExponentialDecay(
+			learning_rate_schedule,
+		) => format!(
+			"exponential_decay (decay_rate: {})",
+			learning_rate_schedule.read().decay_rate()
+		),
+	}
+}
@@ -64,6 +64,13 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				.get(grid_item_index)
 				.unwrap()
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			let ranker = ranker.read();
+			ranker
+				.train_grid_item_outputs()
+				.get(grid_item_index)
+				.unwrap()
+		}
 	};
 	let model_hyperparameters = hyperparameters_for_grid_item(&grid_item);
 	let model_layout_info =
@@ -73,6 +73,10 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				}
 			}
 		}
+		modelfox_model::ModelInnerReader::Ranker(model) => match model.read().comparison_metric() {
+			modelfox_model::RankingComparisonMetricReader::Ndcg(_) => "NDCG".to_owned(),
+			modelfox_model::RankingComparisonMetricReader::Map(_) => "MAP".to_owned(),
+		},
 	};
 	let trained_models_metrics: Vec<TrainedModel> = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => regressor
@@ -104,6 +108,15 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				})
 				.collect::<Vec<_>>()
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => ranker
+			.read()
+			.train_grid_item_outputs()
+			.iter()
+			.enumerate()
+			.map(|(index, grid_item)| {
+				trained_model_metrics_for_grid_item(index.to_string(), &grid_item)
+			})
+			.collect::<Vec<_>>(),
 	};
 	let best_model_metrics_index = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => {
@@ -115,6 +128,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
 			multiclass_classifier.read().best_grid_item_index()
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => ranker.read().best_grid_item_index(),
 	};
 	let best_model_metrics =
 		trained_models_metrics[best_model_metrics_index.to_usize().unwrap()].clone();
@@ -145,6 +159,13 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				)
 				.unwrap()
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			let ranker = ranker.read();
+			ranker
+				.train_grid_item_outputs()
+				.get(ranker.best_grid_item_index().to_usize().unwrap())
+				.unwrap()
+		}
 	};
 	let best_model_hyperparameters = hyperparameters_for_grid_item(&best_model);
 	let page = Page {
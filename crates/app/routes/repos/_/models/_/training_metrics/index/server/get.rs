@@ -1,6 +1,6 @@
 use crate::page::{
 	BinaryClassifier, ClassMetrics, ConfusionMatrixSection, Inner, MulticlassClassifier, Page,
-	Regressor,
+	Ranker, Regressor,
 };
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
@@ -54,6 +54,9 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				multiclass_classifier.read(),
 			))
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			Inner::Ranker(build_inner_ranker(ranker.read()))
+		}
 	};
 	let model_layout_info =
 		model_layout_info(&mut db, app, model_id, ModelNavItem::TrainingMetrics).await?;
@@ -157,3 +160,18 @@ fn build_inner_multiclass_classifier(
 		classes,
 	}
 }
+
+fn build_inner_ranker(model: modelfox_model::RankerReader) -> Ranker {
+	let warning = if model.baseline_metrics().ndcg() > model.test_metrics().ndcg() {
+		Some("Baseline NDCG is higher! Your model performs worse than if it kept the examples in each query group in their original order.".into())
+	} else {
+		None
+	};
+	Ranker {
+		warning,
+		ndcg: model.test_metrics().ndcg(),
+		baseline_ndcg: model.baseline_metrics().ndcg(),
+		map: model.test_metrics().map(),
+		baseline_map: model.baseline_metrics().map(),
+	}
+}
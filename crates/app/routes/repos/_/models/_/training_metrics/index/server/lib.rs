@@ -5,6 +5,7 @@ mod binary_classifier;
 mod get;
 mod multiclass_classifier;
 mod page;
+mod ranker;
 mod regressor;
 
 pub fn init() -> sunfish::Route {
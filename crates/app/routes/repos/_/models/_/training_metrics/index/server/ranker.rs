@@ -0,0 +1,44 @@
+use modelfox_app_ui::colors::{BASELINE_COLOR, TRAINING_COLOR};
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+pub struct Ranker {
+	pub warning: Option<String>,
+	pub baseline_ndcg: f32,
+	pub baseline_map: f32,
+	pub ndcg: f32,
+	pub map: f32,
+}
+
+impl Component for Ranker {
+	fn into_node(self) -> Node {
+		let ndcg_description = "The Normalized Discounted Cumulative Gain (NDCG) measures how close the order of the examples ranked by the model is to the ideal order, averaged over the query groups. A perfect model has an NDCG of 1 because it always ranks the most relevant examples first.";
+		let map_description = "The Mean Average Precision (MAP) is the mean over the query groups of the average precision at each relevant example, where an example is relevant if its target is greater than 0. A perfect model has a MAP of 1 because it always ranks the relevant examples before the others.";
+		ui::S1::new()
+			.child(ui::H1::new("Training Metrics"))
+			.child(
+				ui::S2::new()
+					.child(ui::P::new().child(ndcg_description))
+					.child(
+						ui::NumberComparisonCard::new(Some(self.baseline_ndcg), Some(self.ndcg))
+							.color_a(BASELINE_COLOR.to_owned())
+							.color_b(TRAINING_COLOR.to_owned())
+							.title("NDCG".to_owned())
+							.value_a_title("Baseline".to_owned())
+							.value_b_title("Training".to_owned())
+							.number_formatter(ui::NumberFormatter::float_default()),
+					)
+					.child(ui::P::new().child(map_description))
+					.child(
+						ui::NumberComparisonCard::new(Some(self.baseline_map), Some(self.map))
+							.color_a(BASELINE_COLOR.to_owned())
+							.color_b(TRAINING_COLOR.to_owned())
+							.title("MAP".to_owned())
+							.value_a_title("Baseline".to_owned())
+							.value_b_title("Training".to_owned())
+							.number_formatter(ui::NumberFormatter::float_default()),
+					),
+			)
+			.into_node()
+	}
+}
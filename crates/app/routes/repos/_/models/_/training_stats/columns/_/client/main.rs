@@ -17,4 +17,11 @@ pub fn main() {
 	if document.get_element_by_id("ngram_histogram").is_some() {
 		hydrate::<modelfox_charts::components::BarChart>("ngram_histogram");
 	}
+	for index in 0.. {
+		let id = format!("partial_dependence_{}", index);
+		if document.get_element_by_id(&id).is_none() {
+			break;
+		}
+		hydrate::<modelfox_charts::components::LineChart>(&id);
+	}
 }
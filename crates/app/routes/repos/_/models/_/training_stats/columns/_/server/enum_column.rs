@@ -1,3 +1,4 @@
+use crate::{explanations::ColumnExplanations, imputation::ColumnImputation};
 use modelfox_app_ui::metrics_row::MetricsRow;
 use modelfox_charts::{
 	bar_chart::{BarChartPoint, BarChartSeries},
@@ -13,6 +14,8 @@ pub struct EnumColumn {
 	pub invalid_count: u64,
 	pub name: String,
 	pub unique_count: u64,
+	pub imputation: Option<ColumnImputation>,
+	pub explanations: Option<ColumnExplanations>,
 }
 
 impl Component for EnumColumn {
@@ -47,6 +50,8 @@ impl Component for EnumColumn {
 							}),
 					),
 			)
+			.child(self.imputation)
+			.child(self.explanations)
 			.into_node()
 	}
 }
@@ -0,0 +1,116 @@
+use modelfox_app_ui::metrics_row::MetricsRow;
+use modelfox_charts::{
+	components::LineChart,
+	line_chart::{LineChartPoint, LineChartSeries, LineStyle, PointStyle},
+};
+use modelfox_finite::Finite;
+use modelfox_ui as ui;
+use num::ToPrimitive;
+use pinwheel::prelude::*;
+
+pub struct ColumnExplanations {
+	pub name: String,
+	pub shap_importance: Option<f32>,
+	pub partial_dependences: Vec<PartialDependenceChart>,
+}
+
+pub struct PartialDependenceChart {
+	pub title: String,
+	pub y_axis_title: String,
+	pub grid: PartialDependenceChartGrid,
+	pub average: Vec<f32>,
+	pub individual: Vec<Vec<f32>>,
+}
+
+pub enum PartialDependenceChartGrid {
+	Number(Vec<f32>),
+	Enum(Vec<String>),
+}
+
+impl Component for ColumnExplanations {
+	fn into_node(self) -> Node {
+		let shap_importance_description = "The SHAP importance is the average absolute amount that the features computed from this column change the model's output, measured on the test dataset. Columns with higher importance have more influence on the model's predictions.";
+		let partial_dependence_description = "The partial dependence chart shows how the model's output changes as the value of this column changes while every other column is held fixed, averaged over examples from the test dataset. The gray lines show the same curve for individual examples.";
+		let name = self.name;
+		ui::S2::new()
+			.child(ui::H2::new("Model Explanations"))
+			.child(ui::P::new().child(shap_importance_description))
+			.child(self.shap_importance.map(|shap_importance| {
+				MetricsRow::new().child(ui::NumberCard::new(
+					"SHAP Importance".to_owned(),
+					ui::format_float(shap_importance),
+				))
+			}))
+			.child(if self.partial_dependences.is_empty() {
+				None
+			} else {
+				Some(ui::P::new().child(partial_dependence_description))
+			})
+			.children(self.partial_dependences.into_iter().enumerate().map(
+				|(index, partial_dependence)| {
+					partial_dependence_chart(index, &name, partial_dependence)
+				},
+			))
+			.into_node()
+	}
+}
+
+fn partial_dependence_chart(
+	index: usize,
+	name: &str,
+	partial_dependence: PartialDependenceChart,
+) -> ui::Card {
+	// Enum grids are drawn at evenly spaced positions and labeled with their variants.
+	let (x_values, labels) = match partial_dependence.grid {
+		PartialDependenceChartGrid::Number(values) => (
+			values
+				.iter()
+				.map(|value| value.to_f64().unwrap())
+				.collect::<Vec<_>>(),
+			None,
+		),
+		PartialDependenceChartGrid::Enum(variants) => (
+			(0..variants.len())
+				.map(|index| index.to_f64().unwrap())
+				.collect::<Vec<_>>(),
+			Some(variants),
+		),
+	};
+	let series_data = |values: &[f32]| -> Vec<LineChartPoint> {
+		x_values
+			.iter()
+			.zip(values.iter())
+			.map(|(x, y)| LineChartPoint {
+				x: Finite::new(*x).unwrap(),
+				y: Finite::new(y.to_f64().unwrap()).ok(),
+			})
+			.collect()
+	};
+	let mut series = partial_dependence
+		.individual
+		.iter()
+		.map(|values| LineChartSeries {
+			color: ui::colors::GRAY.to_owned(),
+			data: series_data(values),
+			line_style: Some(LineStyle::Solid),
+			point_style: Some(PointStyle::Hidden),
+			title: None,
+		})
+		.collect::<Vec<_>>();
+	series.push(LineChartSeries {
+		color: ui::colors::BLUE.to_owned(),
+		data: series_data(&partial_dependence.average),
+		line_style: Some(LineStyle::Solid),
+		point_style: Some(PointStyle::Circle),
+		title: Some("Average".to_owned()),
+	});
+	ui::Card::new().child(Dehydrate::new(
+		format!("partial_dependence_{}", index),
+		LineChart::new()
+			.labels(labels)
+			.series(series)
+			.title(partial_dependence.title)
+			.x_axis_title(name.to_owned())
+			.y_axis_title(partial_dependence.y_axis_title),
+	))
+}
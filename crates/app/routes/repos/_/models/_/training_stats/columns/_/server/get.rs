@@ -1,4 +1,7 @@
-use crate::page::{EnumColumn, Inner, NGramStats, NGramsTableRow, NumberColumn, Page, TextColumn};
+use crate::page::{
+	ColumnExplanations, ColumnImputation, EnumColumn, Inner, NGramStats, NGramsTableRow,
+	NumberColumn, Page, PartialDependenceChart, PartialDependenceChartGrid, TextColumn,
+};
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
@@ -46,26 +49,83 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	}
 	let bytes = get_model_bytes(app.storage(), model_id).await?;
 	let model = modelfox_model::from_bytes(&bytes)?;
-	let (column_stats, target_column_stats) = match model.inner() {
+	let (
+		overall_row_count,
+		column_stats,
+		target_column_stats,
+		feature_groups,
+		explanations,
+		partial_dependence_output,
+	) = match model.inner() {
 		modelfox_model::ModelInnerReader::Regressor(regressor) => {
 			let regressor = regressor.read();
+			let target_column_name = regressor.target_column_name().to_owned();
+			let feature_groups = match regressor.model() {
+				modelfox_model::RegressionModelReader::Linear(model) => {
+					model.read().feature_groups()
+				}
+				modelfox_model::RegressionModelReader::Tree(model) => model.read().feature_groups(),
+			};
 			(
+				regressor.overall_row_count(),
 				regressor.overall_column_stats(),
 				regressor.overall_target_column_stats(),
+				feature_groups,
+				regressor.explanations().flatten(),
+				PartialDependenceOutput::Regression { target_column_name },
 			)
 		}
 		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
 			let binary_classifier = binary_classifier.read();
+			let positive_class = binary_classifier.positive_class().to_owned();
+			let feature_groups = match binary_classifier.model() {
+				modelfox_model::BinaryClassificationModelReader::Linear(model) => {
+					model.read().feature_groups()
+				}
+				modelfox_model::BinaryClassificationModelReader::Tree(model) => {
+					model.read().feature_groups()
+				}
+			};
 			(
+				binary_classifier.overall_row_count(),
 				binary_classifier.overall_column_stats(),
 				binary_classifier.overall_target_column_stats(),
+				feature_groups,
+				binary_classifier.explanations().flatten(),
+				PartialDependenceOutput::BinaryClassification { positive_class },
 			)
 		}
 		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
 			let multiclass_classifier = multiclass_classifier.read();
+			let feature_groups = match multiclass_classifier.model() {
+				modelfox_model::MulticlassClassificationModelReader::Linear(model) => {
+					model.read().feature_groups()
+				}
+				modelfox_model::MulticlassClassificationModelReader::Tree(model) => {
+					model.read().feature_groups()
+				}
+			};
 			(
+				multiclass_classifier.overall_row_count(),
 				multiclass_classifier.overall_column_stats(),
 				multiclass_classifier.overall_target_column_stats(),
+				feature_groups,
+				multiclass_classifier.explanations().flatten(),
+				PartialDependenceOutput::MulticlassClassification,
+			)
+		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			let ranker = ranker.read();
+			let feature_groups = match ranker.model() {
+				modelfox_model::RankingModelReader::Tree(model) => model.read().feature_groups(),
+			};
+			(
+				ranker.overall_row_count(),
+				ranker.overall_column_stats(),
+				ranker.overall_target_column_stats(),
+				feature_groups,
+				ranker.explanations().flatten(),
+				PartialDependenceOutput::Ranking,
 			)
 		}
 	};
@@ -79,7 +139,17 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	} else {
 		return Ok(not_found());
 	};
-
+	// Explanations are not computed for the target column, and are absent for models trained before they were computed during training.
+	let explanations = if target_column_stats.column_name() == column_name {
+		None
+	} else {
+		explanations.map(|explanations| {
+			column_explanations(explanations, &column_name, &partial_dependence_output)
+		})
+	};
+	let impute = column_impute(feature_groups.iter(), &column_name);
+	let imputed_fraction =
+		|invalid_count: u64| invalid_count.to_f32().unwrap() / overall_row_count.to_f32().unwrap();
 	let inner = match column {
 		modelfox_model::ColumnStatsReader::UnknownColumn(_) => unimplemented!(),
 		modelfox_model::ColumnStatsReader::NumberColumn(column_stats) => {
@@ -95,6 +165,12 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				p75: column_stats.p75(),
 				std: column_stats.std(),
 				unique_count: column_stats.unique_count(),
+				imputation: impute.map(|impute| ColumnImputation {
+					strategy: impute_strategy_name(impute.strategy()).to_owned(),
+					value: ui::format_float(impute.value()),
+					imputed_fraction: imputed_fraction(column_stats.invalid_count()),
+				}),
+				explanations,
 			})
 		}
 		modelfox_model::ColumnStatsReader::EnumColumn(column_stats) => {
@@ -128,6 +204,20 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				invalid_count: column_stats.invalid_count(),
 				name: column_stats.column_name().to_owned(),
 				unique_count: column_stats.unique_count(),
+				imputation: impute.map(|impute| {
+					// The imputed value of an enum column is the index of a variant, starting at 1.
+					let variant_index = impute.value().to_usize().unwrap() - 1;
+					ColumnImputation {
+						strategy: impute_strategy_name(impute.strategy()).to_owned(),
+						value: column_stats
+							.histogram()
+							.get(variant_index)
+							.map(|(variant, _)| variant.to_owned())
+							.unwrap_or_default(),
+						imputed_fraction: imputed_fraction(column_stats.invalid_count()),
+					}
+				}),
+				explanations,
 			})
 		}
 		modelfox_model::ColumnStatsReader::TextColumn(column_stats) => {
@@ -164,6 +254,7 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 				ngram_count,
 				top_ngrams_chart_values,
 				ngrams_table_rows,
+				explanations,
 			})
 		}
 	};
@@ -181,3 +272,107 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	app.commit_transaction(db).await?;
 	Ok(response)
 }
+
+/// This enum describes what the model's output is, so partial dependence charts can be labeled.
+enum PartialDependenceOutput {
+	Regression { target_column_name: String },
+	BinaryClassification { positive_class: String },
+	MulticlassClassification,
+	Ranking,
+}
+
+/// Find the imputation of the feature group for the column named `column_name`, if it has one.
+fn column_impute<'a>(
+	mut feature_groups: impl Iterator<Item = modelfox_model::FeatureGroupReader<'a>>,
+	column_name: &str,
+) -> Option<modelfox_model::ImputeReader<'a>> {
+	feature_groups.find_map(|feature_group| match feature_group {
+		modelfox_model::FeatureGroupReader::Identity(feature_group) => {
+			let feature_group = feature_group.read();
+			if feature_group.source_column_name() == column_name {
+				feature_group.impute().flatten()
+			} else {
+				None
+			}
+		}
+		modelfox_model::FeatureGroupReader::Normalized(feature_group) => {
+			let feature_group = feature_group.read();
+			if feature_group.source_column_name() == column_name {
+				feature_group.impute().flatten()
+			} else {
+				None
+			}
+		}
+		_ => None,
+	})
+}
+
+fn impute_strategy_name(strategy: modelfox_model::ImputeStrategyReader) -> &'static str {
+	match strategy {
+		modelfox_model::ImputeStrategyReader::Mean(_) => "Mean",
+		modelfox_model::ImputeStrategyReader::Median(_) => "Median",
+		modelfox_model::ImputeStrategyReader::Mode(_) => "Most Frequent",
+		modelfox_model::ImputeStrategyReader::Constant(_) => "Constant",
+	}
+}
+
+fn column_explanations(
+	explanations: modelfox_model::ExplanationsReader,
+	column_name: &str,
+	partial_dependence_output: &PartialDependenceOutput,
+) -> ColumnExplanations {
+	let shap_importance = explanations
+		.shap_column_importances()
+		.iter()
+		.find(|(name, _)| *name == column_name)
+		.map(|(_, importance)| importance);
+	let partial_dependences = explanations
+		.partial_dependences()
+		.iter()
+		.filter(|partial_dependence| partial_dependence.column_name() == column_name)
+		.map(|partial_dependence| {
+			let y_axis_title = match (partial_dependence_output, partial_dependence.class()) {
+				(PartialDependenceOutput::Regression { target_column_name }, _) => {
+					format!("Predicted {}", target_column_name)
+				}
+				(PartialDependenceOutput::BinaryClassification { positive_class }, _) => {
+					format!("Probability of {}", positive_class)
+				}
+				(PartialDependenceOutput::MulticlassClassification, class) => {
+					format!("Probability of {}", class.unwrap_or_default())
+				}
+				(PartialDependenceOutput::Ranking, _) => "Score".to_owned(),
+			};
+			let grid = match partial_dependence.grid() {
+				modelfox_model::PartialDependenceGridReader::Number(grid) => {
+					PartialDependenceChartGrid::Number(grid.read().values().iter().collect())
+				}
+				modelfox_model::PartialDependenceGridReader::Enum(grid) => {
+					PartialDependenceChartGrid::Enum(
+						grid.read()
+							.variants()
+							.iter()
+							.map(|variant| variant.to_owned())
+							.collect(),
+					)
+				}
+			};
+			PartialDependenceChart {
+				title: format!("Partial Dependence of {} on {}", y_axis_title, column_name),
+				y_axis_title,
+				grid,
+				average: partial_dependence.average().iter().collect(),
+				individual: partial_dependence
+					.individual()
+					.iter()
+					.map(|individual| individual.values().iter().collect())
+					.collect(),
+			}
+		})
+		.collect();
+	ColumnExplanations {
+		name: column_name.to_owned(),
+		shap_importance,
+		partial_dependences,
+	}
+}
@@ -0,0 +1,28 @@
+use modelfox_app_ui::metrics_row::MetricsRow;
+use modelfox_ui as ui;
+use pinwheel::prelude::*;
+
+pub struct ColumnImputation {
+	pub strategy: String,
+	pub value: String,
+	pub imputed_fraction: f32,
+}
+
+impl Component for ColumnImputation {
+	fn into_node(self) -> Node {
+		let description = "Invalid values in this column are replaced with the imputed value before computing the model's features. The imputed fraction is the fraction of rows in the dataset whose value was imputed.";
+		ui::S2::new()
+			.child(ui::H2::new("Imputation"))
+			.child(ui::P::new().child(description))
+			.child(
+				MetricsRow::new()
+					.child(ui::NumberCard::new("Strategy".to_owned(), self.strategy))
+					.child(ui::NumberCard::new("Imputed Value".to_owned(), self.value))
+					.child(ui::NumberCard::new(
+						"Imputed Fraction".to_owned(),
+						ui::format_percent(self.imputed_fraction),
+					)),
+			)
+			.into_node()
+	}
+}
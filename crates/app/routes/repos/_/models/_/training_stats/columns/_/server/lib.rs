@@ -1,5 +1,7 @@
 mod enum_column;
+mod explanations;
 mod get;
+mod imputation;
 mod number_column;
 mod page;
 mod text_column;
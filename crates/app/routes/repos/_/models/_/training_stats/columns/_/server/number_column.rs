@@ -1,3 +1,4 @@
+use crate::{explanations::ColumnExplanations, imputation::ColumnImputation};
 use modelfox_app_ui::metrics_row::MetricsRow;
 use modelfox_charts::{
 	box_chart::BoxChartPoint,
@@ -19,6 +20,8 @@ pub struct NumberColumn {
 	pub p75: f32,
 	pub std: f32,
 	pub unique_count: u64,
+	pub imputation: Option<ColumnImputation>,
+	pub explanations: Option<ColumnExplanations>,
 }
 
 impl Component for NumberColumn {
@@ -100,6 +103,8 @@ impl Component for NumberColumn {
 						)),
 					),
 			)
+			.child(self.imputation)
+			.child(self.explanations)
 			.into_node()
 	}
 }
@@ -1,4 +1,4 @@
-pub use crate::{enum_column::*, number_column::*, text_column::*};
+pub use crate::{enum_column::*, explanations::*, imputation::*, number_column::*, text_column::*};
 use modelfox_app_layouts::{
 	document::Document,
 	model_layout::{ModelLayout, ModelLayoutInfo},
@@ -1,3 +1,4 @@
+use crate::explanations::ColumnExplanations;
 use modelfox_charts::{
 	bar_chart::{BarChartPoint, BarChartSeries},
 	components::BarChart,
@@ -11,6 +12,7 @@ pub struct TextColumn {
 	pub ngram_count: usize,
 	pub top_ngrams_chart_values: Vec<NGramStats>,
 	pub ngrams_table_rows: Vec<NGramsTableRow>,
+	pub explanations: Option<ColumnExplanations>,
 }
 
 #[derive(Clone)]
@@ -78,6 +80,7 @@ impl Component for TextColumn {
 				BarChart::new().series(series).title(chart_title).y_min(0.0),
 			))))
 			.child(ui::S2::new().child(table_section))
+			.child(self.explanations)
 			.into_node()
 	}
 }
@@ -1,4 +1,7 @@
-use crate::page::{ColumnStatsTable, ColumnStatsTableRow, Page, TargetColumnStatsTable};
+use crate::page::{
+	ColumnStatsTable, ColumnStatsTableRow, DroppedColumnsTable, DroppedColumnsTableRow, Page,
+	TargetColumnStatsTable,
+};
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
@@ -10,9 +13,10 @@ use modelfox_app_core::{
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
 use modelfox_app_ui::column_type::ColumnType;
 use modelfox_id::Id;
+use modelfox_ui as ui;
 use num::ToPrimitive;
 use pinwheel::prelude::*;
-use std::sync::Arc;
+use std::{fmt::Write, sync::Arc};
 
 pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
 	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
@@ -63,6 +67,16 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 						&regressor.overall_target_column_stats(),
 					),
 				},
+				dropped_columns_table: build_dropped_columns_table(
+					regressor
+						.dropped_columns()
+						.map(|dropped_columns| dropped_columns.iter()),
+				),
+				data_quality_issues: build_data_quality_issues(
+					regressor
+						.data_quality_issues()
+						.map(|data_quality_issues| data_quality_issues.iter()),
+				),
 			}
 		}
 		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
@@ -84,6 +98,16 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 						&binary_classifier.overall_target_column_stats(),
 					),
 				},
+				dropped_columns_table: build_dropped_columns_table(
+					binary_classifier
+						.dropped_columns()
+						.map(|dropped_columns| dropped_columns.iter()),
+				),
+				data_quality_issues: build_data_quality_issues(
+					binary_classifier
+						.data_quality_issues()
+						.map(|data_quality_issues| data_quality_issues.iter()),
+				),
 			}
 		}
 		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
@@ -105,6 +129,47 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 						&multiclass_classifier.overall_target_column_stats(),
 					),
 				},
+				dropped_columns_table: build_dropped_columns_table(
+					multiclass_classifier
+						.dropped_columns()
+						.map(|dropped_columns| dropped_columns.iter()),
+				),
+				data_quality_issues: build_data_quality_issues(
+					multiclass_classifier
+						.data_quality_issues()
+						.map(|data_quality_issues| data_quality_issues.iter()),
+				),
+			}
+		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			let ranker = ranker.read();
+			let column_stats = ranker.overall_column_stats();
+			Page {
+				column_stats_table: ColumnStatsTable {
+					column_stats_table_rows: column_stats
+						.iter()
+						.map(|column_stats| build_column_stats(&column_stats))
+						.collect(),
+				},
+				model_layout_info,
+				row_count: ranker.test_row_count().to_usize().unwrap()
+					+ ranker.train_row_count().to_usize().unwrap(),
+				column_count: column_stats.len(),
+				target_column_stats_table: TargetColumnStatsTable {
+					target_column_stats_table_row: build_column_stats(
+						&ranker.overall_target_column_stats(),
+					),
+				},
+				dropped_columns_table: build_dropped_columns_table(
+					ranker
+						.dropped_columns()
+						.map(|dropped_columns| dropped_columns.iter()),
+				),
+				data_quality_issues: build_data_quality_issues(
+					ranker
+						.data_quality_issues()
+						.map(|data_quality_issues| data_quality_issues.iter()),
+				),
 			}
 		}
 	};
@@ -117,6 +182,99 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	Ok(response)
 }
 
+fn build_dropped_columns_table<'a>(
+	dropped_columns: Option<impl Iterator<Item = modelfox_model::DroppedColumnReader<'a>>>,
+) -> Option<DroppedColumnsTable> {
+	let rows = dropped_columns?
+		.map(|dropped_column| DroppedColumnsTableRow {
+			name: dropped_column.column_name().to_owned(),
+			reason: dropped_column_reason(dropped_column.reason()),
+		})
+		.collect::<Vec<_>>();
+	if rows.is_empty() {
+		return None;
+	}
+	Some(DroppedColumnsTable { rows })
+}
+
+fn dropped_column_reason(reason: modelfox_model::DroppedColumnReasonReader) -> String {
+	match reason {
+		modelfox_model::DroppedColumnReasonReader::NearConstant(reason) => format!(
+			"The most frequent value makes up {} of the valid values.",
+			ui::format_percent(reason.read().most_frequent_fraction())
+		),
+		modelfox_model::DroppedColumnReasonReader::InvalidFraction(reason) => format!(
+			"{} of the values are invalid.",
+			ui::format_percent(reason.read().invalid_fraction())
+		),
+		modelfox_model::DroppedColumnReasonReader::Collinear(reason) => {
+			let reason = reason.read();
+			format!(
+				"The correlation with {} is {}.",
+				reason.column_name(),
+				ui::format_float(reason.correlation())
+			)
+		}
+		modelfox_model::DroppedColumnReasonReader::LowImportance(reason) => format!(
+			"The feature importance in the probe model is {}.",
+			ui::format_float(reason.read().importance())
+		),
+	}
+}
+
+fn build_data_quality_issues<'a>(
+	data_quality_issues: Option<impl Iterator<Item = modelfox_model::DataQualityIssueReader<'a>>>,
+) -> Vec<String> {
+	let data_quality_issues = match data_quality_issues {
+		Some(data_quality_issues) => data_quality_issues,
+		None => return Vec::new(),
+	};
+	data_quality_issues
+		.map(|data_quality_issue| match data_quality_issue {
+			modelfox_model::DataQualityIssueReader::IdColumn(issue) => {
+				let issue = issue.read();
+				format!(
+					"The column \"{}\" looks like an ID. {} of its values in the train dataset are unique.",
+					issue.column_name(),
+					ui::format_percent(issue.unique_fraction())
+				)
+			}
+			modelfox_model::DataQualityIssueReader::TargetLeakage(issue) => {
+				let issue = issue.read();
+				format!(
+					"The column \"{}\" may leak the target. A model that uses only this column scores {} on the comparison dataset, where 1 is a perfect score.",
+					issue.column_name(),
+					ui::format_float(issue.score())
+				)
+			}
+			modelfox_model::DataQualityIssueReader::DuplicateRows(issue) => {
+				let issue = issue.read();
+				format!(
+					"{} row(s) of the test dataset, {} of it, also appear in the train dataset.",
+					issue.duplicate_count(),
+					ui::format_percent(issue.duplicate_fraction())
+				)
+			}
+			modelfox_model::DataQualityIssueReader::DistributionMismatch(issue) => {
+				let issue = issue.read();
+				let mut message = format!(
+					"The train and test datasets have different distributions. A model can tell their rows apart with an AUC ROC of {}.",
+					ui::format_float(issue.auc_roc())
+				);
+				if let Some(column_name) = issue.column_name() {
+					write!(
+						message,
+						" The column \"{}\" differs the most between them.",
+						column_name
+					)
+					.unwrap();
+				}
+				message
+			}
+		})
+		.collect()
+}
+
 fn build_column_stats(column_stats: &modelfox_model::ColumnStatsReader) -> ColumnStatsTableRow {
 	match column_stats {
 		modelfox_model::ColumnStatsReader::UnknownColumn(column_stats) => {
@@ -16,6 +16,8 @@ pub struct Page {
 	pub column_stats_table: ColumnStatsTable,
 	pub column_count: usize,
 	pub row_count: usize,
+	pub dropped_columns_table: Option<DroppedColumnsTable>,
+	pub data_quality_issues: Vec<String>,
 }
 
 pub struct ColumnStatsTableRow {
@@ -37,6 +39,19 @@ impl Component for Page {
 				ModelLayout::new(self.model_layout_info).child(
 					ui::S1::new()
 						.child(ui::H1::new("Training Stats"))
+						.child((!self.data_quality_issues.is_empty()).then(|| {
+							ui::S2::new()
+									.child(ui::H2::new("Data Quality Issues"))
+									.child(ui::P::new().child(
+										"The target leakage and data quality checks found these issues before training.",
+									))
+									.children(self.data_quality_issues.into_iter().map(
+										|data_quality_issue| {
+											ui::Alert::new(ui::Level::Warning)
+												.child(data_quality_issue)
+										},
+									))
+						}))
 						.child(
 							ui::S2::new()
 								.child(ui::H2::new("Target Column"))
@@ -57,7 +72,15 @@ impl Component for Page {
 										)),
 								)
 								.child(self.column_stats_table),
-						),
+						)
+						.child(self.dropped_columns_table.map(|dropped_columns_table| {
+							ui::S2::new()
+								.child(ui::H2::new("Dropped Columns"))
+								.child(ui::P::new().child(
+									"Feature selection dropped these columns before training.",
+								))
+								.child(dropped_columns_table)
+						})),
 				),
 			)
 			.into_node()
@@ -215,3 +238,34 @@ impl Component for ColumnTypeToken {
 		.into_node()
 	}
 }
+
+pub struct DroppedColumnsTable {
+	pub rows: Vec<DroppedColumnsTableRow>,
+}
+
+pub struct DroppedColumnsTableRow {
+	pub name: String,
+	pub reason: String,
+}
+
+impl Component for DroppedColumnsTable {
+	fn into_node(self) -> Node {
+		ui::Table::new()
+			.width("100%".to_owned())
+			.child(
+				ui::TableHeader::new().child(
+					ui::TableRow::new()
+						.child(ui::TableHeaderCell::new().child("Column"))
+						.child(ui::TableHeaderCell::new().child("Reason")),
+				),
+			)
+			.child(
+				ui::TableBody::new().children(self.rows.into_iter().map(|row| {
+					ui::TableRow::new()
+						.child(ui::TableCell::new().child(row.name))
+						.child(ui::TableCell::new().child(row.reason))
+				})),
+			)
+			.into_node()
+	}
+}
@@ -7,6 +7,7 @@ use std::rc::Rc;
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct Tuning {
 	pub default_threshold: f32,
+	pub selected_threshold: Option<f32>,
 	pub metrics: Vec<Metrics>,
 	pub default_threshold_metrics: Metrics,
 	pub class: String,
@@ -29,12 +30,12 @@ impl Component for Tuning {
 	fn into_node(self) -> Node {
 		let baseline_metrics = Rc::new(self.default_threshold_metrics);
 		let metrics = Rc::new(self.metrics);
-		let default_threshold = self.default_threshold;
-		let baseline_index = metrics
-			.iter()
-			.position(|metrics| (metrics.threshold - default_threshold).abs() < std::f32::EPSILON)
-			.unwrap();
-		let selected_index: Mutable<usize> = Mutable::new(baseline_index);
+		let baseline_index = nearest_threshold_index(&metrics, self.default_threshold);
+		let initial_index = self
+			.selected_threshold
+			.map(|selected_threshold| nearest_threshold_index(&metrics, selected_threshold))
+			.unwrap_or(baseline_index);
+		let selected_index: Mutable<usize> = Mutable::new(initial_index);
 		let on_change = {
 			clone!(selected_index);
 			Box::new(move |value: f32| selected_index.set(value.to_usize().unwrap()))
@@ -134,9 +135,28 @@ impl Component for Tuning {
 					value_b_title: "Selected Threshold".to_owned(),
 				})
 		};
-		ui::S1::new()
-			.child(ui::H1::new("Tuning"))
-			.child(ui::P::new().child("Drag the silder to choose a threshold."))
+		let save_threshold_form = ui::Form::new()
+			.post(true)
+			.child(ui::P::new().child("Saving the selected threshold makes it the threshold used by modelfox predict, modelfox serve, and the language libraries when no threshold is passed."))
+			.child(
+				input()
+					.attribute("name", "threshold")
+					.attribute("type", "hidden")
+					.value_signal({
+						clone!(metrics);
+						selected_index.signal().map(move |selected_index| {
+							metrics[selected_index].threshold.to_string()
+						})
+					}),
+			)
+			.child(
+				ui::Button::new()
+					.button_type(ui::ButtonType::Submit)
+					.child("Save Threshold"),
+			);
+		ui::S2::new()
+			.child(ui::H2::new("Choose a Threshold"))
+			.child(ui::P::new().child("Drag the slider to choose a threshold. The baseline is the threshold currently saved in the model."))
 			.child(
 				ui::Slider::new(ui::SliderInit {
 					min: 0.0,
@@ -158,6 +178,22 @@ impl Component for Tuning {
 			})
 			.child(tuning_metrics_grid)
 			.child_signal(confusion_comparison_matrix)
+			.child(save_threshold_form)
 			.into_node()
 	}
 }
+
+/// Find the index of the entry in `metrics` whose threshold is closest to `threshold`. The threshold saved in a model may not be one of the thresholds the test metrics were computed at.
+pub fn nearest_threshold_index(metrics: &[Metrics], threshold: f32) -> usize {
+	metrics
+		.iter()
+		.enumerate()
+		.min_by(|(_, a), (_, b)| {
+			(a.threshold - threshold)
+				.abs()
+				.partial_cmp(&(b.threshold - threshold).abs())
+				.unwrap()
+		})
+		.map(|(index, _)| index)
+		.unwrap()
+}
@@ -1,4 +1,4 @@
-use crate::page::Page;
+use crate::page::{OptimizeThresholdForm, Page, ThresholdObjectiveParam};
 use anyhow::{bail, Result};
 use modelfox_app_context::Context;
 use modelfox_app_core::{
@@ -8,11 +8,21 @@ use modelfox_app_core::{
 	user::{authorize_user, authorize_user_for_model},
 };
 use modelfox_app_layouts::model_layout::{model_layout_info, ModelNavItem};
-use modelfox_app_tuning_common::{Metrics, Tuning};
+use modelfox_app_tuning_common::{nearest_threshold_index, Metrics, Tuning};
 use modelfox_id::Id;
 use pinwheel::prelude::*;
 use std::sync::Arc;
 
+#[derive(serde::Deserialize, Default)]
+struct SearchParams {
+	objective: Option<ThresholdObjectiveParam>,
+	true_positive_cost: Option<String>,
+	false_positive_cost: Option<String>,
+	true_negative_cost: Option<String>,
+	false_negative_cost: Option<String>,
+	target: Option<String>,
+}
+
 pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
 	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
 	let app = &context.app;
@@ -22,6 +32,32 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 		} else {
 			bail!("unexpected path");
 		};
+	let search_params: SearchParams = if let Some(query) = request.uri().query() {
+		match serde_urlencoded::from_str(query) {
+			Ok(search_params) => search_params,
+			Err(_) => return Ok(bad_request()),
+		}
+	} else {
+		SearchParams::default()
+	};
+	// Empty text fields are submitted as empty strings, so treat them as absent.
+	let parse_number = |value: Option<String>| -> Result<Option<f32>, std::num::ParseFloatError> {
+		match value {
+			Some(value) if !value.trim().is_empty() => value.trim().parse().map(Some),
+			_ => Ok(None),
+		}
+	};
+	let (true_positive_cost, false_positive_cost, true_negative_cost, false_negative_cost, target) =
+		match (
+			parse_number(search_params.true_positive_cost),
+			parse_number(search_params.false_positive_cost),
+			parse_number(search_params.true_negative_cost),
+			parse_number(search_params.false_negative_cost),
+			parse_number(search_params.target),
+		) {
+			(Ok(a), Ok(b), Ok(c), Ok(d), Ok(e)) => (a, b, c, d, e),
+			_ => return Ok(bad_request()),
+		};
 	let mut db = match app.begin_transaction().await {
 		Ok(db) => db,
 		Err(_) => return Ok(service_unavailable()),
@@ -39,64 +75,98 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	}
 	let bytes = get_model_bytes(app.storage(), model_id).await?;
 	let model = modelfox_model::from_bytes(&bytes)?;
-	let tuning = match model.inner() {
-		modelfox_model::ModelInnerReader::Regressor(_) => None,
+	let (tuning, optimize_threshold_form) = match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(_) => (None, None),
 		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
 			let model = binary_classifier.read();
-			let metrics: Vec<Metrics> = model
-				.test_metrics()
-				.thresholds()
-				.iter()
-				.map(|metrics| {
-					let total = metrics.false_negatives() as f32
-						+ metrics.false_positives() as f32
-						+ metrics.true_positives() as f32
-						+ metrics.true_negatives() as f32;
-					Metrics {
-						threshold: metrics.threshold(),
-						precision: metrics.precision(),
-						recall: metrics.recall(),
-						accuracy: metrics.accuracy(),
-						f1_score: metrics.f1_score(),
-						false_negatives_fraction: metrics.false_negatives() as f32 / total,
-						false_positives_fraction: metrics.false_positives() as f32 / total,
-						true_negatives_fraction: metrics.true_negatives() as f32 / total,
-						true_positives_fraction: metrics.true_positives() as f32 / total,
+			let thresholds: Vec<modelfox_metrics::BinaryClassificationMetricsOutputForThreshold> =
+				model
+					.test_metrics()
+					.thresholds()
+					.iter()
+					.map(|metrics| {
+						modelfox_metrics::BinaryClassificationMetricsOutputForThreshold {
+							threshold: metrics.threshold(),
+							true_positives: metrics.true_positives(),
+							false_positives: metrics.false_positives(),
+							true_negatives: metrics.true_negatives(),
+							false_negatives: metrics.false_negatives(),
+							accuracy: metrics.accuracy(),
+							precision: metrics.precision(),
+							recall: metrics.recall(),
+							f1_score: metrics.f1_score(),
+							true_positive_rate: metrics.true_positive_rate(),
+							false_positive_rate: metrics.false_positive_rate(),
+						}
+					})
+					.collect();
+			let metrics: Vec<Metrics> = thresholds.iter().map(compute_metrics).collect();
+			// The baseline is the threshold currently saved in the model.
+			let default_threshold = model
+				.threshold()
+				.unwrap_or(modelfox_model::DEFAULT_THRESHOLD);
+			let default_threshold_index = nearest_threshold_index(&metrics, default_threshold);
+			let default_threshold_metrics = metrics[default_threshold_index].clone();
+			let default_threshold = default_threshold_metrics.threshold;
+			// If the user asked for an optimal threshold, compute it from the test metrics.
+			let objective = match search_params.objective {
+				Some(ThresholdObjectiveParam::Cost) => {
+					Ok(Some(modelfox_metrics::ThresholdObjective::Cost(
+						modelfox_metrics::BinaryClassificationCostMatrix {
+							true_positive_cost: true_positive_cost.unwrap_or(0.0),
+							false_positive_cost: false_positive_cost.unwrap_or(0.0),
+							true_negative_cost: true_negative_cost.unwrap_or(0.0),
+							false_negative_cost: false_negative_cost.unwrap_or(0.0),
+						},
+					)))
+				}
+				Some(ThresholdObjectiveParam::MinPrecision) => target
+					.map(|target| Some(modelfox_metrics::ThresholdObjective::MinPrecision(target)))
+					.ok_or("Enter a minimum precision."),
+				Some(ThresholdObjectiveParam::MinRecall) => target
+					.map(|target| Some(modelfox_metrics::ThresholdObjective::MinRecall(target)))
+					.ok_or("Enter a minimum recall."),
+				None => Ok(None),
+			};
+			let (selected_threshold, error) = match objective {
+				Ok(Some(objective)) => {
+					match modelfox_metrics::compute_optimal_threshold(&thresholds, &objective) {
+						Some(optimal) => (Some(optimal.threshold), None),
+						None => (
+							None,
+							Some("No threshold satisfies the target you entered.".to_owned()),
+						),
 					}
-				})
-				.collect();
-			let test_metrics = model.test_metrics();
-			let default_threshold_metrics = test_metrics.default_threshold();
-			let total = default_threshold_metrics.false_negatives() as f32
-				+ default_threshold_metrics.false_positives() as f32
-				+ default_threshold_metrics.true_negatives() as f32
-				+ default_threshold_metrics.true_positives() as f32;
-			let default_threshold_metrics = Metrics {
-				threshold: default_threshold_metrics.threshold(),
-				precision: default_threshold_metrics.precision(),
-				recall: default_threshold_metrics.recall(),
-				accuracy: default_threshold_metrics.accuracy(),
-				f1_score: default_threshold_metrics.f1_score(),
-				false_negatives_fraction: default_threshold_metrics.false_negatives() as f32
-					/ total,
-				false_positives_fraction: default_threshold_metrics.false_positives() as f32
-					/ total,
-				true_negatives_fraction: default_threshold_metrics.true_negatives() as f32 / total,
-				true_positives_fraction: default_threshold_metrics.true_positives() as f32 / total,
+				}
+				Ok(None) => (None, None),
+				Err(error) => (None, Some(error.to_owned())),
 			};
-			Some(Tuning {
-				default_threshold: 0.5,
+			let tuning = Tuning {
+				default_threshold,
+				selected_threshold,
 				metrics,
 				default_threshold_metrics,
 				class: model.positive_class().to_owned(),
-			})
+			};
+			let optimize_threshold_form = OptimizeThresholdForm {
+				objective: search_params.objective,
+				true_positive_cost,
+				false_positive_cost,
+				true_negative_cost,
+				false_negative_cost,
+				target,
+				error,
+			};
+			(Some(tuning), Some(optimize_threshold_form))
 		}
-		modelfox_model::ModelInnerReader::MulticlassClassifier(_) => None,
+		modelfox_model::ModelInnerReader::MulticlassClassifier(_) => (None, None),
+		modelfox_model::ModelInnerReader::Ranker(_) => (None, None),
 	};
 	let model_layout_info = model_layout_info(&mut db, app, model_id, ModelNavItem::Tuning).await?;
 	let page = Page {
 		model_layout_info,
 		tuning,
+		optimize_threshold_form,
 	};
 	let html = html(page);
 	let response = http::Response::builder()
@@ -106,3 +176,23 @@ pub async fn get(request: &mut http::Request<hyper::Body>) -> Result<http::Respo
 	app.commit_transaction(db).await?;
 	Ok(response)
 }
+
+fn compute_metrics(
+	metrics: &modelfox_metrics::BinaryClassificationMetricsOutputForThreshold,
+) -> Metrics {
+	let total = metrics.false_negatives as f32
+		+ metrics.false_positives as f32
+		+ metrics.true_positives as f32
+		+ metrics.true_negatives as f32;
+	Metrics {
+		threshold: metrics.threshold,
+		precision: metrics.precision,
+		recall: metrics.recall,
+		accuracy: metrics.accuracy,
+		f1_score: metrics.f1_score,
+		false_negatives_fraction: metrics.false_negatives as f32 / total,
+		false_positives_fraction: metrics.false_positives as f32 / total,
+		true_negatives_fraction: metrics.true_negatives as f32 / total,
+		true_positives_fraction: metrics.true_positives as f32 / total,
+	}
+}
@@ -9,12 +9,37 @@ use pinwheel::prelude::*;
 pub struct Page {
 	pub model_layout_info: ModelLayoutInfo,
 	pub tuning: Option<Tuning>,
+	pub optimize_threshold_form: Option<OptimizeThresholdForm>,
+}
+
+#[derive(Clone, Copy, serde::Deserialize)]
+pub enum ThresholdObjectiveParam {
+	#[serde(rename = "cost")]
+	Cost,
+	#[serde(rename = "min_precision")]
+	MinPrecision,
+	#[serde(rename = "min_recall")]
+	MinRecall,
+}
+
+impl ThresholdObjectiveParam {
+	fn as_str(&self) -> &'static str {
+		match self {
+			ThresholdObjectiveParam::Cost => "cost",
+			ThresholdObjectiveParam::MinPrecision => "min_precision",
+			ThresholdObjectiveParam::MinRecall => "min_recall",
+		}
+	}
 }
 
 impl Component for Page {
 	fn into_node(self) -> Node {
 		let inner = match self.tuning {
-			Some(tuning) => Dehydrate::new("tuning", tuning).into_node(),
+			Some(tuning) => ui::S1::new()
+				.child(ui::H1::new("Tuning"))
+				.child(self.optimize_threshold_form)
+				.child(Dehydrate::new("tuning", tuning))
+				.into_node(),
 			None => ui::S1::new()
 				.child(ui::P::new().child("Tuning is not supported for this model."))
 				.into_node(),
@@ -25,3 +50,95 @@ impl Component for Page {
 			.into_node()
 	}
 }
+
+pub struct OptimizeThresholdForm {
+	pub objective: Option<ThresholdObjectiveParam>,
+	pub true_positive_cost: Option<f32>,
+	pub false_positive_cost: Option<f32>,
+	pub true_negative_cost: Option<f32>,
+	pub false_negative_cost: Option<f32>,
+	pub target: Option<f32>,
+	pub error: Option<String>,
+}
+
+impl Component for OptimizeThresholdForm {
+	fn into_node(self) -> Node {
+		let objective_options = vec![
+			ui::SelectFieldOption {
+				text: "Minimize Cost".to_owned(),
+				value: "cost".to_owned(),
+			},
+			ui::SelectFieldOption {
+				text: "Maximize Recall With Minimum Precision".to_owned(),
+				value: "min_precision".to_owned(),
+			},
+			ui::SelectFieldOption {
+				text: "Maximize Precision With Minimum Recall".to_owned(),
+				value: "min_recall".to_owned(),
+			},
+		];
+		let objective = self
+			.objective
+			.unwrap_or(ThresholdObjectiveParam::Cost)
+			.as_str()
+			.to_owned();
+		ui::S2::new()
+			.child(ui::H2::new("Optimize Threshold"))
+			.child(ui::P::new().child("Enter the cost of each cell of the confusion matrix to choose the threshold with the lowest total cost on the test dataset. Use negative costs for benefits. Alternatively, choose the threshold that maximizes recall or precision subject to a minimum value of the other."))
+			.child(
+				self.error
+					.map(|error| ui::Alert::new(ui::Level::Danger).child(error)),
+			)
+			.child(
+				ui::Form::new()
+					.child(
+						ui::SelectField::new()
+							.label("Objective".to_owned())
+							.name("objective".to_owned())
+							.options(objective_options)
+							.value(objective),
+					)
+					.child(
+						ui::TextField::new()
+							.label("True Positive Cost".to_owned())
+							.name("true_positive_cost".to_owned())
+							.value(self.true_positive_cost.map(|cost| cost.to_string()))
+							.placeholder("0".to_owned()),
+					)
+					.child(
+						ui::TextField::new()
+							.label("False Positive Cost".to_owned())
+							.name("false_positive_cost".to_owned())
+							.value(self.false_positive_cost.map(|cost| cost.to_string()))
+							.placeholder("0".to_owned()),
+					)
+					.child(
+						ui::TextField::new()
+							.label("True Negative Cost".to_owned())
+							.name("true_negative_cost".to_owned())
+							.value(self.true_negative_cost.map(|cost| cost.to_string()))
+							.placeholder("0".to_owned()),
+					)
+					.child(
+						ui::TextField::new()
+							.label("False Negative Cost".to_owned())
+							.name("false_negative_cost".to_owned())
+							.value(self.false_negative_cost.map(|cost| cost.to_string()))
+							.placeholder("0".to_owned()),
+					)
+					.child(
+						ui::TextField::new()
+							.label("Minimum Precision or Recall".to_owned())
+							.name("target".to_owned())
+							.value(self.target.map(|target| target.to_string()))
+							.placeholder("0.9".to_owned()),
+					)
+					.child(
+						ui::Button::new()
+							.button_type(ui::ButtonType::Submit)
+							.child("Optimize"),
+					),
+			)
+			.into_node()
+	}
+}
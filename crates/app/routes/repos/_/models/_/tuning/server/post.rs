@@ -0,0 +1,74 @@
+use anyhow::{bail, Result};
+use modelfox_app_context::Context;
+use modelfox_app_core::{
+	error::{bad_request, not_found, redirect_to_login, service_unavailable},
+	model::get_model_bytes,
+	path_components,
+	storage::StorageEntity,
+	user::{authorize_user, authorize_user_for_model},
+};
+use modelfox_id::Id;
+use std::sync::Arc;
+
+#[derive(serde::Deserialize)]
+struct SaveThresholdAction {
+	threshold: f32,
+}
+
+pub async fn post(request: &mut http::Request<hyper::Body>) -> Result<http::Response<hyper::Body>> {
+	let context = Arc::clone(request.extensions().get::<Arc<Context>>().unwrap());
+	let app = &context.app;
+	let (repo_id, model_id) = if let ["repos", repo_id, "models", model_id, "tuning"] =
+		*path_components(request).as_slice()
+	{
+		(repo_id.to_owned(), model_id.to_owned())
+	} else {
+		bail!("unexpected path");
+	};
+	let data = match hyper::body::to_bytes(request.body_mut()).await {
+		Ok(data) => data,
+		Err(_) => return Ok(bad_request()),
+	};
+	let action: SaveThresholdAction = match serde_urlencoded::from_bytes(&data) {
+		Ok(action) => action,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !(0.0..=1.0).contains(&action.threshold) {
+		return Ok(bad_request());
+	}
+	let mut db = match app.begin_transaction().await {
+		Ok(db) => db,
+		Err(_) => return Ok(service_unavailable()),
+	};
+	let user = match authorize_user(request, &mut db, app.options().auth_enabled()).await? {
+		Ok(user) => user,
+		Err(_) => return Ok(redirect_to_login()),
+	};
+	let model_id: Id = match model_id.parse() {
+		Ok(model_id) => model_id,
+		Err(_) => return Ok(bad_request()),
+	};
+	if !authorize_user_for_model(&mut db, &user, model_id).await? {
+		return Ok(not_found());
+	}
+	let model_bytes = get_model_bytes(app.storage(), model_id).await?;
+	let mut bytes = model_bytes.to_vec();
+	// The model may be memory mapped from the file we are about to overwrite.
+	drop(model_bytes);
+	if modelfox_model::set_threshold(&mut bytes, action.threshold).is_err() {
+		return Ok(bad_request());
+	}
+	app.storage()
+		.set(StorageEntity::Model, model_id, &bytes)
+		.await?;
+	app.commit_transaction(db).await?;
+	let response = http::Response::builder()
+		.status(http::StatusCode::SEE_OTHER)
+		.header(
+			http::header::LOCATION,
+			format!("/repos/{}/models/{}/tuning", repo_id, model_id),
+		)
+		.body(hyper::Body::empty())
+		.unwrap();
+	Ok(response)
+}
@@ -22,6 +22,7 @@ pub enum PredictOutputInner {
 	Regression(RegressionPredictOutput),
 	BinaryClassification(BinaryClassificationPredictOutput),
 	MulticlassClassification(MulticlassClassificationPredictOutput),
+	Ranking(RankingPredictOutput),
 }
 
 impl Component for PredictOutput {
@@ -30,6 +31,7 @@ impl Component for PredictOutput {
 			PredictOutputInner::Regression(inner) => inner.into_node(),
 			PredictOutputInner::BinaryClassification(inner) => inner.into_node(),
 			PredictOutputInner::MulticlassClassification(inner) => inner.into_node(),
+			PredictOutputInner::Ranking(inner) => inner.into_node(),
 		};
 		fragment()
 			.child(
@@ -115,6 +117,7 @@ pub fn compute_input_table(
 		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
 			multiclass_classifier.read().overall_column_stats()
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => ranker.read().overall_column_stats(),
 	};
 	let rows = column_stats
 		.iter()
@@ -210,6 +213,40 @@ impl Component for RegressionPredictOutput {
 	}
 }
 
+pub struct RankingPredictOutput {
+	pub feature_contributions_chart_series: FeatureContributionsChartSeries,
+	pub score: f32,
+}
+
+impl Component for RankingPredictOutput {
+	fn into_node(self) -> Node {
+		ui::S2::new()
+			.child(ui::H2::new("Output"))
+			.child(ui::NumberCard::new(
+				"Score".to_owned(),
+				ui::format_float(self.score),
+			))
+			.child(ui::H2::new("Explanation"))
+			.child(
+				ui::P::new()
+					.child("This chart shows how the features contributed to the model's output."),
+			)
+			.child(
+				ui::Card::new().child(Dehydrate::new(
+					"ranking_feature_contributions",
+					FeatureContributionsChart::new()
+						.series(vec![self.feature_contributions_chart_series])
+						.negative_color(ui::colors::RED.to_owned())
+						.positive_color(ui::colors::GREEN.to_owned())
+						.include_x_axis_title(true)
+						.include_y_axis_labels(false)
+						.include_y_axis_title(false),
+				)),
+			)
+			.into_node()
+	}
+}
+
 pub struct BinaryClassificationPredictOutput {
 	pub class_name: String,
 	pub feature_contributions_chart_series: FeatureContributionsChartSeries,
@@ -422,5 +459,127 @@ fn compute_feature_contributions_chart_value(
 					.unwrap(),
 			}
 		}
+		modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(
+			feature_contribution,
+		) => {
+			let ngrams = feature_contribution
+				.ngrams
+				.iter()
+				.map(|ngram| format!("\"{}\"", ngram))
+				.collect::<Vec<_>>();
+			let feature = hashed_feature(
+				&feature_contribution.column_name,
+				"contains",
+				feature_contribution.bucket,
+				&ngrams,
+			);
+			FeatureContributionsChartValue {
+				feature,
+				value: feature_contribution
+					.feature_contribution_value
+					.to_f64()
+					.unwrap(),
+			}
+		}
+		modelfox_core::predict::FeatureContributionEntry::HashedEnum(feature_contribution) => {
+			let values = feature_contribution
+				.values
+				.iter()
+				.map(|value| format!("\"{}\"", value))
+				.collect::<Vec<_>>();
+			let feature = hashed_feature(
+				&feature_contribution.column_name,
+				"=",
+				feature_contribution.bucket,
+				&values,
+			);
+			FeatureContributionsChartValue {
+				feature,
+				value: feature_contribution
+					.feature_contribution_value
+					.to_f64()
+					.unwrap(),
+			}
+		}
+		modelfox_core::predict::FeatureContributionEntry::Interaction(feature_contribution) => {
+			let feature = format!(
+				"{} * {}",
+				interaction_side(
+					&feature_contribution.column_name_a,
+					feature_contribution.variant_a.as_deref()
+				),
+				interaction_side(
+					&feature_contribution.column_name_b,
+					feature_contribution.variant_b.as_deref()
+				),
+			);
+			FeatureContributionsChartValue {
+				feature,
+				value: feature_contribution
+					.feature_contribution_value
+					.to_f64()
+					.unwrap(),
+			}
+		}
+		modelfox_core::predict::FeatureContributionEntry::Polynomial(feature_contribution) => {
+			let feature = format!(
+				"{} ^ {}",
+				feature_contribution.column_name, feature_contribution.power
+			);
+			FeatureContributionsChartValue {
+				feature,
+				value: feature_contribution
+					.feature_contribution_value
+					.to_f64()
+					.unwrap(),
+			}
+		}
+		modelfox_core::predict::FeatureContributionEntry::BinnedNumber(feature_contribution) => {
+			let predicate = if feature_contribution.feature_value {
+				"is"
+			} else {
+				"is not"
+			};
+			let range = match (feature_contribution.bin_start, feature_contribution.bin_end) {
+				(None, None) => "valid".to_owned(),
+				(None, Some(end)) => format!("less than {}", end),
+				(Some(start), None) => format!("at least {}", start),
+				(Some(start), Some(end)) => format!("in [{}, {})", start, end),
+			};
+			let feature = format!(
+				"{} {} {}",
+				feature_contribution.column_name, predicate, range
+			);
+			FeatureContributionsChartValue {
+				feature,
+				value: feature_contribution
+					.feature_contribution_value
+					.to_f64()
+					.unwrap(),
+			}
+		}
+	}
+}
+
+/// Describe one side of an interaction feature, which is an enum variant if the column is an enum column.
+fn interaction_side(column_name: &str, variant: Option<&str>) -> String {
+	match variant {
+		Some(variant) => format!("{} = \"{}\"", column_name, variant),
+		None => column_name.to_owned(),
+	}
+}
+
+/// Describe the feature for a bucket of a hashed feature group by the most frequent training values in the bucket.
+fn hashed_feature(column_name: &str, relation: &str, bucket: usize, values: &[String]) -> String {
+	if values.is_empty() {
+		format!("{} bucket {}", column_name, bucket)
+	} else {
+		format!(
+			"{} {} {} (bucket {})",
+			column_name,
+			relation,
+			values.join(" | "),
+			bucket
+		)
 	}
 }
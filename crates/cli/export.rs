@@ -0,0 +1,37 @@
+use crate::{CodegenArgs, CodegenLanguage, ExportArgs, ExportFormat};
+use anyhow::Result;
+
+pub fn export(args: ExportArgs) -> Result<()> {
+	let format = match args.format {
+		ExportFormat::Onnx => modelfox_export::ExportFormat::Onnx,
+		ExportFormat::Pmml => modelfox_export::ExportFormat::Pmml,
+	};
+	let bytes = std::fs::read(&args.model)?;
+	let model = modelfox_model::from_bytes(&bytes)?;
+	let exported = modelfox_export::export(model, format)?;
+	let output_path = args
+		.output
+		.unwrap_or_else(|| args.model.with_extension(format.extension()));
+	std::fs::write(&output_path, exported)?;
+	eprintln!("Your model was exported to {}.", output_path.display());
+	Ok(())
+}
+
+pub fn codegen(args: CodegenArgs) -> Result<()> {
+	let language = match args.lang {
+		CodegenLanguage::Rust => modelfox_export::CodegenLanguage::Rust,
+		CodegenLanguage::C => modelfox_export::CodegenLanguage::C,
+	};
+	let bytes = std::fs::read(&args.model)?;
+	let model = modelfox_model::from_bytes(&bytes)?;
+	let source = modelfox_export::codegen(model, language)?;
+	let output_path = args
+		.output
+		.unwrap_or_else(|| args.model.with_extension(language.extension()));
+	std::fs::write(&output_path, source)?;
+	eprintln!(
+		"Your model's source code was written to {}.",
+		output_path.display()
+	);
+	Ok(())
+}
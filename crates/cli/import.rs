@@ -0,0 +1,19 @@
+use crate::{ImportArgs, ImportFormat};
+use anyhow::Result;
+
+pub fn import(args: ImportArgs) -> Result<()> {
+	let format = match args.from {
+		ImportFormat::LightGbm => modelfox_import::ImportFormat::LightGbm,
+		ImportFormat::XgBoost => modelfox_import::ImportFormat::XgBoost,
+	};
+	let bytes = std::fs::read(&args.model)?;
+	let schema = std::fs::read(&args.schema)?;
+	let schema: modelfox_import::Schema = serde_json::from_slice(&schema)?;
+	let model = modelfox_import::import(format, &bytes, &schema)?;
+	let output_path = args
+		.output
+		.unwrap_or_else(|| args.model.with_extension("modelfox"));
+	model.to_path(&output_path)?;
+	eprintln!("Your model was imported to {}.", output_path.display());
+	Ok(())
+}
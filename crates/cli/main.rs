@@ -7,8 +7,14 @@ use tracing_subscriber::prelude::*;
 
 #[cfg(feature = "modelfox_app")]
 mod app;
+#[cfg(feature = "export")]
+mod export;
+#[cfg(feature = "import")]
+mod import;
 #[cfg(feature = "modelfox_app")]
 mod migrate;
+#[cfg(feature = "optimize")]
+mod optimize;
 #[cfg(feature = "train")]
 mod predict;
 #[cfg(feature = "serve")]
@@ -35,6 +41,18 @@ enum Subcommand {
 	#[cfg(feature = "train")]
 	#[clap(name = "predict")]
 	Predict(Box<PredictArgs>),
+	#[cfg(feature = "export")]
+	#[clap(name = "export")]
+	Export(Box<ExportArgs>),
+	#[cfg(feature = "export")]
+	#[clap(name = "codegen")]
+	Codegen(Box<CodegenArgs>),
+	#[cfg(feature = "import")]
+	#[clap(name = "import")]
+	Import(Box<ImportArgs>),
+	#[cfg(feature = "optimize")]
+	#[clap(name = "optimize")]
+	Optimize(Box<OptimizeArgs>),
 	#[cfg(feature = "modelfox_app")]
 	#[clap(name = "app")]
 	App(Box<AppArgs>),
@@ -78,8 +96,15 @@ pub struct TrainArgs {
 	target: String,
 	#[clap(short, long, help = "the path to a config file")]
 	config: Option<PathBuf>,
+	#[clap(long, help = "the path to a .modelfox file to continue training from")]
+	init_model: Option<PathBuf>,
 	#[clap(short, long, help = "the path to write the .modelfox file to")]
 	output: Option<PathBuf>,
+	#[clap(
+		long,
+		help = "fail training if the target leakage and data quality checks find any issues"
+	)]
+	strict: bool,
 	#[clap(
 		long = "no-progress",
 		help = "disable the cli progress view",
@@ -115,10 +140,145 @@ pub struct PredictArgs {
 		help = "output probabilities instead of class labels, only relevant for classifier models"
 	)]
 	probabilities: Option<bool>,
-	#[clap(short, long, help = "The threshold value to use for predictions.")]
+	#[clap(
+		short,
+		long,
+		help = "The threshold value to use for predictions. If not specified, the threshold saved in the model is used."
+	)]
 	threshold: Option<f32>,
 }
 
+#[cfg(feature = "export")]
+#[derive(Parser)]
+#[clap(
+	about = "Export a model to another format.",
+	long_about = "Export a model to ONNX or PMML so it can be run by other inference engines."
+)]
+pub struct ExportArgs {
+	#[clap(long, arg_enum, help = "the format to export the model to")]
+	format: ExportFormat,
+	#[clap(help = "the path to the model to export")]
+	model: PathBuf,
+	#[clap(
+		short,
+		long,
+		help = "the path to write the exported model to, defaults to the model path with the format's extension"
+	)]
+	output: Option<PathBuf>,
+}
+
+#[cfg(feature = "export")]
+#[derive(clap::ArgEnum, Clone, Copy)]
+pub enum ExportFormat {
+	Onnx,
+	Pmml,
+}
+
+#[cfg(feature = "export")]
+#[derive(Parser)]
+#[clap(
+	about = "Generate source code for a model.",
+	long_about = "Generate standalone Rust or C source code that makes the same predictions as a model, with no dependency on the modelfox runtime."
+)]
+pub struct CodegenArgs {
+	#[clap(long, arg_enum, help = "the language to generate source code in")]
+	lang: CodegenLanguage,
+	#[clap(help = "the path to the model to generate source code for")]
+	model: PathBuf,
+	#[clap(
+		short,
+		long,
+		help = "the path to write the source code to, defaults to the model path with the language's extension"
+	)]
+	output: Option<PathBuf>,
+}
+
+#[cfg(feature = "export")]
+#[derive(clap::ArgEnum, Clone, Copy)]
+pub enum CodegenLanguage {
+	Rust,
+	C,
+}
+
+#[cfg(feature = "import")]
+#[derive(Parser)]
+#[clap(
+	about = "Import a model from another framework.",
+	long_about = "Import a LightGBM model saved in the text format or an XGBoost model saved in the JSON format."
+)]
+pub struct ImportArgs {
+	#[clap(long, arg_enum, help = "the framework the model was trained with")]
+	from: ImportFormat,
+	#[clap(help = "the path to the model to import")]
+	model: PathBuf,
+	#[clap(
+		long,
+		help = "the path to a json file describing the model's feature columns, in order, and its target column"
+	)]
+	schema: PathBuf,
+	#[clap(
+		short,
+		long,
+		help = "the path to write the .modelfox file to, defaults to the model path with the .modelfox extension"
+	)]
+	output: Option<PathBuf>,
+}
+
+#[cfg(feature = "import")]
+#[derive(clap::ArgEnum, Clone, Copy)]
+pub enum ImportFormat {
+	#[clap(name = "lightgbm")]
+	LightGbm,
+	#[clap(name = "xgboost")]
+	XgBoost,
+}
+
+#[cfg(feature = "optimize")]
+#[derive(Parser)]
+#[clap(
+	about = "Make a model smaller.",
+	long_about = "Make a model smaller by removing the features it does not use, quantizing its weights, and dropping the parts of the model that are only needed by the app."
+)]
+pub struct OptimizeArgs {
+	#[clap(help = "the path to the model to optimize")]
+	model: PathBuf,
+	#[clap(
+		long,
+		help = "remove the bag of words features and ngrams that the model does not use"
+	)]
+	prune: bool,
+	#[clap(
+		long,
+		arg_enum,
+		help = "store tree leaf values and linear weights with fewer bits"
+	)]
+	quantize: Option<Quantization>,
+	#[clap(
+		long,
+		help = "drop the stats, grid, losses, and explanations that are only needed by the app, leaving an inference only model"
+	)]
+	strip: bool,
+	#[clap(
+		short,
+		long,
+		help = "the path to a .csv file to compare the predictions of the optimized model with those of the original model"
+	)]
+	file: Option<PathBuf>,
+	#[clap(
+		short,
+		long,
+		help = "the path to write the optimized model to, defaults to the model path with the .optimized.modelfox extension"
+	)]
+	output: Option<PathBuf>,
+}
+
+#[cfg(feature = "optimize")]
+#[derive(clap::ArgEnum, Clone, Copy)]
+pub enum Quantization {
+	F16,
+	I8,
+}
+
 #[cfg(feature = "modelfox_app")]
 #[derive(Parser)]
 #[clap(about = "Run the app.", long_about = "Run the app.")]
@@ -170,6 +330,14 @@ fn main() {
 		Subcommand::Train(args) => self::train::train(*args),
 		#[cfg(feature = "train")]
 		Subcommand::Predict(args) => self::predict::predict(*args),
+		#[cfg(feature = "export")]
+		Subcommand::Export(args) => self::export::export(*args),
+		#[cfg(feature = "export")]
+		Subcommand::Codegen(args) => self::export::codegen(*args),
+		#[cfg(feature = "import")]
+		Subcommand::Import(args) => self::import::import(*args),
+		#[cfg(feature = "optimize")]
+		Subcommand::Optimize(args) => self::optimize::optimize(*args),
 		#[cfg(feature = "modelfox_app")]
 		Subcommand::App(args) => self::app::app(*args),
 		#[cfg(feature = "modelfox_app")]
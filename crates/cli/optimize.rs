@@ -0,0 +1,207 @@
+use crate::{OptimizeArgs, Quantization};
+use anyhow::Result;
+use modelfox_core::predict::{Model, PredictOptions, PredictOutput};
+use modelfox_table::prelude::*;
+use modelfox_zip::zip;
+use num::ToPrimitive;
+
+pub fn optimize(args: OptimizeArgs) -> Result<()> {
+	let bytes = std::fs::read(&args.model)?;
+	let options = modelfox_model::RewriteOptions {
+		prune_features: args.prune,
+		quantization: args.quantize.map(|quantization| match quantization {
+			Quantization::F16 => modelfox_model::Quantization::F16,
+			Quantization::I8 => modelfox_model::Quantization::I8,
+		}),
+		strip: args.strip,
+		..Default::default()
+	};
+	let optimized_bytes = modelfox_model::rewrite(&bytes, &options)?;
+	if let Some(file) = &args.file {
+		let model = modelfox_model::from_bytes(&bytes)?;
+		let target_column_name = target_column_name(model);
+		let model = Model::from(model);
+		let optimized_model = Model::from(modelfox_model::from_bytes(&optimized_bytes)?);
+		let (header, table) = read_table(file)?;
+		let options = PredictOptions::default();
+		let outputs = modelfox_core::predict::predict_table(&model, table.view(), &options);
+		let optimized_outputs =
+			modelfox_core::predict::predict_table(&optimized_model, table.view(), &options);
+		let target = header
+			.iter()
+			.position(|column_name| column_name == target_column_name)
+			.map(|index| match &table.columns()[index] {
+				TableColumn::Text(column) => column.iter().cloned().collect::<Vec<_>>(),
+				_ => unreachable!(),
+			});
+		print_comparison(&outputs, &optimized_outputs, target.as_deref());
+	}
+	let output_path = args
+		.output
+		.unwrap_or_else(|| args.model.with_extension("optimized.modelfox"));
+	std::fs::write(&output_path, &optimized_bytes)?;
+	eprintln!(
+		"Your model was reduced from {} bytes to {} bytes and written to {}.",
+		bytes.len(),
+		optimized_bytes.len(),
+		output_path.display()
+	);
+	Ok(())
+}
+
+/// Read the csv file at `path` into a table with a text column for each column in the csv. The columns are converted to the types the model expects when making the predictions.
+fn read_table(path: &std::path::Path) -> Result<(Vec<String>, Table)> {
+	let mut reader = csv::Reader::from_path(path)?;
+	let header = reader
+		.headers()?
+		.iter()
+		.map(|column_name| column_name.to_owned())
+		.collect::<Vec<_>>();
+	let mut columns: Vec<Vec<String>> = vec![Vec::new(); header.len()];
+	for record in reader.records() {
+		let record = record?;
+		for (column, value) in zip!(columns.iter_mut(), record.into_iter()) {
+			column.push(value.to_owned());
+		}
+	}
+	let mut table = Table::new(Vec::new(), Vec::new());
+	for (column_name, column) in zip!(header.iter(), columns) {
+		table
+			.columns_mut()
+			.push(TableColumn::Text(TextTableColumn::new(
+				Some(column_name.to_owned()),
+				column,
+			)));
+	}
+	Ok((header, table))
+}
+
+fn target_column_name(model: modelfox_model::ModelReader) -> &str {
+	match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			regressor.read().target_column_name()
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			binary_classifier.read().target_column_name()
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			multiclass_classifier.read().target_column_name()
+		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => ranker.read().target_column_name(),
+	}
+}
+
+/// Print how much the predictions of the optimized model differ from those of the original model. If the dataset has a value in the target column for each row, also print the accuracy or root mean squared error of each model.
+fn print_comparison(
+	outputs: &[PredictOutput],
+	optimized_outputs: &[PredictOutput],
+	target: Option<&[String]>,
+) {
+	let n_rows = outputs.len().to_f64().unwrap();
+	let mut max_difference: f64 = 0.0;
+	let mut n_changed_classes = 0;
+	let mut squared_error = 0.0;
+	let mut optimized_squared_error = 0.0;
+	let mut n_correct = 0;
+	let mut optimized_n_correct = 0;
+	for (index, (output, optimized_output)) in
+		zip!(outputs.iter(), optimized_outputs.iter()).enumerate()
+	{
+		let label = target.map(|target| target[index].as_str());
+		match (output, optimized_output) {
+			(PredictOutput::Regression(output), PredictOutput::Regression(optimized_output)) => {
+				let value = output.value.to_f64().unwrap();
+				let optimized_value = optimized_output.value.to_f64().unwrap();
+				max_difference = max_difference.max((value - optimized_value).abs());
+				if let Some(label) = label.and_then(|label| label.parse::<f64>().ok()) {
+					squared_error += (value - label).powi(2);
+					optimized_squared_error += (optimized_value - label).powi(2);
+				}
+			}
+			(
+				PredictOutput::BinaryClassification(output),
+				PredictOutput::BinaryClassification(optimized_output),
+			) => {
+				// The probability is that of the predicted class, so it is only comparable when both models predict the same class.
+				let difference = if output.class_name == optimized_output.class_name {
+					(output.probability - optimized_output.probability).abs()
+				} else {
+					(output.probability - (1.0 - optimized_output.probability)).abs()
+				};
+				max_difference = max_difference.max(difference.to_f64().unwrap());
+				if output.class_name != optimized_output.class_name {
+					n_changed_classes += 1;
+				}
+				if label == Some(output.class_name.as_str()) {
+					n_correct += 1;
+				}
+				if label == Some(optimized_output.class_name.as_str()) {
+					optimized_n_correct += 1;
+				}
+			}
+			(
+				PredictOutput::MulticlassClassification(output),
+				PredictOutput::MulticlassClassification(optimized_output),
+			) => {
+				for (probability, optimized_probability) in zip!(
+					output.probabilities.values(),
+					optimized_output.probabilities.values()
+				) {
+					let difference = (probability - optimized_probability).abs();
+					max_difference = max_difference.max(difference.to_f64().unwrap());
+				}
+				if output.class_name != optimized_output.class_name {
+					n_changed_classes += 1;
+				}
+				if label == Some(output.class_name.as_str()) {
+					n_correct += 1;
+				}
+				if label == Some(optimized_output.class_name.as_str()) {
+					optimized_n_correct += 1;
+				}
+			}
+			(PredictOutput::Ranking(output), PredictOutput::Ranking(optimized_output)) => {
+				let difference = (output.score - optimized_output.score).abs();
+				max_difference = max_difference.max(difference.to_f64().unwrap());
+			}
+			_ => unreachable!(),
+		}
+	}
+	match outputs.first() {
+		Some(PredictOutput::Regression(_)) => {
+			eprintln!(
+				"The largest difference between the predictions of the original and optimized models is {}.",
+				max_difference
+			);
+			if target.is_some() {
+				eprintln!(
+					"The root mean squared error is {} for the original model and {} for the optimized model.",
+					(squared_error / n_rows).sqrt(),
+					(optimized_squared_error / n_rows).sqrt()
+				);
+			}
+		}
+		Some(PredictOutput::Ranking(_)) => {
+			eprintln!(
+				"The largest difference between the scores of the original and optimized models is {}.",
+				max_difference
+			);
+		}
+		Some(_) => {
+			eprintln!(
+				"The optimized model predicted a different class for {} of {} rows. The largest difference between the probabilities of the original and optimized models is {}.",
+				n_changed_classes,
+				outputs.len(),
+				max_difference
+			);
+			if target.is_some() {
+				eprintln!(
+					"The accuracy is {} for the original model and {} for the optimized model.",
+					n_correct.to_f64().unwrap() / n_rows,
+					optimized_n_correct.to_f64().unwrap() / n_rows
+				);
+			}
+		}
+		None => {}
+	}
+}
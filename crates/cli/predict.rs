@@ -2,10 +2,11 @@ use crate::PredictArgs;
 use anyhow::Result;
 use either::Either;
 use itertools::Itertools;
-use modelfox_core::predict::{PredictInput, PredictInputValue, PredictOptions};
+use modelfox_core::predict::PredictOptions;
+use modelfox_table::prelude::*;
 use modelfox_zip::zip;
 
-const PREDICT_CHUNK_SIZE: usize = 100;
+const PREDICT_CHUNK_SIZE: usize = 65536;
 
 pub fn predict(args: PredictArgs) -> Result<()> {
 	let bytes = std::fs::read(&args.model)?;
@@ -20,15 +21,14 @@ pub fn predict(args: PredictArgs) -> Result<()> {
 		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
 			multiclass_classifier.read().target_column_name()
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => ranker.read().target_column_name(),
 	};
 	let model = modelfox_core::predict::Model::from(model);
-	let mut options = PredictOptions {
+	let options = PredictOptions {
+		threshold: args.threshold,
 		compute_feature_contributions: false,
-		..Default::default()
+		compute_feature_interactions: false,
 	};
-	if let Some(threshold) = args.threshold {
-		options.threshold = threshold;
-	}
 	let reader = match args.file {
 		Some(path) => Either::Left(std::fs::File::open(path)?),
 		None => Either::Right(std::io::stdin()),
@@ -67,25 +67,31 @@ pub fn predict(args: PredictArgs) -> Result<()> {
 				writer.write_record(&[target_column_name])?;
 			}
 		}
+		// A ranker outputs a score for each row rather than a value of the target column.
+		modelfox_core::predict::ModelInner::Ranker(_) => {
+			writer.write_record(&["score"])?;
+		}
 	};
 	let header = reader.headers()?.to_owned();
 	for records in &reader.records().chunks(PREDICT_CHUNK_SIZE) {
-		let input: Vec<PredictInput> = records
-			.into_iter()
-			.map(|record| -> Result<PredictInput> {
-				let record = record?;
-				let input = zip!(header.iter(), record.into_iter())
-					.map(|(column_name, value)| {
-						(
-							column_name.to_owned(),
-							PredictInputValue::String(value.to_owned()),
-						)
-					})
-					.collect();
-				Ok(PredictInput(input))
-			})
-			.collect::<Result<_, _>>()?;
-		let output = modelfox_core::predict::predict(&model, &input, &options);
+		// Read the chunk into a table with a text column for each column in the csv. The columns are converted to the types the model expects when making the predictions.
+		let mut columns: Vec<Vec<String>> = vec![Vec::new(); header.len()];
+		for record in records {
+			let record = record?;
+			for (column, value) in zip!(columns.iter_mut(), record.into_iter()) {
+				column.push(value.to_owned());
+			}
+		}
+		let mut table = Table::new(Vec::new(), Vec::new());
+		for (column_name, column) in zip!(header.iter(), columns) {
+			table
+				.columns_mut()
+				.push(TableColumn::Text(TextTableColumn::new(
+					Some(column_name.to_owned()),
+					column,
+				)));
+		}
+		let output = modelfox_core::predict::predict_table(&model, table.view(), &options);
 		for output in output {
 			let output = match output {
 				modelfox_core::predict::PredictOutput::Regression(output) => {
@@ -125,6 +131,9 @@ pub fn predict(args: PredictArgs) -> Result<()> {
 						vec![output.class_name]
 					}
 				}
+				modelfox_core::predict::PredictOutput::Ranking(output) => {
+					vec![output.score.to_string()]
+				}
 			};
 			writer.write_record(&output)?;
 		}
@@ -78,12 +78,17 @@ pub fn train(args: TrainArgs) -> Result<()> {
 			}
 			_ => bail!("Must provide an output path when using stdin for input!"),
 		};
+		let mut config = modelfox_core::train::load_config(args.config.as_deref())?;
+		if args.strict {
+			config.checks.strict = true;
+		}
 		// Load the dataset, compute stats, and prepare for training.
 		let mut trainer = modelfox_core::train::Trainer::prepare(
 			modelfox_id::Id::generate(),
 			input,
 			&args.target,
-			args.config.as_deref(),
+			config,
+			args.init_model.as_deref(),
 			&mut handle_progress_event,
 		)?;
 		if let Some(progress_thread) = progress_thread.as_mut() {
@@ -0,0 +1,727 @@
+/*!
+This module implements the target leakage and data quality checks that run before training. They look for ID-like columns, columns that predict the target almost perfectly on their own, rows of the test dataset that also appear in the train dataset, and differences between the distributions of the train and test datasets.
+*/
+
+use crate::{
+	features::choose_feature_group_tree,
+	heuristics::{
+		CHECKS_ADVERSARIAL_MAX_ROUNDS, CHECKS_LEAKAGE_MAX_BINS, CHECKS_MAX_ROWS,
+		CHECKS_MIN_ADVERSARIAL_AUC_ROC, CHECKS_MIN_DUPLICATE_FRACTION,
+		CHECKS_MIN_ID_UNIQUE_FRACTION, CHECKS_MIN_LEAKAGE_SCORE, CHECKS_MIN_ROWS,
+	},
+	model::Task,
+	stats::ColumnStatsOutput,
+};
+use fnv::{FnvHashSet, FnvHasher};
+use modelfox_kill_chip::KillChip;
+use modelfox_table::prelude::*;
+use ndarray::prelude::*;
+use num::ToPrimitive;
+use std::{
+	hash::{Hash, Hasher},
+	num::NonZeroUsize,
+};
+
+/// This enum describes an issue found by the checks.
+#[derive(Clone, Debug)]
+pub enum DataQualityIssue {
+	/// `unique_fraction` of the column's valid values in the train dataset were unique, so it is likely an ID.
+	IdColumn {
+		column_name: String,
+		unique_fraction: f32,
+	},
+	/// A model that used only this column achieved `score` on the comparison dataset. The score is the accuracy for classifiers and the r-squared for regressors.
+	TargetLeakage { column_name: String, score: f32 },
+	/// `duplicate_count` rows of the test dataset, which is `duplicate_fraction` of it, also appeared in the train dataset.
+	DuplicateRows {
+		duplicate_count: usize,
+		duplicate_fraction: f32,
+	},
+	/// A model trained to distinguish rows of the train and test datasets achieved an AUC ROC of `auc_roc`. `column_name` is the column that was most important to it.
+	DistributionMismatch {
+		auc_roc: f32,
+		column_name: Option<String>,
+	},
+}
+
+impl std::fmt::Display for DataQualityIssue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			DataQualityIssue::IdColumn {
+				column_name,
+				unique_fraction,
+			} => write!(
+				f,
+				"The column \"{}\" looks like an ID. {:.1}% of its values in the train dataset are unique.",
+				column_name,
+				unique_fraction * 100.0,
+			),
+			DataQualityIssue::TargetLeakage { column_name, score } => write!(
+				f,
+				"The column \"{}\" may leak the target. A model that uses only this column scores {:.4} on the comparison dataset, where 1 is a perfect score.",
+				column_name, score,
+			),
+			DataQualityIssue::DuplicateRows {
+				duplicate_count,
+				duplicate_fraction,
+			} => write!(
+				f,
+				"{} row(s) of the test dataset, {:.1}% of it, also appear in the train dataset.",
+				duplicate_count,
+				duplicate_fraction * 100.0,
+			),
+			DataQualityIssue::DistributionMismatch {
+				auc_roc,
+				column_name,
+			} => {
+				write!(
+					f,
+					"The train and test datasets have different distributions. A model can tell their rows apart with an AUC ROC of {:.4}.",
+					auc_roc,
+				)?;
+				if let Some(column_name) = column_name {
+					write!(
+						f,
+						" The column \"{}\" differs the most between them.",
+						column_name
+					)?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+/**
+Run the checks. `train_column_stats` are the train stats of every column other than the target, and each table must contain the target column at `target_column_index`. If there is a column at `group_column_index` that identifies groups of rows, it is not a feature, so it is skipped like the target column.
+*/
+pub fn check_datasets(
+	table_train: &TableView,
+	table_comparison: &TableView,
+	table_test: &TableView,
+	target_column_index: usize,
+	group_column_index: Option<usize>,
+	task: Task,
+	train_column_stats: &[ColumnStatsOutput],
+) -> Vec<DataQualityIssue> {
+	let is_feature_column = |column_index| {
+		column_index != target_column_index && Some(column_index) != group_column_index
+	};
+	let mut issues = Vec::new();
+	let mut id_column_indexes = Vec::new();
+	for (column_index, column) in table_train.columns().iter().enumerate() {
+		if !is_feature_column(column_index) {
+			continue;
+		}
+		if let Some(unique_fraction) = id_unique_fraction(column) {
+			id_column_indexes.push(column_index);
+			issues.push(DataQualityIssue::IdColumn {
+				column_name: column.name().unwrap().to_owned(),
+				unique_fraction,
+			});
+		}
+	}
+	for (column_index, column) in table_train.columns().iter().enumerate() {
+		if !is_feature_column(column_index) || id_column_indexes.contains(&column_index) {
+			continue;
+		}
+		let score = single_column_score(
+			column,
+			&table_comparison.columns()[column_index],
+			&table_train.columns()[target_column_index],
+			&table_comparison.columns()[target_column_index],
+			task,
+		);
+		if let Some(score) = score {
+			issues.push(DataQualityIssue::TargetLeakage {
+				column_name: column.name().unwrap().to_owned(),
+				score,
+			});
+		}
+	}
+	if let Some(issue) = check_duplicate_rows(table_train, table_test) {
+		issues.push(issue);
+	}
+	if let Some(issue) = check_distribution_mismatch(
+		table_train,
+		table_test,
+		target_column_index,
+		group_column_index,
+		train_column_stats,
+	) {
+		issues.push(issue);
+	}
+	issues
+}
+
+/// If the column looks like an ID, return the fraction of its valid values that are unique. Number columns look like IDs if their values are integers and nearly unique. Text columns look like IDs if their values contain no whitespace and are nearly unique.
+fn id_unique_fraction(column: &TableColumnView) -> Option<f32> {
+	let (valid_count, unique_count) = match column {
+		TableColumnView::Number(column) => {
+			let values = &column.as_slice()[..column.len().min(CHECKS_MAX_ROWS)];
+			let mut unique_values = FnvHashSet::default();
+			let mut valid_count = 0;
+			for value in values.iter().filter(|value| value.is_finite()) {
+				if value.fract() != 0.0 {
+					return None;
+				}
+				valid_count += 1;
+				unique_values.insert(value.to_bits());
+			}
+			(valid_count, unique_values.len())
+		}
+		TableColumnView::Text(column) => {
+			let values = &column.as_slice()[..column.len().min(CHECKS_MAX_ROWS)];
+			let mut unique_values = FnvHashSet::default();
+			let mut valid_count = 0;
+			for value in values.iter().filter(|value| !value.is_empty()) {
+				if value.contains(char::is_whitespace) {
+					return None;
+				}
+				valid_count += 1;
+				unique_values.insert(value.as_str());
+			}
+			(valid_count, unique_values.len())
+		}
+		_ => return None,
+	};
+	if valid_count < CHECKS_MIN_ROWS {
+		return None;
+	}
+	let unique_fraction = unique_count.to_f32().unwrap() / valid_count.to_f32().unwrap();
+	if unique_fraction >= CHECKS_MIN_ID_UNIQUE_FRACTION {
+		Some(unique_fraction)
+	} else {
+		None
+	}
+}
+
+/// Fit a model that predicts the target from the bin of a single column on the train dataset and score it on the comparison dataset. This returns the score if it is high enough to suggest the column leaks the target.
+fn single_column_score(
+	column_train: &TableColumnView,
+	column_comparison: &TableColumnView,
+	labels_train: &TableColumnView,
+	labels_comparison: &TableColumnView,
+	task: Task,
+) -> Option<f32> {
+	let binner = Binner::fit(column_train)?;
+	let bins_train = binner.bins(column_train);
+	let bins_comparison = binner.bins(column_comparison);
+	let (score, baseline_score) = match task {
+		// A ranker's relevance labels are numbers, so they are scored the same way as a regressor's labels.
+		Task::Regression | Task::Ranking => {
+			let labels_train = labels_train.as_number().unwrap();
+			let labels_comparison = labels_comparison.as_number().unwrap();
+			regression_scores(
+				binner.n_bins(),
+				&bins_train,
+				labels_train.as_slice(),
+				&bins_comparison,
+				labels_comparison.as_slice(),
+			)?
+		}
+		Task::BinaryClassification | Task::MulticlassClassification => {
+			let labels_train = labels_train.as_enum().unwrap();
+			let labels_comparison = labels_comparison.as_enum().unwrap();
+			classification_scores(
+				binner.n_bins(),
+				labels_train.variants().len(),
+				&bins_train,
+				labels_train.as_slice(),
+				&bins_comparison,
+				labels_comparison.as_slice(),
+			)?
+		}
+	};
+	if score >= CHECKS_MIN_LEAKAGE_SCORE && baseline_score < CHECKS_MIN_LEAKAGE_SCORE {
+		Some(score)
+	} else {
+		None
+	}
+}
+
+/// This struct assigns the values of a number or enum column to bins. Invalid values are assigned to bin 0.
+enum Binner {
+	Number { thresholds: Vec<f32> },
+	Enum { n_variants: usize },
+}
+
+impl Binner {
+	fn fit(column: &TableColumnView) -> Option<Binner> {
+		match column {
+			TableColumnView::Number(column) => {
+				let mut values: Vec<f32> = column.as_slice()[..column.len().min(CHECKS_MAX_ROWS)]
+					.iter()
+					.cloned()
+					.filter(|value| value.is_finite())
+					.collect();
+				if values.is_empty() {
+					return None;
+				}
+				values.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+				let mut thresholds: Vec<f32> = (1..CHECKS_LEAKAGE_MAX_BINS)
+					.map(|bin_index| values[bin_index * values.len() / CHECKS_LEAKAGE_MAX_BINS])
+					.collect();
+				thresholds.dedup();
+				Some(Binner::Number { thresholds })
+			}
+			TableColumnView::Enum(column) => Some(Binner::Enum {
+				n_variants: column.variants().len(),
+			}),
+			_ => None,
+		}
+	}
+
+	fn n_bins(&self) -> usize {
+		match self {
+			Binner::Number { thresholds } => thresholds.len() + 2,
+			Binner::Enum { n_variants } => n_variants + 1,
+		}
+	}
+
+	fn bins(&self, column: &TableColumnView) -> Vec<usize> {
+		match (self, column) {
+			(Binner::Number { thresholds }, TableColumnView::Number(column)) => column
+				.iter()
+				.map(|value| {
+					if value.is_finite() {
+						1 + thresholds.partition_point(|threshold| threshold <= value)
+					} else {
+						0
+					}
+				})
+				.collect(),
+			(Binner::Enum { .. }, TableColumnView::Enum(column)) => column
+				.iter()
+				.map(|value| value.map(|value| value.get()).unwrap_or(0))
+				.collect(),
+			_ => unreachable!(),
+		}
+	}
+}
+
+/// Compute the r-squared on the comparison dataset of predicting the mean of each bin in the train dataset, along with the r-squared of predicting the overall mean.
+fn regression_scores(
+	n_bins: usize,
+	bins_train: &[usize],
+	labels_train: &[f32],
+	bins_comparison: &[usize],
+	labels_comparison: &[f32],
+) -> Option<(f32, f32)> {
+	let mut sums = vec![0.0f64; n_bins];
+	let mut counts = vec![0usize; n_bins];
+	for (bin, label) in bins_train.iter().zip(labels_train.iter()) {
+		if label.is_finite() {
+			sums[*bin] += f64::from(*label);
+			counts[*bin] += 1;
+		}
+	}
+	let total_count = counts.iter().sum::<usize>();
+	if total_count == 0 {
+		return None;
+	}
+	let mean_train = sums.iter().sum::<f64>() / total_count.to_f64().unwrap();
+	let comparison: Vec<(usize, f64)> = bins_comparison
+		.iter()
+		.zip(labels_comparison.iter())
+		.filter(|(_, label)| label.is_finite())
+		.map(|(bin, label)| (*bin, f64::from(*label)))
+		.collect();
+	if comparison.is_empty() {
+		return None;
+	}
+	let mean_comparison =
+		comparison.iter().map(|(_, label)| label).sum::<f64>() / comparison.len().to_f64().unwrap();
+	let total_sum_of_squares = comparison
+		.iter()
+		.map(|(_, label)| (label - mean_comparison).powi(2))
+		.sum::<f64>();
+	if total_sum_of_squares <= 0.0 {
+		return None;
+	}
+	let mut sum_of_squared_errors = 0.0;
+	let mut baseline_sum_of_squared_errors = 0.0;
+	for (bin, label) in comparison.iter() {
+		let prediction = if counts[*bin] > 0 {
+			sums[*bin] / counts[*bin].to_f64().unwrap()
+		} else {
+			mean_train
+		};
+		sum_of_squared_errors += (label - prediction).powi(2);
+		baseline_sum_of_squared_errors += (label - mean_train).powi(2);
+	}
+	let score = 1.0 - sum_of_squared_errors / total_sum_of_squares;
+	let baseline_score = 1.0 - baseline_sum_of_squared_errors / total_sum_of_squares;
+	Some((score.to_f32().unwrap(), baseline_score.to_f32().unwrap()))
+}
+
+/// Compute the accuracy on the comparison dataset of predicting the most frequent class of each bin in the train dataset, along with the accuracy of predicting the overall most frequent class.
+fn classification_scores(
+	n_bins: usize,
+	n_classes: usize,
+	bins_train: &[usize],
+	labels_train: &[Option<NonZeroUsize>],
+	bins_comparison: &[usize],
+	labels_comparison: &[Option<NonZeroUsize>],
+) -> Option<(f32, f32)> {
+	let mut counts = Array2::<usize>::zeros((n_bins, n_classes));
+	for (bin, label) in bins_train.iter().zip(labels_train.iter()) {
+		if let Some(label) = label {
+			counts[(*bin, label.get() - 1)] += 1;
+		}
+	}
+	let overall_counts = counts.sum_axis(Axis(0));
+	if overall_counts.sum() == 0 {
+		return None;
+	}
+	let baseline_prediction = argmax(overall_counts.view());
+	let predictions: Vec<usize> = counts
+		.axis_iter(Axis(0))
+		.map(|bin_counts| {
+			if bin_counts.sum() > 0 {
+				argmax(bin_counts)
+			} else {
+				baseline_prediction
+			}
+		})
+		.collect();
+	let mut total_count = 0;
+	let mut correct_count = 0;
+	let mut baseline_correct_count = 0;
+	for (bin, label) in bins_comparison.iter().zip(labels_comparison.iter()) {
+		if let Some(label) = label {
+			total_count += 1;
+			if predictions[*bin] == label.get() - 1 {
+				correct_count += 1;
+			}
+			if baseline_prediction == label.get() - 1 {
+				baseline_correct_count += 1;
+			}
+		}
+	}
+	if total_count == 0 {
+		return None;
+	}
+	let total_count = total_count.to_f32().unwrap();
+	Some((
+		correct_count.to_f32().unwrap() / total_count,
+		baseline_correct_count.to_f32().unwrap() / total_count,
+	))
+}
+
+fn argmax(counts: ArrayView1<usize>) -> usize {
+	counts
+		.iter()
+		.enumerate()
+		.max_by_key(|(_, count)| **count)
+		.map(|(class_index, _)| class_index)
+		.unwrap()
+}
+
+/// Count the rows of the test dataset whose values in every column, including the target, match a row of the train dataset.
+fn check_duplicate_rows(
+	table_train: &TableView,
+	table_test: &TableView,
+) -> Option<DataQualityIssue> {
+	if table_test.nrows() == 0 {
+		return None;
+	}
+	let train_row_hashes: FnvHashSet<u64> = (0..table_train.nrows())
+		.map(|index| row_hash(table_train, index))
+		.collect();
+	let duplicate_count = (0..table_test.nrows())
+		.filter(|index| train_row_hashes.contains(&row_hash(table_test, *index)))
+		.count();
+	let duplicate_fraction =
+		duplicate_count.to_f32().unwrap() / table_test.nrows().to_f32().unwrap();
+	if duplicate_count > 0 && duplicate_fraction >= CHECKS_MIN_DUPLICATE_FRACTION {
+		Some(DataQualityIssue::DuplicateRows {
+			duplicate_count,
+			duplicate_fraction,
+		})
+	} else {
+		None
+	}
+}
+
+fn row_hash(table: &TableView, index: usize) -> u64 {
+	let mut hasher = FnvHasher::default();
+	for column in table.columns().iter() {
+		match column {
+			TableColumnView::Unknown(_) => {}
+			TableColumnView::Number(column) => column.as_slice()[index].to_bits().hash(&mut hasher),
+			TableColumnView::Enum(column) => column.as_slice()[index].hash(&mut hasher),
+			TableColumnView::Text(column) => column.as_slice()[index].hash(&mut hasher),
+		}
+	}
+	hasher.finish()
+}
+
+/// Train a small tree model to distinguish rows of the train dataset from rows of the test dataset, a technique known as adversarial validation. Rows from each dataset are interleaved, the model is trained on the first half of them, and its AUC ROC is computed on the second half.
+fn check_distribution_mismatch(
+	table_train: &TableView,
+	table_test: &TableView,
+	target_column_index: usize,
+	group_column_index: Option<usize>,
+	train_column_stats: &[ColumnStatsOutput],
+) -> Option<DataQualityIssue> {
+	let n_rows = table_train
+		.nrows()
+		.min(table_test.nrows())
+		.min(CHECKS_MAX_ROWS);
+	if n_rows < CHECKS_MIN_ROWS {
+		return None;
+	}
+	let column_indexes: Vec<usize> = (0..table_train.ncols())
+		.filter(|column_index| {
+			*column_index != target_column_index && Some(*column_index) != group_column_index
+		})
+		.collect();
+	let group_column_name = group_column_index
+		.and_then(|group_column_index| table_train.columns()[group_column_index].name());
+	let table_train = table_train.view_columns(&column_indexes);
+	let table_test = table_test.view_columns(&column_indexes);
+	let mut table = Table::new(
+		table_train
+			.columns()
+			.iter()
+			.map(|column| column.name().map(ToOwned::to_owned))
+			.collect(),
+		table_train.columns().iter().map(column_type).collect(),
+	);
+	// The label of rows from the train dataset is 1 and the label of rows from the test dataset is 2.
+	let mut labels = Vec::with_capacity(2 * n_rows);
+	for index in 0..n_rows {
+		table.push_row(&table_train, index);
+		labels.push(NonZeroUsize::new(1));
+		table.push_row(&table_test, index);
+		labels.push(NonZeroUsize::new(2));
+	}
+	let feature_groups: Vec<(&str, modelfox_features::FeatureGroup)> = train_column_stats
+		.iter()
+		.filter(|column_stats| Some(column_stats.column_name()) != group_column_name)
+		.filter_map(|column_stats| {
+			let feature_group = choose_feature_group_tree(column_stats, None, None, false)?;
+			Some((column_stats.column_name(), feature_group))
+		})
+		.collect();
+	if feature_groups.is_empty() {
+		return None;
+	}
+	let (column_names, feature_groups): (Vec<&str>, Vec<modelfox_features::FeatureGroup>) =
+		feature_groups.into_iter().unzip();
+	// Split at an even row so each half has as many rows from the train dataset as from the test dataset.
+	let split_index = 2 * (n_rows / 2);
+	let (table_fit, table_evaluate) = table.view().split_at_row(split_index);
+	let variants = vec!["train".to_owned(), "test".to_owned()];
+	let labels_fit = EnumTableColumn::new(None, variants, labels[..split_index].to_owned());
+	let features_fit =
+		modelfox_features::compute_features_table(&table_fit, &feature_groups, &|_| {});
+	let train_options = modelfox_tree::TrainOptions {
+		max_rounds: CHECKS_ADVERSARIAL_MAX_ROUNDS,
+		..Default::default()
+	};
+	let kill_chip = KillChip::default();
+	let progress = modelfox_tree::Progress {
+		kill_chip: &kill_chip,
+		handle_progress_event: &mut |_| {},
+	};
+	let train_output = modelfox_tree::BinaryClassifier::train(
+		features_fit.view(),
+		labels_fit.view(),
+		&train_options,
+		progress,
+	);
+	let features_evaluate =
+		modelfox_features::compute_features_array_value(&table_evaluate, &feature_groups, &|| {});
+	let mut probabilities = Array::zeros(features_evaluate.nrows());
+	train_output
+		.model
+		.predict(features_evaluate.view(), probabilities.view_mut());
+	let auc_roc = modelfox_metrics::AucRoc::compute(
+		probabilities
+			.iter()
+			.cloned()
+			.zip(labels[split_index..].iter().map(|label| label.unwrap()))
+			.collect(),
+	);
+	if auc_roc < CHECKS_MIN_ADVERSARIAL_AUC_ROC {
+		return None;
+	}
+	// Report the column whose features were the most important to the model.
+	let column_name = train_output
+		.feature_importances
+		.and_then(|feature_importances| {
+			let mut feature_importances = feature_importances.into_iter();
+			column_names
+				.iter()
+				.zip(feature_groups.iter())
+				.map(|(column_name, feature_group)| {
+					let importance = feature_importances
+						.by_ref()
+						.take(feature_group.n_features())
+						.sum::<f32>();
+					(column_name, importance)
+				})
+				.max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+				.map(|(column_name, _)| (*column_name).to_owned())
+		});
+	Some(DataQualityIssue::DistributionMismatch {
+		auc_roc,
+		column_name,
+	})
+}
+
+fn column_type(column: &TableColumnView) -> TableColumnType {
+	match column {
+		TableColumnView::Unknown(_) => TableColumnType::Unknown,
+		TableColumnView::Number(_) => TableColumnType::Number,
+		TableColumnView::Enum(column) => TableColumnType::Enum {
+			variants: column.variants().to_owned(),
+		},
+		TableColumnView::Text(_) => TableColumnType::Text,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_common::{column_stats, number_column, view};
+	use std::ops::Range;
+
+	/// Create a dataset with a row for each id. The "x" column is unrelated to the "label" column, which is the target.
+	fn dataset(ids: Range<usize>) -> Vec<TableColumn> {
+		let ids: Vec<usize> = ids.collect();
+		let column =
+			|name: &str, f: &dyn Fn(usize) -> f32| number_column(name, ids.iter().map(|id| f(*id)));
+		vec![
+			column("id", &|id| id.to_f32().unwrap()),
+			column("x", &|id| ((id * 7) % 13).to_f32().unwrap() + 0.5),
+			column("label", &|id| (id % 3).to_f32().unwrap()),
+		]
+	}
+
+	#[test]
+	fn test_check_datasets_skips_group_column() {
+		let train = dataset(0..200);
+		let comparison = dataset(1000..1200);
+		let test = dataset(2000..2200);
+		let (train, comparison, test) = (view(&train), view(&comparison), view(&test));
+		let train_column_stats = column_stats(&train, 2);
+		// The group column of a ranking task is skipped the same way.
+		for task in [Task::Regression, Task::Ranking] {
+			let issues = check_datasets(
+				&train,
+				&comparison,
+				&test,
+				2,
+				None,
+				task,
+				&train_column_stats,
+			);
+			assert!(issues.iter().any(|issue| matches!(
+				issue,
+				DataQualityIssue::IdColumn { column_name, .. } if column_name == "id"
+			)));
+			// As the group column, the id column is neither reported nor used to tell the datasets apart.
+			let issues = check_datasets(
+				&train,
+				&comparison,
+				&test,
+				2,
+				Some(0),
+				task,
+				&train_column_stats,
+			);
+			assert!(issues.is_empty(), "{:?}", issues);
+		}
+	}
+
+	#[test]
+	fn test_check_datasets() {
+		// Add a column that is the label in disguise.
+		let with_leak = |mut columns: Vec<TableColumn>| {
+			let labels = columns[2].as_number().unwrap().view().as_slice().to_owned();
+			columns.push(number_column(
+				"leak",
+				labels.iter().map(|label| 10.0 * label + 1.0),
+			));
+			columns
+		};
+		let train = with_leak(dataset(0..200));
+		let comparison = with_leak(dataset(1000..1200));
+		let test = with_leak(dataset(2000..2200));
+		let (train, comparison, test) = (view(&train), view(&comparison), view(&test));
+		let train_column_stats = column_stats(&train, 2);
+		let issues = check_datasets(
+			&train,
+			&comparison,
+			&test,
+			2,
+			None,
+			Task::Regression,
+			&train_column_stats,
+		);
+		assert_eq!(issues.len(), 3, "{:?}", issues);
+		assert!(matches!(
+			&issues[0],
+			DataQualityIssue::IdColumn { column_name, unique_fraction }
+				if column_name == "id" && *unique_fraction == 1.0
+		));
+		// The id column is not checked for leakage, and the "x" column does not leak the target.
+		assert!(matches!(
+			&issues[1],
+			DataQualityIssue::TargetLeakage { column_name, score }
+				if column_name == "leak" && *score == 1.0
+		));
+		// The ids of the train and test datasets do not overlap, so they tell the datasets apart.
+		assert!(matches!(
+			&issues[2],
+			DataQualityIssue::DistributionMismatch { auc_roc, column_name: Some(column_name) }
+				if *auc_roc > 0.99 && column_name == "id"
+		));
+	}
+
+	#[test]
+	fn test_check_duplicate_rows() {
+		let train = dataset(0..200);
+		// A quarter of these rows also appear in the train dataset.
+		let test = dataset(150..350);
+		let issue = check_duplicate_rows(&view(&train), &view(&test));
+		assert!(matches!(
+			issue,
+			Some(DataQualityIssue::DuplicateRows {
+				duplicate_count: 50,
+				duplicate_fraction,
+			}) if duplicate_fraction == 0.25
+		));
+		let test = dataset(200..400);
+		assert!(check_duplicate_rows(&view(&train), &view(&test)).is_none());
+		let test = dataset(0..0);
+		assert!(check_duplicate_rows(&view(&train), &view(&test)).is_none());
+	}
+
+	#[test]
+	fn test_check_distribution_mismatch() {
+		// Without the id column, the train and test datasets have the same distribution.
+		let train = dataset(0..200);
+		let test = dataset(2000..2200);
+		let (train, test) = (view(&train[1..]), view(&test[1..]));
+		let train_column_stats = column_stats(&train, 1);
+		assert!(check_distribution_mismatch(&train, &test, 1, None, &train_column_stats).is_none());
+		// Shifting the "x" column of the test dataset makes them different.
+		let mut test = dataset(2000..2200);
+		for value in test[1].as_number_mut().unwrap().iter_mut() {
+			*value += 100.0;
+		}
+		let test = view(&test[1..]);
+		let issue = check_distribution_mismatch(&train, &test, 1, None, &train_column_stats);
+		assert!(matches!(
+			issue,
+			Some(DataQualityIssue::DistributionMismatch {
+				column_name: Some(column_name),
+				..
+			}) if column_name == "x"
+		));
+	}
+}
@@ -2,6 +2,8 @@
 This module defines the `Config` struct, which is used to configure training a model with [`train`](crate::train::train).
 */
 
+use std::{num::NonZeroUsize, path::PathBuf};
+
 /// This is a configuration used for training.
 #[derive(Debug, Default, serde::Deserialize)]
 #[serde(default, deny_unknown_fields)]
@@ -12,6 +14,8 @@ pub struct Config {
 	pub features: Features,
 	// Use this field to configure training.
 	pub train: Train,
+	/// Use this field to configure the target leakage and data quality checks that run before training.
+	pub checks: Checks,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -25,6 +29,24 @@ pub struct Dataset {
 	pub test_fraction: f32,
 	/// Use this field to specify the column types for a subset of the columns. If you do not configure a column here, its configuration will be inferred.
 	pub columns: Vec<Column>,
+	/// Use this field to train on a dataset that is too large to fit in memory. The train file is read in chunks instead of being loaded all at once. It requires training from a file, not stdin.
+	pub out_of_core: Option<OutOfCore>,
+	/// Set this to the name of the column that identifies the query group of each row to train a ranker instead of a regressor or classifier. The target column must be a number column holding the relevance of each row to its query, where higher values are more relevant. The rows of each query group are kept together when the dataset is shuffled and split, and the group column is not used as a feature.
+	pub group_column: Option<String>,
+}
+
+/// This option enables out of core training. Rows are assigned to the train, comparison, and test datasets at random as they are read, so the `shuffle` option only sets the seed. Only the comparison and test datasets, a sample of the train dataset, and the binned features for tree models are held in memory. Early stopping is disabled.
+#[derive(Debug, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutOfCore {
+	/// This is the number of rows read from the train file at a time. The default value is `100000`.
+	pub chunk_size: usize,
+	/// This is the number of rows of the train dataset sampled to compute the bin thresholds for tree models. The default value is `200000`.
+	pub sample_size: usize,
+	/// This is the maximum number of rows kept in memory for the comparison dataset. If more rows are set aside for comparison, a uniform sample of them is kept. The default value is `1000000`.
+	pub max_comparison_rows: usize,
+	/// This is the maximum number of rows kept in memory for the test dataset. If more rows are set aside for testing, a uniform sample of them is kept. The default value is `1000000`.
+	pub max_test_rows: usize,
 }
 
 /// This option controls whether the dataset should be shuffled before splitting and training.
@@ -80,6 +102,22 @@ pub struct Features {
 	pub auto: AutoFeatures,
 	/// Use this field to include custom feature groups.
 	pub include: Option<Vec<FeatureGroup>>,
+	/// Use this field to enable automatic feature selection, which drops columns from automatic feature engineering before training.
+	pub selection: Option<FeatureSelection>,
+}
+
+/// The parameters in this struct control automatic feature selection. Only columns whose feature groups are chosen automatically are considered, and each column that is dropped is recorded in the model along with the reason it was dropped.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeatureSelection {
+	/// Drop number and enum columns whose most frequent value makes up at least this fraction of their valid values. The default is 0.99.
+	pub max_most_frequent_fraction: Option<f32>,
+	/// Drop number and enum columns with at least this fraction of invalid values. The default is 0.5.
+	pub max_invalid_fraction: Option<f32>,
+	/// Drop number columns whose absolute correlation with a number column that was kept is at least this value. The default is 0.95.
+	pub max_correlation: Option<f32>,
+	/// Drop columns whose feature importance in a quick probe model is less than this value. The feature importances of the probe model sum to 1. The default is 0.001, and setting it to 0 skips training the probe model.
+	pub min_importance: Option<f32>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -89,6 +127,12 @@ pub struct AutoFeatures {
 	pub enable: bool,
 	/// Exclude columns from automatic feature engineering.
 	pub exclude_columns: Option<Vec<String>>,
+	/// Use this field to control how invalid values are imputed in the number and enum feature groups chosen automatically.
+	#[serde(default)]
+	pub impute: Option<Impute>,
+	/// Enable this field to add a feature indicating whether the value was invalid to the number and enum feature groups chosen automatically.
+	#[serde(default)]
+	pub missing_indicator: bool,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -104,18 +148,109 @@ pub enum FeatureGroup {
 	BagOfWords(BagOfWordsFeatureGroup),
 	#[serde(rename = "bag_of_words_cosine_similarity")]
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureGroup),
+	#[serde(rename = "hashed_bag_of_words")]
+	HashedBagOfWords(HashedBagOfWordsFeatureGroup),
+	#[serde(rename = "hashed_enum")]
+	HashedEnum(HashedEnumFeatureGroup),
+	#[serde(rename = "word_embedding")]
+	WordEmbedding(WordEmbeddingFeatureGroup),
+	#[serde(rename = "interaction")]
+	Interaction(InteractionFeatureGroup),
+	#[serde(rename = "polynomial")]
+	Polynomial(PolynomialFeatureGroup),
+	#[serde(rename = "binned_number")]
+	BinnedNumber(BinnedNumberFeatureGroup),
+}
+
+impl FeatureGroup {
+	/// Return the names of the columns this feature group is computed from.
+	pub fn source_column_names(&self) -> Vec<&str> {
+		match self {
+			FeatureGroup::Identity(feature_group) => {
+				vec![feature_group.source_column_name.as_str()]
+			}
+			FeatureGroup::Normalized(feature_group) => {
+				vec![feature_group.source_column_name.as_str()]
+			}
+			FeatureGroup::OneHotEncoded(feature_group) => {
+				vec![feature_group.source_column_name.as_str()]
+			}
+			FeatureGroup::BagOfWords(feature_group) => {
+				vec![feature_group.source_column_name.as_str()]
+			}
+			FeatureGroup::BagOfWordsCosineSimilarity(feature_group) => vec![
+				feature_group.source_column_name_a.as_str(),
+				feature_group.source_column_name_b.as_str(),
+			],
+			FeatureGroup::HashedBagOfWords(feature_group) => {
+				vec![feature_group.source_column_name.as_str()]
+			}
+			FeatureGroup::HashedEnum(feature_group) => {
+				vec![feature_group.source_column_name.as_str()]
+			}
+			FeatureGroup::WordEmbedding(feature_group) => {
+				vec![feature_group.source_column_name.as_str()]
+			}
+			FeatureGroup::Interaction(feature_group) => vec![
+				feature_group.source_column_name_a.as_str(),
+				feature_group.source_column_name_b.as_str(),
+			],
+			FeatureGroup::Polynomial(feature_group) => {
+				vec![feature_group.source_column_name.as_str()]
+			}
+			FeatureGroup::BinnedNumber(feature_group) => {
+				vec![feature_group.source_column_name.as_str()]
+			}
+		}
+	}
 }
 
 #[derive(Debug, serde::Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct IdentityFeatureGroup {
 	pub source_column_name: String,
+	/// Use this field to control how invalid values in the source column are imputed.
+	#[serde(default)]
+	pub impute: Option<Impute>,
+	/// Enable this field to add a feature indicating whether the value in the source column was invalid.
+	#[serde(default)]
+	pub missing_indicator: bool,
 }
 
 #[derive(Debug, serde::Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct NormalizedFeatureGroup {
 	pub source_column_name: String,
+	/// Use this field to control how invalid values in the source column are imputed.
+	#[serde(default)]
+	pub impute: Option<Impute>,
+	/// Enable this field to add a feature indicating whether the value in the source column was invalid.
+	#[serde(default)]
+	pub missing_indicator: bool,
+}
+
+/// An `Impute` chooses the value that replaces invalid values in a number or enum column. Enum columns are always imputed with their most frequent variant in the train dataset, regardless of the strategy.
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "strategy")]
+pub enum Impute {
+	/// Impute the mean of the column's values in the train dataset.
+	#[serde(rename = "mean")]
+	Mean,
+	/// Impute the median of the column's values in the train dataset.
+	#[serde(rename = "median")]
+	Median,
+	/// Impute the most frequent of the column's values in the train dataset.
+	#[serde(rename = "mode", alias = "most_frequent")]
+	Mode,
+	/// Impute the provided value.
+	#[serde(rename = "constant")]
+	Constant(ImputeConstant),
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ImputeConstant {
+	pub value: f32,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -129,6 +264,75 @@ pub struct OneHotEncodedFeatureGroup {
 pub struct BagOfWordsFeatureGroup {
 	pub source_column_name: String,
 	pub strategy: Option<BagOfWordsFeatureGroupStrategy>,
+	/// Use this field to control how values in the source column are split into tokens. The tokenizer is stored in the model, so prediction tokenizes the same way.
+	#[serde(default)]
+	pub tokenizer: Option<Tokenizer>,
+	/// Use this field to choose which ngram types are counted. The default is unigrams and bigrams.
+	#[serde(default)]
+	pub ngram_types: Option<Vec<NGramType>>,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tokenizer {
+	/// Convert tokens to lowercase. The default is `true`.
+	pub lowercase: bool,
+	/// Convert tokens to Unicode normalization form NFKC. The default is `false`.
+	pub nfkc: bool,
+	/// Remove accents and other combining marks from tokens. The default is `false`.
+	pub fold_accents: bool,
+	/// Drop these words, either a built in list such as `"english"` or a list of words.
+	pub stop_words: Option<StopWords>,
+	/// Reduce each token to its stem.
+	pub stemmer: Option<Stemmer>,
+	/// Use the matches of this regular expression as the tokens instead of splitting on whitespace and punctuation.
+	pub token_pattern: Option<String>,
+}
+
+impl Default for Tokenizer {
+	fn default() -> Tokenizer {
+		Tokenizer {
+			lowercase: true,
+			nfkc: false,
+			fold_accents: false,
+			stop_words: None,
+			stemmer: None,
+			token_pattern: None,
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+#[serde(untagged)]
+pub enum StopWords {
+	Language(StopWordsLanguage),
+	Words(Vec<String>),
+}
+
+#[derive(Debug, PartialEq, Eq, serde::Deserialize)]
+pub enum StopWordsLanguage {
+	#[serde(rename = "english")]
+	English,
+}
+
+#[derive(Debug, PartialEq, Eq, serde::Deserialize)]
+pub enum Stemmer {
+	#[serde(rename = "english")]
+	English,
+}
+
+#[derive(Debug, PartialEq, Eq, serde::Deserialize)]
+pub enum NGramType {
+	#[serde(rename = "unigram")]
+	Unigram,
+	#[serde(rename = "bigram")]
+	Bigram,
+	#[serde(rename = "character_trigram")]
+	CharacterTrigram,
+	#[serde(rename = "character_quadgram")]
+	CharacterQuadgram,
+	#[serde(rename = "character_pentagram")]
+	CharacterPentagram,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -148,6 +352,105 @@ pub struct BagOfWordsCosineSimilarityFeatureGroup {
 	pub source_column_name_b: String,
 }
 
+/// A hashed bag of words feature group hashes each ngram in a text column to one of a fixed number of features, so the model does not need to store a vocabulary and ngrams that are rare in the train dataset are not dropped.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HashedBagOfWordsFeatureGroup {
+	pub source_column_name: String,
+	pub strategy: Option<HashedBagOfWordsFeatureGroupStrategy>,
+	/// This is the number of features. The default is 1024.
+	pub n_buckets: Option<NonZeroUsize>,
+	/// If this is true, each ngram adds either 1 or -1 to its feature depending on its hash, so ngrams that share a feature tend to cancel out instead of accumulating. The default is `true`.
+	pub signed: Option<bool>,
+	/// Use this field to control how values in the source column are split into tokens.
+	#[serde(default)]
+	pub tokenizer: Option<Tokenizer>,
+	/// Use this field to choose which ngram types are hashed. The default is unigrams and bigrams.
+	#[serde(default)]
+	pub ngram_types: Option<Vec<NGramType>>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub enum HashedBagOfWordsFeatureGroupStrategy {
+	#[serde(rename = "present")]
+	Present,
+	#[serde(rename = "count")]
+	Count,
+}
+
+/// A hashed enum feature group hashes each value of an enum or text column to one of a fixed number of features. Use it instead of one hot encoding for columns with many distinct values.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HashedEnumFeatureGroup {
+	pub source_column_name: String,
+	/// This is the number of features. The default is 1024.
+	pub n_buckets: Option<NonZeroUsize>,
+	/// If this is true, the feature for each value is set to either 1 or -1 depending on its hash. The default is `true`.
+	pub signed: Option<bool>,
+}
+
+/// A word embedding feature group represents a text column by the mean of the pretrained embeddings of its tokens. The embeddings are read from a file when training and stored in the model.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WordEmbeddingFeatureGroup {
+	pub source_column_name: String,
+	/// This is the path to a file of pretrained word embeddings, such as the vectors distributed by fastText or GloVe.
+	pub path: PathBuf,
+	/// This is the format of the file. The default is `binary` if the path ends with `.bin` and `text` otherwise.
+	pub format: Option<WordEmbeddingFormat>,
+	/// If this is true, only the embeddings of the most frequent tokens in the train dataset are stored in the model, which keeps the model small. The default is `true`.
+	pub restrict_to_vocabulary: Option<bool>,
+	/// Use this field to control how values in the source column are split into tokens. The tokens must match the words in the file, so you may need to disable `lowercase`.
+	#[serde(default)]
+	pub tokenizer: Option<Tokenizer>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub enum WordEmbeddingFormat {
+	/// Each line contains a word followed by the values of its embedding, as in fastText `.vec` and GloVe `.txt` files.
+	#[serde(rename = "text")]
+	Text,
+	/// The binary format written by word2vec.
+	#[serde(rename = "binary")]
+	Binary,
+}
+
+/// An interaction feature group multiplies the features of two columns, so a linear model can learn how the effect of one column depends on the other. Number columns are normalized and enum columns are one hot encoded before they are multiplied.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InteractionFeatureGroup {
+	pub source_column_name_a: String,
+	pub source_column_name_b: String,
+}
+
+/// A polynomial feature group creates a feature for each power of a normalized number column from 2 up to `degree`, so a linear model can fit a curve to the column.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolynomialFeatureGroup {
+	pub source_column_name: String,
+	/// This is the highest power. It must be at least 2. The default is 2.
+	pub degree: Option<usize>,
+}
+
+/// A binned number feature group splits a number column into bins at its quantiles in the train dataset and one hot encodes the bin each value falls into.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BinnedNumberFeatureGroup {
+	pub source_column_name: String,
+	/// This is the number of bins. It must be at least 2. The default is 10. There may be fewer bins if the column has many repeated values.
+	pub n_bins: Option<NonZeroUsize>,
+}
+
+/// The checks look for ID-like columns, columns that predict the target almost perfectly on their own, rows of the test dataset that also appear in the train dataset, and differences between the distributions of the train and test datasets. Each issue found is reported as a warning and recorded in the model.
+#[derive(Debug, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Checks {
+	/// Enable or disable the checks. The default is `true`.
+	pub enable: bool,
+	/// If this is `true`, training fails if any check finds an issue. The default is `false`.
+	pub strict: bool,
+}
+
 #[derive(Debug, Default, serde::Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Train {
@@ -157,6 +460,23 @@ pub struct Train {
 	pub grid: Option<Vec<GridItem>>,
 	/// This is the metric that will be computed on the comparison dataset to choose the best model.
 	pub comparison_metric: Option<ComparisonMetric>,
+	/// Use this field to transform the target column of a regressor before training. Predictions and metrics are always in the original scale of the target column.
+	pub target_transform: Option<TargetTransform>,
+	/// For rankers, this is the number of top rows in each query group whose NDCG is optimized during training and reported in the metrics. The default is to use all of the rows in each query group. Each round of training takes time proportional to the number of rows in each query group times `ndcg_k`, so set it when the query groups are large.
+	pub ndcg_k: Option<NonZeroUsize>,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize)]
+pub enum TargetTransform {
+	/// Transform the target with `ln(1 + y)`. The target must be greater than -1.
+	#[serde(rename = "log1p")]
+	Log1p,
+	/// Transform the target with the Box-Cox transform, choosing the lambda that maximizes the log likelihood on the train dataset. The target must be positive.
+	#[serde(rename = "box_cox")]
+	BoxCox,
+	/// Subtract the mean and divide by the standard deviation of the target in the train dataset.
+	#[serde(rename = "standardize")]
+	Standardize,
 }
 
 #[derive(Debug, Default, serde::Deserialize)]
@@ -183,29 +503,98 @@ pub enum GridItem {
 }
 
 /// These are the options used for training linear models.
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Default, serde::Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct LinearGridItem {
 	/// Specify options for early stopping. If the value is `Some`, early stopping will be enabled. If it is `None`, early stopping will be disabled.
 	pub early_stopping_options: Option<EarlyStoppingOptions>,
+	/// This is the L1 regularization value to use when updating the model parameters. It sets the weights of uninformative features to exactly zero. Set both `l1_regularization` and `l2_regularization` to use elastic net regularization.
+	pub l1_regularization: Option<f32>,
 	/// This is the L2 regularization value to use when updating the model parameters.
 	pub l2_regularization: Option<f32>,
 	/// This is the learning rate to use when updating the model parameters.
 	pub learning_rate: Option<f32>,
+	/// This is the schedule used to decay the learning rate after each epoch.
+	pub learning_rate_schedule: Option<LearningRateSchedule>,
 	/// This is the maximum number of epochs to train.
 	pub max_epochs: Option<u64>,
 	/// This is the number of examples to use for each batch of training.
 	pub n_examples_per_batch: Option<u64>,
+	/// This is the optimizer used to update the model parameters.
+	pub optimizer: Option<LinearOptimizer>,
+}
+
+/// This enum specifies the optimizer used to train linear models.
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum LinearOptimizer {
+	#[serde(rename = "sgd")]
+	Sgd,
+	#[serde(rename = "momentum")]
+	Momentum(MomentumOptimizer),
+	#[serde(rename = "adam")]
+	Adam(AdamOptimizer),
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MomentumOptimizer {
+	/// This is the fraction of the previous step that is added to each step. It defaults to 0.9.
+	pub momentum: Option<f32>,
 }
 
-/// These are the options used for training tree models.
 #[derive(Debug, serde::Deserialize)]
 #[serde(deny_unknown_fields)]
+pub struct AdamOptimizer {
+	/// This is the decay rate of the running mean of the gradients. It defaults to 0.9.
+	pub beta_1: Option<f32>,
+	/// This is the decay rate of the running mean of the squared gradients. It defaults to 0.999.
+	pub beta_2: Option<f32>,
+	/// This value is added to the denominator of each step to avoid dividing by zero. It defaults to 1e-8.
+	pub epsilon: Option<f32>,
+}
+
+/// This enum specifies how the learning rate of a linear model decays after each epoch.
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "type")]
+pub enum LearningRateSchedule {
+	/// The learning rate does not change.
+	#[serde(rename = "constant")]
+	Constant,
+	/// The learning rate for each epoch is `learning_rate / (1 + decay_rate * epoch)`.
+	#[serde(rename = "inverse_time_decay")]
+	InverseTimeDecay(LearningRateDecay),
+	/// The learning rate for each epoch is `learning_rate * decay_rate ^ epoch`.
+	#[serde(rename = "exponential_decay")]
+	ExponentialDecay(LearningRateDecay),
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LearningRateDecay {
+	pub decay_rate: f32,
+}
+
+/// These are the options used for training tree models.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct TreeGridItem {
+	/// This is the fraction of the training examples sampled without replacement to train each tree.
+	pub bagging_fraction: Option<f32>,
+	/// The examples are resampled every `bagging_frequency` rounds. If this value is 0, bagging is disabled.
+	pub bagging_frequency: Option<u64>,
 	/// This option controls whether binned features will be laid out in row major or column major order. Each will produce the same result, but row major will be faster for datasets with more rows and fewer columns, while column major will be faster for datasets with fewer rows and more columns.
 	pub binned_features_layout: Option<BinnedFeaturesLayout>,
+	/// This option enables DART, which randomly drops previously trained trees when training each round to avoid overfitting.
+	pub dart_options: Option<DartOptions>,
 	/// This option controls early stopping. If it is `Some`, then early stopping will be enabled. If it is `None`, then early stopping will be disabled.
 	pub early_stopping_options: Option<EarlyStoppingOptions>,
+	/// This is the fraction of the features, chosen from those sampled for the tree, that are considered when splitting each node.
+	pub feature_fraction_per_node: Option<f32>,
+	/// This is the fraction of the features randomly sampled to be considered for splits in each tree.
+	pub feature_fraction_per_tree: Option<f32>,
+	/// This option enables gradient-based one-side sampling. If it is `Some`, bagging is disabled.
+	pub goss_options: Option<GossOptions>,
 	/// This option sets the L2 regularization value for continuous splits, which helps avoid overfitting.
 	pub l2_regularization_for_continuous_splits: Option<f32>,
 	/// This option sets the L2 regularization value for discrete splits, which helps avoid overfitting.
@@ -228,10 +617,34 @@ pub struct TreeGridItem {
 	pub min_gain_to_split: Option<f32>,
 	/// A split will only be considered valid if the sum of hessians in each of the resulting children is at least this value.
 	pub min_sum_hessians_per_node: Option<f32>,
+	/// This is the seed for the random number generator used to sample examples and features.
+	pub seed: Option<u64>,
 	/// When choosing which direction each enum variant should be sent in a discrete split, the enum variants are sorted by a score computed from the sum of gradients and hessians for examples with that enum variant. This smoothing factor is added to the denominator of that score.
 	pub smoothing_factor_for_discrete_bin_sorting: Option<f32>,
 }
 
+/// The parameters in this struct control gradient-based one-side sampling.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GossOptions {
+	/// This is the fraction of the examples with the largest gradients that are always kept.
+	pub top_rate: f32,
+	/// This is the fraction of the examples that are randomly sampled from the rest.
+	pub other_rate: f32,
+}
+
+/// The parameters in this struct control DART.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DartOptions {
+	/// This is the probability that each previously trained round is dropped.
+	pub drop_rate: f32,
+	/// This is the maximum number of rounds dropped when training a round. If it is 0, the number of dropped rounds is not limited.
+	pub max_drop: u64,
+	/// This is the probability that no rounds are dropped when training a round.
+	pub skip_drop: f32,
+}
+
 /// This enum defines whether binned features will be layed out in row major or column major order.
 #[derive(Debug, serde::Deserialize)]
 pub enum BinnedFeaturesLayout {
@@ -269,6 +682,10 @@ pub enum ComparisonMetric {
 	Auc,
 	#[serde(rename = "f1")]
 	F1,
+	#[serde(rename = "ndcg")]
+	Ndcg,
+	#[serde(rename = "map")]
+	Map,
 }
 
 impl Default for Dataset {
@@ -278,6 +695,19 @@ impl Default for Dataset {
 			test_fraction: 0.2,
 			shuffle: Default::default(),
 			columns: Default::default(),
+			out_of_core: None,
+			group_column: None,
+		}
+	}
+}
+
+impl Default for OutOfCore {
+	fn default() -> Self {
+		OutOfCore {
+			chunk_size: 100_000,
+			sample_size: 200_000,
+			max_comparison_rows: 1_000_000,
+			max_test_rows: 1_000_000,
 		}
 	}
 }
@@ -291,11 +721,22 @@ impl Default for Shuffle {
 	}
 }
 
+impl Default for Checks {
+	fn default() -> Self {
+		Checks {
+			enable: true,
+			strict: false,
+		}
+	}
+}
+
 impl Default for AutoFeatures {
 	fn default() -> Self {
 		AutoFeatures {
 			enable: true,
 			exclude_columns: Default::default(),
+			impute: Default::default(),
+			missing_indicator: Default::default(),
 		}
 	}
 }
@@ -310,6 +751,8 @@ impl std::fmt::Display for ComparisonMetric {
 			ComparisonMetric::Accuracy => "Accuracy",
 			ComparisonMetric::Auc => "Area Under the Receiver Operating Characteristic Curve",
 			ComparisonMetric::F1 => "F1",
+			ComparisonMetric::Ndcg => "Normalized Discounted Cumulative Gain",
+			ComparisonMetric::Map => "Mean Average Precision",
 		};
 		write!(f, "{}", s)
 	}
@@ -0,0 +1,847 @@
+use crate::train::TrainModelOutput;
+use modelfox_table::prelude::*;
+use ndarray::prelude::*;
+use num::ToPrimitive;
+use rayon::prelude::*;
+use std::num::NonZeroUsize;
+
+/// This is the number of columns, starting with the most important, to compute partial dependence for.
+const PARTIAL_DEPENDENCE_MAX_COLUMNS: usize = 5;
+/// This is the maximum number of values of each column to compute partial dependence at.
+const PARTIAL_DEPENDENCE_MAX_GRID_SIZE: usize = 20;
+/// This is the maximum number of test examples averaged over to compute partial dependence.
+const PARTIAL_DEPENDENCE_MAX_EXAMPLES: usize = 500;
+/// This is the number of test examples to compute individual conditional expectation curves for.
+const INDIVIDUAL_CONDITIONAL_EXPECTATION_MAX_EXAMPLES: usize = 10;
+/// This is the maximum number of test examples averaged over to compute feature interactions.
+const FEATURE_INTERACTIONS_MAX_EXAMPLES: usize = 200;
+/// Feature interactions require memory quadratic in the number of features, so they are not computed for models with more features than this.
+const FEATURE_INTERACTIONS_MAX_FEATURES: usize = 256;
+/// This is the number of pairs of features, starting with the most interacting, to keep.
+const FEATURE_INTERACTIONS_MAX_ENTRIES: usize = 20;
+
+pub struct ExplanationsOutput {
+	/// The mean absolute SHAP value of each feature over the test dataset.
+	pub shap_feature_importances: Vec<f32>,
+	/// The SHAP feature importances summed over the features computed from each column, sorted from most to least important.
+	pub shap_column_importances: Vec<(String, f32)>,
+	pub partial_dependences: Vec<PartialDependenceOutput>,
+	/// The pairs of features with the largest mean absolute SHAP interaction value, sorted from most to least interacting. This is `None` for linear models and for models with too many features.
+	pub feature_interactions: Option<Vec<FeatureInteractionOutput>>,
+}
+
+pub struct FeatureInteractionOutput {
+	pub feature_name_a: String,
+	pub feature_name_b: String,
+	pub value: f32,
+}
+
+pub struct PartialDependenceOutput {
+	pub column_name: String,
+	/// For multiclass classifiers, this is the class whose probability the curves describe.
+	pub class: Option<String>,
+	pub grid: PartialDependenceGridOutput,
+	pub average: Vec<f32>,
+	pub individual: Vec<Vec<f32>>,
+}
+
+pub enum PartialDependenceGridOutput {
+	Number(Vec<f32>),
+	Enum(Vec<String>),
+}
+
+/// Compute global explanations for the trained model using the test dataset. The model output used for partial dependence is the prediction for regressors, the score for rankers, the probability of the positive class for binary classifiers, and the probability of each class in `classes` for multiclass classifiers. For regressors with a target transform, SHAP importances are measured in the transformed space of the target and partial dependence is in its original scale.
+pub fn compute_explanations(
+	train_model_output: &TrainModelOutput,
+	table_test: &TableView,
+	classes: Option<&[String]>,
+) -> ExplanationsOutput {
+	let shap_feature_importances = compute_shap_feature_importances(train_model_output, table_test);
+	let shap_column_importances = compute_shap_column_importances(
+		feature_groups(train_model_output),
+		&shap_feature_importances,
+	);
+	let partial_dependences = shap_column_importances
+		.iter()
+		.filter_map(|(column_name, _)| {
+			table_test
+				.columns()
+				.iter()
+				.position(|column| column.name() == Some(column_name))
+		})
+		.filter(|column_index| {
+			matches!(
+				table_test.columns()[*column_index],
+				TableColumnView::Number(_) | TableColumnView::Enum(_)
+			)
+		})
+		.take(PARTIAL_DEPENDENCE_MAX_COLUMNS)
+		.flat_map(|column_index| {
+			compute_partial_dependences(train_model_output, table_test, column_index, classes)
+		})
+		.collect();
+	let feature_interactions = compute_feature_interactions(train_model_output, table_test);
+	ExplanationsOutput {
+		shap_feature_importances,
+		shap_column_importances,
+		partial_dependences,
+		feature_interactions,
+	}
+}
+
+fn feature_groups(train_model_output: &TrainModelOutput) -> &[modelfox_features::FeatureGroup] {
+	match train_model_output {
+		TrainModelOutput::LinearRegressor(output) => &output.feature_groups,
+		TrainModelOutput::TreeRegressor(output) => &output.feature_groups,
+		TrainModelOutput::LinearBinaryClassifier(output) => &output.feature_groups,
+		TrainModelOutput::TreeBinaryClassifier(output) => &output.feature_groups,
+		TrainModelOutput::LinearMulticlassClassifier(output) => &output.feature_groups,
+		TrainModelOutput::TreeMulticlassClassifier(output) => &output.feature_groups,
+		TrainModelOutput::TreeRanker(output) => &output.feature_groups,
+	}
+}
+
+fn compute_shap_feature_importances(
+	train_model_output: &TrainModelOutput,
+	table: &TableView,
+) -> Vec<f32> {
+	let n_features = feature_groups(train_model_output)
+		.iter()
+		.map(|feature_group| feature_group.n_features())
+		.sum::<usize>();
+	let (sums, n_classes) = match train_model_output {
+		TrainModelOutput::LinearRegressor(output) => {
+			let features = modelfox_features::compute_features_array_f32(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let sums = sum_absolute_contributions(features.view(), n_features, |features| {
+				output
+					.model
+					.compute_feature_contributions(features)
+					.into_iter()
+					.map(|output| output.feature_contribution_values)
+					.collect()
+			});
+			(sums, 1)
+		}
+		TrainModelOutput::TreeRegressor(output) => {
+			let features = modelfox_features::compute_features_array_value(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let sums = sum_absolute_contributions(features.view(), n_features, |features| {
+				output
+					.model
+					.compute_feature_contributions(features)
+					.into_iter()
+					.map(|output| output.feature_contribution_values)
+					.collect()
+			});
+			(sums, 1)
+		}
+		TrainModelOutput::LinearBinaryClassifier(output) => {
+			let features = modelfox_features::compute_features_array_f32(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let sums = sum_absolute_contributions(features.view(), n_features, |features| {
+				output
+					.model
+					.compute_feature_contributions(features)
+					.into_iter()
+					.map(|output| output.feature_contribution_values)
+					.collect()
+			});
+			(sums, 1)
+		}
+		TrainModelOutput::TreeBinaryClassifier(output) => {
+			let features = modelfox_features::compute_features_array_value(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let sums = sum_absolute_contributions(features.view(), n_features, |features| {
+				output
+					.model
+					.compute_feature_contributions(features)
+					.into_iter()
+					.map(|output| output.feature_contribution_values)
+					.collect()
+			});
+			(sums, 1)
+		}
+		TrainModelOutput::LinearMulticlassClassifier(output) => {
+			let features = modelfox_features::compute_features_array_f32(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let sums = sum_absolute_contributions(features.view(), n_features, |features| {
+				output
+					.model
+					.compute_feature_contributions(features)
+					.into_iter()
+					.flatten()
+					.map(|output| output.feature_contribution_values)
+					.collect()
+			});
+			(sums, output.model.biases.len())
+		}
+		TrainModelOutput::TreeMulticlassClassifier(output) => {
+			let features = modelfox_features::compute_features_array_value(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let sums = sum_absolute_contributions(features.view(), n_features, |features| {
+				output
+					.model
+					.compute_feature_contributions(features)
+					.into_iter()
+					.flatten()
+					.map(|output| output.feature_contribution_values)
+					.collect()
+			});
+			(sums, output.model.biases.len())
+		}
+		TrainModelOutput::TreeRanker(output) => {
+			let features = modelfox_features::compute_features_array_value(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let sums = sum_absolute_contributions(features.view(), n_features, |features| {
+				output
+					.model
+					.compute_feature_contributions(features)
+					.into_iter()
+					.map(|output| output.feature_contribution_values)
+					.collect()
+			});
+			(sums, 1)
+		}
+	};
+	let denominator = (table.nrows() * n_classes).max(1).to_f64().unwrap();
+	sums.into_iter()
+		.map(|sum| (sum / denominator).to_f32().unwrap())
+		.collect()
+}
+
+/// Sum the absolute value of each feature's contribution over all examples, computing the contributions in parallel batches so the contributions for the whole dataset are never held in memory at once.
+fn sum_absolute_contributions<T, F>(
+	features: ArrayView2<T>,
+	n_features: usize,
+	compute_feature_contributions: F,
+) -> Vec<f64>
+where
+	T: Sync,
+	F: Fn(ArrayView2<T>) -> Vec<Vec<f32>> + Sync,
+{
+	let n_examples_per_batch = 256;
+	features
+		.axis_chunks_iter(Axis(0), n_examples_per_batch)
+		.into_par_iter()
+		.map(|features| {
+			let mut sums = vec![0.0; n_features];
+			for contributions in compute_feature_contributions(features) {
+				for (sum, contribution) in sums.iter_mut().zip(contributions.iter()) {
+					*sum += contribution.abs().to_f64().unwrap();
+				}
+			}
+			sums
+		})
+		.reduce(
+			|| vec![0.0; n_features],
+			|mut sums_a, sums_b| {
+				for (sum_a, sum_b) in sums_a.iter_mut().zip(sums_b.iter()) {
+					*sum_a += sum_b;
+				}
+				sums_a
+			},
+		)
+}
+
+fn compute_feature_interactions(
+	train_model_output: &TrainModelOutput,
+	table: &TableView,
+) -> Option<Vec<FeatureInteractionOutput>> {
+	let feature_groups = feature_groups(train_model_output);
+	let n_features = feature_groups
+		.iter()
+		.map(|feature_group| feature_group.n_features())
+		.sum::<usize>();
+	if n_features > FEATURE_INTERACTIONS_MAX_FEATURES {
+		return None;
+	}
+	let n_examples = table.nrows().min(FEATURE_INTERACTIONS_MAX_EXAMPLES);
+	let table = table_from_view(table, n_examples);
+	let (sums, n_classes) = match train_model_output {
+		TrainModelOutput::LinearRegressor(_)
+		| TrainModelOutput::LinearBinaryClassifier(_)
+		| TrainModelOutput::LinearMulticlassClassifier(_)
+		| TrainModelOutput::TreeRanker(_) => return None,
+		TrainModelOutput::TreeRegressor(output) => {
+			let features = modelfox_features::compute_features_array_value(
+				&table.view(),
+				&output.feature_groups,
+				&|| {},
+			);
+			let sums = sum_absolute_interactions(features.view(), n_features, |features| {
+				output
+					.model
+					.compute_feature_interactions(features)
+					.into_iter()
+					.map(|output| output.feature_interaction_values)
+					.collect()
+			});
+			(sums, 1)
+		}
+		TrainModelOutput::TreeBinaryClassifier(output) => {
+			let features = modelfox_features::compute_features_array_value(
+				&table.view(),
+				&output.feature_groups,
+				&|| {},
+			);
+			let sums = sum_absolute_interactions(features.view(), n_features, |features| {
+				output
+					.model
+					.compute_feature_interactions(features)
+					.into_iter()
+					.map(|output| output.feature_interaction_values)
+					.collect()
+			});
+			(sums, 1)
+		}
+		TrainModelOutput::TreeMulticlassClassifier(output) => {
+			let features = modelfox_features::compute_features_array_value(
+				&table.view(),
+				&output.feature_groups,
+				&|| {},
+			);
+			let sums = sum_absolute_interactions(features.view(), n_features, |features| {
+				output
+					.model
+					.compute_feature_interactions(features)
+					.into_iter()
+					.flatten()
+					.map(|output| output.feature_interaction_values)
+					.collect()
+			});
+			(sums, output.model.biases.len())
+		}
+	};
+	let denominator = (n_examples * n_classes).max(1).to_f64().unwrap();
+	let feature_names = feature_groups
+		.iter()
+		.flat_map(|feature_group| feature_group.feature_names())
+		.collect::<Vec<_>>();
+	let mut feature_interactions = Vec::new();
+	for index_a in 0..n_features {
+		for index_b in index_a + 1..n_features {
+			let value = (sums[(index_a, index_b)] / denominator).to_f32().unwrap();
+			if value > 0.0 {
+				feature_interactions.push(FeatureInteractionOutput {
+					feature_name_a: feature_names[index_a].clone(),
+					feature_name_b: feature_names[index_b].clone(),
+					value,
+				});
+			}
+		}
+	}
+	feature_interactions.sort_by(|a, b| b.value.partial_cmp(&a.value).unwrap());
+	feature_interactions.truncate(FEATURE_INTERACTIONS_MAX_ENTRIES);
+	Some(feature_interactions)
+}
+
+/// Sum the absolute value of each pair of features' interaction over all examples. The interaction matrices are symmetric with half of each interaction on either side of the diagonal, so only the entries above the diagonal of the sums are filled in, with the full interaction.
+fn sum_absolute_interactions<T, F>(
+	features: ArrayView2<T>,
+	n_features: usize,
+	compute_feature_interactions: F,
+) -> Array2<f64>
+where
+	T: Sync,
+	F: Fn(ArrayView2<T>) -> Vec<Array2<f32>> + Sync,
+{
+	let n_examples_per_batch = 16;
+	features
+		.axis_chunks_iter(Axis(0), n_examples_per_batch)
+		.into_par_iter()
+		.map(|features| {
+			let mut sums = Array2::zeros((n_features, n_features));
+			for interactions in compute_feature_interactions(features) {
+				for index_a in 0..n_features {
+					for index_b in index_a + 1..n_features {
+						let interaction =
+							interactions[(index_a, index_b)] + interactions[(index_b, index_a)];
+						sums[(index_a, index_b)] += interaction.abs().to_f64().unwrap();
+					}
+				}
+			}
+			sums
+		})
+		.reduce(|| Array2::zeros((n_features, n_features)), |a, b| a + b)
+}
+
+fn compute_shap_column_importances(
+	feature_groups: &[modelfox_features::FeatureGroup],
+	shap_feature_importances: &[f32],
+) -> Vec<(String, f32)> {
+	let mut shap_column_importances: Vec<(String, f32)> = Vec::new();
+	let mut add = |column_name: &str, importance: f32| match shap_column_importances
+		.iter_mut()
+		.find(|(name, _)| name == column_name)
+	{
+		Some((_, column_importance)) => *column_importance += importance,
+		None => shap_column_importances.push((column_name.to_owned(), importance)),
+	};
+	let mut feature_index = 0;
+	for feature_group in feature_groups.iter() {
+		let n_features = feature_group.n_features();
+		let importance = shap_feature_importances[feature_index..feature_index + n_features]
+			.iter()
+			.sum::<f32>();
+		match feature_group {
+			modelfox_features::FeatureGroup::Identity(feature_group) => {
+				add(&feature_group.source_column_name, importance)
+			}
+			modelfox_features::FeatureGroup::Normalized(feature_group) => {
+				add(&feature_group.source_column_name, importance)
+			}
+			modelfox_features::FeatureGroup::OneHotEncoded(feature_group) => {
+				add(&feature_group.source_column_name, importance)
+			}
+			modelfox_features::FeatureGroup::BagOfWords(feature_group) => {
+				add(&feature_group.source_column_name, importance)
+			}
+			modelfox_features::FeatureGroup::WordEmbedding(feature_group) => {
+				add(&feature_group.source_column_name, importance)
+			}
+			modelfox_features::FeatureGroup::HashedBagOfWords(feature_group) => {
+				add(&feature_group.source_column_name, importance)
+			}
+			modelfox_features::FeatureGroup::HashedEnum(feature_group) => {
+				add(&feature_group.source_column_name, importance)
+			}
+			modelfox_features::FeatureGroup::BagOfWordsCosineSimilarity(feature_group) => {
+				// The similarity depends on both columns equally, so split the importance between them.
+				add(&feature_group.source_column_name_a, importance / 2.0);
+				add(&feature_group.source_column_name_b, importance / 2.0);
+			}
+			modelfox_features::FeatureGroup::Interaction(feature_group) => {
+				// The product depends on both columns equally, so split the importance between them.
+				add(
+					feature_group.source_a.source_column_name(),
+					importance / 2.0,
+				);
+				add(
+					feature_group.source_b.source_column_name(),
+					importance / 2.0,
+				);
+			}
+			modelfox_features::FeatureGroup::Polynomial(feature_group) => {
+				add(&feature_group.source_column_name, importance)
+			}
+			modelfox_features::FeatureGroup::BinnedNumber(feature_group) => {
+				add(&feature_group.source_column_name, importance)
+			}
+		}
+		feature_index += n_features;
+	}
+	shap_column_importances.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+	shap_column_importances
+}
+
+/// Compute the partial dependence and individual conditional expectation curves for the column at `column_index`, one for each model output.
+fn compute_partial_dependences(
+	train_model_output: &TrainModelOutput,
+	table_test: &TableView,
+	column_index: usize,
+	classes: Option<&[String]>,
+) -> Vec<PartialDependenceOutput> {
+	let column = &table_test.columns()[column_index];
+	let column_name = column.name().unwrap().to_owned();
+	let grid = match column {
+		TableColumnView::Number(column) => {
+			PartialDependenceGridOutput::Number(compute_number_grid(column.as_slice()))
+		}
+		TableColumnView::Enum(column) => PartialDependenceGridOutput::Enum(
+			column
+				.variants()
+				.iter()
+				.take(PARTIAL_DEPENDENCE_MAX_GRID_SIZE)
+				.cloned()
+				.collect(),
+		),
+		_ => unreachable!(),
+	};
+	let grid_size = match &grid {
+		PartialDependenceGridOutput::Number(values) => values.len(),
+		PartialDependenceGridOutput::Enum(variants) => variants.len(),
+	};
+	if grid_size == 0 {
+		return Vec::new();
+	}
+	let n_examples = table_test.nrows().min(PARTIAL_DEPENDENCE_MAX_EXAMPLES);
+	// There is nothing to average over if the test dataset is empty.
+	if n_examples == 0 {
+		return Vec::new();
+	}
+	let n_individual = n_examples.min(INDIVIDUAL_CONDITIONAL_EXPECTATION_MAX_EXAMPLES);
+	let mut table = table_from_view(table_test, n_examples);
+	// For each grid value, set the column to that value in every example and record the model's outputs.
+	let outputs: Vec<Array2<f32>> = (0..grid_size)
+		.map(|grid_index| {
+			match (&grid, &mut table.columns_mut()[column_index]) {
+				(PartialDependenceGridOutput::Number(values), TableColumn::Number(column)) => {
+					column.data_mut().fill(values[grid_index]);
+				}
+				(PartialDependenceGridOutput::Enum(_), TableColumn::Enum(column)) => {
+					column
+						.data_mut()
+						.fill(Some(NonZeroUsize::new(grid_index + 1).unwrap()));
+				}
+				_ => unreachable!(),
+			}
+			predict(train_model_output, &table.view())
+		})
+		.collect();
+	let n_outputs = outputs[0].ncols();
+	(0..n_outputs)
+		.map(|output_index| {
+			let average = outputs
+				.iter()
+				.map(|outputs| outputs.column(output_index).mean().unwrap())
+				.collect();
+			let individual = (0..n_individual)
+				.map(|example_index| {
+					outputs
+						.iter()
+						.map(|outputs| outputs[(example_index, output_index)])
+						.collect()
+				})
+				.collect();
+			let class = classes.map(|classes| classes[output_index].clone());
+			PartialDependenceOutput {
+				column_name: column_name.clone(),
+				class,
+				grid: match &grid {
+					PartialDependenceGridOutput::Number(values) => {
+						PartialDependenceGridOutput::Number(values.clone())
+					}
+					PartialDependenceGridOutput::Enum(variants) => {
+						PartialDependenceGridOutput::Enum(variants.clone())
+					}
+				},
+				average,
+				individual,
+			}
+		})
+		.collect()
+}
+
+/// Choose evenly spaced quantiles of the column's values as the grid, so that the grid covers the range where most of the data lies.
+fn compute_number_grid(values: &[f32]) -> Vec<f32> {
+	let mut values = values
+		.iter()
+		.cloned()
+		.filter(|value| value.is_finite())
+		.collect::<Vec<_>>();
+	if values.is_empty() {
+		return Vec::new();
+	}
+	values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+	let mut grid = (0..PARTIAL_DEPENDENCE_MAX_GRID_SIZE)
+		.map(|grid_index| {
+			let quantile = (grid_index.to_f32().unwrap() + 0.5)
+				/ PARTIAL_DEPENDENCE_MAX_GRID_SIZE.to_f32().unwrap();
+			let index = (quantile * (values.len() - 1).to_f32().unwrap())
+				.round()
+				.to_usize()
+				.unwrap();
+			values[index]
+		})
+		.collect::<Vec<_>>();
+	grid.dedup();
+	grid
+}
+
+/// Copy the first `n_rows` rows of `table` into a new `Table` whose columns can be modified.
+fn table_from_view(table: &TableView, n_rows: usize) -> Table {
+	let column_names = table
+		.columns()
+		.iter()
+		.map(|column| column.name().map(|name| name.to_owned()))
+		.collect();
+	let column_types = table
+		.columns()
+		.iter()
+		.map(|column| match column {
+			TableColumnView::Unknown(_) => TableColumnType::Unknown,
+			TableColumnView::Number(_) => TableColumnType::Number,
+			TableColumnView::Enum(column) => TableColumnType::Enum {
+				variants: column.variants().to_owned(),
+			},
+			TableColumnView::Text(_) => TableColumnType::Text,
+		})
+		.collect();
+	let mut owned_table = Table::new(column_names, column_types);
+	for (column, owned_column) in table
+		.columns()
+		.iter()
+		.zip(owned_table.columns_mut().iter_mut())
+	{
+		match (column, owned_column) {
+			(TableColumnView::Unknown(_), TableColumn::Unknown(owned_column)) => {
+				*owned_column.len_mut() = n_rows;
+			}
+			(TableColumnView::Number(column), TableColumn::Number(owned_column)) => {
+				owned_column
+					.data_mut()
+					.extend_from_slice(&column.as_slice()[..n_rows]);
+			}
+			(TableColumnView::Enum(column), TableColumn::Enum(owned_column)) => {
+				owned_column
+					.data_mut()
+					.extend_from_slice(&column.as_slice()[..n_rows]);
+			}
+			(TableColumnView::Text(column), TableColumn::Text(owned_column)) => {
+				owned_column
+					.data_mut()
+					.extend_from_slice(&column.as_slice()[..n_rows]);
+			}
+			_ => unreachable!(),
+		}
+	}
+	owned_table
+}
+
+/// Make predictions with shape (n_examples, n_outputs).
+fn predict(train_model_output: &TrainModelOutput, table: &TableView) -> Array2<f32> {
+	match train_model_output {
+		TrainModelOutput::LinearRegressor(output) => {
+			let features = modelfox_features::compute_features_array_f32(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let mut predictions = Array::zeros(features.nrows());
+			output
+				.model
+				.predict(features.view(), predictions.view_mut());
+			if let Some(target_transform) = output.target_transform {
+				predictions
+					.mapv_inplace(|prediction| target_transform.inverse_transform(prediction));
+			}
+			predictions.insert_axis(Axis(1))
+		}
+		TrainModelOutput::TreeRegressor(output) => {
+			let features = modelfox_features::compute_features_array_value(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let mut predictions = Array::zeros(features.nrows());
+			output
+				.model
+				.predict(features.view(), predictions.view_mut());
+			if let Some(target_transform) = output.target_transform {
+				predictions
+					.mapv_inplace(|prediction| target_transform.inverse_transform(prediction));
+			}
+			predictions.insert_axis(Axis(1))
+		}
+		TrainModelOutput::LinearBinaryClassifier(output) => {
+			let features = modelfox_features::compute_features_array_f32(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let mut probabilities = Array::zeros(features.nrows());
+			output
+				.model
+				.predict(features.view(), probabilities.view_mut());
+			probabilities.insert_axis(Axis(1))
+		}
+		TrainModelOutput::TreeBinaryClassifier(output) => {
+			let features = modelfox_features::compute_features_array_value(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let mut probabilities = Array::zeros(features.nrows());
+			output
+				.model
+				.predict(features.view(), probabilities.view_mut());
+			probabilities.insert_axis(Axis(1))
+		}
+		TrainModelOutput::LinearMulticlassClassifier(output) => {
+			let features = modelfox_features::compute_features_array_f32(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let mut probabilities = Array::zeros((features.nrows(), output.model.biases.len()));
+			output
+				.model
+				.predict(features.view(), probabilities.view_mut());
+			probabilities
+		}
+		TrainModelOutput::TreeMulticlassClassifier(output) => {
+			let features = modelfox_features::compute_features_array_value(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let mut probabilities = Array::zeros((features.nrows(), output.model.biases.len()));
+			output
+				.model
+				.predict(features.view(), probabilities.view_mut());
+			probabilities
+		}
+		TrainModelOutput::TreeRanker(output) => {
+			let features = modelfox_features::compute_features_array_value(
+				table,
+				&output.feature_groups,
+				&|| {},
+			);
+			let mut scores = Array::zeros(features.nrows());
+			output.model.predict(features.view(), scores.view_mut());
+			scores.insert_axis(Axis(1))
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::train::LinearRegressorTrainModelOutput;
+
+	/// Create a linear regressor with an identity feature for each of `column_names`.
+	fn linear_regressor(
+		column_names: &[&str],
+		bias: f32,
+		weights: Vec<f32>,
+		means: Vec<f32>,
+	) -> TrainModelOutput {
+		let feature_groups = column_names
+			.iter()
+			.map(|column_name| {
+				modelfox_features::FeatureGroup::Identity(modelfox_features::IdentityFeatureGroup {
+					source_column_name: column_name.to_string(),
+					impute: None,
+					missing_indicator: false,
+				})
+			})
+			.collect();
+		TrainModelOutput::LinearRegressor(LinearRegressorTrainModelOutput {
+			model: modelfox_linear::Regressor {
+				bias,
+				weights: Array::from(weights),
+				means,
+			},
+			feature_groups,
+			target_column_index: column_names.len(),
+			losses: None,
+			train_options: Default::default(),
+			feature_importances: vec![0.0; column_names.len()],
+			target_transform: None,
+		})
+	}
+
+	fn table(columns: &[(&str, &[f32])]) -> Table {
+		let mut table = Table::new(
+			columns
+				.iter()
+				.map(|(column_name, _)| Some(column_name.to_string()))
+				.collect(),
+			vec![TableColumnType::Number; columns.len()],
+		);
+		for ((_, values), column) in columns.iter().zip(table.columns_mut().iter_mut()) {
+			column
+				.as_number_mut()
+				.unwrap()
+				.data_mut()
+				.extend_from_slice(values);
+		}
+		table
+	}
+
+	#[test]
+	fn test_shap_importances_sum_to_the_output() {
+		let model = linear_regressor(&["x1", "x2"], 1.0, vec![2.0, -3.0], vec![0.0, 0.0]);
+		// In every example both features move the output in the same direction, so the mean absolute SHAP values sum to the mean absolute difference between the output and the baseline.
+		let table = table(&[
+			("x1", &[1.0, 2.0, -1.0, -2.0]),
+			("x2", &[-1.0, -1.0, 1.0, 2.0]),
+		]);
+		let explanations = compute_explanations(&model, &table.view(), None);
+		let outputs = predict(&model, &table.view());
+		let baseline = 1.0;
+		let mean_absolute_output = outputs
+			.iter()
+			.map(|output| (output - baseline).abs())
+			.sum::<f32>()
+			/ outputs.len().to_f32().unwrap();
+		let importances = &explanations.shap_feature_importances;
+		assert_eq!(importances, &[3.0, 3.75]);
+		assert!((importances.iter().sum::<f32>() - mean_absolute_output).abs() < 1e-6);
+		assert_eq!(
+			explanations.shap_column_importances,
+			vec![("x2".to_owned(), 3.75), ("x1".to_owned(), 3.0)],
+		);
+	}
+
+	#[test]
+	fn test_partial_dependence_is_monotonic_for_a_linear_model() {
+		let model = linear_regressor(&["x1", "x2"], 1.0, vec![2.0, -3.0], vec![4.5, 0.0]);
+		let x1 = (0..10).map(|i| i.to_f32().unwrap()).collect::<Vec<_>>();
+		let x2 = (0..10)
+			.map(|i| (i % 3).to_f32().unwrap())
+			.collect::<Vec<_>>();
+		let table = table(&[("x1", &x1), ("x2", &x2)]);
+		let explanations = compute_explanations(&model, &table.view(), None);
+		assert_eq!(explanations.partial_dependences.len(), 2);
+		for partial_dependence in explanations.partial_dependences.iter() {
+			let grid = match &partial_dependence.grid {
+				PartialDependenceGridOutput::Number(grid) => grid,
+				PartialDependenceGridOutput::Enum(_) => panic!("expected a number grid"),
+			};
+			assert_eq!(grid.len(), partial_dependence.average.len());
+			assert!(grid.windows(2).all(|window| window[0] < window[1]));
+			// Each step of the average is the column's weight times the step of the grid.
+			let weight = match partial_dependence.column_name.as_str() {
+				"x1" => 2.0,
+				"x2" => -3.0,
+				_ => unreachable!(),
+			};
+			for (grid, average) in grid.windows(2).zip(partial_dependence.average.windows(2)) {
+				assert!((average[1] - average[0] - weight * (grid[1] - grid[0])).abs() < 1e-4);
+			}
+			assert_eq!(partial_dependence.individual.len(), 10);
+		}
+	}
+
+	#[test]
+	fn test_explanations_for_an_empty_test_table() {
+		let model = linear_regressor(&["x1"], 1.0, vec![2.0], vec![0.0]);
+		let table = Table::new(
+			vec![Some("color".to_owned()), Some("x1".to_owned())],
+			vec![
+				TableColumnType::Enum {
+					variants: vec!["red".to_owned(), "green".to_owned()],
+				},
+				TableColumnType::Number,
+			],
+		);
+		let explanations = compute_explanations(&model, &table.view(), None);
+		assert_eq!(explanations.shap_feature_importances, vec![0.0]);
+		assert!(explanations.partial_dependences.is_empty());
+		// An enum column has a grid even without examples, but there is nothing to average over.
+		assert!(compute_partial_dependences(&model, &table.view(), 0, None).is_empty());
+	}
+}
@@ -0,0 +1,436 @@
+/*!
+This module implements automatic feature selection. Before the hyperparameter grid is computed, columns that would be used for automatic feature engineering are dropped if they are nearly constant, have too many invalid values, are highly correlated with another number column, or are unimportant to a quick probe model.
+*/
+
+use crate::{
+	config,
+	features::choose_feature_group_tree,
+	heuristics::{
+		FEATURE_SELECTION_DEFAULT_MAX_CORRELATION, FEATURE_SELECTION_DEFAULT_MAX_INVALID_FRACTION,
+		FEATURE_SELECTION_DEFAULT_MAX_MOST_FREQUENT_FRACTION,
+		FEATURE_SELECTION_DEFAULT_MIN_IMPORTANCE, FEATURE_SELECTION_MAX_ROWS_FOR_CORRELATION,
+		FEATURE_SELECTION_PROBE_MAX_ROUNDS,
+	},
+	model::Task,
+	stats::ColumnStatsOutput,
+};
+use modelfox_kill_chip::KillChip;
+use modelfox_table::prelude::*;
+use num::ToPrimitive;
+
+/// This struct records a column that feature selection dropped.
+#[derive(Clone, Debug)]
+pub struct DroppedColumn {
+	pub column_name: String,
+	pub reason: DroppedColumnReason,
+}
+
+#[derive(Clone, Debug)]
+pub enum DroppedColumnReason {
+	/// The most frequent value made up `most_frequent_fraction` of the column's valid values.
+	NearConstant { most_frequent_fraction: f32 },
+	/// `invalid_fraction` of the column's values were invalid.
+	InvalidFraction { invalid_fraction: f32 },
+	/// The column's correlation with the number column `column_name`, which was kept, was `correlation`.
+	Collinear {
+		column_name: String,
+		correlation: f32,
+	},
+	/// The column's feature importance in the probe model was `importance`.
+	LowImportance { importance: f32 },
+}
+
+/**
+Choose the columns to drop from automatic feature engineering. `column_stats` are the train stats of every column other than the target, and `table_train` is the train dataset, which must contain the target column at `target_column_index`.
+*/
+pub fn select_columns(
+	selection: &config::FeatureSelection,
+	config: &config::Config,
+	table_train: &TableView,
+	target_column_index: usize,
+	task: Task,
+	column_stats: &[ColumnStatsOutput],
+) -> Vec<DroppedColumn> {
+	let max_most_frequent_fraction = selection
+		.max_most_frequent_fraction
+		.unwrap_or(FEATURE_SELECTION_DEFAULT_MAX_MOST_FREQUENT_FRACTION);
+	let max_invalid_fraction = selection
+		.max_invalid_fraction
+		.unwrap_or(FEATURE_SELECTION_DEFAULT_MAX_INVALID_FRACTION);
+	let max_correlation = selection
+		.max_correlation
+		.unwrap_or(FEATURE_SELECTION_DEFAULT_MAX_CORRELATION);
+	let min_importance = selection
+		.min_importance
+		.unwrap_or(FEATURE_SELECTION_DEFAULT_MIN_IMPORTANCE);
+	let mut dropped_columns = Vec::new();
+	if !config.features.auto.enable {
+		return dropped_columns;
+	}
+	let exclude_columns = config
+		.features
+		.auto
+		.exclude_columns
+		.as_deref()
+		.unwrap_or_default();
+	// Only consider the columns that automatic feature engineering would use.
+	let mut candidates: Vec<&ColumnStatsOutput> = column_stats
+		.iter()
+		.filter(|column_stats| {
+			!matches!(column_stats, ColumnStatsOutput::Unknown(_))
+				&& !exclude_columns
+					.iter()
+					.any(|column_name| column_name == column_stats.column_name())
+		})
+		.collect();
+
+	// Drop columns with too many invalid values, then columns that are nearly constant.
+	candidates.retain(|column_stats| {
+		let reason = invalid_fraction(column_stats)
+			.filter(|invalid_fraction| *invalid_fraction >= max_invalid_fraction)
+			.map(|invalid_fraction| DroppedColumnReason::InvalidFraction { invalid_fraction })
+			.or_else(|| {
+				most_frequent_fraction(column_stats)
+					.filter(|most_frequent_fraction| {
+						*most_frequent_fraction >= max_most_frequent_fraction
+					})
+					.map(|most_frequent_fraction| DroppedColumnReason::NearConstant {
+						most_frequent_fraction,
+					})
+			});
+		match reason {
+			Some(reason) => {
+				dropped_columns.push(DroppedColumn {
+					column_name: column_stats.column_name().to_owned(),
+					reason,
+				});
+				false
+			}
+			None => true,
+		}
+	});
+
+	// Drop number columns that are highly correlated with a number column that was kept.
+	let n_rows = table_train
+		.nrows()
+		.min(FEATURE_SELECTION_MAX_ROWS_FOR_CORRELATION);
+	let mut kept_number_columns: Vec<(&str, Vec<f32>)> = Vec::new();
+	candidates.retain(|column_stats| {
+		let column_name = column_stats.column_name();
+		let values = match column_stats {
+			ColumnStatsOutput::Number(_) => table_train
+				.columns()
+				.iter()
+				.find(|column| column.name() == Some(column_name))
+				.and_then(|column| column.as_number())
+				.map(|column| column.as_slice()[..n_rows].to_owned()),
+			_ => None,
+		};
+		let values = match values {
+			Some(values) => values,
+			None => return true,
+		};
+		let collinear_column =
+			kept_number_columns
+				.iter()
+				.find_map(|(kept_column_name, kept_values)| {
+					let correlation = correlation(&values, kept_values)?;
+					if correlation.abs() >= max_correlation {
+						Some((*kept_column_name, correlation))
+					} else {
+						None
+					}
+				});
+		match collinear_column {
+			Some((kept_column_name, correlation)) => {
+				dropped_columns.push(DroppedColumn {
+					column_name: column_name.to_owned(),
+					reason: DroppedColumnReason::Collinear {
+						column_name: kept_column_name.to_owned(),
+						correlation,
+					},
+				});
+				false
+			}
+			None => {
+				kept_number_columns.push((column_name, values));
+				true
+			}
+		}
+	});
+
+	// Drop columns that are unimportant to a quick probe model trained on the remaining columns.
+	if min_importance > 0.0 && !candidates.is_empty() {
+		let importances =
+			compute_probe_importances(&candidates, config, table_train, target_column_index, task);
+		for (column_stats, importance) in candidates.iter().zip(importances) {
+			if importance < min_importance {
+				dropped_columns.push(DroppedColumn {
+					column_name: column_stats.column_name().to_owned(),
+					reason: DroppedColumnReason::LowImportance { importance },
+				});
+			}
+		}
+	}
+
+	dropped_columns
+}
+
+fn invalid_fraction(column_stats: &ColumnStatsOutput) -> Option<f32> {
+	let (invalid_count, count) = match column_stats {
+		ColumnStatsOutput::Number(column_stats) => (column_stats.invalid_count, column_stats.count),
+		ColumnStatsOutput::Enum(column_stats) => (
+			column_stats.invalid_count,
+			column_stats.count.to_usize().unwrap(),
+		),
+		_ => return None,
+	};
+	if count == 0 {
+		return None;
+	}
+	Some(invalid_count.to_f32().unwrap() / count.to_f32().unwrap())
+}
+
+fn most_frequent_fraction(column_stats: &ColumnStatsOutput) -> Option<f32> {
+	let (max_count, valid_count) = match column_stats {
+		ColumnStatsOutput::Number(column_stats) => {
+			// The histogram is absent when the column has too many unique values to be nearly constant.
+			let histogram = column_stats.histogram.as_ref()?;
+			let max_count = histogram.iter().map(|(_, count)| *count).max()?;
+			(max_count, column_stats.count - column_stats.invalid_count)
+		}
+		ColumnStatsOutput::Enum(column_stats) => {
+			let max_count = column_stats
+				.histogram
+				.iter()
+				.map(|(_, count)| *count)
+				.max()?;
+			(
+				max_count,
+				column_stats.count.to_usize().unwrap() - column_stats.invalid_count,
+			)
+		}
+		_ => return None,
+	};
+	if valid_count == 0 {
+		return None;
+	}
+	Some(max_count.to_f32().unwrap() / valid_count.to_f32().unwrap())
+}
+
+/// Compute the Pearson correlation of `a` and `b` over the rows where both values are valid. This returns `None` if either column has no variance over those rows.
+fn correlation(a: &[f32], b: &[f32]) -> Option<f32> {
+	let mut n = 0.0f64;
+	let mut sum_a = 0.0f64;
+	let mut sum_b = 0.0f64;
+	let mut sum_aa = 0.0f64;
+	let mut sum_bb = 0.0f64;
+	let mut sum_ab = 0.0f64;
+	for (a, b) in a.iter().zip(b.iter()) {
+		if !a.is_finite() || !b.is_finite() {
+			continue;
+		}
+		let (a, b) = (f64::from(*a), f64::from(*b));
+		n += 1.0;
+		sum_a += a;
+		sum_b += b;
+		sum_aa += a * a;
+		sum_bb += b * b;
+		sum_ab += a * b;
+	}
+	let covariance = n * sum_ab - sum_a * sum_b;
+	let variance_a = n * sum_aa - sum_a * sum_a;
+	let variance_b = n * sum_bb - sum_b * sum_b;
+	if variance_a <= 0.0 || variance_b <= 0.0 {
+		return None;
+	}
+	(covariance / (variance_a * variance_b).sqrt()).to_f32()
+}
+
+/// Train a small tree model on the feature groups chosen for `candidates` and return the importance of each candidate, which is the sum of the importances of its features.
+fn compute_probe_importances(
+	candidates: &[&ColumnStatsOutput],
+	config: &config::Config,
+	table_train: &TableView,
+	target_column_index: usize,
+	task: Task,
+) -> Vec<f32> {
+	let feature_groups: Vec<modelfox_features::FeatureGroup> = candidates
+		.iter()
+		.map(|column_stats| {
+			choose_feature_group_tree(
+				column_stats,
+				None,
+				config.features.auto.impute.as_ref(),
+				config.features.auto.missing_indicator,
+			)
+			.unwrap()
+		})
+		.collect();
+	let features = modelfox_features::compute_features_table(table_train, &feature_groups, &|_| {});
+	let train_options = modelfox_tree::TrainOptions {
+		max_rounds: FEATURE_SELECTION_PROBE_MAX_ROUNDS,
+		..Default::default()
+	};
+	let kill_chip = KillChip::default();
+	let progress = modelfox_tree::Progress {
+		kill_chip: &kill_chip,
+		handle_progress_event: &mut |_| {},
+	};
+	let labels = &table_train.columns()[target_column_index];
+	let feature_importances = match task {
+		// The probe does not know the query groups, so for ranking it predicts the relevance labels with a regressor instead.
+		Task::Regression | Task::Ranking => {
+			modelfox_tree::Regressor::train(
+				features.view(),
+				labels.as_number().unwrap(),
+				&train_options,
+				progress,
+			)
+			.feature_importances
+		}
+		Task::BinaryClassification => {
+			modelfox_tree::BinaryClassifier::train(
+				features.view(),
+				labels.as_enum().unwrap(),
+				&train_options,
+				progress,
+			)
+			.feature_importances
+		}
+		Task::MulticlassClassification => {
+			modelfox_tree::MulticlassClassifier::train(
+				features.view(),
+				labels.as_enum().unwrap(),
+				&train_options,
+				progress,
+			)
+			.feature_importances
+		}
+	}
+	.unwrap();
+	let mut feature_importances = feature_importances.into_iter();
+	feature_groups
+		.iter()
+		.map(|feature_group| {
+			feature_importances
+				.by_ref()
+				.take(feature_group.n_features())
+				.sum::<f32>()
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::test_common::{column_stats, number_column, view};
+	use std::num::NonZeroUsize;
+
+	const N_ROWS: usize = 200;
+
+	/// Create a table whose "y" column, the target, is equal to its "x" column. Each other column is one that feature selection should drop.
+	fn table() -> Vec<TableColumn> {
+		let column = |name: &str, f: &dyn Fn(usize) -> f32| number_column(name, (0..N_ROWS).map(f));
+		let x = |index: usize| (index % 8).to_f32().unwrap();
+		vec![
+			column("x", &x),
+			column("x_copy", &|index| 3.0 * x(index) + 1.0),
+			column("constant", &|index| if index == 0 { 2.0 } else { 1.0 }),
+			column("missing", &|index| {
+				if index % 5 < 3 {
+					f32::NAN
+				} else {
+					index.to_f32().unwrap()
+				}
+			}),
+			TableColumn::Enum(EnumTableColumn::new(
+				Some("noise".to_owned()),
+				vec!["a".to_owned(), "b".to_owned(), "c".to_owned()],
+				(0..N_ROWS)
+					.map(|index| NonZeroUsize::new(index % 3 + 1))
+					.collect(),
+			)),
+			column("y", &x),
+		]
+	}
+
+	fn select(columns: &[TableColumn], selection: &config::FeatureSelection) -> Vec<DroppedColumn> {
+		let table = view(columns);
+		select_columns(
+			selection,
+			&config::Config::default(),
+			&table,
+			5,
+			Task::Regression,
+			&column_stats(&table, 5),
+		)
+	}
+
+	#[test]
+	fn test_select_columns() {
+		let columns = table();
+		let dropped_columns = select(
+			&columns,
+			&config::FeatureSelection {
+				max_most_frequent_fraction: None,
+				max_invalid_fraction: None,
+				max_correlation: None,
+				min_importance: Some(0.05),
+			},
+		);
+		let dropped_columns = dropped_columns
+			.iter()
+			.map(|dropped_column| (dropped_column.column_name.as_str(), &dropped_column.reason))
+			.collect::<Vec<_>>();
+		assert_eq!(dropped_columns.len(), 4, "{:?}", dropped_columns);
+		assert!(matches!(
+			dropped_columns[0],
+			("constant", DroppedColumnReason::NearConstant { most_frequent_fraction })
+				if *most_frequent_fraction == 0.995
+		));
+		assert!(matches!(
+			dropped_columns[1],
+			("missing", DroppedColumnReason::InvalidFraction { invalid_fraction })
+				if *invalid_fraction == 0.6
+		));
+		assert!(matches!(
+			dropped_columns[2],
+			("x_copy", DroppedColumnReason::Collinear { column_name, correlation })
+				if column_name == "x" && (correlation - 1.0).abs() < 1e-6
+		));
+		assert!(matches!(
+			dropped_columns[3],
+			("noise", DroppedColumnReason::LowImportance { .. })
+		));
+	}
+
+	#[test]
+	fn test_select_columns_with_loose_thresholds() {
+		// With thresholds that nothing reaches and no probe model, every column is kept.
+		let columns = table();
+		let dropped_columns = select(
+			&columns,
+			&config::FeatureSelection {
+				max_most_frequent_fraction: Some(1.0),
+				max_invalid_fraction: Some(1.0),
+				max_correlation: Some(1.1),
+				min_importance: Some(0.0),
+			},
+		);
+		assert!(dropped_columns.is_empty(), "{:?}", dropped_columns);
+	}
+
+	#[test]
+	fn test_correlation() {
+		let a = [1.0, 2.0, 3.0, 4.0, f32::NAN];
+		assert!((correlation(&a, &[2.0, 4.0, 6.0, 8.0, 0.0]).unwrap() - 1.0).abs() < 1e-6);
+		assert!((correlation(&a, &[4.0, 3.0, 2.0, 1.0, 0.0]).unwrap() + 1.0).abs() < 1e-6);
+		// Rows where either value is invalid are skipped.
+		assert!((correlation(&a, &[1.0, 2.0, 3.0, 4.0, 100.0]).unwrap() - 1.0).abs() < 1e-6);
+		// A column with no variance has no correlation.
+		assert!(correlation(&a, &[1.0, 1.0, 1.0, 1.0, 0.0]).is_none());
+		// a = [1, 2, 3, 4] and b = [1, 3, 2, 4] have correlation 0.8.
+		assert!((correlation(&a, &[1.0, 3.0, 2.0, 4.0, 0.0]).unwrap() - 0.8).abs() < 1e-6);
+	}
+}
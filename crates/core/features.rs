@@ -4,6 +4,10 @@ This module implements ModelFox's feature engineering that prepares datasets for
 
 use crate::{
 	config,
+	heuristics::{
+		HASHED_FEATURE_GROUP_DEFAULT_N_BUCKETS, HASHED_FEATURE_GROUP_MAX_BUCKET_EXAMPLES,
+		POLYNOMIAL_FEATURE_GROUP_DEFAULT_DEGREE,
+	},
 	stats::{
 		ColumnStatsOutput, EnumColumnStatsOutput, NumberColumnStatsOutput, TextColumnStatsOutput,
 		TextColumnStatsOutputTopNGramsEntry,
@@ -11,11 +15,13 @@ use crate::{
 };
 use fnv::FnvBuildHasher;
 use indexmap::IndexMap;
-use modelfox_text::NGram;
+use modelfox_text::{NGram, WordEmbeddingModel};
 use num::ToPrimitive;
+use std::collections::BTreeMap;
 
 pub fn choose_feature_groups_linear(
 	column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, WordEmbeddingModel>,
 	config: &config::Config,
 ) -> Vec<modelfox_features::FeatureGroup> {
 	let mut result = Vec::new();
@@ -32,20 +38,27 @@ pub fn choose_feature_groups_linear(
 	if config.features.auto.enable {
 		for column_stats in column_stats.iter() {
 			if !exclude_columns.contains(column_stats.column_name()) {
-				if let Some(feature_group_type) = choose_feature_group_linear(column_stats, None) {
+				if let Some(feature_group_type) = choose_feature_group_linear(
+					column_stats,
+					None,
+					config.features.auto.impute.as_ref(),
+					config.features.auto.missing_indicator,
+				) {
 					result.push(feature_group_type)
 				}
 			}
 		}
 	}
 	// Add the feature groups provided in the config.
-	let feature_group_types_config = compute_feature_group_types_from_config(column_stats, config);
+	let feature_group_types_config =
+		compute_feature_group_types_from_config(column_stats, word_embedding_models, config);
 	result.extend(feature_group_types_config);
 	result
 }
 
 pub fn choose_feature_groups_tree(
 	column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, WordEmbeddingModel>,
 	config: &config::Config,
 ) -> Vec<modelfox_features::FeatureGroup> {
 	let mut result = Vec::new();
@@ -62,20 +75,27 @@ pub fn choose_feature_groups_tree(
 	if config.features.auto.enable {
 		for column_stats in column_stats.iter() {
 			if !exclude_columns.contains(column_stats.column_name()) {
-				if let Some(feature_group_type) = choose_feature_group_tree(column_stats, None) {
+				if let Some(feature_group_type) = choose_feature_group_tree(
+					column_stats,
+					None,
+					config.features.auto.impute.as_ref(),
+					config.features.auto.missing_indicator,
+				) {
 					result.push(feature_group_type)
 				}
 			}
 		}
 	}
 	// Add the feature groups provided in the config.
-	let feature_group_types_config = compute_feature_group_types_from_config(column_stats, config);
+	let feature_group_types_config =
+		compute_feature_group_types_from_config(column_stats, word_embedding_models, config);
 	result.extend(feature_group_types_config);
 	result
 }
 
 pub fn compute_feature_group_types_from_config(
 	column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, WordEmbeddingModel>,
 	config: &config::Config,
 ) -> Vec<modelfox_features::FeatureGroup> {
 	let mut result = Vec::new();
@@ -94,7 +114,11 @@ pub fn compute_feature_group_types_from_config(
 						column_stats.column_name() == feature_group.source_column_name
 					})
 					.unwrap();
-				result.push(identity_feature_group_for_column(column_stats))
+				result.push(identity_feature_group_for_column(
+					column_stats,
+					feature_group.impute.as_ref(),
+					feature_group.missing_indicator,
+				))
 			}
 			config::FeatureGroup::Normalized(feature_group) => {
 				let column_stats = column_stats
@@ -107,7 +131,11 @@ pub fn compute_feature_group_types_from_config(
 					ColumnStatsOutput::Number(column_stats) => column_stats,
 					_ => panic!(),
 				};
-				result.push(normalized_feature_group_for_column(column_stats))
+				result.push(normalized_feature_group_for_column(
+					column_stats,
+					feature_group.impute.as_ref(),
+					feature_group.missing_indicator,
+				))
 			}
 			config::FeatureGroup::OneHotEncoded(feature_group) => {
 				let column_stats = column_stats
@@ -138,6 +166,50 @@ pub fn compute_feature_group_types_from_config(
 					Some(feature_group),
 				))
 			}
+			config::FeatureGroup::HashedBagOfWords(feature_group) => {
+				let column_stats = column_stats
+					.iter()
+					.find(|column_stats| {
+						column_stats.column_name() == feature_group.source_column_name
+					})
+					.unwrap();
+				let column_stats = match column_stats {
+					ColumnStatsOutput::Text(column_stats) => column_stats,
+					_ => panic!(),
+				};
+				result.push(hashed_bag_of_words_feature_group_for_column(
+					column_stats,
+					feature_group,
+				))
+			}
+			config::FeatureGroup::HashedEnum(feature_group) => {
+				let column_stats = column_stats
+					.iter()
+					.find(|column_stats| {
+						column_stats.column_name() == feature_group.source_column_name
+					})
+					.unwrap();
+				result.push(hashed_enum_feature_group_for_column(
+					column_stats,
+					feature_group,
+				))
+			}
+			config::FeatureGroup::WordEmbedding(feature_group) => {
+				let column_stats = column_stats
+					.iter()
+					.find(|column_stats| {
+						column_stats.column_name() == feature_group.source_column_name
+					})
+					.unwrap();
+				let column_stats = match column_stats {
+					ColumnStatsOutput::Text(column_stats) => column_stats,
+					_ => panic!(),
+				};
+				let model = word_embedding_models
+					.get(&feature_group.source_column_name)
+					.unwrap();
+				result.push(word_embedding_feature_group_for_column(column_stats, model))
+			}
 			config::FeatureGroup::BagOfWordsCosineSimilarity(feature_group) => {
 				let column_stats_a = column_stats
 					.iter()
@@ -165,6 +237,53 @@ pub fn compute_feature_group_types_from_config(
 					Default::default(),
 				))
 			}
+			config::FeatureGroup::Interaction(feature_group) => {
+				let column_stats_a = column_stats
+					.iter()
+					.find(|column_stats| {
+						column_stats.column_name() == feature_group.source_column_name_a
+					})
+					.unwrap();
+				let column_stats_b = column_stats
+					.iter()
+					.find(|column_stats| {
+						column_stats.column_name() == feature_group.source_column_name_b
+					})
+					.unwrap();
+				result.push(interaction_feature_group_for_columns(
+					column_stats_a,
+					column_stats_b,
+				))
+			}
+			config::FeatureGroup::Polynomial(feature_group) => {
+				let column_stats = column_stats
+					.iter()
+					.find(|column_stats| {
+						column_stats.column_name() == feature_group.source_column_name
+					})
+					.unwrap();
+				let column_stats = match column_stats {
+					ColumnStatsOutput::Number(column_stats) => column_stats,
+					_ => panic!(),
+				};
+				result.push(polynomial_feature_group_for_column(
+					column_stats,
+					feature_group,
+				))
+			}
+			config::FeatureGroup::BinnedNumber(feature_group) => {
+				let column_stats = column_stats
+					.iter()
+					.find(|column_stats| {
+						column_stats.column_name() == feature_group.source_column_name
+					})
+					.unwrap();
+				let column_stats = match column_stats {
+					ColumnStatsOutput::Number(column_stats) => column_stats,
+					_ => panic!(),
+				};
+				result.push(binned_number_feature_group_for_column(column_stats))
+			}
 		}
 	}
 	result
@@ -174,12 +293,16 @@ pub fn compute_feature_group_types_from_config(
 pub fn choose_feature_group_linear(
 	column_stats: &ColumnStatsOutput,
 	feature_group_config: Option<&config::FeatureGroup>,
+	impute: Option<&config::Impute>,
+	missing_indicator: bool,
 ) -> Option<modelfox_features::FeatureGroup> {
 	match column_stats {
 		ColumnStatsOutput::Unknown(_) => None,
-		ColumnStatsOutput::Number(column_stats) => {
-			Some(choose_feature_group_linear_number_column(column_stats))
-		}
+		ColumnStatsOutput::Number(column_stats) => Some(choose_feature_group_linear_number_column(
+			column_stats,
+			impute,
+			missing_indicator,
+		)),
 		ColumnStatsOutput::Enum(column_stats) => {
 			Some(choose_feature_group_linear_enum_column(column_stats))
 		}
@@ -199,8 +322,10 @@ pub fn choose_feature_group_linear(
 
 fn choose_feature_group_linear_number_column(
 	column_stats: &NumberColumnStatsOutput,
+	impute: Option<&config::Impute>,
+	missing_indicator: bool,
 ) -> modelfox_features::FeatureGroup {
-	normalized_feature_group_for_column(column_stats)
+	normalized_feature_group_for_column(column_stats, impute, missing_indicator)
 }
 
 fn choose_feature_group_linear_enum_column(
@@ -217,14 +342,24 @@ fn choose_feature_group_linear_text_column(
 }
 
 /// Choose feature group for tree models based on the column stats.
-fn choose_feature_group_tree(
+pub fn choose_feature_group_tree(
 	column_stats: &ColumnStatsOutput,
 	feature_group_config: Option<&config::FeatureGroup>,
+	impute: Option<&config::Impute>,
+	missing_indicator: bool,
 ) -> Option<modelfox_features::FeatureGroup> {
 	match column_stats {
 		ColumnStatsOutput::Unknown(_) => None,
-		ColumnStatsOutput::Number(_) => Some(choose_feature_group_tree_number_column(column_stats)),
-		ColumnStatsOutput::Enum(_) => Some(choose_feature_group_tree_enum_column(column_stats)),
+		ColumnStatsOutput::Number(_) => Some(choose_feature_group_tree_number_column(
+			column_stats,
+			impute,
+			missing_indicator,
+		)),
+		ColumnStatsOutput::Enum(_) => Some(choose_feature_group_tree_enum_column(
+			column_stats,
+			impute,
+			missing_indicator,
+		)),
 		ColumnStatsOutput::Text(column_stats) => {
 			let feature_group_config =
 				feature_group_config.map(|feature_group_config| match feature_group_config {
@@ -241,14 +376,18 @@ fn choose_feature_group_tree(
 
 fn choose_feature_group_tree_number_column(
 	column_stats: &ColumnStatsOutput,
+	impute: Option<&config::Impute>,
+	missing_indicator: bool,
 ) -> modelfox_features::FeatureGroup {
-	identity_feature_group_for_column(column_stats)
+	identity_feature_group_for_column(column_stats, impute, missing_indicator)
 }
 
 fn choose_feature_group_tree_enum_column(
 	column_stats: &ColumnStatsOutput,
+	impute: Option<&config::Impute>,
+	missing_indicator: bool,
 ) -> modelfox_features::FeatureGroup {
-	identity_feature_group_for_column(column_stats)
+	identity_feature_group_for_column(column_stats, impute, missing_indicator)
 }
 
 fn choose_feature_group_tree_text_column(
@@ -260,34 +399,172 @@ fn choose_feature_group_tree_text_column(
 
 fn identity_feature_group_for_column(
 	column_stats: &ColumnStatsOutput,
+	impute: Option<&config::Impute>,
+	missing_indicator: bool,
 ) -> modelfox_features::FeatureGroup {
+	let impute = impute.and_then(|impute| match column_stats {
+		ColumnStatsOutput::Number(column_stats) => {
+			Some(impute_for_number_column(column_stats, impute))
+		}
+		ColumnStatsOutput::Enum(column_stats) => Some(impute_for_enum_column(column_stats, impute)),
+		_ => None,
+	});
 	modelfox_features::FeatureGroup::Identity(modelfox_features::IdentityFeatureGroup {
 		source_column_name: column_stats.column_name().to_owned(),
+		impute,
+		missing_indicator,
 	})
 }
 
 fn normalized_feature_group_for_column(
 	column_stats: &NumberColumnStatsOutput,
+	impute: Option<&config::Impute>,
+	missing_indicator: bool,
 ) -> modelfox_features::FeatureGroup {
 	modelfox_features::FeatureGroup::Normalized(modelfox_features::NormalizedFeatureGroup {
 		source_column_name: column_stats.column_name.to_owned(),
 		mean: column_stats.mean,
 		variance: column_stats.variance,
+		impute: impute.map(|impute| impute_for_number_column(column_stats, impute)),
+		missing_indicator,
 	})
 }
 
+/// Compute the value to impute for invalid values in a number column from the column's stats in the train dataset.
+fn impute_for_number_column(
+	column_stats: &NumberColumnStatsOutput,
+	impute: &config::Impute,
+) -> modelfox_features::Impute {
+	match impute {
+		config::Impute::Mean => modelfox_features::Impute {
+			strategy: modelfox_features::ImputeStrategy::Mean,
+			value: column_stats.mean,
+		},
+		config::Impute::Median => modelfox_features::Impute {
+			strategy: modelfox_features::ImputeStrategy::Median,
+			value: column_stats.p50,
+		},
+		config::Impute::Mode => {
+			// The histogram is not available for columns with many unique values. The median is a reasonable substitute for the mode in that case.
+			let value = column_stats
+				.histogram
+				.as_ref()
+				.and_then(|histogram| {
+					histogram
+						.iter()
+						.max_by_key(|(_, count)| *count)
+						.map(|(value, _)| value.get())
+				})
+				.unwrap_or(column_stats.p50);
+			modelfox_features::Impute {
+				strategy: modelfox_features::ImputeStrategy::Mode,
+				value,
+			}
+		}
+		config::Impute::Constant(constant) => modelfox_features::Impute {
+			strategy: modelfox_features::ImputeStrategy::Constant,
+			value: constant.value,
+		},
+	}
+}
+
+/// Compute the variant to impute for invalid values in an enum column. Enum columns are always imputed with their most frequent variant in the train dataset.
+fn impute_for_enum_column(
+	column_stats: &EnumColumnStatsOutput,
+	_impute: &config::Impute,
+) -> modelfox_features::Impute {
+	let index = column_stats
+		.histogram
+		.iter()
+		.enumerate()
+		.max_by_key(|(_, (_, count))| *count)
+		.map(|(index, _)| index)
+		.unwrap_or(0);
+	modelfox_features::Impute {
+		strategy: modelfox_features::ImputeStrategy::Mode,
+		value: (index + 1).to_f32().unwrap(),
+	}
+}
+
 fn one_hot_encoded_feature_group_for_column(
 	column_stats: &EnumColumnStatsOutput,
 ) -> modelfox_features::FeatureGroup {
+	modelfox_features::FeatureGroup::OneHotEncoded(one_hot_encoded_feature_group(column_stats))
+}
+
+fn one_hot_encoded_feature_group(
+	column_stats: &EnumColumnStatsOutput,
+) -> modelfox_features::OneHotEncodedFeatureGroup {
 	let mut unique_values: Vec<_> = column_stats
 		.histogram
 		.iter()
 		.map(|(value, _)| value.clone())
 		.collect();
 	unique_values.sort_unstable();
-	modelfox_features::FeatureGroup::OneHotEncoded(modelfox_features::OneHotEncodedFeatureGroup {
+	modelfox_features::OneHotEncodedFeatureGroup {
 		source_column_name: column_stats.column_name.to_owned(),
 		variants: unique_values,
+	}
+}
+
+fn interaction_feature_group_for_columns(
+	column_stats_a: &ColumnStatsOutput,
+	column_stats_b: &ColumnStatsOutput,
+) -> modelfox_features::FeatureGroup {
+	modelfox_features::FeatureGroup::Interaction(modelfox_features::InteractionFeatureGroup {
+		source_a: interaction_feature_group_source_for_column(column_stats_a),
+		source_b: interaction_feature_group_source_for_column(column_stats_b),
+	})
+}
+
+/// Number columns are normalized and enum columns are one hot encoded before they are multiplied.
+fn interaction_feature_group_source_for_column(
+	column_stats: &ColumnStatsOutput,
+) -> modelfox_features::InteractionFeatureGroupSource {
+	match column_stats {
+		ColumnStatsOutput::Number(column_stats) => {
+			modelfox_features::InteractionFeatureGroupSource::Normalized(
+				modelfox_features::NormalizedFeatureGroup {
+					source_column_name: column_stats.column_name.to_owned(),
+					mean: column_stats.mean,
+					variance: column_stats.variance,
+					impute: None,
+					missing_indicator: false,
+				},
+			)
+		}
+		ColumnStatsOutput::Enum(column_stats) => {
+			modelfox_features::InteractionFeatureGroupSource::OneHotEncoded(
+				one_hot_encoded_feature_group(column_stats),
+			)
+		}
+		_ => panic!(),
+	}
+}
+
+fn polynomial_feature_group_for_column(
+	column_stats: &NumberColumnStatsOutput,
+	feature_group: &config::PolynomialFeatureGroup,
+) -> modelfox_features::FeatureGroup {
+	modelfox_features::FeatureGroup::Polynomial(modelfox_features::PolynomialFeatureGroup {
+		source_column_name: column_stats.column_name.to_owned(),
+		mean: column_stats.mean,
+		variance: column_stats.variance,
+		degree: feature_group
+			.degree
+			.unwrap_or(POLYNOMIAL_FEATURE_GROUP_DEFAULT_DEGREE),
+	})
+}
+
+/// The thresholds are the quantiles requested for the column in the stats settings. Columns with many repeated values can have equal quantiles, which are merged so that no bin is empty.
+fn binned_number_feature_group_for_column(
+	column_stats: &NumberColumnStatsOutput,
+) -> modelfox_features::FeatureGroup {
+	let mut thresholds = column_stats.quantiles.clone();
+	thresholds.dedup();
+	modelfox_features::FeatureGroup::BinnedNumber(modelfox_features::BinnedNumberFeatureGroup {
+		source_column_name: column_stats.column_name.to_owned(),
+		thresholds,
 	})
 }
 
@@ -337,6 +614,80 @@ fn bag_of_words_feature_group_for_column(
 	})
 }
 
+fn hashed_bag_of_words_feature_group_for_column(
+	column_stats: &TextColumnStatsOutput,
+	feature_group: &config::HashedBagOfWordsFeatureGroup,
+) -> modelfox_features::FeatureGroup {
+	let strategy = match feature_group.strategy {
+		Some(config::HashedBagOfWordsFeatureGroupStrategy::Count) => {
+			modelfox_features::hashed_bag_of_words::HashedBagOfWordsFeatureGroupStrategy::Count
+		}
+		Some(config::HashedBagOfWordsFeatureGroupStrategy::Present) | None => {
+			modelfox_features::hashed_bag_of_words::HashedBagOfWordsFeatureGroupStrategy::Present
+		}
+	};
+	let n_buckets = feature_group
+		.n_buckets
+		.map(|n_buckets| n_buckets.get())
+		.unwrap_or(HASHED_FEATURE_GROUP_DEFAULT_N_BUCKETS);
+	let mut feature_group = modelfox_features::HashedBagOfWordsFeatureGroup {
+		source_column_name: column_stats.column_name.to_owned(),
+		strategy,
+		tokenizer: column_stats.tokenizer.clone(),
+		ngram_types: column_stats.ngram_types.to_owned(),
+		n_buckets,
+		signed: feature_group.signed.unwrap_or(true),
+		bucket_ngrams: vec![Vec::new(); n_buckets],
+	};
+	// The top ngrams are sorted by descending row count, so the first ngrams assigned to each bucket are its most frequent.
+	for ngram in column_stats.top_ngrams.keys() {
+		let (bucket, _) = feature_group.bucket(&ngram.as_ngram_ref());
+		let bucket_ngrams = &mut feature_group.bucket_ngrams[bucket];
+		if bucket_ngrams.len() < HASHED_FEATURE_GROUP_MAX_BUCKET_EXAMPLES {
+			bucket_ngrams.push(ngram.clone());
+		}
+	}
+	modelfox_features::FeatureGroup::HashedBagOfWords(feature_group)
+}
+
+fn hashed_enum_feature_group_for_column(
+	column_stats: &ColumnStatsOutput,
+	feature_group: &config::HashedEnumFeatureGroup,
+) -> modelfox_features::FeatureGroup {
+	let mut value_counts: Vec<(&str, usize)> = match column_stats {
+		ColumnStatsOutput::Enum(column_stats) => column_stats
+			.histogram
+			.iter()
+			.map(|(value, count)| (value.as_str(), *count))
+			.collect(),
+		ColumnStatsOutput::Text(column_stats) => column_stats
+			.top_values
+			.iter()
+			.map(|(value, count)| (value.as_str(), *count))
+			.collect(),
+		_ => panic!(),
+	};
+	value_counts.sort_by(|(_, count_a), (_, count_b)| count_b.cmp(count_a));
+	let n_buckets = feature_group
+		.n_buckets
+		.map(|n_buckets| n_buckets.get())
+		.unwrap_or(HASHED_FEATURE_GROUP_DEFAULT_N_BUCKETS);
+	let mut feature_group = modelfox_features::HashedEnumFeatureGroup {
+		source_column_name: column_stats.column_name().to_owned(),
+		n_buckets,
+		signed: feature_group.signed.unwrap_or(true),
+		bucket_values: vec![Vec::new(); n_buckets],
+	};
+	for (value, _) in value_counts {
+		let (bucket, _) = feature_group.bucket(value);
+		let bucket_values = &mut feature_group.bucket_values[bucket];
+		if bucket_values.len() < HASHED_FEATURE_GROUP_MAX_BUCKET_EXAMPLES {
+			bucket_values.push(value.to_owned());
+		}
+	}
+	modelfox_features::FeatureGroup::HashedEnum(feature_group)
+}
+
 struct BagOfWordsCosineSimilarityFeatureGroupSettings {
 	ngrams_max_count: usize,
 }
@@ -401,14 +752,13 @@ fn bag_of_words_cosine_similarity_feature_group_for_column(
 	)
 }
 
-// fn word_embedding_feature_group_for_column(
-// 	column_stats: &TextColumnStatsOutput,
-// ) -> modelfox_features::FeatureGroup {
-// 	let model = buffalo::read::<modelfox_model::WordEmbeddingModelReader>(bytes);
-// 	let model = model.into();
-// 	modelfox_features::FeatureGroup::WordEmbedding(modelfox_features::WordEmbeddingFeatureGroup {
-// 		source_column_name: column_stats.column_name.to_owned(),
-// 		tokenizer: column_stats.tokenizer.clone(),
-// 		model,
-// 	})
-// }
+fn word_embedding_feature_group_for_column(
+	column_stats: &TextColumnStatsOutput,
+	model: &WordEmbeddingModel,
+) -> modelfox_features::FeatureGroup {
+	modelfox_features::FeatureGroup::WordEmbedding(modelfox_features::WordEmbeddingFeatureGroup {
+		source_column_name: column_stats.column_name.to_owned(),
+		tokenizer: column_stats.tokenizer.clone(),
+		model: model.clone(),
+	})
+}
@@ -4,6 +4,8 @@ use crate::{
 	stats::ColumnStatsOutput,
 };
 use itertools::iproduct;
+use modelfox_text::WordEmbeddingModel;
+use std::collections::BTreeMap;
 
 /// A `GridItem` is a description of a single entry in a hyperparameter grid. It specifies what feature engineering to perform on the training data, which model to train, and which hyperparameters to use.
 #[derive(Clone, Debug)]
@@ -38,21 +40,37 @@ pub enum GridItem {
 		feature_groups: Vec<modelfox_features::FeatureGroup>,
 		options: TreeModelTrainOptions,
 	},
+	TreeRanker {
+		target_column_index: usize,
+		group_column_index: usize,
+		ndcg_k: Option<usize>,
+		feature_groups: Vec<modelfox_features::FeatureGroup>,
+		options: TreeModelTrainOptions,
+	},
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct LinearModelTrainOptions {
+	pub l1_regularization: Option<f32>,
 	pub l2_regularization: Option<f32>,
 	pub learning_rate: Option<f32>,
+	pub learning_rate_schedule: Option<LearningRateSchedule>,
 	pub max_epochs: Option<u64>,
 	pub n_examples_per_batch: Option<u64>,
+	pub optimizer: Option<LinearOptimizer>,
 	pub early_stopping_options: Option<EarlyStoppingOptions>,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct TreeModelTrainOptions {
+	pub bagging_fraction: Option<f32>,
+	pub bagging_frequency: Option<u64>,
 	pub binned_features_layout: Option<BinnedFeaturesLayout>,
+	pub dart_options: Option<DartOptions>,
 	pub early_stopping_options: Option<EarlyStoppingOptions>,
+	pub feature_fraction_per_node: Option<f32>,
+	pub feature_fraction_per_tree: Option<f32>,
+	pub goss_options: Option<GossOptions>,
 	pub l2_regularization_for_continuous_splits: Option<f32>,
 	pub l2_regularization_for_discrete_splits: Option<f32>,
 	pub learning_rate: Option<f32>,
@@ -64,15 +82,49 @@ pub struct TreeModelTrainOptions {
 	pub min_examples_per_node: Option<u64>,
 	pub min_gain_to_split: Option<f32>,
 	pub min_sum_hessians_per_node: Option<f32>,
+	pub seed: Option<u64>,
 	pub smoothing_factor_for_discrete_bin_sorting: Option<f32>,
 }
 
+#[derive(Clone, Copy, Debug)]
+pub enum LinearOptimizer {
+	Sgd,
+	Momentum {
+		momentum: f32,
+	},
+	Adam {
+		beta_1: f32,
+		beta_2: f32,
+		epsilon: f32,
+	},
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum LearningRateSchedule {
+	Constant,
+	InverseTimeDecay { decay_rate: f32 },
+	ExponentialDecay { decay_rate: f32 },
+}
+
 #[derive(Clone, Debug)]
 pub enum BinnedFeaturesLayout {
 	RowMajor,
 	ColumnMajor,
 }
 
+#[derive(Clone, Debug)]
+pub struct DartOptions {
+	pub drop_rate: f32,
+	pub max_drop: u64,
+	pub skip_drop: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct GossOptions {
+	pub top_rate: f32,
+	pub other_rate: f32,
+}
+
 #[derive(Clone, Debug)]
 pub struct EarlyStoppingOptions {
 	pub early_stopping_fraction: f32,
@@ -94,18 +146,29 @@ pub fn compute_regression_hyperparameter_grid(
 	grid: &[config::GridItem],
 	target_column_index: usize,
 	column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, WordEmbeddingModel>,
 	config: &config::Config,
 ) -> Vec<GridItem> {
 	grid.iter()
 		.map(|item| match item {
 			config::GridItem::Linear(item) => GridItem::LinearRegressor {
 				target_column_index,
-				feature_groups: choose_feature_groups_linear(column_stats, config),
+				feature_groups: choose_feature_groups_linear(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: LinearModelTrainOptions {
+					l1_regularization: item.l1_regularization,
 					l2_regularization: item.l2_regularization,
 					learning_rate: item.learning_rate,
+					learning_rate_schedule: item
+						.learning_rate_schedule
+						.as_ref()
+						.map(learning_rate_schedule_from_config),
 					max_epochs: item.max_epochs,
 					n_examples_per_batch: item.n_examples_per_batch,
+					optimizer: item.optimizer.as_ref().map(linear_optimizer_from_config),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -119,8 +182,14 @@ pub fn compute_regression_hyperparameter_grid(
 			},
 			config::GridItem::Tree(item) => GridItem::TreeRegressor {
 				target_column_index,
-				feature_groups: choose_feature_groups_tree(column_stats, config),
+				feature_groups: choose_feature_groups_tree(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: TreeModelTrainOptions {
+					bagging_fraction: item.bagging_fraction,
+					bagging_frequency: item.bagging_frequency,
 					binned_features_layout: item.binned_features_layout.as_ref().map(
 						|binned_feature_layout| match binned_feature_layout {
 							config::BinnedFeaturesLayout::RowMajor => {
@@ -131,6 +200,11 @@ pub fn compute_regression_hyperparameter_grid(
 							}
 						},
 					),
+					dart_options: item.dart_options.as_ref().map(|dart_options| DartOptions {
+						drop_rate: dart_options.drop_rate,
+						max_drop: dart_options.max_drop,
+						skip_drop: dart_options.skip_drop,
+					}),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -140,6 +214,12 @@ pub fn compute_regression_hyperparameter_grid(
 								.min_decrease_in_loss_for_significant_change,
 						},
 					),
+					feature_fraction_per_node: item.feature_fraction_per_node,
+					feature_fraction_per_tree: item.feature_fraction_per_tree,
+					goss_options: item.goss_options.as_ref().map(|goss_options| GossOptions {
+						top_rate: goss_options.top_rate,
+						other_rate: goss_options.other_rate,
+					}),
 					l2_regularization_for_continuous_splits: item
 						.l2_regularization_for_continuous_splits,
 					l2_regularization_for_discrete_splits: item
@@ -154,6 +234,7 @@ pub fn compute_regression_hyperparameter_grid(
 					min_examples_per_node: item.min_examples_per_node,
 					min_gain_to_split: item.min_gain_to_split,
 					min_sum_hessians_per_node: item.min_sum_hessians_per_node,
+					seed: item.seed,
 					smoothing_factor_for_discrete_bin_sorting: item
 						.smoothing_factor_for_discrete_bin_sorting,
 				},
@@ -162,22 +243,76 @@ pub fn compute_regression_hyperparameter_grid(
 		.collect()
 }
 
+/// Compute the hyperparameter grid for ranking. Only tree models can be trained for ranking, so the tree items are taken from the regression grid and the linear items are skipped.
+pub fn compute_ranking_hyperparameter_grid(
+	grid: &[config::GridItem],
+	target_column_index: usize,
+	group_column_index: usize,
+	ndcg_k: Option<usize>,
+	column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, WordEmbeddingModel>,
+	config: &config::Config,
+) -> Vec<GridItem> {
+	compute_regression_hyperparameter_grid(
+		grid,
+		target_column_index,
+		column_stats,
+		word_embedding_models,
+		config,
+	)
+	.into_iter()
+	.filter_map(|item| ranking_grid_item(item, group_column_index, ndcg_k))
+	.collect()
+}
+
+fn ranking_grid_item(
+	item: GridItem,
+	group_column_index: usize,
+	ndcg_k: Option<usize>,
+) -> Option<GridItem> {
+	match item {
+		GridItem::TreeRegressor {
+			target_column_index,
+			feature_groups,
+			options,
+		} => Some(GridItem::TreeRanker {
+			target_column_index,
+			group_column_index,
+			ndcg_k,
+			feature_groups,
+			options,
+		}),
+		_ => None,
+	}
+}
+
 pub fn compute_binary_classification_hyperparameter_grid(
 	grid: &[config::GridItem],
 	target_column_index: usize,
 	column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, WordEmbeddingModel>,
 	config: &config::Config,
 ) -> Vec<GridItem> {
 	grid.iter()
 		.map(|item| match item {
 			config::GridItem::Linear(item) => GridItem::LinearBinaryClassifier {
 				target_column_index,
-				feature_groups: choose_feature_groups_linear(column_stats, config),
+				feature_groups: choose_feature_groups_linear(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: LinearModelTrainOptions {
+					l1_regularization: item.l1_regularization,
 					l2_regularization: item.l2_regularization,
 					learning_rate: item.learning_rate,
+					learning_rate_schedule: item
+						.learning_rate_schedule
+						.as_ref()
+						.map(learning_rate_schedule_from_config),
 					max_epochs: item.max_epochs,
 					n_examples_per_batch: item.n_examples_per_batch,
+					optimizer: item.optimizer.as_ref().map(linear_optimizer_from_config),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -191,8 +326,14 @@ pub fn compute_binary_classification_hyperparameter_grid(
 			},
 			config::GridItem::Tree(item) => GridItem::TreeBinaryClassifier {
 				target_column_index,
-				feature_groups: choose_feature_groups_tree(column_stats, config),
+				feature_groups: choose_feature_groups_tree(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: TreeModelTrainOptions {
+					bagging_fraction: item.bagging_fraction,
+					bagging_frequency: item.bagging_frequency,
 					binned_features_layout: item.binned_features_layout.as_ref().map(
 						|binned_feature_layout| match binned_feature_layout {
 							config::BinnedFeaturesLayout::RowMajor => {
@@ -203,6 +344,11 @@ pub fn compute_binary_classification_hyperparameter_grid(
 							}
 						},
 					),
+					dart_options: item.dart_options.as_ref().map(|dart_options| DartOptions {
+						drop_rate: dart_options.drop_rate,
+						max_drop: dart_options.max_drop,
+						skip_drop: dart_options.skip_drop,
+					}),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -212,6 +358,12 @@ pub fn compute_binary_classification_hyperparameter_grid(
 								.min_decrease_in_loss_for_significant_change,
 						},
 					),
+					feature_fraction_per_node: item.feature_fraction_per_node,
+					feature_fraction_per_tree: item.feature_fraction_per_tree,
+					goss_options: item.goss_options.as_ref().map(|goss_options| GossOptions {
+						top_rate: goss_options.top_rate,
+						other_rate: goss_options.other_rate,
+					}),
 					l2_regularization_for_continuous_splits: item
 						.l2_regularization_for_continuous_splits,
 					l2_regularization_for_discrete_splits: item
@@ -226,6 +378,7 @@ pub fn compute_binary_classification_hyperparameter_grid(
 					min_examples_per_node: item.min_examples_per_node,
 					min_gain_to_split: item.min_gain_to_split,
 					min_sum_hessians_per_node: item.min_sum_hessians_per_node,
+					seed: item.seed,
 					smoothing_factor_for_discrete_bin_sorting: item
 						.smoothing_factor_for_discrete_bin_sorting,
 				},
@@ -238,18 +391,29 @@ pub fn compute_multiclass_classification_hyperparameter_grid(
 	grid: &[config::GridItem],
 	target_column_index: usize,
 	column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, WordEmbeddingModel>,
 	config: &config::Config,
 ) -> Vec<GridItem> {
 	grid.iter()
 		.map(|item| match item {
 			config::GridItem::Linear(item) => GridItem::LinearMulticlassClassifier {
 				target_column_index,
-				feature_groups: choose_feature_groups_linear(column_stats, config),
+				feature_groups: choose_feature_groups_linear(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: LinearModelTrainOptions {
+					l1_regularization: item.l1_regularization,
 					l2_regularization: item.l2_regularization,
 					learning_rate: item.learning_rate,
+					learning_rate_schedule: item
+						.learning_rate_schedule
+						.as_ref()
+						.map(learning_rate_schedule_from_config),
 					max_epochs: item.max_epochs,
 					n_examples_per_batch: item.n_examples_per_batch,
+					optimizer: item.optimizer.as_ref().map(linear_optimizer_from_config),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -263,8 +427,14 @@ pub fn compute_multiclass_classification_hyperparameter_grid(
 			},
 			config::GridItem::Tree(item) => GridItem::TreeMulticlassClassifier {
 				target_column_index,
-				feature_groups: choose_feature_groups_tree(column_stats, config),
+				feature_groups: choose_feature_groups_tree(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: TreeModelTrainOptions {
+					bagging_fraction: item.bagging_fraction,
+					bagging_frequency: item.bagging_frequency,
 					binned_features_layout: item.binned_features_layout.as_ref().map(
 						|binned_feature_layout| match binned_feature_layout {
 							config::BinnedFeaturesLayout::RowMajor => {
@@ -275,6 +445,11 @@ pub fn compute_multiclass_classification_hyperparameter_grid(
 							}
 						},
 					),
+					dart_options: item.dart_options.as_ref().map(|dart_options| DartOptions {
+						drop_rate: dart_options.drop_rate,
+						max_drop: dart_options.max_drop,
+						skip_drop: dart_options.skip_drop,
+					}),
 					early_stopping_options: item.early_stopping_options.as_ref().map(
 						|early_stopping_options| EarlyStoppingOptions {
 							early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -284,6 +459,12 @@ pub fn compute_multiclass_classification_hyperparameter_grid(
 								.min_decrease_in_loss_for_significant_change,
 						},
 					),
+					feature_fraction_per_node: item.feature_fraction_per_node,
+					feature_fraction_per_tree: item.feature_fraction_per_tree,
+					goss_options: item.goss_options.as_ref().map(|goss_options| GossOptions {
+						top_rate: goss_options.top_rate,
+						other_rate: goss_options.other_rate,
+					}),
 					l2_regularization_for_continuous_splits: item
 						.l2_regularization_for_continuous_splits,
 					l2_regularization_for_discrete_splits: item
@@ -298,6 +479,7 @@ pub fn compute_multiclass_classification_hyperparameter_grid(
 					min_examples_per_node: item.min_examples_per_node,
 					min_gain_to_split: item.min_gain_to_split,
 					min_sum_hessians_per_node: item.min_sum_hessians_per_node,
+					seed: item.seed,
 					smoothing_factor_for_discrete_bin_sorting: item
 						.smoothing_factor_for_discrete_bin_sorting,
 				},
@@ -306,8 +488,59 @@ pub fn compute_multiclass_classification_hyperparameter_grid(
 		.collect()
 }
 
-const DEFAULT_LINEAR_MODEL_LEARNING_RATE_VALUES: [f32; 2] = [0.1, 0.01];
-const DEFAULT_LINEAR_L2_REGULARIZATION_VALUES: [f32; 2] = [1.0, 0.1];
+const DEFAULT_MOMENTUM: f32 = 0.9;
+const DEFAULT_ADAM_BETA_1: f32 = 0.9;
+const DEFAULT_ADAM_BETA_2: f32 = 0.999;
+const DEFAULT_ADAM_EPSILON: f32 = 1e-8;
+
+fn linear_optimizer_from_config(optimizer: &config::LinearOptimizer) -> LinearOptimizer {
+	match optimizer {
+		config::LinearOptimizer::Sgd => LinearOptimizer::Sgd,
+		config::LinearOptimizer::Momentum(optimizer) => LinearOptimizer::Momentum {
+			momentum: optimizer.momentum.unwrap_or(DEFAULT_MOMENTUM),
+		},
+		config::LinearOptimizer::Adam(optimizer) => LinearOptimizer::Adam {
+			beta_1: optimizer.beta_1.unwrap_or(DEFAULT_ADAM_BETA_1),
+			beta_2: optimizer.beta_2.unwrap_or(DEFAULT_ADAM_BETA_2),
+			epsilon: optimizer.epsilon.unwrap_or(DEFAULT_ADAM_EPSILON),
+		},
+	}
+}
+
+fn learning_rate_schedule_from_config(
+	learning_rate_schedule: &config::LearningRateSchedule,
+) -> LearningRateSchedule {
+	match learning_rate_schedule {
+		config::LearningRateSchedule::Constant => LearningRateSchedule::Constant,
+		config::LearningRateSchedule::InverseTimeDecay(decay) => {
+			LearningRateSchedule::InverseTimeDecay {
+				decay_rate: decay.decay_rate,
+			}
+		}
+		config::LearningRateSchedule::ExponentialDecay(decay) => {
+			LearningRateSchedule::ExponentialDecay {
+				decay_rate: decay.decay_rate,
+			}
+		}
+	}
+}
+
+/// Each pair is an L1 and an L2 regularization value. The last pair uses elastic net regularization.
+const DEFAULT_LINEAR_REGULARIZATION_VALUES: [(f32, f32); 3] =
+	[(0.0, 0.01), (0.0, 0.001), (0.001, 0.001)];
+/// Each optimizer is paired with the learning rate to use with it.
+const DEFAULT_LINEAR_OPTIMIZER_VALUES: [(LinearOptimizer, f32); 3] = [
+	(LinearOptimizer::Sgd, 0.1),
+	(LinearOptimizer::Sgd, 0.01),
+	(
+		LinearOptimizer::Adam {
+			beta_1: DEFAULT_ADAM_BETA_1,
+			beta_2: DEFAULT_ADAM_BETA_2,
+			epsilon: DEFAULT_ADAM_EPSILON,
+		},
+		0.001,
+	),
+];
 const DEFAULT_LINEAR_MAX_EPOCHS_VALUES: [u64; 1] = [1000];
 const DEFAULT_LINEAR_N_EXAMPLES_PER_BATCH_VALUES: [u64; 1] = [128];
 
@@ -316,11 +549,13 @@ const DEFAULT_TREE_L2_REGULARIZATION_VALUES_FOR_CONTINUOUS_SPLITS: [f32; 2] = [1
 const DEFAULT_TREE_MAX_LEAF_NODES: [u64; 1] = [512];
 const DEFAULT_TREE_MAX_ROUNDS_VALUES: [u64; 1] = [1000];
 const DEFAULT_TREE_MAX_DEPTH: [u64; 1] = [50];
+const DEFAULT_TREE_BAGGING_FRACTION_VALUES: [f32; 2] = [1.0, 0.8];
 
 /// Compute the default hyperparameter grid for regression.
 pub fn auto_regression_hyperparameter_grid(
 	target_column_index: usize,
 	column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, WordEmbeddingModel>,
 	config: &config::Config,
 ) -> Vec<GridItem> {
 	let autogrid = &config.train.autogrid;
@@ -336,20 +571,32 @@ pub fn auto_regression_hyperparameter_grid(
 	};
 	let mut grid = Vec::new();
 	if train_linear {
-		for (&l2_regularization, &learning_rate, &max_epochs, &n_examples_per_batch) in iproduct!(
-			DEFAULT_LINEAR_L2_REGULARIZATION_VALUES.iter(),
-			DEFAULT_LINEAR_MODEL_LEARNING_RATE_VALUES.iter(),
+		for (
+			&(l1_regularization, l2_regularization),
+			&(optimizer, learning_rate),
+			&max_epochs,
+			&n_examples_per_batch,
+		) in iproduct!(
+			DEFAULT_LINEAR_REGULARIZATION_VALUES.iter(),
+			DEFAULT_LINEAR_OPTIMIZER_VALUES.iter(),
 			DEFAULT_LINEAR_MAX_EPOCHS_VALUES.iter(),
 			DEFAULT_LINEAR_N_EXAMPLES_PER_BATCH_VALUES.iter()
 		) {
 			grid.push(GridItem::LinearRegressor {
 				target_column_index,
-				feature_groups: choose_feature_groups_linear(column_stats, config),
+				feature_groups: choose_feature_groups_linear(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: LinearModelTrainOptions {
+					l1_regularization: Some(l1_regularization),
 					l2_regularization: Some(l2_regularization),
 					learning_rate: Some(learning_rate),
+					learning_rate_schedule: None,
 					max_epochs: Some(max_epochs),
 					n_examples_per_batch: Some(n_examples_per_batch),
+					optimizer: Some(optimizer),
 					early_stopping_options: Some(Default::default()),
 				},
 			});
@@ -362,16 +609,22 @@ pub fn auto_regression_hyperparameter_grid(
 			&l2_regularization_for_continuous_splits,
 			&max_rounds,
 			&max_depth,
+			&bagging_fraction,
 		) in iproduct!(
 			DEFAULT_TREE_MAX_LEAF_NODES.iter(),
 			DEFAULT_TREE_LEARNING_RATE_VALUES.iter(),
 			DEFAULT_TREE_L2_REGULARIZATION_VALUES_FOR_CONTINUOUS_SPLITS.iter(),
 			DEFAULT_TREE_MAX_ROUNDS_VALUES.iter(),
-			DEFAULT_TREE_MAX_DEPTH.iter()
+			DEFAULT_TREE_MAX_DEPTH.iter(),
+			DEFAULT_TREE_BAGGING_FRACTION_VALUES.iter()
 		) {
 			grid.push(GridItem::TreeRegressor {
 				target_column_index,
-				feature_groups: choose_feature_groups_tree(column_stats, config),
+				feature_groups: choose_feature_groups_tree(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: TreeModelTrainOptions {
 					max_leaf_nodes: Some(max_leaf_nodes),
 					learning_rate: Some(learning_rate),
@@ -380,6 +633,8 @@ pub fn auto_regression_hyperparameter_grid(
 					l2_regularization_for_continuous_splits: Some(
 						l2_regularization_for_continuous_splits,
 					),
+					bagging_fraction: Some(bagging_fraction),
+					bagging_frequency: Some(1),
 					early_stopping_options: Some(Default::default()),
 					..Default::default()
 				},
@@ -389,10 +644,31 @@ pub fn auto_regression_hyperparameter_grid(
 	grid
 }
 
+/// Compute the default hyperparameter grid for ranking. This is the tree portion of the default regression grid.
+pub fn auto_ranking_hyperparameter_grid(
+	target_column_index: usize,
+	group_column_index: usize,
+	ndcg_k: Option<usize>,
+	column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, WordEmbeddingModel>,
+	config: &config::Config,
+) -> Vec<GridItem> {
+	auto_regression_hyperparameter_grid(
+		target_column_index,
+		column_stats,
+		word_embedding_models,
+		config,
+	)
+	.into_iter()
+	.filter_map(|item| ranking_grid_item(item, group_column_index, ndcg_k))
+	.collect()
+}
+
 /// Compute the default hyperparameter grid for binary classification.
 pub fn auto_binary_classification_hyperparameter_grid(
 	target_column_index: usize,
 	column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, WordEmbeddingModel>,
 	config: &config::Config,
 ) -> Vec<GridItem> {
 	let autogrid = &config.train.autogrid;
@@ -408,20 +684,32 @@ pub fn auto_binary_classification_hyperparameter_grid(
 	};
 	let mut grid = Vec::new();
 	if train_linear {
-		for (&l2_regularization, &learning_rate, &max_epochs, &n_examples_per_batch) in iproduct!(
-			DEFAULT_LINEAR_L2_REGULARIZATION_VALUES.iter(),
-			DEFAULT_LINEAR_MODEL_LEARNING_RATE_VALUES.iter(),
+		for (
+			&(l1_regularization, l2_regularization),
+			&(optimizer, learning_rate),
+			&max_epochs,
+			&n_examples_per_batch,
+		) in iproduct!(
+			DEFAULT_LINEAR_REGULARIZATION_VALUES.iter(),
+			DEFAULT_LINEAR_OPTIMIZER_VALUES.iter(),
 			DEFAULT_LINEAR_MAX_EPOCHS_VALUES.iter(),
 			DEFAULT_LINEAR_N_EXAMPLES_PER_BATCH_VALUES.iter()
 		) {
 			grid.push(GridItem::LinearBinaryClassifier {
 				target_column_index,
-				feature_groups: choose_feature_groups_linear(column_stats, config),
+				feature_groups: choose_feature_groups_linear(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: LinearModelTrainOptions {
+					l1_regularization: Some(l1_regularization),
 					l2_regularization: Some(l2_regularization),
 					learning_rate: Some(learning_rate),
+					learning_rate_schedule: None,
 					max_epochs: Some(max_epochs),
 					n_examples_per_batch: Some(n_examples_per_batch),
+					optimizer: Some(optimizer),
 					early_stopping_options: Some(Default::default()),
 				},
 			});
@@ -434,16 +722,22 @@ pub fn auto_binary_classification_hyperparameter_grid(
 			&l2_regularization_for_continous_splits,
 			&max_rounds,
 			&max_depth,
+			&bagging_fraction,
 		) in iproduct!(
 			DEFAULT_TREE_MAX_LEAF_NODES.iter(),
 			DEFAULT_TREE_LEARNING_RATE_VALUES.iter(),
 			DEFAULT_TREE_L2_REGULARIZATION_VALUES_FOR_CONTINUOUS_SPLITS.iter(),
 			DEFAULT_TREE_MAX_ROUNDS_VALUES.iter(),
-			DEFAULT_TREE_MAX_DEPTH.iter()
+			DEFAULT_TREE_MAX_DEPTH.iter(),
+			DEFAULT_TREE_BAGGING_FRACTION_VALUES.iter()
 		) {
 			grid.push(GridItem::TreeBinaryClassifier {
 				target_column_index,
-				feature_groups: choose_feature_groups_tree(column_stats, config),
+				feature_groups: choose_feature_groups_tree(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: TreeModelTrainOptions {
 					max_leaf_nodes: Some(max_leaf_nodes),
 					learning_rate: Some(learning_rate),
@@ -452,6 +746,8 @@ pub fn auto_binary_classification_hyperparameter_grid(
 					l2_regularization_for_continuous_splits: Some(
 						l2_regularization_for_continous_splits,
 					),
+					bagging_fraction: Some(bagging_fraction),
+					bagging_frequency: Some(1),
 					early_stopping_options: Some(Default::default()),
 					..Default::default()
 				},
@@ -465,6 +761,7 @@ pub fn auto_binary_classification_hyperparameter_grid(
 pub fn auto_multiclass_classification_hyperparameter_grid(
 	target_column_index: usize,
 	column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, WordEmbeddingModel>,
 	config: &config::Config,
 ) -> Vec<GridItem> {
 	let autogrid = &config.train.autogrid;
@@ -480,20 +777,32 @@ pub fn auto_multiclass_classification_hyperparameter_grid(
 	};
 	let mut grid = Vec::new();
 	if train_linear {
-		for (&l2_regularization, &learning_rate, &max_epochs, &n_examples_per_batch) in iproduct!(
-			DEFAULT_LINEAR_L2_REGULARIZATION_VALUES.iter(),
-			DEFAULT_LINEAR_MODEL_LEARNING_RATE_VALUES.iter(),
+		for (
+			&(l1_regularization, l2_regularization),
+			&(optimizer, learning_rate),
+			&max_epochs,
+			&n_examples_per_batch,
+		) in iproduct!(
+			DEFAULT_LINEAR_REGULARIZATION_VALUES.iter(),
+			DEFAULT_LINEAR_OPTIMIZER_VALUES.iter(),
 			DEFAULT_LINEAR_MAX_EPOCHS_VALUES.iter(),
 			DEFAULT_LINEAR_N_EXAMPLES_PER_BATCH_VALUES.iter()
 		) {
 			grid.push(GridItem::LinearMulticlassClassifier {
 				target_column_index,
-				feature_groups: choose_feature_groups_linear(column_stats, config),
+				feature_groups: choose_feature_groups_linear(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: LinearModelTrainOptions {
+					l1_regularization: Some(l1_regularization),
 					l2_regularization: Some(l2_regularization),
 					learning_rate: Some(learning_rate),
+					learning_rate_schedule: None,
 					max_epochs: Some(max_epochs),
 					n_examples_per_batch: Some(n_examples_per_batch),
+					optimizer: Some(optimizer),
 					early_stopping_options: Some(Default::default()),
 				},
 			});
@@ -506,16 +815,22 @@ pub fn auto_multiclass_classification_hyperparameter_grid(
 			&l2_regularization_for_continuous_splits,
 			&max_rounds,
 			&max_depth,
+			&bagging_fraction,
 		) in iproduct!(
 			DEFAULT_TREE_MAX_LEAF_NODES.iter(),
 			DEFAULT_TREE_LEARNING_RATE_VALUES.iter(),
 			DEFAULT_TREE_L2_REGULARIZATION_VALUES_FOR_CONTINUOUS_SPLITS.iter(),
 			DEFAULT_TREE_MAX_ROUNDS_VALUES.iter(),
-			DEFAULT_TREE_MAX_DEPTH.iter()
+			DEFAULT_TREE_MAX_DEPTH.iter(),
+			DEFAULT_TREE_BAGGING_FRACTION_VALUES.iter()
 		) {
 			grid.push(GridItem::TreeMulticlassClassifier {
 				target_column_index,
-				feature_groups: choose_feature_groups_tree(column_stats, config),
+				feature_groups: choose_feature_groups_tree(
+					column_stats,
+					word_embedding_models,
+					config,
+				),
 				options: TreeModelTrainOptions {
 					max_leaf_nodes: Some(max_leaf_nodes),
 					learning_rate: Some(learning_rate),
@@ -524,6 +839,8 @@ pub fn auto_multiclass_classification_hyperparameter_grid(
 					l2_regularization_for_continuous_splits: Some(
 						l2_regularization_for_continuous_splits,
 					),
+					bagging_fraction: Some(bagging_fraction),
+					bagging_frequency: Some(1),
 					early_stopping_options: Some(Default::default()),
 					..Default::default()
 				},
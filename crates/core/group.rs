@@ -0,0 +1,95 @@
+/*!
+This module implements the helpers for keeping the query groups of a ranking dataset together. The rows of each query group are made contiguous before the dataset is split, and the splits are moved to group boundaries so that no query group is broken across the train, comparison, and test datasets.
+*/
+
+use fnv::FnvHashMap;
+use modelfox_table::prelude::*;
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_xoshiro::Xoshiro256Plus;
+use std::num::NonZeroUsize;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum GroupKey<'a> {
+	Unknown,
+	Number(u32),
+	Enum(Option<NonZeroUsize>),
+	Text(&'a str),
+}
+
+fn group_key<'a>(column: &TableColumnView<'a>, index: usize) -> GroupKey<'a> {
+	match column {
+		TableColumnView::Unknown(_) => GroupKey::Unknown,
+		// Normalize negative zero so that 0 and -0 are in the same group.
+		TableColumnView::Number(column) => {
+			GroupKey::Number((column.as_slice()[index] + 0.0).to_bits())
+		}
+		TableColumnView::Enum(column) => GroupKey::Enum(column.as_slice()[index]),
+		TableColumnView::Text(column) => GroupKey::Text(column.data()[index].as_str()),
+	}
+}
+
+/// Reorder the rows of `table` so that the rows of each query group in the column at `group_column_index` are contiguous. If `shuffle_seed` is set, the order of the groups and the order of the rows within each group are shuffled. Otherwise, the groups are ordered by their first appearance and the rows keep their original order.
+pub fn group_table(table: &mut Table, group_column_index: usize, shuffle_seed: Option<u64>) {
+	let mut groups: Vec<Vec<usize>> = Vec::new();
+	{
+		let mut group_indexes: FnvHashMap<GroupKey, usize> = FnvHashMap::default();
+		let column = table.columns()[group_column_index].view();
+		for row_index in 0..column.len() {
+			let group_index = *group_indexes
+				.entry(group_key(&column, row_index))
+				.or_insert_with(|| {
+					groups.push(Vec::new());
+					groups.len() - 1
+				});
+			groups[group_index].push(row_index);
+		}
+	}
+	if let Some(seed) = shuffle_seed {
+		let mut rng = Xoshiro256Plus::seed_from_u64(seed);
+		groups.shuffle(&mut rng);
+		for group in groups.iter_mut() {
+			group.shuffle(&mut rng);
+		}
+	}
+	let permutation = groups.into_iter().flatten().collect::<Vec<_>>();
+	for column in table.columns_mut().iter_mut() {
+		match column {
+			TableColumn::Unknown(_) => {}
+			TableColumn::Number(column) => permute(column.data_mut(), &permutation),
+			TableColumn::Enum(column) => permute(column.data_mut(), &permutation),
+			TableColumn::Text(column) => permute(column.data_mut(), &permutation),
+		}
+	}
+}
+
+fn permute<T: Clone>(data: &mut Vec<T>, permutation: &[usize]) {
+	*data = permutation
+		.iter()
+		.map(|index| data[*index].clone())
+		.collect();
+}
+
+/// Compute the query group of each row in `column`, numbering the groups in order. The rows of each group must be contiguous.
+pub fn compute_groups(column: TableColumnView) -> Vec<usize> {
+	let mut groups = Vec::with_capacity(column.len());
+	let mut group = 0;
+	for index in 0..column.len() {
+		if index > 0 && group_key(&column, index) != group_key(&column, index - 1) {
+			group += 1;
+		}
+		groups.push(group);
+	}
+	groups
+}
+
+/// Return the first index at or after `index` that starts a new query group in `column`, so that splitting the rows at the returned index does not break a group.
+pub fn next_group_boundary(column: &TableColumnView, index: usize) -> usize {
+	let mut index = index;
+	while index > 0
+		&& index < column.len()
+		&& group_key(column, index) == group_key(column, index - 1)
+	{
+		index += 1;
+	}
+	index
+}
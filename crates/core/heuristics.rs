@@ -1,3 +1,32 @@
 pub const MIN_TRAIN_ROWS: usize = 35;
 pub const MIN_TEST_ROWS: usize = 10;
 pub const MIN_COMPARISON_ROWS: usize = 5;
+pub const FEATURE_SELECTION_DEFAULT_MAX_MOST_FREQUENT_FRACTION: f32 = 0.99;
+pub const FEATURE_SELECTION_DEFAULT_MAX_INVALID_FRACTION: f32 = 0.5;
+pub const FEATURE_SELECTION_DEFAULT_MAX_CORRELATION: f32 = 0.95;
+pub const FEATURE_SELECTION_DEFAULT_MIN_IMPORTANCE: f32 = 0.001;
+/// This is the maximum number of rows of the train dataset used to compute correlations between number columns during feature selection.
+pub const FEATURE_SELECTION_MAX_ROWS_FOR_CORRELATION: usize = 10_000;
+/// This is the number of rounds used to train the probe model during feature selection.
+pub const FEATURE_SELECTION_PROBE_MAX_ROUNDS: usize = 20;
+/// This is the maximum number of rows of each dataset used by the target leakage and data quality checks.
+pub const CHECKS_MAX_ROWS: usize = 10_000;
+/// The ID and distribution mismatch checks are skipped when there are fewer than this many rows to check.
+pub const CHECKS_MIN_ROWS: usize = 100;
+/// Number and text columns whose fraction of unique valid values is at least this value are reported as ID-like.
+pub const CHECKS_MIN_ID_UNIQUE_FRACTION: f32 = 0.95;
+/// Columns whose single column model achieves at least this accuracy or r-squared on the comparison dataset are reported as leaking the target.
+pub const CHECKS_MIN_LEAKAGE_SCORE: f32 = 0.99;
+/// This is the maximum number of bins number columns are divided into when checking for target leakage.
+pub const CHECKS_LEAKAGE_MAX_BINS: usize = 64;
+/// The test dataset is reported as overlapping the train dataset if at least this fraction of its rows also appear in the train dataset.
+pub const CHECKS_MIN_DUPLICATE_FRACTION: f32 = 0.01;
+/// The train and test datasets are reported as having different distributions if a model distinguishing their rows achieves at least this AUC ROC.
+pub const CHECKS_MIN_ADVERSARIAL_AUC_ROC: f32 = 0.8;
+/// This is the number of rounds used to train the model that distinguishes rows of the train and test datasets.
+pub const CHECKS_ADVERSARIAL_MAX_ROUNDS: usize = 20;
+pub const HASHED_FEATURE_GROUP_DEFAULT_N_BUCKETS: usize = 1024;
+/// This is the maximum number of the most frequent training ngrams or values recorded for each bucket of a hashed feature group, which are used to describe its features.
+pub const HASHED_FEATURE_GROUP_MAX_BUCKET_EXAMPLES: usize = 5;
+pub const POLYNOMIAL_FEATURE_GROUP_DEFAULT_DEGREE: usize = 2;
+pub const BINNED_NUMBER_FEATURE_GROUP_DEFAULT_N_BINS: usize = 10;
@@ -1,10 +1,18 @@
-mod config;
+pub mod checks;
+pub mod config;
+pub mod explain;
+pub mod feature_selection;
 mod features;
 mod grid;
+mod group;
 pub mod heuristics;
 pub mod model;
 pub mod predict;
 pub mod progress;
-mod stats;
+pub mod stats;
+pub mod target_transform;
 mod test;
 pub mod train;
+
+#[cfg(test)]
+pub mod test_common;
@@ -1,8 +1,12 @@
 use crate::{
+	checks::DataQualityIssue,
+	explain::{ExplanationsOutput, PartialDependenceGridOutput},
+	feature_selection::{DroppedColumn, DroppedColumnReason},
 	stats::{
 		ColumnStatsOutput, EnumColumnStatsOutput, NumberColumnStatsOutput, StatsSettings,
 		TextColumnStatsOutput, TextColumnStatsOutputTopNGramsEntry, UnknownColumnStatsOutput,
 	},
+	target_transform::TargetTransform,
 	train::{TrainGridItemOutput, TrainModelOutput},
 };
 use anyhow::Result;
@@ -16,12 +20,15 @@ pub struct Model {
 	pub version: String,
 	pub date: String,
 	pub inner: ModelInner,
+	/// This is the id of the model that training continued from, if any.
+	pub parent_id: Option<Id>,
 }
 
 pub enum ModelInner {
 	Regressor(Regressor),
 	BinaryClassifier(BinaryClassifier),
 	MulticlassClassifier(MulticlassClassifier),
+	Ranker(Ranker),
 }
 
 pub struct Regressor {
@@ -42,6 +49,12 @@ pub struct Regressor {
 	pub best_grid_item_index: usize,
 	pub model: RegressionModel,
 	pub test_metrics: modelfox_metrics::RegressionMetricsOutput,
+	pub explanations: ExplanationsOutput,
+	pub target_transform: Option<TargetTransform>,
+	/// These are the columns that feature selection dropped before training.
+	pub dropped_columns: Vec<DroppedColumn>,
+	/// These are the issues found by the target leakage and data quality checks.
+	pub data_quality_issues: Vec<DataQualityIssue>,
 }
 
 pub struct BinaryClassifier {
@@ -64,6 +77,11 @@ pub struct BinaryClassifier {
 	pub best_grid_item_index: usize,
 	pub model: BinaryClassificationModel,
 	pub test_metrics: modelfox_metrics::BinaryClassificationMetricsOutput,
+	pub explanations: ExplanationsOutput,
+	/// These are the columns that feature selection dropped before training.
+	pub dropped_columns: Vec<DroppedColumn>,
+	/// These are the issues found by the target leakage and data quality checks.
+	pub data_quality_issues: Vec<DataQualityIssue>,
 }
 
 pub struct MulticlassClassifier {
@@ -85,6 +103,40 @@ pub struct MulticlassClassifier {
 	pub best_grid_item_index: usize,
 	pub model: MulticlassClassificationModel,
 	pub test_metrics: modelfox_metrics::MulticlassClassificationMetricsOutput,
+	pub explanations: ExplanationsOutput,
+	/// These are the columns that feature selection dropped before training.
+	pub dropped_columns: Vec<DroppedColumn>,
+	/// These are the issues found by the target leakage and data quality checks.
+	pub data_quality_issues: Vec<DataQualityIssue>,
+}
+
+pub struct Ranker {
+	pub target_column_name: String,
+	/// This is the column whose values identify the query group of each example.
+	pub group_column_name: String,
+	pub train_row_count: usize,
+	pub test_row_count: usize,
+	pub overall_row_count: usize,
+	pub stats_settings: StatsSettings,
+	pub overall_column_stats: Vec<ColumnStatsOutput>,
+	pub overall_target_column_stats: ColumnStatsOutput,
+	pub train_column_stats: Vec<ColumnStatsOutput>,
+	pub train_target_column_stats: ColumnStatsOutput,
+	pub test_column_stats: Vec<ColumnStatsOutput>,
+	pub test_target_column_stats: ColumnStatsOutput,
+	pub baseline_metrics: modelfox_metrics::RankingMetricsOutput,
+	pub comparison_metric: RankingComparisonMetric,
+	pub train_grid_item_outputs: Vec<TrainGridItemOutput>,
+	pub best_grid_item_index: usize,
+	pub model: RankingModel,
+	pub test_metrics: modelfox_metrics::RankingMetricsOutput,
+	/// If this is set, the model was trained and evaluated on the NDCG of only the top `ndcg_k` examples of each query group.
+	pub ndcg_k: Option<usize>,
+	pub explanations: ExplanationsOutput,
+	/// These are the columns that feature selection dropped before training.
+	pub dropped_columns: Vec<DroppedColumn>,
+	/// These are the issues found by the target leakage and data quality checks.
+	pub data_quality_issues: Vec<DataQualityIssue>,
 }
 
 #[derive(Clone, Copy)]
@@ -92,6 +144,7 @@ pub enum Task {
 	BinaryClassification,
 	MulticlassClassification,
 	Regression,
+	Ranking,
 }
 
 #[derive(Clone, Copy)]
@@ -175,17 +228,37 @@ pub struct TreeMulticlassClassificationModel {
 	pub feature_importances: Vec<f32>,
 }
 
+pub enum RankingModel {
+	Tree(TreeRankingModel),
+}
+
+pub struct TreeRankingModel {
+	pub model: modelfox_tree::Ranker,
+	pub train_options: modelfox_tree::TrainOptions,
+	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
+	pub losses: Option<Vec<f32>>,
+	pub feature_importances: Vec<f32>,
+}
+
+#[derive(Clone, Copy)]
+pub enum RankingComparisonMetric {
+	Ndcg,
+	Map,
+}
+
 #[derive(Clone, Copy)]
 pub enum ComparisonMetric {
 	Regression(RegressionComparisonMetric),
 	BinaryClassification(BinaryClassificationComparisonMetric),
 	MulticlassClassification(MulticlassClassificationComparisonMetric),
+	Ranking(RankingComparisonMetric),
 }
 
 pub enum Metrics {
 	Regression(modelfox_metrics::RegressionMetricsOutput),
 	BinaryClassification(modelfox_metrics::BinaryClassificationMetricsOutput),
 	MulticlassClassification(modelfox_metrics::MulticlassClassificationMetricsOutput),
+	Ranking(modelfox_metrics::RankingMetricsOutput),
 }
 
 impl Model {
@@ -197,6 +270,15 @@ impl Model {
 		modelfox_model::to_path(path, &bytes)?;
 		Ok(())
 	}
+
+	/// Serialize the model to the bytes of a `.modelfox` file.
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut writer = buffalo::Writer::new();
+		let model = serialize_model(self, &mut writer);
+		writer.write(&model);
+		let bytes = writer.into_bytes();
+		modelfox_model::to_bytes(&bytes)
+	}
 }
 
 fn serialize_model(
@@ -207,11 +289,15 @@ fn serialize_model(
 	let version = writer.write(model.version.as_str());
 	let date = writer.write(model.date.to_string().as_str());
 	let inner = serialize_model_inner(&model.inner, writer);
+	let parent_id = model
+		.parent_id
+		.map(|parent_id| writer.write(parent_id.to_string().as_str()));
 	writer.write(&modelfox_model::ModelWriter {
 		id,
 		version,
 		date,
 		inner,
+		parent_id,
 	})
 }
 
@@ -233,6 +319,10 @@ fn serialize_model_inner(
 				serialize_multiclass_classifier(multiclass_classifier, writer);
 			modelfox_model::ModelInnerWriter::MulticlassClassifier(multiclass_classifier)
 		}
+		ModelInner::Ranker(ranker) => {
+			let ranker = serialize_ranker(ranker, writer);
+			modelfox_model::ModelInnerWriter::Ranker(ranker)
+		}
 	}
 }
 
@@ -279,6 +369,13 @@ fn serialize_regressor(
 	let train_grid_item_outputs = writer.write(&train_grid_item_outputs);
 	let model = serialize_regression_model(&regressor.model, writer);
 	let test_metrics = serialize_regression_metrics_output(&regressor.test_metrics, writer);
+	let explanations = serialize_explanations_output(&regressor.explanations, writer);
+	let dropped_columns = serialize_dropped_columns(&regressor.dropped_columns, writer);
+	let data_quality_issues = serialize_data_quality_issues(&regressor.data_quality_issues, writer);
+	let target_transform = regressor
+		.target_transform
+		.as_ref()
+		.map(|target_transform| serialize_target_transform(target_transform, writer));
 	let regressor_writer = modelfox_model::RegressorWriter {
 		target_column_name,
 		train_row_count: regressor.train_row_count.to_u64().unwrap(),
@@ -297,10 +394,156 @@ fn serialize_regressor(
 		best_grid_item_index: regressor.best_grid_item_index.to_u64().unwrap(),
 		model,
 		test_metrics,
+		explanations: Some(explanations),
+		target_transform,
+		dropped_columns,
+		data_quality_issues,
 	};
 	writer.write(&regressor_writer)
 }
 
+fn serialize_dropped_columns(
+	dropped_columns: &[DroppedColumn],
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<[buffalo::Position<modelfox_model::DroppedColumnWriter>]> {
+	let dropped_columns = dropped_columns
+		.iter()
+		.map(|dropped_column| {
+			let column_name = writer.write(dropped_column.column_name.as_str());
+			let reason = serialize_dropped_column_reason(&dropped_column.reason, writer);
+			writer.write(&modelfox_model::DroppedColumnWriter {
+				column_name,
+				reason,
+			})
+		})
+		.collect::<Vec<_>>();
+	writer.write(&dropped_columns)
+}
+
+fn serialize_dropped_column_reason(
+	reason: &DroppedColumnReason,
+	writer: &mut buffalo::Writer,
+) -> modelfox_model::DroppedColumnReasonWriter {
+	match reason {
+		DroppedColumnReason::NearConstant {
+			most_frequent_fraction,
+		} => {
+			let reason = writer.write(&modelfox_model::NearConstantDroppedColumnReasonWriter {
+				most_frequent_fraction: *most_frequent_fraction,
+			});
+			modelfox_model::DroppedColumnReasonWriter::NearConstant(reason)
+		}
+		DroppedColumnReason::InvalidFraction { invalid_fraction } => {
+			let reason = writer.write(&modelfox_model::InvalidFractionDroppedColumnReasonWriter {
+				invalid_fraction: *invalid_fraction,
+			});
+			modelfox_model::DroppedColumnReasonWriter::InvalidFraction(reason)
+		}
+		DroppedColumnReason::Collinear {
+			column_name,
+			correlation,
+		} => {
+			let column_name = writer.write(column_name.as_str());
+			let reason = writer.write(&modelfox_model::CollinearDroppedColumnReasonWriter {
+				column_name,
+				correlation: *correlation,
+			});
+			modelfox_model::DroppedColumnReasonWriter::Collinear(reason)
+		}
+		DroppedColumnReason::LowImportance { importance } => {
+			let reason = writer.write(&modelfox_model::LowImportanceDroppedColumnReasonWriter {
+				importance: *importance,
+			});
+			modelfox_model::DroppedColumnReasonWriter::LowImportance(reason)
+		}
+	}
+}
+
+fn serialize_data_quality_issues(
+	data_quality_issues: &[DataQualityIssue],
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<[modelfox_model::DataQualityIssueWriter]> {
+	let data_quality_issues = data_quality_issues
+		.iter()
+		.map(|data_quality_issue| serialize_data_quality_issue(data_quality_issue, writer))
+		.collect::<Vec<_>>();
+	writer.write(&data_quality_issues)
+}
+
+fn serialize_data_quality_issue(
+	data_quality_issue: &DataQualityIssue,
+	writer: &mut buffalo::Writer,
+) -> modelfox_model::DataQualityIssueWriter {
+	match data_quality_issue {
+		DataQualityIssue::IdColumn {
+			column_name,
+			unique_fraction,
+		} => {
+			let column_name = writer.write(column_name.as_str());
+			let issue = writer.write(&modelfox_model::IdColumnDataQualityIssueWriter {
+				column_name,
+				unique_fraction: *unique_fraction,
+			});
+			modelfox_model::DataQualityIssueWriter::IdColumn(issue)
+		}
+		DataQualityIssue::TargetLeakage { column_name, score } => {
+			let column_name = writer.write(column_name.as_str());
+			let issue = writer.write(&modelfox_model::TargetLeakageDataQualityIssueWriter {
+				column_name,
+				score: *score,
+			});
+			modelfox_model::DataQualityIssueWriter::TargetLeakage(issue)
+		}
+		DataQualityIssue::DuplicateRows {
+			duplicate_count,
+			duplicate_fraction,
+		} => {
+			let issue = writer.write(&modelfox_model::DuplicateRowsDataQualityIssueWriter {
+				duplicate_count: duplicate_count.to_u64().unwrap(),
+				duplicate_fraction: *duplicate_fraction,
+			});
+			modelfox_model::DataQualityIssueWriter::DuplicateRows(issue)
+		}
+		DataQualityIssue::DistributionMismatch {
+			auc_roc,
+			column_name,
+		} => {
+			let column_name = column_name
+				.as_ref()
+				.map(|column_name| writer.write(column_name.as_str()));
+			let issue = writer.write(
+				&modelfox_model::DistributionMismatchDataQualityIssueWriter {
+					auc_roc: *auc_roc,
+					column_name,
+				},
+			);
+			modelfox_model::DataQualityIssueWriter::DistributionMismatch(issue)
+		}
+	}
+}
+
+fn serialize_target_transform(
+	target_transform: &TargetTransform,
+	writer: &mut buffalo::Writer,
+) -> modelfox_model::TargetTransformWriter {
+	match target_transform {
+		TargetTransform::Log1p => modelfox_model::TargetTransformWriter::Log1p,
+		TargetTransform::BoxCox { lambda } => {
+			let target_transform =
+				writer.write(&modelfox_model::BoxCoxTargetTransformWriter { lambda: *lambda });
+			modelfox_model::TargetTransformWriter::BoxCox(target_transform)
+		}
+		TargetTransform::Standardize { mean, std } => {
+			let target_transform =
+				writer.write(&modelfox_model::StandardizeTargetTransformWriter {
+					mean: *mean,
+					std: *std,
+				});
+			modelfox_model::TargetTransformWriter::Standardize(target_transform)
+		}
+	}
+}
+
 fn serialize_binary_classifier(
 	binary_classifier: &BinaryClassifier,
 	writer: &mut buffalo::Writer,
@@ -350,6 +593,10 @@ fn serialize_binary_classifier(
 	let model = serialize_binary_classification_model(&binary_classifier.model, writer);
 	let test_metrics =
 		serialize_binary_classification_metrics_output(&binary_classifier.test_metrics, writer);
+	let explanations = serialize_explanations_output(&binary_classifier.explanations, writer);
+	let dropped_columns = serialize_dropped_columns(&binary_classifier.dropped_columns, writer);
+	let data_quality_issues =
+		serialize_data_quality_issues(&binary_classifier.data_quality_issues, writer);
 	let binary_classifier_writer = modelfox_model::BinaryClassifierWriter {
 		target_column_name,
 		train_row_count: binary_classifier.train_row_count.to_u64().unwrap(),
@@ -370,6 +617,10 @@ fn serialize_binary_classifier(
 		test_metrics,
 		negative_class,
 		positive_class,
+		threshold: modelfox_model::DEFAULT_THRESHOLD,
+		explanations: Some(explanations),
+		dropped_columns,
+		data_quality_issues,
 	};
 	writer.write(&binary_classifier_writer)
 }
@@ -431,6 +682,10 @@ fn serialize_multiclass_classifier(
 		.map(|class| writer.write(class))
 		.collect::<Vec<_>>();
 	let classes = writer.write(&classes);
+	let explanations = serialize_explanations_output(&multiclass_classifier.explanations, writer);
+	let dropped_columns = serialize_dropped_columns(&multiclass_classifier.dropped_columns, writer);
+	let data_quality_issues =
+		serialize_data_quality_issues(&multiclass_classifier.data_quality_issues, writer);
 	let multiclass_classifier_writer = modelfox_model::MulticlassClassifierWriter {
 		target_column_name,
 		train_row_count: multiclass_classifier.train_row_count.to_u64().unwrap(),
@@ -450,10 +705,100 @@ fn serialize_multiclass_classifier(
 		model,
 		test_metrics,
 		classes,
+		explanations: Some(explanations),
+		dropped_columns,
+		data_quality_issues,
 	};
 	writer.write(&multiclass_classifier_writer)
 }
 
+fn serialize_explanations_output(
+	explanations: &ExplanationsOutput,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::ExplanationsWriter> {
+	let shap_feature_importances = writer.write(explanations.shap_feature_importances.as_slice());
+	let shap_column_importances = explanations
+		.shap_column_importances
+		.iter()
+		.map(|(column_name, importance)| (writer.write(column_name.as_str()), *importance))
+		.collect::<Vec<_>>();
+	let shap_column_importances = writer.write(&shap_column_importances);
+	let partial_dependences = explanations
+		.partial_dependences
+		.iter()
+		.map(|partial_dependence| {
+			let column_name = writer.write(partial_dependence.column_name.as_str());
+			let class = partial_dependence
+				.class
+				.as_ref()
+				.map(|class| writer.write(class.as_str()));
+			let grid = match &partial_dependence.grid {
+				PartialDependenceGridOutput::Number(values) => {
+					let values = writer.write(values.as_slice());
+					let grid =
+						writer.write(&modelfox_model::NumberPartialDependenceGridWriter { values });
+					modelfox_model::PartialDependenceGridWriter::Number(grid)
+				}
+				PartialDependenceGridOutput::Enum(variants) => {
+					let variants = variants
+						.iter()
+						.map(|variant| writer.write(variant.as_str()))
+						.collect::<Vec<_>>();
+					let variants = writer.write(&variants);
+					let grid =
+						writer.write(&modelfox_model::EnumPartialDependenceGridWriter { variants });
+					modelfox_model::PartialDependenceGridWriter::Enum(grid)
+				}
+			};
+			let average = writer.write(partial_dependence.average.as_slice());
+			let individual = partial_dependence
+				.individual
+				.iter()
+				.map(|values| {
+					let values = writer.write(values.as_slice());
+					writer.write(&modelfox_model::IndividualConditionalExpectationWriter { values })
+				})
+				.collect::<Vec<_>>();
+			let individual = writer.write(&individual);
+			writer.write(&modelfox_model::PartialDependenceWriter {
+				column_name,
+				class,
+				grid,
+				average,
+				individual,
+			})
+		})
+		.collect::<Vec<_>>();
+	let partial_dependences = writer.write(&partial_dependences);
+	let feature_interactions =
+		explanations
+			.feature_interactions
+			.as_ref()
+			.map(|feature_interactions| {
+				let feature_interactions = feature_interactions
+					.iter()
+					.map(|feature_interaction| {
+						let feature_name_a =
+							writer.write(feature_interaction.feature_name_a.as_str());
+						let feature_name_b =
+							writer.write(feature_interaction.feature_name_b.as_str());
+						writer.write(&modelfox_model::FeatureInteractionWriter {
+							feature_name_a,
+							feature_name_b,
+							value: feature_interaction.value,
+						})
+					})
+					.collect::<Vec<_>>();
+				writer.write(&feature_interactions)
+			});
+	writer.write(&modelfox_model::ExplanationsWriter {
+		shap_feature_importances,
+		shap_column_importances,
+		partial_dependences,
+		feature_interactions,
+	})
+}
+
 fn serialize_stats_settings(
 	stats_settings: &StatsSettings,
 	writer: &mut buffalo::Writer,
@@ -592,9 +937,28 @@ fn serialize_tokenizer(
 	tokenizer: &modelfox_text::Tokenizer,
 	writer: &mut buffalo::Writer,
 ) -> buffalo::Position<modelfox_model::TokenizerWriter> {
+	let mut stop_words = tokenizer.stop_words.iter().collect::<Vec<_>>();
+	stop_words.sort_unstable();
+	let stop_words = stop_words
+		.into_iter()
+		.map(|stop_word| writer.write(stop_word))
+		.collect::<Vec<_>>();
+	let stop_words = writer.write(&stop_words);
+	let stemmer = tokenizer.stemmer.map(|stemmer| match stemmer {
+		modelfox_text::Stemmer::English => modelfox_model::StemmerWriter::English,
+	});
+	let token_pattern = tokenizer
+		.token_pattern
+		.as_ref()
+		.map(|token_pattern| writer.write(token_pattern.as_str()));
 	writer.write(&modelfox_model::TokenizerWriter {
 		lowercase: tokenizer.lowercase,
 		alphanumeric: tokenizer.alphanumeric,
+		nfkc: tokenizer.nfkc,
+		fold_accents: tokenizer.fold_accents,
+		stop_words,
+		stemmer,
+		token_pattern,
 	})
 }
 
@@ -612,6 +976,10 @@ fn serialize_ngram(
 			let token_b = writer.write(token_b);
 			writer.write(&modelfox_model::NGramWriter::Bigram((token_a, token_b)))
 		}
+		modelfox_text::NGram::Character(ngram) => {
+			let ngram = writer.write(ngram);
+			writer.write(&modelfox_model::NGramWriter::Character(ngram))
+		}
 	}
 }
 
@@ -639,6 +1007,15 @@ fn serialize_ngram_type(
 	match ngram_type {
 		modelfox_text::NGramType::Unigram => modelfox_model::NGramTypeWriter::Unigram,
 		modelfox_text::NGramType::Bigram => modelfox_model::NGramTypeWriter::Bigram,
+		modelfox_text::NGramType::CharacterTrigram => {
+			modelfox_model::NGramTypeWriter::CharacterTrigram
+		}
+		modelfox_text::NGramType::CharacterQuadgram => {
+			modelfox_model::NGramTypeWriter::CharacterQuadgram
+		}
+		modelfox_text::NGramType::CharacterPentagram => {
+			modelfox_model::NGramTypeWriter::CharacterPentagram
+		}
 	}
 }
 
@@ -744,6 +1121,9 @@ fn serialize_linear_train_options(
 			.map(|early_stopping_options| {
 				serialize_early_stopping_options(early_stopping_options, writer)
 			});
+	let optimizer = serialize_linear_optimizer(&train_options.optimizer, writer);
+	let learning_rate_schedule =
+		serialize_linear_learning_rate_schedule(&train_options.learning_rate_schedule, writer);
 	let train_options = modelfox_model::LinearModelTrainOptionsWriter {
 		compute_loss: train_options.compute_losses,
 		l2_regularization: train_options.l2_regularization,
@@ -751,10 +1131,71 @@ fn serialize_linear_train_options(
 		max_epochs: train_options.max_epochs.to_u64().unwrap(),
 		n_examples_per_batch: train_options.n_examples_per_batch.to_u64().unwrap(),
 		early_stopping_options,
+		l1_regularization: train_options.l1_regularization,
+		optimizer,
+		learning_rate_schedule,
 	};
 	writer.write(&train_options)
 }
 
+fn serialize_linear_optimizer(
+	optimizer: &modelfox_linear::Optimizer,
+	writer: &mut buffalo::Writer,
+) -> modelfox_model::LinearOptimizerWriter {
+	match optimizer {
+		modelfox_linear::Optimizer::Sgd => modelfox_model::LinearOptimizerWriter::Sgd,
+		modelfox_linear::Optimizer::Momentum { momentum } => {
+			let optimizer = writer.write(&modelfox_model::MomentumOptimizerWriter {
+				momentum: *momentum,
+			});
+			modelfox_model::LinearOptimizerWriter::Momentum(optimizer)
+		}
+		modelfox_linear::Optimizer::Adam {
+			beta_1,
+			beta_2,
+			epsilon,
+		} => {
+			let optimizer = writer.write(&modelfox_model::AdamOptimizerWriter {
+				beta_1: *beta_1,
+				beta_2: *beta_2,
+				epsilon: *epsilon,
+			});
+			modelfox_model::LinearOptimizerWriter::Adam(optimizer)
+		}
+	}
+}
+
+fn serialize_linear_learning_rate_schedule(
+	learning_rate_schedule: &modelfox_linear::LearningRateSchedule,
+	writer: &mut buffalo::Writer,
+) -> modelfox_model::LinearLearningRateScheduleWriter {
+	match learning_rate_schedule {
+		modelfox_linear::LearningRateSchedule::Constant => {
+			modelfox_model::LinearLearningRateScheduleWriter::Constant
+		}
+		modelfox_linear::LearningRateSchedule::InverseTimeDecay { decay_rate } => {
+			let learning_rate_schedule = writer.write(
+				&modelfox_model::InverseTimeDecayLearningRateScheduleWriter {
+					decay_rate: *decay_rate,
+				},
+			);
+			modelfox_model::LinearLearningRateScheduleWriter::InverseTimeDecay(
+				learning_rate_schedule,
+			)
+		}
+		modelfox_linear::LearningRateSchedule::ExponentialDecay { decay_rate } => {
+			let learning_rate_schedule = writer.write(
+				&modelfox_model::ExponentialDecayLearningRateScheduleWriter {
+					decay_rate: *decay_rate,
+				},
+			);
+			modelfox_model::LinearLearningRateScheduleWriter::ExponentialDecay(
+				learning_rate_schedule,
+			)
+		}
+	}
+}
+
 fn serialize_tree_train_options(
 	train_options: &modelfox_tree::TrainOptions,
 	writer: &mut buffalo::Writer,
@@ -771,6 +1212,19 @@ fn serialize_tree_train_options(
 		.map(|max_depth| max_depth.to_u64().unwrap());
 	let binned_features_layout =
 		serialize_binned_features_layout(&train_options.binned_features_layout, writer);
+	let goss_options = train_options.goss_options.as_ref().map(|goss_options| {
+		writer.write(&modelfox_model::TreeGossOptionsWriter {
+			top_rate: goss_options.top_rate,
+			other_rate: goss_options.other_rate,
+		})
+	});
+	let dart_options = train_options.dart_options.as_ref().map(|dart_options| {
+		writer.write(&modelfox_model::TreeDartOptionsWriter {
+			drop_rate: dart_options.drop_rate,
+			max_drop: dart_options.max_drop.to_u64().unwrap(),
+			skip_drop: dart_options.skip_drop,
+		})
+	});
 	let train_options = modelfox_model::TreeModelTrainOptionsWriter {
 		compute_loss: train_options.compute_losses,
 		l2_regularization_for_continuous_splits: train_options
@@ -795,6 +1249,13 @@ fn serialize_tree_train_options(
 		min_sum_hessians_per_node: train_options.min_sum_hessians_per_node,
 		smoothing_factor_for_discrete_bin_sorting: train_options
 			.smoothing_factor_for_discrete_bin_sorting,
+		bagging_fraction: train_options.bagging_fraction,
+		bagging_frequency: train_options.bagging_frequency.to_u64().unwrap(),
+		feature_fraction_per_node: train_options.feature_fraction_per_node,
+		feature_fraction_per_tree: train_options.feature_fraction_per_tree,
+		goss_options,
+		seed: train_options.seed,
+		dart_options,
 	};
 	writer.write(&train_options)
 }
@@ -885,6 +1346,10 @@ fn serialize_train_grid_item_output(
 			let options = serialize_tree_train_options(&model.train_options, writer);
 			modelfox_model::ModelTrainOptionsWriter::Tree(options)
 		}
+		TrainModelOutput::TreeRanker(model) => {
+			let options = serialize_tree_train_options(&model.train_options, writer);
+			modelfox_model::ModelTrainOptionsWriter::Tree(options)
+		}
 	};
 	let train_grid_item_output_writer = modelfox_model::TrainGridItemOutputWriter {
 		comparison_metric_value: train_grid_item_output.comparison_metric_value,
@@ -924,6 +1389,26 @@ fn serialize_feature_group(
 			let feature_group = serialize_word_embedding_feature_group(feature_group, writer);
 			modelfox_model::FeatureGroupWriter::WordEmbedding(feature_group)
 		}
+		modelfox_features::FeatureGroup::HashedBagOfWords(feature_group) => {
+			let feature_group = serialize_hashed_bag_of_words_feature_group(feature_group, writer);
+			modelfox_model::FeatureGroupWriter::HashedBagOfWords(feature_group)
+		}
+		modelfox_features::FeatureGroup::HashedEnum(feature_group) => {
+			let feature_group = serialize_hashed_enum_feature_group(feature_group, writer);
+			modelfox_model::FeatureGroupWriter::HashedEnum(feature_group)
+		}
+		modelfox_features::FeatureGroup::Interaction(feature_group) => {
+			let feature_group = serialize_interaction_feature_group(feature_group, writer);
+			modelfox_model::FeatureGroupWriter::Interaction(feature_group)
+		}
+		modelfox_features::FeatureGroup::Polynomial(feature_group) => {
+			let feature_group = serialize_polynomial_feature_group(feature_group, writer);
+			modelfox_model::FeatureGroupWriter::Polynomial(feature_group)
+		}
+		modelfox_features::FeatureGroup::BinnedNumber(feature_group) => {
+			let feature_group = serialize_binned_number_feature_group(feature_group, writer);
+			modelfox_model::FeatureGroupWriter::BinnedNumber(feature_group)
+		}
 	}
 }
 
@@ -932,7 +1417,15 @@ fn serialize_identity_feature_group(
 	writer: &mut buffalo::Writer,
 ) -> buffalo::Position<modelfox_model::IdentityFeatureGroupWriter> {
 	let source_column_name = writer.write(identity_feature_group.source_column_name.as_str());
-	let feature_group = modelfox_model::IdentityFeatureGroupWriter { source_column_name };
+	let impute = identity_feature_group
+		.impute
+		.as_ref()
+		.map(|impute| serialize_impute(impute, writer));
+	let feature_group = modelfox_model::IdentityFeatureGroupWriter {
+		source_column_name,
+		impute,
+		missing_indicator: identity_feature_group.missing_indicator,
+	};
 	writer.write(&feature_group)
 }
 
@@ -941,14 +1434,38 @@ fn serialize_normalized_feature_group(
 	writer: &mut buffalo::Writer,
 ) -> buffalo::Position<modelfox_model::NormalizedFeatureGroupWriter> {
 	let source_column_name = writer.write(normalized_feature_group.source_column_name.as_str());
+	let impute = normalized_feature_group
+		.impute
+		.as_ref()
+		.map(|impute| serialize_impute(impute, writer));
 	let feature_group = modelfox_model::NormalizedFeatureGroupWriter {
 		source_column_name,
 		mean: normalized_feature_group.mean,
 		variance: normalized_feature_group.variance,
+		impute,
+		missing_indicator: normalized_feature_group.missing_indicator,
 	};
 	writer.write(&feature_group)
 }
 
+fn serialize_impute(
+	impute: &modelfox_features::Impute,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::ImputeWriter> {
+	let strategy = match impute.strategy {
+		modelfox_features::ImputeStrategy::Mean => modelfox_model::ImputeStrategyWriter::Mean,
+		modelfox_features::ImputeStrategy::Median => modelfox_model::ImputeStrategyWriter::Median,
+		modelfox_features::ImputeStrategy::Mode => modelfox_model::ImputeStrategyWriter::Mode,
+		modelfox_features::ImputeStrategy::Constant => {
+			modelfox_model::ImputeStrategyWriter::Constant
+		}
+	};
+	writer.write(&modelfox_model::ImputeWriter {
+		strategy,
+		value: impute.value,
+	})
+}
+
 fn serialize_one_hot_encoded_feature_group(
 	one_hot_encoded_feature_group: &modelfox_features::OneHotEncodedFeatureGroup,
 	writer: &mut buffalo::Writer,
@@ -1029,6 +1546,140 @@ fn serialize_bag_of_words_feature_group_n_gram_entry(
 	})
 }
 
+fn serialize_hashed_bag_of_words_feature_group(
+	hashed_bag_of_words_feature_group: &modelfox_features::HashedBagOfWordsFeatureGroup,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::HashedBagOfWordsFeatureGroupWriter> {
+	let source_column_name = writer.write(
+		hashed_bag_of_words_feature_group
+			.source_column_name
+			.as_str(),
+	);
+	let tokenizer = serialize_tokenizer(&hashed_bag_of_words_feature_group.tokenizer, writer);
+	let strategy = match hashed_bag_of_words_feature_group.strategy {
+		modelfox_features::hashed_bag_of_words::HashedBagOfWordsFeatureGroupStrategy::Present => {
+			modelfox_model::HashedBagOfWordsFeatureGroupStrategyWriter::Present
+		}
+		modelfox_features::hashed_bag_of_words::HashedBagOfWordsFeatureGroupStrategy::Count => {
+			modelfox_model::HashedBagOfWordsFeatureGroupStrategyWriter::Count
+		}
+	};
+	let ngram_types = hashed_bag_of_words_feature_group
+		.ngram_types
+		.iter()
+		.map(|ngram_type| serialize_ngram_type(ngram_type, writer))
+		.collect::<Vec<_>>();
+	let ngram_types = writer.write(&ngram_types);
+	let buckets = hashed_bag_of_words_feature_group
+		.bucket_ngrams
+		.iter()
+		.map(|ngrams| {
+			let ngrams = ngrams
+				.iter()
+				.map(|ngram| serialize_ngram(ngram, writer))
+				.collect::<Vec<_>>();
+			let ngrams = writer.write(&ngrams);
+			writer.write(&modelfox_model::HashedBagOfWordsFeatureGroupBucketWriter { ngrams })
+		})
+		.collect::<Vec<_>>();
+	let buckets = writer.write(&buckets);
+	let feature_group = modelfox_model::HashedBagOfWordsFeatureGroupWriter {
+		source_column_name,
+		tokenizer,
+		strategy,
+		ngram_types,
+		n_buckets: hashed_bag_of_words_feature_group
+			.n_buckets
+			.to_u64()
+			.unwrap(),
+		signed: hashed_bag_of_words_feature_group.signed,
+		buckets,
+	};
+	writer.write(&feature_group)
+}
+
+fn serialize_hashed_enum_feature_group(
+	hashed_enum_feature_group: &modelfox_features::HashedEnumFeatureGroup,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::HashedEnumFeatureGroupWriter> {
+	let source_column_name = writer.write(hashed_enum_feature_group.source_column_name.as_str());
+	let buckets = hashed_enum_feature_group
+		.bucket_values
+		.iter()
+		.map(|values| {
+			let values = values
+				.iter()
+				.map(|value| writer.write(value))
+				.collect::<Vec<_>>();
+			let values = writer.write(&values);
+			writer.write(&modelfox_model::HashedEnumFeatureGroupBucketWriter { values })
+		})
+		.collect::<Vec<_>>();
+	let buckets = writer.write(&buckets);
+	let feature_group = modelfox_model::HashedEnumFeatureGroupWriter {
+		source_column_name,
+		n_buckets: hashed_enum_feature_group.n_buckets.to_u64().unwrap(),
+		signed: hashed_enum_feature_group.signed,
+		buckets,
+	};
+	writer.write(&feature_group)
+}
+
+fn serialize_interaction_feature_group(
+	interaction_feature_group: &modelfox_features::InteractionFeatureGroup,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::InteractionFeatureGroupWriter> {
+	let source_a =
+		serialize_interaction_feature_group_source(&interaction_feature_group.source_a, writer);
+	let source_b =
+		serialize_interaction_feature_group_source(&interaction_feature_group.source_b, writer);
+	let feature_group = modelfox_model::InteractionFeatureGroupWriter { source_a, source_b };
+	writer.write(&feature_group)
+}
+
+fn serialize_interaction_feature_group_source(
+	source: &modelfox_features::InteractionFeatureGroupSource,
+	writer: &mut buffalo::Writer,
+) -> modelfox_model::InteractionFeatureGroupSourceWriter {
+	match source {
+		modelfox_features::InteractionFeatureGroupSource::Normalized(feature_group) => {
+			let feature_group = serialize_normalized_feature_group(feature_group, writer);
+			modelfox_model::InteractionFeatureGroupSourceWriter::Normalized(feature_group)
+		}
+		modelfox_features::InteractionFeatureGroupSource::OneHotEncoded(feature_group) => {
+			let feature_group = serialize_one_hot_encoded_feature_group(feature_group, writer);
+			modelfox_model::InteractionFeatureGroupSourceWriter::OneHotEncoded(feature_group)
+		}
+	}
+}
+
+fn serialize_polynomial_feature_group(
+	polynomial_feature_group: &modelfox_features::PolynomialFeatureGroup,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::PolynomialFeatureGroupWriter> {
+	let source_column_name = writer.write(polynomial_feature_group.source_column_name.as_str());
+	let feature_group = modelfox_model::PolynomialFeatureGroupWriter {
+		source_column_name,
+		mean: polynomial_feature_group.mean,
+		variance: polynomial_feature_group.variance,
+		degree: polynomial_feature_group.degree.to_u64().unwrap(),
+	};
+	writer.write(&feature_group)
+}
+
+fn serialize_binned_number_feature_group(
+	binned_number_feature_group: &modelfox_features::BinnedNumberFeatureGroup,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::BinnedNumberFeatureGroupWriter> {
+	let source_column_name = writer.write(binned_number_feature_group.source_column_name.as_str());
+	let thresholds = writer.write(binned_number_feature_group.thresholds.as_slice());
+	let feature_group = modelfox_model::BinnedNumberFeatureGroupWriter {
+		source_column_name,
+		thresholds,
+	};
+	writer.write(&feature_group)
+}
+
 fn serialize_word_embedding_feature_group(
 	word_embedding_feature_group: &modelfox_features::WordEmbeddingFeatureGroup,
 	writer: &mut buffalo::Writer,
@@ -1401,3 +2052,137 @@ fn serialize_multiclass_classification_comparison_metric(
 		}
 	}
 }
+
+fn serialize_ranker(
+	ranker: &Ranker,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::RankerWriter> {
+	let target_column_name = writer.write(ranker.target_column_name.as_str());
+	let group_column_name = writer.write(ranker.group_column_name.as_str());
+	let stats_settings = serialize_stats_settings(&ranker.stats_settings, writer);
+	let overall_column_stats = ranker
+		.overall_column_stats
+		.iter()
+		.map(|overall_column_stats| serialize_column_stats_output(overall_column_stats, writer))
+		.collect::<Vec<_>>();
+	let overall_column_stats = writer.write(&overall_column_stats);
+	let overall_target_column_stats =
+		serialize_column_stats_output(&ranker.overall_target_column_stats, writer);
+	let train_column_stats = ranker
+		.train_column_stats
+		.iter()
+		.map(|train_column_stats| serialize_column_stats_output(train_column_stats, writer))
+		.collect::<Vec<_>>();
+	let train_column_stats = writer.write(&train_column_stats);
+	let train_target_column_stats =
+		serialize_column_stats_output(&ranker.train_target_column_stats, writer);
+	let test_column_stats = ranker
+		.test_column_stats
+		.iter()
+		.map(|test_column_stats| serialize_column_stats_output(test_column_stats, writer))
+		.collect::<Vec<_>>();
+	let test_column_stats = writer.write(&test_column_stats);
+	let test_target_column_stats =
+		serialize_column_stats_output(&ranker.test_target_column_stats, writer);
+	let baseline_metrics = serialize_ranking_metrics_output(&ranker.baseline_metrics, writer);
+	let comparison_metric = serialize_ranking_comparison_metric(&ranker.comparison_metric, writer);
+	let train_grid_item_outputs = ranker
+		.train_grid_item_outputs
+		.iter()
+		.map(|train_grid_item_output| {
+			serialize_train_grid_item_output(train_grid_item_output, writer)
+		})
+		.collect::<Vec<_>>();
+	let train_grid_item_outputs = writer.write(&train_grid_item_outputs);
+	let model = serialize_ranking_model(&ranker.model, writer);
+	let test_metrics = serialize_ranking_metrics_output(&ranker.test_metrics, writer);
+	let explanations = serialize_explanations_output(&ranker.explanations, writer);
+	let dropped_columns = serialize_dropped_columns(&ranker.dropped_columns, writer);
+	let data_quality_issues = serialize_data_quality_issues(&ranker.data_quality_issues, writer);
+	let ranker_writer = modelfox_model::RankerWriter {
+		target_column_name,
+		group_column_name,
+		train_row_count: ranker.train_row_count.to_u64().unwrap(),
+		test_row_count: ranker.test_row_count.to_u64().unwrap(),
+		overall_row_count: ranker.overall_row_count.to_u64().unwrap(),
+		stats_settings,
+		overall_column_stats,
+		overall_target_column_stats,
+		train_column_stats,
+		train_target_column_stats,
+		test_column_stats,
+		test_target_column_stats,
+		baseline_metrics,
+		comparison_metric,
+		train_grid_item_outputs,
+		best_grid_item_index: ranker.best_grid_item_index.to_u64().unwrap(),
+		model,
+		test_metrics,
+		ndcg_k: ranker.ndcg_k.map(|ndcg_k| ndcg_k.to_u64().unwrap()),
+		explanations: Some(explanations),
+		dropped_columns,
+		data_quality_issues,
+	};
+	writer.write(&ranker_writer)
+}
+
+fn serialize_ranking_model(
+	ranking_model: &RankingModel,
+	writer: &mut buffalo::Writer,
+) -> modelfox_model::RankingModelWriter {
+	match ranking_model {
+		RankingModel::Tree(tree_model) => {
+			let tree_ranker = serialize_tree_ranking_model(tree_model, writer);
+			modelfox_model::RankingModelWriter::Tree(tree_ranker)
+		}
+	}
+}
+
+fn serialize_tree_ranking_model(
+	tree_ranking_model: &TreeRankingModel,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::TreeRankerWriter> {
+	let feature_importances = writer.write(tree_ranking_model.feature_importances.as_slice());
+	let train_options = serialize_tree_train_options(&tree_ranking_model.train_options, writer);
+	let feature_groups = tree_ranking_model
+		.feature_groups
+		.iter()
+		.map(|feature_group| serialize_feature_group(feature_group, writer))
+		.collect::<Vec<_>>();
+	let feature_groups = writer.write(&feature_groups);
+	let losses = tree_ranking_model
+		.losses
+		.as_ref()
+		.map(|losses| writer.write(losses.as_slice()));
+	let model = tree_ranking_model.model.to_writer(writer);
+	let model = modelfox_model::TreeRankerWriter {
+		model,
+		train_options,
+		feature_groups,
+		losses,
+		feature_importances,
+	};
+	writer.write(&model)
+}
+
+fn serialize_ranking_metrics_output(
+	ranking_metrics_output: &modelfox_metrics::RankingMetricsOutput,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<modelfox_model::RankingMetricsWriter> {
+	let metrics = modelfox_model::RankingMetricsWriter {
+		ndcg: ranking_metrics_output.ndcg,
+		map: ranking_metrics_output.map,
+		n_groups: ranking_metrics_output.n_groups,
+	};
+	writer.write(&metrics)
+}
+
+fn serialize_ranking_comparison_metric(
+	ranking_comparison_metric: &RankingComparisonMetric,
+	_writer: &mut buffalo::Writer,
+) -> modelfox_model::RankingComparisonMetricWriter {
+	match ranking_comparison_metric {
+		RankingComparisonMetric::Ndcg => modelfox_model::RankingComparisonMetricWriter::Ndcg,
+		RankingComparisonMetric::Map => modelfox_model::RankingComparisonMetricWriter::Map,
+	}
+}
@@ -1,13 +1,19 @@
+use crate::target_transform::TargetTransform;
 use modelfox_features::{
-	bag_of_words::BagOfWordsFeatureGroupNGramEntry, BagOfWordsCosineSimilarityFeatureGroup,
-	BagOfWordsFeatureGroup, FeatureGroup, IdentityFeatureGroup, NormalizedFeatureGroup,
-	OneHotEncodedFeatureGroup, WordEmbeddingFeatureGroup,
+	bag_of_words::BagOfWordsFeatureGroupNGramEntry,
+	hashed_bag_of_words::HashedBagOfWordsFeatureGroupStrategy,
+	BagOfWordsCosineSimilarityFeatureGroup, BagOfWordsFeatureGroup, BinnedNumberFeatureGroup,
+	FeatureGroup, HashedBagOfWordsFeatureGroup, HashedEnumFeatureGroup, IdentityFeatureGroup,
+	Impute, ImputeStrategy, InteractionFeatureGroup, InteractionFeatureGroupSource,
+	NormalizedFeatureGroup, OneHotEncodedFeatureGroup, PolynomialFeatureGroup,
+	WordEmbeddingFeatureGroup,
 };
 use modelfox_table::prelude::*;
 use modelfox_text::NGramType;
 use modelfox_zip::zip;
 use ndarray::prelude::*;
 use num::ToPrimitive;
+use rayon::prelude::*;
 use std::collections::BTreeMap;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -106,19 +112,13 @@ impl From<&str> for PredictInputValue {
 	}
 }
 
-#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct PredictOptions {
-	pub threshold: f32,
+	/// If this is `None`, binary classifiers will use the threshold saved in the model.
+	pub threshold: Option<f32>,
 	pub compute_feature_contributions: bool,
-}
-
-impl Default for PredictOptions {
-	fn default() -> PredictOptions {
-		PredictOptions {
-			threshold: 0.5,
-			compute_feature_contributions: false,
-		}
-	}
+	/// Feature interactions are only computed for tree models.
+	pub compute_feature_interactions: bool,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -130,12 +130,16 @@ pub enum PredictOutput {
 	BinaryClassification(BinaryClassificationPredictOutput),
 	#[serde(rename = "multiclass_classification")]
 	MulticlassClassification(MulticlassClassificationPredictOutput),
+	#[serde(rename = "ranking")]
+	Ranking(RankingPredictOutput),
 }
 
 #[derive(Debug, serde::Serialize)]
 pub struct RegressionPredictOutput {
 	pub value: f32,
+	/// If the regressor was trained with a target transform, the feature contributions are in the transformed space of the target, so their output value is the transformed `value`.
 	pub feature_contributions: Option<FeatureContributions>,
+	pub feature_interactions: Option<FeatureInteractions>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -143,6 +147,7 @@ pub struct BinaryClassificationPredictOutput {
 	pub class_name: String,
 	pub probability: f32,
 	pub feature_contributions: Option<FeatureContributions>,
+	pub feature_interactions: Option<FeatureInteractions>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -151,6 +156,14 @@ pub struct MulticlassClassificationPredictOutput {
 	pub probability: f32,
 	pub probabilities: BTreeMap<String, f32>,
 	pub feature_contributions: Option<BTreeMap<String, FeatureContributions>>,
+	pub feature_interactions: Option<BTreeMap<String, FeatureInteractions>>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct RankingPredictOutput {
+	/// Sort the rows of a query group by this score in descending order to rank them. Scores are only comparable within a query group.
+	pub score: f32,
+	pub feature_contributions: Option<FeatureContributions>,
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -163,6 +176,31 @@ pub struct FeatureContributions {
 	pub entries: Vec<FeatureContributionEntry>,
 }
 
+#[derive(Debug, serde::Serialize)]
+pub struct FeatureInteractions {
+	/// The baseline value is the value output by the model for this class before taking into account the feature values.
+	pub baseline_value: f32,
+	/// The output value is the sum of the baseline value and all of the main effects and interaction values.
+	pub output_value: f32,
+	/// These are the main effect of each feature, with the interactions with other features removed.
+	pub main_effects: Vec<FeatureMainEffect>,
+	/// These are the interactions between each pair of features that interact, sorted from the largest to the smallest absolute interaction value.
+	pub entries: Vec<FeatureInteractionEntry>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct FeatureMainEffect {
+	pub feature_name: String,
+	pub main_effect_value: f32,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct FeatureInteractionEntry {
+	pub feature_name_a: String,
+	pub feature_name_b: String,
+	pub feature_interaction_value: f32,
+}
+
 #[derive(Debug, serde::Serialize)]
 #[serde(tag = "type")]
 pub enum FeatureContributionEntry {
@@ -178,6 +216,16 @@ pub enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "hashed_bag_of_words")]
+	HashedBagOfWords(HashedBagOfWordsFeatureContribution),
+	#[serde(rename = "hashed_enum")]
+	HashedEnum(HashedEnumFeatureContribution),
+	#[serde(rename = "interaction")]
+	Interaction(InteractionFeatureContribution),
+	#[serde(rename = "polynomial")]
+	Polynomial(PolynomialFeatureContribution),
+	#[serde(rename = "binned_number")]
+	BinnedNumber(BinnedNumberFeatureContribution),
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -225,12 +273,65 @@ pub struct WordEmbeddingFeatureContribution {
 	pub feature_contribution_value: f32,
 }
 
+#[derive(Debug, serde::Serialize)]
+pub struct HashedBagOfWordsFeatureContribution {
+	pub column_name: String,
+	pub bucket: usize,
+	/// These are the most frequent ngrams in the training data that hash to this bucket.
+	pub ngrams: Vec<NGram>,
+	pub feature_value: f32,
+	pub feature_contribution_value: f32,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct HashedEnumFeatureContribution {
+	pub column_name: String,
+	pub bucket: usize,
+	/// These are the most frequent values in the training data that hash to this bucket.
+	pub values: Vec<String>,
+	pub feature_value: f32,
+	pub feature_contribution_value: f32,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct InteractionFeatureContribution {
+	pub column_name_a: String,
+	/// This is the variant of the first column if it is an enum column and the feature is not for invalid values.
+	pub variant_a: Option<String>,
+	pub column_name_b: String,
+	/// This is the variant of the second column if it is an enum column and the feature is not for invalid values.
+	pub variant_b: Option<String>,
+	pub feature_value: f32,
+	pub feature_contribution_value: f32,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct PolynomialFeatureContribution {
+	pub column_name: String,
+	pub power: usize,
+	pub feature_value: f32,
+	pub feature_contribution_value: f32,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct BinnedNumberFeatureContribution {
+	pub column_name: String,
+	/// This is the lower bound of the bin, or `None` for the first bin.
+	pub bin_start: Option<f32>,
+	/// This is the upper bound of the bin, or `None` for the last bin.
+	pub bin_end: Option<f32>,
+	pub feature_value: bool,
+	pub feature_contribution_value: f32,
+}
+
 #[derive(Debug, serde::Serialize)]
 pub enum NGram {
 	#[serde(rename = "unigram")]
 	Unigram(String),
 	#[serde(rename = "bigram")]
 	Bigram(String, String),
+	#[serde(rename = "character")]
+	Character(String),
 }
 
 impl From<modelfox_text::NGram> for NGram {
@@ -238,6 +339,7 @@ impl From<modelfox_text::NGram> for NGram {
 		match value {
 			modelfox_text::NGram::Unigram(token) => NGram::Unigram(token),
 			modelfox_text::NGram::Bigram(token_a, token_b) => NGram::Bigram(token_a, token_b),
+			modelfox_text::NGram::Character(ngram) => NGram::Character(ngram),
 		}
 	}
 }
@@ -247,6 +349,7 @@ impl std::fmt::Display for NGram {
 		match self {
 			NGram::Unigram(token) => write!(f, "{}", token),
 			NGram::Bigram(token_a, token_b) => write!(f, "{} {}", token_a, token_b),
+			NGram::Character(ngram) => write!(f, "{}", ngram),
 		}
 	}
 }
@@ -262,12 +365,14 @@ pub enum ModelInner {
 	Regressor(Regressor),
 	BinaryClassifier(BinaryClassifier),
 	MulticlassClassifier(MulticlassClassifier),
+	Ranker(Ranker),
 }
 
 #[derive(Debug)]
 pub struct Regressor {
 	pub columns: Vec<Column>,
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
+	pub target_transform: Option<TargetTransform>,
 	pub model: RegressionModel,
 }
 
@@ -276,6 +381,7 @@ pub struct BinaryClassifier {
 	pub columns: Vec<Column>,
 	pub negative_class: String,
 	pub positive_class: String,
+	pub threshold: f32,
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
 	pub model: BinaryClassificationModel,
 }
@@ -288,6 +394,13 @@ pub struct MulticlassClassifier {
 	pub model: MulticlassClassificationModel,
 }
 
+#[derive(Debug)]
+pub struct Ranker {
+	pub columns: Vec<Column>,
+	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
+	pub model: RankingModel,
+}
+
 #[derive(Debug)]
 pub enum RegressionModel {
 	Linear(modelfox_linear::Regressor),
@@ -306,6 +419,11 @@ pub enum MulticlassClassificationModel {
 	Tree(modelfox_tree::MulticlassClassifier),
 }
 
+#[derive(Debug)]
+pub enum RankingModel {
+	Tree(modelfox_tree::Ranker),
+}
+
 #[derive(Debug)]
 pub enum Column {
 	Unknown(UnknownColumn),
@@ -314,25 +432,36 @@ pub enum Column {
 	Text(TextColumn),
 }
 
+impl Column {
+	pub fn name(&self) -> &str {
+		match self {
+			Column::Unknown(column) => &column.name,
+			Column::Number(column) => &column.name,
+			Column::Enum(column) => &column.name,
+			Column::Text(column) => &column.name,
+		}
+	}
+}
+
 #[derive(Debug)]
 pub struct UnknownColumn {
-	name: String,
+	pub name: String,
 }
 
 #[derive(Debug)]
 pub struct NumberColumn {
-	name: String,
+	pub name: String,
 }
 
 #[derive(Debug)]
 pub struct EnumColumn {
-	name: String,
-	variants: Vec<String>,
+	pub name: String,
+	pub variants: Vec<String>,
 }
 
 #[derive(Debug)]
 pub struct TextColumn {
-	name: String,
+	pub name: String,
 }
 
 impl<'a> From<modelfox_model::ModelReader<'a>> for Model {
@@ -378,9 +507,14 @@ fn deserialize_model_inner(model_inner: modelfox_model::ModelInnerReader) -> Mod
 					modelfox_tree::Regressor::from_reader(model.read().model()),
 				),
 			};
+			let target_transform = regressor
+				.target_transform()
+				.flatten()
+				.map(deserialize_target_transform);
 			ModelInner::Regressor(Regressor {
 				columns,
 				feature_groups,
+				target_transform,
 				model,
 			})
 		}
@@ -388,6 +522,9 @@ fn deserialize_model_inner(model_inner: modelfox_model::ModelInnerReader) -> Mod
 			let binary_classifier = binary_classifier.read();
 			let negative_class = binary_classifier.negative_class().to_owned();
 			let positive_class = binary_classifier.positive_class().to_owned();
+			let threshold = binary_classifier
+				.threshold()
+				.unwrap_or(modelfox_model::DEFAULT_THRESHOLD);
 			let columns = binary_classifier
 				.train_column_stats()
 				.iter()
@@ -423,6 +560,7 @@ fn deserialize_model_inner(model_inner: modelfox_model::ModelInnerReader) -> Mod
 				columns,
 				negative_class,
 				positive_class,
+				threshold,
 				feature_groups,
 				model,
 			})
@@ -472,6 +610,32 @@ fn deserialize_model_inner(model_inner: modelfox_model::ModelInnerReader) -> Mod
 				model,
 			})
 		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			let ranker = ranker.read();
+			let columns = ranker
+				.train_column_stats()
+				.iter()
+				.map(deserialize_column_stats)
+				.collect::<Vec<_>>();
+			let (feature_groups, model) = match ranker.model() {
+				modelfox_model::RankingModelReader::Tree(model) => {
+					let model = model.read();
+					let feature_groups = model
+						.feature_groups()
+						.iter()
+						.map(deserialize_feature_group)
+						.collect::<Vec<_>>();
+					let model =
+						RankingModel::Tree(modelfox_tree::Ranker::from_reader(model.model()));
+					(feature_groups, model)
+				}
+			};
+			ModelInner::Ranker(Ranker {
+				columns,
+				feature_groups,
+				model,
+			})
+		}
 	}
 }
 
@@ -510,31 +674,21 @@ fn deserialize_feature_group(feature_group: modelfox_model::FeatureGroupReader)
 		modelfox_model::FeatureGroupReader::Identity(feature_group) => {
 			let feature_group = feature_group.read();
 			let source_column_name = feature_group.source_column_name().to_owned();
-			FeatureGroup::Identity(IdentityFeatureGroup { source_column_name })
-		}
-		modelfox_model::FeatureGroupReader::Normalized(feature_group) => {
-			let feature_group = feature_group.read();
-			let source_column_name = feature_group.source_column_name().to_owned();
-			let mean = feature_group.mean();
-			let variance = feature_group.variance();
-			FeatureGroup::Normalized(NormalizedFeatureGroup {
+			let impute = feature_group.impute().flatten().map(deserialize_impute);
+			let missing_indicator = feature_group.missing_indicator().unwrap_or(false);
+			FeatureGroup::Identity(IdentityFeatureGroup {
 				source_column_name,
-				mean,
-				variance,
+				impute,
+				missing_indicator,
 			})
 		}
+		modelfox_model::FeatureGroupReader::Normalized(feature_group) => {
+			FeatureGroup::Normalized(deserialize_normalized_feature_group(feature_group.read()))
+		}
 		modelfox_model::FeatureGroupReader::OneHotEncoded(feature_group) => {
-			let feature_group = feature_group.read();
-			let source_column_name = feature_group.source_column_name().to_owned();
-			let variants = feature_group
-				.variants()
-				.iter()
-				.map(|key| key.to_owned())
-				.collect::<Vec<_>>();
-			FeatureGroup::OneHotEncoded(OneHotEncodedFeatureGroup {
-				source_column_name,
-				variants,
-			})
+			FeatureGroup::OneHotEncoded(deserialize_one_hot_encoded_feature_group(
+				feature_group.read(),
+			))
 		}
 		modelfox_model::FeatureGroupReader::BagOfWords(feature_group) => {
 			let feature_group = feature_group.read();
@@ -555,10 +709,7 @@ fn deserialize_feature_group(feature_group: modelfox_model::FeatureGroupReader)
 			let ngram_types = feature_group
 				.ngram_types()
 				.iter()
-				.map(|ngram_type| match ngram_type {
-					modelfox_model::NGramTypeReader::Unigram(_) => NGramType::Unigram,
-					modelfox_model::NGramTypeReader::Bigram(_) => NGramType::Bigram,
-				})
+				.map(deserialize_ngram_type)
 				.collect();
 			FeatureGroup::BagOfWords(BagOfWordsFeatureGroup {
 				source_column_name,
@@ -588,10 +739,7 @@ fn deserialize_feature_group(feature_group: modelfox_model::FeatureGroupReader)
 			let ngram_types = feature_group
 				.ngram_types()
 				.iter()
-				.map(|ngram_type| match ngram_type {
-					modelfox_model::NGramTypeReader::Unigram(_) => NGramType::Unigram,
-					modelfox_model::NGramTypeReader::Bigram(_) => NGramType::Bigram,
-				})
+				.map(deserialize_ngram_type)
 				.collect();
 			FeatureGroup::BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureGroup {
 				source_column_name_a,
@@ -613,6 +761,166 @@ fn deserialize_feature_group(feature_group: modelfox_model::FeatureGroupReader)
 				model,
 			})
 		}
+		modelfox_model::FeatureGroupReader::HashedBagOfWords(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = feature_group.source_column_name().to_owned();
+			let tokenizer = deserialize_tokenizer(feature_group.tokenizer());
+			let strategy = match feature_group.strategy() {
+				modelfox_model::HashedBagOfWordsFeatureGroupStrategyReader::Present(_) => {
+					HashedBagOfWordsFeatureGroupStrategy::Present
+				}
+				modelfox_model::HashedBagOfWordsFeatureGroupStrategyReader::Count(_) => {
+					HashedBagOfWordsFeatureGroupStrategy::Count
+				}
+			};
+			let ngram_types = feature_group
+				.ngram_types()
+				.iter()
+				.map(deserialize_ngram_type)
+				.collect();
+			let bucket_ngrams = feature_group
+				.buckets()
+				.iter()
+				.map(|bucket| bucket.ngrams().iter().map(deserialize_ngram).collect())
+				.collect();
+			FeatureGroup::HashedBagOfWords(HashedBagOfWordsFeatureGroup {
+				source_column_name,
+				strategy,
+				tokenizer,
+				ngram_types,
+				n_buckets: feature_group.n_buckets().to_usize().unwrap(),
+				signed: feature_group.signed(),
+				bucket_ngrams,
+			})
+		}
+		modelfox_model::FeatureGroupReader::HashedEnum(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = feature_group.source_column_name().to_owned();
+			let bucket_values = feature_group
+				.buckets()
+				.iter()
+				.map(|bucket| {
+					bucket
+						.values()
+						.iter()
+						.map(|value| value.to_owned())
+						.collect()
+				})
+				.collect();
+			FeatureGroup::HashedEnum(HashedEnumFeatureGroup {
+				source_column_name,
+				n_buckets: feature_group.n_buckets().to_usize().unwrap(),
+				signed: feature_group.signed(),
+				bucket_values,
+			})
+		}
+		modelfox_model::FeatureGroupReader::Interaction(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_a = deserialize_interaction_feature_group_source(feature_group.source_a());
+			let source_b = deserialize_interaction_feature_group_source(feature_group.source_b());
+			FeatureGroup::Interaction(InteractionFeatureGroup { source_a, source_b })
+		}
+		modelfox_model::FeatureGroupReader::Polynomial(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = feature_group.source_column_name().to_owned();
+			FeatureGroup::Polynomial(PolynomialFeatureGroup {
+				source_column_name,
+				mean: feature_group.mean(),
+				variance: feature_group.variance(),
+				degree: feature_group.degree().to_usize().unwrap(),
+			})
+		}
+		modelfox_model::FeatureGroupReader::BinnedNumber(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = feature_group.source_column_name().to_owned();
+			let thresholds = feature_group.thresholds().iter().collect();
+			FeatureGroup::BinnedNumber(BinnedNumberFeatureGroup {
+				source_column_name,
+				thresholds,
+			})
+		}
+	}
+}
+
+fn deserialize_normalized_feature_group(
+	feature_group: modelfox_model::NormalizedFeatureGroupReader,
+) -> NormalizedFeatureGroup {
+	let source_column_name = feature_group.source_column_name().to_owned();
+	let mean = feature_group.mean();
+	let variance = feature_group.variance();
+	let impute = feature_group.impute().flatten().map(deserialize_impute);
+	let missing_indicator = feature_group.missing_indicator().unwrap_or(false);
+	NormalizedFeatureGroup {
+		source_column_name,
+		mean,
+		variance,
+		impute,
+		missing_indicator,
+	}
+}
+
+fn deserialize_one_hot_encoded_feature_group(
+	feature_group: modelfox_model::OneHotEncodedFeatureGroupReader,
+) -> OneHotEncodedFeatureGroup {
+	let source_column_name = feature_group.source_column_name().to_owned();
+	let variants = feature_group
+		.variants()
+		.iter()
+		.map(|key| key.to_owned())
+		.collect::<Vec<_>>();
+	OneHotEncodedFeatureGroup {
+		source_column_name,
+		variants,
+	}
+}
+
+fn deserialize_interaction_feature_group_source(
+	source: modelfox_model::InteractionFeatureGroupSourceReader,
+) -> InteractionFeatureGroupSource {
+	match source {
+		modelfox_model::InteractionFeatureGroupSourceReader::Normalized(feature_group) => {
+			InteractionFeatureGroupSource::Normalized(deserialize_normalized_feature_group(
+				feature_group.read(),
+			))
+		}
+		modelfox_model::InteractionFeatureGroupSourceReader::OneHotEncoded(feature_group) => {
+			InteractionFeatureGroupSource::OneHotEncoded(deserialize_one_hot_encoded_feature_group(
+				feature_group.read(),
+			))
+		}
+	}
+}
+
+fn deserialize_target_transform(
+	target_transform: modelfox_model::TargetTransformReader,
+) -> TargetTransform {
+	match target_transform {
+		modelfox_model::TargetTransformReader::Log1p(_) => TargetTransform::Log1p,
+		modelfox_model::TargetTransformReader::BoxCox(target_transform) => {
+			TargetTransform::BoxCox {
+				lambda: target_transform.read().lambda(),
+			}
+		}
+		modelfox_model::TargetTransformReader::Standardize(target_transform) => {
+			let target_transform = target_transform.read();
+			TargetTransform::Standardize {
+				mean: target_transform.mean(),
+				std: target_transform.std(),
+			}
+		}
+	}
+}
+
+fn deserialize_impute(impute: modelfox_model::ImputeReader) -> Impute {
+	let strategy = match impute.strategy() {
+		modelfox_model::ImputeStrategyReader::Mean(_) => ImputeStrategy::Mean,
+		modelfox_model::ImputeStrategyReader::Median(_) => ImputeStrategy::Median,
+		modelfox_model::ImputeStrategyReader::Mode(_) => ImputeStrategy::Mode,
+		modelfox_model::ImputeStrategyReader::Constant(_) => ImputeStrategy::Constant,
+	};
+	Impute {
+		strategy,
+		value: impute.value(),
 	}
 }
 
@@ -642,6 +950,16 @@ fn deserialize_word_embedding_model(
 	model.into()
 }
 
+fn deserialize_ngram_type(ngram_type: modelfox_model::NGramTypeReader) -> NGramType {
+	match ngram_type {
+		modelfox_model::NGramTypeReader::Unigram(_) => NGramType::Unigram,
+		modelfox_model::NGramTypeReader::Bigram(_) => NGramType::Bigram,
+		modelfox_model::NGramTypeReader::CharacterTrigram(_) => NGramType::CharacterTrigram,
+		modelfox_model::NGramTypeReader::CharacterQuadgram(_) => NGramType::CharacterQuadgram,
+		modelfox_model::NGramTypeReader::CharacterPentagram(_) => NGramType::CharacterPentagram,
+	}
+}
+
 fn deserialize_ngram(ngram: modelfox_model::NGramReader) -> modelfox_text::NGram {
 	match ngram {
 		modelfox_model::NGramReader::Unigram(unigram) => {
@@ -652,9 +970,16 @@ fn deserialize_ngram(ngram: modelfox_model::NGramReader) -> modelfox_text::NGram
 			let (token_a, token_b) = bigram.read();
 			modelfox_text::NGram::Bigram(token_a.to_owned(), token_b.to_owned())
 		}
+		modelfox_model::NGramReader::Character(ngram) => {
+			let ngram = ngram.read();
+			modelfox_text::NGram::Character((*ngram).to_owned())
+		}
 	}
 }
 
+/// This is the number of rows predicted together by each parallel task in [`predict_table`].
+const PREDICT_CHUNK_SIZE: usize = 1024;
+
 pub fn predict(
 	model: &Model,
 	input: &[PredictInput],
@@ -667,15 +992,11 @@ pub fn predict(
 		ModelInner::MulticlassClassifier(multiclass_classifier) => {
 			multiclass_classifier.columns.as_slice()
 		}
+		ModelInner::Ranker(ranker) => ranker.columns.as_slice(),
 	};
 	let column_names = columns
 		.iter()
-		.map(|column| match column {
-			Column::Unknown(column) => Some(column.name.clone()),
-			Column::Number(column) => Some(column.name.clone()),
-			Column::Enum(column) => Some(column.name.clone()),
-			Column::Text(column) => Some(column.name.clone()),
-		})
+		.map(|column| Some(column.name().to_owned()))
 		.collect();
 	let column_types = columns
 		.iter()
@@ -697,57 +1018,225 @@ pub fn predict(
 				modelfox_table::TableColumn::Number(column) => {
 					let value = match input.0.get(column.name().as_ref().unwrap()) {
 						Some(PredictInputValue::Number(value)) => value.to_f32().unwrap(),
-						Some(PredictInputValue::String(value)) => {
-							fast_float::parse::<f32, &str>(value)
-								.map(|value| if value.is_finite() { value } else { f32::NAN })
-								.unwrap_or(f32::NAN)
-						}
+						Some(PredictInputValue::String(value)) => parse_number(value),
 						_ => f32::NAN,
 					};
 					column.data_mut().push(value);
 				}
 				modelfox_table::TableColumn::Enum(column) => {
-					let value = input
-						.0
-						.get(column.name().as_ref().unwrap())
-						.and_then(|value| value.as_str());
-					let value = value.and_then(|value| column.value_for_variant(value));
+					let value = match input.0.get(column.name().as_ref().unwrap()) {
+						Some(PredictInputValue::String(value)) => column.value_for_variant(value),
+						Some(PredictInputValue::Number(value)) => {
+							format_number(value.to_f32().unwrap())
+								.and_then(|value| column.value_for_variant(&value))
+						}
+						_ => None,
+					};
 					column.data_mut().push(value);
 				}
 				modelfox_table::TableColumn::Text(column) => {
-					let value = input
-						.0
-						.get(column.name().as_ref().unwrap())
-						.and_then(|value| value.as_str())
-						.unwrap_or("")
-						.to_owned();
+					let value = match input.0.get(column.name().as_ref().unwrap()) {
+						Some(PredictInputValue::String(value)) => value.clone(),
+						Some(PredictInputValue::Number(value)) => {
+							format_number(value.to_f32().unwrap()).unwrap_or_default()
+						}
+						_ => String::new(),
+					};
 					column.data_mut().push(value);
 				}
 			}
 		}
 	}
-	// Make the predictions by matching on the model type.
-	match &model.inner {
-		ModelInner::Regressor(regressor) => predict_regressor(regressor, table, options)
-			.into_iter()
-			.map(PredictOutput::Regression)
-			.collect(),
-		ModelInner::BinaryClassifier(model) => predict_binary_classifier(model, table, options)
-			.into_iter()
-			.map(PredictOutput::BinaryClassification)
-			.collect(),
-		ModelInner::MulticlassClassifier(model) => {
-			predict_multiclass_classifier(model, table, options)
+	predict_table(model, table.view(), options)
+}
+
+/// Make a prediction for each row of `table`. The columns of `table` are matched to the model's columns by name and converted if their types differ, so no map is built for each row. The rows are split into chunks that are predicted in parallel.
+pub fn predict_table(
+	model: &Model,
+	table: TableView,
+	options: &PredictOptions,
+) -> Vec<PredictOutput> {
+	let columns = match &model.inner {
+		ModelInner::Regressor(regressor) => regressor.columns.as_slice(),
+		ModelInner::BinaryClassifier(binary_classifier) => binary_classifier.columns.as_slice(),
+		ModelInner::MulticlassClassifier(multiclass_classifier) => {
+			multiclass_classifier.columns.as_slice()
+		}
+		ModelInner::Ranker(ranker) => ranker.columns.as_slice(),
+	};
+	let n_rows = table.nrows();
+	let table_columns = columns
+		.iter()
+		.map(|column| {
+			table
+				.columns()
+				.iter()
+				.find(|table_column| table_column.name() == Some(column.name()))
+		})
+		.collect::<Vec<_>>();
+	let conformed_columns = zip!(columns.iter(), table_columns.iter())
+		.map(|(column, table_column)| conform_column(column, *table_column, n_rows))
+		.collect::<Vec<_>>();
+	let table = zip!(table_columns.iter(), conformed_columns.iter())
+		.map(
+			|(table_column, conformed_column)| match (table_column, conformed_column) {
+				(_, Some(conformed_column)) => conformed_column.view(),
+				(Some(table_column), None) => (*table_column).clone(),
+				(None, None) => unreachable!(),
+			},
+		)
+		.collect::<Vec<_>>();
+	let table = TableView::from_columns(table);
+	// Split the table into chunks of rows.
+	let mut chunks = Vec::new();
+	let mut rest = table;
+	while rest.nrows() > PREDICT_CHUNK_SIZE {
+		let (chunk, next_rest) = rest.split_at_row(PREDICT_CHUNK_SIZE);
+		chunks.push(chunk);
+		rest = next_rest;
+	}
+	chunks.push(rest);
+	// Make the predictions for each chunk by matching on the model type.
+	let outputs: Vec<Vec<PredictOutput>> = chunks
+		.into_par_iter()
+		.map(|table| match &model.inner {
+			ModelInner::Regressor(regressor) => predict_regressor(regressor, table, options)
+				.into_iter()
+				.map(PredictOutput::Regression)
+				.collect(),
+			ModelInner::BinaryClassifier(model) => predict_binary_classifier(model, table, options)
+				.into_iter()
+				.map(PredictOutput::BinaryClassification)
+				.collect(),
+			ModelInner::MulticlassClassifier(model) => {
+				predict_multiclass_classifier(model, table, options)
+					.into_iter()
+					.map(PredictOutput::MulticlassClassification)
+					.collect()
+			}
+			ModelInner::Ranker(model) => predict_ranker(model, table, options)
 				.into_iter()
-				.map(PredictOutput::MulticlassClassification)
-				.collect()
+				.map(PredictOutput::Ranking)
+				.collect(),
+		})
+		.collect();
+	outputs.into_iter().flatten().collect()
+}
+
+/// Convert a column of the input table to the type of the model's column. If the input column already has the right type it is used as is and `None` is returned. Values that cannot be converted are treated as missing, the same way as for a `PredictInput`.
+fn conform_column(
+	column: &Column,
+	table_column: Option<&TableColumnView>,
+	n_rows: usize,
+) -> Option<TableColumn> {
+	match (column, table_column) {
+		(Column::Unknown(_), Some(TableColumnView::Unknown(_)))
+		| (Column::Number(_), Some(TableColumnView::Number(_)))
+		| (Column::Text(_), Some(TableColumnView::Text(_))) => None,
+		(Column::Enum(column), Some(TableColumnView::Enum(table_column)))
+			if table_column.variants() == column.variants.as_slice() =>
+		{
+			None
+		}
+		(Column::Unknown(column), _) => {
+			let mut unknown_column = UnknownTableColumn::new(Some(column.name.clone()));
+			*unknown_column.len_mut() = n_rows;
+			Some(TableColumn::Unknown(unknown_column))
+		}
+		(Column::Number(column), table_column) => {
+			let data = match table_column {
+				Some(TableColumnView::Text(table_column)) => table_column
+					.iter()
+					.map(|value| parse_number(value))
+					.collect(),
+				Some(TableColumnView::Enum(table_column)) => table_column
+					.iter()
+					.map(|value| {
+						value
+							.map(|value| parse_number(&table_column.variants()[value.get() - 1]))
+							.unwrap_or(f32::NAN)
+					})
+					.collect(),
+				_ => vec![f32::NAN; n_rows],
+			};
+			Some(TableColumn::Number(NumberTableColumn::new(
+				Some(column.name.clone()),
+				data,
+			)))
+		}
+		(Column::Enum(column), table_column) => {
+			let mut enum_column = EnumTableColumn::new(
+				Some(column.name.clone()),
+				column.variants.clone(),
+				Vec::new(),
+			);
+			let data = match table_column {
+				Some(TableColumnView::Text(table_column)) => table_column
+					.iter()
+					.map(|value| enum_column.value_for_variant(value))
+					.collect(),
+				Some(TableColumnView::Enum(table_column)) => table_column
+					.iter()
+					.map(|value| {
+						value.and_then(|value| {
+							enum_column.value_for_variant(&table_column.variants()[value.get() - 1])
+						})
+					})
+					.collect(),
+				Some(TableColumnView::Number(table_column)) => table_column
+					.iter()
+					.map(|value| {
+						format_number(*value)
+							.and_then(|value| enum_column.value_for_variant(&value))
+					})
+					.collect(),
+				_ => vec![None; n_rows],
+			};
+			*enum_column.data_mut() = data;
+			Some(TableColumn::Enum(enum_column))
+		}
+		(Column::Text(column), table_column) => {
+			let data = match table_column {
+				Some(TableColumnView::Enum(table_column)) => table_column
+					.iter()
+					.map(|value| {
+						value
+							.map(|value| table_column.variants()[value.get() - 1].clone())
+							.unwrap_or_default()
+					})
+					.collect(),
+				Some(TableColumnView::Number(table_column)) => table_column
+					.iter()
+					.map(|value| format_number(*value).unwrap_or_default())
+					.collect(),
+				_ => vec![String::new(); n_rows],
+			};
+			Some(TableColumn::Text(TextTableColumn::new(
+				Some(column.name.clone()),
+				data,
+			)))
 		}
 	}
 }
 
+fn parse_number(value: &str) -> f32 {
+	fast_float::parse::<f32, &str>(value)
+		.map(|value| if value.is_finite() { value } else { f32::NAN })
+		.unwrap_or(f32::NAN)
+}
+
+/// Format a number so it can be matched to an enum variant, such as 1 to "1". Numbers that are not finite are treated as missing.
+fn format_number(value: f32) -> Option<String> {
+	if value.is_finite() {
+		Some(value.to_string())
+	} else {
+		None
+	}
+}
+
 fn predict_regressor(
 	model: &Regressor,
-	table: Table,
+	table: TableView,
 	options: &PredictOptions,
 ) -> Vec<RegressionPredictOutput> {
 	let n_rows = table.nrows();
@@ -755,7 +1244,7 @@ fn predict_regressor(
 		RegressionModel::Linear(inner_model) => {
 			let mut predictions = Array::zeros(n_rows);
 			let features = modelfox_features::compute_features_array_f32(
-				&table.view(),
+				&table,
 				&model.feature_groups,
 				&|| {},
 			);
@@ -763,8 +1252,9 @@ fn predict_regressor(
 			let mut outputs: Vec<RegressionPredictOutput> = predictions
 				.iter()
 				.map(|prediction| RegressionPredictOutput {
-					value: *prediction,
+					value: inverse_transform_prediction(model, *prediction),
 					feature_contributions: None,
+					feature_interactions: None,
 				})
 				.collect();
 			if options.compute_feature_contributions {
@@ -795,7 +1285,7 @@ fn predict_regressor(
 		}
 		RegressionModel::Tree(inner_model) => {
 			let features = modelfox_features::compute_features_array_value(
-				&table.view(),
+				&table,
 				&model.feature_groups,
 				&|| {},
 			);
@@ -804,7 +1294,80 @@ fn predict_regressor(
 			let mut outputs: Vec<RegressionPredictOutput> = predictions
 				.iter()
 				.map(|prediction| RegressionPredictOutput {
-					value: *prediction,
+					value: inverse_transform_prediction(model, *prediction),
+					feature_contributions: None,
+					feature_interactions: None,
+				})
+				.collect();
+			if options.compute_feature_contributions {
+				let feature_contributions =
+					inner_model.compute_feature_contributions(features.view());
+				for (mut output, features, feature_contributions) in zip!(
+					outputs.iter_mut(),
+					features.axis_iter(Axis(0)),
+					feature_contributions,
+				) {
+					let baseline_value = feature_contributions.baseline_value;
+					let output_value = feature_contributions.output_value;
+					let feature_contributions = compute_feature_contributions(
+						model.feature_groups.iter(),
+						features.iter().map(|v| match v {
+							modelfox_table::TableValue::Number(value) => *value,
+							modelfox_table::TableValue::Enum(value) => {
+								value.map(|v| v.get()).unwrap_or(0).to_f32().unwrap()
+							}
+							_ => unreachable!(),
+						}),
+						feature_contributions
+							.feature_contribution_values
+							.into_iter(),
+					);
+					output.feature_contributions = Some(FeatureContributions {
+						baseline_value,
+						output_value,
+						entries: feature_contributions,
+					});
+				}
+			}
+			if options.compute_feature_interactions {
+				let feature_names = compute_feature_names(&model.feature_groups);
+				let feature_interactions =
+					inner_model.compute_feature_interactions(features.view());
+				for (mut output, feature_interactions) in
+					zip!(outputs.iter_mut(), feature_interactions)
+				{
+					output.feature_interactions = Some(compute_feature_interactions(
+						&feature_names,
+						feature_interactions.baseline_value,
+						feature_interactions.output_value,
+						feature_interactions.feature_interaction_values.view(),
+					));
+				}
+			}
+			outputs
+		}
+	}
+}
+
+fn predict_ranker(
+	model: &Ranker,
+	table: TableView,
+	options: &PredictOptions,
+) -> Vec<RankingPredictOutput> {
+	let n_rows = table.nrows();
+	match &model.model {
+		RankingModel::Tree(inner_model) => {
+			let features = modelfox_features::compute_features_array_value(
+				&table,
+				&model.feature_groups,
+				&|| {},
+			);
+			let mut scores = Array::zeros(n_rows);
+			inner_model.predict(features.view(), scores.view_mut());
+			let mut outputs: Vec<RankingPredictOutput> = scores
+				.iter()
+				.map(|score| RankingPredictOutput {
+					score: *score,
 					feature_contributions: None,
 				})
 				.collect();
@@ -843,17 +1406,26 @@ fn predict_regressor(
 	}
 }
 
+/// Transform a prediction of the regressor back to the original scale of the target column.
+fn inverse_transform_prediction(model: &Regressor, prediction: f32) -> f32 {
+	match model.target_transform {
+		Some(target_transform) => target_transform.inverse_transform(prediction),
+		None => prediction,
+	}
+}
+
 fn predict_binary_classifier(
 	model: &BinaryClassifier,
-	table: Table,
+	table: TableView,
 	options: &PredictOptions,
 ) -> Vec<BinaryClassificationPredictOutput> {
 	let n_rows = table.nrows();
+	let threshold = options.threshold.unwrap_or(model.threshold);
 	match &model.model {
 		BinaryClassificationModel::Linear(inner_model) => {
 			let mut probabilities = Array::zeros(n_rows);
 			let features = modelfox_features::compute_features_array_f32(
-				&table.view(),
+				&table,
 				&model.feature_groups,
 				&|| {},
 			);
@@ -861,7 +1433,7 @@ fn predict_binary_classifier(
 			let mut outputs: Vec<BinaryClassificationPredictOutput> = probabilities
 				.iter()
 				.map(|probability| {
-					let (probability, class_name) = if *probability >= options.threshold {
+					let (probability, class_name) = if *probability >= threshold {
 						(*probability, model.positive_class.clone())
 					} else {
 						(1.0 - probability, model.negative_class.clone())
@@ -870,6 +1442,7 @@ fn predict_binary_classifier(
 						class_name,
 						probability,
 						feature_contributions: None,
+						feature_interactions: None,
 					}
 				})
 				.collect();
@@ -899,7 +1472,7 @@ fn predict_binary_classifier(
 		}
 		BinaryClassificationModel::Tree(inner_model) => {
 			let features = modelfox_features::compute_features_array_value(
-				&table.view(),
+				&table,
 				&model.feature_groups,
 				&|| {},
 			);
@@ -908,7 +1481,7 @@ fn predict_binary_classifier(
 			let mut outputs: Vec<BinaryClassificationPredictOutput> = probabilities
 				.iter()
 				.map(|probability| {
-					let (probability, class_name) = if *probability >= options.threshold {
+					let (probability, class_name) = if *probability >= threshold {
 						(*probability, model.positive_class.clone())
 					} else {
 						(1.0 - probability, model.negative_class.clone())
@@ -917,6 +1490,7 @@ fn predict_binary_classifier(
 						class_name,
 						probability,
 						feature_contributions: None,
+						feature_interactions: None,
 					}
 				})
 				.collect();
@@ -948,6 +1522,21 @@ fn predict_binary_classifier(
 					});
 				}
 			}
+			if options.compute_feature_interactions {
+				let feature_names = compute_feature_names(&model.feature_groups);
+				let feature_interactions =
+					inner_model.compute_feature_interactions(features.view());
+				for (mut output, feature_interactions) in
+					zip!(outputs.iter_mut(), feature_interactions)
+				{
+					output.feature_interactions = Some(compute_feature_interactions(
+						&feature_names,
+						feature_interactions.baseline_value,
+						feature_interactions.output_value,
+						feature_interactions.feature_interaction_values.view(),
+					));
+				}
+			}
 			outputs
 		}
 	}
@@ -955,7 +1544,7 @@ fn predict_binary_classifier(
 
 fn predict_multiclass_classifier(
 	model: &MulticlassClassifier,
-	table: Table,
+	table: TableView,
 	options: &PredictOptions,
 ) -> Vec<MulticlassClassificationPredictOutput> {
 	let n_rows = table.nrows();
@@ -964,7 +1553,7 @@ fn predict_multiclass_classifier(
 		MulticlassClassificationModel::Linear(inner_model) => {
 			let mut probabilities = Array::zeros((n_rows, n_classes));
 			let features = modelfox_features::compute_features_array_f32(
-				&table.view(),
+				&table,
 				&model.feature_groups,
 				&|| {},
 			);
@@ -984,6 +1573,7 @@ fn predict_multiclass_classifier(
 						probability: *probability,
 						probabilities,
 						feature_contributions: None,
+						feature_interactions: None,
 					}
 				})
 				.collect();
@@ -1019,7 +1609,7 @@ fn predict_multiclass_classifier(
 		}
 		MulticlassClassificationModel::Tree(inner_model) => {
 			let features = modelfox_features::compute_features_array_value(
-				&table.view(),
+				&table,
 				&model.feature_groups,
 				&|| {},
 			);
@@ -1040,6 +1630,7 @@ fn predict_multiclass_classifier(
 						probability: *probability,
 						probabilities,
 						feature_contributions: None,
+						feature_interactions: None,
 					}
 				})
 				.collect();
@@ -1077,11 +1668,83 @@ fn predict_multiclass_classifier(
 					output.feature_contributions = Some(feature_contributions)
 				}
 			}
+			if options.compute_feature_interactions {
+				let feature_names = compute_feature_names(&model.feature_groups);
+				let feature_interactions =
+					inner_model.compute_feature_interactions(features.view());
+				for (mut output, feature_interactions) in
+					zip!(outputs.iter_mut(), feature_interactions)
+				{
+					let feature_interactions = zip!(model.classes.iter(), feature_interactions)
+						.map(|(class, feature_interactions)| {
+							let feature_interactions = compute_feature_interactions(
+								&feature_names,
+								feature_interactions.baseline_value,
+								feature_interactions.output_value,
+								feature_interactions.feature_interaction_values.view(),
+							);
+							(class.clone(), feature_interactions)
+						})
+						.collect();
+					output.feature_interactions = Some(feature_interactions);
+				}
+			}
 			outputs
 		}
 	}
 }
 
+fn compute_feature_names(feature_groups: &[modelfox_features::FeatureGroup]) -> Vec<String> {
+	feature_groups
+		.iter()
+		.flat_map(|feature_group| feature_group.feature_names())
+		.collect()
+}
+
+fn compute_feature_interactions(
+	feature_names: &[String],
+	baseline_value: f32,
+	output_value: f32,
+	feature_interaction_values: ArrayView2<f32>,
+) -> FeatureInteractions {
+	let main_effects = zip!(
+		feature_names.iter(),
+		feature_interaction_values.diag().iter()
+	)
+	.map(|(feature_name, main_effect_value)| FeatureMainEffect {
+		feature_name: feature_name.clone(),
+		main_effect_value: *main_effect_value,
+	})
+	.collect();
+	// The interaction matrix is symmetric and each off diagonal entry holds half of the interaction, so sum the entries above and below the diagonal.
+	let mut entries = Vec::new();
+	for (index_a, feature_name_a) in feature_names.iter().enumerate() {
+		for (index_b, feature_name_b) in feature_names.iter().enumerate().skip(index_a + 1) {
+			let feature_interaction_value = feature_interaction_values[[index_a, index_b]]
+				+ feature_interaction_values[[index_b, index_a]];
+			if feature_interaction_value != 0.0 {
+				entries.push(FeatureInteractionEntry {
+					feature_name_a: feature_name_a.clone(),
+					feature_name_b: feature_name_b.clone(),
+					feature_interaction_value,
+				});
+			}
+		}
+	}
+	entries.sort_by(|a, b| {
+		b.feature_interaction_value
+			.abs()
+			.partial_cmp(&a.feature_interaction_value.abs())
+			.unwrap()
+	});
+	FeatureInteractions {
+		baseline_value,
+		output_value,
+		main_effects,
+		entries,
+	}
+}
+
 fn compute_feature_contributions<'a>(
 	feature_groups: impl Iterator<Item = &'a modelfox_features::FeatureGroup>,
 	mut features: impl Iterator<Item = f32>,
@@ -1092,7 +1755,12 @@ fn compute_feature_contributions<'a>(
 		match feature_group {
 			modelfox_features::FeatureGroup::Identity(feature_group) => {
 				let feature_value = features.next().unwrap();
-				let feature_contribution_value = feature_contribution_values.next().unwrap();
+				let mut feature_contribution_value = feature_contribution_values.next().unwrap();
+				// The contribution of the missing indicator feature is included in the contribution of the source column.
+				if feature_group.missing_indicator {
+					features.next().unwrap();
+					feature_contribution_value += feature_contribution_values.next().unwrap();
+				}
 				entries.push(FeatureContributionEntry::Identity(
 					IdentityFeatureContribution {
 						column_name: feature_group.source_column_name.clone(),
@@ -1103,7 +1771,11 @@ fn compute_feature_contributions<'a>(
 			}
 			modelfox_features::FeatureGroup::Normalized(feature_group) => {
 				let feature_value = features.next().unwrap();
-				let feature_contribution_value = feature_contribution_values.next().unwrap();
+				let mut feature_contribution_value = feature_contribution_values.next().unwrap();
+				if feature_group.missing_indicator {
+					features.next().unwrap();
+					feature_contribution_value += feature_contribution_values.next().unwrap();
+				}
 				entries.push(FeatureContributionEntry::Normalized(
 					NormalizedFeatureContribution {
 						column_name: feature_group.source_column_name.clone(),
@@ -1174,7 +1846,756 @@ fn compute_feature_contributions<'a>(
 					));
 				}
 			}
+			modelfox_features::FeatureGroup::HashedBagOfWords(feature_group) => {
+				for (bucket, ngrams) in feature_group.bucket_ngrams.iter().enumerate() {
+					let feature_value = features.next().unwrap();
+					let feature_contribution_value = feature_contribution_values.next().unwrap();
+					entries.push(FeatureContributionEntry::HashedBagOfWords(
+						HashedBagOfWordsFeatureContribution {
+							column_name: feature_group.source_column_name.clone(),
+							bucket,
+							ngrams: ngrams.iter().cloned().map(Into::into).collect(),
+							feature_value,
+							feature_contribution_value,
+						},
+					));
+				}
+			}
+			modelfox_features::FeatureGroup::HashedEnum(feature_group) => {
+				for (bucket, values) in feature_group.bucket_values.iter().enumerate() {
+					let feature_value = features.next().unwrap();
+					let feature_contribution_value = feature_contribution_values.next().unwrap();
+					entries.push(FeatureContributionEntry::HashedEnum(
+						HashedEnumFeatureContribution {
+							column_name: feature_group.source_column_name.clone(),
+							bucket,
+							values: values.clone(),
+							feature_value,
+							feature_contribution_value,
+						},
+					));
+				}
+			}
+			modelfox_features::FeatureGroup::Interaction(feature_group) => {
+				for feature_index in 0..feature_group.n_features() {
+					let (feature_index_a, feature_index_b) =
+						feature_group.source_feature_indexes(feature_index);
+					let feature_value = features.next().unwrap();
+					let feature_contribution_value = feature_contribution_values.next().unwrap();
+					entries.push(FeatureContributionEntry::Interaction(
+						InteractionFeatureContribution {
+							column_name_a: feature_group.source_a.source_column_name().to_owned(),
+							variant_a: feature_group
+								.source_a
+								.variant(feature_index_a)
+								.map(|variant| variant.to_owned()),
+							column_name_b: feature_group.source_b.source_column_name().to_owned(),
+							variant_b: feature_group
+								.source_b
+								.variant(feature_index_b)
+								.map(|variant| variant.to_owned()),
+							feature_value,
+							feature_contribution_value,
+						},
+					));
+				}
+			}
+			modelfox_features::FeatureGroup::Polynomial(feature_group) => {
+				for power in feature_group.powers() {
+					let feature_value = features.next().unwrap();
+					let feature_contribution_value = feature_contribution_values.next().unwrap();
+					entries.push(FeatureContributionEntry::Polynomial(
+						PolynomialFeatureContribution {
+							column_name: feature_group.source_column_name.clone(),
+							power,
+							feature_value,
+							feature_contribution_value,
+						},
+					));
+				}
+			}
+			modelfox_features::FeatureGroup::BinnedNumber(feature_group) => {
+				for bin in 0..feature_group.thresholds.len() + 1 {
+					let (bin_start, bin_end) = feature_group.bin_range(bin);
+					let feature_value = features.next().unwrap();
+					let feature_contribution_value = feature_contribution_values.next().unwrap();
+					entries.push(FeatureContributionEntry::BinnedNumber(
+						BinnedNumberFeatureContribution {
+							column_name: feature_group.source_column_name.clone(),
+							bin_start,
+							bin_end,
+							feature_value: feature_value > 0.0,
+							feature_contribution_value,
+						},
+					));
+				}
+			}
 		}
 	}
 	entries
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		checks::DataQualityIssue,
+		config::Config,
+		train::{Trainer, TrainingDataSource},
+	};
+	use modelfox_kill_chip::KillChip;
+	use std::{num::NonZeroUsize, path::Path};
+
+	/// Train a model on the heart disease dataset with a grid of a single item, and return the bytes of its `.modelfox` file.
+	fn train_heart_disease_model(grid_item: &str) -> Vec<u8> {
+		let config = format!(r#"{{ "train": {{ "grid": [{}] }} }}"#, grid_item);
+		let config: Config = serde_json::from_str(&config).unwrap();
+		let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../heart_disease.csv");
+		let mut trainer = Trainer::prepare(
+			modelfox_id::Id::generate(),
+			TrainingDataSource::File(path),
+			"diagnosis",
+			config,
+			None,
+			&mut |_| {},
+		)
+		.unwrap();
+		let train_grid_item_outputs = trainer
+			.train_grid(&KillChip::default(), &mut |_| {})
+			.unwrap();
+		let model = trainer
+			.test_and_assemble_model(train_grid_item_outputs, &mut |_| {})
+			.unwrap();
+		model.to_bytes()
+	}
+
+	#[test]
+	fn test_set_threshold() {
+		let mut bytes = train_heart_disease_model(r#"{ "model": "linear", "max_epochs": 10 }"#);
+		let original_bytes = bytes.clone();
+		let threshold = |bytes: &[u8]| {
+			let model = modelfox_model::from_bytes(bytes).unwrap();
+			model.inner().as_binary_classifier().unwrap().threshold()
+		};
+		assert_eq!(threshold(&bytes), Some(modelfox_model::DEFAULT_THRESHOLD));
+		modelfox_model::set_threshold(&mut bytes, 0.0).unwrap();
+		assert_eq!(threshold(&bytes), Some(0.0));
+		// Only the four bytes of the threshold changed.
+		let n_changed_bytes = bytes
+			.iter()
+			.zip(original_bytes.iter())
+			.filter(|(a, b)| a != b)
+			.count();
+		assert!(n_changed_bytes <= 4);
+		assert_eq!(bytes.len(), original_bytes.len());
+		// With a threshold of zero, every example is predicted to be the positive class.
+		let model = Model::from(modelfox_model::from_bytes(&bytes).unwrap());
+		let input: PredictInput =
+			serde_json::from_str(r#"{ "age": 40, "gender": "female" }"#).unwrap();
+		let output = predict(&model, &[input], &PredictOptions::default());
+		match &output[0] {
+			PredictOutput::BinaryClassification(output) => {
+				assert_eq!(output.class_name, "Positive")
+			}
+			_ => panic!("expected a binary classification output"),
+		}
+	}
+
+	#[test]
+	fn test_set_threshold_on_a_model_without_a_threshold() {
+		// This model was trained before binary classifiers saved their threshold.
+		let mut bytes = std::fs::read(
+			Path::new(env!("CARGO_MANIFEST_DIR")).join("../../heart_disease.modelfox"),
+		)
+		.unwrap();
+		assert!(modelfox_model::set_threshold(&mut bytes, 0.3).is_err());
+	}
+
+	fn heart_disease_model() -> Model {
+		let bytes = train_heart_disease_model(r#"{ "model": "tree", "max_rounds": 20 }"#);
+		Model::from(modelfox_model::from_bytes(&bytes).unwrap())
+	}
+
+	fn heart_disease_rows() -> Vec<BTreeMap<String, String>> {
+		let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../heart_disease.csv");
+		csv::Reader::from_path(path)
+			.unwrap()
+			.deserialize()
+			.take(50)
+			.map(Result::unwrap)
+			.collect()
+	}
+
+	fn to_json(outputs: &[PredictOutput]) -> serde_json::Value {
+		serde_json::to_value(outputs).unwrap()
+	}
+
+	#[test]
+	fn test_conform_number_column() {
+		let table_column = NumberTableColumn::new(
+			Some("column".to_owned()),
+			vec![1.0, 3.0, f32::NAN, 4.0, 2.5],
+		);
+		let table_column = TableColumnView::Number(table_column.view());
+		let column = Column::Enum(EnumColumn {
+			name: "column".to_owned(),
+			variants: vec![
+				"1".to_owned(),
+				"2".to_owned(),
+				"3".to_owned(),
+				"2.5".to_owned(),
+			],
+		});
+		let conformed_column = conform_column(&column, Some(&table_column), 5).unwrap();
+		assert_eq!(
+			conformed_column.as_enum().unwrap().view().as_slice(),
+			&[
+				NonZeroUsize::new(1),
+				NonZeroUsize::new(3),
+				None,
+				None,
+				NonZeroUsize::new(4),
+			]
+		);
+		let column = Column::Text(TextColumn {
+			name: "column".to_owned(),
+		});
+		let conformed_column = conform_column(&column, Some(&table_column), 5).unwrap();
+		assert_eq!(
+			conformed_column.as_text().unwrap().view().as_slice(),
+			&["1", "3", "", "4", "2.5"]
+		);
+	}
+
+	#[test]
+	fn test_predict_table_matches_predict_with_mismatched_column_types() {
+		let model = heart_disease_model();
+		let options = PredictOptions::default();
+		let rows = heart_disease_rows();
+		// Predicting with every value as a string is the reference.
+		let string_inputs = rows
+			.iter()
+			.map(|row| {
+				PredictInput(
+					row.iter()
+						.map(|(key, value)| (key.clone(), PredictInputValue::String(value.clone())))
+						.collect(),
+				)
+			})
+			.collect::<Vec<_>>();
+		let expected = to_json(&predict(&model, &string_inputs, &options));
+		// Values that look like numbers are passed as numbers, including the values of enum columns.
+		let number_inputs = rows
+			.iter()
+			.map(|row| {
+				PredictInput(
+					row.iter()
+						.map(|(key, value)| {
+							let value = match value.parse::<f64>() {
+								Ok(value) => PredictInputValue::Number(value),
+								Err(_) => PredictInputValue::String(value.clone()),
+							};
+							(key.clone(), value)
+						})
+						.collect(),
+				)
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(
+			to_json(&predict(&model, &number_inputs, &options)),
+			expected
+		);
+		// Every column of this table is text.
+		let column_names = rows[0].keys().cloned().collect::<Vec<_>>();
+		let text_columns = column_names
+			.iter()
+			.map(|column_name| {
+				TableColumn::Text(TextTableColumn::new(
+					Some(column_name.clone()),
+					rows.iter().map(|row| row[column_name].clone()).collect(),
+				))
+			})
+			.collect::<Vec<_>>();
+		let text_table =
+			TableView::from_columns(text_columns.iter().map(TableColumn::view).collect());
+		assert_eq!(
+			to_json(&predict_table(&model, text_table, &options)),
+			expected
+		);
+		// Every column of this table whose values all look like numbers is a number column, including the enum columns with numeric variants.
+		let number_columns = column_names
+			.iter()
+			.map(|column_name| {
+				let values = rows
+					.iter()
+					.map(|row| row[column_name].parse::<f32>())
+					.collect::<Result<Vec<_>, _>>();
+				match values {
+					Ok(values) => TableColumn::Number(NumberTableColumn::new(
+						Some(column_name.clone()),
+						values,
+					)),
+					Err(_) => TableColumn::Text(TextTableColumn::new(
+						Some(column_name.clone()),
+						rows.iter().map(|row| row[column_name].clone()).collect(),
+					)),
+				}
+			})
+			.collect::<Vec<_>>();
+		let number_table =
+			TableView::from_columns(number_columns.iter().map(TableColumn::view).collect());
+		assert_eq!(
+			to_json(&predict_table(&model, number_table, &options)),
+			expected
+		);
+	}
+
+	fn positive_class_probabilities(model: &Model, rows: &[BTreeMap<String, String>]) -> Vec<f32> {
+		let inputs = rows
+			.iter()
+			.map(|row| {
+				PredictInput(
+					row.iter()
+						.map(|(key, value)| (key.clone(), PredictInputValue::String(value.clone())))
+						.collect(),
+				)
+			})
+			.collect::<Vec<_>>();
+		predict(model, &inputs, &PredictOptions::default())
+			.into_iter()
+			.map(|output| match output {
+				PredictOutput::BinaryClassification(output) if output.class_name == "Positive" => {
+					output.probability
+				}
+				PredictOutput::BinaryClassification(output) => 1.0 - output.probability,
+				_ => unreachable!(),
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_rewritten_models_predict_within_tolerance() {
+		let rows = heart_disease_rows();
+		let grid_items = [
+			r#"{ "model": "linear", "max_epochs": 10, "l1_regularization": 0.01 }"#,
+			r#"{ "model": "tree", "max_rounds": 20 }"#,
+		];
+		// Each rewrite is listed with the tolerance of its predictions and the revision it must be written with.
+		let rewrites = [
+			(
+				modelfox_model::RewriteOptions {
+					prune_features: true,
+					..Default::default()
+				},
+				1e-6,
+				0,
+			),
+			(
+				modelfox_model::RewriteOptions {
+					prune_features: true,
+					strip: true,
+					..Default::default()
+				},
+				1e-6,
+				1,
+			),
+			(
+				modelfox_model::RewriteOptions {
+					quantization: Some(modelfox_model::Quantization::F16),
+					..Default::default()
+				},
+				1e-2,
+				1,
+			),
+			(
+				modelfox_model::RewriteOptions {
+					quantization: Some(modelfox_model::Quantization::I8),
+					..Default::default()
+				},
+				5e-2,
+				1,
+			),
+		];
+		let revision = |bytes: &[u8]| u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+		for grid_item in grid_items {
+			let bytes = train_heart_disease_model(grid_item);
+			assert_eq!(revision(&bytes), 0);
+			let model = Model::from(modelfox_model::from_bytes(&bytes).unwrap());
+			let expected = positive_class_probabilities(&model, &rows);
+			for (options, tolerance, expected_revision) in rewrites.iter() {
+				let rewritten_bytes = modelfox_model::rewrite(&bytes, options).unwrap();
+				assert_eq!(revision(&rewritten_bytes), *expected_revision);
+				let rewritten_model =
+					Model::from(modelfox_model::from_bytes(&rewritten_bytes).unwrap());
+				let actual = positive_class_probabilities(&rewritten_model, &rows);
+				for (actual, expected) in actual.iter().zip(expected.iter()) {
+					assert!(
+						(actual - expected).abs() <= *tolerance,
+						"{:?}: {} != {}",
+						options,
+						actual,
+						expected,
+					);
+				}
+			}
+		}
+	}
+
+	/// Format `model` with `Debug`, leaving out the heap addresses that bit vectors print.
+	fn describe_model(model: &Model) -> String {
+		let description = format!("{:?}", model);
+		let mut parts = description.split("addr: 0x");
+		let mut result = parts.next().unwrap().to_owned();
+		for part in parts {
+			result.push_str(part.trim_start_matches(|c: char| c.is_ascii_hexdigit()));
+		}
+		result
+	}
+
+	/// Rewriting a model without any options must copy every field. The copy is laid out differently, so this checks that it has the same length, which catches dropped sections, and that it reads back into the same model, which catches dropped fields. Together the configs populate every optional section of the model, and the sections that training leaves empty for this dataset are filled in by hand.
+
+	#[test]
+	fn test_required_revision() {
+		// Train a model with `feature_group` for the `chest_pain` column, read as text, and return the revision it is written with.
+		let revision = |feature_group: &str| {
+			let config = format!(
+				r#"{{
+					"dataset": {{ "columns": [{{ "name": "chest_pain", "type": "text" }}] }},
+					"features": {{
+						"auto": {{ "enable": true, "exclude_columns": ["chest_pain"] }},
+						"include": [{}]
+					}},
+					"train": {{ "grid": [{{ "model": "linear", "max_epochs": 1 }}] }}
+				}}"#,
+				feature_group
+			);
+			let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../heart_disease.csv");
+			let mut trainer = Trainer::prepare(
+				modelfox_id::Id::generate(),
+				TrainingDataSource::File(path),
+				"diagnosis",
+				serde_json::from_str(&config).unwrap(),
+				None,
+				&mut |_| {},
+			)
+			.unwrap();
+			let train_grid_item_outputs = trainer
+				.train_grid(&KillChip::default(), &mut |_| {})
+				.unwrap();
+			let model = trainer
+				.test_and_assemble_model(train_grid_item_outputs, &mut |_| {})
+				.unwrap();
+			let bytes = model.to_bytes();
+			u32::from_le_bytes(bytes[8..12].try_into().unwrap())
+		};
+		// Word n-grams can be read by older versions of modelfox, but character n-grams cannot.
+		assert_eq!(
+			revision(
+				r#"{ "type": "bag_of_words", "source_column_name": "chest_pain", "ngram_types": ["unigram", "bigram"] }"#
+			),
+			0
+		);
+		assert_eq!(
+			revision(
+				r#"{ "type": "bag_of_words", "source_column_name": "chest_pain", "ngram_types": ["unigram", "character_trigram"] }"#
+			),
+			1
+		);
+		assert_eq!(
+			revision(r#"{ "type": "hashed_bag_of_words", "source_column_name": "chest_pain" }"#),
+			1
+		);
+		assert_eq!(
+			revision(r#"{ "type": "hashed_enum", "source_column_name": "chest_pain" }"#),
+			1
+		);
+		assert_eq!(
+			revision(
+				r#"{ "type": "interaction", "source_column_name_a": "age", "source_column_name_b": "gender" }"#
+			),
+			1
+		);
+		assert_eq!(
+			revision(r#"{ "type": "polynomial", "source_column_name": "age" }"#),
+			1
+		);
+		assert_eq!(
+			revision(r#"{ "type": "binned_number", "source_column_name": "cholesterol" }"#),
+			1
+		);
+		// Older versions of modelfox cannot read rankers at all.
+		let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../heart_disease.csv");
+		let config = r#"{
+			"dataset": { "group_column": "age" },
+			"train": { "grid": [{ "model": "tree", "max_rounds": 1 }] }
+		}"#;
+		let mut trainer = Trainer::prepare(
+			modelfox_id::Id::generate(),
+			TrainingDataSource::File(path),
+			"exercise_st_depression",
+			serde_json::from_str(config).unwrap(),
+			None,
+			&mut |_| {},
+		)
+		.unwrap();
+		let train_grid_item_outputs = trainer
+			.train_grid(&KillChip::default(), &mut |_| {})
+			.unwrap();
+		let model = trainer
+			.test_and_assemble_model(train_grid_item_outputs, &mut |_| {})
+			.unwrap();
+		let bytes = model.to_bytes();
+		assert_eq!(u32::from_le_bytes(bytes[8..12].try_into().unwrap()), 1);
+	}
+
+	#[test]
+	fn test_rewrite_without_options_copies_every_field() {
+		let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../heart_disease.csv");
+		let configs = [
+			(
+				"age",
+				r#"{
+					"features": {
+						"auto": {
+							"enable": true,
+							"impute": { "strategy": "median" },
+							"missing_indicator": true
+						}
+					},
+					"train": {
+						"grid": [{ "model": "linear", "max_epochs": 10 }],
+						"target_transform": "box_cox"
+					}
+				}"#,
+			),
+			(
+				"age",
+				r#"{
+					"features": {
+						"auto": {
+							"enable": true,
+							"impute": { "strategy": "constant", "value": 1 },
+							"missing_indicator": true
+						},
+						"selection": { "max_most_frequent_fraction": 0.6 }
+					},
+					"train": {
+						"grid": [{ "model": "tree", "max_rounds": 10 }],
+						"target_transform": "log1p"
+					}
+				}"#,
+			),
+			(
+				"diagnosis",
+				r#"{
+					"train": {
+						"grid": [{
+							"model": "linear",
+							"max_epochs": 10,
+							"l1_regularization": 0.01,
+							"optimizer": { "type": "adam" },
+							"learning_rate_schedule": { "type": "inverse_time_decay", "decay_rate": 0.1 }
+						}]
+					}
+				}"#,
+			),
+			(
+				"diagnosis",
+				r#"{
+					"features": {
+						"auto": { "enable": true },
+						"selection": { "max_most_frequent_fraction": 0.6 }
+					},
+					"train": { "grid": [{ "model": "tree", "max_rounds": 10 }] }
+				}"#,
+			),
+			(
+				"chest_pain",
+				r#"{
+					"features": {
+						"auto": { "enable": true },
+						"selection": { "max_most_frequent_fraction": 0.6 }
+					},
+					"train": {
+						"grid": [{
+							"model": "linear",
+							"max_epochs": 10,
+							"optimizer": { "type": "momentum" },
+							"learning_rate_schedule": { "type": "exponential_decay", "decay_rate": 0.9 }
+						}]
+					}
+				}"#,
+			),
+			(
+				"chest_pain",
+				r#"{
+					"train": {
+						"grid": [{
+							"model": "tree",
+							"max_rounds": 10,
+							"goss_options": { "top_rate": 0.3, "other_rate": 0.2 },
+							"dart_options": { "drop_rate": 0.1, "max_drop": 2, "skip_drop": 0.5 }
+						}]
+					}
+				}"#,
+			),
+			(
+				"diagnosis",
+				r#"{
+					"dataset": { "columns": [{ "name": "chest_pain", "type": "text" }] },
+					"features": {
+						"auto": { "enable": true, "exclude_columns": ["chest_pain"] },
+						"include": [{
+							"type": "bag_of_words",
+							"source_column_name": "chest_pain",
+							"tokenizer": {
+								"nfkc": true,
+								"fold_accents": true,
+								"stop_words": ["pain"],
+								"stemmer": "english",
+								"token_pattern": "[a-z]+"
+							},
+							"ngram_types": ["unigram", "character_trigram"]
+						}]
+					},
+					"train": { "grid": [{ "model": "linear", "max_epochs": 10 }] }
+				}"#,
+			),
+			(
+				"diagnosis",
+				r#"{
+					"dataset": { "columns": [{ "name": "chest_pain", "type": "text" }] },
+					"features": {
+						"auto": { "enable": true, "exclude_columns": ["chest_pain"] },
+						"include": [
+							{
+								"type": "hashed_bag_of_words",
+								"source_column_name": "chest_pain",
+								"strategy": "count",
+								"n_buckets": 8
+							},
+							{
+								"type": "hashed_enum",
+								"source_column_name": "chest_pain",
+								"n_buckets": 4,
+								"signed": false
+							}
+						]
+					},
+					"train": { "grid": [{ "model": "tree", "max_rounds": 10 }] }
+				}"#,
+			),
+			(
+				"diagnosis",
+				r#"{
+					"features": {
+						"auto": { "enable": true },
+						"include": [
+							{
+								"type": "interaction",
+								"source_column_name_a": "age",
+								"source_column_name_b": "gender"
+							},
+							{
+								"type": "interaction",
+								"source_column_name_a": "chest_pain",
+								"source_column_name_b": "gender"
+							},
+							{ "type": "polynomial", "source_column_name": "age", "degree": 3 },
+							{ "type": "binned_number", "source_column_name": "cholesterol", "n_bins": 4 }
+						]
+					},
+					"train": { "grid": [{ "model": "linear", "max_epochs": 10 }] }
+				}"#,
+			),
+			(
+				"exercise_st_depression",
+				r#"{
+					"dataset": { "group_column": "age" },
+					"train": {
+						"ndcg_k": 5,
+						"grid": [{ "model": "tree", "max_rounds": 10 }]
+					}
+				}"#,
+			),
+		];
+		let rows = heart_disease_rows();
+		let inputs = rows
+			.iter()
+			.map(|row| {
+				PredictInput(
+					row.iter()
+						.map(|(key, value)| (key.clone(), PredictInputValue::String(value.clone())))
+						.collect(),
+				)
+			})
+			.collect::<Vec<_>>();
+		let options = PredictOptions {
+			compute_feature_contributions: true,
+			..Default::default()
+		};
+		for (target_column_name, config) in configs {
+			let mut trainer = Trainer::prepare(
+				modelfox_id::Id::generate(),
+				TrainingDataSource::File(path.clone()),
+				target_column_name,
+				serde_json::from_str(config).unwrap(),
+				None,
+				&mut |_| {},
+			)
+			.unwrap();
+			let train_grid_item_outputs = trainer
+				.train_grid(&KillChip::default(), &mut |_| {})
+				.unwrap();
+			let mut model = trainer
+				.test_and_assemble_model(train_grid_item_outputs, &mut |_| {})
+				.unwrap();
+			// Fill in the sections that training leaves empty for this dataset.
+			model.parent_id = Some(modelfox_id::Id::generate());
+			let data_quality_issues = match &mut model.inner {
+				crate::model::ModelInner::Regressor(model) => &mut model.data_quality_issues,
+				crate::model::ModelInner::BinaryClassifier(model) => &mut model.data_quality_issues,
+				crate::model::ModelInner::MulticlassClassifier(model) => {
+					&mut model.data_quality_issues
+				}
+				crate::model::ModelInner::Ranker(model) => &mut model.data_quality_issues,
+			};
+			data_quality_issues.extend([
+				DataQualityIssue::IdColumn {
+					column_name: "age".to_owned(),
+					unique_fraction: 0.99,
+				},
+				DataQualityIssue::TargetLeakage {
+					column_name: "thallium_stress_test".to_owned(),
+					score: 0.98,
+				},
+				DataQualityIssue::DuplicateRows {
+					duplicate_count: 2,
+					duplicate_fraction: 0.03,
+				},
+				DataQualityIssue::DistributionMismatch {
+					auc_roc: 0.9,
+					column_name: Some("cholesterol".to_owned()),
+				},
+				DataQualityIssue::DistributionMismatch {
+					auc_roc: 0.8,
+					column_name: None,
+				},
+			]);
+			let bytes = model.to_bytes();
+			let rewritten_bytes =
+				modelfox_model::rewrite(&bytes, &modelfox_model::RewriteOptions::default())
+					.unwrap();
+			assert_eq!(rewritten_bytes.len(), bytes.len(), "{}", config);
+			assert_eq!(rewritten_bytes[..12], bytes[..12]);
+			let model = Model::from(modelfox_model::from_bytes(&bytes).unwrap());
+			let rewritten_model =
+				Model::from(modelfox_model::from_bytes(&rewritten_bytes).unwrap());
+			assert_eq!(describe_model(&rewritten_model), describe_model(&model));
+			assert_eq!(
+				serde_json::to_string(&predict(&rewritten_model, &inputs, &options)).unwrap(),
+				serde_json::to_string(&predict(&model, &inputs, &options)).unwrap(),
+			);
+		}
+	}
+}
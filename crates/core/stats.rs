@@ -1,13 +1,16 @@
-use fnv::{FnvBuildHasher, FnvHashSet};
+use fnv::{FnvBuildHasher, FnvHashMap, FnvHashSet};
 use indexmap::IndexMap;
-use itertools::Itertools;
 use modelfox_finite::Finite;
 use modelfox_progress_counter::ProgressCounter;
 use modelfox_table::prelude::*;
 use modelfox_text::{NGram, NGramType, Tokenizer};
 use modelfox_zip::zip;
 use num::ToPrimitive;
-use std::{cmp::Ordering, collections::BTreeMap, num::NonZeroU64};
+use std::{
+	cmp::Ordering,
+	collections::{BTreeMap, BTreeSet},
+	num::NonZeroU64,
+};
 
 /// This struct contains settings used to compute stats.
 #[derive(Clone, Debug)]
@@ -18,6 +21,12 @@ pub struct StatsSettings {
 	pub ngrams_max_count: usize,
 	/// This setting specifies which ngram types should be computed.
 	pub ngram_types: FnvHashSet<NGramType>,
+	/// These settings override the tokenizer and ngram types for individual text columns, keyed by column name.
+	pub text_column_settings: BTreeMap<String, TextColumnStatsSettings>,
+	/// These are the names of the text columns whose whole values are also counted, for feature groups that treat each value as a category.
+	pub text_columns_with_value_counts: BTreeSet<String>,
+	/// These are additional quantiles to compute for individual number columns, keyed by column name.
+	pub number_column_quantiles: BTreeMap<String, Vec<f32>>,
 }
 
 impl Default for StatsSettings {
@@ -28,13 +37,17 @@ impl Default for StatsSettings {
 			ngram_types: vec![NGramType::Unigram, NGramType::Bigram]
 				.into_iter()
 				.collect(),
+			text_column_settings: BTreeMap::new(),
+			text_columns_with_value_counts: BTreeSet::new(),
+			number_column_quantiles: BTreeMap::new(),
 		}
 	}
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TextColumnStatsSettings {
 	pub tokenizer: Tokenizer,
+	pub ngram_types: FnvHashSet<NGramType>,
 }
 
 /// This struct holds column stats.
@@ -104,6 +117,8 @@ pub struct TextColumnStats {
 	pub ngram_types: FnvHashSet<NGramType>,
 	/// These are stats collected on ngrams.
 	pub ngrams: IndexMap<NGram, TextColumnStatsNGramEntry, FnvBuildHasher>,
+	/// If the column is in `stats_settings.text_columns_with_value_counts`, this maps each value to the number of rows with that value.
+	pub value_counts: Option<FnvHashMap<String, usize>>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -173,6 +188,8 @@ pub struct NumberColumnStatsOutput {
 	pub p50: f32,
 	/// This is the p75, or 75th-percentile value in the column.
 	pub p75: f32,
+	/// These are the values of the quantiles requested for this column in [`number_column_quantiles`](StatsSettings#number_column_quantiles), in the same order.
+	pub quantiles: Vec<f32>,
 }
 
 /// This struct contains stats for enum columns.
@@ -205,6 +222,8 @@ pub struct TextColumnStatsOutput {
 	pub ngrams_count: usize,
 	/// This contains stats for up to `stats_settings.ngrams_max_count` ngrams with the highest `entry.row_count`s.
 	pub top_ngrams: IndexMap<NGram, TextColumnStatsOutputTopNGramsEntry, FnvBuildHasher>,
+	/// If the column is in `stats_settings.text_columns_with_value_counts`, this contains up to `stats_settings.ngrams_max_count` non-empty values with the highest counts, along with their counts, in descending order of count.
+	pub top_values: Vec<(String, usize)>,
 }
 
 /// This struct contains stats for individual ngrams.
@@ -365,7 +384,15 @@ impl NumberColumnStats {
 		let min = self.histogram.iter().next().unwrap().0.get();
 		let max = self.histogram.iter().next_back().unwrap().0.get();
 		let total_values_count = self.valid_count.to_f32().unwrap();
-		let quantiles: Vec<f32> = vec![0.25, 0.50, 0.75];
+		let extra_quantiles = settings
+			.number_column_quantiles
+			.get(&self.column_name)
+			.cloned()
+			.unwrap_or_default();
+		let quantiles: Vec<f32> = vec![0.25, 0.50, 0.75]
+			.into_iter()
+			.chain(extra_quantiles)
+			.collect();
 		// Find the index of each quantile given the total number of values in the dataset.
 		let quantile_indexes: Vec<usize> = quantiles
 			.iter()
@@ -420,6 +447,7 @@ impl NumberColumnStats {
 		let p25 = quantiles[0];
 		let p50 = quantiles[1];
 		let p75 = quantiles[2];
+		let quantiles = quantiles[3..].to_vec();
 		let mean = mean.to_f32().unwrap();
 		let variance = modelfox_metrics::m2_to_variance(
 			m2,
@@ -439,6 +467,7 @@ impl NumberColumnStats {
 			p25,
 			p50,
 			p75,
+			quantiles,
 		}
 	}
 }
@@ -516,39 +545,34 @@ impl TextColumnStats {
 		settings: &StatsSettings,
 		progress: impl Fn(u64),
 	) -> TextColumnStats {
-		let tokenizer = Tokenizer::default();
+		let column_name = column.name().unwrap().to_owned();
+		let (tokenizer, ngram_types) = match settings.text_column_settings.get(&column_name) {
+			Some(text_column_settings) => (
+				text_column_settings.tokenizer.clone(),
+				text_column_settings.ngram_types.clone(),
+			),
+			None => (Tokenizer::default(), settings.ngram_types.to_owned()),
+		};
+		let mut value_counts = if settings
+			.text_columns_with_value_counts
+			.contains(&column_name)
+		{
+			Some(FnvHashMap::default())
+		} else {
+			None
+		};
 		let mut stats = TextColumnStats {
-			column_name: column.name().unwrap().to_owned(),
+			column_name,
 			row_count: column.len(),
 			tokenizer,
 			ngrams: IndexMap::default(),
-			ngram_types: settings.ngram_types.to_owned(),
+			ngram_types,
+			value_counts: None,
 		};
 		let mut ngrams_for_row = FnvHashSet::default();
 		for value in column.iter() {
 			ngrams_for_row.clear();
-			let unigram_iter = if stats.ngram_types.contains(&NGramType::Unigram) {
-				Some(
-					stats
-						.tokenizer
-						.tokenize(value)
-						.map(modelfox_text::NGramRef::Unigram),
-				)
-			} else {
-				None
-			};
-			let bigram_iter =
-				if stats.ngram_types.contains(&NGramType::Bigram) {
-					Some(stats.tokenizer.tokenize(value).tuple_windows().map(
-						|(token_a, token_b)| modelfox_text::NGramRef::Bigram(token_a, token_b),
-					))
-				} else {
-					None
-				};
-			let ngram_iter = unigram_iter
-				.into_iter()
-				.flatten()
-				.chain(bigram_iter.into_iter().flatten());
+			let ngram_iter = stats.tokenizer.ngrams(value, stats.ngram_types.iter());
 			for ngram in ngram_iter {
 				if let Some(entry) = stats.ngrams.get_mut(&ngram) {
 					entry.occurrence_count += 1;
@@ -565,8 +589,14 @@ impl TextColumnStats {
 			for ngram in ngrams_for_row.iter() {
 				stats.ngrams.get_mut(ngram).unwrap().row_count += 1;
 			}
+			if let Some(value_counts) = value_counts.as_mut() {
+				if !value.is_empty() {
+					*value_counts.entry(value.to_owned()).or_insert(0) += 1;
+				}
+			}
 			progress(1);
 		}
+		stats.value_counts = value_counts;
 		stats
 	}
 
@@ -580,6 +610,13 @@ impl TextColumnStats {
 				self.ngrams.insert(other_ngram, other_entry);
 			}
 		}
+		if let (Some(value_counts), Some(other_value_counts)) =
+			(self.value_counts.as_mut(), other.value_counts)
+		{
+			for (value, count) in other_value_counts {
+				*value_counts.entry(value).or_insert(0) += count;
+			}
+		}
 		self
 	}
 
@@ -604,13 +641,22 @@ impl TextColumnStats {
 				(ngram, entry)
 			})
 			.collect();
+		let mut top_values = self
+			.value_counts
+			.map(|value_counts| value_counts.into_iter().collect::<Vec<_>>())
+			.unwrap_or_default();
+		top_values.sort_by(|(value_a, count_a), (value_b, count_b)| {
+			count_b.cmp(count_a).then_with(|| value_a.cmp(value_b))
+		});
+		top_values.truncate(settings.ngrams_max_count);
 		TextColumnStatsOutput {
 			column_name: self.column_name,
 			tokenizer: self.tokenizer,
 			row_count: self.row_count.to_u64().unwrap(),
-			ngram_types: settings.ngram_types.clone(),
+			ngram_types: self.ngram_types,
 			ngrams_count,
 			top_ngrams: ngrams,
+			top_values,
 		}
 	}
 }
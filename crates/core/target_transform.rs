@@ -0,0 +1,225 @@
+/*!
+This module implements the transforms that can be applied to the target column of a regressor before training. The regressor is trained to predict the transformed target, and its predictions are transformed back to the original scale.
+*/
+
+use crate::config;
+use anyhow::{bail, Result};
+use num::ToPrimitive;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TargetTransform {
+	/// `y' = ln(1 + y)`.
+	Log1p,
+	/// `y' = (y ^ lambda - 1) / lambda`, or `y' = ln(y)` if `lambda` is 0.
+	BoxCox { lambda: f32 },
+	/// `y' = (y - mean) / std`.
+	Standardize { mean: f32, std: f32 },
+}
+
+/// The lambda for the Box-Cox transform is chosen from this range.
+const BOX_COX_LAMBDA_RANGE: (f64, f64) = (-2.0, 2.0);
+const BOX_COX_LAMBDA_TOLERANCE: f64 = 1e-4;
+
+impl TargetTransform {
+	/// Fit the transform in `config` to the target `values` of the train dataset.
+	pub fn fit(config: config::TargetTransform, values: &[f32]) -> Result<TargetTransform> {
+		let values = values
+			.iter()
+			.cloned()
+			.filter(|value| value.is_finite())
+			.collect::<Vec<_>>();
+		if values.is_empty() {
+			bail!("The target column must contain at least one valid value to fit the target transform.");
+		}
+		let min = values.iter().cloned().fold(f32::INFINITY, f32::min);
+		let target_transform = match config {
+			config::TargetTransform::Log1p => {
+				if min <= -1.0 {
+					bail!("The log1p target transform requires every value of the target column to be greater than -1, but the minimum value is {}.", min);
+				}
+				TargetTransform::Log1p
+			}
+			config::TargetTransform::BoxCox => {
+				if min <= 0.0 {
+					bail!("The box_cox target transform requires every value of the target column to be positive, but the minimum value is {}.", min);
+				}
+				TargetTransform::BoxCox {
+					lambda: fit_box_cox_lambda(&values),
+				}
+			}
+			config::TargetTransform::Standardize => {
+				let mean_variance = modelfox_metrics::MeanVariance::compute(values.iter().cloned());
+				TargetTransform::Standardize {
+					mean: mean_variance.mean,
+					std: mean_variance.variance.sqrt(),
+				}
+			}
+		};
+		Ok(target_transform)
+	}
+
+	/// Transform a value of the target column.
+	pub fn transform(&self, value: f32) -> f32 {
+		match *self {
+			TargetTransform::Log1p => value.ln_1p(),
+			TargetTransform::BoxCox { lambda } => box_cox(value.to_f64().unwrap(), lambda.into())
+				.to_f32()
+				.unwrap(),
+			TargetTransform::Standardize { mean, std } => {
+				if std == 0.0 {
+					value - mean
+				} else {
+					(value - mean) / std
+				}
+			}
+		}
+	}
+
+	/// Transform a prediction back to the original scale of the target column.
+	pub fn inverse_transform(&self, value: f32) -> f32 {
+		match *self {
+			TargetTransform::Log1p => value.exp_m1(),
+			TargetTransform::BoxCox { lambda } => {
+				let base = lambda * value + 1.0;
+				if lambda == 0.0 {
+					value.exp()
+				} else if lambda > 0.0 {
+					// Predictions below the range of the transform are clamped to the smallest value in the original scale.
+					base.max(0.0).powf(1.0 / lambda)
+				} else {
+					// Predictions at or above the supremum of the transform, -1 / lambda, are clamped to the largest value in the original scale.
+					base.max(f32::MIN_POSITIVE).powf(1.0 / lambda).min(f32::MAX)
+				}
+			}
+			TargetTransform::Standardize { mean, std } => {
+				if std == 0.0 {
+					value + mean
+				} else {
+					value * std + mean
+				}
+			}
+		}
+	}
+}
+
+fn box_cox(value: f64, lambda: f64) -> f64 {
+	if lambda == 0.0 {
+		value.ln()
+	} else {
+		(value.powf(lambda) - 1.0) / lambda
+	}
+}
+
+/// Choose the lambda that maximizes the log likelihood of the Box-Cox transformed `values` under a normal distribution, using a golden section search.
+fn fit_box_cox_lambda(values: &[f32]) -> f32 {
+	let values = values
+		.iter()
+		.map(|value| value.to_f64().unwrap())
+		.collect::<Vec<_>>();
+	let n = values.len().to_f64().unwrap();
+	let sum_ln = values.iter().map(|value| value.ln()).sum::<f64>();
+	let log_likelihood = |lambda: f64| {
+		let transformed_values = values
+			.iter()
+			.map(|value| box_cox(*value, lambda))
+			.collect::<Vec<_>>();
+		let mean = transformed_values.iter().sum::<f64>() / n;
+		let variance = transformed_values
+			.iter()
+			.map(|value| (value - mean).powi(2))
+			.sum::<f64>()
+			/ n;
+		if variance <= 0.0 {
+			return f64::NEG_INFINITY;
+		}
+		-0.5 * n * variance.ln() + (lambda - 1.0) * sum_ln
+	};
+	let inverse_golden_ratio = (5.0f64.sqrt() - 1.0) / 2.0;
+	let (mut a, mut b) = BOX_COX_LAMBDA_RANGE;
+	let mut c = b - inverse_golden_ratio * (b - a);
+	let mut d = a + inverse_golden_ratio * (b - a);
+	while (b - a).abs() > BOX_COX_LAMBDA_TOLERANCE {
+		if log_likelihood(c) > log_likelihood(d) {
+			b = d;
+		} else {
+			a = c;
+		}
+		c = b - inverse_golden_ratio * (b - a);
+		d = a + inverse_golden_ratio * (b - a);
+	}
+	((a + b) / 2.0).to_f32().unwrap()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn assert_round_trip(target_transform: TargetTransform, values: &[f32]) {
+		for value in values {
+			let actual = target_transform.inverse_transform(target_transform.transform(*value));
+			assert!(
+				(actual - value).abs() <= 1e-4 * value.abs().max(1.0),
+				"{:?}: expected {} but got {}",
+				target_transform,
+				value,
+				actual,
+			);
+		}
+	}
+
+	#[test]
+	fn test_log1p_round_trip() {
+		assert_round_trip(TargetTransform::Log1p, &[-0.5, 0.0, 1.0, 10.0, 1000.0]);
+	}
+
+	#[test]
+	fn test_standardize_round_trip() {
+		assert_round_trip(
+			TargetTransform::Standardize {
+				mean: 3.0,
+				std: 2.0,
+			},
+			&[-4.0, 0.0, 3.0, 100.0],
+		);
+		assert_round_trip(
+			TargetTransform::Standardize {
+				mean: 3.0,
+				std: 0.0,
+			},
+			&[-4.0, 0.0, 3.0, 100.0],
+		);
+	}
+
+	#[test]
+	fn test_box_cox_round_trip() {
+		for lambda in [-1.5, -0.5, 0.0, 0.5, 2.0] {
+			assert_round_trip(TargetTransform::BoxCox { lambda }, &[0.1, 1.0, 2.5, 40.0]);
+		}
+	}
+
+	#[test]
+	fn test_box_cox_inverse_transform_out_of_range() {
+		// With a positive lambda, predictions below -1 / lambda clamp to 0.
+		let target_transform = TargetTransform::BoxCox { lambda: 0.5 };
+		assert_eq!(target_transform.inverse_transform(-10.0), 0.0);
+		// With a negative lambda, predictions at or above -1 / lambda clamp to the largest finite value.
+		let target_transform = TargetTransform::BoxCox { lambda: -0.5 };
+		assert_eq!(target_transform.inverse_transform(2.0), f32::MAX);
+		assert_eq!(target_transform.inverse_transform(10.0), f32::MAX);
+		assert!(target_transform.inverse_transform(-10.0).is_finite());
+	}
+
+	#[test]
+	fn test_fit() {
+		let values = [1.0, 2.0, 3.0, 4.0];
+		match TargetTransform::fit(config::TargetTransform::Standardize, &values).unwrap() {
+			TargetTransform::Standardize { mean, std } => {
+				assert!((mean - 2.5).abs() < 1e-6);
+				assert!((std - 1.25f32.sqrt()).abs() < 1e-6);
+			}
+			_ => unreachable!(),
+		}
+		assert!(TargetTransform::fit(config::TargetTransform::Log1p, &[-1.0, 2.0]).is_err());
+		assert!(TargetTransform::fit(config::TargetTransform::BoxCox, &[0.0, 2.0]).is_err());
+	}
+}
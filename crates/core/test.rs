@@ -1,4 +1,4 @@
-use crate::progress::ModelTestProgressEvent;
+use crate::{progress::ModelTestProgressEvent, target_transform::TargetTransform};
 use modelfox_progress_counter::ProgressCounter;
 use modelfox_table::prelude::*;
 use modelfox_zip::pzip;
@@ -9,6 +9,7 @@ pub fn test_linear_regressor(
 	table_test: &TableView,
 	target_column_index: usize,
 	feature_groups: &[modelfox_features::FeatureGroup],
+	target_transform: Option<TargetTransform>,
 	model: &modelfox_linear::Regressor,
 	handle_progress_event: &mut dyn FnMut(ModelTestProgressEvent),
 ) -> modelfox_metrics::RegressionMetricsOutput {
@@ -38,6 +39,11 @@ pub fn test_linear_regressor(
 		|mut test_metrics, (features, labels)| {
 			let mut predictions = Array::zeros(features.nrows());
 			model.predict(features, predictions.view_mut());
+			// Metrics are computed in the original scale of the target column.
+			if let Some(target_transform) = target_transform {
+				predictions
+					.mapv_inplace(|prediction| target_transform.inverse_transform(prediction));
+			}
 			test_metrics.update(modelfox_metrics::RegressionMetricsInput {
 				predictions: predictions.as_slice().unwrap(),
 				labels: labels.as_slice().unwrap(),
@@ -62,6 +68,7 @@ pub fn test_tree_regressor(
 	table_test: &TableView,
 	target_column_index: usize,
 	feature_groups: &[modelfox_features::FeatureGroup],
+	target_transform: Option<TargetTransform>,
 	model: &modelfox_tree::Regressor,
 	handle_progress_event: &mut dyn FnMut(ModelTestProgressEvent),
 ) -> modelfox_metrics::RegressionMetricsOutput {
@@ -90,6 +97,11 @@ pub fn test_tree_regressor(
 		|mut test_metrics, (features, labels)| {
 			let mut predictions = Array::zeros(features.nrows());
 			model.predict(features, predictions.view_mut());
+			// Metrics are computed in the original scale of the target column.
+			if let Some(target_transform) = target_transform {
+				predictions
+					.mapv_inplace(|prediction| target_transform.inverse_transform(prediction));
+			}
 			test_metrics.update(modelfox_metrics::RegressionMetricsInput {
 				predictions: predictions.as_slice().unwrap(),
 				labels: labels.as_slice().unwrap(),
@@ -339,3 +351,50 @@ pub fn test_tree_multiclass_classifier(
 	handle_progress_event(ModelTestProgressEvent::TestDone);
 	test_metrics
 }
+
+pub fn test_tree_ranker(
+	table_test: &TableView,
+	target_column_index: usize,
+	group_column_index: usize,
+	ndcg_k: Option<usize>,
+	feature_groups: &[modelfox_features::FeatureGroup],
+	model: &modelfox_tree::Ranker,
+	handle_progress_event: &mut dyn FnMut(ModelTestProgressEvent),
+) -> modelfox_metrics::RankingMetricsOutput {
+	let n_features = feature_groups.iter().map(|g| g.n_features()).sum::<usize>();
+	let progress_total = n_features as u64 * table_test.nrows() as u64;
+	let progress_counter = ProgressCounter::new(progress_total);
+	handle_progress_event(ModelTestProgressEvent::ComputeFeatures(
+		progress_counter.clone(),
+	));
+	let features =
+		modelfox_features::compute_features_array_value(table_test, feature_groups, &|| {
+			progress_counter.inc(1)
+		});
+	let progress_total = table_test.nrows() as u64;
+	let progress_counter = ProgressCounter::new(progress_total);
+	handle_progress_event(ModelTestProgressEvent::Test(progress_counter.clone()));
+	let labels = table_test.columns().get(target_column_index).unwrap();
+	let labels = labels.as_number().unwrap();
+	let groups = crate::group::compute_groups(table_test.columns()[group_column_index].clone());
+	// A query group may span several batches, so make all the predictions before computing the metrics.
+	let n_examples_per_batch = 256;
+	let mut predictions = Array::zeros(features.nrows());
+	pzip!(
+		features.axis_chunks_iter(Axis(0), n_examples_per_batch),
+		predictions.axis_chunks_iter_mut(Axis(0), n_examples_per_batch),
+	)
+	.for_each(|(features, predictions)| {
+		model.predict(features, predictions);
+		progress_counter.inc(features.nrows() as u64);
+	});
+	let mut test_metrics = modelfox_metrics::RankingMetrics::new(ndcg_k);
+	test_metrics.update(modelfox_metrics::RankingMetricsInput {
+		predictions: predictions.as_slice().unwrap(),
+		labels: labels.as_slice(),
+		groups: &groups,
+	});
+	let test_metrics = test_metrics.finalize();
+	handle_progress_event(ModelTestProgressEvent::TestDone);
+	test_metrics
+}
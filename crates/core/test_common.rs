@@ -0,0 +1,27 @@
+//! This module contains functionality used to test the steps of training on small tables.
+
+use crate::stats::{ColumnStatsOutput, Stats, StatsSettings};
+use modelfox_table::prelude::*;
+
+/// Make a number column named `name` with `values`.
+pub fn number_column(name: &str, values: impl IntoIterator<Item = f32>) -> TableColumn {
+	TableColumn::Number(NumberTableColumn::new(
+		Some(name.to_owned()),
+		values.into_iter().collect(),
+	))
+}
+
+/// View `columns` as a table.
+pub fn view(columns: &[TableColumn]) -> TableView {
+	TableView::from_columns(columns.iter().map(TableColumn::view).collect())
+}
+
+/// Compute the column stats for `table` without the target column, the way training does before it chooses features.
+pub fn column_stats(table: &TableView, target_column_index: usize) -> Vec<ColumnStatsOutput> {
+	let settings = StatsSettings::default();
+	let mut column_stats = Stats::compute(table, &settings, &mut |_| {})
+		.finalize(&settings)
+		.0;
+	column_stats.remove(target_column_index);
+	column_stats
+}
@@ -1,35 +1,47 @@
 use crate::{
+	checks::{self, DataQualityIssue},
 	config::{self, Config},
-	grid,
-	heuristics::{MIN_COMPARISON_ROWS, MIN_TEST_ROWS, MIN_TRAIN_ROWS},
+	explain,
+	feature_selection::{self, DroppedColumn},
+	grid, group,
+	heuristics::{
+		BINNED_NUMBER_FEATURE_GROUP_DEFAULT_N_BINS, MIN_COMPARISON_ROWS, MIN_TEST_ROWS,
+		MIN_TRAIN_ROWS,
+	},
 	model::{
 		BinaryClassificationComparisonMetric, BinaryClassificationModel, BinaryClassifier,
 		ComparisonMetric, LinearBinaryClassificationModel, LinearMulticlassClassificationModel,
 		LinearRegressionModel, Metrics, Model, ModelInner,
 		MulticlassClassificationComparisonMetric, MulticlassClassificationModel,
-		MulticlassClassifier, RegressionComparisonMetric, RegressionModel, Regressor, Task,
-		TreeBinaryClassificationModel, TreeMulticlassClassificationModel, TreeRegressionModel,
+		MulticlassClassifier, Ranker, RankingComparisonMetric, RankingModel,
+		RegressionComparisonMetric, RegressionModel, Regressor, Task,
+		TreeBinaryClassificationModel, TreeMulticlassClassificationModel, TreeRankingModel,
+		TreeRegressionModel,
 	},
 	progress::{
 		LoadProgressEvent, ModelTestProgressEvent, ModelTrainProgressEvent, ProgressEvent,
 		StatsProgressEvent, TrainGridItemProgressEvent, TrainProgressEvent,
 	},
-	stats::{ColumnStatsOutput, Stats, StatsSettings},
+	stats::{ColumnStatsOutput, Stats, StatsSettings, TextColumnStatsSettings},
+	target_transform::TargetTransform,
 	test,
 };
 use anyhow::{anyhow, bail, Result};
+use fnv::FnvHashSet;
 use modelfox_id::Id;
 use modelfox_kill_chip::KillChip;
 use modelfox_progress_counter::ProgressCounter;
-use modelfox_table::prelude::*;
+use modelfox_table::{prelude::*, TableChunks};
+use modelfox_text::{NGram, NGramType};
+use modelfox_zip::zip;
 use ndarray::prelude::*;
 use num::ToPrimitive;
-use rand::{seq::SliceRandom, SeedableRng};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
 use rand_xoshiro::Xoshiro256Plus;
 use std::{
 	collections::BTreeMap,
 	io::Read,
-	path::Path,
+	path::{Path, PathBuf},
 	sync::Arc,
 	time::{Duration, Instant},
 	unreachable,
@@ -42,6 +54,13 @@ pub enum TrainingDataSource {
 		train: std::path::PathBuf,
 		test: std::path::PathBuf,
 	},
+	/// Train on a table that is already in memory, such as one built from a data frame.
+	Table(Table),
+	/// Train and test on tables that are already in memory. The test table must have the same columns as the train table.
+	TrainAndTestTables {
+		train: Table,
+		test: Table,
+	},
 }
 
 pub struct Trainer {
@@ -62,6 +81,12 @@ pub struct Trainer {
 	dataset: Arc<Dataset>,
 	grid: Vec<grid::GridItem>,
 	task: Task,
+	group_column_index: Option<usize>,
+	ndcg_k: Option<usize>,
+	target_transform: Option<TargetTransform>,
+	dropped_columns: Vec<DroppedColumn>,
+	data_quality_issues: Vec<DataQualityIssue>,
+	init_model: Option<InitModel>,
 }
 
 impl Trainer {
@@ -69,14 +94,49 @@ impl Trainer {
 		id: Id,
 		input: TrainingDataSource,
 		target_column_name: &str,
-		config_path: Option<&Path>,
+		mut config: Config,
+		init_model_path: Option<&Path>,
 		handle_progress_event: &mut dyn FnMut(ProgressEvent),
 	) -> Result<Trainer> {
-		// Load the config from the config file, if provided.
-		let config = load_config(config_path)?;
+		let stats_settings = compute_stats_settings(&config)?;
+
+		// Load the model to continue training from, if provided.
+		let init_model = init_model_path.map(InitModel::from_path).transpose()?;
+		if init_model.is_some() && config.dataset.out_of_core.is_some() {
+			bail!("Continuing training from an init model is not supported with out of core training.");
+		}
+		if config.dataset.group_column.is_some() {
+			if config.dataset.out_of_core.is_some() {
+				bail!("Training a ranker is not supported with out of core training.");
+			}
+			if init_model.is_some() {
+				bail!("Continuing training from an init model is not supported for rankers.");
+			}
+		}
 
 		// Load the train and test tables from the csv file(s).
 		let dataset = match input {
+			_ if config.dataset.out_of_core.is_some() => {
+				let (file_path_train, file_path_test) = match input {
+					TrainingDataSource::Stdin => {
+						bail!("Out of core training requires a train file and cannot read from stdin.")
+					}
+					TrainingDataSource::File(file_path) => (file_path, None),
+					TrainingDataSource::TrainAndTest { train, test } => (train, Some(test)),
+					TrainingDataSource::Table(_)
+					| TrainingDataSource::TrainAndTestTables { .. } => {
+						bail!("Out of core training requires a train file and cannot train on a table in memory.")
+					}
+				};
+				Dataset::OutOfCore(load_dataset_out_of_core(
+					file_path_train,
+					file_path_test.as_deref(),
+					&config,
+					target_column_name,
+					&stats_settings,
+					handle_progress_event,
+				)?)
+			}
 			TrainingDataSource::Stdin => Dataset::Train(load_and_shuffle_dataset_stdin(
 				&config,
 				target_column_name,
@@ -97,16 +157,36 @@ impl Trainer {
 					handle_progress_event,
 				)?)
 			}
+			TrainingDataSource::Table(table) => Dataset::Train(shuffle_dataset_table(
+				table,
+				&config,
+				target_column_name,
+				handle_progress_event,
+			)?),
+			TrainingDataSource::TrainAndTestTables { train, test } => {
+				Dataset::TrainAndTest(shuffle_dataset_tables_train_and_test(
+					train,
+					test,
+					&config,
+					target_column_name,
+					handle_progress_event,
+				)?)
+			}
 		};
 		let (table_train, table_comparison, table_test) = dataset.split();
+		// When training out of core, `table_train` is only a sample of the train dataset.
+		let train_row_count = match &dataset {
+			Dataset::OutOfCore(dataset) => dataset.train_row_count,
+			_ => table_train.nrows(),
+		};
 
 		// Do not allow training if any dataset has no rows, or emit warnings if any dataset is too small.
-		if table_train.nrows() == 0 {
+		if train_row_count == 0 {
 			bail!("The train dataset must contain at least one row.");
-		} else if table_train.nrows() < MIN_TRAIN_ROWS {
+		} else if train_row_count < MIN_TRAIN_ROWS {
 			handle_progress_event(ProgressEvent::Warning(format!(
 				"The train dataset is very small. It has only {} row(s).",
-				table_train.nrows(),
+				train_row_count,
 			)));
 		}
 		if table_comparison.nrows() == 0 {
@@ -134,17 +214,18 @@ impl Trainer {
 			.collect();
 
 		// Get the row counts.
-		let train_row_count = table_train.nrows();
 		let test_row_count = table_test.nrows();
 		let overall_row_count = train_row_count + test_row_count + table_comparison.nrows();
 
-		// Compute stats.
-		let stats_settings = StatsSettings::default();
-		let train_column_stats = Stats::compute(&table_train, &stats_settings, &mut |progress| {
-			handle_progress_event(ProgressEvent::Stats(StatsProgressEvent::ComputeTrainStats(
-				progress,
-			)));
-		});
+		// Compute stats. The train stats for out of core training were computed one chunk at a time while loading.
+		let train_column_stats = match &dataset {
+			Dataset::OutOfCore(dataset) => dataset.train_stats.clone(),
+			_ => Stats::compute(&table_train, &stats_settings, &mut |progress| {
+				handle_progress_event(ProgressEvent::Stats(StatsProgressEvent::ComputeTrainStats(
+					progress,
+				)));
+			}),
+		};
 		handle_progress_event(ProgressEvent::Stats(
 			StatsProgressEvent::ComputeTrainStatsDone,
 		));
@@ -175,20 +256,71 @@ impl Trainer {
 				)
 			})?;
 
+		// Find the group column, if there is one.
+		let group_column_index = config
+			.dataset
+			.group_column
+			.as_ref()
+			.map(|group_column_name| {
+				let group_column_index = column_names
+					.iter()
+					.position(|column_name| column_name == group_column_name)
+					.ok_or_else(|| {
+						anyhow!(
+							"did not find group column \"{}\" among column names \"{}\"",
+							group_column_name,
+							column_names.join(", ")
+						)
+					})?;
+				if group_column_index == target_column_index {
+					bail!("The group column cannot be the target column.");
+				}
+				Ok(group_column_index)
+			})
+			.transpose()?;
+
 		// Pull out the target column from the column stats.
 		let train_target_column_stats = train_column_stats.remove(target_column_index);
 		let test_target_column_stats = test_column_stats.remove(target_column_index);
 		let overall_target_column_stats = overall_column_stats.remove(target_column_index);
 
 		// Determine the task.
-		let task = match &overall_target_column_stats {
-			ColumnStatsOutput::Number(_) => Task::Regression,
-			ColumnStatsOutput::Enum(target_column) => match target_column.unique_count {
+		let task = match (&overall_target_column_stats, group_column_index) {
+			(ColumnStatsOutput::Number(_), Some(_)) => Task::Ranking,
+			(_, Some(_)) => {
+				bail!("The target column of a ranker must be a number column holding the relevance of each row.")
+			}
+			(ColumnStatsOutput::Number(_), None) => Task::Regression,
+			(ColumnStatsOutput::Enum(target_column), None) => match target_column.unique_count {
 				2 => Task::BinaryClassification,
 				_ => Task::MulticlassClassification,
 			},
 			_ => bail!("invalid target column type"),
 		};
+		let ndcg_k = config.train.ndcg_k.map(|ndcg_k| ndcg_k.get());
+
+		// The group column identifies the query group of each row, so it is not used as a feature.
+		if let Some(group_column_name) = &config.dataset.group_column {
+			let is_group_column_included = config
+				.features
+				.include
+				.iter()
+				.flatten()
+				.flat_map(|feature_group| feature_group.source_column_names())
+				.any(|column_name| column_name == group_column_name);
+			if is_group_column_included {
+				bail!(
+					"The group column \"{}\" cannot be used as the source column of a feature group.",
+					group_column_name
+				);
+			}
+			config
+				.features
+				.auto
+				.exclude_columns
+				.get_or_insert_with(Vec::new)
+				.push(group_column_name.clone());
+		}
 
 		// Compute the baseline metrics.
 		let progress_counter = ProgressCounter::new(test_row_count as u64);
@@ -199,18 +331,146 @@ impl Trainer {
 			task,
 			&table_test,
 			target_column_index,
+			group_column_index,
+			ndcg_k,
 			&train_target_column_stats,
 			&test_target_column_stats,
 			&|| progress_counter.inc(1),
 		);
 		handle_progress_event(ProgressEvent::ComputeBaselineMetricsDone);
 
+		// Check the datasets for target leakage and data quality issues.
+		let data_quality_issues = if config.checks.enable {
+			checks::check_datasets(
+				&table_train,
+				&table_comparison,
+				&table_test,
+				target_column_index,
+				group_column_index,
+				task,
+				&train_column_stats,
+			)
+		} else {
+			Vec::new()
+		};
+		for issue in data_quality_issues.iter() {
+			handle_progress_event(ProgressEvent::Warning(issue.to_string()));
+		}
+		if config.checks.strict && !data_quality_issues.is_empty() {
+			bail!(
+				"Training failed because the checks found {} issue(s):\n{}",
+				data_quality_issues.len(),
+				data_quality_issues
+					.iter()
+					.map(|issue| issue.to_string())
+					.collect::<Vec<_>>()
+					.join("\n"),
+			);
+		}
+
+		// Make sure the init model, if provided, predicts the same target column.
+		if let Some(init_model) = &init_model {
+			let target_column_variants = table_train.columns()[target_column_index]
+				.as_enum()
+				.map(|column| column.variants().to_owned());
+			init_model.check_target_column(
+				target_column_name,
+				&task,
+				target_column_variants.as_deref(),
+			)?;
+		}
+
+		// Fit the target transform. Training continues in the init model's target space, if there is one.
+		let target_transform = match (&init_model, config.train.target_transform) {
+			(Some(_), Some(_)) => {
+				bail!("A target transform cannot be configured when continuing training from an init model. The init model's target transform is used instead.")
+			}
+			(Some(init_model), None) => init_model.target_transform,
+			(None, Some(_)) if !matches!(task, Task::Regression) => {
+				bail!("A target transform can only be used to train a regressor.")
+			}
+			(None, Some(target_transform)) => {
+				let target_values = match &dataset {
+					Dataset::OutOfCore(dataset) => dataset.table_train_labels.columns()[0]
+						.as_number()
+						.unwrap()
+						.view()
+						.as_slice()
+						.to_owned(),
+					_ => table_train.columns()[target_column_index]
+						.as_number()
+						.unwrap()
+						.as_slice()
+						.to_owned(),
+				};
+				Some(TargetTransform::fit(target_transform, &target_values)?)
+			}
+			(None, None) => None,
+		};
+
 		// Choose the comparison metric.
 		let comparison_metric = choose_comparison_metric(&config, &task)?;
 
-		// Create the hyperparameter grid.
-		let grid =
-			compute_hyperparameter_grid(&config, &task, target_column_index, &train_column_stats);
+		// Select features. The dropped columns are excluded from automatic feature engineering when the grid is computed.
+		let dropped_columns = match (&init_model, config.features.selection.as_ref()) {
+			(Some(_), Some(_)) => {
+				bail!("Feature selection cannot be configured when continuing training from an init model. The init model's feature groups are used instead.")
+			}
+			(None, Some(selection)) => feature_selection::select_columns(
+				selection,
+				&config,
+				&table_train,
+				target_column_index,
+				task,
+				&train_column_stats,
+			),
+			(_, None) => Vec::new(),
+		};
+		if !dropped_columns.is_empty() {
+			config
+				.features
+				.auto
+				.exclude_columns
+				.get_or_insert_with(Vec::new)
+				.extend(
+					dropped_columns
+						.iter()
+						.map(|dropped_column| dropped_column.column_name.clone()),
+				);
+		}
+
+		// Load the pretrained word embeddings for the word embedding feature groups.
+		let word_embedding_models = load_word_embedding_models(&config, &train_column_stats)?;
+
+		// Create the hyperparameter grid. If there is an init model, every grid item continues training it.
+		let grid = compute_hyperparameter_grid(
+			&config,
+			&task,
+			target_column_index,
+			group_column_index,
+			ndcg_k,
+			&train_column_stats,
+			&word_embedding_models,
+		);
+		// Only tree models can be trained for ranking.
+		if matches!(task, Task::Ranking) {
+			let grid_has_linear_items = config
+				.train
+				.grid
+				.as_ref()
+				.map(|grid| {
+					grid.iter()
+						.any(|item| matches!(item, config::GridItem::Linear(_)))
+				})
+				.unwrap_or(false);
+			if grid_has_linear_items || grid.is_empty() {
+				bail!("Only tree models can be trained for rankers.");
+			}
+		}
+		let grid = match &init_model {
+			Some(init_model) => compute_warm_start_grid(grid, init_model, target_column_index),
+			None => grid,
+		};
 
 		let trainer = Trainer {
 			id,
@@ -230,6 +490,12 @@ impl Trainer {
 			dataset: Arc::new(dataset),
 			grid,
 			task,
+			group_column_index,
+			ndcg_k,
+			target_transform,
+			dropped_columns,
+			data_quality_issues,
+			init_model,
 		};
 		Ok(trainer)
 	}
@@ -243,8 +509,8 @@ impl Trainer {
 		let (table_train, table_comparison, _) = self.dataset.split();
 		let grid = &self.grid;
 		let comparison_metric = self.comparison_metric;
-		let train_grid_item_outputs = grid
-			.iter()
+		let target_transform = self.target_transform;
+		grid.iter()
 			.cloned()
 			.enumerate()
 			.take_while(|_| !kill_chip.is_activated())
@@ -253,6 +519,9 @@ impl Trainer {
 					grid.len(),
 					grid_item_index,
 					grid_item,
+					&self.dataset,
+					self.init_model.as_ref(),
+					target_transform,
 					&table_train,
 					&table_comparison,
 					comparison_metric,
@@ -260,8 +529,7 @@ impl Trainer {
 					handle_progress_event,
 				)
 			})
-			.collect();
-		Ok(train_grid_item_outputs)
+			.collect()
 	}
 
 	pub fn test_and_assemble_model(
@@ -286,6 +554,11 @@ impl Trainer {
 			comparison_metric,
 			task,
 			dataset,
+			group_column_index,
+			ndcg_k,
+			dropped_columns,
+			data_quality_issues,
+			init_model,
 			..
 		} = self;
 
@@ -301,7 +574,8 @@ impl Trainer {
 			| TrainModelOutput::LinearMulticlassClassifier(_) => "Linear",
 			TrainModelOutput::TreeRegressor(_)
 			| TrainModelOutput::TreeBinaryClassifier(_)
-			| TrainModelOutput::TreeMulticlassClassifier(_) => "Tree",
+			| TrainModelOutput::TreeMulticlassClassifier(_)
+			| TrainModelOutput::TreeRanker(_) => "Tree",
 		};
 		let grid_len = train_grid_item_outputs.len();
 		let comparison_metric_value =
@@ -328,6 +602,17 @@ impl Trainer {
 					Metrics::Regression(test_metrics) => test_metrics,
 					_ => unreachable!(),
 				};
+				let explanations =
+					explain::compute_explanations(&train_model_output, &table_test, None);
+				let target_transform = match &train_model_output {
+					TrainModelOutput::LinearRegressor(train_model_output) => {
+						train_model_output.target_transform
+					}
+					TrainModelOutput::TreeRegressor(train_model_output) => {
+						train_model_output.target_transform
+					}
+					_ => unreachable!(),
+				};
 				let model = match train_model_output {
 					TrainModelOutput::LinearRegressor(LinearRegressorTrainModelOutput {
 						model,
@@ -377,6 +662,10 @@ impl Trainer {
 					best_grid_item_index,
 					model,
 					test_metrics,
+					explanations,
+					target_transform,
+					dropped_columns,
+					data_quality_issues,
 				})
 			}
 			Task::BinaryClassification => {
@@ -392,6 +681,8 @@ impl Trainer {
 					Metrics::BinaryClassification(test_metrics) => test_metrics,
 					_ => unreachable!(),
 				};
+				let explanations =
+					explain::compute_explanations(&train_model_output, &table_test, None);
 				let model = match train_model_output {
 					TrainModelOutput::LinearBinaryClassifier(
 						LinearBinaryClassifierTrainModelOutput {
@@ -454,6 +745,9 @@ impl Trainer {
 					best_grid_item_index,
 					model,
 					test_metrics,
+					explanations,
+					dropped_columns,
+					data_quality_issues,
 				})
 			}
 			Task::MulticlassClassification { .. } => {
@@ -471,6 +765,16 @@ impl Trainer {
 					Metrics::MulticlassClassification(test_metrics) => test_metrics,
 					_ => unreachable!(),
 				};
+				let classes = match &train_target_column_stats {
+					ColumnStatsOutput::Enum(train_target_column_stats) => train_target_column_stats
+						.histogram
+						.iter()
+						.map(|(class, _)| class.clone())
+						.collect::<Vec<_>>(),
+					_ => unreachable!(),
+				};
+				let explanations =
+					explain::compute_explanations(&train_model_output, &table_test, Some(&classes));
 				let model = match train_model_output {
 					TrainModelOutput::LinearMulticlassClassifier(
 						LinearMulticlassClassifierTrainModelOutput {
@@ -508,14 +812,6 @@ impl Trainer {
 					}),
 					_ => unreachable!(),
 				};
-				let classes = match &train_target_column_stats {
-					ColumnStatsOutput::Enum(train_target_column_stats) => train_target_column_stats
-						.histogram
-						.iter()
-						.map(|(class, _)| class.clone())
-						.collect(),
-					_ => unreachable!(),
-				};
 				ModelInner::MulticlassClassifier(MulticlassClassifier {
 					target_column_name,
 					classes,
@@ -535,6 +831,70 @@ impl Trainer {
 					best_grid_item_index,
 					model,
 					test_metrics,
+					explanations,
+					dropped_columns,
+					data_quality_issues,
+				})
+			}
+			Task::Ranking => {
+				let baseline_metrics = match baseline_metrics {
+					Metrics::Ranking(baseline_metrics) => baseline_metrics,
+					_ => unreachable!(),
+				};
+				let comparison_metric = match comparison_metric {
+					ComparisonMetric::Ranking(comparison_metric) => comparison_metric,
+					_ => unreachable!(),
+				};
+				let test_metrics = match test_metrics {
+					Metrics::Ranking(test_metrics) => test_metrics,
+					_ => unreachable!(),
+				};
+				let explanations =
+					explain::compute_explanations(&train_model_output, &table_test, None);
+				let model = match train_model_output {
+					TrainModelOutput::TreeRanker(TreeRankerTrainModelOutput {
+						model,
+						feature_groups,
+						train_options,
+						losses,
+						feature_importances,
+						..
+					}) => RankingModel::Tree(TreeRankingModel {
+						model,
+						train_options,
+						feature_groups,
+						losses,
+						feature_importances,
+					}),
+					_ => unreachable!(),
+				};
+				let group_column_name = table_test.columns()[group_column_index.unwrap()]
+					.name()
+					.unwrap()
+					.to_owned();
+				ModelInner::Ranker(Ranker {
+					target_column_name,
+					group_column_name,
+					train_row_count,
+					test_row_count,
+					overall_row_count,
+					stats_settings,
+					overall_column_stats,
+					overall_target_column_stats,
+					train_column_stats,
+					train_target_column_stats,
+					test_column_stats,
+					test_target_column_stats,
+					baseline_metrics,
+					comparison_metric,
+					train_grid_item_outputs,
+					best_grid_item_index,
+					model,
+					test_metrics,
+					ndcg_k,
+					explanations,
+					dropped_columns,
+					data_quality_issues,
 				})
 			}
 		};
@@ -543,12 +903,14 @@ impl Trainer {
 			version: env!("CARGO_PKG_VERSION").to_owned(),
 			date: chrono::Utc::now().to_rfc3339(),
 			inner,
+			parent_id: init_model.map(|init_model| init_model.id),
 		};
 		handle_progress_event(ProgressEvent::FinalizeDone);
 		let task_str = match task {
 			Task::BinaryClassification => "binary classification",
 			Task::MulticlassClassification => "multiclass classification",
 			Task::Regression => "regression",
+			Task::Ranking => "ranking",
 		};
 		let comparison_metric_str = match comparison_metric {
 			ComparisonMetric::BinaryClassification(bcm) => match bcm {
@@ -563,6 +925,10 @@ impl Trainer {
 				RegressionComparisonMetric::RootMeanSquaredError => "root mean squared error",
 				RegressionComparisonMetric::R2 => "r2",
 			},
+			ComparisonMetric::Ranking(rcm) => match rcm {
+				RankingComparisonMetric::Ndcg => "NDCG",
+				RankingComparisonMetric::Map => "MAP",
+			},
 		};
 		handle_progress_event(ProgressEvent::Info(format!(
 			"Selected {} Model {} of {} for {} result ({}: {})",
@@ -577,7 +943,248 @@ impl Trainer {
 	}
 }
 
-fn load_config(config_path: Option<&Path>) -> Result<Config> {
+/// This is a previously trained model that training continues from. Its feature groups are reused, tree models append new trees to its trees, and linear models start from its weights.
+struct InitModel {
+	id: Id,
+	target_column_name: String,
+	/// These are the classes of a classifier, which must match the variants of the target column in the new dataset.
+	classes: Option<Vec<String>>,
+	feature_groups: Vec<modelfox_features::FeatureGroup>,
+	/// This is the target transform of a regressor. Training continues in the transformed space.
+	target_transform: Option<TargetTransform>,
+	model: InitModelInner,
+}
+
+enum InitModelInner {
+	LinearRegressor(modelfox_linear::Regressor),
+	TreeRegressor(modelfox_tree::Regressor),
+	LinearBinaryClassifier(modelfox_linear::BinaryClassifier),
+	TreeBinaryClassifier(modelfox_tree::BinaryClassifier),
+	LinearMulticlassClassifier(modelfox_linear::MulticlassClassifier),
+	TreeMulticlassClassifier(modelfox_tree::MulticlassClassifier),
+}
+
+impl InitModel {
+	fn from_path(path: &Path) -> Result<InitModel> {
+		let bytes = std::fs::read(path)?;
+		let model = modelfox_model::from_bytes(&bytes)?;
+		let target_column_name = match model.inner() {
+			modelfox_model::ModelInnerReader::Regressor(regressor) => {
+				regressor.read().target_column_name().to_owned()
+			}
+			modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+				binary_classifier.read().target_column_name().to_owned()
+			}
+			modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+				multiclass_classifier.read().target_column_name().to_owned()
+			}
+			modelfox_model::ModelInnerReader::Ranker(_) => {
+				bail!("Continuing training from a ranker is not supported.")
+			}
+		};
+		let model = crate::predict::Model::from(model);
+		let id = model.id.parse()?;
+		let (classes, feature_groups, target_transform, model) = match model.inner {
+			crate::predict::ModelInner::Regressor(regressor) => {
+				let model = match regressor.model {
+					crate::predict::RegressionModel::Linear(model) => {
+						InitModelInner::LinearRegressor(model)
+					}
+					crate::predict::RegressionModel::Tree(model) => {
+						InitModelInner::TreeRegressor(model)
+					}
+				};
+				(
+					None,
+					regressor.feature_groups,
+					regressor.target_transform,
+					model,
+				)
+			}
+			crate::predict::ModelInner::BinaryClassifier(binary_classifier) => {
+				let model = match binary_classifier.model {
+					crate::predict::BinaryClassificationModel::Linear(model) => {
+						InitModelInner::LinearBinaryClassifier(model)
+					}
+					crate::predict::BinaryClassificationModel::Tree(model) => {
+						InitModelInner::TreeBinaryClassifier(model)
+					}
+				};
+				let classes = vec![
+					binary_classifier.negative_class,
+					binary_classifier.positive_class,
+				];
+				(Some(classes), binary_classifier.feature_groups, None, model)
+			}
+			crate::predict::ModelInner::MulticlassClassifier(multiclass_classifier) => {
+				let model = match multiclass_classifier.model {
+					crate::predict::MulticlassClassificationModel::Linear(model) => {
+						InitModelInner::LinearMulticlassClassifier(model)
+					}
+					crate::predict::MulticlassClassificationModel::Tree(model) => {
+						InitModelInner::TreeMulticlassClassifier(model)
+					}
+				};
+				(
+					Some(multiclass_classifier.classes),
+					multiclass_classifier.feature_groups,
+					None,
+					model,
+				)
+			}
+			crate::predict::ModelInner::Ranker(_) => unreachable!(),
+		};
+		Ok(InitModel {
+			id,
+			target_column_name,
+			classes,
+			feature_groups,
+			target_transform,
+			model,
+		})
+	}
+
+	/// Check that the init model was trained to predict the same target column, with the same task and classes, as the new dataset.
+	fn check_target_column(
+		&self,
+		target_column_name: &str,
+		task: &Task,
+		target_column_variants: Option<&[String]>,
+	) -> Result<()> {
+		if self.target_column_name != target_column_name {
+			bail!(
+				"The init model predicts the column \"{}\", but the target column is \"{}\".",
+				self.target_column_name,
+				target_column_name,
+			);
+		}
+		let tasks_match = matches!(
+			(&self.model, task),
+			(
+				InitModelInner::LinearRegressor(_) | InitModelInner::TreeRegressor(_),
+				Task::Regression
+			) | (
+				InitModelInner::LinearBinaryClassifier(_) | InitModelInner::TreeBinaryClassifier(_),
+				Task::BinaryClassification
+			) | (
+				InitModelInner::LinearMulticlassClassifier(_)
+					| InitModelInner::TreeMulticlassClassifier(_),
+				Task::MulticlassClassification
+			)
+		);
+		if !tasks_match {
+			bail!("The init model was trained for a different task than the new dataset.");
+		}
+		if self.classes.as_deref() != target_column_variants {
+			bail!("The classes of the init model do not match the variants of the target column in the new dataset.");
+		}
+		Ok(())
+	}
+}
+
+/// Make every grid item continue training the init model. Grid items for the other model type are dropped, and all grid items use the init model's feature groups. If no grid items remain, a single grid item with the default options is used.
+fn compute_warm_start_grid(
+	grid: Vec<grid::GridItem>,
+	init_model: &InitModel,
+	target_column_index: usize,
+) -> Vec<grid::GridItem> {
+	let feature_groups = &init_model.feature_groups;
+	let grid: Vec<grid::GridItem> = grid
+		.into_iter()
+		.filter_map(|grid_item| match (grid_item, &init_model.model) {
+			(
+				grid::GridItem::LinearRegressor { options, .. },
+				InitModelInner::LinearRegressor(_),
+			) => Some(grid::GridItem::LinearRegressor {
+				target_column_index,
+				feature_groups: feature_groups.clone(),
+				options,
+			}),
+			(grid::GridItem::TreeRegressor { options, .. }, InitModelInner::TreeRegressor(_)) => {
+				Some(grid::GridItem::TreeRegressor {
+					target_column_index,
+					feature_groups: feature_groups.clone(),
+					options,
+				})
+			}
+			(
+				grid::GridItem::LinearBinaryClassifier { options, .. },
+				InitModelInner::LinearBinaryClassifier(_),
+			) => Some(grid::GridItem::LinearBinaryClassifier {
+				target_column_index,
+				feature_groups: feature_groups.clone(),
+				options,
+			}),
+			(
+				grid::GridItem::TreeBinaryClassifier { options, .. },
+				InitModelInner::TreeBinaryClassifier(_),
+			) => Some(grid::GridItem::TreeBinaryClassifier {
+				target_column_index,
+				feature_groups: feature_groups.clone(),
+				options,
+			}),
+			(
+				grid::GridItem::LinearMulticlassClassifier { options, .. },
+				InitModelInner::LinearMulticlassClassifier(_),
+			) => Some(grid::GridItem::LinearMulticlassClassifier {
+				target_column_index,
+				feature_groups: feature_groups.clone(),
+				options,
+			}),
+			(
+				grid::GridItem::TreeMulticlassClassifier { options, .. },
+				InitModelInner::TreeMulticlassClassifier(_),
+			) => Some(grid::GridItem::TreeMulticlassClassifier {
+				target_column_index,
+				feature_groups: feature_groups.clone(),
+				options,
+			}),
+			_ => None,
+		})
+		.collect();
+	if !grid.is_empty() {
+		return grid;
+	}
+	let feature_groups = feature_groups.clone();
+	let grid_item = match &init_model.model {
+		InitModelInner::LinearRegressor(_) => grid::GridItem::LinearRegressor {
+			target_column_index,
+			feature_groups,
+			options: Default::default(),
+		},
+		InitModelInner::TreeRegressor(_) => grid::GridItem::TreeRegressor {
+			target_column_index,
+			feature_groups,
+			options: Default::default(),
+		},
+		InitModelInner::LinearBinaryClassifier(_) => grid::GridItem::LinearBinaryClassifier {
+			target_column_index,
+			feature_groups,
+			options: Default::default(),
+		},
+		InitModelInner::TreeBinaryClassifier(_) => grid::GridItem::TreeBinaryClassifier {
+			target_column_index,
+			feature_groups,
+			options: Default::default(),
+		},
+		InitModelInner::LinearMulticlassClassifier(_) => {
+			grid::GridItem::LinearMulticlassClassifier {
+				target_column_index,
+				feature_groups,
+				options: Default::default(),
+			}
+		}
+		InitModelInner::TreeMulticlassClassifier(_) => grid::GridItem::TreeMulticlassClassifier {
+			target_column_index,
+			feature_groups,
+			options: Default::default(),
+		},
+	};
+	vec![grid_item]
+}
+
+/// Load the config from the config file at `config_path`, or use the default config if no path is provided.
+pub fn load_config(config_path: Option<&Path>) -> Result<Config> {
 	if let Some(config_path) = config_path {
 		let config = std::fs::read_to_string(config_path)?;
 		let extension = config_path.extension().and_then(|s| s.to_str());
@@ -592,21 +1199,427 @@ fn load_config(config_path: Option<&Path>) -> Result<Config> {
 	}
 }
 
+/// Compute the stats settings, using the tokenizer and ngram types from each bag of words and hashed bag of words feature group in the config for its source column, and the quantiles that separate the bins of each binned number feature group.
+fn compute_stats_settings(config: &Config) -> Result<StatsSettings> {
+	let mut stats_settings = StatsSettings::default();
+	let feature_groups = config.features.include.iter().flatten();
+	for feature_group in feature_groups {
+		let (source_column_name, tokenizer, ngram_types) = match feature_group {
+			config::FeatureGroup::BagOfWords(feature_group) => (
+				&feature_group.source_column_name,
+				&feature_group.tokenizer,
+				&feature_group.ngram_types,
+			),
+			config::FeatureGroup::HashedBagOfWords(feature_group) => (
+				&feature_group.source_column_name,
+				&feature_group.tokenizer,
+				&feature_group.ngram_types,
+			),
+			config::FeatureGroup::WordEmbedding(feature_group) => (
+				&feature_group.source_column_name,
+				&feature_group.tokenizer,
+				&None,
+			),
+			config::FeatureGroup::HashedEnum(feature_group) => {
+				// Text columns used as categories need the counts of their values to describe the buckets.
+				stats_settings
+					.text_columns_with_value_counts
+					.insert(feature_group.source_column_name.clone());
+				continue;
+			}
+			config::FeatureGroup::Polynomial(feature_group) => {
+				if feature_group
+					.degree
+					.map(|degree| degree < 2)
+					.unwrap_or(false)
+				{
+					bail!(
+						"The degree of the polynomial feature group for the column \"{}\" must be at least 2.",
+						feature_group.source_column_name
+					);
+				}
+				continue;
+			}
+			config::FeatureGroup::BinnedNumber(feature_group) => {
+				let source_column_name = &feature_group.source_column_name;
+				let n_bins = feature_group
+					.n_bins
+					.map(|n_bins| n_bins.get())
+					.unwrap_or(BINNED_NUMBER_FEATURE_GROUP_DEFAULT_N_BINS);
+				if n_bins < 2 {
+					bail!(
+						"The number of bins of the binned number feature group for the column \"{}\" must be at least 2.",
+						source_column_name
+					);
+				}
+				// The bins are separated by evenly spaced quantiles of the column.
+				let quantiles = (1..n_bins)
+					.map(|i| i.to_f32().unwrap() / n_bins.to_f32().unwrap())
+					.collect::<Vec<_>>();
+				match stats_settings.number_column_quantiles.get(source_column_name) {
+					Some(existing) if *existing != quantiles => bail!(
+						"The column \"{}\" is used by more than one binned number feature group with different numbers of bins.",
+						source_column_name
+					),
+					Some(_) => {}
+					None => {
+						stats_settings
+							.number_column_quantiles
+							.insert(source_column_name.clone(), quantiles);
+					}
+				}
+				continue;
+			}
+			_ => continue,
+		};
+		if tokenizer.is_none() && ngram_types.is_none() {
+			continue;
+		}
+		let tokenizer = match tokenizer {
+			Some(tokenizer) => compute_tokenizer(tokenizer).map_err(|error| {
+				anyhow!(
+					"Invalid tokenizer for the column \"{}\": {}",
+					source_column_name,
+					error
+				)
+			})?,
+			None => modelfox_text::Tokenizer::default(),
+		};
+		let ngram_types = match ngram_types {
+			Some(ngram_types) if ngram_types.is_empty() => bail!(
+				"The ngram types for the column \"{}\" must not be empty.",
+				source_column_name
+			),
+			Some(ngram_types) => ngram_types
+				.iter()
+				.map(|ngram_type| match ngram_type {
+					config::NGramType::Unigram => NGramType::Unigram,
+					config::NGramType::Bigram => NGramType::Bigram,
+					config::NGramType::CharacterTrigram => NGramType::CharacterTrigram,
+					config::NGramType::CharacterQuadgram => NGramType::CharacterQuadgram,
+					config::NGramType::CharacterPentagram => NGramType::CharacterPentagram,
+				})
+				.collect(),
+			None => stats_settings.ngram_types.clone(),
+		};
+		let text_column_settings = TextColumnStatsSettings {
+			tokenizer,
+			ngram_types,
+		};
+		match stats_settings
+			.text_column_settings
+			.get(source_column_name)
+		{
+			Some(existing) if *existing != text_column_settings => bail!(
+				"The column \"{}\" is used by more than one feature group with different tokenizers or ngram types.",
+				source_column_name
+			),
+			Some(_) => {}
+			None => {
+				stats_settings.text_column_settings.insert(
+					source_column_name.clone(),
+					text_column_settings,
+				);
+			}
+		}
+	}
+	Ok(stats_settings)
+}
+
+/// Load the word embeddings for each word embedding feature group in the config, keyed by the name of its source column.
+fn load_word_embedding_models(
+	config: &Config,
+	column_stats: &[ColumnStatsOutput],
+) -> Result<BTreeMap<String, modelfox_text::WordEmbeddingModel>> {
+	let mut word_embedding_models = BTreeMap::new();
+	let feature_groups = config.features.include.iter().flatten();
+	for feature_group in feature_groups {
+		let feature_group = match feature_group {
+			config::FeatureGroup::WordEmbedding(feature_group) => feature_group,
+			_ => continue,
+		};
+		let source_column_name = &feature_group.source_column_name;
+		if word_embedding_models.contains_key(source_column_name) {
+			bail!(
+				"The column \"{}\" is used by more than one word embedding feature group.",
+				source_column_name
+			);
+		}
+		let column_stats = match column_stats
+			.iter()
+			.find(|column_stats| column_stats.column_name() == source_column_name)
+		{
+			Some(ColumnStatsOutput::Text(column_stats)) => column_stats,
+			Some(_) => bail!(
+				"The word embedding feature group for the column \"{}\" requires a text column.",
+				source_column_name
+			),
+			None => bail!(
+				"Did not find the column \"{}\" for the word embedding feature group.",
+				source_column_name
+			),
+		};
+		// The vocabulary is the unigrams tracked by the stats, which are the most frequent tokens in the train dataset.
+		let vocabulary = if feature_group.restrict_to_vocabulary.unwrap_or(true) {
+			if !column_stats.ngram_types.contains(&NGramType::Unigram) {
+				bail!(
+					"The word embeddings for the column \"{}\" cannot be restricted to its vocabulary because its ngram types do not include unigrams.",
+					source_column_name
+				);
+			}
+			let vocabulary: FnvHashSet<String> = column_stats
+				.top_ngrams
+				.keys()
+				.filter_map(|ngram| match ngram {
+					NGram::Unigram(token) => Some(token.clone()),
+					_ => None,
+				})
+				.collect();
+			Some(vocabulary)
+		} else {
+			None
+		};
+		let path = &feature_group.path;
+		let binary = match feature_group.format {
+			Some(config::WordEmbeddingFormat::Text) => false,
+			Some(config::WordEmbeddingFormat::Binary) => true,
+			None => path.extension().and_then(|extension| extension.to_str()) == Some("bin"),
+		};
+		let file = std::fs::File::open(path).map_err(|error| {
+			anyhow!(
+				"Failed to open the word embeddings file \"{}\": {}",
+				path.display(),
+				error
+			)
+		})?;
+		let reader = std::io::BufReader::new(file);
+		let model = if binary {
+			modelfox_text::WordEmbeddingModel::from_binary(reader, vocabulary.as_ref())
+		} else {
+			modelfox_text::WordEmbeddingModel::from_text(reader, vocabulary.as_ref())
+		}
+		.map_err(|error| {
+			anyhow!(
+				"Failed to read the word embeddings file \"{}\": {}",
+				path.display(),
+				error
+			)
+		})?;
+		word_embedding_models.insert(source_column_name.clone(), model);
+	}
+	Ok(word_embedding_models)
+}
+
+fn compute_tokenizer(tokenizer: &config::Tokenizer) -> Result<modelfox_text::Tokenizer> {
+	let stop_words = match &tokenizer.stop_words {
+		None => Default::default(),
+		Some(config::StopWords::Language(config::StopWordsLanguage::English)) => {
+			modelfox_text::ENGLISH_STOP_WORDS
+				.iter()
+				.map(|stop_word| stop_word.to_string())
+				.collect()
+		}
+		Some(config::StopWords::Words(stop_words)) => stop_words
+			.iter()
+			.map(|stop_word| {
+				if tokenizer.lowercase {
+					stop_word.to_lowercase()
+				} else {
+					stop_word.clone()
+				}
+			})
+			.collect(),
+	};
+	let stemmer = tokenizer.stemmer.as_ref().map(|stemmer| match stemmer {
+		config::Stemmer::English => modelfox_text::Stemmer::English,
+	});
+	let token_pattern = tokenizer
+		.token_pattern
+		.as_deref()
+		.map(modelfox_text::TokenPattern::new)
+		.transpose()?;
+	Ok(modelfox_text::Tokenizer {
+		lowercase: tokenizer.lowercase,
+		alphanumeric: true,
+		nfkc: tokenizer.nfkc,
+		fold_accents: tokenizer.fold_accents,
+		stop_words,
+		stemmer,
+		token_pattern,
+	})
+}
+
 enum Dataset {
 	Train(DatasetTrain),
 	TrainAndTest(DatasetTrainAndTest),
+	OutOfCore(DatasetOutOfCore),
 }
 
 struct DatasetTrain {
 	table: Table,
 	comparison_fraction: f32,
 	test_fraction: f32,
+	/// If this is set, the rows of each query group are contiguous and the splits are moved to group boundaries.
+	group_column_index: Option<usize>,
 }
 
 struct DatasetTrainAndTest {
 	table_train: Table,
 	table_test: Table,
 	comparison_fraction: f32,
+	/// If this is set, the rows of each query group are contiguous and the splits are moved to group boundaries.
+	group_column_index: Option<usize>,
+}
+
+/// This is a dataset for out of core training. The rows of the train dataset are read from the train file in chunks every time a model is trained, so only their stats, target values, and a sample are kept in memory.
+struct DatasetOutOfCore {
+	file_path_train: PathBuf,
+	column_types: BTreeMap<String, TableColumnType>,
+	target_column_index: usize,
+	chunk_size: usize,
+	seed: u64,
+	comparison_fraction: f32,
+	test_fraction: f32,
+	train_row_count: usize,
+	/// These are the stats for the train dataset, computed by merging the stats for each chunk.
+	train_stats: Stats,
+	/// This table has only the target column, with a row for each row of the train dataset.
+	table_train_labels: Table,
+	/// This is a uniform sample of the rows of the train dataset.
+	table_train_sample: Table,
+	table_comparison: Table,
+	table_test: Table,
+}
+
+impl DatasetOutOfCore {
+	fn row_splitter(&self) -> RowSplitter {
+		RowSplitter::new(self.seed, self.comparison_fraction, self.test_fraction)
+	}
+
+	/// Read the rows of the train dataset from the train file in chunks.
+	fn train_chunks(&self) -> impl Iterator<Item = Result<Table>> + '_ {
+		let mut row_splitter = self.row_splitter();
+		// Every chunk of train rows has the same columns as the sample.
+		let empty_table = Table::new(
+			self.table_train_sample
+				.columns()
+				.iter()
+				.map(|column| column.name().map(ToOwned::to_owned))
+				.collect(),
+			self.table_train_sample
+				.columns()
+				.iter()
+				.map(column_type)
+				.collect(),
+		);
+		let chunks: Box<dyn Iterator<Item = Result<Table>>> = match TableChunks::from_path(
+			&self.file_path_train,
+			&self.column_types,
+			self.chunk_size,
+		) {
+			Ok(chunks) => Box::new(chunks),
+			Err(error) => Box::new(std::iter::once(Err(error))),
+		};
+		chunks.map(move |chunk| {
+			let chunk = chunk?;
+			let chunk = chunk.view();
+			let mut table_train = empty_table.clone();
+			for index in 0..chunk.nrows() {
+				let row_split = row_splitter.next_row_split();
+				if row_split == RowSplit::Train
+					&& target_is_valid(&chunk, self.target_column_index, index)
+				{
+					table_train.push_row(&chunk, index);
+				}
+			}
+			Ok(table_train)
+		})
+	}
+}
+
+/// This assigns each row of the train file to the train, comparison, or test dataset at random. It is seeded, so it assigns every row to the same dataset each time the file is read.
+struct RowSplitter {
+	rng: Xoshiro256Plus,
+	comparison_fraction: f32,
+	test_fraction: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RowSplit {
+	Train,
+	Comparison,
+	Test,
+}
+
+impl RowSplitter {
+	fn new(seed: u64, comparison_fraction: f32, test_fraction: f32) -> RowSplitter {
+		RowSplitter {
+			rng: Xoshiro256Plus::seed_from_u64(seed),
+			comparison_fraction,
+			test_fraction,
+		}
+	}
+
+	fn next_row_split(&mut self) -> RowSplit {
+		let value: f32 = self.rng.gen();
+		if value < self.test_fraction {
+			RowSplit::Test
+		} else if value < self.test_fraction + self.comparison_fraction {
+			RowSplit::Comparison
+		} else {
+			RowSplit::Train
+		}
+	}
+}
+
+/// This keeps a uniform sample of at most `capacity` of the rows pushed to it using reservoir sampling.
+struct Reservoir {
+	table: Table,
+	capacity: usize,
+	n_rows_pushed: usize,
+	rng: Xoshiro256Plus,
+}
+
+impl Reservoir {
+	fn new(table: Table, capacity: usize, seed: u64) -> Reservoir {
+		Reservoir {
+			table,
+			capacity,
+			n_rows_pushed: 0,
+			rng: Xoshiro256Plus::seed_from_u64(seed),
+		}
+	}
+
+	fn push(&mut self, table: &TableView, index: usize) {
+		if self.n_rows_pushed < self.capacity {
+			self.table.push_row(table, index);
+		} else {
+			let row_index = self.rng.gen_range(0..=self.n_rows_pushed);
+			if row_index < self.capacity {
+				self.table.replace_row(row_index, table, index);
+			}
+		}
+		self.n_rows_pushed += 1;
+	}
+}
+
+fn column_type(column: &TableColumn) -> TableColumnType {
+	match column {
+		TableColumn::Unknown(_) => TableColumnType::Unknown,
+		TableColumn::Number(_) => TableColumnType::Number,
+		TableColumn::Enum(column) => TableColumnType::Enum {
+			variants: column.variants().to_owned(),
+		},
+		TableColumn::Text(_) => TableColumnType::Text,
+	}
+}
+
+fn target_is_valid(table: &TableView, target_column_index: usize, index: usize) -> bool {
+	match &table.columns()[target_column_index] {
+		TableColumnView::Number(column) => column.as_slice()[index].is_finite(),
+		TableColumnView::Enum(column) => column.as_slice()[index].is_some(),
+		_ => true,
+	}
 }
 
 impl Dataset {
@@ -616,6 +1629,7 @@ impl Dataset {
 				table,
 				comparison_fraction,
 				test_fraction,
+				group_column_index,
 			}) => {
 				let n_rows_test = (test_fraction * table.nrows().to_f32().unwrap())
 					.floor()
@@ -626,7 +1640,21 @@ impl Dataset {
 					.to_usize()
 					.unwrap();
 				let n_rows_train = table.nrows() - n_rows_test - n_rows_comparison;
-				let (table_train, table_rest) = table.view().split_at_row(n_rows_train);
+				let table = table.view();
+				// Never break a query group across the datasets.
+				let (n_rows_train, n_rows_comparison) = match group_column_index {
+					Some(group_column_index) => {
+						let group_column = &table.columns()[*group_column_index];
+						let train_end = group::next_group_boundary(group_column, n_rows_train);
+						let comparison_end = group::next_group_boundary(
+							group_column,
+							train_end.max(n_rows_train + n_rows_comparison),
+						);
+						(train_end, comparison_end - train_end)
+					}
+					None => (n_rows_train, n_rows_comparison),
+				};
+				let (table_train, table_rest) = table.split_at_row(n_rows_train);
 				let (table_comparison, table_test) = table_rest.split_at_row(n_rows_comparison);
 				(table_train, table_comparison, table_test)
 			}
@@ -634,6 +1662,7 @@ impl Dataset {
 				table_train,
 				table_test,
 				comparison_fraction,
+				group_column_index,
 			}) => {
 				let n_rows_comparison = (comparison_fraction
 					* table_train.nrows().to_f32().unwrap())
@@ -641,10 +1670,29 @@ impl Dataset {
 				.to_usize()
 				.unwrap();
 				let n_rows_train = table_train.nrows() - n_rows_comparison;
-				let (table_train, table_comparison) = table_train.view().split_at_row(n_rows_train);
+				let table_train = table_train.view();
+				// Never break a query group across the datasets.
+				let n_rows_train = match group_column_index {
+					Some(group_column_index) => group::next_group_boundary(
+						&table_train.columns()[*group_column_index],
+						n_rows_train,
+					),
+					None => n_rows_train,
+				};
+				let (table_train, table_comparison) = table_train.split_at_row(n_rows_train);
 				let table_test = table_test.view();
 				(table_train, table_comparison, table_test)
 			}
+			Dataset::OutOfCore(DatasetOutOfCore {
+				table_train_sample,
+				table_comparison,
+				table_test,
+				..
+			}) => (
+				table_train_sample.view(),
+				table_comparison.view(),
+				table_test.view(),
+			),
 		}
 	}
 }
@@ -719,12 +1767,14 @@ fn load_and_shuffle_dataset_stdin(
 	// Drop any rows with invalid data in the target column
 	drop_invalid_target_rows(&mut table, target_column_name, handle_progress_event);
 	// Shuffle the table if enabled.
-	shuffle_table(&mut table, config, handle_progress_event);
+	shuffle_table(&mut table, config, handle_progress_event)?;
 	// Split the table into train and test tables.
+	let group_column_index = find_group_column_index(&table, config)?;
 	Ok(DatasetTrain {
 		table,
 		comparison_fraction: config.dataset.comparison_fraction,
 		test_fraction: config.dataset.test_fraction,
+		group_column_index,
 	})
 }
 
@@ -751,12 +1801,14 @@ fn load_and_shuffle_dataset_train(
 	// Drop any rows with invalid data in the target column
 	drop_invalid_target_rows(&mut table, target_column_name, handle_progress_event);
 	// Shuffle the table if enabled.
-	shuffle_table(&mut table, config, handle_progress_event);
+	shuffle_table(&mut table, config, handle_progress_event)?;
 	// Split the table into train and test tables.
+	let group_column_index = find_group_column_index(&table, config)?;
 	Ok(DatasetTrain {
 		table,
 		comparison_fraction: config.dataset.comparison_fraction,
 		test_fraction: config.dataset.test_fraction,
+		group_column_index,
 	})
 }
 
@@ -819,11 +1871,256 @@ fn load_and_shuffle_dataset_train_and_test(
 	// Drop any rows with invalid data in the target column
 	drop_invalid_target_rows(&mut table_train, target_column_name, handle_progress_event);
 	drop_invalid_target_rows(&mut table_test, target_column_name, handle_progress_event);
-	shuffle_table(&mut table_train, config, handle_progress_event);
+	shuffle_table(&mut table_train, config, handle_progress_event)?;
+	group_table_test(&mut table_test, config)?;
+	let group_column_index = find_group_column_index(&table_train, config)?;
+	Ok(DatasetTrainAndTest {
+		table_train,
+		table_test,
+		comparison_fraction: config.dataset.comparison_fraction,
+		group_column_index,
+	})
+}
+
+fn shuffle_dataset_table(
+	mut table: Table,
+	config: &Config,
+	target_column_name: &str,
+	handle_progress_event: &mut dyn FnMut(ProgressEvent),
+) -> Result<DatasetTrain> {
+	if !table
+		.columns()
+		.iter()
+		.any(|column| column.name() == Some(target_column_name))
+	{
+		bail!(
+			"did not find target column \"{}\" in the table",
+			target_column_name
+		);
+	}
+	// Drop any rows with invalid data in the target column
+	drop_invalid_target_rows(&mut table, target_column_name, handle_progress_event);
+	// Shuffle the table if enabled.
+	shuffle_table(&mut table, config, handle_progress_event)?;
+	let group_column_index = find_group_column_index(&table, config)?;
+	Ok(DatasetTrain {
+		table,
+		comparison_fraction: config.dataset.comparison_fraction,
+		test_fraction: config.dataset.test_fraction,
+		group_column_index,
+	})
+}
+
+fn shuffle_dataset_tables_train_and_test(
+	mut table_train: Table,
+	table_test: Table,
+	config: &Config,
+	target_column_name: &str,
+	handle_progress_event: &mut dyn FnMut(ProgressEvent),
+) -> Result<DatasetTrainAndTest> {
+	if !table_train
+		.columns()
+		.iter()
+		.any(|column| column.name() == Some(target_column_name))
+	{
+		bail!(
+			"did not find target column \"{}\" in the train table",
+			target_column_name
+		);
+	}
+	// Make the enum columns in table_test use the same variants as table_train, just like the column types for a test csv file are forced to match the train csv file.
+	let mut table_test = conform_table_test(&table_train, table_test)?;
+	// Drop any rows with invalid data in the target column
+	drop_invalid_target_rows(&mut table_train, target_column_name, handle_progress_event);
+	drop_invalid_target_rows(&mut table_test, target_column_name, handle_progress_event);
+	shuffle_table(&mut table_train, config, handle_progress_event)?;
+	group_table_test(&mut table_test, config)?;
+	let group_column_index = find_group_column_index(&table_train, config)?;
 	Ok(DatasetTrainAndTest {
 		table_train,
 		table_test,
 		comparison_fraction: config.dataset.comparison_fraction,
+		group_column_index,
+	})
+}
+
+/// Check that `table_test` has the same columns as `table_train` and re-encode its enum columns using the variants from `table_train`. Values whose variants do not appear in `table_train` become invalid.
+fn conform_table_test(table_train: &Table, mut table_test: Table) -> Result<Table> {
+	if table_train.columns().len() != table_test.columns().len() {
+		bail!("Training data and test data must contain the same number of columns.")
+	}
+	for (column_train, column_test) in zip!(
+		table_train.columns().iter(),
+		table_test.columns_mut().iter_mut()
+	) {
+		if column_train.name() != column_test.name() {
+			bail!(
+				"Training data and test data must contain the same columns, but found \"{}\" in the test data where \"{}\" was expected.",
+				column_test.name().unwrap_or_default(),
+				column_train.name().unwrap_or_default(),
+			)
+		}
+		match (column_train, &*column_test) {
+			(TableColumn::Enum(column_train), TableColumn::Enum(column_test_enum)) => {
+				let data = column_test_enum
+					.iter()
+					.map(|value| {
+						value.and_then(|value| {
+							column_train
+								.value_for_variant(&column_test_enum.variants()[value.get() - 1])
+						})
+					})
+					.collect();
+				*column_test = TableColumn::Enum(EnumTableColumn::new(
+					column_test_enum.name().clone(),
+					column_train.variants().to_owned(),
+					data,
+				));
+			}
+			(TableColumn::Unknown(_), TableColumn::Unknown(_))
+			| (TableColumn::Number(_), TableColumn::Number(_))
+			| (TableColumn::Text(_), TableColumn::Text(_)) => {}
+			_ => bail!(
+				"The column \"{}\" has a different type in the test data than in the training data.",
+				column_train.name().unwrap_or_default(),
+			),
+		}
+	}
+	Ok(table_test)
+}
+
+/// Load a dataset for out of core training. The train file is read once to infer the column types and once more to compute the train stats, collect the target values, sample the train dataset, and collect the comparison and test datasets.
+#[allow(clippy::too_many_lines)]
+fn load_dataset_out_of_core(
+	file_path_train: PathBuf,
+	file_path_test: Option<&Path>,
+	config: &Config,
+	target_column_name: &str,
+	stats_settings: &StatsSettings,
+	handle_progress_event: &mut dyn FnMut(ProgressEvent),
+) -> Result<DatasetOutOfCore> {
+	let out_of_core = config.dataset.out_of_core.as_ref().unwrap();
+	let seed = config.dataset.shuffle.seed;
+	// Infer the column types with a first pass over the train file.
+	let column_types = Table::infer_column_types_from_path(
+		&file_path_train,
+		modelfox_table::FromCsvOptions {
+			column_types: column_types_from_config(config),
+			infer_options: Default::default(),
+			..Default::default()
+		},
+		&mut |progress_event| {
+			handle_progress_event(ProgressEvent::Load(LoadProgressEvent::Train(
+				progress_event,
+			)))
+		},
+	)?;
+	let mut chunks =
+		TableChunks::from_path(&file_path_train, &column_types, out_of_core.chunk_size)?;
+	let empty_table = chunks.empty_table();
+	let target_column_index = empty_table
+		.columns()
+		.iter()
+		.position(|column| column.name() == Some(target_column_name))
+		.ok_or_else(|| anyhow!("did not find target column \"{}\"", target_column_name))?;
+	let empty_table_labels = Table::new(
+		vec![Some(target_column_name.to_owned())],
+		vec![column_type(&empty_table.columns()[target_column_index])],
+	);
+	// If there is a test file, load it. Otherwise, set aside rows of the train file for the test dataset.
+	let (table_test, test_fraction) = if let Some(file_path_test) = file_path_test {
+		let mut table_test = Table::from_path(
+			file_path_test,
+			modelfox_table::FromCsvOptions {
+				column_types: Some(column_types.clone()),
+				infer_options: Default::default(),
+				..Default::default()
+			},
+			&mut |progress_event| {
+				handle_progress_event(ProgressEvent::Load(LoadProgressEvent::Test(progress_event)))
+			},
+		)?;
+		if table_test.columns().len() != empty_table.columns().len() {
+			bail!("Training data and test data must contain the same number of columns.")
+		}
+		drop_invalid_target_rows(&mut table_test, target_column_name, handle_progress_event);
+		(Some(table_test), 0.0)
+	} else {
+		(None, config.dataset.test_fraction)
+	};
+	let comparison_fraction = config.dataset.comparison_fraction;
+	let mut row_splitter = RowSplitter::new(seed, comparison_fraction, test_fraction);
+	let mut train_sample = Reservoir::new(empty_table.clone(), out_of_core.sample_size, seed);
+	let mut comparison = Reservoir::new(
+		empty_table.clone(),
+		out_of_core.max_comparison_rows,
+		seed.wrapping_add(1),
+	);
+	let mut test = Reservoir::new(
+		empty_table.clone(),
+		out_of_core.max_test_rows,
+		seed.wrapping_add(2),
+	);
+	let mut table_train_labels = empty_table_labels;
+	let mut train_stats = Stats::compute(&empty_table.view(), stats_settings, &mut |_| {});
+	let mut train_row_count = 0;
+	let mut n_invalid_target_rows = 0;
+	// Read the train file one chunk at a time.
+	let progress_counter = ProgressCounter::new(std::fs::metadata(&file_path_train)?.len());
+	handle_progress_event(ProgressEvent::Load(LoadProgressEvent::Train(
+		modelfox_table::ProgressEvent::LoadStarted(progress_counter.clone()),
+	)));
+	while let Some(chunk) = chunks.next() {
+		let chunk = chunk?;
+		let chunk = chunk.view();
+		let mut table_train = empty_table.clone();
+		for index in 0..chunk.nrows() {
+			let row_split = row_splitter.next_row_split();
+			if !target_is_valid(&chunk, target_column_index, index) {
+				n_invalid_target_rows += 1;
+				continue;
+			}
+			match row_split {
+				RowSplit::Train => {
+					table_train.push_row(&chunk, index);
+					train_sample.push(&chunk, index);
+				}
+				RowSplit::Comparison => comparison.push(&chunk, index),
+				RowSplit::Test => test.push(&chunk, index),
+			}
+		}
+		let table_train = table_train.view();
+		train_stats = train_stats.merge(Stats::compute(&table_train, stats_settings, &mut |_| {}));
+		let labels = table_train.view_columns(&[target_column_index]);
+		for index in 0..labels.nrows() {
+			table_train_labels.push_row(&labels, index);
+		}
+		train_row_count += table_train.nrows();
+		progress_counter.set(chunks.position());
+	}
+	handle_progress_event(ProgressEvent::Load(LoadProgressEvent::Train(
+		modelfox_table::ProgressEvent::LoadDone,
+	)));
+	if n_invalid_target_rows > 0 {
+		handle_progress_event(ProgressEvent::Warning(format!(
+			"Dropping {} row(s) with invalid values for the target column.",
+			n_invalid_target_rows
+		)));
+	}
+	Ok(DatasetOutOfCore {
+		file_path_train,
+		column_types,
+		target_column_index,
+		chunk_size: out_of_core.chunk_size,
+		seed,
+		comparison_fraction,
+		test_fraction,
+		train_row_count,
+		train_stats,
+		table_train_labels,
+		table_train_sample: train_sample.table,
+		table_comparison: comparison.table,
+		table_test: table_test.unwrap_or(test.table),
 	})
 }
 
@@ -848,13 +2145,29 @@ fn column_types_from_config(config: &Config) -> Option<BTreeMap<String, TableCol
 	)
 }
 
-/// Shuffle the table.
+/// Shuffle the table. If there is a group column, the rows of each query group are kept together.
 fn shuffle_table(
 	table: &mut Table,
 	config: &Config,
 	handle_progress_event: &mut dyn FnMut(ProgressEvent),
-) {
-	if config.dataset.shuffle.enable {
+) -> Result<()> {
+	if let Some(group_column_index) = find_group_column_index(table, config)? {
+		if config.dataset.shuffle.enable {
+			handle_progress_event(ProgressEvent::Load(LoadProgressEvent::Shuffle));
+		}
+		group::group_table(
+			table,
+			group_column_index,
+			config
+				.dataset
+				.shuffle
+				.enable
+				.then(|| config.dataset.shuffle.seed),
+		);
+		if config.dataset.shuffle.enable {
+			handle_progress_event(ProgressEvent::Load(LoadProgressEvent::ShuffleDone));
+		}
+	} else if config.dataset.shuffle.enable {
 		handle_progress_event(ProgressEvent::Load(LoadProgressEvent::Shuffle));
 		for column in table.columns_mut().iter_mut() {
 			let mut rng = Xoshiro256Plus::seed_from_u64(config.dataset.shuffle.seed);
@@ -867,13 +2180,46 @@ fn shuffle_table(
 		}
 		handle_progress_event(ProgressEvent::Load(LoadProgressEvent::ShuffleDone));
 	}
+	Ok(())
+}
+
+/// Make the rows of each query group in the test table contiguous. The test table is not shuffled.
+fn group_table_test(table: &mut Table, config: &Config) -> Result<()> {
+	if let Some(group_column_index) = find_group_column_index(table, config)? {
+		group::group_table(table, group_column_index, None);
+	}
+	Ok(())
+}
+
+/// Find the index of the group column in `table`, if one is configured.
+fn find_group_column_index(table: &Table, config: &Config) -> Result<Option<usize>> {
+	config
+		.dataset
+		.group_column
+		.as_ref()
+		.map(|group_column_name| {
+			table
+				.columns()
+				.iter()
+				.position(|column| column.name() == Some(group_column_name.as_str()))
+				.ok_or_else(|| {
+					anyhow!(
+						"did not find group column \"{}\" in the table",
+						group_column_name
+					)
+				})
+		})
+		.transpose()
 }
 
 fn compute_hyperparameter_grid(
 	config: &Config,
 	task: &Task,
 	target_column_index: usize,
+	group_column_index: Option<usize>,
+	ndcg_k: Option<usize>,
 	train_column_stats: &[ColumnStatsOutput],
+	word_embedding_models: &BTreeMap<String, modelfox_text::WordEmbeddingModel>,
 ) -> Vec<grid::GridItem> {
 	config
 		.train
@@ -884,12 +2230,14 @@ fn compute_hyperparameter_grid(
 				grid,
 				target_column_index,
 				train_column_stats,
+				word_embedding_models,
 				config,
 			),
 			Task::BinaryClassification => grid::compute_binary_classification_hyperparameter_grid(
 				grid,
 				target_column_index,
 				train_column_stats,
+				word_embedding_models,
 				config,
 			),
 			Task::MulticlassClassification { .. } => {
@@ -897,35 +2245,59 @@ fn compute_hyperparameter_grid(
 					grid,
 					target_column_index,
 					train_column_stats,
+					word_embedding_models,
 					config,
 				)
 			}
+			Task::Ranking => grid::compute_ranking_hyperparameter_grid(
+				grid,
+				target_column_index,
+				group_column_index.unwrap(),
+				ndcg_k,
+				train_column_stats,
+				word_embedding_models,
+				config,
+			),
 		})
 		.unwrap_or_else(|| match &task {
 			Task::Regression => grid::auto_regression_hyperparameter_grid(
 				target_column_index,
 				train_column_stats,
+				word_embedding_models,
 				config,
 			),
 			Task::BinaryClassification => grid::auto_binary_classification_hyperparameter_grid(
 				target_column_index,
 				train_column_stats,
+				word_embedding_models,
 				config,
 			),
 			Task::MulticlassClassification { .. } => {
 				grid::auto_multiclass_classification_hyperparameter_grid(
 					target_column_index,
 					train_column_stats,
+					word_embedding_models,
 					config,
 				)
 			}
+			Task::Ranking => grid::auto_ranking_hyperparameter_grid(
+				target_column_index,
+				group_column_index.unwrap(),
+				ndcg_k,
+				train_column_stats,
+				word_embedding_models,
+				config,
+			),
 		})
 }
 
+#[allow(clippy::too_many_arguments)]
 fn compute_baseline_metrics(
 	task: Task,
 	table_test: &TableView,
 	target_column_index: usize,
+	group_column_index: Option<usize>,
+	ndcg_k: Option<usize>,
 	train_target_column_stats: &ColumnStatsOutput,
 	test_target_column_stats: &ColumnStatsOutput,
 	progress: &impl Fn(),
@@ -1005,6 +2377,24 @@ fn compute_baseline_metrics(
 			}
 			Metrics::MulticlassClassification(metrics.finalize())
 		}
+		Task::Ranking => {
+			let labels = table_test.columns().get(target_column_index).unwrap();
+			let labels = labels.as_number().unwrap();
+			let groups =
+				group::compute_groups(table_test.columns()[group_column_index.unwrap()].clone());
+			// The baseline gives every row the same score, so each query group is ranked in its original order.
+			let predictions = vec![0.0; labels.len()];
+			let mut metrics = modelfox_metrics::RankingMetrics::new(ndcg_k);
+			for range in modelfox_metrics::group_ranges(&groups) {
+				metrics.update(modelfox_metrics::RankingMetricsInput {
+					predictions: &predictions[range.clone()],
+					labels: &labels.as_slice()[range.clone()],
+					groups: &groups[range.clone()],
+				});
+				range.for_each(|_| progress());
+			}
+			Metrics::Ranking(metrics.finalize())
+		}
 	}
 }
 
@@ -1020,20 +2410,40 @@ fn train_grid_item(
 	grid_item_count: usize,
 	grid_item_index: usize,
 	grid_item: grid::GridItem,
+	dataset: &Dataset,
+	init_model: Option<&InitModel>,
+	target_transform: Option<TargetTransform>,
 	table_train: &TableView,
 	table_comparison: &TableView,
 	comparison_metric: ComparisonMetric,
 	kill_chip: &KillChip,
 	handle_progress_event: &mut dyn FnMut(ProgressEvent),
-) -> TrainGridItemOutput {
+) -> Result<TrainGridItemOutput> {
 	let start = Instant::now();
-	let train_model_output = train_model(grid_item, table_train, kill_chip, &mut |progress| {
+	let handle_train_progress_event = &mut |progress| {
 		handle_progress_event(ProgressEvent::Train(TrainProgressEvent {
 			grid_item_index,
 			grid_item_count,
 			grid_item_progress_event: progress,
 		}))
-	});
+	};
+	let train_model_output = match dataset {
+		Dataset::OutOfCore(dataset) => train_model_out_of_core(
+			grid_item,
+			dataset,
+			target_transform,
+			kill_chip,
+			handle_train_progress_event,
+		)?,
+		_ => train_model(
+			grid_item,
+			table_train,
+			init_model,
+			target_transform,
+			kill_chip,
+			handle_train_progress_event,
+		),
+	};
 	let duration = start.elapsed();
 	let comparison_metrics =
 		compute_comparison_metrics(&train_model_output, table_comparison, &mut |progress| {
@@ -1060,6 +2470,10 @@ fn train_grid_item(
 			RegressionComparisonMetric::RootMeanSquaredError => "root mean squared error",
 			RegressionComparisonMetric::R2 => "r2",
 		},
+		ComparisonMetric::Ranking(rcm) => match rcm {
+			RankingComparisonMetric::Ndcg => "NDCG",
+			RankingComparisonMetric::Map => "MAP",
+		},
 	};
 	handle_progress_event(ProgressEvent::Info(format!(
 		"🎯 Model {} {}: {}",
@@ -1067,12 +2481,12 @@ fn train_grid_item(
 		comparison_metric_str,
 		comparison_metric_value
 	)));
-	TrainGridItemOutput {
+	Ok(TrainGridItemOutput {
 		train_model_output,
 		comparison_metrics,
 		comparison_metric_value,
 		duration,
-	}
+	})
 }
 
 fn get_comparison_metric_value(metrics: &Metrics, comparison_metric: ComparisonMetric) -> f32 {
@@ -1097,6 +2511,12 @@ fn get_comparison_metric_value(metrics: &Metrics, comparison_metric: ComparisonM
 		) => match comparison_metric {
 			MulticlassClassificationComparisonMetric::Accuracy => metrics.accuracy,
 		},
+		(ComparisonMetric::Ranking(comparison_metric), Metrics::Ranking(metrics)) => {
+			match comparison_metric {
+				RankingComparisonMetric::Ndcg => metrics.ndcg,
+				RankingComparisonMetric::Map => metrics.map,
+			}
+		}
 		_ => unreachable!(),
 	}
 }
@@ -1109,6 +2529,7 @@ pub enum TrainModelOutput {
 	TreeBinaryClassifier(TreeBinaryClassifierTrainModelOutput),
 	LinearMulticlassClassifier(LinearMulticlassClassifierTrainModelOutput),
 	TreeMulticlassClassifier(TreeMulticlassClassifierTrainModelOutput),
+	TreeRanker(TreeRankerTrainModelOutput),
 }
 
 #[derive(Clone, Debug)]
@@ -1116,6 +2537,8 @@ pub struct LinearRegressorTrainModelOutput {
 	pub model: modelfox_linear::Regressor,
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
 	pub target_column_index: usize,
+	/// The model predicts the target column transformed with this transform.
+	pub target_transform: Option<TargetTransform>,
 	pub losses: Option<Vec<f32>>,
 	pub train_options: modelfox_linear::TrainOptions,
 	pub feature_importances: Vec<f32>,
@@ -1126,6 +2549,8 @@ pub struct TreeRegressorTrainModelOutput {
 	pub model: modelfox_tree::Regressor,
 	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
 	pub target_column_index: usize,
+	/// The model predicts the target column transformed with this transform.
+	pub target_transform: Option<TargetTransform>,
 	pub losses: Option<Vec<f32>>,
 	pub train_options: modelfox_tree::TrainOptions,
 	pub feature_importances: Vec<f32>,
@@ -1171,9 +2596,23 @@ pub struct TreeMulticlassClassifierTrainModelOutput {
 	pub feature_importances: Vec<f32>,
 }
 
+#[derive(Clone, Debug)]
+pub struct TreeRankerTrainModelOutput {
+	pub model: modelfox_tree::Ranker,
+	pub feature_groups: Vec<modelfox_features::FeatureGroup>,
+	pub target_column_index: usize,
+	pub group_column_index: usize,
+	pub ndcg_k: Option<usize>,
+	pub losses: Option<Vec<f32>>,
+	pub train_options: modelfox_tree::TrainOptions,
+	pub feature_importances: Vec<f32>,
+}
+
 fn train_model(
 	grid_item: grid::GridItem,
 	table_train: &TableView,
+	init_model: Option<&InitModel>,
+	target_transform: Option<TargetTransform>,
 	kill_chip: &KillChip,
 	handle_progress_event: &mut dyn FnMut(TrainGridItemProgressEvent),
 ) -> TrainModelOutput {
@@ -1187,6 +2626,8 @@ fn train_model(
 			target_column_index,
 			feature_groups,
 			options,
+			init_model,
+			target_transform,
 			kill_chip,
 			handle_progress_event,
 		),
@@ -1199,6 +2640,8 @@ fn train_model(
 			target_column_index,
 			feature_groups,
 			options,
+			init_model,
+			target_transform,
 			kill_chip,
 			handle_progress_event,
 		),
@@ -1211,53 +2654,355 @@ fn train_model(
 			target_column_index,
 			feature_groups,
 			options,
+			init_model,
+			kill_chip,
+			handle_progress_event,
+		),
+		grid::GridItem::TreeBinaryClassifier {
+			target_column_index,
+			feature_groups,
+			options,
+		} => train_tree_binary_classifier(
+			table_train,
+			target_column_index,
+			feature_groups,
+			options,
+			init_model,
+			kill_chip,
+			handle_progress_event,
+		),
+		grid::GridItem::LinearMulticlassClassifier {
+			target_column_index,
+			feature_groups,
+			options,
+		} => train_linear_multiclass_classifier(
+			table_train,
+			target_column_index,
+			feature_groups,
+			options,
+			init_model,
+			kill_chip,
+			handle_progress_event,
+		),
+		grid::GridItem::TreeMulticlassClassifier {
+			target_column_index,
+			feature_groups,
+			options,
+		} => train_tree_multiclass_classifier(
+			table_train,
+			target_column_index,
+			feature_groups,
+			options,
+			init_model,
+			kill_chip,
+			handle_progress_event,
+		),
+		grid::GridItem::TreeRanker {
+			target_column_index,
+			group_column_index,
+			ndcg_k,
+			feature_groups,
+			options,
+		} => train_tree_ranker(
+			table_train,
+			target_column_index,
+			group_column_index,
+			ndcg_k,
+			feature_groups,
+			options,
 			kill_chip,
 			handle_progress_event,
 		),
-		grid::GridItem::TreeBinaryClassifier {
+	}
+}
+
+/// Train a model for out of core training. Tree models compute their bin thresholds from the sample of the train dataset and bin the features one chunk at a time. Linear models read the train dataset in chunks once per epoch.
+#[allow(clippy::too_many_lines)]
+fn train_model_out_of_core(
+	grid_item: grid::GridItem,
+	dataset: &DatasetOutOfCore,
+	target_transform: Option<TargetTransform>,
+	kill_chip: &KillChip,
+	handle_progress_event: &mut dyn FnMut(TrainGridItemProgressEvent),
+) -> Result<TrainModelOutput> {
+	let table_train_labels = dataset.table_train_labels.view();
+	let train_labels = table_train_labels.columns().first().unwrap();
+	let train_model_output = match grid_item {
+		grid::GridItem::LinearRegressor {
 			target_column_index,
 			feature_groups,
 			options,
-		} => train_tree_binary_classifier(
-			table_train,
+		} => {
+			let linear_options = compute_linear_options(&options);
+			let progress = &mut |progress| {
+				handle_progress_event(TrainGridItemProgressEvent::TrainModel(
+					ModelTrainProgressEvent::Linear(progress),
+				))
+			};
+			let progress = modelfox_linear::Progress {
+				kill_chip,
+				handle_progress_event: progress,
+			};
+			let train_output = modelfox_linear::Regressor::train_out_of_core(
+				feature_groups.iter().map(|f| f.n_features()).sum(),
+				|| {
+					linear_train_chunks(
+						dataset,
+						&feature_groups,
+						target_column_index,
+						move |labels| {
+							transform_labels(labels.as_number().unwrap(), target_transform)
+								.view()
+								.as_slice()
+								.to_owned()
+						},
+					)
+				},
+				&linear_options,
+				progress,
+			)?;
+			TrainModelOutput::LinearRegressor(LinearRegressorTrainModelOutput {
+				model: train_output.model,
+				feature_groups,
+				target_column_index,
+				target_transform,
+				train_options: linear_options,
+				losses: train_output.losses,
+				feature_importances: train_output.feature_importances.unwrap(),
+			})
+		}
+		grid::GridItem::LinearBinaryClassifier {
 			target_column_index,
 			feature_groups,
 			options,
-			kill_chip,
-			handle_progress_event,
-		),
+		} => {
+			let linear_options = compute_linear_options(&options);
+			let progress = &mut |progress| {
+				handle_progress_event(TrainGridItemProgressEvent::TrainModel(
+					ModelTrainProgressEvent::Linear(progress),
+				))
+			};
+			let progress = modelfox_linear::Progress {
+				kill_chip,
+				handle_progress_event: progress,
+			};
+			let train_output = modelfox_linear::BinaryClassifier::train_out_of_core(
+				feature_groups.iter().map(|f| f.n_features()).sum(),
+				|| {
+					linear_train_chunks(dataset, &feature_groups, target_column_index, |labels| {
+						labels.as_enum().unwrap().as_slice().to_owned()
+					})
+				},
+				&linear_options,
+				progress,
+			)?;
+			TrainModelOutput::LinearBinaryClassifier(LinearBinaryClassifierTrainModelOutput {
+				model: train_output.model,
+				feature_groups,
+				target_column_index,
+				train_options: linear_options,
+				losses: train_output.losses,
+				feature_importances: train_output.feature_importances.unwrap(),
+			})
+		}
 		grid::GridItem::LinearMulticlassClassifier {
 			target_column_index,
 			feature_groups,
 			options,
-		} => train_linear_multiclass_classifier(
-			table_train,
+		} => {
+			let linear_options = compute_linear_options(&options);
+			let progress = &mut |progress| {
+				handle_progress_event(TrainGridItemProgressEvent::TrainModel(
+					ModelTrainProgressEvent::Linear(progress),
+				))
+			};
+			let progress = modelfox_linear::Progress {
+				kill_chip,
+				handle_progress_event: progress,
+			};
+			let train_output = modelfox_linear::MulticlassClassifier::train_out_of_core(
+				feature_groups.iter().map(|f| f.n_features()).sum(),
+				train_labels.as_enum().unwrap().variants().len(),
+				|| {
+					linear_train_chunks(dataset, &feature_groups, target_column_index, |labels| {
+						labels.as_enum().unwrap().as_slice().to_owned()
+					})
+				},
+				&linear_options,
+				progress,
+			)?;
+			TrainModelOutput::LinearMulticlassClassifier(
+				LinearMulticlassClassifierTrainModelOutput {
+					model: train_output.model,
+					feature_groups,
+					target_column_index,
+					train_options: linear_options,
+					losses: train_output.losses,
+					feature_importances: train_output.feature_importances.unwrap(),
+				},
+			)
+		}
+		grid::GridItem::TreeRegressor {
 			target_column_index,
 			feature_groups,
 			options,
-			kill_chip,
-			handle_progress_event,
-		),
-		grid::GridItem::TreeMulticlassClassifier {
+		} => {
+			let tree_options = compute_tree_options(&options);
+			let progress = &mut |progress| {
+				handle_progress_event(TrainGridItemProgressEvent::TrainModel(
+					ModelTrainProgressEvent::Tree(progress),
+				))
+			};
+			let progress = modelfox_tree::Progress {
+				kill_chip,
+				handle_progress_event: progress,
+			};
+			let features_sample = modelfox_features::compute_features_table(
+				&dataset.table_train_sample.view(),
+				&feature_groups,
+				&|_| {},
+			);
+			let train_labels =
+				transform_labels(train_labels.as_number().unwrap(), target_transform);
+			let train_output = modelfox_tree::Regressor::train_out_of_core(
+				features_sample.view(),
+				tree_train_chunks(dataset, &feature_groups),
+				train_labels.view(),
+				&tree_options,
+				progress,
+			)?;
+			TrainModelOutput::TreeRegressor(TreeRegressorTrainModelOutput {
+				model: train_output.model,
+				feature_groups,
+				target_column_index,
+				target_transform,
+				train_options: tree_options,
+				losses: train_output.losses,
+				feature_importances: train_output.feature_importances.unwrap(),
+			})
+		}
+		grid::GridItem::TreeBinaryClassifier {
 			target_column_index,
 			feature_groups,
 			options,
-		} => train_tree_multiclass_classifier(
-			table_train,
+		} => {
+			let tree_options = compute_tree_options(&options);
+			let progress = &mut |progress| {
+				handle_progress_event(TrainGridItemProgressEvent::TrainModel(
+					ModelTrainProgressEvent::Tree(progress),
+				))
+			};
+			let progress = modelfox_tree::Progress {
+				kill_chip,
+				handle_progress_event: progress,
+			};
+			let features_sample = modelfox_features::compute_features_table(
+				&dataset.table_train_sample.view(),
+				&feature_groups,
+				&|_| {},
+			);
+			let train_output = modelfox_tree::BinaryClassifier::train_out_of_core(
+				features_sample.view(),
+				tree_train_chunks(dataset, &feature_groups),
+				train_labels.as_enum().unwrap(),
+				&tree_options,
+				progress,
+			)?;
+			TrainModelOutput::TreeBinaryClassifier(TreeBinaryClassifierTrainModelOutput {
+				model: train_output.model,
+				feature_groups,
+				target_column_index,
+				train_options: tree_options,
+				losses: train_output.losses,
+				feature_importances: train_output.feature_importances.unwrap(),
+			})
+		}
+		grid::GridItem::TreeMulticlassClassifier {
 			target_column_index,
 			feature_groups,
 			options,
-			kill_chip,
-			handle_progress_event,
-		),
-	}
+		} => {
+			let tree_options = compute_tree_options(&options);
+			let progress = &mut |progress| {
+				handle_progress_event(TrainGridItemProgressEvent::TrainModel(
+					ModelTrainProgressEvent::Tree(progress),
+				))
+			};
+			let progress = modelfox_tree::Progress {
+				kill_chip,
+				handle_progress_event: progress,
+			};
+			let features_sample = modelfox_features::compute_features_table(
+				&dataset.table_train_sample.view(),
+				&feature_groups,
+				&|_| {},
+			);
+			let train_output = modelfox_tree::MulticlassClassifier::train_out_of_core(
+				features_sample.view(),
+				tree_train_chunks(dataset, &feature_groups),
+				train_labels.as_enum().unwrap(),
+				&tree_options,
+				progress,
+			)?;
+			TrainModelOutput::TreeMulticlassClassifier(TreeMulticlassClassifierTrainModelOutput {
+				model: train_output.model,
+				feature_groups,
+				target_column_index,
+				train_options: tree_options,
+				losses: train_output.losses,
+				feature_importances: train_output.feature_importances.unwrap(),
+			})
+		}
+		grid::GridItem::TreeRanker { .. } => {
+			bail!("Training a ranker is not supported with out of core training.")
+		}
+	};
+	Ok(train_model_output)
+}
+
+/// Read the train dataset in chunks and compute the features and labels for each chunk to train a linear model.
+fn linear_train_chunks<'a, Label>(
+	dataset: &'a DatasetOutOfCore,
+	feature_groups: &'a [modelfox_features::FeatureGroup],
+	target_column_index: usize,
+	labels: impl Fn(&TableColumnView) -> Vec<Label> + 'a,
+) -> impl Iterator<Item = Result<modelfox_linear::TrainChunk<Label>>> + 'a
+where
+	Label: 'a,
+{
+	dataset.train_chunks().map(move |chunk| {
+		let chunk = chunk?;
+		let chunk = chunk.view();
+		let features =
+			modelfox_features::compute_features_array_f32(&chunk, feature_groups, &|| {});
+		let labels = Array::from(labels(&chunk.columns()[target_column_index]));
+		Ok((features, labels))
+	})
+}
+
+/// Read the train dataset in chunks and compute the features for each chunk to train a tree model.
+fn tree_train_chunks<'a>(
+	dataset: &'a DatasetOutOfCore,
+	feature_groups: &'a [modelfox_features::FeatureGroup],
+) -> impl Iterator<Item = Result<Table>> + 'a {
+	dataset.train_chunks().map(move |chunk| {
+		let chunk = chunk?;
+		Ok(modelfox_features::compute_features_table(
+			&chunk.view(),
+			feature_groups,
+			&|_| {},
+		))
+	})
 }
 
+#[allow(clippy::too_many_arguments)]
 fn train_linear_regressor(
 	table_train: &TableView,
 	target_column_index: usize,
 	feature_groups: Vec<modelfox_features::FeatureGroup>,
 	options: grid::LinearModelTrainOptions,
+	init_model: Option<&InitModel>,
+	target_transform: Option<TargetTransform>,
 	kill_chip: &KillChip,
 	handle_progress_event: &mut dyn FnMut(TrainGridItemProgressEvent),
 ) -> TrainModelOutput {
@@ -1279,6 +3024,7 @@ fn train_linear_regressor(
 		.unwrap()
 		.as_number()
 		.unwrap();
+	let labels = transform_labels(labels, target_transform);
 	let linear_options = compute_linear_options(&options);
 	let progress = &mut |progress| {
 		handle_progress_event(TrainGridItemProgressEvent::TrainModel(
@@ -1289,23 +3035,42 @@ fn train_linear_regressor(
 		kill_chip,
 		handle_progress_event: progress,
 	};
-	let train_output =
-		modelfox_linear::Regressor::train(features.view(), labels, &linear_options, progress);
+	let train_output = match init_model.map(|init_model| &init_model.model) {
+		Some(InitModelInner::LinearRegressor(init_model)) => {
+			modelfox_linear::Regressor::train_warm_start(
+				init_model,
+				features.view(),
+				labels.view(),
+				&linear_options,
+				progress,
+			)
+		}
+		_ => modelfox_linear::Regressor::train(
+			features.view(),
+			labels.view(),
+			&linear_options,
+			progress,
+		),
+	};
 	TrainModelOutput::LinearRegressor(LinearRegressorTrainModelOutput {
 		model: train_output.model,
 		feature_groups,
 		target_column_index,
+		target_transform,
 		train_options: linear_options,
 		losses: train_output.losses,
 		feature_importances: train_output.feature_importances.unwrap(),
 	})
 }
 
+#[allow(clippy::too_many_arguments)]
 fn train_tree_regressor(
 	table_train: &TableView,
 	target_column_index: usize,
 	feature_groups: Vec<modelfox_features::FeatureGroup>,
 	options: grid::TreeModelTrainOptions,
+	init_model: Option<&InitModel>,
+	target_transform: Option<TargetTransform>,
 	kill_chip: &KillChip,
 	handle_progress_event: &mut dyn FnMut(TrainGridItemProgressEvent),
 ) -> TrainModelOutput {
@@ -1325,8 +3090,8 @@ fn train_tree_regressor(
 		.get(target_column_index)
 		.unwrap()
 		.as_number()
-		.unwrap()
-		.clone();
+		.unwrap();
+	let labels = transform_labels(labels, target_transform);
 	let tree_options = compute_tree_options(&options);
 	let progress = &mut |progress| {
 		handle_progress_event(TrainGridItemProgressEvent::TrainModel(
@@ -1337,23 +3102,52 @@ fn train_tree_regressor(
 		kill_chip,
 		handle_progress_event: progress,
 	};
-	let train_output =
-		modelfox_tree::Regressor::train(features.view(), labels, &tree_options, progress);
+	let train_output = match init_model.map(|init_model| &init_model.model) {
+		Some(InitModelInner::TreeRegressor(init_model)) => {
+			modelfox_tree::Regressor::train_warm_start(
+				init_model,
+				features.view(),
+				labels.view(),
+				&tree_options,
+				progress,
+			)
+		}
+		_ => {
+			modelfox_tree::Regressor::train(features.view(), labels.view(), &tree_options, progress)
+		}
+	};
 	TrainModelOutput::TreeRegressor(TreeRegressorTrainModelOutput {
 		model: train_output.model,
 		feature_groups,
 		target_column_index,
+		target_transform,
 		train_options: tree_options,
 		losses: train_output.losses,
 		feature_importances: train_output.feature_importances.unwrap(),
 	})
 }
 
+/// Apply the target transform, if there is one, to the labels of a regressor.
+fn transform_labels(
+	labels: NumberTableColumnView,
+	target_transform: Option<TargetTransform>,
+) -> NumberTableColumn {
+	let values = match target_transform {
+		Some(target_transform) => labels
+			.iter()
+			.map(|label| target_transform.transform(*label))
+			.collect(),
+		None => labels.as_slice().to_owned(),
+	};
+	NumberTableColumn::new(labels.name().map(|name| name.to_owned()), values)
+}
+
 fn train_linear_binary_classifier(
 	table_train: &TableView,
 	target_column_index: usize,
 	feature_groups: Vec<modelfox_features::FeatureGroup>,
 	options: grid::LinearModelTrainOptions,
+	init_model: Option<&InitModel>,
 	kill_chip: &KillChip,
 	handle_progress_event: &mut dyn FnMut(TrainGridItemProgressEvent),
 ) -> TrainModelOutput {
@@ -1385,12 +3179,23 @@ fn train_linear_binary_classifier(
 		kill_chip,
 		handle_progress_event: progress,
 	};
-	let train_output = modelfox_linear::BinaryClassifier::train(
-		features.view(),
-		labels,
-		&linear_options,
-		progress,
-	);
+	let train_output = match init_model.map(|init_model| &init_model.model) {
+		Some(InitModelInner::LinearBinaryClassifier(init_model)) => {
+			modelfox_linear::BinaryClassifier::train_warm_start(
+				init_model,
+				features.view(),
+				labels,
+				&linear_options,
+				progress,
+			)
+		}
+		_ => modelfox_linear::BinaryClassifier::train(
+			features.view(),
+			labels,
+			&linear_options,
+			progress,
+		),
+	};
 	TrainModelOutput::LinearBinaryClassifier(LinearBinaryClassifierTrainModelOutput {
 		model: train_output.model,
 		feature_groups,
@@ -1406,6 +3211,7 @@ fn train_tree_binary_classifier(
 	target_column_index: usize,
 	feature_groups: Vec<modelfox_features::FeatureGroup>,
 	options: grid::TreeModelTrainOptions,
+	init_model: Option<&InitModel>,
 	kill_chip: &KillChip,
 	handle_progress_event: &mut dyn FnMut(TrainGridItemProgressEvent),
 ) -> TrainModelOutput {
@@ -1437,8 +3243,20 @@ fn train_tree_binary_classifier(
 		kill_chip,
 		handle_progress_event: progress,
 	};
-	let train_output =
-		modelfox_tree::BinaryClassifier::train(features.view(), labels, &tree_options, progress);
+	let train_output = match init_model.map(|init_model| &init_model.model) {
+		Some(InitModelInner::TreeBinaryClassifier(init_model)) => {
+			modelfox_tree::BinaryClassifier::train_warm_start(
+				init_model,
+				features.view(),
+				labels,
+				&tree_options,
+				progress,
+			)
+		}
+		_ => {
+			modelfox_tree::BinaryClassifier::train(features.view(), labels, &tree_options, progress)
+		}
+	};
 	TrainModelOutput::TreeBinaryClassifier(TreeBinaryClassifierTrainModelOutput {
 		model: train_output.model,
 		feature_groups,
@@ -1454,6 +3272,7 @@ fn train_linear_multiclass_classifier(
 	target_column_index: usize,
 	feature_groups: Vec<modelfox_features::FeatureGroup>,
 	options: grid::LinearModelTrainOptions,
+	init_model: Option<&InitModel>,
 	kill_chip: &KillChip,
 	handle_progress_event: &mut dyn FnMut(TrainGridItemProgressEvent),
 ) -> TrainModelOutput {
@@ -1485,12 +3304,23 @@ fn train_linear_multiclass_classifier(
 		kill_chip,
 		handle_progress_event: progress,
 	};
-	let train_output = modelfox_linear::MulticlassClassifier::train(
-		features.view(),
-		labels,
-		&linear_options,
-		progress,
-	);
+	let train_output = match init_model.map(|init_model| &init_model.model) {
+		Some(InitModelInner::LinearMulticlassClassifier(init_model)) => {
+			modelfox_linear::MulticlassClassifier::train_warm_start(
+				init_model,
+				features.view(),
+				labels,
+				&linear_options,
+				progress,
+			)
+		}
+		_ => modelfox_linear::MulticlassClassifier::train(
+			features.view(),
+			labels,
+			&linear_options,
+			progress,
+		),
+	};
 	TrainModelOutput::LinearMulticlassClassifier(LinearMulticlassClassifierTrainModelOutput {
 		model: train_output.model,
 		feature_groups,
@@ -1506,6 +3336,7 @@ fn train_tree_multiclass_classifier(
 	target_column_index: usize,
 	feature_groups: Vec<modelfox_features::FeatureGroup>,
 	options: grid::TreeModelTrainOptions,
+	init_model: Option<&InitModel>,
 	kill_chip: &KillChip,
 	handle_progress_event: &mut dyn FnMut(TrainGridItemProgressEvent),
 ) -> TrainModelOutput {
@@ -1537,16 +3368,86 @@ fn train_tree_multiclass_classifier(
 		kill_chip,
 		handle_progress_event: progress,
 	};
-	let train_output = modelfox_tree::MulticlassClassifier::train(
+	let train_output = match init_model.map(|init_model| &init_model.model) {
+		Some(InitModelInner::TreeMulticlassClassifier(init_model)) => {
+			modelfox_tree::MulticlassClassifier::train_warm_start(
+				init_model,
+				features.view(),
+				labels,
+				&tree_options,
+				progress,
+			)
+		}
+		_ => modelfox_tree::MulticlassClassifier::train(
+			features.view(),
+			labels,
+			&tree_options,
+			progress,
+		),
+	};
+	TrainModelOutput::TreeMulticlassClassifier(TreeMulticlassClassifierTrainModelOutput {
+		model: train_output.model,
+		feature_groups,
+		target_column_index,
+		train_options: tree_options,
+		losses: train_output.losses,
+		feature_importances: train_output.feature_importances.unwrap(),
+	})
+}
+
+#[allow(clippy::too_many_arguments)]
+fn train_tree_ranker(
+	table_train: &TableView,
+	target_column_index: usize,
+	group_column_index: usize,
+	ndcg_k: Option<usize>,
+	feature_groups: Vec<modelfox_features::FeatureGroup>,
+	options: grid::TreeModelTrainOptions,
+	kill_chip: &KillChip,
+	handle_progress_event: &mut dyn FnMut(TrainGridItemProgressEvent),
+) -> TrainModelOutput {
+	let n_features = feature_groups.iter().map(|f| f.n_features()).sum::<usize>();
+	let n_features = n_features.to_u64().unwrap();
+	let n_rows = table_train.nrows().to_u64().unwrap();
+	let progress_counter = ProgressCounter::new(n_features * n_rows);
+	handle_progress_event(TrainGridItemProgressEvent::ComputeFeatures(
+		progress_counter.clone(),
+	));
+	let features = modelfox_features::compute_features_table(table_train, &feature_groups, &|i| {
+		progress_counter.inc(i)
+	});
+	handle_progress_event(TrainGridItemProgressEvent::ComputeFeaturesDone);
+	let labels = table_train
+		.columns()
+		.get(target_column_index)
+		.unwrap()
+		.as_number()
+		.unwrap();
+	let groups = group::compute_groups(table_train.columns()[group_column_index].clone());
+	let tree_options = compute_tree_options(&options);
+	let progress = &mut |progress| {
+		handle_progress_event(TrainGridItemProgressEvent::TrainModel(
+			ModelTrainProgressEvent::Tree(progress),
+		))
+	};
+	let progress = modelfox_tree::Progress {
+		kill_chip,
+		handle_progress_event: progress,
+	};
+	let train_output = modelfox_tree::Ranker::train(
 		features.view(),
 		labels,
+		&groups,
+		ndcg_k,
 		&tree_options,
 		progress,
 	);
-	TrainModelOutput::TreeMulticlassClassifier(TreeMulticlassClassifierTrainModelOutput {
+	TrainModelOutput::TreeRanker(TreeRankerTrainModelOutput {
 		model: train_output.model,
 		feature_groups,
 		target_column_index,
+		group_column_index,
+		ndcg_k,
 		train_options: tree_options,
 		losses: train_output.losses,
 		feature_importances: train_output.feature_importances.unwrap(),
@@ -1560,12 +3461,43 @@ fn compute_linear_options(
 		compute_losses: true,
 		..Default::default()
 	};
+	if let Some(l1_regularization) = options.l1_regularization {
+		linear_options.l1_regularization = l1_regularization;
+	}
 	if let Some(l2_regularization) = options.l2_regularization {
 		linear_options.l2_regularization = l2_regularization;
 	}
 	if let Some(learning_rate) = options.learning_rate {
 		linear_options.learning_rate = learning_rate;
 	}
+	if let Some(learning_rate_schedule) = options.learning_rate_schedule {
+		linear_options.learning_rate_schedule = match learning_rate_schedule {
+			grid::LearningRateSchedule::Constant => modelfox_linear::LearningRateSchedule::Constant,
+			grid::LearningRateSchedule::InverseTimeDecay { decay_rate } => {
+				modelfox_linear::LearningRateSchedule::InverseTimeDecay { decay_rate }
+			}
+			grid::LearningRateSchedule::ExponentialDecay { decay_rate } => {
+				modelfox_linear::LearningRateSchedule::ExponentialDecay { decay_rate }
+			}
+		};
+	}
+	if let Some(optimizer) = options.optimizer {
+		linear_options.optimizer = match optimizer {
+			grid::LinearOptimizer::Sgd => modelfox_linear::Optimizer::Sgd,
+			grid::LinearOptimizer::Momentum { momentum } => {
+				modelfox_linear::Optimizer::Momentum { momentum }
+			}
+			grid::LinearOptimizer::Adam {
+				beta_1,
+				beta_2,
+				epsilon,
+			} => modelfox_linear::Optimizer::Adam {
+				beta_1,
+				beta_2,
+				epsilon,
+			},
+		};
+	}
 	if let Some(max_epochs) = options.max_epochs {
 		linear_options.max_epochs = max_epochs.to_usize().unwrap();
 	}
@@ -1588,6 +3520,19 @@ fn compute_tree_options(options: &grid::TreeModelTrainOptions) -> modelfox_tree:
 		compute_losses: true,
 		..Default::default()
 	};
+	if let Some(bagging_fraction) = options.bagging_fraction {
+		tree_options.bagging_fraction = bagging_fraction;
+	}
+	if let Some(bagging_frequency) = options.bagging_frequency {
+		tree_options.bagging_frequency = bagging_frequency.to_usize().unwrap();
+	}
+	if let Some(dart_options) = options.dart_options.as_ref() {
+		tree_options.dart_options = Some(modelfox_tree::DartOptions {
+			drop_rate: dart_options.drop_rate,
+			max_drop: dart_options.max_drop.to_usize().unwrap(),
+			skip_drop: dart_options.skip_drop,
+		})
+	}
 	if let Some(early_stopping_options) = options.early_stopping_options.as_ref() {
 		tree_options.early_stopping_options = Some(modelfox_tree::EarlyStoppingOptions {
 			early_stopping_fraction: early_stopping_options.early_stopping_fraction,
@@ -1596,6 +3541,18 @@ fn compute_tree_options(options: &grid::TreeModelTrainOptions) -> modelfox_tree:
 				.early_stopping_threshold,
 		})
 	}
+	if let Some(feature_fraction_per_node) = options.feature_fraction_per_node {
+		tree_options.feature_fraction_per_node = feature_fraction_per_node;
+	}
+	if let Some(feature_fraction_per_tree) = options.feature_fraction_per_tree {
+		tree_options.feature_fraction_per_tree = feature_fraction_per_tree;
+	}
+	if let Some(goss_options) = options.goss_options.as_ref() {
+		tree_options.goss_options = Some(modelfox_tree::GossOptions {
+			top_rate: goss_options.top_rate,
+			other_rate: goss_options.other_rate,
+		})
+	}
 	if let Some(l2_regularization_for_continuous_splits) =
 		options.l2_regularization_for_continuous_splits
 	{
@@ -1639,6 +3596,9 @@ fn compute_tree_options(options: &grid::TreeModelTrainOptions) -> modelfox_tree:
 	if let Some(min_sum_hessians_per_node) = options.min_sum_hessians_per_node {
 		tree_options.min_sum_hessians_per_node = min_sum_hessians_per_node;
 	}
+	if let Some(seed) = options.seed {
+		tree_options.seed = seed;
+	}
 	if let Some(smoothing_factor_for_discrete_bin_sorting) =
 		options.smoothing_factor_for_discrete_bin_sorting
 	{
@@ -1714,6 +3674,24 @@ fn choose_comparison_metric(config: &Config, task: &Task) -> Result<ComparisonMe
 				))
 			}
 		}
+		Task::Ranking => {
+			if let Some(comparison_metric) = &config.train.comparison_metric {
+				match comparison_metric {
+					config::ComparisonMetric::Ndcg => {
+						Ok(ComparisonMetric::Ranking(RankingComparisonMetric::Ndcg))
+					}
+					config::ComparisonMetric::Map => {
+						Ok(ComparisonMetric::Ranking(RankingComparisonMetric::Map))
+					}
+					metric => Err(anyhow!(
+						"{} is an invalid comparison metric for ranking",
+						metric,
+					)),
+				}
+			} else {
+				Ok(ComparisonMetric::Ranking(RankingComparisonMetric::Ndcg))
+			}
+		}
 	}
 }
 
@@ -1727,6 +3705,7 @@ fn compute_comparison_metrics(
 			let LinearRegressorTrainModelOutput {
 				target_column_index,
 				feature_groups,
+				target_transform,
 				model,
 				..
 			} = &train_model_output;
@@ -1734,6 +3713,7 @@ fn compute_comparison_metrics(
 				table_comparison,
 				*target_column_index,
 				feature_groups,
+				*target_transform,
 				model,
 				handle_progress_event,
 			);
@@ -1743,6 +3723,7 @@ fn compute_comparison_metrics(
 			let TreeRegressorTrainModelOutput {
 				target_column_index,
 				feature_groups,
+				target_transform,
 				model,
 				..
 			} = &train_model_output;
@@ -1750,6 +3731,7 @@ fn compute_comparison_metrics(
 				table_comparison,
 				*target_column_index,
 				feature_groups,
+				*target_transform,
 				model,
 				handle_progress_event,
 			);
@@ -1819,6 +3801,26 @@ fn compute_comparison_metrics(
 			);
 			Metrics::MulticlassClassification(metrics)
 		}
+		TrainModelOutput::TreeRanker(train_model_output) => {
+			let TreeRankerTrainModelOutput {
+				target_column_index,
+				group_column_index,
+				ndcg_k,
+				feature_groups,
+				model,
+				..
+			} = &train_model_output;
+			let metrics = test::test_tree_ranker(
+				table_comparison,
+				*target_column_index,
+				*group_column_index,
+				*ndcg_k,
+				feature_groups,
+				model,
+				handle_progress_event,
+			);
+			Metrics::Ranking(metrics)
+		}
 	}
 }
 
@@ -1836,6 +3838,9 @@ fn choose_best_model(
 		ComparisonMetric::MulticlassClassification(comparison_metric) => {
 			choose_best_model_multiclass_classification(outputs, comparison_metric)
 		}
+		ComparisonMetric::Ranking(comparison_metric) => {
+			choose_best_model_ranking(outputs, comparison_metric)
+		}
 	}
 }
 
@@ -1921,6 +3926,33 @@ fn choose_best_model_multiclass_classification(
 		.unwrap())
 }
 
+fn choose_best_model_ranking(
+	outputs: &[TrainGridItemOutput],
+	comparison_metric: &RankingComparisonMetric,
+) -> Result<(TrainModelOutput, usize)> {
+	Ok(outputs
+		.iter()
+		.enumerate()
+		.max_by(|(_, output_a), (_, output_b)| {
+			let metrics_a = match &output_a.comparison_metrics {
+				Metrics::Ranking(metrics) => metrics,
+				_ => unreachable!(),
+			};
+			let metrics_b = match &output_b.comparison_metrics {
+				Metrics::Ranking(metrics) => metrics,
+				_ => unreachable!(),
+			};
+			match comparison_metric {
+				RankingComparisonMetric::Ndcg => {
+					metrics_a.ndcg.partial_cmp(&metrics_b.ndcg).unwrap()
+				}
+				RankingComparisonMetric::Map => metrics_a.map.partial_cmp(&metrics_b.map).unwrap(),
+			}
+		})
+		.map(|(index, output)| (output.train_model_output.clone(), index))
+		.unwrap())
+}
+
 fn test_model(
 	train_model_output: &TrainModelOutput,
 	table_test: &TableView,
@@ -1931,6 +3963,7 @@ fn test_model(
 			let LinearRegressorTrainModelOutput {
 				target_column_index,
 				feature_groups,
+				target_transform,
 				model,
 				..
 			} = &train_model_output;
@@ -1938,6 +3971,7 @@ fn test_model(
 				table_test,
 				*target_column_index,
 				feature_groups,
+				*target_transform,
 				model,
 				handle_progress_event,
 			);
@@ -1947,6 +3981,7 @@ fn test_model(
 			let TreeRegressorTrainModelOutput {
 				target_column_index,
 				feature_groups,
+				target_transform,
 				model,
 				..
 			} = &train_model_output;
@@ -1954,6 +3989,7 @@ fn test_model(
 				table_test,
 				*target_column_index,
 				feature_groups,
+				*target_transform,
 				model,
 				handle_progress_event,
 			);
@@ -2023,5 +4059,25 @@ fn test_model(
 			);
 			Metrics::MulticlassClassification(test_metrics)
 		}
+		TrainModelOutput::TreeRanker(train_model_output) => {
+			let TreeRankerTrainModelOutput {
+				target_column_index,
+				group_column_index,
+				ndcg_k,
+				feature_groups,
+				model,
+				..
+			} = &train_model_output;
+			let test_metrics = test::test_tree_ranker(
+				table_test,
+				*target_column_index,
+				*group_column_index,
+				*ndcg_k,
+				feature_groups,
+				model,
+				handle_progress_event,
+			);
+			Metrics::Ranking(test_metrics)
+		}
 	}
 }
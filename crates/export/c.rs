@@ -0,0 +1,416 @@
+/*!
+This module generates a standalone C source file for a lowered model. The file defines a `modelfox_input` struct with a field for each of the model's columns, a `modelfox_output` struct, and a `modelfox_predict` function. It only depends on the C standard library, so it can be compiled into any program with `-lm`.
+*/
+
+use crate::codegen::{identifiers, n_features, write_tree, Writer};
+use crate::lower::{Estimator, FeatureGroup, Input, LoweredModel, Task};
+use anyhow::{bail, Result};
+use std::fmt::Write;
+
+const KEYWORDS: &[&str] = &[
+	"auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else",
+	"enum", "errno", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+	"restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+	"union", "unsigned", "void", "volatile", "while",
+];
+
+/// These are the names of the `modelfox_input` struct's fields, in the order of the model's inputs.
+pub fn field_names(model: &LoweredModel) -> Vec<String> {
+	identifiers(model.inputs.iter().map(|input| input.name()), KEYWORDS)
+}
+
+pub fn float_literal(value: f32) -> String {
+	if value.is_nan() {
+		"NAN".to_owned()
+	} else if value == f32::INFINITY {
+		"INFINITY".to_owned()
+	} else if value == f32::NEG_INFINITY {
+		"-INFINITY".to_owned()
+	} else {
+		format!("{:?}f", value)
+	}
+}
+
+/// Write `value` as a C string literal. Bytes outside of printable ASCII are written as octal escapes so the literal has the same bytes as `value` regardless of the compiler's source character set.
+pub fn string_literal(value: &str) -> String {
+	let mut literal = String::from("\"");
+	for byte in value.bytes() {
+		match byte {
+			b'"' => literal.push_str("\\\""),
+			b'\\' => literal.push_str("\\\\"),
+			b'?' => literal.push_str("\\?"),
+			0x20..=0x7e => literal.push(byte as char),
+			_ => write!(literal, "\\{:03o}", byte).unwrap(),
+		}
+	}
+	literal.push('"');
+	literal
+}
+
+pub fn write(model: &LoweredModel) -> Result<String> {
+	if let Some(input) = model
+		.inputs
+		.iter()
+		.find(|input| matches!(input, Input::Text { .. }))
+	{
+		bail!(
+			"The model has features for the text column \"{}\", which cannot be generated as C. Generate Rust instead.",
+			input.name()
+		);
+	}
+	let field_names = field_names(model);
+	let n_features = n_features(model);
+	let n_outputs = model.estimator.n_outputs();
+	let mut w = Writer::default();
+	w.line(format!(
+		"/* This file was generated by `modelfox codegen` from the model {}. It predicts the column \"{}\" and depends only on the C standard library. */",
+		model.id,
+		model.target_column_name.escape_debug().to_string().replace("*/", "* /"),
+	));
+	w.line("");
+	w.line("#include <math.h>");
+	w.line("#include <stddef.h>");
+	w.line("#include <string.h>");
+	w.line("");
+
+	// Write the input struct.
+	w.line("/* The input to the model. Set a number to NAN or a string to NULL if its value is missing. */");
+	w.line("typedef struct {");
+	w.indent();
+	for (input, field_name) in model.inputs.iter().zip(field_names.iter()) {
+		let (kind, ty) = match input {
+			Input::Number { .. } => ("number", "float"),
+			Input::Enum { .. } => ("enum", "const char*"),
+			Input::Text { .. } => unreachable!(),
+		};
+		w.line(format!(
+			"/* The {} column \"{}\". */",
+			kind,
+			input.name().escape_debug().to_string().replace("*/", "* /")
+		));
+		w.line(format!("{} {};", ty, field_name));
+	}
+	w.dedent();
+	w.line("} modelfox_input;");
+	w.line("");
+
+	// Write the output struct and the classes.
+	match &model.task {
+		Task::Regression => {
+			w.line("typedef struct {");
+			w.indent();
+			w.line("float value;");
+			w.dedent();
+			w.line("} modelfox_output;");
+		}
+		Task::BinaryClassification {
+			negative_class,
+			positive_class,
+			threshold,
+		} => {
+			w.line(format!(
+				"static const char* const MODELFOX_NEGATIVE_CLASS = {};",
+				string_literal(negative_class)
+			));
+			w.line(format!(
+				"static const char* const MODELFOX_POSITIVE_CLASS = {};",
+				string_literal(positive_class)
+			));
+			w.line("/* Inputs whose probability of being the positive class is at least this value are predicted to be the positive class. */");
+			w.line(format!(
+				"static const float MODELFOX_THRESHOLD = {};",
+				float_literal(*threshold)
+			));
+			w.line("");
+			w.line("typedef struct {");
+			w.indent();
+			w.line("const char* class_name;");
+			w.line("/* The probability of `class_name`. */");
+			w.line("float probability;");
+			w.dedent();
+			w.line("} modelfox_output;");
+		}
+		Task::MulticlassClassification { classes } => {
+			w.line(format!(
+				"static const char* const MODELFOX_CLASSES[{}] = {{{}}};",
+				classes.len(),
+				classes
+					.iter()
+					.map(|class| string_literal(class))
+					.collect::<Vec<_>>()
+					.join(", ")
+			));
+			w.line("");
+			w.line("typedef struct {");
+			w.indent();
+			w.line("const char* class_name;");
+			w.line("/* The probability of each class, in the order of `MODELFOX_CLASSES`. */");
+			w.line(format!("float probabilities[{}];", classes.len()));
+			w.dedent();
+			w.line("} modelfox_output;");
+		}
+	}
+	w.line("");
+
+	// Write the estimator's parameters. C does not allow arrays with no elements, so arrays of features have at least one element.
+	let features_len = n_features.max(1);
+	let biases = match &model.estimator {
+		Estimator::Linear { biases, .. } => biases,
+		Estimator::Trees { biases, .. } => biases,
+	};
+	w.line(format!(
+		"static const float MODELFOX_BIASES[{}] = {{{}}};",
+		n_outputs,
+		biases
+			.iter()
+			.map(|bias| float_literal(*bias))
+			.collect::<Vec<_>>()
+			.join(", ")
+	));
+	if let Estimator::Linear { weights, .. } = &model.estimator {
+		if n_features > 0 {
+			w.line(format!(
+				"static const float MODELFOX_WEIGHTS[{}][{}] = {{",
+				n_features, n_outputs
+			));
+			w.indent();
+			for row in weights.rows() {
+				w.line(format!(
+					"{{{}}},",
+					row.iter()
+						.map(|weight| float_literal(*weight))
+						.collect::<Vec<_>>()
+						.join(", ")
+				));
+			}
+			w.dedent();
+			w.line("};");
+		}
+	}
+
+	// Write the function that computes the features.
+	w.line("");
+	w.line("static void modelfox_features(const modelfox_input* input, float* features) {");
+	w.indent();
+	for (input_index, (input, field_name)) in
+		model.inputs.iter().zip(field_names.iter()).enumerate()
+	{
+		match input {
+			Input::Number { .. } => w.line(format!(
+				"float input_{0} = isfinite(input->{1}) ? input->{1} : NAN;",
+				input_index, field_name
+			)),
+			Input::Enum { variants, .. } => {
+				w.line(format!("size_t input_{} = 0;", input_index));
+				if !variants.is_empty() {
+					w.line(format!("if (input->{} != NULL) {{", field_name));
+					w.indent();
+					for (variant_index, variant) in variants.iter().enumerate() {
+						let condition = format!(
+							"strcmp(input->{}, {}) == 0",
+							field_name,
+							string_literal(variant)
+						);
+						if variant_index == 0 {
+							w.line(format!("if ({}) {{", condition));
+						} else {
+							w.line(format!("}} else if ({}) {{", condition));
+						}
+						w.indent();
+						w.line(format!("input_{} = {};", input_index, variant_index + 1));
+						w.dedent();
+					}
+					w.line("}");
+					w.dedent();
+					w.line("}");
+				}
+			}
+			Input::Text { .. } => unreachable!(),
+		}
+	}
+	let mut offset = 0;
+	for feature_group in model.feature_groups.iter() {
+		let input_index = feature_group.input();
+		let input = &model.inputs[input_index];
+		match feature_group {
+			FeatureGroup::Identity { .. } => {
+				let value = match input {
+					Input::Number { .. } => format!("input_{}", input_index),
+					Input::Enum { .. } => format!("(float)input_{}", input_index),
+					Input::Text { .. } => unreachable!(),
+				};
+				w.line(format!("features[{}] = {};", offset, value));
+			}
+			FeatureGroup::Normalized { mean, scale, .. } => {
+				let value = match input {
+					Input::Number { .. } => format!(
+						"isnan(input_{0}) ? 0.0f : (input_{0} - {1}) * {2}",
+						input_index,
+						float_literal(*mean),
+						float_literal(*scale),
+					),
+					Input::Enum { .. } => format!(
+						"((float)input_{} - {}) * {}",
+						input_index,
+						float_literal(*mean),
+						float_literal(*scale),
+					),
+					Input::Text { .. } => unreachable!(),
+				};
+				w.line(format!("features[{}] = {};", offset, value));
+			}
+			FeatureGroup::OneHotEncoded { n_indexes, .. } => {
+				if *n_indexes > 0 {
+					w.line(format!(
+						"features[{} + input_{}] = 1.0f;",
+						offset, input_index
+					));
+				}
+			}
+			FeatureGroup::Tokens { .. } => unreachable!(),
+			FeatureGroup::SplitDirections { directions, .. } => {
+				for (split_index, directions) in directions.iter().enumerate() {
+					let conditions = directions
+						.iter()
+						.enumerate()
+						.filter(|(_, right)| **right)
+						.map(|(enum_index, _)| format!("input_{} == {}", input_index, enum_index))
+						.collect::<Vec<_>>();
+					if conditions.is_empty() {
+						continue;
+					}
+					w.line(format!(
+						"features[{}] = ({}) ? 1.0f : 0.0f;",
+						offset + split_index,
+						conditions.join(" || ")
+					));
+				}
+			}
+		}
+		offset += feature_group.n_features();
+	}
+	w.dedent();
+	w.line("}");
+
+	// Write a function for each tree.
+	if let Estimator::Trees { trees, .. } = &model.estimator {
+		for (tree_index, tree) in trees.iter().enumerate() {
+			w.line("");
+			w.line(format!(
+				"static float modelfox_tree_{}(const float* features) {{",
+				tree_index
+			));
+			w.indent();
+			write_tree(
+				&mut w,
+				tree,
+				&|feature_index, threshold, missing_values_left| {
+					if missing_values_left {
+						format!(
+							"(isnan(features[{0}]) || features[{0}] <= {1})",
+							feature_index,
+							float_literal(threshold)
+						)
+					} else {
+						format!(
+							"(features[{}] <= {})",
+							feature_index,
+							float_literal(threshold)
+						)
+					}
+				},
+				&|value| format!("return {};", float_literal(value)),
+			);
+			w.dedent();
+			w.line("}");
+		}
+	}
+
+	// Write the predict function.
+	w.line("");
+	w.line("/* Make a prediction for `input`. */");
+	w.line("modelfox_output modelfox_predict(const modelfox_input* input) {");
+	w.indent();
+	w.line(format!("float features[{}] = {{0}};", features_len));
+	w.line("modelfox_features(input, features);");
+	w.line(format!("float logits[{}];", n_outputs));
+	w.line(format!("for (size_t i = 0; i < {}; i++) {{", n_outputs));
+	w.indent();
+	w.line("logits[i] = MODELFOX_BIASES[i];");
+	w.dedent();
+	w.line("}");
+	match &model.estimator {
+		Estimator::Linear { .. } => {
+			if n_features > 0 {
+				w.line(format!("for (size_t i = 0; i < {}; i++) {{", n_features));
+				w.indent();
+				w.line(format!("for (size_t j = 0; j < {}; j++) {{", n_outputs));
+				w.indent();
+				w.line("logits[j] += features[i] * MODELFOX_WEIGHTS[i][j];");
+				w.dedent();
+				w.line("}");
+				w.dedent();
+				w.line("}");
+			}
+		}
+		Estimator::Trees { trees, .. } => {
+			for (tree_index, tree) in trees.iter().enumerate() {
+				w.line(format!(
+					"logits[{}] += modelfox_tree_{}(features);",
+					tree.output, tree_index
+				));
+			}
+		}
+	}
+	w.line("modelfox_output output;");
+	match &model.task {
+		Task::Regression => {
+			w.line("output.value = logits[0];");
+		}
+		Task::BinaryClassification { .. } => {
+			w.line("float probability = 1.0f / (expf(-logits[0]) + 1.0f);");
+			w.line("if (probability >= MODELFOX_THRESHOLD) {");
+			w.indent();
+			w.line("output.class_name = MODELFOX_POSITIVE_CLASS;");
+			w.line("output.probability = probability;");
+			w.dedent();
+			w.line("} else {");
+			w.indent();
+			w.line("output.class_name = MODELFOX_NEGATIVE_CLASS;");
+			w.line("output.probability = 1.0f - probability;");
+			w.dedent();
+			w.line("}");
+		}
+		Task::MulticlassClassification { .. } => {
+			w.line("float max = logits[0];");
+			w.line(format!("for (size_t i = 1; i < {}; i++) {{", n_outputs));
+			w.indent();
+			w.line("max = logits[i] > max ? logits[i] : max;");
+			w.dedent();
+			w.line("}");
+			w.line("float sum = 0.0f;");
+			w.line(format!("for (size_t i = 0; i < {}; i++) {{", n_outputs));
+			w.indent();
+			w.line("output.probabilities[i] = expf(logits[i] - max);");
+			w.line("sum += output.probabilities[i];");
+			w.dedent();
+			w.line("}");
+			w.line("size_t class_index = 0;");
+			w.line(format!("for (size_t i = 0; i < {}; i++) {{", n_outputs));
+			w.indent();
+			w.line("output.probabilities[i] /= sum;");
+			w.line("if (output.probabilities[i] >= output.probabilities[class_index]) {");
+			w.indent();
+			w.line("class_index = i;");
+			w.dedent();
+			w.line("}");
+			w.dedent();
+			w.line("}");
+			w.line("output.class_name = MODELFOX_CLASSES[class_index];");
+		}
+	}
+	w.line("return output;");
+	w.dedent();
+	w.line("}");
+	Ok(w.finish())
+}
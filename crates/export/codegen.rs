@@ -0,0 +1,132 @@
+/*!
+This module holds the pieces shared by the Rust and C code generators, which write a lowered model as standalone source code with no dependencies. Trees are written as nested if-else statements, so a compiler can turn them into straight-line branches instead of walking an array of nodes.
+*/
+
+use crate::lower::{LoweredModel, Node, Tree};
+
+/// This struct writes lines of source code at the current indentation level.
+#[derive(Default)]
+pub struct Writer {
+	source: String,
+	indent: usize,
+}
+
+impl Writer {
+	pub fn line(&mut self, line: impl AsRef<str>) {
+		let line = line.as_ref();
+		if !line.is_empty() {
+			for _ in 0..self.indent {
+				self.source.push('\t');
+			}
+			self.source.push_str(line);
+		}
+		self.source.push('\n');
+	}
+
+	pub fn indent(&mut self) {
+		self.indent += 1;
+	}
+
+	pub fn dedent(&mut self) {
+		self.indent -= 1;
+	}
+
+	pub fn finish(self) -> String {
+		self.source
+	}
+}
+
+/// Return an identifier for each name that is valid in the target language and distinct from the others and from `keywords`.
+pub fn identifiers<'a>(names: impl Iterator<Item = &'a str>, keywords: &[&str]) -> Vec<String> {
+	let mut identifiers: Vec<String> = Vec::new();
+	for name in names {
+		let mut identifier = name
+			.chars()
+			.map(|c| {
+				if c.is_ascii_alphanumeric() {
+					c.to_ascii_lowercase()
+				} else {
+					'_'
+				}
+			})
+			.collect::<String>();
+		if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+			identifier.insert(0, '_');
+		}
+		if keywords.contains(&identifier.as_str()) {
+			identifier.push('_');
+		}
+		let base = identifier.clone();
+		let mut suffix = 2;
+		while identifiers.contains(&identifier) {
+			identifier = format!("{}_{}", base, suffix);
+			suffix += 1;
+		}
+		identifiers.push(identifier);
+	}
+	identifiers
+}
+
+pub fn n_features(model: &LoweredModel) -> usize {
+	model
+		.feature_groups
+		.iter()
+		.map(|feature_group| feature_group.n_features())
+		.sum()
+}
+
+/// Write the body of a function that returns the value of `tree`. Each branch is written as an if statement whose right child continues the if-else chain, so the nesting depth only grows along left children. `condition` returns the condition for sending an example left at a branch and `leaf` returns the statement for a leaf's value.
+pub fn write_tree(
+	writer: &mut Writer,
+	tree: &Tree,
+	condition: &impl Fn(usize, f32, bool) -> String,
+	leaf: &impl Fn(f32) -> String,
+) {
+	write_node(writer, tree, 0, condition, leaf)
+}
+
+fn write_node(
+	writer: &mut Writer,
+	tree: &Tree,
+	node_index: usize,
+	condition: &impl Fn(usize, f32, bool) -> String,
+	leaf: &impl Fn(f32) -> String,
+) {
+	let mut node_index = node_index;
+	let mut first = true;
+	loop {
+		match &tree.nodes[node_index] {
+			Node::Leaf { value } if first => {
+				writer.line(leaf(*value));
+				return;
+			}
+			Node::Leaf { value } => {
+				writer.line("} else {");
+				writer.indent();
+				writer.line(leaf(*value));
+				writer.dedent();
+				writer.line("}");
+				return;
+			}
+			Node::Branch {
+				feature_index,
+				threshold,
+				missing_values_left,
+				left_child_index,
+				right_child_index,
+			} => {
+				let branch_condition = condition(*feature_index, *threshold, *missing_values_left);
+				if first {
+					writer.line(format!("if {} {{", branch_condition));
+				} else {
+					writer.line(format!("}} else if {} {{", branch_condition));
+				}
+				writer.indent();
+				write_node(writer, tree, *left_child_index, condition, leaf);
+				writer.dedent();
+				node_index = *right_child_index;
+				first = false;
+			}
+		}
+	}
+}
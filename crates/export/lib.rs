@@ -0,0 +1,1144 @@
+/*!
+This crate exports ModelFox models to the [ONNX](https://onnx.ai) and [PMML](https://dmg.org/pmml/v4-4-1/GeneralStructure.html) formats, so they can be run by other inference engines.
+
+Models are first lowered to a description of their inputs, features, and estimator, which each format then writes. Identity, normalized, and one hot encoded features and both linear and tree models are supported by both formats. Bag of words features with unigrams and the present or count strategy are supported by PMML only. Exporting a model with any other features returns an error.
+
+Lowered models can also be generated as standalone Rust or C source code with [`codegen`], which writes each tree as nested if-else statements for low latency inference without the modelfox runtime. Rust supports the same features as PMML. C does not support text columns.
+*/
+
+use anyhow::Result;
+
+mod c;
+mod codegen;
+mod lower;
+mod onnx;
+mod pmml;
+mod protobuf;
+mod rust;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+	Onnx,
+	Pmml,
+}
+
+impl ExportFormat {
+	/// This is the file extension for files in this format.
+	pub fn extension(&self) -> &'static str {
+		match self {
+			ExportFormat::Onnx => "onnx",
+			ExportFormat::Pmml => "pmml",
+		}
+	}
+}
+
+/// Export a model to the chosen format and return the bytes of the exported file.
+pub fn export(model: modelfox_model::ModelReader, format: ExportFormat) -> Result<Vec<u8>> {
+	let target_column_name = target_column_name(&model);
+	let model = modelfox_core::predict::Model::from(model);
+	let model = lower::lower(&model, &target_column_name)?;
+	match format {
+		ExportFormat::Onnx => onnx::write(&model),
+		ExportFormat::Pmml => pmml::write(&model),
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodegenLanguage {
+	Rust,
+	C,
+}
+
+impl CodegenLanguage {
+	/// This is the file extension for source files in this language.
+	pub fn extension(&self) -> &'static str {
+		match self {
+			CodegenLanguage::Rust => "rs",
+			CodegenLanguage::C => "c",
+		}
+	}
+}
+
+/// Generate standalone source code in the chosen language that makes the same predictions as the model.
+pub fn codegen(model: modelfox_model::ModelReader, language: CodegenLanguage) -> Result<String> {
+	let target_column_name = target_column_name(&model);
+	let model = modelfox_core::predict::Model::from(model);
+	let model = lower::lower(&model, &target_column_name)?;
+	match language {
+		CodegenLanguage::Rust => rust::write(&model),
+		CodegenLanguage::C => c::write(&model),
+	}
+}
+
+fn target_column_name(model: &modelfox_model::ModelReader) -> String {
+	match model.inner() {
+		modelfox_model::ModelInnerReader::Regressor(regressor) => {
+			regressor.read().target_column_name().to_owned()
+		}
+		modelfox_model::ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			binary_classifier.read().target_column_name().to_owned()
+		}
+		modelfox_model::ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			multiclass_classifier.read().target_column_name().to_owned()
+		}
+		modelfox_model::ModelInnerReader::Ranker(ranker) => {
+			ranker.read().target_column_name().to_owned()
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::lower::{
+		lower, Estimator, FeatureGroup, Input, LoweredModel, Node, Task, TokensStrategy, Tree,
+	};
+	use crate::{c, rust};
+	use modelfox_core::predict::{PredictInput, PredictInputValue, PredictOptions, PredictOutput};
+	use std::fmt::Write;
+	use std::path::{Path, PathBuf};
+
+	fn heart_disease_path() -> PathBuf {
+		Path::new(env!("CARGO_MANIFEST_DIR")).join("../../heart_disease.csv")
+	}
+
+	/// Train a model on heart_disease.csv with a single grid item, then write and read it back.
+	fn train(target: &str, grid_item: &str) -> (modelfox_core::predict::Model, Vec<u8>) {
+		let dir = tempfile::tempdir().unwrap();
+		let config_path = dir.path().join("config.json");
+		std::fs::write(
+			&config_path,
+			format!(r#"{{ "train": {{ "grid": [{}] }} }}"#, grid_item),
+		)
+		.unwrap();
+		let mut trainer = modelfox_core::train::Trainer::prepare(
+			modelfox_id::Id::generate(),
+			modelfox_core::train::TrainingDataSource::File(heart_disease_path()),
+			target,
+			modelfox_core::train::load_config(Some(config_path.as_path())).unwrap(),
+			None,
+			&mut |_| {},
+		)
+		.unwrap();
+		let kill_chip = modelfox_kill_chip::KillChip::default();
+		let train_grid_item_outputs = trainer.train_grid(&kill_chip, &mut |_| {}).unwrap();
+		let model = trainer
+			.test_and_assemble_model(train_grid_item_outputs, &mut |_| {})
+			.unwrap();
+		let model_path = dir.path().join("model.modelfox");
+		model.to_path(&model_path).unwrap();
+		let bytes = std::fs::read(&model_path).unwrap();
+		let model = modelfox_model::from_bytes(&bytes).unwrap();
+		let model = modelfox_core::predict::Model::from(model);
+		(model, bytes)
+	}
+
+	/// Read the examples in heart_disease.csv, followed by an example with every input missing and an example with missing numbers and enum values the model has never seen.
+	fn read_examples() -> Vec<PredictInput> {
+		let mut reader = csv::Reader::from_path(heart_disease_path()).unwrap();
+		let header = reader.headers().unwrap().clone();
+		let mut examples: Vec<PredictInput> = reader
+			.records()
+			.map(|record| {
+				let record = record.unwrap();
+				PredictInput(
+					header
+						.iter()
+						.zip(record.iter())
+						.map(|(column_name, value)| {
+							(
+								column_name.to_owned(),
+								PredictInputValue::String(value.to_owned()),
+							)
+						})
+						.collect(),
+				)
+			})
+			.collect();
+		examples.push(PredictInput::default());
+		examples.push(PredictInput(
+			[
+				("age", ""),
+				("cholesterol", "high"),
+				("gender", "unknown"),
+				("chest_pain", ""),
+				("thallium_stress_test", "not tested"),
+			]
+			.into_iter()
+			.map(|(column_name, value)| {
+				(
+					column_name.to_owned(),
+					PredictInputValue::String(value.to_owned()),
+				)
+			})
+			.collect(),
+		));
+		examples
+	}
+
+	/// Evaluate a lowered model the way the exported ONNX and PMML models do. This returns the regression value, the probability of the positive class, or the probability of each class.
+	fn evaluate(model: &LoweredModel, example: &PredictInput) -> Vec<f32> {
+		let input_str = |input: &Input| {
+			example
+				.0
+				.get(input.name())
+				.and_then(|value| value.as_str())
+				.unwrap_or("")
+				.to_owned()
+		};
+		// Read each input as a number, which for enum inputs is the index of the variant.
+		let values = model
+			.inputs
+			.iter()
+			.map(|input| {
+				let value = input_str(input);
+				match input {
+					Input::Number { .. } => value
+						.parse::<f32>()
+						.ok()
+						.filter(|value| value.is_finite())
+						.unwrap_or(f32::NAN),
+					Input::Enum { variants, .. } => variants
+						.iter()
+						.position(|variant| *variant == value)
+						.map(|index| (index + 1) as f32)
+						.unwrap_or(0.0),
+					Input::Text { .. } => f32::NAN,
+				}
+			})
+			.collect::<Vec<_>>();
+		let mut features = Vec::new();
+		for feature_group in model.feature_groups.iter() {
+			let value = values[feature_group.input()];
+			match feature_group {
+				FeatureGroup::Identity { .. } => features.push(value),
+				FeatureGroup::Normalized { mean, scale, .. } => {
+					features.push(if value.is_nan() {
+						0.0
+					} else {
+						(value - mean) * scale
+					});
+				}
+				FeatureGroup::OneHotEncoded { n_indexes, .. } => {
+					features.extend((0..*n_indexes).map(|index| {
+						if value == index as f32 {
+							1.0
+						} else {
+							0.0
+						}
+					}));
+				}
+				FeatureGroup::Tokens {
+					input,
+					lowercase,
+					strategy,
+					tokens,
+				} => {
+					let text = input_str(&model.inputs[*input]);
+					let text = if *lowercase {
+						text.to_lowercase()
+					} else {
+						text
+					};
+					let words = text
+						.split(|c: char| !c.is_alphanumeric())
+						.filter(|word| !word.is_empty())
+						.collect::<Vec<_>>();
+					features.extend(tokens.iter().map(|token| {
+						let count = words.iter().filter(|word| *word == token).count();
+						match strategy {
+							TokensStrategy::Present => (count > 0) as usize as f32,
+							TokensStrategy::Count => count as f32,
+						}
+					}));
+				}
+				FeatureGroup::SplitDirections { directions, .. } => {
+					features.extend(
+						directions
+							.iter()
+							.map(|directions| directions[value as usize] as usize as f32),
+					);
+				}
+			}
+		}
+		let logits = match &model.estimator {
+			Estimator::Linear { weights, biases } => biases
+				.iter()
+				.enumerate()
+				.map(|(output, bias)| {
+					bias + features
+						.iter()
+						.zip(weights.column(output))
+						.map(|(feature, weight)| feature * weight)
+						.sum::<f32>()
+				})
+				.collect::<Vec<_>>(),
+			Estimator::Trees { trees, biases } => {
+				let mut logits = biases.clone();
+				for tree in trees.iter() {
+					let mut node_index = 0;
+					let value = loop {
+						match &tree.nodes[node_index] {
+							Node::Leaf { value } => break *value,
+							Node::Branch {
+								feature_index,
+								threshold,
+								missing_values_left,
+								left_child_index,
+								right_child_index,
+							} => {
+								let value = features[*feature_index];
+								let left = if value.is_nan() {
+									*missing_values_left
+								} else {
+									value <= *threshold
+								};
+								node_index = if left {
+									*left_child_index
+								} else {
+									*right_child_index
+								};
+							}
+						}
+					};
+					logits[tree.output] += value;
+				}
+				logits
+			}
+		};
+		match &model.task {
+			Task::Regression => logits,
+			Task::BinaryClassification { .. } => vec![1.0 / ((-logits[0]).exp() + 1.0)],
+			Task::MulticlassClassification { .. } => {
+				let max = logits.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+				let exps = logits
+					.iter()
+					.map(|logit| (logit - max).exp())
+					.collect::<Vec<_>>();
+				let sum = exps.iter().sum::<f32>();
+				exps.iter().map(|exp| exp / sum).collect()
+			}
+		}
+	}
+
+	fn expected(output: &PredictOutput, task: &Task) -> Vec<f32> {
+		match (output, task) {
+			(PredictOutput::Regression(output), _) => vec![output.value],
+			(
+				PredictOutput::BinaryClassification(output),
+				Task::BinaryClassification { positive_class, .. },
+			) => {
+				if output.class_name == *positive_class {
+					vec![output.probability]
+				} else {
+					vec![1.0 - output.probability]
+				}
+			}
+			(
+				PredictOutput::MulticlassClassification(output),
+				Task::MulticlassClassification { classes },
+			) => classes
+				.iter()
+				.map(|class| output.probabilities[class])
+				.collect(),
+			_ => unreachable!(),
+		}
+	}
+
+	/// Train a model, then check that the lowered model and the compiled Rust and C source generated from it make the same predictions as `modelfox_core::predict`, and that it can be written as ONNX and PMML.
+	fn test_export(target: &str, grid_item: &str) {
+		let (model, bytes) = train(target, grid_item);
+		let lowered = lower(&model, target).unwrap();
+		// Compare the exported model's predictions with modelfox_core::predict.
+		let examples = read_examples();
+		let outputs =
+			modelfox_core::predict::predict(&model, &examples, &PredictOptions::default());
+		let expected = outputs
+			.iter()
+			.map(|output| expected(output, &lowered.task))
+			.collect::<Vec<_>>();
+		for (example, expected) in examples.iter().zip(expected.iter()) {
+			assert_close(&evaluate(&lowered, example), expected);
+		}
+		// Compile and run the generated Rust and C source code and compare its predictions too.
+		let actual = run_rust(&lowered, &examples);
+		assert_eq!(actual.len(), expected.len());
+		for (actual, expected) in actual.iter().zip(expected.iter()) {
+			assert_close(actual, expected);
+		}
+		let actual = run_c(&lowered, &examples);
+		assert_eq!(actual.len(), expected.len());
+		for (actual, expected) in actual.iter().zip(expected.iter()) {
+			assert_close(actual, expected);
+		}
+		// Write the model in both formats.
+		let onnx = crate::export(
+			modelfox_model::from_bytes(&bytes).unwrap(),
+			crate::ExportFormat::Onnx,
+		)
+		.unwrap();
+		let pmml = crate::export(
+			modelfox_model::from_bytes(&bytes).unwrap(),
+			crate::ExportFormat::Pmml,
+		)
+		.unwrap();
+		let pmml = String::from_utf8(pmml).unwrap();
+		assert!(pmml.starts_with("<?xml"));
+		assert_eq!(
+			pmml.matches("<PMML").count(),
+			pmml.matches("</PMML>").count()
+		);
+		match &lowered.estimator {
+			Estimator::Linear { .. } => {
+				assert!(!windows_contain(&onnx, b"TreeEnsembleRegressor"));
+				assert!(windows_contain(&onnx, b"MatMul"));
+				assert!(pmml.contains("<RegressionModel"));
+			}
+			Estimator::Trees { trees, .. } => {
+				assert!(windows_contain(&onnx, b"TreeEnsembleRegressor"));
+				assert_eq!(pmml.matches("<TreeModel").count(), trees.len());
+			}
+		}
+	}
+
+	fn assert_close(actual: &[f32], expected: &[f32]) {
+		assert_eq!(actual.len(), expected.len());
+		for (actual, expected) in actual.iter().zip(expected.iter()) {
+			assert!(
+				(actual - expected).abs() <= 1e-4 * expected.abs().max(1.0),
+				"expected {}, got {}",
+				expected,
+				actual,
+			);
+		}
+	}
+
+	/// Get an input's value in an example, or `None` if it is a number input whose value is not a finite number.
+	fn example_value(input: &Input, example: &PredictInput) -> Option<String> {
+		let value = example
+			.0
+			.get(input.name())
+			.and_then(|value| value.as_str())
+			.unwrap_or("")
+			.to_owned();
+		match input {
+			Input::Number { .. } => value
+				.parse::<f32>()
+				.ok()
+				.filter(|value| value.is_finite())
+				.map(|value| value.to_string()),
+			_ => Some(value),
+		}
+	}
+
+	/// Compile a program consisting of `files` with `command` in a temporary directory, run it, and parse each line it prints as a list of numbers.
+	fn run(files: &[(&str, String)], command: &[&str]) -> Vec<Vec<f32>> {
+		let dir = tempfile::tempdir().unwrap();
+		for (name, contents) in files {
+			std::fs::write(dir.path().join(name), contents).unwrap();
+		}
+		let status = std::process::Command::new(command[0])
+			.args(&command[1..])
+			.current_dir(dir.path())
+			.status()
+			.unwrap();
+		assert!(status.success());
+		let output = std::process::Command::new(dir.path().join("main"))
+			.output()
+			.unwrap();
+		assert!(output.status.success());
+		String::from_utf8(output.stdout)
+			.unwrap()
+			.lines()
+			.map(|line| {
+				line.split(' ')
+					.map(|value| value.parse().unwrap())
+					.collect()
+			})
+			.collect()
+	}
+
+	fn run_rust(model: &LoweredModel, examples: &[PredictInput]) -> Vec<Vec<f32>> {
+		let source = rust::write(model).unwrap();
+		let field_names = rust::field_names(model);
+		let mut main =
+			String::from("mod model;\nuse model::*;\n\nfn main() {\n\tlet examples = [\n");
+		for example in examples.iter() {
+			main.push_str("\t\tInput {\n");
+			for (input, field_name) in model.inputs.iter().zip(field_names.iter()) {
+				let value = match (input, example_value(input, example)) {
+					(_, None) => "None".to_owned(),
+					(Input::Number { .. }, Some(value)) => {
+						format!("Some({})", rust::float_literal(value.parse().unwrap()))
+					}
+					(_, Some(value)) => format!("Some({})", rust::string_literal(&value)),
+				};
+				writeln!(main, "\t\t\t{}: {},", field_name, value).unwrap();
+			}
+			main.push_str("\t\t},\n");
+		}
+		main.push_str("\t];\n\tfor input in examples.iter() {\n\t\tlet output = predict(input);\n");
+		main.push_str(match model.task {
+			Task::Regression => "\t\tprintln!(\"{}\", output.value);\n",
+			Task::BinaryClassification { .. } => "\t\tprintln!(\"{}\", if output.class_name == POSITIVE_CLASS { output.probability } else { 1.0 - output.probability });\n",
+			Task::MulticlassClassification { .. } => "\t\tprintln!(\"{}\", output.probabilities.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(\" \"));\n",
+		});
+		main.push_str("\t}\n}\n");
+		run(
+			&[("model.rs", source), ("main.rs", main)],
+			&["rustc", "--edition", "2021", "-o", "main", "main.rs"],
+		)
+	}
+
+	fn run_c(model: &LoweredModel, examples: &[PredictInput]) -> Vec<Vec<f32>> {
+		let source = c::write(model).unwrap();
+		let field_names = c::field_names(model);
+		let mut main = String::from("#include <stdio.h>\n#include \"model.c\"\n\nint main(void) {\n\tmodelfox_input examples[] = {\n");
+		for example in examples.iter() {
+			let fields = model
+				.inputs
+				.iter()
+				.zip(field_names.iter())
+				.map(|(input, field_name)| {
+					let value = match (input, example_value(input, example)) {
+						(Input::Number { .. }, None) => "NAN".to_owned(),
+						(_, None) => "NULL".to_owned(),
+						(Input::Number { .. }, Some(value)) => {
+							c::float_literal(value.parse().unwrap())
+						}
+						(_, Some(value)) => c::string_literal(&value),
+					};
+					format!(".{} = {}", field_name, value)
+				})
+				.collect::<Vec<_>>();
+			writeln!(main, "\t\t{{ {} }},", fields.join(", ")).unwrap();
+		}
+		main.push_str("\t};\n\tfor (size_t i = 0; i < sizeof(examples) / sizeof(examples[0]); i++) {\n\t\tmodelfox_output output = modelfox_predict(&examples[i]);\n");
+		main.push_str(match &model.task {
+			Task::Regression => "\t\tprintf(\"%.9g\\n\", output.value);\n".to_owned(),
+			Task::BinaryClassification { .. } => "\t\tprintf(\"%.9g\\n\", strcmp(output.class_name, MODELFOX_POSITIVE_CLASS) == 0 ? output.probability : 1.0f - output.probability);\n".to_owned(),
+			Task::MulticlassClassification { classes } => format!("\t\tfor (size_t j = 0; j < {}; j++) {{\n\t\t\tprintf(j == 0 ? \"%.9g\" : \" %.9g\", output.probabilities[j]);\n\t\t}}\n\t\tprintf(\"\\n\");\n", classes.len()),
+		}.as_str());
+		main.push_str("\t}\n\treturn 0;\n}\n");
+		run(
+			&[("model.c", source), ("main.c", main)],
+			&["cc", "-std=c99", "-o", "main", "main.c", "-lm"],
+		)
+	}
+
+	fn windows_contain(bytes: &[u8], needle: &[u8]) -> bool {
+		bytes.windows(needle.len()).any(|window| window == needle)
+	}
+
+	/// A linear binary classifier small enough to check by hand. Its logit is `0.25 + 0.5 * (age - 50) * 0.1` plus 1 if the color is red and -1 if the color is green.
+	fn small_linear_model() -> LoweredModel {
+		LoweredModel {
+			id: "small_linear_model".to_owned(),
+			target_column_name: "diagnosis".to_owned(),
+			inputs: vec![
+				Input::Number {
+					name: "age".to_owned(),
+				},
+				Input::Enum {
+					name: "color".to_owned(),
+					variants: vec!["red".to_owned(), "green".to_owned()],
+				},
+			],
+			feature_groups: vec![
+				FeatureGroup::Normalized {
+					input: 0,
+					mean: 50.0,
+					scale: 0.1,
+				},
+				FeatureGroup::OneHotEncoded {
+					input: 1,
+					n_indexes: 3,
+				},
+			],
+			estimator: Estimator::Linear {
+				weights: ndarray::arr2(&[[0.5], [0.0], [1.0], [-1.0]]),
+				biases: vec![0.25],
+			},
+			task: Task::BinaryClassification {
+				negative_class: "Negative".to_owned(),
+				positive_class: "Positive".to_owned(),
+				threshold: 0.5,
+			},
+		}
+	}
+
+	/// A tree regressor small enough to check by hand. It predicts 1.5 if `x <= 2.5`, and otherwise 3.5 if the color is green and 2.5 if it is not.
+	fn small_tree_model() -> LoweredModel {
+		LoweredModel {
+			id: "small_tree_model".to_owned(),
+			target_column_name: "y".to_owned(),
+			inputs: vec![
+				Input::Number {
+					name: "x".to_owned(),
+				},
+				Input::Enum {
+					name: "color".to_owned(),
+					variants: vec!["red".to_owned(), "green".to_owned()],
+				},
+			],
+			feature_groups: vec![
+				FeatureGroup::Identity { input: 0 },
+				FeatureGroup::SplitDirections {
+					input: 1,
+					directions: vec![vec![false, false, true]],
+				},
+			],
+			estimator: Estimator::Trees {
+				trees: vec![Tree {
+					output: 0,
+					nodes: vec![
+						Node::Branch {
+							feature_index: 0,
+							threshold: 2.5,
+							missing_values_left: false,
+							left_child_index: 1,
+							right_child_index: 2,
+						},
+						Node::Leaf { value: 1.0 },
+						Node::Branch {
+							feature_index: 1,
+							threshold: 0.5,
+							missing_values_left: false,
+							left_child_index: 3,
+							right_child_index: 4,
+						},
+						Node::Leaf { value: 2.0 },
+						Node::Leaf { value: 3.0 },
+					],
+				}],
+				biases: vec![0.5],
+			},
+			task: Task::Regression,
+		}
+	}
+
+	fn example(values: &[(&str, &str)]) -> PredictInput {
+		PredictInput(
+			values
+				.iter()
+				.map(|(column_name, value)| {
+					(
+						column_name.to_string(),
+						PredictInputValue::String(value.to_string()),
+					)
+				})
+				.collect(),
+		)
+	}
+
+	#[test]
+	fn test_evaluate_small_models() {
+		let model = small_linear_model();
+		let sigmoid = |logit: f32| 1.0 / ((-logit).exp() + 1.0);
+		let probability = |values| evaluate(&model, &example(values))[0];
+		assert_eq!(
+			probability(&[("age", "60"), ("color", "red")]),
+			sigmoid(1.75)
+		);
+		assert_eq!(
+			probability(&[("age", "40"), ("color", "green")]),
+			sigmoid(-1.25)
+		);
+		// Missing numbers are normalized to 0 and unknown enum values have index 0.
+		assert_eq!(
+			probability(&[("age", ""), ("color", "blue")]),
+			sigmoid(0.25)
+		);
+		assert_eq!(probability(&[]), sigmoid(0.25));
+		let model = small_tree_model();
+		let value = |values| evaluate(&model, &example(values))[0];
+		assert_eq!(value(&[("x", "1"), ("color", "green")]), 1.5);
+		assert_eq!(value(&[("x", "3"), ("color", "green")]), 3.5);
+		assert_eq!(value(&[("x", "3"), ("color", "red")]), 2.5);
+		// Missing numbers are sent right.
+		assert_eq!(value(&[("color", "green")]), 3.5);
+		assert_eq!(value(&[]), 2.5);
+	}
+
+	#[test]
+	fn test_pmml_small_linear_model() {
+		let output = String::from_utf8(crate::pmml::write(&small_linear_model()).unwrap())
+			.unwrap()
+			.replace(env!("CARGO_PKG_VERSION"), "VERSION");
+		insta::assert_snapshot!(output, @r###"
+			<?xml version="1.0" encoding="UTF-8"?>
+			<PMML xmlns="http://www.dmg.org/PMML-4_4" version="4.4">
+				<Header>
+					<Application name="ModelFox" version="VERSION"/>
+				</Header>
+				<DataDictionary numberOfFields="3">
+					<DataField name="age" optype="continuous" dataType="double"/>
+					<DataField name="color" optype="categorical" dataType="string">
+						<Value value="red"/>
+						<Value value="green"/>
+					</DataField>
+					<DataField name="diagnosis" optype="categorical" dataType="string">
+						<Value value="Negative"/>
+						<Value value="Positive"/>
+					</DataField>
+				</DataDictionary>
+				<TransformationDictionary>
+					<DerivedField name="modelfox_index(color)" optype="continuous" dataType="integer">
+						<MapValues outputColumn="index" dataType="integer" mapMissingTo="0" defaultValue="0">
+							<FieldColumnPair field="color" column="variant"/>
+							<InlineTable>
+								<row>
+									<variant>red</variant>
+									<index>1</index>
+								</row>
+								<row>
+									<variant>green</variant>
+									<index>2</index>
+								</row>
+							</InlineTable>
+						</MapValues>
+					</DerivedField>
+					<DerivedField name="modelfox_feature(0)" optype="continuous" dataType="double">
+						<Apply function="if">
+							<Apply function="isMissing">
+								<FieldRef field="age"/>
+							</Apply>
+							<Constant>0</Constant>
+							<Apply function="*">
+								<Apply function="-">
+									<FieldRef field="age"/>
+									<Constant>50</Constant>
+								</Apply>
+								<Constant>0.1</Constant>
+							</Apply>
+						</Apply>
+					</DerivedField>
+					<DerivedField name="modelfox_feature(1)" optype="continuous" dataType="double">
+						<Apply function="if">
+							<Apply function="equal">
+								<FieldRef field="modelfox_index(color)"/>
+								<Constant>0</Constant>
+							</Apply>
+							<Constant>1</Constant>
+							<Constant>0</Constant>
+						</Apply>
+					</DerivedField>
+					<DerivedField name="modelfox_feature(2)" optype="continuous" dataType="double">
+						<Apply function="if">
+							<Apply function="equal">
+								<FieldRef field="modelfox_index(color)"/>
+								<Constant>1</Constant>
+							</Apply>
+							<Constant>1</Constant>
+							<Constant>0</Constant>
+						</Apply>
+					</DerivedField>
+					<DerivedField name="modelfox_feature(3)" optype="continuous" dataType="double">
+						<Apply function="if">
+							<Apply function="equal">
+								<FieldRef field="modelfox_index(color)"/>
+								<Constant>2</Constant>
+							</Apply>
+							<Constant>1</Constant>
+							<Constant>0</Constant>
+						</Apply>
+					</DerivedField>
+				</TransformationDictionary>
+				<RegressionModel functionName="classification" normalizationMethod="logit">
+					<MiningSchema>
+						<MiningField name="age"/>
+						<MiningField name="color" invalidValueTreatment="asMissing"/>
+						<MiningField name="diagnosis" usageType="target"/>
+					</MiningSchema>
+					<Output>
+						<OutputField name="probability(Positive)" optype="continuous" dataType="double" feature="probability" value="Positive"/>
+						<OutputField name="probability(Negative)" optype="continuous" dataType="double" feature="probability" value="Negative"/>
+						<OutputField name="label" optype="categorical" dataType="string" feature="transformedValue">
+							<Apply function="if">
+								<Apply function="greaterOrEqual">
+									<FieldRef field="probability(Positive)"/>
+									<Constant>0.5</Constant>
+								</Apply>
+								<Constant>Positive</Constant>
+								<Constant>Negative</Constant>
+							</Apply>
+						</OutputField>
+					</Output>
+					<RegressionTable intercept="0.25" targetCategory="Positive">
+						<NumericPredictor name="modelfox_feature(0)" coefficient="0.5"/>
+						<NumericPredictor name="modelfox_feature(1)" coefficient="0"/>
+						<NumericPredictor name="modelfox_feature(2)" coefficient="1"/>
+						<NumericPredictor name="modelfox_feature(3)" coefficient="-1"/>
+					</RegressionTable>
+					<RegressionTable intercept="0" targetCategory="Negative"/>
+				</RegressionModel>
+			</PMML>
+		"###);
+	}
+
+	#[test]
+	fn test_pmml_small_tree_model() {
+		let output = String::from_utf8(crate::pmml::write(&small_tree_model()).unwrap())
+			.unwrap()
+			.replace(env!("CARGO_PKG_VERSION"), "VERSION");
+		insta::assert_snapshot!(output, @r###"
+			<?xml version="1.0" encoding="UTF-8"?>
+			<PMML xmlns="http://www.dmg.org/PMML-4_4" version="4.4">
+				<Header>
+					<Application name="ModelFox" version="VERSION"/>
+				</Header>
+				<DataDictionary numberOfFields="3">
+					<DataField name="x" optype="continuous" dataType="double"/>
+					<DataField name="color" optype="categorical" dataType="string">
+						<Value value="red"/>
+						<Value value="green"/>
+					</DataField>
+					<DataField name="y" optype="continuous" dataType="double"/>
+				</DataDictionary>
+				<TransformationDictionary>
+					<DerivedField name="modelfox_index(color)" optype="continuous" dataType="integer">
+						<MapValues outputColumn="index" dataType="integer" mapMissingTo="0" defaultValue="0">
+							<FieldColumnPair field="color" column="variant"/>
+							<InlineTable>
+								<row>
+									<variant>red</variant>
+									<index>1</index>
+								</row>
+								<row>
+									<variant>green</variant>
+									<index>2</index>
+								</row>
+							</InlineTable>
+						</MapValues>
+					</DerivedField>
+					<DerivedField name="modelfox_feature(0)" optype="continuous" dataType="double">
+						<FieldRef field="x"/>
+					</DerivedField>
+					<DerivedField name="modelfox_feature(1)" optype="continuous" dataType="double">
+						<Apply function="if">
+							<Apply function="isIn">
+								<FieldRef field="modelfox_index(color)"/>
+								<Constant>2</Constant>
+							</Apply>
+							<Constant>1</Constant>
+							<Constant>0</Constant>
+						</Apply>
+					</DerivedField>
+				</TransformationDictionary>
+				<MiningModel functionName="regression">
+					<MiningSchema>
+						<MiningField name="x"/>
+						<MiningField name="color" invalidValueTreatment="asMissing"/>
+						<MiningField name="y" usageType="target"/>
+					</MiningSchema>
+					<Output>
+						<OutputField name="value" optype="continuous" dataType="double" feature="predictedValue"/>
+					</Output>
+					<Targets>
+						<Target field="y" rescaleConstant="0.5"/>
+					</Targets>
+					<Segmentation multipleModelMethod="sum">
+						<Segment id="1">
+							<True/>
+							<TreeModel functionName="regression" missingValueStrategy="defaultChild" splitCharacteristic="binarySplit">
+								<MiningSchema>
+									<MiningField name="x"/>
+									<MiningField name="color" invalidValueTreatment="asMissing"/>
+								</MiningSchema>
+								<Node id="0" defaultChild="2">
+									<True/>
+									<Node id="1" score="1">
+										<SimplePredicate field="modelfox_feature(0)" operator="lessOrEqual" value="2.5"/>
+									</Node>
+									<Node id="2" defaultChild="4">
+										<SimplePredicate field="modelfox_feature(0)" operator="greaterThan" value="2.5"/>
+										<Node id="3" score="2">
+											<SimplePredicate field="modelfox_feature(1)" operator="lessOrEqual" value="0.5"/>
+										</Node>
+										<Node id="4" score="3">
+											<SimplePredicate field="modelfox_feature(1)" operator="greaterThan" value="0.5"/>
+										</Node>
+									</Node>
+								</Node>
+							</TreeModel>
+						</Segment>
+					</Segmentation>
+				</MiningModel>
+			</PMML>
+		"###);
+	}
+
+	#[test]
+	fn test_onnx_small_linear_model() {
+		let output = describe_onnx(&crate::onnx::write(&small_linear_model()).unwrap());
+		insta::assert_snapshot!(output, @r###"
+			LabelEncoder(color) -> color_1
+				keys_strings = ["red", "green"]
+				values_int64s = [1, 2]
+				default_int64 = [0]
+			constant_3 [1] = [50]
+			constant_4 [1] = [0.1]
+			constant_5 [1] = [0]
+			Sub(age, constant_3) -> sub_6
+			Mul(sub_6, constant_4) -> mul_8
+			IsNaN(age) -> isnan_10
+			Where(isnan_10, constant_5, mul_8) -> where_12
+			constant_14 [3] = [0, 1, 2]
+			Equal(color_1, constant_14) -> equal_15
+			Cast(equal_15) -> cast_17
+				to = [1]
+			Concat(where_12, cast_17) -> concat_19
+				axis = [1]
+			constant_21 [4, 1] = [0.5, 0, 1, -1]
+			constant_22 [1] = [0.25]
+			MatMul(concat_19, constant_21) -> matmul_23
+			Add(matmul_23, constant_22) -> add_25
+			Sigmoid(add_25) -> probability
+			constant_28 [1] = [0.5]
+			GreaterOrEqual(probability, constant_28) -> greaterorequal_29
+			constant_31 [1] = ["Positive"]
+			constant_32 [1] = ["Negative"]
+			Where(greaterorequal_29, constant_31, constant_32) -> label
+		"###);
+	}
+
+	#[test]
+	fn test_onnx_small_tree_model() {
+		let output = describe_onnx(&crate::onnx::write(&small_tree_model()).unwrap());
+		insta::assert_snapshot!(output, @r###"
+			LabelEncoder(color) -> color_1
+				keys_strings = ["red", "green"]
+				values_int64s = [1, 2]
+				default_int64 = [0]
+			constant_3 [3, 1] = [0, 0, 1]
+			Gather(constant_3, color_1) -> gather_4
+			constant_6 [2] = [-1, 1]
+			Reshape(gather_4, constant_6) -> reshape_7
+			Concat(x, reshape_7) -> concat_9
+				axis = [1]
+			TreeEnsembleRegressor(concat_9) -> logits_11
+				aggregate_function = ["SUM"]
+				base_values = [0.5]
+				n_targets = [1]
+				nodes_falsenodeids = [2, 0, 4, 0, 0]
+				nodes_featureids = [0, 0, 1, 0, 0]
+				nodes_missing_value_tracks_true = [0, 0, 0, 0, 0]
+				nodes_modes = ["BRANCH_LEQ", "LEAF", "BRANCH_LEQ", "LEAF", "LEAF"]
+				nodes_nodeids = [0, 1, 2, 3, 4]
+				nodes_treeids = [0, 0, 0, 0, 0]
+				nodes_truenodeids = [1, 0, 3, 0, 0]
+				nodes_values = [2.5, 0, 0.5, 0, 0]
+				post_transform = ["NONE"]
+				target_ids = [0, 0, 0]
+				target_nodeids = [1, 3, 4]
+				target_treeids = [0, 0, 0]
+				target_weights = [1, 2, 3]
+			Identity(logits_11) -> value
+		"###);
+	}
+
+	enum Field<'a> {
+		Varint(u64),
+		Fixed(u64),
+		Bytes(&'a [u8]),
+	}
+
+	/// Decode the fields of a protocol buffers message.
+	fn decode(mut bytes: &[u8]) -> Vec<(u64, Field)> {
+		let varint = |bytes: &mut &[u8]| {
+			let mut value = 0;
+			let mut shift = 0;
+			loop {
+				let byte = bytes[0];
+				*bytes = &bytes[1..];
+				value |= ((byte & 0x7f) as u64) << shift;
+				shift += 7;
+				if byte < 0x80 {
+					break value;
+				}
+			}
+		};
+		let mut fields = Vec::new();
+		while !bytes.is_empty() {
+			let tag = varint(&mut bytes);
+			let field = match tag & 7 {
+				0 => Field::Varint(varint(&mut bytes)),
+				1 | 5 => {
+					let size = if tag & 7 == 1 { 8 } else { 4 };
+					let mut value = [0; 8];
+					value[..size].copy_from_slice(&bytes[..size]);
+					bytes = &bytes[size..];
+					Field::Fixed(u64::from_le_bytes(value))
+				}
+				2 => {
+					let len = varint(&mut bytes) as usize;
+					let (value, rest) = bytes.split_at(len);
+					bytes = rest;
+					Field::Bytes(value)
+				}
+				_ => panic!("unexpected wire type"),
+			};
+			fields.push((tag >> 3, field));
+		}
+		fields
+	}
+
+	fn string(field: &Field) -> String {
+		match field {
+			Field::Bytes(bytes) => String::from_utf8(bytes.to_vec()).unwrap(),
+			_ => panic!("expected a string"),
+		}
+	}
+
+	/// Describe the initializers and nodes of an ONNX model's graph, one per line, with the values of their attributes.
+	fn describe_onnx(bytes: &[u8]) -> String {
+		let packed_floats = |bytes: &[u8]| {
+			bytes
+				.chunks(4)
+				.map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()).to_string())
+				.collect::<Vec<_>>()
+				.join(", ")
+		};
+		let packed_ints = |bytes: &[u8]| {
+			decode_packed_varints(bytes)
+				.iter()
+				.map(|value| (*value as i64).to_string())
+				.collect::<Vec<_>>()
+				.join(", ")
+		};
+		let model = decode(bytes);
+		let graph = model
+			.iter()
+			.find_map(|(number, field)| match (number, field) {
+				(7, Field::Bytes(graph)) => Some(decode(graph)),
+				_ => None,
+			})
+			.unwrap();
+		let mut lines = Vec::new();
+		for (number, field) in graph.iter() {
+			let message = match (number, field) {
+				(1 | 5, Field::Bytes(message)) => decode(message),
+				_ => continue,
+			};
+			match number {
+				// initializer
+				5 => {
+					let mut name = String::new();
+					let mut dims = String::new();
+					let mut values = String::new();
+					let mut strings = Vec::new();
+					for (number, field) in message.iter() {
+						match (number, field) {
+							(1, Field::Bytes(bytes)) => dims = packed_ints(bytes),
+							(4, Field::Bytes(bytes)) => values = packed_floats(bytes),
+							(6, field) => strings.push(format!("{:?}", string(field))),
+							(7, Field::Bytes(bytes)) => values = packed_ints(bytes),
+							(8, field) => name = string(field),
+							_ => {}
+						}
+					}
+					if !strings.is_empty() {
+						values = strings.join(", ");
+					}
+					lines.push(format!("{} [{}] = [{}]", name, dims, values));
+				}
+				// node
+				1 => {
+					let mut inputs = Vec::new();
+					let mut outputs = Vec::new();
+					let mut op_type = String::new();
+					let mut attributes = Vec::new();
+					for (number, field) in message.iter() {
+						match number {
+							1 => inputs.push(string(field)),
+							2 => outputs.push(string(field)),
+							4 => op_type = string(field),
+							5 => {
+								let attribute = match field {
+									Field::Bytes(bytes) => decode(bytes),
+									_ => unreachable!(),
+								};
+								let mut name = String::new();
+								let mut values = Vec::new();
+								for (number, field) in attribute.iter() {
+									match (number, field) {
+										(1, field) => name = string(field),
+										(3, Field::Varint(value)) => {
+											values.push((*value as i64).to_string())
+										}
+										(4, field) => values.push(format!("{:?}", string(field))),
+										(7, Field::Bytes(bytes)) => {
+											values.push(packed_floats(bytes))
+										}
+										(8, Field::Bytes(bytes)) => values.push(packed_ints(bytes)),
+										(9, field) => values.push(format!("{:?}", string(field))),
+										_ => {}
+									}
+								}
+								attributes.push(format!("\n\t{} = [{}]", name, values.join(", ")));
+							}
+							_ => {}
+						}
+					}
+					lines.push(format!(
+						"{}({}) -> {}{}",
+						op_type,
+						inputs.join(", "),
+						outputs.join(", "),
+						attributes.concat(),
+					));
+				}
+				_ => {}
+			}
+		}
+		lines.join("\n")
+	}
+
+	fn decode_packed_varints(mut bytes: &[u8]) -> Vec<u64> {
+		let mut values = Vec::new();
+		while !bytes.is_empty() {
+			let mut value = 0;
+			let mut shift = 0;
+			loop {
+				let byte = bytes[0];
+				bytes = &bytes[1..];
+				value |= ((byte & 0x7f) as u64) << shift;
+				shift += 7;
+				if byte < 0x80 {
+					break;
+				}
+			}
+			values.push(value);
+		}
+		values
+	}
+
+	#[test]
+	fn test_export_linear_regressor() {
+		test_export("age", r#"{ "model": "linear", "max_epochs": 10 }"#);
+	}
+
+	#[test]
+	fn test_export_tree_regressor() {
+		test_export("age", r#"{ "model": "tree", "max_rounds": 10 }"#);
+	}
+
+	#[test]
+	fn test_export_linear_binary_classifier() {
+		test_export("diagnosis", r#"{ "model": "linear", "max_epochs": 10 }"#);
+	}
+
+	#[test]
+	fn test_export_tree_binary_classifier() {
+		test_export("diagnosis", r#"{ "model": "tree", "max_rounds": 10 }"#);
+	}
+
+	#[test]
+	fn test_export_linear_multiclass_classifier() {
+		test_export("chest_pain", r#"{ "model": "linear", "max_epochs": 10 }"#);
+	}
+
+	#[test]
+	fn test_export_tree_multiclass_classifier() {
+		test_export("chest_pain", r#"{ "model": "tree", "max_rounds": 10 }"#);
+	}
+}
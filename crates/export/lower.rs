@@ -0,0 +1,553 @@
+/*!
+This module lowers a model into a flat description of its inputs, feature computations, and estimator. The ONNX and PMML writers translate this description one piece at a time, so any part of a model that cannot be exported is rejected here with an error describing why.
+*/
+
+use anyhow::{anyhow, bail, Result};
+use modelfox_core::predict::{
+	BinaryClassificationModel, Column, Model, ModelInner, MulticlassClassificationModel,
+	RegressionModel,
+};
+use modelfox_features::bag_of_words::BagOfWordsFeatureGroupStrategy;
+use modelfox_text::NGram;
+use ndarray::prelude::*;
+use num::ToPrimitive;
+
+#[derive(Debug)]
+pub struct LoweredModel {
+	pub id: String,
+	pub target_column_name: String,
+	pub inputs: Vec<Input>,
+	pub feature_groups: Vec<FeatureGroup>,
+	pub estimator: Estimator,
+	pub task: Task,
+}
+
+#[derive(Debug)]
+pub enum Input {
+	Number { name: String },
+	Enum { name: String, variants: Vec<String> },
+	Text { name: String },
+}
+
+/// Each feature group reads a single input and produces one or more features. Enum inputs are read as the index of their variant, which is 0 for missing or invalid values.
+#[derive(Debug)]
+pub enum FeatureGroup {
+	/// The input's value, or its enum index.
+	Identity { input: usize },
+	/// `(value - mean) * scale`, or 0 if the value is missing. `scale` is 0 if the input's variance was 0.
+	Normalized { input: usize, mean: f32, scale: f32 },
+	/// One feature for each enum index in `0..n_indexes`, which is 1 if the input's enum index is equal to it and 0 otherwise.
+	OneHotEncoded { input: usize, n_indexes: usize },
+	/// One feature for each token, which counts the token's occurrences in the input or indicates whether it is present.
+	Tokens {
+		input: usize,
+		lowercase: bool,
+		strategy: TokensStrategy,
+		tokens: Vec<String>,
+	},
+	/// One feature for each discrete split in a tree model on this input, which is 1 if the split sends the input's enum index right and 0 otherwise. `directions[split][enum_index]` is `true` if the split sends that enum index right.
+	SplitDirections {
+		input: usize,
+		directions: Vec<Vec<bool>>,
+	},
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TokensStrategy {
+	Present,
+	Count,
+}
+
+#[derive(Debug)]
+pub enum Estimator {
+	/// The outputs are `features · weights + biases`, where `weights` has shape `(n_features, n_outputs)`.
+	Linear {
+		weights: Array2<f32>,
+		biases: Vec<f32>,
+	},
+	/// Each output is the sum of the values of its trees plus its bias.
+	Trees { trees: Vec<Tree>, biases: Vec<f32> },
+}
+
+#[derive(Debug)]
+pub struct Tree {
+	pub output: usize,
+	pub nodes: Vec<Node>,
+}
+
+#[derive(Debug)]
+pub enum Node {
+	/// Examples whose feature value is less than or equal to `threshold` are sent left and all others are sent right. Examples whose feature value is missing are sent left if `missing_values_left` is true.
+	Branch {
+		feature_index: usize,
+		threshold: f32,
+		missing_values_left: bool,
+		left_child_index: usize,
+		right_child_index: usize,
+	},
+	Leaf {
+		value: f32,
+	},
+}
+
+#[derive(Debug)]
+pub enum Task {
+	Regression,
+	BinaryClassification {
+		negative_class: String,
+		positive_class: String,
+		threshold: f32,
+	},
+	MulticlassClassification {
+		classes: Vec<String>,
+	},
+}
+
+impl Input {
+	pub fn name(&self) -> &str {
+		match self {
+			Input::Number { name } => name,
+			Input::Enum { name, .. } => name,
+			Input::Text { name } => name,
+		}
+	}
+}
+
+impl FeatureGroup {
+	pub fn input(&self) -> usize {
+		match self {
+			FeatureGroup::Identity { input } => *input,
+			FeatureGroup::Normalized { input, .. } => *input,
+			FeatureGroup::OneHotEncoded { input, .. } => *input,
+			FeatureGroup::Tokens { input, .. } => *input,
+			FeatureGroup::SplitDirections { input, .. } => *input,
+		}
+	}
+
+	pub fn n_features(&self) -> usize {
+		match self {
+			FeatureGroup::Identity { .. } => 1,
+			FeatureGroup::Normalized { .. } => 1,
+			FeatureGroup::OneHotEncoded { n_indexes, .. } => *n_indexes,
+			FeatureGroup::Tokens { tokens, .. } => tokens.len(),
+			FeatureGroup::SplitDirections { directions, .. } => directions.len(),
+		}
+	}
+}
+
+impl Estimator {
+	pub fn n_outputs(&self) -> usize {
+		match self {
+			Estimator::Linear { biases, .. } => biases.len(),
+			Estimator::Trees { biases, .. } => biases.len(),
+		}
+	}
+}
+
+pub fn lower(model: &Model, target_column_name: &str) -> Result<LoweredModel> {
+	if let ModelInner::Regressor(model) = &model.inner {
+		if model.target_transform.is_some() {
+			bail!("The regressor was trained with a target transform, which cannot be exported. Train the model without the target_transform option instead.");
+		}
+	}
+	if let ModelInner::Ranker(_) = &model.inner {
+		bail!("Rankers cannot be exported.");
+	}
+	let (columns, feature_groups) = match &model.inner {
+		ModelInner::Regressor(model) => (&model.columns, &model.feature_groups),
+		ModelInner::BinaryClassifier(model) => (&model.columns, &model.feature_groups),
+		ModelInner::MulticlassClassifier(model) => (&model.columns, &model.feature_groups),
+		ModelInner::Ranker(_) => unreachable!(),
+	};
+	let mut inputs = InputsBuilder {
+		columns,
+		inputs: Vec::new(),
+	};
+	let (feature_groups, estimator) = match &model.inner {
+		ModelInner::Regressor(model) => match &model.model {
+			RegressionModel::Linear(model) => (
+				lower_linear_feature_groups(feature_groups, &mut inputs)?,
+				Estimator::Linear {
+					weights: model.weights.clone().insert_axis(Axis(1)),
+					biases: vec![model.bias],
+				},
+			),
+			RegressionModel::Tree(model) => lower_trees(
+				feature_groups,
+				&mut inputs,
+				model.trees.iter().map(|tree| (0, tree)),
+				vec![model.bias],
+			)?,
+		},
+		ModelInner::BinaryClassifier(model) => match &model.model {
+			BinaryClassificationModel::Linear(model) => (
+				lower_linear_feature_groups(feature_groups, &mut inputs)?,
+				Estimator::Linear {
+					weights: model.weights.clone().insert_axis(Axis(1)),
+					biases: vec![model.bias],
+				},
+			),
+			BinaryClassificationModel::Tree(model) => lower_trees(
+				feature_groups,
+				&mut inputs,
+				model.trees.iter().map(|tree| (0, tree)),
+				vec![model.bias],
+			)?,
+		},
+		ModelInner::MulticlassClassifier(model) => match &model.model {
+			MulticlassClassificationModel::Linear(model) => (
+				lower_linear_feature_groups(feature_groups, &mut inputs)?,
+				Estimator::Linear {
+					weights: model.weights.clone(),
+					biases: model.biases.to_vec(),
+				},
+			),
+			MulticlassClassificationModel::Tree(model) => lower_trees(
+				feature_groups,
+				&mut inputs,
+				model
+					.trees
+					.indexed_iter()
+					.map(|((_, class_index), tree)| (class_index, tree)),
+				model.biases.to_vec(),
+			)?,
+		},
+		ModelInner::Ranker(_) => unreachable!(),
+	};
+	let task = match &model.inner {
+		ModelInner::Regressor(_) => Task::Regression,
+		ModelInner::BinaryClassifier(model) => Task::BinaryClassification {
+			negative_class: model.negative_class.clone(),
+			positive_class: model.positive_class.clone(),
+			threshold: model.threshold,
+		},
+		ModelInner::MulticlassClassifier(model) => Task::MulticlassClassification {
+			classes: model.classes.clone(),
+		},
+		ModelInner::Ranker(_) => unreachable!(),
+	};
+	Ok(LoweredModel {
+		id: model.id.clone(),
+		target_column_name: target_column_name.to_owned(),
+		inputs: inputs.inputs,
+		feature_groups,
+		estimator,
+		task,
+	})
+}
+
+/// This struct collects the columns used by the model's feature groups, in the order they are first used.
+struct InputsBuilder<'a> {
+	columns: &'a [Column],
+	inputs: Vec<Input>,
+}
+
+impl<'a> InputsBuilder<'a> {
+	fn get(&mut self, column_name: &str) -> Result<usize> {
+		if let Some(index) = self
+			.inputs
+			.iter()
+			.position(|input| input.name() == column_name)
+		{
+			return Ok(index);
+		}
+		let column = self
+			.columns
+			.iter()
+			.find(|column| column_name_for_column(column) == column_name)
+			.ok_or_else(|| anyhow!("The model has no column named \"{}\".", column_name))?;
+		let input = match column {
+			Column::Unknown(_) => bail!(
+				"The column \"{}\" has an unknown type and cannot be exported.",
+				column_name
+			),
+			Column::Number(column) => Input::Number {
+				name: column.name.clone(),
+			},
+			Column::Enum(column) => Input::Enum {
+				name: column.name.clone(),
+				variants: column.variants.clone(),
+			},
+			Column::Text(column) => Input::Text {
+				name: column.name.clone(),
+			},
+		};
+		self.inputs.push(input);
+		Ok(self.inputs.len() - 1)
+	}
+
+	fn n_enum_indexes(&self, input: usize) -> usize {
+		match &self.inputs[input] {
+			Input::Enum { variants, .. } => variants.len() + 1,
+			_ => 0,
+		}
+	}
+}
+
+fn column_name_for_column(column: &Column) -> &str {
+	match column {
+		Column::Unknown(column) => &column.name,
+		Column::Number(column) => &column.name,
+		Column::Enum(column) => &column.name,
+		Column::Text(column) => &column.name,
+	}
+}
+
+fn lower_linear_feature_groups(
+	feature_groups: &[modelfox_features::FeatureGroup],
+	inputs: &mut InputsBuilder,
+) -> Result<Vec<FeatureGroup>> {
+	feature_groups
+		.iter()
+		.map(|feature_group| lower_feature_group(feature_group, inputs))
+		.collect()
+}
+
+/// Bail if the features for the column named `column_name` impute invalid values or have a missing indicator, because exported models cannot compute them.
+fn check_impute(column_name: &str, impute: bool, missing_indicator: bool) -> Result<()> {
+	if impute || missing_indicator {
+		bail!(
+			"The features for the column \"{}\" impute invalid values or have a missing indicator, which cannot be exported. Train the model without the impute and missing_indicator options instead.",
+			column_name
+		);
+	}
+	Ok(())
+}
+
+fn lower_feature_group(
+	feature_group: &modelfox_features::FeatureGroup,
+	inputs: &mut InputsBuilder,
+) -> Result<FeatureGroup> {
+	match feature_group {
+		modelfox_features::FeatureGroup::Identity(feature_group) => {
+			check_impute(
+				&feature_group.source_column_name,
+				feature_group.impute.is_some(),
+				feature_group.missing_indicator,
+			)?;
+			Ok(FeatureGroup::Identity {
+				input: inputs.get(&feature_group.source_column_name)?,
+			})
+		}
+		modelfox_features::FeatureGroup::Normalized(feature_group) => {
+			check_impute(
+				&feature_group.source_column_name,
+				feature_group.impute.is_some(),
+				feature_group.missing_indicator,
+			)?;
+			let scale = if feature_group.variance == 0.0 {
+				0.0
+			} else {
+				1.0 / feature_group.variance.sqrt()
+			};
+			Ok(FeatureGroup::Normalized {
+				input: inputs.get(&feature_group.source_column_name)?,
+				mean: feature_group.mean,
+				scale,
+			})
+		}
+		modelfox_features::FeatureGroup::OneHotEncoded(feature_group) => {
+			let input = inputs.get(&feature_group.source_column_name)?;
+			Ok(FeatureGroup::OneHotEncoded {
+				input,
+				n_indexes: inputs.n_enum_indexes(input),
+			})
+		}
+		modelfox_features::FeatureGroup::BagOfWords(feature_group) => {
+			let column_name = &feature_group.source_column_name;
+			let strategy = match feature_group.strategy {
+				BagOfWordsFeatureGroupStrategy::Present => TokensStrategy::Present,
+				BagOfWordsFeatureGroupStrategy::Count => TokensStrategy::Count,
+				BagOfWordsFeatureGroupStrategy::TfIdf => bail!(
+					"The bag of words features for the column \"{}\" use the tf-idf strategy, which cannot be exported. Use the present or count strategy instead.",
+					column_name
+				),
+			};
+			let tokens = feature_group
+				.ngrams
+				.keys()
+				.map(|ngram| match ngram {
+					NGram::Unigram(token) if token.chars().all(|c| c.is_alphanumeric()) => {
+						Ok(token.clone())
+					}
+					NGram::Unigram(token) => Err(anyhow!(
+						"The bag of words features for the column \"{}\" include the punctuation token \"{}\", which cannot be exported.",
+						column_name,
+						token
+					)),
+					NGram::Bigram(_, _) => Err(anyhow!(
+						"The bag of words features for the column \"{}\" include bigrams, which cannot be exported.",
+						column_name
+					)),
+					NGram::Character(_) => Err(anyhow!(
+						"The bag of words features for the column \"{}\" include character ngrams, which cannot be exported.",
+						column_name
+					)),
+				})
+				.collect::<Result<Vec<_>>>()?;
+			Ok(FeatureGroup::Tokens {
+				input: inputs.get(column_name)?,
+				lowercase: feature_group.tokenizer.lowercase,
+				strategy,
+				tokens,
+			})
+		}
+		modelfox_features::FeatureGroup::WordEmbedding(feature_group) => bail!(
+			"The word embedding features for the column \"{}\" cannot be exported.",
+			feature_group.source_column_name
+		),
+		modelfox_features::FeatureGroup::BagOfWordsCosineSimilarity(feature_group) => bail!(
+			"The bag of words cosine similarity feature for the columns \"{}\" and \"{}\" cannot be exported.",
+			feature_group.source_column_name_a,
+			feature_group.source_column_name_b
+		),
+		modelfox_features::FeatureGroup::HashedBagOfWords(feature_group) => bail!(
+			"The hashed bag of words features for the column \"{}\" cannot be exported.",
+			feature_group.source_column_name
+		),
+		modelfox_features::FeatureGroup::HashedEnum(feature_group) => bail!(
+			"The hashed enum features for the column \"{}\" cannot be exported.",
+			feature_group.source_column_name
+		),
+		modelfox_features::FeatureGroup::Interaction(feature_group) => bail!(
+			"The interaction features for the columns \"{}\" and \"{}\" cannot be exported.",
+			feature_group.source_a.source_column_name(),
+			feature_group.source_b.source_column_name()
+		),
+		modelfox_features::FeatureGroup::Polynomial(feature_group) => bail!(
+			"The polynomial features for the column \"{}\" cannot be exported.",
+			feature_group.source_column_name
+		),
+		modelfox_features::FeatureGroup::BinnedNumber(feature_group) => bail!(
+			"The binned number features for the column \"{}\" cannot be exported.",
+			feature_group.source_column_name
+		),
+	}
+}
+
+/// Trees split enum features by looking up a direction for each enum index. Each of these discrete splits is lowered to a continuous split on a `SplitDirections` feature, which is placed after all the other features.
+enum TreeFeature {
+	Continuous(usize),
+	Discrete(usize),
+}
+
+fn lower_trees<'a>(
+	feature_groups: &[modelfox_features::FeatureGroup],
+	inputs: &mut InputsBuilder,
+	trees: impl Iterator<Item = (usize, &'a modelfox_tree::Tree)>,
+	biases: Vec<f32>,
+) -> Result<(Vec<FeatureGroup>, Estimator)> {
+	// Lower the feature groups, recording where each of the model's features went.
+	let mut lowered_feature_groups = Vec::new();
+	let mut tree_features = Vec::new();
+	let mut n_continuous_features = 0;
+	for feature_group in feature_groups.iter() {
+		let lowered_feature_group = lower_feature_group(feature_group, inputs)?;
+		let input = lowered_feature_group.input();
+		if matches!(lowered_feature_group, FeatureGroup::Identity { .. })
+			&& matches!(inputs.inputs[input], Input::Enum { .. })
+		{
+			tree_features.push(TreeFeature::Discrete(input));
+			continue;
+		}
+		for _ in 0..lowered_feature_group.n_features() {
+			tree_features.push(TreeFeature::Continuous(n_continuous_features));
+			n_continuous_features += 1;
+		}
+		lowered_feature_groups.push(lowered_feature_group);
+	}
+	// Collect the directions of every discrete split, grouped by input.
+	let trees = trees.collect::<Vec<_>>();
+	let mut split_directions: Vec<(usize, Vec<Vec<bool>>)> = Vec::new();
+	let mut split_feature_indexes = Vec::with_capacity(trees.len());
+	for (_, tree) in trees.iter() {
+		let mut tree_split_feature_indexes = Vec::with_capacity(tree.nodes.len());
+		for node in tree.nodes.iter() {
+			let split = match node {
+				modelfox_tree::Node::Branch(modelfox_tree::BranchNode {
+					split: modelfox_tree::BranchSplit::Discrete(split),
+					..
+				}) => split,
+				_ => {
+					tree_split_feature_indexes.push(None);
+					continue;
+				}
+			};
+			let input = match tree_features[split.feature_index] {
+				TreeFeature::Discrete(input) => input,
+				TreeFeature::Continuous(_) => unreachable!(),
+			};
+			let directions = (0..inputs.n_enum_indexes(input))
+				.map(|enum_index| split.directions.get(enum_index).map_or(false, |d| *d))
+				.collect();
+			let group_index = match split_directions.iter().position(|(i, _)| *i == input) {
+				Some(group_index) => group_index,
+				None => {
+					split_directions.push((input, Vec::new()));
+					split_directions.len() - 1
+				}
+			};
+			let group = &mut split_directions[group_index].1;
+			group.push(directions);
+			tree_split_feature_indexes.push(Some((group_index, group.len() - 1)));
+		}
+		split_feature_indexes.push(tree_split_feature_indexes);
+	}
+	let mut split_directions_offsets = Vec::with_capacity(split_directions.len());
+	let mut offset = n_continuous_features;
+	for (_, directions) in split_directions.iter() {
+		split_directions_offsets.push(offset);
+		offset += directions.len();
+	}
+	// Lower the trees.
+	let trees = trees
+		.into_iter()
+		.zip(split_feature_indexes)
+		.map(|((output, tree), tree_split_feature_indexes)| {
+			let nodes = tree
+				.nodes
+				.iter()
+				.zip(tree_split_feature_indexes)
+				.map(|(node, split_feature_index)| match node {
+					modelfox_tree::Node::Leaf(leaf) => Node::Leaf {
+						value: (tree.weight as f64 * leaf.value).to_f32().unwrap(),
+					},
+					modelfox_tree::Node::Branch(branch) => {
+						let (feature_index, threshold, missing_values_left) = match &branch.split {
+							modelfox_tree::BranchSplit::Continuous(split) => {
+								let feature_index = match tree_features[split.feature_index] {
+									TreeFeature::Continuous(feature_index) => feature_index,
+									TreeFeature::Discrete(_) => unreachable!(),
+								};
+								let missing_values_left = matches!(
+									split.invalid_values_direction,
+									modelfox_tree::SplitDirection::Left
+								);
+								(feature_index, split.split_value, missing_values_left)
+							}
+							// Split direction features are never missing.
+							modelfox_tree::BranchSplit::Discrete(_) => {
+								let (group_index, index) = split_feature_index.unwrap();
+								(split_directions_offsets[group_index] + index, 0.5, false)
+							}
+						};
+						Node::Branch {
+							feature_index,
+							threshold,
+							missing_values_left,
+							left_child_index: branch.left_child_index,
+							right_child_index: branch.right_child_index,
+						}
+					}
+				})
+				.collect();
+			Tree { output, nodes }
+		})
+		.collect();
+	lowered_feature_groups.extend(
+		split_directions
+			.into_iter()
+			.map(|(input, directions)| FeatureGroup::SplitDirections { input, directions }),
+	);
+	Ok((lowered_feature_groups, Estimator::Trees { trees, biases }))
+}
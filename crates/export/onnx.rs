@@ -0,0 +1,490 @@
+/*!
+This module writes a lowered model as an [ONNX](https://onnx.ai) model.
+
+The graph has one input of shape `[N, 1]` for each column the model uses, with type `float` for number columns and `string` for enum columns. Regressors have a single output named `value`. Binary classifiers output the `probability` of the positive class and the predicted `label`. Multiclass classifiers output the `probabilities` of each class and the predicted `label`.
+*/
+
+use crate::{
+	lower::{Estimator, FeatureGroup, Input, LoweredModel, Node, Task},
+	protobuf::Writer,
+};
+use anyhow::{bail, Result};
+use num::ToPrimitive;
+
+const IR_VERSION: i64 = 8;
+const OPSET_VERSION: i64 = 13;
+const ML_DOMAIN: &str = "ai.onnx.ml";
+const ML_OPSET_VERSION: i64 = 3;
+
+const TENSOR_TYPE_FLOAT: i64 = 1;
+const TENSOR_TYPE_INT64: i64 = 7;
+const TENSOR_TYPE_STRING: i64 = 8;
+
+const ATTRIBUTE_TYPE_INT: i64 = 2;
+const ATTRIBUTE_TYPE_STRING: i64 = 3;
+const ATTRIBUTE_TYPE_FLOATS: i64 = 6;
+const ATTRIBUTE_TYPE_INTS: i64 = 7;
+const ATTRIBUTE_TYPE_STRINGS: i64 = 8;
+
+pub fn write(model: &LoweredModel) -> Result<Vec<u8>> {
+	let mut graph = Graph::new();
+	graph.writer.string(2, "modelfox");
+
+	// Add an input for each column and encode enum columns as the index of their variant.
+	let mut inputs = Vec::with_capacity(model.inputs.len());
+	for input in model.inputs.iter() {
+		match input {
+			Input::Number { name } => {
+				graph.input(name, TENSOR_TYPE_FLOAT);
+				inputs.push(InputValue::Number(name.clone()));
+			}
+			Input::Enum { name, variants } => {
+				graph.input(name, TENSOR_TYPE_STRING);
+				let index = graph.name(name);
+				graph.node(
+					"LabelEncoder",
+					ML_DOMAIN,
+					&[name],
+					&[&index],
+					&[
+						Attribute::Strings("keys_strings", variants.clone()),
+						Attribute::Ints(
+							"values_int64s",
+							(1..=variants.len())
+								.map(|index| index.to_i64().unwrap())
+								.collect(),
+						),
+						Attribute::Int("default_int64", 0),
+					],
+				);
+				inputs.push(InputValue::Enum(index));
+			}
+			Input::Text { name } => bail!(
+				"The model uses the text column \"{}\". ONNX has no operator to tokenize text, so this model can only be exported to PMML.",
+				name
+			),
+		}
+	}
+
+	// Compute the features for each feature group and concatenate them.
+	let mut feature_groups = Vec::with_capacity(model.feature_groups.len());
+	for feature_group in model.feature_groups.iter() {
+		let input = &inputs[feature_group.input()];
+		let features = match feature_group {
+			FeatureGroup::Identity { .. } => input.float(&mut graph),
+			FeatureGroup::Normalized { mean, scale, .. } => {
+				let value = input.float(&mut graph);
+				let mean = graph.float_constant(&[1], &[*mean]);
+				let scale = graph.float_constant(&[1], &[*scale]);
+				let zero = graph.float_constant(&[1], &[0.0]);
+				let centered = graph.op("Sub", &[&value, &mean], &[]);
+				let normalized = graph.op("Mul", &[&centered, &scale], &[]);
+				let is_nan = graph.op("IsNaN", &[&value], &[]);
+				graph.op("Where", &[&is_nan, &zero, &normalized], &[])
+			}
+			FeatureGroup::OneHotEncoded { n_indexes, .. } => {
+				let index = input.index();
+				let indexes = (0..*n_indexes)
+					.map(|index| index.to_i64().unwrap())
+					.collect::<Vec<_>>();
+				let indexes = graph.int_constant(&[n_indexes.to_i64().unwrap()], &indexes);
+				let equal = graph.op("Equal", &[index, &indexes], &[]);
+				graph.op(
+					"Cast",
+					&[&equal],
+					&[Attribute::Int("to", TENSOR_TYPE_FLOAT)],
+				)
+			}
+			FeatureGroup::SplitDirections { directions, .. } => {
+				let index = input.index();
+				let n_indexes = directions.first().map(|d| d.len()).unwrap_or(0);
+				let n_splits = directions.len();
+				let mut table = vec![0.0; n_indexes * n_splits];
+				for (split_index, directions) in directions.iter().enumerate() {
+					for (enum_index, direction) in directions.iter().enumerate() {
+						table[enum_index * n_splits + split_index] =
+							if *direction { 1.0 } else { 0.0 };
+					}
+				}
+				let table = graph.float_constant(
+					&[n_indexes.to_i64().unwrap(), n_splits.to_i64().unwrap()],
+					&table,
+				);
+				let gathered = graph.op("Gather", &[&table, index], &[]);
+				let shape = graph.int_constant(&[2], &[-1, n_splits.to_i64().unwrap()]);
+				graph.op("Reshape", &[&gathered, &shape], &[])
+			}
+			FeatureGroup::Tokens { .. } => bail!(
+				"ONNX has no operator to tokenize text, so bag of words features can only be exported to PMML."
+			),
+		};
+		feature_groups.push(features);
+	}
+	let feature_groups = feature_groups
+		.iter()
+		.map(|name| name.as_str())
+		.collect::<Vec<_>>();
+	let features = graph.op("Concat", &feature_groups, &[Attribute::Int("axis", 1)]);
+
+	// Compute the logits with the estimator.
+	let n_outputs = model.estimator.n_outputs();
+	let logits = match &model.estimator {
+		Estimator::Linear { weights, biases } => {
+			let weights = graph.float_constant(
+				&[
+					weights.nrows().to_i64().unwrap(),
+					weights.ncols().to_i64().unwrap(),
+				],
+				&weights.iter().cloned().collect::<Vec<_>>(),
+			);
+			let biases = graph.float_constant(&[biases.len().to_i64().unwrap()], biases);
+			let product = graph.op("MatMul", &[&features, &weights], &[]);
+			graph.op("Add", &[&product, &biases], &[])
+		}
+		Estimator::Trees { trees, biases } => {
+			let logits = graph.name("logits");
+			graph.node(
+				"TreeEnsembleRegressor",
+				ML_DOMAIN,
+				&[&features],
+				&[&logits],
+				&tree_ensemble_attributes(trees, biases),
+			);
+			logits
+		}
+	};
+
+	// Compute the outputs for the task.
+	match &model.task {
+		Task::Regression => {
+			graph.node("Identity", "", &[&logits], &["value"], &[]);
+			graph.output("value", TENSOR_TYPE_FLOAT, 1);
+		}
+		Task::BinaryClassification {
+			negative_class,
+			positive_class,
+			threshold,
+		} => {
+			graph.node("Sigmoid", "", &[&logits], &["probability"], &[]);
+			let threshold = graph.float_constant(&[1], &[*threshold]);
+			let is_positive = graph.op("GreaterOrEqual", &["probability", &threshold], &[]);
+			let positive_class = graph.string_constant(&[1], std::slice::from_ref(positive_class));
+			let negative_class = graph.string_constant(&[1], std::slice::from_ref(negative_class));
+			graph.node(
+				"Where",
+				"",
+				&[&is_positive, &positive_class, &negative_class],
+				&["label"],
+				&[],
+			);
+			graph.output("probability", TENSOR_TYPE_FLOAT, 1);
+			graph.output("label", TENSOR_TYPE_STRING, 1);
+		}
+		Task::MulticlassClassification { classes } => {
+			graph.node(
+				"Softmax",
+				"",
+				&[&logits],
+				&["probabilities"],
+				&[Attribute::Int("axis", 1)],
+			);
+			let class_index = graph.op(
+				"ArgMax",
+				&["probabilities"],
+				&[Attribute::Int("axis", 1), Attribute::Int("keepdims", 1)],
+			);
+			let classes = graph.string_constant(&[classes.len().to_i64().unwrap()], classes);
+			graph.node("Gather", "", &[&classes, &class_index], &["label"], &[]);
+			graph.output(
+				"probabilities",
+				TENSOR_TYPE_FLOAT,
+				n_outputs.to_i64().unwrap(),
+			);
+			graph.output("label", TENSOR_TYPE_STRING, 1);
+		}
+	}
+
+	let mut writer = Writer::new();
+	writer.int(1, IR_VERSION);
+	writer.string(2, "modelfox");
+	writer.string(3, env!("CARGO_PKG_VERSION"));
+	writer.bytes(7, &graph.writer.into_bytes());
+	writer.message(8, |writer| {
+		writer.string(1, "");
+		writer.int(2, OPSET_VERSION);
+	});
+	writer.message(8, |writer| {
+		writer.string(1, ML_DOMAIN);
+		writer.int(2, ML_OPSET_VERSION);
+	});
+	for (key, value) in [
+		("modelfox_model_id", model.id.as_str()),
+		(
+			"modelfox_target_column_name",
+			model.target_column_name.as_str(),
+		),
+	] {
+		writer.message(14, |writer| {
+			writer.string(1, key);
+			writer.string(2, value);
+		});
+	}
+	Ok(writer.into_bytes())
+}
+
+/// This is the tensor holding an input's value after it has been read into the graph.
+enum InputValue {
+	Number(String),
+	Enum(String),
+}
+
+impl InputValue {
+	/// Get the tensor holding the input's value as a float, which for enum inputs is the index of the variant.
+	fn float(&self, graph: &mut Graph) -> String {
+		match self {
+			InputValue::Number(name) => name.clone(),
+			InputValue::Enum(index) => {
+				graph.op("Cast", &[index], &[Attribute::Int("to", TENSOR_TYPE_FLOAT)])
+			}
+		}
+	}
+
+	fn index(&self) -> &str {
+		match self {
+			InputValue::Enum(index) => index,
+			InputValue::Number(_) => unreachable!(),
+		}
+	}
+}
+
+fn tree_ensemble_attributes(trees: &[crate::lower::Tree], biases: &[f32]) -> Vec<Attribute> {
+	let mut nodes_treeids = Vec::new();
+	let mut nodes_nodeids = Vec::new();
+	let mut nodes_featureids = Vec::new();
+	let mut nodes_values = Vec::new();
+	let mut nodes_modes = Vec::new();
+	let mut nodes_truenodeids = Vec::new();
+	let mut nodes_falsenodeids = Vec::new();
+	let mut nodes_missing_value_tracks_true = Vec::new();
+	let mut target_treeids = Vec::new();
+	let mut target_nodeids = Vec::new();
+	let mut target_ids = Vec::new();
+	let mut target_weights = Vec::new();
+	for (tree_index, tree) in trees.iter().enumerate() {
+		let tree_index = tree_index.to_i64().unwrap();
+		for (node_index, node) in tree.nodes.iter().enumerate() {
+			let node_index = node_index.to_i64().unwrap();
+			nodes_treeids.push(tree_index);
+			nodes_nodeids.push(node_index);
+			match node {
+				Node::Branch {
+					feature_index,
+					threshold,
+					missing_values_left,
+					left_child_index,
+					right_child_index,
+				} => {
+					nodes_missing_value_tracks_true.push(*missing_values_left as i64);
+					nodes_featureids.push(feature_index.to_i64().unwrap());
+					nodes_values.push(*threshold);
+					nodes_modes.push("BRANCH_LEQ".to_owned());
+					nodes_truenodeids.push(left_child_index.to_i64().unwrap());
+					nodes_falsenodeids.push(right_child_index.to_i64().unwrap());
+				}
+				Node::Leaf { value } => {
+					nodes_missing_value_tracks_true.push(0);
+					nodes_featureids.push(0);
+					nodes_values.push(0.0);
+					nodes_modes.push("LEAF".to_owned());
+					nodes_truenodeids.push(0);
+					nodes_falsenodeids.push(0);
+					target_treeids.push(tree_index);
+					target_nodeids.push(node_index);
+					target_ids.push(tree.output.to_i64().unwrap());
+					target_weights.push(*value);
+				}
+			}
+		}
+	}
+	vec![
+		Attribute::String("aggregate_function", "SUM".to_owned()),
+		Attribute::Floats("base_values", biases.to_owned()),
+		Attribute::Int("n_targets", biases.len().to_i64().unwrap()),
+		Attribute::Ints("nodes_falsenodeids", nodes_falsenodeids),
+		Attribute::Ints("nodes_featureids", nodes_featureids),
+		Attribute::Ints(
+			"nodes_missing_value_tracks_true",
+			nodes_missing_value_tracks_true,
+		),
+		Attribute::Strings("nodes_modes", nodes_modes),
+		Attribute::Ints("nodes_nodeids", nodes_nodeids),
+		Attribute::Ints("nodes_treeids", nodes_treeids),
+		Attribute::Ints("nodes_truenodeids", nodes_truenodeids),
+		Attribute::Floats("nodes_values", nodes_values),
+		Attribute::String("post_transform", "NONE".to_owned()),
+		Attribute::Ints("target_ids", target_ids),
+		Attribute::Ints("target_nodeids", target_nodeids),
+		Attribute::Ints("target_treeids", target_treeids),
+		Attribute::Floats("target_weights", target_weights),
+	]
+}
+
+enum Attribute {
+	Int(&'static str, i64),
+	String(&'static str, String),
+	Floats(&'static str, Vec<f32>),
+	Ints(&'static str, Vec<i64>),
+	Strings(&'static str, Vec<String>),
+}
+
+/// This struct accumulates the fields of an ONNX `GraphProto`.
+struct Graph {
+	writer: Writer,
+	n_names: usize,
+}
+
+impl Graph {
+	fn new() -> Graph {
+		Graph {
+			writer: Writer::new(),
+			n_names: 0,
+		}
+	}
+
+	/// Generate a unique name for a tensor or node.
+	fn name(&mut self, hint: &str) -> String {
+		self.n_names += 1;
+		format!("{}_{}", hint, self.n_names)
+	}
+
+	fn input(&mut self, name: &str, elem_type: i64) {
+		write_value_info(&mut self.writer, 11, name, elem_type, 1);
+	}
+
+	fn output(&mut self, name: &str, elem_type: i64, n_columns: i64) {
+		write_value_info(&mut self.writer, 12, name, elem_type, n_columns);
+	}
+
+	fn node(
+		&mut self,
+		op_type: &str,
+		domain: &str,
+		inputs: &[&str],
+		outputs: &[&str],
+		attributes: &[Attribute],
+	) {
+		let name = self.name(op_type);
+		self.writer.message(1, |writer| {
+			for input in inputs {
+				writer.string(1, input);
+			}
+			for output in outputs {
+				writer.string(2, output);
+			}
+			writer.string(3, &name);
+			writer.string(4, op_type);
+			for attribute in attributes {
+				writer.message(5, |writer| write_attribute(writer, attribute));
+			}
+			if !domain.is_empty() {
+				writer.string(7, domain);
+			}
+		});
+	}
+
+	/// Add a node from the default domain with a single output and return the output's name.
+	fn op(&mut self, op_type: &str, inputs: &[&str], attributes: &[Attribute]) -> String {
+		let output = self.name(&op_type.to_lowercase());
+		self.node(op_type, "", inputs, &[&output], attributes);
+		output
+	}
+
+	fn float_constant(&mut self, dims: &[i64], values: &[f32]) -> String {
+		let name = self.name("constant");
+		self.writer.message(5, |writer| {
+			writer.packed_ints(1, dims);
+			writer.int(2, TENSOR_TYPE_FLOAT);
+			writer.packed_floats(4, values);
+			writer.string(8, &name);
+		});
+		name
+	}
+
+	fn int_constant(&mut self, dims: &[i64], values: &[i64]) -> String {
+		let name = self.name("constant");
+		self.writer.message(5, |writer| {
+			writer.packed_ints(1, dims);
+			writer.int(2, TENSOR_TYPE_INT64);
+			writer.packed_ints(7, values);
+			writer.string(8, &name);
+		});
+		name
+	}
+
+	fn string_constant(&mut self, dims: &[i64], values: &[String]) -> String {
+		let name = self.name("constant");
+		self.writer.message(5, |writer| {
+			writer.packed_ints(1, dims);
+			writer.int(2, TENSOR_TYPE_STRING);
+			for value in values {
+				writer.string(6, value);
+			}
+			writer.string(8, &name);
+		});
+		name
+	}
+}
+
+/// Write a `ValueInfoProto` for a tensor of shape `[N, n_columns]`.
+fn write_value_info(
+	writer: &mut Writer,
+	field_number: u32,
+	name: &str,
+	elem_type: i64,
+	n_columns: i64,
+) {
+	writer.message(field_number, |writer| {
+		writer.string(1, name);
+		writer.message(2, |writer| {
+			writer.message(1, |writer| {
+				writer.int(1, elem_type);
+				writer.message(2, |writer| {
+					writer.message(1, |writer| writer.string(2, "N"));
+					writer.message(1, |writer| writer.int(1, n_columns));
+				});
+			});
+		});
+	});
+}
+
+fn write_attribute(writer: &mut Writer, attribute: &Attribute) {
+	match attribute {
+		Attribute::Int(name, value) => {
+			writer.string(1, name);
+			writer.int(3, *value);
+			writer.int(20, ATTRIBUTE_TYPE_INT);
+		}
+		Attribute::String(name, value) => {
+			writer.string(1, name);
+			writer.string(4, value);
+			writer.int(20, ATTRIBUTE_TYPE_STRING);
+		}
+		Attribute::Floats(name, values) => {
+			writer.string(1, name);
+			writer.packed_floats(7, values);
+			writer.int(20, ATTRIBUTE_TYPE_FLOATS);
+		}
+		Attribute::Ints(name, values) => {
+			writer.string(1, name);
+			writer.packed_ints(8, values);
+			writer.int(20, ATTRIBUTE_TYPE_INTS);
+		}
+		Attribute::Strings(name, values) => {
+			writer.string(1, name);
+			for value in values {
+				writer.string(9, value);
+			}
+			writer.int(20, ATTRIBUTE_TYPE_STRINGS);
+		}
+	}
+}
@@ -0,0 +1,695 @@
+/*!
+This module writes a lowered model as a [PMML 4.4](https://dmg.org/pmml/v4-4-1/GeneralStructure.html) document.
+
+Each feature is a `DerivedField` in the `TransformationDictionary`. Linear models are written as a `RegressionModel` and tree models as a `MiningModel` that sums a `TreeModel` for each tree. Classifiers chain the summed trees into a `RegressionModel` that applies the bias and the logistic or softmax function. Binary classifiers output a `label` field that applies the model's threshold.
+*/
+
+use crate::lower::{Estimator, FeatureGroup, Input, LoweredModel, Node, Task, TokensStrategy};
+use anyhow::Result;
+use std::fmt::Write;
+
+pub fn write(model: &LoweredModel) -> Result<Vec<u8>> {
+	let mut xml = Xml::new();
+	xml.declaration();
+	xml.open(
+		"PMML",
+		&[("xmlns", "http://www.dmg.org/PMML-4_4"), ("version", "4.4")],
+	);
+	xml.open("Header", &[]);
+	xml.empty(
+		"Application",
+		&[("name", "ModelFox"), ("version", env!("CARGO_PKG_VERSION"))],
+	);
+	xml.close("Header");
+	write_data_dictionary(&mut xml, model);
+	write_transformation_dictionary(&mut xml, model);
+	match &model.estimator {
+		Estimator::Linear { .. } => write_linear_model(&mut xml, model),
+		Estimator::Trees { .. } => write_tree_model(&mut xml, model),
+	}
+	xml.close("PMML");
+	Ok(xml.into_string().into_bytes())
+}
+
+fn write_data_dictionary(xml: &mut Xml, model: &LoweredModel) {
+	let n_fields = (model.inputs.len() + 1).to_string();
+	xml.open("DataDictionary", &[("numberOfFields", &n_fields)]);
+	for input in model.inputs.iter() {
+		match input {
+			Input::Number { name } => xml.empty(
+				"DataField",
+				&[
+					("name", name),
+					("optype", "continuous"),
+					("dataType", "double"),
+				],
+			),
+			Input::Enum { name, variants } => {
+				xml.open(
+					"DataField",
+					&[
+						("name", name),
+						("optype", "categorical"),
+						("dataType", "string"),
+					],
+				);
+				for variant in variants {
+					xml.empty("Value", &[("value", variant)]);
+				}
+				xml.close("DataField");
+			}
+			Input::Text { name } => xml.empty(
+				"DataField",
+				&[
+					("name", name),
+					("optype", "categorical"),
+					("dataType", "string"),
+				],
+			),
+		}
+	}
+	let target = model.target_column_name.as_str();
+	match &model.task {
+		Task::Regression => xml.empty(
+			"DataField",
+			&[
+				("name", target),
+				("optype", "continuous"),
+				("dataType", "double"),
+			],
+		),
+		Task::BinaryClassification {
+			negative_class,
+			positive_class,
+			..
+		} => {
+			xml.open(
+				"DataField",
+				&[
+					("name", target),
+					("optype", "categorical"),
+					("dataType", "string"),
+				],
+			);
+			xml.empty("Value", &[("value", negative_class)]);
+			xml.empty("Value", &[("value", positive_class)]);
+			xml.close("DataField");
+		}
+		Task::MulticlassClassification { classes } => {
+			xml.open(
+				"DataField",
+				&[
+					("name", target),
+					("optype", "categorical"),
+					("dataType", "string"),
+				],
+			);
+			for class in classes {
+				xml.empty("Value", &[("value", class)]);
+			}
+			xml.close("DataField");
+		}
+	}
+	xml.close("DataDictionary");
+}
+
+/// This is the name of the derived field holding the enum index of an enum input.
+fn index_field_name(input: &Input) -> String {
+	format!("modelfox_index({})", input.name())
+}
+
+/// This is the name of the derived field holding a feature.
+fn feature_field_name(feature_index: usize) -> String {
+	format!("modelfox_feature({})", feature_index)
+}
+
+fn write_transformation_dictionary(xml: &mut Xml, model: &LoweredModel) {
+	xml.open("TransformationDictionary", &[]);
+	// Map the variants of each enum input to their index, with 0 for missing or invalid values.
+	for input in model.inputs.iter() {
+		let variants = match input {
+			Input::Enum { variants, .. } => variants,
+			_ => continue,
+		};
+		let name = index_field_name(input);
+		xml.open(
+			"DerivedField",
+			&[
+				("name", &name),
+				("optype", "continuous"),
+				("dataType", "integer"),
+			],
+		);
+		xml.open(
+			"MapValues",
+			&[
+				("outputColumn", "index"),
+				("dataType", "integer"),
+				("mapMissingTo", "0"),
+				("defaultValue", "0"),
+			],
+		);
+		xml.empty(
+			"FieldColumnPair",
+			&[("field", input.name()), ("column", "variant")],
+		);
+		xml.open("InlineTable", &[]);
+		for (index, variant) in variants.iter().enumerate() {
+			xml.open("row", &[]);
+			xml.text("variant", variant);
+			xml.text("index", &(index + 1).to_string());
+			xml.close("row");
+		}
+		xml.close("InlineTable");
+		xml.close("MapValues");
+		xml.close("DerivedField");
+	}
+	// Write a derived field for each feature.
+	let mut feature_index = 0;
+	for feature_group in model.feature_groups.iter() {
+		let input = &model.inputs[feature_group.input()];
+		// This is the field holding the input's value as a number, which for enum inputs is the index of the variant.
+		let value_field = match input {
+			Input::Enum { .. } => index_field_name(input),
+			_ => input.name().to_owned(),
+		};
+		for index in 0..feature_group.n_features() {
+			xml.open(
+				"DerivedField",
+				&[
+					("name", &feature_field_name(feature_index)),
+					("optype", "continuous"),
+					("dataType", "double"),
+				],
+			);
+			match feature_group {
+				FeatureGroup::Identity { .. } => {
+					xml.empty("FieldRef", &[("field", &value_field)]);
+				}
+				FeatureGroup::Normalized { mean, scale, .. } => {
+					xml.open("Apply", &[("function", "if")]);
+					xml.open("Apply", &[("function", "isMissing")]);
+					xml.empty("FieldRef", &[("field", &value_field)]);
+					xml.close("Apply");
+					xml.text("Constant", "0");
+					xml.open("Apply", &[("function", "*")]);
+					xml.open("Apply", &[("function", "-")]);
+					xml.empty("FieldRef", &[("field", &value_field)]);
+					xml.text("Constant", &mean.to_string());
+					xml.close("Apply");
+					xml.text("Constant", &scale.to_string());
+					xml.close("Apply");
+					xml.close("Apply");
+				}
+				FeatureGroup::OneHotEncoded { .. } => {
+					xml.open("Apply", &[("function", "if")]);
+					xml.open("Apply", &[("function", "equal")]);
+					xml.empty("FieldRef", &[("field", &value_field)]);
+					xml.text("Constant", &index.to_string());
+					xml.close("Apply");
+					xml.text("Constant", "1");
+					xml.text("Constant", "0");
+					xml.close("Apply");
+				}
+				FeatureGroup::Tokens {
+					lowercase,
+					strategy,
+					tokens,
+					..
+				} => {
+					let local_term_weights = match strategy {
+						TokensStrategy::Present => "binary",
+						TokensStrategy::Count => "termFrequency",
+					};
+					xml.open(
+						"TextIndex",
+						&[
+							("textField", input.name()),
+							("localTermWeights", local_term_weights),
+							("isCaseSensitive", if *lowercase { "false" } else { "true" }),
+							("maxLevenshteinDistance", "0"),
+							("countHits", "allHits"),
+							("wordSeparatorCharacterRE", "[^\\p{L}\\p{N}]+"),
+						],
+					);
+					xml.text("Constant", &tokens[index]);
+					xml.close("TextIndex");
+				}
+				FeatureGroup::SplitDirections { directions, .. } => {
+					let right_indexes = directions[index]
+						.iter()
+						.enumerate()
+						.filter(|(_, direction)| **direction)
+						.map(|(enum_index, _)| enum_index)
+						.collect::<Vec<_>>();
+					xml.open("Apply", &[("function", "if")]);
+					xml.open("Apply", &[("function", "isIn")]);
+					xml.empty("FieldRef", &[("field", &value_field)]);
+					for enum_index in right_indexes {
+						xml.text("Constant", &enum_index.to_string());
+					}
+					xml.close("Apply");
+					xml.text("Constant", "1");
+					xml.text("Constant", "0");
+					xml.close("Apply");
+				}
+			}
+			xml.close("DerivedField");
+			feature_index += 1;
+		}
+	}
+	xml.close("TransformationDictionary");
+}
+
+/// Write the mining schema for a model. Models nested in a segmentation do not include the target field.
+fn write_mining_schema(
+	xml: &mut Xml,
+	model: &LoweredModel,
+	extra_fields: &[String],
+	include_target: bool,
+) {
+	xml.open("MiningSchema", &[]);
+	for input in model.inputs.iter() {
+		match input {
+			// Invalid enum values are treated as missing, which maps them to the index 0.
+			Input::Enum { name, .. } => xml.empty(
+				"MiningField",
+				&[("name", name), ("invalidValueTreatment", "asMissing")],
+			),
+			_ => xml.empty("MiningField", &[("name", input.name())]),
+		}
+	}
+	for field in extra_fields {
+		xml.empty("MiningField", &[("name", field)]);
+	}
+	if include_target {
+		xml.empty(
+			"MiningField",
+			&[("name", &model.target_column_name), ("usageType", "target")],
+		);
+	}
+	xml.close("MiningSchema");
+}
+
+/// This is the name of the output field holding the probability of a class.
+fn probability_field_name(class: &str) -> String {
+	format!("probability({})", class)
+}
+
+fn write_output(xml: &mut Xml, model: &LoweredModel) {
+	xml.open("Output", &[]);
+	match &model.task {
+		Task::Regression => xml.empty(
+			"OutputField",
+			&[
+				("name", "value"),
+				("optype", "continuous"),
+				("dataType", "double"),
+				("feature", "predictedValue"),
+			],
+		),
+		Task::BinaryClassification {
+			negative_class,
+			positive_class,
+			threshold,
+		} => {
+			for class in [positive_class, negative_class] {
+				xml.empty(
+					"OutputField",
+					&[
+						("name", &probability_field_name(class)),
+						("optype", "continuous"),
+						("dataType", "double"),
+						("feature", "probability"),
+						("value", class),
+					],
+				);
+			}
+			xml.open(
+				"OutputField",
+				&[
+					("name", "label"),
+					("optype", "categorical"),
+					("dataType", "string"),
+					("feature", "transformedValue"),
+				],
+			);
+			xml.open("Apply", &[("function", "if")]);
+			xml.open("Apply", &[("function", "greaterOrEqual")]);
+			xml.empty(
+				"FieldRef",
+				&[("field", &probability_field_name(positive_class))],
+			);
+			xml.text("Constant", &threshold.to_string());
+			xml.close("Apply");
+			xml.text("Constant", positive_class);
+			xml.text("Constant", negative_class);
+			xml.close("Apply");
+			xml.close("OutputField");
+		}
+		Task::MulticlassClassification { classes } => {
+			for class in classes {
+				xml.empty(
+					"OutputField",
+					&[
+						("name", &probability_field_name(class)),
+						("optype", "continuous"),
+						("dataType", "double"),
+						("feature", "probability"),
+						("value", class),
+					],
+				);
+			}
+			xml.empty(
+				"OutputField",
+				&[
+					("name", "label"),
+					("optype", "categorical"),
+					("dataType", "string"),
+					("feature", "predictedValue"),
+				],
+			);
+		}
+	}
+	xml.close("Output");
+}
+
+/// Write a regression model whose regression tables are computed by `write_predictors`. For classifiers, the positive class or each class gets its own table. The negative class of a binary classifier gets an empty table, so the logistic function gives the probability of the positive class.
+fn write_regression_model(
+	xml: &mut Xml,
+	model: &LoweredModel,
+	extra_fields: &[String],
+	biases: &[f32],
+	write_predictors: &dyn Fn(&mut Xml, usize),
+) {
+	let (function_name, normalization_method) = match &model.task {
+		Task::Regression => ("regression", "none"),
+		Task::BinaryClassification { .. } => ("classification", "logit"),
+		Task::MulticlassClassification { .. } => ("classification", "softmax"),
+	};
+	xml.open(
+		"RegressionModel",
+		&[
+			("functionName", function_name),
+			("normalizationMethod", normalization_method),
+		],
+	);
+	write_mining_schema(xml, model, extra_fields, true);
+	write_output(xml, model);
+	match &model.task {
+		Task::Regression => {
+			xml.open("RegressionTable", &[("intercept", &biases[0].to_string())]);
+			write_predictors(xml, 0);
+			xml.close("RegressionTable");
+		}
+		Task::BinaryClassification {
+			negative_class,
+			positive_class,
+			..
+		} => {
+			xml.open(
+				"RegressionTable",
+				&[
+					("intercept", &biases[0].to_string()),
+					("targetCategory", positive_class),
+				],
+			);
+			write_predictors(xml, 0);
+			xml.close("RegressionTable");
+			xml.empty(
+				"RegressionTable",
+				&[("intercept", "0"), ("targetCategory", negative_class)],
+			);
+		}
+		Task::MulticlassClassification { classes } => {
+			for (output, class) in classes.iter().enumerate() {
+				xml.open(
+					"RegressionTable",
+					&[
+						("intercept", &biases[output].to_string()),
+						("targetCategory", class),
+					],
+				);
+				write_predictors(xml, output);
+				xml.close("RegressionTable");
+			}
+		}
+	}
+	xml.close("RegressionModel");
+}
+
+fn write_linear_model(xml: &mut Xml, model: &LoweredModel) {
+	let (weights, biases) = match &model.estimator {
+		Estimator::Linear { weights, biases } => (weights, biases),
+		_ => unreachable!(),
+	};
+	write_regression_model(xml, model, &[], biases, &|xml, output| {
+		for (feature_index, weight) in weights.column(output).iter().enumerate() {
+			xml.empty(
+				"NumericPredictor",
+				&[
+					("name", &feature_field_name(feature_index)),
+					("coefficient", &weight.to_string()),
+				],
+			);
+		}
+	});
+}
+
+fn write_tree_model(xml: &mut Xml, model: &LoweredModel) {
+	let (trees, biases) = match &model.estimator {
+		Estimator::Trees { trees, biases } => (trees, biases),
+		_ => unreachable!(),
+	};
+	// For regression, the bias is added to the sum of the trees as a rescale constant.
+	if let Task::Regression = model.task {
+		xml.open("MiningModel", &[("functionName", "regression")]);
+		write_mining_schema(xml, model, &[], true);
+		write_output(xml, model);
+		xml.open("Targets", &[]);
+		xml.empty(
+			"Target",
+			&[
+				("field", &model.target_column_name),
+				("rescaleConstant", &biases[0].to_string()),
+			],
+		);
+		xml.close("Targets");
+		write_tree_segmentation(xml, model, trees.iter().filter(|tree| tree.output == 0));
+		xml.close("MiningModel");
+		return;
+	}
+	// For classification, each output's trees are summed into a logit, which is then passed to a regression model that adds the bias and computes the probabilities.
+	let logit_field_names = (0..biases.len())
+		.map(|output| format!("modelfox_logit({})", output))
+		.collect::<Vec<_>>();
+	xml.open("MiningModel", &[("functionName", "classification")]);
+	write_mining_schema(xml, model, &[], true);
+	xml.open("Segmentation", &[("multipleModelMethod", "modelChain")]);
+	for (output, logit_field_name) in logit_field_names.iter().enumerate() {
+		xml.open("Segment", &[("id", &(output + 1).to_string())]);
+		xml.empty("True", &[]);
+		xml.open("MiningModel", &[("functionName", "regression")]);
+		write_mining_schema(xml, model, &[], false);
+		xml.open("Output", &[]);
+		xml.empty(
+			"OutputField",
+			&[
+				("name", logit_field_name),
+				("optype", "continuous"),
+				("dataType", "double"),
+				("feature", "predictedValue"),
+				("isFinalResult", "false"),
+			],
+		);
+		xml.close("Output");
+		write_tree_segmentation(
+			xml,
+			model,
+			trees.iter().filter(|tree| tree.output == output),
+		);
+		xml.close("MiningModel");
+		xml.close("Segment");
+	}
+	xml.open(
+		"Segment",
+		&[("id", &(logit_field_names.len() + 1).to_string())],
+	);
+	xml.empty("True", &[]);
+	write_regression_model(xml, model, &logit_field_names, biases, &|xml, output| {
+		xml.empty(
+			"NumericPredictor",
+			&[("name", &logit_field_names[output]), ("coefficient", "1")],
+		);
+	});
+	xml.close("Segment");
+	xml.close("Segmentation");
+	xml.close("MiningModel");
+}
+
+fn write_tree_segmentation<'a>(
+	xml: &mut Xml,
+	model: &LoweredModel,
+	trees: impl Iterator<Item = &'a crate::lower::Tree>,
+) {
+	xml.open("Segmentation", &[("multipleModelMethod", "sum")]);
+	for (tree_index, tree) in trees.enumerate() {
+		xml.open("Segment", &[("id", &(tree_index + 1).to_string())]);
+		xml.empty("True", &[]);
+		xml.open(
+			"TreeModel",
+			&[
+				("functionName", "regression"),
+				("missingValueStrategy", "defaultChild"),
+				("splitCharacteristic", "binarySplit"),
+			],
+		);
+		write_mining_schema(xml, model, &[], false);
+		write_tree_node(xml, &tree.nodes, 0, None);
+		xml.close("TreeModel");
+		xml.close("Segment");
+	}
+	xml.close("Segmentation");
+}
+
+/// Write the node at `node_index`. `predicate` is the comparison that sends examples to this node from its parent, or `None` for the root.
+fn write_tree_node(
+	xml: &mut Xml,
+	nodes: &[Node],
+	node_index: usize,
+	predicate: Option<(usize, &str, f32)>,
+) {
+	let id = node_index.to_string();
+	match &nodes[node_index] {
+		Node::Leaf { value } => {
+			xml.open("Node", &[("id", &id), ("score", &value.to_string())]);
+		}
+		Node::Branch {
+			missing_values_left,
+			left_child_index,
+			right_child_index,
+			..
+		} => {
+			let default_child_index = if *missing_values_left {
+				left_child_index
+			} else {
+				right_child_index
+			};
+			xml.open(
+				"Node",
+				&[
+					("id", &id),
+					("defaultChild", &default_child_index.to_string()),
+				],
+			);
+		}
+	}
+	match predicate {
+		None => xml.empty("True", &[]),
+		Some((feature_index, operator, threshold)) => xml.empty(
+			"SimplePredicate",
+			&[
+				("field", &feature_field_name(feature_index)),
+				("operator", operator),
+				("value", &threshold.to_string()),
+			],
+		),
+	}
+	if let Node::Branch {
+		feature_index,
+		threshold,
+		left_child_index,
+		right_child_index,
+		..
+	} = &nodes[node_index]
+	{
+		write_tree_node(
+			xml,
+			nodes,
+			*left_child_index,
+			Some((*feature_index, "lessOrEqual", *threshold)),
+		);
+		write_tree_node(
+			xml,
+			nodes,
+			*right_child_index,
+			Some((*feature_index, "greaterThan", *threshold)),
+		);
+	}
+	xml.close("Node");
+}
+
+/// This is a small helper for writing indented XML.
+struct Xml {
+	string: String,
+	depth: usize,
+}
+
+impl Xml {
+	fn new() -> Xml {
+		Xml {
+			string: String::new(),
+			depth: 0,
+		}
+	}
+
+	fn into_string(self) -> String {
+		self.string
+	}
+
+	fn declaration(&mut self) {
+		self.string
+			.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+	}
+
+	fn open(&mut self, name: &str, attributes: &[(&str, &str)]) {
+		self.start_tag(name, attributes);
+		self.string.push_str(">\n");
+		self.depth += 1;
+	}
+
+	fn empty(&mut self, name: &str, attributes: &[(&str, &str)]) {
+		self.start_tag(name, attributes);
+		self.string.push_str("/>\n");
+	}
+
+	fn text(&mut self, name: &str, text: &str) {
+		self.start_tag(name, &[]);
+		writeln!(self.string, ">{}</{}>", escape(text), name).unwrap();
+	}
+
+	fn close(&mut self, name: &str) {
+		self.depth -= 1;
+		self.indent();
+		writeln!(self.string, "</{}>", name).unwrap();
+	}
+
+	fn start_tag(&mut self, name: &str, attributes: &[(&str, &str)]) {
+		self.indent();
+		write!(self.string, "<{}", name).unwrap();
+		for (key, value) in attributes {
+			write!(self.string, " {}=\"{}\"", key, escape(value)).unwrap();
+		}
+	}
+
+	fn indent(&mut self) {
+		for _ in 0..self.depth {
+			self.string.push('\t');
+		}
+	}
+}
+
+fn escape(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+	for c in text.chars() {
+		match c {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&apos;"),
+			c => escaped.push(c),
+		}
+	}
+	escaped
+}
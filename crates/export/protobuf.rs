@@ -0,0 +1,84 @@
+/*!
+This module contains a minimal protocol buffers encoder, which is just enough to write ONNX models without depending on generated code.
+*/
+
+const WIRE_TYPE_VARINT: u8 = 0;
+const WIRE_TYPE_LENGTH_DELIMITED: u8 = 2;
+
+#[derive(Default)]
+pub struct Writer {
+	bytes: Vec<u8>,
+}
+
+impl Writer {
+	pub fn new() -> Writer {
+		Writer::default()
+	}
+
+	pub fn into_bytes(self) -> Vec<u8> {
+		self.bytes
+	}
+
+	pub fn int(&mut self, field_number: u32, value: i64) {
+		self.write_tag(field_number, WIRE_TYPE_VARINT);
+		self.write_varint(value as u64);
+	}
+
+	pub fn bytes(&mut self, field_number: u32, value: &[u8]) {
+		self.write_tag(field_number, WIRE_TYPE_LENGTH_DELIMITED);
+		self.write_varint(value.len() as u64);
+		self.bytes.extend_from_slice(value);
+	}
+
+	pub fn string(&mut self, field_number: u32, value: &str) {
+		self.bytes(field_number, value.as_bytes());
+	}
+
+	/// Write an embedded message whose fields are written by `f`.
+	pub fn message(&mut self, field_number: u32, f: impl FnOnce(&mut Writer)) {
+		let mut writer = Writer::new();
+		f(&mut writer);
+		self.bytes(field_number, &writer.bytes);
+	}
+
+	pub fn packed_ints(&mut self, field_number: u32, values: &[i64]) {
+		let mut writer = Writer::new();
+		for value in values {
+			writer.write_varint(*value as u64);
+		}
+		self.bytes(field_number, &writer.bytes);
+	}
+
+	pub fn packed_floats(&mut self, field_number: u32, values: &[f32]) {
+		let mut writer = Writer::new();
+		for value in values {
+			writer.bytes.extend_from_slice(&value.to_le_bytes());
+		}
+		self.bytes(field_number, &writer.bytes);
+	}
+
+	fn write_tag(&mut self, field_number: u32, wire_type: u8) {
+		self.write_varint(((field_number as u64) << 3) | wire_type as u64);
+	}
+
+	fn write_varint(&mut self, mut value: u64) {
+		while value >= 0x80 {
+			self.bytes.push((value as u8) | 0x80);
+			value >>= 7;
+		}
+		self.bytes.push(value as u8);
+	}
+}
+
+#[test]
+fn test_varint() {
+	let mut writer = Writer::new();
+	writer.int(1, 300);
+	assert_eq!(writer.into_bytes(), vec![0x08, 0xac, 0x02]);
+	let mut writer = Writer::new();
+	writer.int(1, -1);
+	assert_eq!(
+		writer.into_bytes(),
+		vec![0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]
+	);
+}
@@ -0,0 +1,445 @@
+/*!
+This module generates a standalone Rust source file for a lowered model. The file exposes an `Input` struct with a field for each of the model's columns, an `Output` struct, and a `predict` function.
+*/
+
+use crate::codegen::{identifiers, n_features, write_tree, Writer};
+use crate::lower::{Estimator, FeatureGroup, Input, LoweredModel, Task, TokensStrategy};
+use anyhow::Result;
+
+const KEYWORDS: &[&str] = &[
+	"_", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+	"crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl",
+	"in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+	"return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+	"union", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// These are the names of the `Input` struct's fields, in the order of the model's inputs.
+pub fn field_names(model: &LoweredModel) -> Vec<String> {
+	identifiers(model.inputs.iter().map(|input| input.name()), KEYWORDS)
+}
+
+pub fn float_literal(value: f32) -> String {
+	if value.is_nan() {
+		"f32::NAN".to_owned()
+	} else if value == f32::INFINITY {
+		"f32::INFINITY".to_owned()
+	} else if value == f32::NEG_INFINITY {
+		"f32::NEG_INFINITY".to_owned()
+	} else {
+		format!("{:?}f32", value)
+	}
+}
+
+pub fn string_literal(value: &str) -> String {
+	format!("{:?}", value)
+}
+
+pub fn write(model: &LoweredModel) -> Result<String> {
+	let field_names = field_names(model);
+	let n_features = n_features(model);
+	let n_outputs = model.estimator.n_outputs();
+	let mut w = Writer::default();
+	w.line(format!(
+		"// This file was generated by `modelfox codegen` from the model {}. It predicts the column \"{}\" and has no dependencies.",
+		model.id,
+		model.target_column_name.escape_debug(),
+	));
+	w.line("");
+
+	// Write the input struct.
+	let has_strings = model
+		.inputs
+		.iter()
+		.any(|input| !matches!(input, Input::Number { .. }));
+	w.line("/// The input to the model. Set a field to `None` if its value is missing.");
+	w.line("#[derive(Clone, Debug, Default)]");
+	if has_strings {
+		w.line("pub struct Input<'a> {");
+	} else {
+		w.line("pub struct Input {");
+	}
+	w.indent();
+	for (input, field_name) in model.inputs.iter().zip(field_names.iter()) {
+		let (kind, ty) = match input {
+			Input::Number { .. } => ("number", "Option<f32>"),
+			Input::Enum { .. } => ("enum", "Option<&'a str>"),
+			Input::Text { .. } => ("text", "Option<&'a str>"),
+		};
+		w.line(format!(
+			"/// The {} column \"{}\".",
+			kind,
+			input.name().escape_debug()
+		));
+		w.line(format!("pub {}: {},", field_name, ty));
+	}
+	w.dedent();
+	w.line("}");
+	w.line("");
+
+	// Write the output struct and the classes.
+	match &model.task {
+		Task::Regression => {
+			w.line("#[derive(Clone, Debug)]");
+			w.line("pub struct Output {");
+			w.indent();
+			w.line("pub value: f32,");
+			w.dedent();
+			w.line("}");
+		}
+		Task::BinaryClassification {
+			negative_class,
+			positive_class,
+			threshold,
+		} => {
+			w.line(format!(
+				"pub const NEGATIVE_CLASS: &str = {};",
+				string_literal(negative_class)
+			));
+			w.line(format!(
+				"pub const POSITIVE_CLASS: &str = {};",
+				string_literal(positive_class)
+			));
+			w.line("/// Inputs whose probability of being the positive class is at least this value are predicted to be the positive class.");
+			w.line(format!(
+				"pub const THRESHOLD: f32 = {};",
+				float_literal(*threshold)
+			));
+			w.line("");
+			w.line("#[derive(Clone, Debug)]");
+			w.line("pub struct Output {");
+			w.indent();
+			w.line("pub class_name: &'static str,");
+			w.line("/// The probability of `class_name`.");
+			w.line("pub probability: f32,");
+			w.dedent();
+			w.line("}");
+		}
+		Task::MulticlassClassification { classes } => {
+			w.line(format!(
+				"pub const CLASSES: [&str; {}] = [{}];",
+				classes.len(),
+				classes
+					.iter()
+					.map(|class| string_literal(class))
+					.collect::<Vec<_>>()
+					.join(", ")
+			));
+			w.line("");
+			w.line("#[derive(Clone, Debug)]");
+			w.line("pub struct Output {");
+			w.indent();
+			w.line("pub class_name: &'static str,");
+			w.line("/// The probability of each class, in the order of `CLASSES`.");
+			w.line(format!("pub probabilities: [f32; {}],", classes.len()));
+			w.dedent();
+			w.line("}");
+		}
+	}
+	w.line("");
+
+	// Write the estimator's parameters.
+	w.line(format!("const N_FEATURES: usize = {};", n_features));
+	w.line(format!("const N_OUTPUTS: usize = {};", n_outputs));
+	let biases = match &model.estimator {
+		Estimator::Linear { biases, .. } => biases,
+		Estimator::Trees { biases, .. } => biases,
+	};
+	w.line(format!(
+		"const BIASES: [f32; N_OUTPUTS] = [{}];",
+		biases
+			.iter()
+			.map(|bias| float_literal(*bias))
+			.collect::<Vec<_>>()
+			.join(", ")
+	));
+	if let Estimator::Linear { weights, .. } = &model.estimator {
+		w.line("const WEIGHTS: [[f32; N_OUTPUTS]; N_FEATURES] = [");
+		w.indent();
+		for row in weights.rows() {
+			w.line(format!(
+				"[{}],",
+				row.iter()
+					.map(|weight| float_literal(*weight))
+					.collect::<Vec<_>>()
+					.join(", ")
+			));
+		}
+		w.dedent();
+		w.line("];");
+	}
+	w.line("");
+
+	// Write the predict function.
+	w.line("/// Make a prediction for `input`.");
+	w.line("pub fn predict(input: &Input) -> Output {");
+	w.indent();
+	w.line("let features = features(input);");
+	match &model.estimator {
+		Estimator::Linear { .. } => {
+			w.line("let mut logits = BIASES;");
+			w.line("for (feature, weights) in features.iter().zip(WEIGHTS.iter()) {");
+			w.indent();
+			w.line("for (logit, weight) in logits.iter_mut().zip(weights.iter()) {");
+			w.indent();
+			w.line("*logit += feature * weight;");
+			w.dedent();
+			w.line("}");
+			w.dedent();
+			w.line("}");
+		}
+		Estimator::Trees { trees, .. } if trees.is_empty() => {
+			w.line("let logits = BIASES;");
+		}
+		Estimator::Trees { trees, .. } => {
+			w.line("let mut logits = BIASES;");
+			for (tree_index, tree) in trees.iter().enumerate() {
+				w.line(format!(
+					"logits[{}] += tree_{}(&features);",
+					tree.output, tree_index
+				));
+			}
+		}
+	}
+	match &model.task {
+		Task::Regression => {
+			w.line("Output { value: logits[0] }");
+		}
+		Task::BinaryClassification { .. } => {
+			w.line("let probability = 1.0 / ((-logits[0]).exp() + 1.0);");
+			w.line("if probability >= THRESHOLD {");
+			w.indent();
+			w.line("Output {");
+			w.indent();
+			w.line("class_name: POSITIVE_CLASS,");
+			w.line("probability,");
+			w.dedent();
+			w.line("}");
+			w.dedent();
+			w.line("} else {");
+			w.indent();
+			w.line("Output {");
+			w.indent();
+			w.line("class_name: NEGATIVE_CLASS,");
+			w.line("probability: 1.0 - probability,");
+			w.dedent();
+			w.line("}");
+			w.dedent();
+			w.line("}");
+		}
+		Task::MulticlassClassification { .. } => {
+			w.line("let max = logits.iter().fold(f32::MIN, |a, &b| f32::max(a, b));");
+			w.line("let mut probabilities = [0.0f32; N_OUTPUTS];");
+			w.line("for (probability, logit) in probabilities.iter_mut().zip(logits.iter()) {");
+			w.indent();
+			w.line("*probability = (logit - max).exp();");
+			w.dedent();
+			w.line("}");
+			w.line("let sum: f32 = probabilities.iter().sum();");
+			w.line("for probability in probabilities.iter_mut() {");
+			w.indent();
+			w.line("*probability /= sum;");
+			w.dedent();
+			w.line("}");
+			w.line("let mut class_index = 0;");
+			w.line("for (index, probability) in probabilities.iter().enumerate() {");
+			w.indent();
+			w.line("if *probability >= probabilities[class_index] {");
+			w.indent();
+			w.line("class_index = index;");
+			w.dedent();
+			w.line("}");
+			w.dedent();
+			w.line("}");
+			w.line("Output {");
+			w.indent();
+			w.line("class_name: CLASSES[class_index],");
+			w.line("probabilities,");
+			w.dedent();
+			w.line("}");
+		}
+	}
+	w.dedent();
+	w.line("}");
+	w.line("");
+
+	// Write the function that computes the features.
+	w.line("fn features(input: &Input) -> [f32; N_FEATURES] {");
+	w.indent();
+	w.line("let mut features = [0.0f32; N_FEATURES];");
+	for (input_index, (input, field_name)) in
+		model.inputs.iter().zip(field_names.iter()).enumerate()
+	{
+		match input {
+			Input::Number { .. } => w.line(format!(
+				"let input_{} = input.{}.filter(|value| value.is_finite()).unwrap_or(f32::NAN);",
+				input_index, field_name
+			)),
+			Input::Enum { variants, .. } if variants.is_empty() => {
+				w.line(format!("let input_{}: usize = 0;", input_index))
+			}
+			Input::Enum { variants, .. } => {
+				w.line(format!(
+					"let input_{}: usize = match input.{} {{",
+					input_index, field_name
+				));
+				w.indent();
+				for (variant_index, variant) in variants.iter().enumerate() {
+					w.line(format!(
+						"Some({}) => {},",
+						string_literal(variant),
+						variant_index + 1
+					));
+				}
+				w.line("_ => 0,");
+				w.dedent();
+				w.line("};");
+			}
+			Input::Text { .. } => w.line(format!(
+				"let input_{} = input.{}.unwrap_or(\"\");",
+				input_index, field_name
+			)),
+		}
+	}
+	let mut offset = 0;
+	for feature_group in model.feature_groups.iter() {
+		let input_index = feature_group.input();
+		let input = &model.inputs[input_index];
+		match feature_group {
+			FeatureGroup::Identity { .. } => {
+				let value = match input {
+					Input::Number { .. } => format!("input_{}", input_index),
+					Input::Enum { .. } => format!("input_{} as f32", input_index),
+					Input::Text { .. } => "f32::NAN".to_owned(),
+				};
+				w.line(format!("features[{}] = {};", offset, value));
+			}
+			FeatureGroup::Normalized { mean, scale, .. } => {
+				let value = match input {
+					Input::Number { .. } => format!(
+						"if input_{0}.is_nan() {{ 0.0 }} else {{ (input_{0} - {1}) * {2} }}",
+						input_index,
+						float_literal(*mean),
+						float_literal(*scale),
+					),
+					Input::Enum { .. } => format!(
+						"(input_{} as f32 - {}) * {}",
+						input_index,
+						float_literal(*mean),
+						float_literal(*scale),
+					),
+					Input::Text { .. } => "0.0".to_owned(),
+				};
+				w.line(format!("features[{}] = {};", offset, value));
+			}
+			FeatureGroup::OneHotEncoded { n_indexes, .. } => {
+				if *n_indexes > 0 {
+					if offset == 0 {
+						w.line(format!("features[input_{}] = 1.0;", input_index));
+					} else {
+						w.line(format!(
+							"features[{} + input_{}] = 1.0;",
+							offset, input_index
+						));
+					}
+				}
+			}
+			FeatureGroup::Tokens {
+				lowercase,
+				strategy,
+				tokens,
+				..
+			} => {
+				if !tokens.is_empty() {
+					let text = if *lowercase {
+						format!("input_{}.to_lowercase()", input_index)
+					} else {
+						format!("input_{}", input_index)
+					};
+					w.line(format!(
+						"for word in {}.split(|c: char| !c.is_alphanumeric()) {{",
+						text
+					));
+					w.indent();
+					w.line("match word {");
+					w.indent();
+					for (token_index, token) in tokens.iter().enumerate() {
+						let statement = match strategy {
+							TokensStrategy::Present => "= 1.0",
+							TokensStrategy::Count => "+= 1.0",
+						};
+						w.line(format!(
+							"{} => features[{}] {},",
+							string_literal(token),
+							offset + token_index,
+							statement
+						));
+					}
+					w.line("_ => {}");
+					w.dedent();
+					w.line("}");
+					w.dedent();
+					w.line("}");
+				}
+			}
+			FeatureGroup::SplitDirections { directions, .. } => {
+				for (split_index, directions) in directions.iter().enumerate() {
+					let right_indexes = directions
+						.iter()
+						.enumerate()
+						.filter(|(_, right)| **right)
+						.map(|(enum_index, _)| enum_index.to_string())
+						.collect::<Vec<_>>();
+					if right_indexes.is_empty() {
+						continue;
+					}
+					w.line(format!(
+						"features[{}] = match input_{} {{ {} => 1.0, _ => 0.0 }};",
+						offset + split_index,
+						input_index,
+						right_indexes.join(" | ")
+					));
+				}
+			}
+		}
+		offset += feature_group.n_features();
+	}
+	w.line("features");
+	w.dedent();
+	w.line("}");
+
+	// Write a function for each tree.
+	if let Estimator::Trees { trees, .. } = &model.estimator {
+		for (tree_index, tree) in trees.iter().enumerate() {
+			w.line("");
+			w.line(format!(
+				"fn tree_{}(features: &[f32; N_FEATURES]) -> f32 {{",
+				tree_index
+			));
+			w.indent();
+			write_tree(
+				&mut w,
+				tree,
+				&|feature_index, threshold, missing_values_left| {
+					if missing_values_left {
+						format!(
+							"features[{0}].is_nan() || features[{0}] <= {1}",
+							feature_index,
+							float_literal(threshold)
+						)
+					} else {
+						format!(
+							"features[{}] <= {}",
+							feature_index,
+							float_literal(threshold)
+						)
+					}
+				},
+				&float_literal,
+			);
+			w.dedent();
+			w.line("}");
+		}
+	}
+	Ok(w.finish())
+}
@@ -1,6 +1,5 @@
 use fnv::{FnvBuildHasher, FnvHashSet};
 use indexmap::IndexMap;
-use itertools::Itertools;
 use modelfox_table::{
 	NumberTableColumn, TableColumn, TableColumnView, TableValue, TextTableColumnView,
 };
@@ -95,28 +94,8 @@ impl BagOfWordsFeatureGroup {
 		let mut feature_columns = vec![vec![0.0; column.len()]; self.ngrams.len()];
 		// Compute the feature values for each example.
 		for (example_index, value) in column.iter().enumerate() {
-			// Set the feature value for each token for this example.
-			let unigram_iter = if self.ngram_types.contains(&NGramType::Unigram) {
-				Some(
-					self.tokenizer
-						.tokenize(value)
-						.map(modelfox_text::NGramRef::Unigram),
-				)
-			} else {
-				None
-			};
-			let bigram_iter =
-				if self.ngram_types.contains(&NGramType::Bigram) {
-					Some(self.tokenizer.tokenize(value).tuple_windows().map(
-						|(token_a, token_b)| modelfox_text::NGramRef::Bigram(token_a, token_b),
-					))
-				} else {
-					None
-				};
-			let ngram_iter = unigram_iter
-				.into_iter()
-				.flatten()
-				.chain(bigram_iter.into_iter().flatten());
+			// Set the feature value for each ngram for this example.
+			let ngram_iter = self.tokenizer.ngrams(value, self.ngram_types.iter());
 			for ngram in ngram_iter {
 				if let Some((ngram_index, _, ngram_entry)) = self.ngrams.get_full(&ngram) {
 					match self.strategy {
@@ -169,28 +148,8 @@ impl BagOfWordsFeatureGroup {
 		features.fill(0.0);
 		// Compute the feature values for each example.
 		for (example_index, value) in column.iter().enumerate() {
-			// Set the feature value for each token for this example.
-			let unigram_iter = if self.ngram_types.contains(&NGramType::Unigram) {
-				Some(
-					self.tokenizer
-						.tokenize(value)
-						.map(modelfox_text::NGramRef::Unigram),
-				)
-			} else {
-				None
-			};
-			let bigram_iter =
-				if self.ngram_types.contains(&NGramType::Bigram) {
-					Some(self.tokenizer.tokenize(value).tuple_windows().map(
-						|(token_a, token_b)| modelfox_text::NGramRef::Bigram(token_a, token_b),
-					))
-				} else {
-					None
-				};
-			let ngram_iter = unigram_iter
-				.into_iter()
-				.flatten()
-				.chain(bigram_iter.into_iter().flatten());
+			// Set the feature value for each ngram for this example.
+			let ngram_iter = self.tokenizer.ngrams(value, self.ngram_types.iter());
 			for ngram in ngram_iter {
 				if let Some((ngram_index, _, ngram_entry)) = self.ngrams.get_full(&ngram) {
 					match self.strategy {
@@ -242,28 +201,8 @@ impl BagOfWordsFeatureGroup {
 		}
 		// Compute the feature values for each example.
 		for (example_index, value) in column.iter().enumerate() {
-			// Set the feature value for each token for this example.
-			let unigram_iter = if self.ngram_types.contains(&NGramType::Unigram) {
-				Some(
-					self.tokenizer
-						.tokenize(value)
-						.map(modelfox_text::NGramRef::Unigram),
-				)
-			} else {
-				None
-			};
-			let bigram_iter =
-				if self.ngram_types.contains(&NGramType::Bigram) {
-					Some(self.tokenizer.tokenize(value).tuple_windows().map(
-						|(token_a, token_b)| modelfox_text::NGramRef::Bigram(token_a, token_b),
-					))
-				} else {
-					None
-				};
-			let ngram_iter = unigram_iter
-				.into_iter()
-				.flatten()
-				.chain(bigram_iter.into_iter().flatten());
+			// Set the feature value for each ngram for this example.
+			let ngram_iter = self.tokenizer.ngrams(value, self.ngram_types.iter());
 			for ngram in ngram_iter {
 				if let Some((ngram_index, _, ngram_entry)) = self.ngrams.get_full(&ngram) {
 					match self.strategy {
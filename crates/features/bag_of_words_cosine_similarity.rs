@@ -1,7 +1,6 @@
 use crate::bag_of_words::{BagOfWordsFeatureGroupNGramEntry, BagOfWordsFeatureGroupStrategy};
 use fnv::{FnvBuildHasher, FnvHashSet};
 use indexmap::IndexMap;
-use itertools::Itertools;
 use modelfox_table::{
 	NumberTableColumn, TableColumn, TableColumnView, TableValue, TextTableColumnView,
 };
@@ -131,29 +130,7 @@ impl BagOfWordsCosineSimilarityFeatureGroup {
 		value: &'a str,
 		bag_of_words_features: &mut [f32],
 	) {
-		let value_unigram_iter = if self.ngram_types.contains(&NGramType::Unigram) {
-			Some(
-				self.tokenizer
-					.tokenize(value)
-					.map(modelfox_text::NGramRef::Unigram),
-			)
-		} else {
-			None
-		};
-		let value_bigram_iter = if self.ngram_types.contains(&NGramType::Bigram) {
-			Some(
-				self.tokenizer
-					.tokenize(value)
-					.tuple_windows()
-					.map(|(token_a, token_b)| modelfox_text::NGramRef::Bigram(token_a, token_b)),
-			)
-		} else {
-			None
-		};
-		let ngram_iter = value_unigram_iter
-			.into_iter()
-			.flatten()
-			.chain(value_bigram_iter.into_iter().flatten());
+		let ngram_iter = self.tokenizer.ngrams(value, self.ngram_types.iter());
 		for ngram in ngram_iter {
 			if let Some((ngram_index, _, ngram_entry)) = self.ngrams.get_full(&ngram) {
 				match self.strategy {
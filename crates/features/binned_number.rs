@@ -0,0 +1,173 @@
+use modelfox_table::{
+	NumberTableColumn, NumberTableColumnView, TableColumn, TableColumnView, TableValue,
+};
+use modelfox_zip::zip;
+use ndarray::prelude::*;
+
+/**
+A `BinnedNumberFeatureGroup` splits a number column into bins and one hot encodes the bin each value falls into, so a linear model can learn a separate weight for each range of the column. The bins are separated by `thresholds`, which are usually quantiles of the column in the train dataset. A value equal to a threshold falls into the bin above it. Invalid values set none of the features.
+
+Thresholds: [1.0, 3.0]
+
+| input value     | feature values |
+|-----------------|----------------|
+| "INVALID!"      | [0, 0, 0]      |
+| 0.5             | [1, 0, 0]      |
+| 1.0             | [0, 1, 0]      |
+| 5.0             | [0, 0, 1]      |
+*/
+#[derive(Clone, Debug)]
+pub struct BinnedNumberFeatureGroup {
+	pub source_column_name: String,
+	/// These are the boundaries between the bins in ascending order. There is one more bin than there are thresholds.
+	pub thresholds: Vec<f32>,
+}
+
+impl BinnedNumberFeatureGroup {
+	pub fn compute_table(
+		&self,
+		column: TableColumnView,
+		progress: &impl Fn(u64),
+	) -> Vec<TableColumn> {
+		match column {
+			TableColumnView::Number(column) => {
+				self.compute_table_for_number_column(column, &|| progress(1))
+			}
+			TableColumnView::Unknown(_) => unimplemented!(),
+			TableColumnView::Enum(_) => unimplemented!(),
+			TableColumnView::Text(_) => unimplemented!(),
+		}
+	}
+
+	pub fn compute_array_f32(
+		&self,
+		mut features: ArrayViewMut2<f32>,
+		column: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		let column = match column {
+			TableColumnView::Number(column) => column,
+			_ => unimplemented!(),
+		};
+		// Fill the features with zeros.
+		features.fill(0.0);
+		for (mut features, value) in zip!(features.axis_iter_mut(Axis(0)), column.iter()) {
+			if let Some(bin) = self.bin(*value) {
+				features[bin] = 1.0;
+			}
+			progress();
+		}
+	}
+
+	pub fn compute_array_value(
+		&self,
+		mut features: ArrayViewMut2<TableValue>,
+		column: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		let column = match column {
+			TableColumnView::Number(column) => column,
+			_ => unimplemented!(),
+		};
+		// Fill the features with zeros.
+		for feature in features.iter_mut() {
+			*feature = TableValue::Number(0.0);
+		}
+		for (mut features, value) in zip!(features.axis_iter_mut(Axis(0)), column.iter()) {
+			if let Some(bin) = self.bin(*value) {
+				features[bin] = TableValue::Number(1.0);
+			}
+			progress();
+		}
+	}
+
+	/// Return the index of the bin that `value` falls into, or `None` if it is invalid.
+	pub fn bin(&self, value: f32) -> Option<usize> {
+		if value.is_nan() {
+			None
+		} else {
+			Some(
+				self.thresholds
+					.partition_point(|threshold| *threshold <= value),
+			)
+		}
+	}
+
+	/// Return the lower and upper bounds of the bin at `bin`. The first bin has no lower bound and the last bin has no upper bound.
+	pub fn bin_range(&self, bin: usize) -> (Option<f32>, Option<f32>) {
+		let start = bin.checked_sub(1).map(|index| self.thresholds[index]);
+		let end = self.thresholds.get(bin).cloned();
+		(start, end)
+	}
+}
+
+impl BinnedNumberFeatureGroup {
+	fn compute_table_for_number_column(
+		&self,
+		column: NumberTableColumnView,
+		progress: &impl Fn(),
+	) -> Vec<TableColumn> {
+		let mut feature_columns = vec![vec![0.0; column.len()]; self.thresholds.len() + 1];
+		for (example_index, value) in column.iter().enumerate() {
+			if let Some(bin) = self.bin(*value) {
+				feature_columns[bin][example_index] = 1.0;
+			}
+			progress();
+		}
+		feature_columns
+			.into_iter()
+			.map(|feature_column| TableColumn::Number(NumberTableColumn::new(None, feature_column)))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::binned_number::*;
+	use crate::test_common::compute_features;
+
+	fn feature_group() -> BinnedNumberFeatureGroup {
+		BinnedNumberFeatureGroup {
+			source_column_name: "values".to_owned(),
+			thresholds: vec![1.0, 3.0],
+		}
+	}
+
+	#[test]
+	fn test_bin() {
+		let feature_group = feature_group();
+		assert_eq!(feature_group.bin(f32::NAN), None);
+		assert_eq!(feature_group.bin(0.5), Some(0));
+		assert_eq!(feature_group.bin(1.0), Some(1));
+		assert_eq!(feature_group.bin(2.9), Some(1));
+		assert_eq!(feature_group.bin(3.0), Some(2));
+		assert_eq!(feature_group.bin(5.0), Some(2));
+		assert_eq!(feature_group.bin_range(0), (None, Some(1.0)));
+		assert_eq!(feature_group.bin_range(1), (Some(1.0), Some(3.0)));
+		assert_eq!(feature_group.bin_range(2), (Some(3.0), None));
+	}
+
+	#[test]
+	fn test_binned_number() {
+		// These are the values in the table in the docs for `BinnedNumberFeatureGroup`.
+		let feature_group = feature_group();
+		let column = NumberTableColumn::new(None, vec![f32::NAN, 0.5, 1.0, 5.0]);
+		let column = TableColumnView::Number(column.view());
+		let features = compute_features(
+			column.len(),
+			3,
+			|| feature_group.compute_table(column.clone(), &|_| {}),
+			|features| feature_group.compute_array_f32(features, column.clone(), &|| {}),
+			|features| feature_group.compute_array_value(features, column.clone(), &|| {}),
+		);
+		assert_eq!(
+			features,
+			arr2(&[
+				[0.0, 0.0, 0.0],
+				[1.0, 0.0, 0.0],
+				[0.0, 1.0, 0.0],
+				[0.0, 0.0, 1.0],
+			])
+		);
+	}
+}
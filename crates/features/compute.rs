@@ -1,9 +1,11 @@
 use crate::{
 	bag_of_words::BagOfWordsFeatureGroup,
 	bag_of_words_cosine_similarity::BagOfWordsCosineSimilarityFeatureGroup,
-	identity::IdentityFeatureGroup, normalized::NormalizedFeatureGroup,
-	one_hot_encoded::OneHotEncodedFeatureGroup, word_embedding::WordEmbeddingFeatureGroup,
-	FeatureGroup,
+	binned_number::BinnedNumberFeatureGroup, hashed_bag_of_words::HashedBagOfWordsFeatureGroup,
+	hashed_enum::HashedEnumFeatureGroup, identity::IdentityFeatureGroup,
+	interaction::InteractionFeatureGroup, normalized::NormalizedFeatureGroup,
+	one_hot_encoded::OneHotEncodedFeatureGroup, polynomial::PolynomialFeatureGroup,
+	word_embedding::WordEmbeddingFeatureGroup, FeatureGroup,
 };
 use modelfox_table::prelude::*;
 use ndarray::prelude::*;
@@ -85,6 +87,46 @@ fn compute_features_array_f32_for_feature_group(
 				progress,
 			)
 		}
+		FeatureGroup::HashedBagOfWords(feature_group) => {
+			compute_features_array_f32_for_hashed_bag_of_words_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::HashedEnum(feature_group) => {
+			compute_features_array_f32_for_hashed_enum_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::Interaction(feature_group) => {
+			compute_features_array_f32_for_interaction_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::Polynomial(feature_group) => {
+			compute_features_array_f32_for_polynomial_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::BinnedNumber(feature_group) => {
+			compute_features_array_f32_for_binned_number_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
 	}
 }
 
@@ -189,6 +231,91 @@ fn compute_features_array_f32_for_word_embedding_feature_group(
 	feature_group.compute_array_f32(features, source_column.view(), progress);
 }
 
+fn compute_features_array_f32_for_hashed_bag_of_words_feature_group(
+	table: &TableView,
+	feature_group: &HashedBagOfWordsFeatureGroup,
+	features: ArrayViewMut2<f32>,
+	progress: &impl Fn(),
+) {
+	// Get the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name() == Some(&feature_group.source_column_name))
+		.unwrap();
+	feature_group.compute_array_f32(features, source_column.view(), progress);
+}
+
+fn compute_features_array_f32_for_hashed_enum_feature_group(
+	table: &TableView,
+	feature_group: &HashedEnumFeatureGroup,
+	features: ArrayViewMut2<f32>,
+	progress: &impl Fn(),
+) {
+	// Get the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name() == Some(&feature_group.source_column_name))
+		.unwrap();
+	feature_group.compute_array_f32(features, source_column.view(), progress);
+}
+
+fn compute_features_array_f32_for_interaction_feature_group(
+	table: &TableView,
+	feature_group: &InteractionFeatureGroup,
+	features: ArrayViewMut2<f32>,
+	progress: &impl Fn(),
+) {
+	// Get the data for the source columns.
+	let source_column_a = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_a.source_column_name())
+		.unwrap();
+	let source_column_b = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_b.source_column_name())
+		.unwrap();
+	feature_group.compute_array_f32(
+		features,
+		source_column_a.view(),
+		source_column_b.view(),
+		progress,
+	);
+}
+
+fn compute_features_array_f32_for_polynomial_feature_group(
+	table: &TableView,
+	feature_group: &PolynomialFeatureGroup,
+	features: ArrayViewMut2<f32>,
+	progress: &impl Fn(),
+) {
+	// Get the data for the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_column_name)
+		.unwrap();
+	feature_group.compute_array_f32(features, source_column.view(), progress);
+}
+
+fn compute_features_array_f32_for_binned_number_feature_group(
+	table: &TableView,
+	feature_group: &BinnedNumberFeatureGroup,
+	features: ArrayViewMut2<f32>,
+	progress: &impl Fn(),
+) {
+	// Get the data for the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_column_name)
+		.unwrap();
+	feature_group.compute_array_f32(features, source_column.view(), progress);
+}
+
 /// Compute features as a `Table`.
 pub fn compute_features_table(
 	table: &TableView,
@@ -248,6 +375,46 @@ fn compute_features_table_for_feature_group(
 				progress,
 			)
 		}
+		FeatureGroup::HashedBagOfWords(feature_group) => {
+			compute_features_table_for_hashed_bag_of_words_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::HashedEnum(feature_group) => {
+			compute_features_table_for_hashed_enum_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::Interaction(feature_group) => {
+			compute_features_table_for_interaction_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::Polynomial(feature_group) => {
+			compute_features_table_for_polynomial_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::BinnedNumber(feature_group) => {
+			compute_features_table_for_binned_number_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
 	};
 }
 
@@ -262,8 +429,10 @@ fn compute_features_table_for_identity_feature_group(
 		.iter()
 		.find(|column| column.name().unwrap() == feature_group.source_column_name)
 		.unwrap();
-	let feature_column = feature_group.compute_table(column.view(), progress);
-	features.columns_mut().push(feature_column);
+	let feature_columns = feature_group.compute_table(column.view(), progress);
+	for feature_column in feature_columns {
+		features.columns_mut().push(feature_column);
+	}
 }
 
 fn compute_features_table_for_normalized_feature_group(
@@ -277,8 +446,10 @@ fn compute_features_table_for_normalized_feature_group(
 		.iter()
 		.find(|column| column.name().unwrap() == feature_group.source_column_name)
 		.unwrap();
-	let feature_column = feature_group.compute_table(column.view(), progress);
-	features.columns_mut().push(feature_column);
+	let feature_columns = feature_group.compute_table(column.view(), progress);
+	for feature_column in feature_columns {
+		features.columns_mut().push(feature_column);
+	}
 }
 
 fn compute_features_table_for_bag_of_words_feature_group(
@@ -339,6 +510,102 @@ fn compute_features_table_for_word_embedding_feature_group(
 	}
 }
 
+fn compute_features_table_for_hashed_bag_of_words_feature_group(
+	table: &TableView,
+	feature_group: &HashedBagOfWordsFeatureGroup,
+	features: &mut Table,
+	progress: &impl Fn(u64),
+) {
+	// Get the data for the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_column_name)
+		.unwrap();
+	let columns = feature_group.compute_table(source_column.view(), progress);
+	for column in columns {
+		features.columns_mut().push(column);
+	}
+}
+
+fn compute_features_table_for_hashed_enum_feature_group(
+	table: &TableView,
+	feature_group: &HashedEnumFeatureGroup,
+	features: &mut Table,
+	progress: &impl Fn(u64),
+) {
+	// Get the data for the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_column_name)
+		.unwrap();
+	let columns = feature_group.compute_table(source_column.view(), progress);
+	for column in columns {
+		features.columns_mut().push(column);
+	}
+}
+
+fn compute_features_table_for_interaction_feature_group(
+	table: &TableView,
+	feature_group: &InteractionFeatureGroup,
+	features: &mut Table,
+	progress: &impl Fn(u64),
+) {
+	// Get the data for the source columns.
+	let source_column_a = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_a.source_column_name())
+		.unwrap();
+	let source_column_b = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_b.source_column_name())
+		.unwrap();
+	let columns =
+		feature_group.compute_table(source_column_a.view(), source_column_b.view(), progress);
+	for column in columns {
+		features.columns_mut().push(column);
+	}
+}
+
+fn compute_features_table_for_polynomial_feature_group(
+	table: &TableView,
+	feature_group: &PolynomialFeatureGroup,
+	features: &mut Table,
+	progress: &impl Fn(u64),
+) {
+	// Get the data for the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_column_name)
+		.unwrap();
+	let columns = feature_group.compute_table(source_column.view(), progress);
+	for column in columns {
+		features.columns_mut().push(column);
+	}
+}
+
+fn compute_features_table_for_binned_number_feature_group(
+	table: &TableView,
+	feature_group: &BinnedNumberFeatureGroup,
+	features: &mut Table,
+	progress: &impl Fn(u64),
+) {
+	// Get the data for the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_column_name)
+		.unwrap();
+	let columns = feature_group.compute_table(source_column.view(), progress);
+	for column in columns {
+		features.columns_mut().push(column);
+	}
+}
+
 pub fn compute_features_array_value<'a>(
 	table: &TableView<'a>,
 	feature_groups: &[FeatureGroup],
@@ -405,6 +672,46 @@ fn compute_features_array_value_for_feature_group(
 				progress,
 			)
 		}
+		FeatureGroup::HashedBagOfWords(feature_group) => {
+			compute_features_array_value_for_hashed_bag_of_words_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::HashedEnum(feature_group) => {
+			compute_features_array_value_for_hashed_enum_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::Interaction(feature_group) => {
+			compute_features_array_value_for_interaction_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::Polynomial(feature_group) => {
+			compute_features_array_value_for_polynomial_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
+		FeatureGroup::BinnedNumber(feature_group) => {
+			compute_features_array_value_for_binned_number_feature_group(
+				table,
+				feature_group,
+				features,
+				progress,
+			)
+		}
 	}
 }
 
@@ -490,3 +797,88 @@ fn compute_features_array_value_for_word_embedding_feature_group(
 		.unwrap();
 	feature_group.compute_array_value(features, source_column.view(), progress);
 }
+
+fn compute_features_array_value_for_hashed_bag_of_words_feature_group(
+	table: &TableView,
+	feature_group: &HashedBagOfWordsFeatureGroup,
+	features: ArrayViewMut2<modelfox_table::TableValue>,
+	progress: &impl Fn(),
+) {
+	// Get the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name() == Some(&feature_group.source_column_name))
+		.unwrap();
+	feature_group.compute_array_value(features, source_column.view(), progress);
+}
+
+fn compute_features_array_value_for_hashed_enum_feature_group(
+	table: &TableView,
+	feature_group: &HashedEnumFeatureGroup,
+	features: ArrayViewMut2<modelfox_table::TableValue>,
+	progress: &impl Fn(),
+) {
+	// Get the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name() == Some(&feature_group.source_column_name))
+		.unwrap();
+	feature_group.compute_array_value(features, source_column.view(), progress);
+}
+
+fn compute_features_array_value_for_interaction_feature_group(
+	table: &TableView,
+	feature_group: &InteractionFeatureGroup,
+	features: ArrayViewMut2<modelfox_table::TableValue>,
+	progress: &impl Fn(),
+) {
+	// Get the data for the source columns.
+	let source_column_a = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_a.source_column_name())
+		.unwrap();
+	let source_column_b = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_b.source_column_name())
+		.unwrap();
+	feature_group.compute_array_value(
+		features,
+		source_column_a.view(),
+		source_column_b.view(),
+		progress,
+	);
+}
+
+fn compute_features_array_value_for_polynomial_feature_group(
+	table: &TableView,
+	feature_group: &PolynomialFeatureGroup,
+	features: ArrayViewMut2<modelfox_table::TableValue>,
+	progress: &impl Fn(),
+) {
+	// Get the data for the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_column_name)
+		.unwrap();
+	feature_group.compute_array_value(features, source_column.view(), progress);
+}
+
+fn compute_features_array_value_for_binned_number_feature_group(
+	table: &TableView,
+	feature_group: &BinnedNumberFeatureGroup,
+	features: ArrayViewMut2<modelfox_table::TableValue>,
+	progress: &impl Fn(),
+) {
+	// Get the data for the source column.
+	let source_column = table
+		.columns()
+		.iter()
+		.find(|column| column.name().unwrap() == feature_group.source_column_name)
+		.unwrap();
+	feature_group.compute_array_value(features, source_column.view(), progress);
+}
@@ -0,0 +1,253 @@
+use crate::hash_to_bucket;
+use fnv::{FnvHashSet, FnvHasher};
+use modelfox_table::{
+	NumberTableColumn, TableColumn, TableColumnView, TableValue, TextTableColumnView,
+};
+use modelfox_text::{NGram, NGramRef, NGramType, Tokenizer};
+use ndarray::prelude::*;
+use std::hash::Hasher;
+
+/**
+A `HashedBagOfWordsFeatureGroup` creates features for a text column using the [hashing trick](https://en.wikipedia.org/wiki/Feature_hashing). Instead of keeping a vocabulary with one feature per ngram, each ngram is hashed to one of `n_buckets` features, so ngrams that were rare or unseen in training still contribute and the size of the model does not grow with the vocabulary.
+
+If `signed` is true, each ngram adds either 1 or -1 to its bucket depending on another bit of its hash, so ngrams that collide in a bucket tend to cancel out instead of accumulating.
+*/
+#[derive(Clone, Debug)]
+pub struct HashedBagOfWordsFeatureGroup {
+	/// This is the name of the text column used to compute features with this feature group.
+	pub source_column_name: String,
+	/// The strategy specifies how to compute feature values given the ngrams in the source column.
+	pub strategy: HashedBagOfWordsFeatureGroupStrategy,
+	/// This is the tokenizer used to split the text into tokens.
+	pub tokenizer: Tokenizer,
+	/// These are the ngram types used to create features.
+	pub ngram_types: FnvHashSet<NGramType>,
+	/// This is the number of buckets, one for each feature in this feature group.
+	pub n_buckets: usize,
+	/// If this is true, each ngram's contribution to its bucket is multiplied by a sign chosen by its hash.
+	pub signed: bool,
+	/// These are the most frequent ngrams in the training data that hash to each bucket. They are only used to describe the features.
+	pub bucket_ngrams: Vec<Vec<NGram>>,
+}
+
+#[derive(Clone, Debug)]
+pub enum HashedBagOfWordsFeatureGroupStrategy {
+	/// Each distinct ngram in the source column value is counted once.
+	Present,
+	/// Each ngram is counted once for each of its occurrences in the source column value.
+	Count,
+}
+
+impl HashedBagOfWordsFeatureGroup {
+	pub fn compute_table(
+		&self,
+		column: TableColumnView,
+		progress: &impl Fn(u64),
+	) -> Vec<TableColumn> {
+		match column {
+			TableColumnView::Unknown(_) => unimplemented!(),
+			TableColumnView::Number(_) => unimplemented!(),
+			TableColumnView::Enum(_) => unimplemented!(),
+			TableColumnView::Text(column) => {
+				self.compute_table_for_text_column(column, &|| progress(1))
+			}
+		}
+	}
+
+	pub fn compute_array_f32(
+		&self,
+		features: ArrayViewMut2<f32>,
+		column: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		match column {
+			TableColumnView::Unknown(_) => unimplemented!(),
+			TableColumnView::Number(_) => unimplemented!(),
+			TableColumnView::Enum(_) => unimplemented!(),
+			TableColumnView::Text(column) => {
+				self.compute_array_f32_for_text_column(features, column, progress)
+			}
+		}
+	}
+
+	pub fn compute_array_value(
+		&self,
+		features: ArrayViewMut2<TableValue>,
+		column: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		match column {
+			TableColumnView::Unknown(_) => unimplemented!(),
+			TableColumnView::Number(_) => unimplemented!(),
+			TableColumnView::Enum(_) => unimplemented!(),
+			TableColumnView::Text(column) => {
+				self.compute_array_value_for_text_column(features, column, progress)
+			}
+		}
+	}
+
+	/// Return the bucket and sign for `ngram`.
+	pub fn bucket(&self, ngram: &NGramRef) -> (usize, f32) {
+		hash_to_bucket(hash_ngram(ngram), self.n_buckets, self.signed)
+	}
+}
+
+impl HashedBagOfWordsFeatureGroup {
+	/// Call `f` with the bucket and signed amount to add to it for each ngram in `value` that the strategy counts.
+	fn for_each_bucket(&self, value: &str, mut f: impl FnMut(usize, f32)) {
+		let mut seen = FnvHashSet::default();
+		for ngram in self.tokenizer.ngrams(value, self.ngram_types.iter()) {
+			let hash = hash_ngram(&ngram);
+			if matches!(self.strategy, HashedBagOfWordsFeatureGroupStrategy::Present)
+				&& !seen.insert(hash)
+			{
+				continue;
+			}
+			let (bucket, sign) = hash_to_bucket(hash, self.n_buckets, self.signed);
+			f(bucket, sign);
+		}
+	}
+
+	fn compute_table_for_text_column(
+		&self,
+		column: TextTableColumnView,
+		progress: &impl Fn(),
+	) -> Vec<TableColumn> {
+		let mut feature_columns = vec![vec![0.0; column.len()]; self.n_buckets];
+		for (example_index, value) in column.iter().enumerate() {
+			self.for_each_bucket(value, |bucket, value| {
+				feature_columns[bucket][example_index] += value;
+			});
+			progress();
+		}
+		feature_columns
+			.into_iter()
+			.map(|feature_column| TableColumn::Number(NumberTableColumn::new(None, feature_column)))
+			.collect()
+	}
+
+	fn compute_array_f32_for_text_column(
+		&self,
+		mut features: ArrayViewMut2<f32>,
+		column: TextTableColumnView,
+		progress: &impl Fn(),
+	) {
+		// Fill the features with zeros.
+		features.fill(0.0);
+		for (mut features, value) in features.axis_iter_mut(Axis(0)).zip(column.iter()) {
+			self.for_each_bucket(value, |bucket, value| {
+				features[bucket] += value;
+			});
+			progress();
+		}
+	}
+
+	fn compute_array_value_for_text_column(
+		&self,
+		mut features: ArrayViewMut2<TableValue>,
+		column: TextTableColumnView,
+		progress: &impl Fn(),
+	) {
+		// Fill the features with zeros.
+		for feature in features.iter_mut() {
+			*feature = TableValue::Number(0.0);
+		}
+		for (mut features, value) in features.axis_iter_mut(Axis(0)).zip(column.iter()) {
+			self.for_each_bucket(value, |bucket, value| {
+				*features[bucket].as_number_mut().unwrap() += value;
+			});
+			progress();
+		}
+	}
+}
+
+/// Hash an ngram. The hash must not change between versions, because it determines which feature each ngram is assigned to in a trained model.
+fn hash_ngram(ngram: &NGramRef) -> u64 {
+	let mut hasher = FnvHasher::default();
+	match ngram {
+		NGramRef::Unigram(token) => {
+			hasher.write_u8(0);
+			hasher.write(token.as_bytes());
+		}
+		NGramRef::Bigram(token_a, token_b) => {
+			hasher.write_u8(1);
+			hasher.write(token_a.as_bytes());
+			hasher.write_u8(0xff);
+			hasher.write(token_b.as_bytes());
+		}
+		NGramRef::Character(ngram) => {
+			hasher.write_u8(2);
+			hasher.write(ngram.as_bytes());
+		}
+	}
+	hasher.finish()
+}
+
+#[cfg(test)]
+mod test {
+	use crate::hashed_bag_of_words::*;
+	use crate::test_common::compute_features;
+	use modelfox_table::TextTableColumn;
+
+	fn feature_group(
+		strategy: HashedBagOfWordsFeatureGroupStrategy,
+	) -> HashedBagOfWordsFeatureGroup {
+		HashedBagOfWordsFeatureGroup {
+			source_column_name: "text".to_owned(),
+			strategy,
+			tokenizer: Tokenizer::default(),
+			ngram_types: vec![NGramType::Unigram].into_iter().collect(),
+			n_buckets: 4,
+			signed: true,
+			bucket_ngrams: Vec::new(),
+		}
+	}
+
+	fn compute(
+		feature_group: &HashedBagOfWordsFeatureGroup,
+		column: TableColumnView,
+	) -> Array2<f32> {
+		compute_features(
+			column.len(),
+			feature_group.n_buckets,
+			|| feature_group.compute_table(column.clone(), &|_| {}),
+			|features| feature_group.compute_array_f32(features, column.clone(), &|| {}),
+			|features| feature_group.compute_array_value(features, column.clone(), &|| {}),
+		)
+	}
+
+	#[test]
+	fn test_bucket() {
+		// The buckets must not change between versions, because trained models depend on them.
+		let feature_group = feature_group(HashedBagOfWordsFeatureGroupStrategy::Present);
+		let bucket = |ngram| feature_group.bucket(&ngram);
+		assert_eq!(bucket(NGramRef::Unigram("hello".into())), (1, 1.0));
+		assert_eq!(bucket(NGramRef::Unigram("world".into())), (1, 1.0));
+		assert_eq!(bucket(NGramRef::Unigram("the".into())), (2, -1.0));
+		assert_eq!(bucket(NGramRef::Unigram("fox".into())), (0, 1.0));
+		assert_eq!(
+			bucket(NGramRef::Bigram("hello".into(), "world".into())),
+			(3, 1.0)
+		);
+	}
+
+	#[test]
+	fn test_hashed_bag_of_words() {
+		let column = TextTableColumn::new(
+			None,
+			vec!["hello world hello the fox".to_owned(), String::new()],
+		);
+		let column = TableColumnView::Text(column.view());
+		// "hello" and "world" share bucket 1, "the" is in bucket 2 with a negative sign, and "fox" is in bucket 0.
+		let present = feature_group(HashedBagOfWordsFeatureGroupStrategy::Present);
+		assert_eq!(
+			compute(&present, column.clone()),
+			arr2(&[[1.0, 2.0, -1.0, 0.0], [0.0; 4]])
+		);
+		let count = feature_group(HashedBagOfWordsFeatureGroupStrategy::Count);
+		assert_eq!(
+			compute(&count, column),
+			arr2(&[[1.0, 3.0, -1.0, 0.0], [0.0; 4]])
+		);
+	}
+}
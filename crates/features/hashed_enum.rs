@@ -0,0 +1,183 @@
+use crate::hash_to_bucket;
+use fnv::FnvHasher;
+use modelfox_table::prelude::*;
+use ndarray::prelude::*;
+use std::hash::Hasher;
+
+/**
+A `HashedEnumFeatureGroup` creates features for a column with many distinct values by hashing each value to one of `n_buckets` features, instead of creating one feature for each value like a [`OneHotEncodedFeatureGroup`](crate::OneHotEncodedFeatureGroup). The source column can be an enum column, or a text column whose values are treated as categories. Invalid enum values and empty text values set none of the features.
+
+If `signed` is true, the feature for a value's bucket is set to either 1 or -1 depending on another bit of its hash. In the example below, "red" and "green" hash to the same bucket with opposite signs.
+
+| input value     | feature values (4 buckets) |
+|-----------------|----------------------------|
+| "INVALID!"      | [0, 0, 0, 0]               |
+| "red"           | [-1, 0, 0, 0]              |
+| "green"         | [1, 0, 0, 0]               |
+| "blue"          | [0, -1, 0, 0]              |
+*/
+#[derive(Clone, Debug)]
+pub struct HashedEnumFeatureGroup {
+	pub source_column_name: String,
+	/// This is the number of buckets, one for each feature in this feature group.
+	pub n_buckets: usize,
+	/// If this is true, the feature value for each value's bucket is a sign chosen by its hash.
+	pub signed: bool,
+	/// These are the most frequent values in the training data that hash to each bucket. They are only used to describe the features.
+	pub bucket_values: Vec<Vec<String>>,
+}
+
+impl HashedEnumFeatureGroup {
+	pub fn compute_table(
+		&self,
+		column: TableColumnView,
+		progress: &impl Fn(u64),
+	) -> Vec<TableColumn> {
+		let mut feature_columns = vec![vec![0.0; column.len()]; self.n_buckets];
+		self.for_each_bucket(column, &|| progress(1), |example_index, bucket, value| {
+			feature_columns[bucket][example_index] = value;
+		});
+		feature_columns
+			.into_iter()
+			.map(|feature_column| TableColumn::Number(NumberTableColumn::new(None, feature_column)))
+			.collect()
+	}
+
+	pub fn compute_array_f32(
+		&self,
+		mut features: ArrayViewMut2<f32>,
+		column: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		// Fill the features with zeros.
+		features.fill(0.0);
+		self.for_each_bucket(column, progress, |example_index, bucket, value| {
+			features[[example_index, bucket]] = value;
+		});
+	}
+
+	pub fn compute_array_value(
+		&self,
+		mut features: ArrayViewMut2<TableValue>,
+		column: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		// Fill the features with zeros.
+		for feature in features.iter_mut() {
+			*feature = TableValue::Number(0.0);
+		}
+		self.for_each_bucket(column, progress, |example_index, bucket, value| {
+			features[[example_index, bucket]] = TableValue::Number(value);
+		});
+	}
+
+	/// Return the bucket and sign for `value`.
+	pub fn bucket(&self, value: &str) -> (usize, f32) {
+		hash_to_bucket(hash_value(value), self.n_buckets, self.signed)
+	}
+}
+
+impl HashedEnumFeatureGroup {
+	/// Call `f` with the example index, bucket, and feature value for each example with a valid value.
+	fn for_each_bucket(
+		&self,
+		column: TableColumnView,
+		progress: &impl Fn(),
+		mut f: impl FnMut(usize, usize, f32),
+	) {
+		match column {
+			TableColumnView::Unknown(_) => unimplemented!(),
+			TableColumnView::Number(_) => unimplemented!(),
+			TableColumnView::Enum(column) => {
+				// Hash each variant once rather than once for each example.
+				let variant_buckets = column
+					.variants()
+					.iter()
+					.map(|variant| self.bucket(variant))
+					.collect::<Vec<_>>();
+				for (example_index, value) in column.iter().enumerate() {
+					if let Some(value) = value {
+						let (bucket, sign) = variant_buckets[value.get() - 1];
+						f(example_index, bucket, sign);
+					}
+					progress();
+				}
+			}
+			TableColumnView::Text(column) => {
+				for (example_index, value) in column.iter().enumerate() {
+					if !value.is_empty() {
+						let (bucket, sign) = self.bucket(value);
+						f(example_index, bucket, sign);
+					}
+					progress();
+				}
+			}
+		}
+	}
+}
+
+/// Hash a value. The hash must not change between versions, because it determines which feature each value is assigned to in a trained model.
+fn hash_value(value: &str) -> u64 {
+	let mut hasher = FnvHasher::default();
+	hasher.write(value.as_bytes());
+	hasher.finish()
+}
+
+#[cfg(test)]
+mod test {
+	use crate::hashed_enum::*;
+	use crate::test_common::{color_column, compute_features};
+
+	fn feature_group(signed: bool) -> HashedEnumFeatureGroup {
+		HashedEnumFeatureGroup {
+			source_column_name: "color".to_owned(),
+			n_buckets: 4,
+			signed,
+			bucket_values: Vec::new(),
+		}
+	}
+
+	fn compute(feature_group: &HashedEnumFeatureGroup, column: TableColumnView) -> Array2<f32> {
+		compute_features(
+			column.len(),
+			feature_group.n_buckets,
+			|| feature_group.compute_table(column.clone(), &|_| {}),
+			|features| feature_group.compute_array_f32(features, column.clone(), &|| {}),
+			|features| feature_group.compute_array_value(features, column.clone(), &|| {}),
+		)
+	}
+
+	#[test]
+	fn test_hashed_enum() {
+		// These are the values in the table in the docs for `HashedEnumFeatureGroup`.
+		let expected = arr2(&[
+			[0.0, 0.0, 0.0, 0.0],
+			[-1.0, 0.0, 0.0, 0.0],
+			[1.0, 0.0, 0.0, 0.0],
+			[0.0, -1.0, 0.0, 0.0],
+		]);
+		let column = color_column(&[None, Some(1), Some(2), Some(3)]);
+		let signed = feature_group(true);
+		assert_eq!(
+			compute(&signed, TableColumnView::Enum(column.view())),
+			expected
+		);
+		// A text column gives the same features as an enum column with the same values.
+		let column = TextTableColumn::new(
+			None,
+			vec!["", "red", "green", "blue"]
+				.into_iter()
+				.map(ToOwned::to_owned)
+				.collect(),
+		);
+		assert_eq!(
+			compute(&signed, TableColumnView::Text(column.view())),
+			expected
+		);
+		// Without signs, every feature value is 0 or 1.
+		assert_eq!(
+			compute(&feature_group(false), TableColumnView::Text(column.view())),
+			expected.mapv(f32::abs)
+		);
+	}
+}
@@ -1,3 +1,7 @@
+use crate::impute::{
+	compute_missing_indicator_array_f32, compute_missing_indicator_array_value,
+	compute_missing_indicator_table, Impute,
+};
 use modelfox_table::{
 	EnumTableColumn, EnumTableColumnView, NumberTableColumn, NumberTableColumnView, TableColumn,
 	TableColumnView, TableValue,
@@ -45,15 +49,28 @@ EnumTableColumn::new(
 | "red"           | Some(1)       |
 | "green"         | Some(2)       |
 | "blue"          | Some(3)       |
+
+If `impute` is `Some`, invalid values are replaced with the imputed value. If `missing_indicator` is true, a second feature is produced whose value is 1 where the source column value is invalid and 0 elsewhere.
 */
 #[derive(Clone, Debug)]
 pub struct IdentityFeatureGroup {
 	pub source_column_name: String,
+	pub impute: Option<Impute>,
+	pub missing_indicator: bool,
 }
 
 impl IdentityFeatureGroup {
-	pub fn compute_table(&self, column: TableColumnView, progress: &impl Fn(u64)) -> TableColumn {
-		let column = match column {
+	pub fn compute_table(
+		&self,
+		column: TableColumnView,
+		progress: &impl Fn(u64),
+	) -> Vec<TableColumn> {
+		let missing_indicator_column = if self.missing_indicator {
+			Some(compute_missing_indicator_table(&column, &|| progress(1)))
+		} else {
+			None
+		};
+		let feature_column = match column {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(column) => {
 				TableColumn::Number(self.compute_table_for_number_column(column))
@@ -63,24 +80,29 @@ impl IdentityFeatureGroup {
 			}
 			TableColumnView::Text(_) => unimplemented!(),
 		};
-		progress(column.len().to_u64().unwrap());
-		column
+		progress(feature_column.len().to_u64().unwrap());
+		std::iter::once(feature_column)
+			.chain(missing_indicator_column.map(TableColumn::Number))
+			.collect()
 	}
 
 	pub fn compute_array_f32(
 		&self,
-		features: ArrayViewMut2<f32>,
+		mut features: ArrayViewMut2<f32>,
 		column: TableColumnView,
 		progress: &impl Fn(),
 	) {
+		if self.missing_indicator {
+			compute_missing_indicator_array_f32(features.column_mut(1), &column, progress);
+		}
 		// Set the feature values to the source column values.
 		match column {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(column) => {
-				self.compute_array_f32_for_number_column(features, column, progress)
+				self.compute_array_f32_for_number_column(features.column_mut(0), column, progress)
 			}
 			TableColumnView::Enum(column) => {
-				self.compute_array_f32_for_enum_column(features, column, progress)
+				self.compute_array_f32_for_enum_column(features.column_mut(0), column, progress)
 			}
 			TableColumnView::Text(_) => unimplemented!(),
 		}
@@ -88,56 +110,77 @@ impl IdentityFeatureGroup {
 
 	pub fn compute_array_value(
 		&self,
-		features: ArrayViewMut2<TableValue>,
+		mut features: ArrayViewMut2<TableValue>,
 		column: TableColumnView,
 		progress: &impl Fn(),
 	) {
+		if self.missing_indicator {
+			compute_missing_indicator_array_value(features.column_mut(1), &column, progress);
+		}
 		match column {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(column) => {
-				self.compute_array_value_for_number_column(features, column, progress)
+				self.compute_array_value_for_number_column(features.column_mut(0), column, progress)
 			}
 			TableColumnView::Enum(column) => {
-				self.compute_array_value_for_enum_column(features, column, progress)
+				self.compute_array_value_for_enum_column(features.column_mut(0), column, progress)
 			}
 			TableColumnView::Text(_) => unimplemented!(),
 		}
 	}
 
 	fn compute_table_for_number_column(&self, column: NumberTableColumnView) -> NumberTableColumn {
-		NumberTableColumn::new(
-			column.name().map(|name| name.to_owned()),
-			column.as_slice().to_owned(),
-		)
+		let values = match &self.impute {
+			Some(impute) => column
+				.iter()
+				.map(|value| impute.impute_number(*value))
+				.collect(),
+			None => column.as_slice().to_owned(),
+		};
+		NumberTableColumn::new(column.name().map(|name| name.to_owned()), values)
 	}
 
 	fn compute_table_for_enum_column(&self, column: EnumTableColumnView) -> EnumTableColumn {
+		let values = match &self.impute {
+			Some(impute) => column
+				.iter()
+				.map(|value| impute.impute_enum(*value))
+				.collect(),
+			None => column.as_slice().to_owned(),
+		};
 		EnumTableColumn::new(
 			column.name().map(|name| name.to_owned()),
 			column.variants().to_owned(),
-			column.as_slice().to_owned(),
+			values,
 		)
 	}
 
 	fn compute_array_f32_for_number_column(
 		&self,
-		mut features: ArrayViewMut2<f32>,
+		mut features: ArrayViewMut1<f32>,
 		column: NumberTableColumnView,
 		progress: &impl Fn(),
 	) {
 		for (feature, value) in zip!(features.iter_mut(), column.view().iter()) {
-			*feature = *value;
+			*feature = match &self.impute {
+				Some(impute) => impute.impute_number(*value),
+				None => *value,
+			};
 			progress()
 		}
 	}
 
 	fn compute_array_f32_for_enum_column(
 		&self,
-		mut features: ArrayViewMut2<f32>,
+		mut features: ArrayViewMut1<f32>,
 		column: EnumTableColumnView,
 		progress: &impl Fn(),
 	) {
 		for (feature, value) in zip!(features.iter_mut(), column.view().iter()) {
+			let value = match &self.impute {
+				Some(impute) => impute.impute_enum(*value),
+				None => *value,
+			};
 			*feature = value.map(|v| v.get().to_f32().unwrap()).unwrap_or(0.0);
 			progress()
 		}
@@ -145,24 +188,32 @@ impl IdentityFeatureGroup {
 
 	fn compute_array_value_for_number_column(
 		&self,
-		mut features: ArrayViewMut2<TableValue>,
+		mut features: ArrayViewMut1<TableValue>,
 		column: NumberTableColumnView,
 		progress: &impl Fn(),
 	) {
-		for (feature_column, column_value) in zip!(features.column_mut(0), column.iter()) {
-			*feature_column = TableValue::Number(*column_value);
+		for (feature_column, column_value) in zip!(features.iter_mut(), column.iter()) {
+			let column_value = match &self.impute {
+				Some(impute) => impute.impute_number(*column_value),
+				None => *column_value,
+			};
+			*feature_column = TableValue::Number(column_value);
 			progress()
 		}
 	}
 
 	fn compute_array_value_for_enum_column(
 		&self,
-		mut features: ArrayViewMut2<TableValue>,
+		mut features: ArrayViewMut1<TableValue>,
 		column: EnumTableColumnView,
 		progress: &impl Fn(),
 	) {
-		for (feature_column, column_value) in zip!(features.column_mut(0), column.iter()) {
-			*feature_column = TableValue::Enum(*column_value);
+		for (feature_column, column_value) in zip!(features.iter_mut(), column.iter()) {
+			let column_value = match &self.impute {
+				Some(impute) => impute.impute_enum(*column_value),
+				None => *column_value,
+			};
+			*feature_column = TableValue::Enum(column_value);
 			progress()
 		}
 	}
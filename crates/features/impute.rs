@@ -0,0 +1,157 @@
+use modelfox_table::{NumberTableColumn, TableColumnView, TableValue};
+use ndarray::prelude::*;
+use num::ToPrimitive;
+use std::num::NonZeroUsize;
+
+/**
+An `Impute` describes how a feature group replaces invalid values in its source column before computing its features. Invalid values are `NaN` in number columns and `None` in enum columns.
+
+# Example
+
+With the median strategy, for a number column whose median in the train dataset is 3.0:
+
+| input value     | imputed value |
+|-----------------|---------------|
+| 0.2             | 0.2           |
+| "INVALID!"      | 3.0           |
+| 2.1             | 2.1           |
+*/
+#[derive(Clone, Debug)]
+pub struct Impute {
+	/// This is the strategy that was used to choose `value`.
+	pub strategy: ImputeStrategy,
+	/// Invalid values are replaced with this value. For enum columns, this is the index of a variant, starting at 1.
+	pub value: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImputeStrategy {
+	/// Impute the mean of the column's values in the train dataset.
+	Mean,
+	/// Impute the median of the column's values in the train dataset.
+	Median,
+	/// Impute the most frequent of the column's values in the train dataset.
+	Mode,
+	/// Impute a constant value provided in the config.
+	Constant,
+}
+
+impl Impute {
+	/// Replace `value` with the imputed value if it is invalid.
+	pub fn impute_number(&self, value: f32) -> f32 {
+		if value.is_nan() {
+			self.value
+		} else {
+			value
+		}
+	}
+
+	/// Replace `value` with the imputed variant if it is invalid.
+	pub fn impute_enum(&self, value: Option<NonZeroUsize>) -> Option<NonZeroUsize> {
+		value.or_else(|| NonZeroUsize::new(self.value.to_usize().unwrap()))
+	}
+}
+
+/// Compute the missing indicator feature for `column` as a table column. Its value is 1 where the source column value is invalid and 0 elsewhere.
+pub fn compute_missing_indicator_table(
+	column: &TableColumnView,
+	progress: &impl Fn(),
+) -> NumberTableColumn {
+	let mut feature_values = Vec::with_capacity(column.len());
+	for index in 0..column.len() {
+		feature_values.push(missing_indicator_value(column, index));
+		progress();
+	}
+	NumberTableColumn::new(None, feature_values)
+}
+
+/// Compute the missing indicator feature for `column` into `features`, which is the column of the feature array for the missing indicator feature.
+pub fn compute_missing_indicator_array_f32(
+	mut features: ArrayViewMut1<f32>,
+	column: &TableColumnView,
+	progress: &impl Fn(),
+) {
+	for (index, feature) in features.iter_mut().enumerate() {
+		*feature = missing_indicator_value(column, index);
+		progress();
+	}
+}
+
+/// Compute the missing indicator feature for `column` into `features`, which is the column of the feature array for the missing indicator feature.
+pub fn compute_missing_indicator_array_value(
+	mut features: ArrayViewMut1<TableValue>,
+	column: &TableColumnView,
+	progress: &impl Fn(),
+) {
+	for (index, feature) in features.iter_mut().enumerate() {
+		*feature = TableValue::Number(missing_indicator_value(column, index));
+		progress();
+	}
+}
+
+fn missing_indicator_value(column: &TableColumnView, index: usize) -> f32 {
+	let is_missing = match column {
+		TableColumnView::Number(column) => column.as_slice()[index].is_nan(),
+		TableColumnView::Enum(column) => column.as_slice()[index].is_none(),
+		_ => unimplemented!(),
+	};
+	if is_missing {
+		1.0
+	} else {
+		0.0
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::impute::*;
+	use crate::test_common::{color_column, compute_features};
+	use modelfox_table::{NumberTableColumn, TableColumn};
+
+	#[test]
+	fn test_impute() {
+		let impute = Impute {
+			strategy: ImputeStrategy::Median,
+			value: 3.0,
+		};
+		assert_eq!(impute.impute_number(f32::NAN), 3.0);
+		assert_eq!(impute.impute_number(0.2), 0.2);
+		assert_eq!(impute.impute_enum(None), NonZeroUsize::new(3));
+		assert_eq!(
+			impute.impute_enum(NonZeroUsize::new(1)),
+			NonZeroUsize::new(1)
+		);
+	}
+
+	#[test]
+	fn test_compute_missing_indicator() {
+		let compute = |column: TableColumnView| {
+			compute_features(
+				column.len(),
+				1,
+				|| {
+					vec![TableColumn::Number(compute_missing_indicator_table(
+						&column,
+						&|| {},
+					))]
+				},
+				|mut features| {
+					compute_missing_indicator_array_f32(features.column_mut(0), &column, &|| {})
+				},
+				|mut features| {
+					compute_missing_indicator_array_value(features.column_mut(0), &column, &|| {})
+				},
+			)
+		};
+		let column = NumberTableColumn::new(None, vec![0.2, f32::NAN, 2.1, f32::NAN]);
+		assert_eq!(
+			compute(TableColumnView::Number(column.view())),
+			arr2(&[[0.0], [1.0], [0.0], [1.0]])
+		);
+		let column = color_column(&[Some(1), None, Some(2)]);
+		assert_eq!(
+			compute(TableColumnView::Enum(column.view())),
+			arr2(&[[0.0], [1.0], [0.0]])
+		);
+	}
+}
@@ -0,0 +1,308 @@
+use crate::{NormalizedFeatureGroup, OneHotEncodedFeatureGroup};
+use modelfox_table::{NumberTableColumn, TableColumn, TableColumnView, TableValue};
+use ndarray::prelude::*;
+
+/**
+An `InteractionFeatureGroup` creates features for the product of two other feature groups, so a linear model can learn how the effect of one column depends on the value of another. There is one feature for each pair of features from `source_a` and `source_b`.
+
+| source_a | source_b | features                                                |
+|----------|----------|---------------------------------------------------------|
+| number   | number   | one feature, the product of the normalized values       |
+| number   | enum     | the normalized number for the enum's value, 0 elsewhere |
+| enum     | enum     | 1 for the pair of enum values, 0 elsewhere              |
+*/
+#[derive(Clone, Debug)]
+pub struct InteractionFeatureGroup {
+	pub source_a: InteractionFeatureGroupSource,
+	pub source_b: InteractionFeatureGroupSource,
+}
+
+/// An `InteractionFeatureGroupSource` is one of the two feature groups whose features are multiplied.
+#[derive(Clone, Debug)]
+pub enum InteractionFeatureGroupSource {
+	Normalized(NormalizedFeatureGroup),
+	OneHotEncoded(OneHotEncodedFeatureGroup),
+}
+
+impl InteractionFeatureGroupSource {
+	pub fn source_column_name(&self) -> &str {
+		match self {
+			InteractionFeatureGroupSource::Normalized(s) => &s.source_column_name,
+			InteractionFeatureGroupSource::OneHotEncoded(s) => &s.source_column_name,
+		}
+	}
+
+	pub fn n_features(&self) -> usize {
+		match self {
+			InteractionFeatureGroupSource::Normalized(_) => 1,
+			InteractionFeatureGroupSource::OneHotEncoded(s) => s.variants.len() + 1,
+		}
+	}
+
+	/// Return a human readable name for each feature of this source.
+	pub fn feature_names(&self) -> Vec<String> {
+		match self {
+			InteractionFeatureGroupSource::Normalized(s) => vec![s.source_column_name.clone()],
+			InteractionFeatureGroupSource::OneHotEncoded(s) => std::iter::once("OOV")
+				.chain(s.variants.iter().map(|variant| variant.as_str()))
+				.map(|variant| format!("{} = {}", s.source_column_name, variant))
+				.collect(),
+		}
+	}
+
+	/// Return the enum variant for the feature at `feature_index`, or `None` if this source is a number column or the feature is for invalid values.
+	pub fn variant(&self, feature_index: usize) -> Option<&str> {
+		match self {
+			InteractionFeatureGroupSource::Normalized(_) => None,
+			InteractionFeatureGroupSource::OneHotEncoded(s) => feature_index
+				.checked_sub(1)
+				.map(|variant_index| s.variants[variant_index].as_str()),
+		}
+	}
+
+	/// Return the index and value of the one feature of this source that can be nonzero for each example in `column`.
+	fn features(&self, column: TableColumnView) -> Vec<(usize, f32)> {
+		match (self, column) {
+			(InteractionFeatureGroupSource::Normalized(s), TableColumnView::Number(column)) => {
+				column
+					.iter()
+					.map(|value| {
+						let value = if value.is_nan() || s.variance == 0.0 {
+							0.0
+						} else {
+							(*value - s.mean) / f32::sqrt(s.variance)
+						};
+						(0, value)
+					})
+					.collect()
+			}
+			(InteractionFeatureGroupSource::OneHotEncoded(_), TableColumnView::Enum(column)) => {
+				column
+					.iter()
+					.map(|value| (value.map(|value| value.get()).unwrap_or(0), 1.0))
+					.collect()
+			}
+			_ => unimplemented!(),
+		}
+	}
+}
+
+impl InteractionFeatureGroup {
+	pub fn n_features(&self) -> usize {
+		self.source_a.n_features() * self.source_b.n_features()
+	}
+
+	/// Return the indexes of the features from `source_a` and `source_b` that are multiplied to produce the feature at `feature_index`.
+	pub fn source_feature_indexes(&self, feature_index: usize) -> (usize, usize) {
+		let n_features_b = self.source_b.n_features();
+		(feature_index / n_features_b, feature_index % n_features_b)
+	}
+
+	pub fn compute_table(
+		&self,
+		column_a: TableColumnView,
+		column_b: TableColumnView,
+		progress: &impl Fn(u64),
+	) -> Vec<TableColumn> {
+		let mut feature_columns = vec![vec![0.0; column_a.len()]; self.n_features()];
+		self.for_each_feature(
+			column_a,
+			column_b,
+			&|| progress(1),
+			|example_index, feature_index, value| {
+				feature_columns[feature_index][example_index] = value;
+			},
+		);
+		feature_columns
+			.into_iter()
+			.map(|feature_column| TableColumn::Number(NumberTableColumn::new(None, feature_column)))
+			.collect()
+	}
+
+	pub fn compute_array_f32(
+		&self,
+		mut features: ArrayViewMut2<f32>,
+		column_a: TableColumnView,
+		column_b: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		// Fill the features with zeros.
+		features.fill(0.0);
+		self.for_each_feature(
+			column_a,
+			column_b,
+			progress,
+			|example_index, feature_index, value| {
+				features[[example_index, feature_index]] = value;
+			},
+		);
+	}
+
+	pub fn compute_array_value(
+		&self,
+		mut features: ArrayViewMut2<TableValue>,
+		column_a: TableColumnView,
+		column_b: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		// Fill the features with zeros.
+		for feature in features.iter_mut() {
+			*feature = TableValue::Number(0.0);
+		}
+		self.for_each_feature(
+			column_a,
+			column_b,
+			progress,
+			|example_index, feature_index, value| {
+				features[[example_index, feature_index]] = TableValue::Number(value);
+			},
+		);
+	}
+}
+
+impl InteractionFeatureGroup {
+	/// Call `f` with the example index, feature index, and feature value of the one feature that can be nonzero for each example.
+	fn for_each_feature(
+		&self,
+		column_a: TableColumnView,
+		column_b: TableColumnView,
+		progress: &impl Fn(),
+		mut f: impl FnMut(usize, usize, f32),
+	) {
+		let features_a = self.source_a.features(column_a);
+		let features_b = self.source_b.features(column_b);
+		let n_features_b = self.source_b.n_features();
+		for (example_index, ((index_a, value_a), (index_b, value_b))) in
+			features_a.into_iter().zip(features_b).enumerate()
+		{
+			f(
+				example_index,
+				index_a * n_features_b + index_b,
+				value_a * value_b,
+			);
+			progress();
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::interaction::*;
+	use crate::test_common::{color_column, compute_features};
+
+	fn normalized(source_column_name: &str) -> InteractionFeatureGroupSource {
+		InteractionFeatureGroupSource::Normalized(NormalizedFeatureGroup {
+			source_column_name: source_column_name.to_owned(),
+			mean: 1.0,
+			variance: 4.0,
+			impute: None,
+			missing_indicator: false,
+		})
+	}
+
+	fn one_hot_encoded(source_column_name: &str) -> InteractionFeatureGroupSource {
+		InteractionFeatureGroupSource::OneHotEncoded(OneHotEncodedFeatureGroup {
+			source_column_name: source_column_name.to_owned(),
+			variants: vec!["red".to_owned(), "green".to_owned(), "blue".to_owned()],
+		})
+	}
+
+	fn compute(
+		feature_group: &InteractionFeatureGroup,
+		column_a: TableColumnView,
+		column_b: TableColumnView,
+	) -> Array2<f32> {
+		compute_features(
+			column_a.len(),
+			feature_group.n_features(),
+			|| feature_group.compute_table(column_a.clone(), column_b.clone(), &|_| {}),
+			|features| {
+				feature_group.compute_array_f32(
+					features,
+					column_a.clone(),
+					column_b.clone(),
+					&|| {},
+				)
+			},
+			|features| {
+				feature_group.compute_array_value(
+					features,
+					column_a.clone(),
+					column_b.clone(),
+					&|| {},
+				)
+			},
+		)
+	}
+
+	#[test]
+	fn test_source_feature_indexes() {
+		let feature_group = InteractionFeatureGroup {
+			source_a: normalized("number"),
+			source_b: one_hot_encoded("color"),
+		};
+		assert_eq!(feature_group.n_features(), 4);
+		assert_eq!(feature_group.source_feature_indexes(0), (0, 0));
+		assert_eq!(feature_group.source_feature_indexes(3), (0, 3));
+		let feature_group = InteractionFeatureGroup {
+			source_a: one_hot_encoded("color_a"),
+			source_b: one_hot_encoded("color_b"),
+		};
+		assert_eq!(feature_group.n_features(), 16);
+		assert_eq!(feature_group.source_feature_indexes(6), (1, 2));
+		assert_eq!(feature_group.source_feature_indexes(15), (3, 3));
+		assert_eq!(feature_group.source_b.variant(0), None);
+		assert_eq!(feature_group.source_b.variant(3), Some("blue"));
+	}
+
+	#[test]
+	fn test_interaction() {
+		// These are the three rows of the table in the docs for `InteractionFeatureGroup`. Invalid numbers are 0 and invalid enum values use the first feature.
+		let number_a = NumberTableColumn::new(None, vec![5.0, f32::NAN, 2.0]);
+		let number_b = NumberTableColumn::new(None, vec![3.0, 3.0, 1.0]);
+		let feature_group = InteractionFeatureGroup {
+			source_a: normalized("number_a"),
+			source_b: normalized("number_b"),
+		};
+		assert_eq!(
+			compute(
+				&feature_group,
+				TableColumnView::Number(number_a.view()),
+				TableColumnView::Number(number_b.view()),
+			),
+			arr2(&[[2.0], [0.0], [0.0]])
+		);
+		let color_a = color_column(&[Some(1), Some(2), None]);
+		let feature_group = InteractionFeatureGroup {
+			source_a: normalized("number_a"),
+			source_b: one_hot_encoded("color_a"),
+		};
+		assert_eq!(
+			compute(
+				&feature_group,
+				TableColumnView::Number(number_a.view()),
+				TableColumnView::Enum(color_a.view()),
+			),
+			arr2(&[
+				[0.0, 2.0, 0.0, 0.0],
+				[0.0, 0.0, 0.0, 0.0],
+				[0.5, 0.0, 0.0, 0.0],
+			])
+		);
+		let color_b = color_column(&[Some(2), Some(1), Some(3)]);
+		let feature_group = InteractionFeatureGroup {
+			source_a: one_hot_encoded("color_a"),
+			source_b: one_hot_encoded("color_b"),
+		};
+		let features = compute(
+			&feature_group,
+			TableColumnView::Enum(color_a.view()),
+			TableColumnView::Enum(color_b.view()),
+		);
+		for (example_index, feature_index) in [(0, 6), (1, 9), (2, 3)] {
+			let mut expected = Array1::zeros(16);
+			expected[feature_index] = 1.0;
+			assert_eq!(features.row(example_index), expected);
+		}
+	}
+}
@@ -1,21 +1,36 @@
 pub use self::{
 	bag_of_words::BagOfWordsFeatureGroup,
 	bag_of_words_cosine_similarity::BagOfWordsCosineSimilarityFeatureGroup,
+	binned_number::BinnedNumberFeatureGroup,
 	compute::{compute_features_array_f32, compute_features_array_value, compute_features_table},
+	hashed_bag_of_words::HashedBagOfWordsFeatureGroup,
+	hashed_enum::HashedEnumFeatureGroup,
 	identity::IdentityFeatureGroup,
+	impute::{Impute, ImputeStrategy},
+	interaction::{InteractionFeatureGroup, InteractionFeatureGroupSource},
 	normalized::NormalizedFeatureGroup,
 	one_hot_encoded::OneHotEncodedFeatureGroup,
+	polynomial::PolynomialFeatureGroup,
 	word_embedding::WordEmbeddingFeatureGroup,
 };
 
 pub mod bag_of_words;
 pub mod bag_of_words_cosine_similarity;
+pub mod binned_number;
 pub mod compute;
+pub mod hashed_bag_of_words;
+pub mod hashed_enum;
 pub mod identity;
+pub mod impute;
+pub mod interaction;
 pub mod normalized;
 pub mod one_hot_encoded;
+pub mod polynomial;
 pub mod word_embedding;
 
+#[cfg(test)]
+pub mod test_common;
+
 /// The `FeatureGroup` struct describes how to transform one or more columns from the input table to one or more columns in the output features.
 #[derive(Clone, Debug)]
 pub enum FeatureGroup {
@@ -25,18 +40,139 @@ pub enum FeatureGroup {
 	BagOfWords(BagOfWordsFeatureGroup),
 	WordEmbedding(WordEmbeddingFeatureGroup),
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureGroup),
+	HashedBagOfWords(HashedBagOfWordsFeatureGroup),
+	HashedEnum(HashedEnumFeatureGroup),
+	Interaction(InteractionFeatureGroup),
+	Polynomial(PolynomialFeatureGroup),
+	BinnedNumber(BinnedNumberFeatureGroup),
 }
 
 impl FeatureGroup {
 	/// Return the number of features this feature group will produce.
 	pub fn n_features(&self) -> usize {
 		match self {
-			FeatureGroup::Identity(_) => 1,
-			FeatureGroup::Normalized(_) => 1,
+			FeatureGroup::Identity(s) => 1 + usize::from(s.missing_indicator),
+			FeatureGroup::Normalized(s) => 1 + usize::from(s.missing_indicator),
 			FeatureGroup::OneHotEncoded(s) => s.variants.len() + 1,
 			FeatureGroup::BagOfWords(s) => s.ngrams.len(),
 			FeatureGroup::BagOfWordsCosineSimilarity(_) => 1,
 			FeatureGroup::WordEmbedding(s) => s.model.size,
+			FeatureGroup::HashedBagOfWords(s) => s.n_buckets,
+			FeatureGroup::HashedEnum(s) => s.n_buckets,
+			FeatureGroup::Interaction(s) => s.n_features(),
+			FeatureGroup::Polynomial(s) => s.powers().count(),
+			FeatureGroup::BinnedNumber(s) => s.thresholds.len() + 1,
 		}
 	}
+
+	/// Return a human readable name for each feature this feature group will produce.
+	pub fn feature_names(&self) -> Vec<String> {
+		match self {
+			FeatureGroup::Identity(s) => {
+				missing_indicator_feature_names(&s.source_column_name, s.missing_indicator)
+			}
+			FeatureGroup::Normalized(s) => {
+				missing_indicator_feature_names(&s.source_column_name, s.missing_indicator)
+			}
+			FeatureGroup::OneHotEncoded(s) => std::iter::once("OOV")
+				.chain(s.variants.iter().map(|variant| variant.as_str()))
+				.map(|variant| format!("{} = {}", s.source_column_name, variant))
+				.collect(),
+			FeatureGroup::BagOfWords(s) => s
+				.ngrams
+				.keys()
+				.map(|ngram| format!("{} contains {}", s.source_column_name, ngram))
+				.collect(),
+			FeatureGroup::BagOfWordsCosineSimilarity(s) => vec![format!(
+				"similarity of {} and {}",
+				s.source_column_name_a, s.source_column_name_b
+			)],
+			FeatureGroup::WordEmbedding(s) => (0..s.model.size)
+				.map(|i| format!("{} word embedding value {}", s.source_column_name, i))
+				.collect(),
+			FeatureGroup::HashedBagOfWords(s) => s
+				.bucket_ngrams
+				.iter()
+				.enumerate()
+				.map(|(bucket, ngrams)| {
+					hashed_feature_name(&s.source_column_name, "contains", bucket, ngrams)
+				})
+				.collect(),
+			FeatureGroup::HashedEnum(s) => s
+				.bucket_values
+				.iter()
+				.enumerate()
+				.map(|(bucket, values)| {
+					hashed_feature_name(&s.source_column_name, "=", bucket, values)
+				})
+				.collect(),
+			FeatureGroup::Interaction(s) => {
+				let feature_names_b = s.source_b.feature_names();
+				s.source_a
+					.feature_names()
+					.iter()
+					.flat_map(|feature_name_a| {
+						feature_names_b.iter().map(move |feature_name_b| {
+							format!("{} * {}", feature_name_a, feature_name_b)
+						})
+					})
+					.collect()
+			}
+			FeatureGroup::Polynomial(s) => s
+				.powers()
+				.map(|power| format!("{} ^ {}", s.source_column_name, power))
+				.collect(),
+			FeatureGroup::BinnedNumber(s) => (0..s.thresholds.len() + 1)
+				.map(|bin| match s.bin_range(bin) {
+					(None, None) => s.source_column_name.clone(),
+					(None, Some(end)) => format!("{} < {}", s.source_column_name, end),
+					(Some(start), None) => format!("{} >= {}", s.source_column_name, start),
+					(Some(start), Some(end)) => {
+						format!("{} <= {} < {}", start, s.source_column_name, end)
+					}
+				})
+				.collect(),
+		}
+	}
+}
+
+/// Return the name of the feature for a bucket of a hashed feature group, which lists the most frequent training values in the bucket.
+fn hashed_feature_name(
+	source_column_name: &str,
+	relation: &str,
+	bucket: usize,
+	values: &[impl std::fmt::Display],
+) -> String {
+	let values = values
+		.iter()
+		.map(|value| value.to_string())
+		.collect::<Vec<_>>()
+		.join(" | ");
+	if values.is_empty() {
+		format!("{} bucket {}", source_column_name, bucket)
+	} else {
+		format!(
+			"{} {} {} (bucket {})",
+			source_column_name, relation, values, bucket
+		)
+	}
+}
+
+/// Return the bucket for a hash and the sign of the feature value. The sign uses the highest bit of the hash, which is independent of the bucket for any number of buckets much smaller than 2^63.
+fn hash_to_bucket(hash: u64, n_buckets: usize, signed: bool) -> (usize, f32) {
+	let bucket = (hash % n_buckets as u64) as usize;
+	let sign = if signed && hash >> 63 == 1 { -1.0 } else { 1.0 };
+	(bucket, sign)
+}
+
+/// Return the feature names for a feature group that produces one feature for its source column, followed by a missing indicator feature if `missing_indicator` is true.
+fn missing_indicator_feature_names(
+	source_column_name: &str,
+	missing_indicator: bool,
+) -> Vec<String> {
+	let mut feature_names = vec![source_column_name.to_owned()];
+	if missing_indicator {
+		feature_names.push(format!("{} is missing", source_column_name));
+	}
+	feature_names
 }
@@ -1,3 +1,7 @@
+use crate::impute::{
+	compute_missing_indicator_array_f32, compute_missing_indicator_array_value,
+	compute_missing_indicator_table, Impute,
+};
 use modelfox_table::{
 	EnumTableColumnView, NumberTableColumn, NumberTableColumnView, TableColumn, TableColumnView,
 	TableValue,
@@ -5,6 +9,7 @@ use modelfox_table::{
 use modelfox_zip::zip;
 use ndarray::prelude::*;
 use num::ToPrimitive;
+use std::num::NonZeroUsize;
 
 /**
 A `NormalizedFeatureGroup` transforms a number column to zero mean and unit variance. [Learn more](https://en.wikipedia.org/wiki/Feature_scaling#Standardization_(Z-score_Normalization).
@@ -31,12 +36,16 @@ Standard Deviation: 2.70617
 | 0.0             | (0.0 - 2.16667) / 2.70617  = -0.80064 |
 | 5.2             | (5.2 - 2.16667) / 2.70617  = 1.12089  |
 | 1.3             | (1.3 - 2.16667) / 2.70617  = -0.32026 |
+
+Invalid values have the feature value 0, unless `impute` is `Some`, in which case they are replaced with the imputed value before being normalized. If `missing_indicator` is true, a second feature is produced whose value is 1 where the source column value is invalid and 0 elsewhere.
 */
 #[derive(Clone, Debug)]
 pub struct NormalizedFeatureGroup {
 	pub source_column_name: String,
 	pub mean: f32,
 	pub variance: f32,
+	pub impute: Option<Impute>,
+	pub missing_indicator: bool,
 }
 
 impl NormalizedFeatureGroup {
@@ -55,6 +64,8 @@ impl NormalizedFeatureGroup {
 			source_column_name: column.name().unwrap().to_owned(),
 			mean: mean_variance.mean,
 			variance: mean_variance.variance,
+			impute: None,
+			missing_indicator: false,
 		}
 	}
 
@@ -68,14 +79,25 @@ impl NormalizedFeatureGroup {
 			source_column_name: column.name().unwrap().to_owned(),
 			mean: mean_variance.mean,
 			variance: mean_variance.variance,
+			impute: None,
+			missing_indicator: false,
 		}
 	}
 }
 
 impl NormalizedFeatureGroup {
-	pub fn compute_table(&self, column: TableColumnView, progress: &impl Fn(u64)) -> TableColumn {
+	pub fn compute_table(
+		&self,
+		column: TableColumnView,
+		progress: &impl Fn(u64),
+	) -> Vec<TableColumn> {
+		let missing_indicator_column = if self.missing_indicator {
+			Some(compute_missing_indicator_table(&column, &|| progress(1)))
+		} else {
+			None
+		};
 		// Set the feature values to the normalized source column values.
-		match column {
+		let feature_column = match column {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(column) => {
 				TableColumn::Number(self.compute_table_for_number_column(column, &|| progress(1)))
@@ -84,23 +106,29 @@ impl NormalizedFeatureGroup {
 				TableColumn::Number(self.compute_table_for_enum_column(column, &|| progress(1)))
 			}
 			TableColumnView::Text(_) => unimplemented!(),
-		}
+		};
+		std::iter::once(feature_column)
+			.chain(missing_indicator_column.map(TableColumn::Number))
+			.collect()
 	}
 
 	pub fn compute_array_f32(
 		&self,
-		features: ArrayViewMut2<f32>,
+		mut features: ArrayViewMut2<f32>,
 		column: TableColumnView,
 		progress: &impl Fn(),
 	) {
+		if self.missing_indicator {
+			compute_missing_indicator_array_f32(features.column_mut(1), &column, progress);
+		}
 		// Set the feature values to the normalized source column values.
 		match column {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(column) => {
-				self.compute_array_f32_for_number_column(features, column, progress)
+				self.compute_array_f32_for_number_column(features.column_mut(0), column, progress)
 			}
 			TableColumnView::Enum(column) => {
-				self.compute_array_f32_for_enum_column(features, column, progress)
+				self.compute_array_f32_for_enum_column(features.column_mut(0), column, progress)
 			}
 			TableColumnView::Text(_) => unimplemented!(),
 		}
@@ -108,22 +136,39 @@ impl NormalizedFeatureGroup {
 
 	pub fn compute_array_value(
 		&self,
-		features: ArrayViewMut2<TableValue>,
+		mut features: ArrayViewMut2<TableValue>,
 		column: TableColumnView,
 		progress: &impl Fn(),
 	) {
+		if self.missing_indicator {
+			compute_missing_indicator_array_value(features.column_mut(1), &column, progress);
+		}
 		match column {
 			TableColumnView::Unknown(_) => unimplemented!(),
 			TableColumnView::Number(column) => {
-				self.compute_array_value_for_number_column(features, column, progress)
+				self.compute_array_value_for_number_column(features.column_mut(0), column, progress)
 			}
 			TableColumnView::Enum(column) => {
-				self.compute_array_value_for_enum_column(features, column, progress)
+				self.compute_array_value_for_enum_column(features.column_mut(0), column, progress)
 			}
 			TableColumnView::Text(_) => unimplemented!(),
 		}
 	}
 
+	fn impute_number(&self, value: f32) -> f32 {
+		match &self.impute {
+			Some(impute) => impute.impute_number(value),
+			None => value,
+		}
+	}
+
+	fn impute_enum(&self, value: Option<NonZeroUsize>) -> Option<NonZeroUsize> {
+		match &self.impute {
+			Some(impute) => impute.impute_enum(value),
+			None => value,
+		}
+	}
+
 	fn compute_table_for_number_column(
 		&self,
 		column: NumberTableColumnView,
@@ -131,10 +176,11 @@ impl NormalizedFeatureGroup {
 	) -> NumberTableColumn {
 		let mut feature_values = Vec::with_capacity(column.len());
 		for value in column.iter() {
+			let value = self.impute_number(*value);
 			let feature = if value.is_nan() || self.variance == 0.0 {
 				0.0
 			} else {
-				(*value - self.mean) / f32::sqrt(self.variance)
+				(value - self.mean) / f32::sqrt(self.variance)
 			};
 			feature_values.push(feature);
 			progress()
@@ -149,7 +195,8 @@ impl NormalizedFeatureGroup {
 	) -> NumberTableColumn {
 		let mut feature_values = Vec::with_capacity(column.len());
 		for value in column.iter() {
-			let value = value
+			let value = self
+				.impute_enum(*value)
 				.map(|value| value.get().to_f32().unwrap())
 				.unwrap_or(0.0);
 			let feature = if value.is_nan() || self.variance == 0.0 {
@@ -165,15 +212,16 @@ impl NormalizedFeatureGroup {
 
 	fn compute_array_f32_for_number_column(
 		&self,
-		mut features: ArrayViewMut2<f32>,
+		mut features: ArrayViewMut1<f32>,
 		column: NumberTableColumnView,
 		progress: &impl Fn(),
 	) {
 		for (feature, value) in zip!(features.iter_mut(), column.iter()) {
+			let value = self.impute_number(*value);
 			*feature = if value.is_nan() || self.variance == 0.0 {
 				0.0
 			} else {
-				(*value - self.mean) / f32::sqrt(self.variance)
+				(value - self.mean) / f32::sqrt(self.variance)
 			};
 			progress()
 		}
@@ -181,12 +229,13 @@ impl NormalizedFeatureGroup {
 
 	fn compute_array_f32_for_enum_column(
 		&self,
-		mut features: ArrayViewMut2<f32>,
+		mut features: ArrayViewMut1<f32>,
 		column: EnumTableColumnView,
 		progress: &impl Fn(),
 	) {
 		for (feature, value) in zip!(features.iter_mut(), column.iter()) {
-			let value = value
+			let value = self
+				.impute_enum(*value)
 				.map(|value| value.get().to_f32().unwrap())
 				.unwrap_or(0.0);
 			*feature = if value.is_nan() || self.variance == 0.0 {
@@ -200,11 +249,12 @@ impl NormalizedFeatureGroup {
 
 	fn compute_array_value_for_number_column(
 		&self,
-		mut features: ArrayViewMut2<TableValue>,
+		mut features: ArrayViewMut1<TableValue>,
 		column: NumberTableColumnView,
 		progress: &impl Fn(),
 	) {
-		for (feature, value) in zip!(features.column_mut(0), column.iter()) {
+		for (feature, value) in zip!(features.iter_mut(), column.iter()) {
+			let value = self.impute_number(*value);
 			*feature = if value.is_nan() || self.variance == 0.0 {
 				TableValue::Number(0.0)
 			} else {
@@ -216,19 +266,82 @@ impl NormalizedFeatureGroup {
 
 	fn compute_array_value_for_enum_column(
 		&self,
-		mut features: ArrayViewMut2<TableValue>,
+		mut features: ArrayViewMut1<TableValue>,
 		column: EnumTableColumnView,
 		progress: &impl Fn(),
 	) {
-		for (feature, value) in zip!(features.column_mut(0), column.iter()) {
-			*feature = if value.is_none() || self.variance == 0.0 {
-				TableValue::Number(0.0)
-			} else {
-				TableValue::Number(
-					(value.unwrap().get().to_f32().unwrap() - self.mean) / f32::sqrt(self.variance),
-				)
+		for (feature, value) in zip!(features.iter_mut(), column.iter()) {
+			*feature = match self.impute_enum(*value) {
+				Some(value) if self.variance != 0.0 => TableValue::Number(
+					(value.get().to_f32().unwrap() - self.mean) / f32::sqrt(self.variance),
+				),
+				_ => TableValue::Number(0.0),
 			};
 			progress()
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use crate::impute::{Impute, ImputeStrategy};
+	use crate::normalized::*;
+	use crate::test_common::{color_column, compute_features};
+
+	fn compute(feature_group: &NormalizedFeatureGroup, column: TableColumnView) -> Array2<f32> {
+		compute_features(
+			column.len(),
+			1 + usize::from(feature_group.missing_indicator),
+			|| feature_group.compute_table(column.clone(), &|_| {}),
+			|features| feature_group.compute_array_f32(features, column.clone(), &|| {}),
+			|features| feature_group.compute_array_value(features, column.clone(), &|| {}),
+		)
+	}
+
+	#[test]
+	fn test_normalized_number_column() {
+		let column = NumberTableColumn::new(None, vec![0.0, 4.0, f32::NAN, 1.0]);
+		let column = TableColumnView::Number(column.view());
+		let mut feature_group = NormalizedFeatureGroup {
+			source_column_name: "values".to_owned(),
+			mean: 2.0,
+			variance: 4.0,
+			impute: None,
+			missing_indicator: false,
+		};
+		// Without imputation, the invalid value has the feature value 0.
+		assert_eq!(
+			compute(&feature_group, column.clone()),
+			arr2(&[[-1.0], [1.0], [0.0], [-0.5]])
+		);
+		// With imputation, the invalid value is replaced before it is normalized, and the missing indicator marks it.
+		feature_group.impute = Some(Impute {
+			strategy: ImputeStrategy::Constant,
+			value: 6.0,
+		});
+		feature_group.missing_indicator = true;
+		assert_eq!(
+			compute(&feature_group, column),
+			arr2(&[[-1.0, 0.0], [1.0, 0.0], [2.0, 1.0], [-0.5, 0.0]])
+		);
+	}
+
+	#[test]
+	fn test_normalized_enum_column() {
+		let column = color_column(&[Some(1), None, Some(3), Some(3)]);
+		let feature_group = NormalizedFeatureGroup {
+			source_column_name: "color".to_owned(),
+			mean: 2.0,
+			variance: 1.0,
+			impute: Some(Impute {
+				strategy: ImputeStrategy::Mode,
+				value: 3.0,
+			}),
+			missing_indicator: true,
+		};
+		assert_eq!(
+			compute(&feature_group, TableColumnView::Enum(column.view())),
+			arr2(&[[-1.0, 0.0], [1.0, 1.0], [1.0, 0.0], [1.0, 0.0]])
+		);
+	}
+}
@@ -0,0 +1,160 @@
+use modelfox_table::{
+	NumberTableColumn, NumberTableColumnView, TableColumn, TableColumnView, TableValue,
+};
+use modelfox_zip::zip;
+use ndarray::prelude::*;
+
+/**
+A `PolynomialFeatureGroup` creates one feature for each power of a number column from 2 up to `degree`, so a linear model can fit a curve to the column instead of a line. The column is normalized to zero mean and unit variance before it is raised to each power, which keeps the features on a similar scale. Pair it with a [`NormalizedFeatureGroup`](crate::NormalizedFeatureGroup) for the same column to include the first power.
+
+Mean: 2.0, Standard Deviation: 1.0, Degree: 3
+
+| input value     | feature values  |
+|-----------------|-----------------|
+| "INVALID!"      | [0, 0]          |
+| 1.0             | [1, -1]         |
+| 2.0             | [0, 0]          |
+| 4.0             | [4, 8]          |
+*/
+#[derive(Clone, Debug)]
+pub struct PolynomialFeatureGroup {
+	pub source_column_name: String,
+	pub mean: f32,
+	pub variance: f32,
+	/// This is the highest power of the normalized column value.
+	pub degree: usize,
+}
+
+impl PolynomialFeatureGroup {
+	pub fn compute_table(
+		&self,
+		column: TableColumnView,
+		progress: &impl Fn(u64),
+	) -> Vec<TableColumn> {
+		match column {
+			TableColumnView::Number(column) => {
+				self.compute_table_for_number_column(column, &|| progress(1))
+			}
+			TableColumnView::Unknown(_) => unimplemented!(),
+			TableColumnView::Enum(_) => unimplemented!(),
+			TableColumnView::Text(_) => unimplemented!(),
+		}
+	}
+
+	pub fn compute_array_f32(
+		&self,
+		mut features: ArrayViewMut2<f32>,
+		column: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		let column = match column {
+			TableColumnView::Number(column) => column,
+			_ => unimplemented!(),
+		};
+		for (mut features, value) in zip!(features.axis_iter_mut(Axis(0)), column.iter()) {
+			for (feature, power) in zip!(features.iter_mut(), self.powers()) {
+				*feature = self.feature_value(*value, power);
+			}
+			progress();
+		}
+	}
+
+	pub fn compute_array_value(
+		&self,
+		mut features: ArrayViewMut2<TableValue>,
+		column: TableColumnView,
+		progress: &impl Fn(),
+	) {
+		let column = match column {
+			TableColumnView::Number(column) => column,
+			_ => unimplemented!(),
+		};
+		for (mut features, value) in zip!(features.axis_iter_mut(Axis(0)), column.iter()) {
+			for (feature, power) in zip!(features.iter_mut(), self.powers()) {
+				*feature = TableValue::Number(self.feature_value(*value, power));
+			}
+			progress();
+		}
+	}
+
+	/// Return the power of the normalized column value for each feature.
+	pub fn powers(&self) -> impl Iterator<Item = usize> {
+		2..=self.degree
+	}
+}
+
+impl PolynomialFeatureGroup {
+	fn compute_table_for_number_column(
+		&self,
+		column: NumberTableColumnView,
+		progress: &impl Fn(),
+	) -> Vec<TableColumn> {
+		let mut feature_columns = vec![Vec::with_capacity(column.len()); self.powers().count()];
+		for value in column.iter() {
+			for (feature_column, power) in zip!(feature_columns.iter_mut(), self.powers()) {
+				feature_column.push(self.feature_value(*value, power));
+			}
+			progress();
+		}
+		feature_columns
+			.into_iter()
+			.map(|feature_column| TableColumn::Number(NumberTableColumn::new(None, feature_column)))
+			.collect()
+	}
+
+	fn feature_value(&self, value: f32, power: usize) -> f32 {
+		if value.is_nan() || self.variance == 0.0 {
+			0.0
+		} else {
+			((value - self.mean) / f32::sqrt(self.variance)).powi(power as i32)
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::polynomial::*;
+	use crate::test_common::compute_features;
+
+	fn compute(feature_group: &PolynomialFeatureGroup, values: Vec<f32>) -> Array2<f32> {
+		let column = NumberTableColumn::new(None, values);
+		let column = TableColumnView::Number(column.view());
+		compute_features(
+			column.len(),
+			feature_group.powers().count(),
+			|| feature_group.compute_table(column.clone(), &|_| {}),
+			|features| feature_group.compute_array_f32(features, column.clone(), &|| {}),
+			|features| feature_group.compute_array_value(features, column.clone(), &|| {}),
+		)
+	}
+
+	#[test]
+	fn test_polynomial() {
+		// These are the values in the table in the docs for `PolynomialFeatureGroup`.
+		let feature_group = PolynomialFeatureGroup {
+			source_column_name: "values".to_owned(),
+			mean: 2.0,
+			variance: 1.0,
+			degree: 3,
+		};
+		assert_eq!(feature_group.powers().collect::<Vec<_>>(), vec![2, 3]);
+		assert_eq!(
+			compute(&feature_group, vec![f32::NAN, 1.0, 2.0, 4.0]),
+			arr2(&[[0.0, 0.0], [1.0, -1.0], [0.0, 0.0], [4.0, 8.0]])
+		);
+	}
+
+	#[test]
+	fn test_zero_variance() {
+		let feature_group = PolynomialFeatureGroup {
+			source_column_name: "values".to_owned(),
+			mean: 2.0,
+			variance: 0.0,
+			degree: 2,
+		};
+		assert_eq!(
+			compute(&feature_group, vec![2.0, 2.0]),
+			arr2(&[[0.0], [0.0]])
+		);
+	}
+}
@@ -0,0 +1,40 @@
+//! This module contains functionality used to test feature groups.
+
+use modelfox_table::{EnumTableColumn, TableColumn, TableValue};
+use ndarray::prelude::*;
+use std::num::NonZeroUsize;
+
+/// Make an enum column with the variants "red", "green", and "blue" from 1-based variant indexes.
+pub fn color_column(values: &[Option<usize>]) -> EnumTableColumn {
+	EnumTableColumn::new(
+		None,
+		vec!["red".to_owned(), "green".to_owned(), "blue".to_owned()],
+		values
+			.iter()
+			.map(|value| value.and_then(NonZeroUsize::new))
+			.collect(),
+	)
+}
+
+/// Compute `n_features` features for `n_rows` rows with each of the three methods, check that they agree, and return them.
+pub fn compute_features(
+	n_rows: usize,
+	n_features: usize,
+	compute_table: impl FnOnce() -> Vec<TableColumn>,
+	compute_array_f32: impl FnOnce(ArrayViewMut2<f32>),
+	compute_array_value: impl FnOnce(ArrayViewMut2<TableValue>),
+) -> Array2<f32> {
+	let table = compute_table();
+	assert_eq!(table.len(), n_features);
+	let table = Array2::from_shape_fn((n_rows, n_features), |(example_index, feature_index)| {
+		table[feature_index].as_number().unwrap().view().as_slice()[example_index]
+	});
+	let mut array_f32 = Array2::from_elem((n_rows, n_features), f32::NAN);
+	compute_array_f32(array_f32.view_mut());
+	let mut array_value = Array2::from_elem((n_rows, n_features), TableValue::Unknown);
+	compute_array_value(array_value.view_mut());
+	let array_value = array_value.map(|value| *value.as_number().unwrap());
+	assert_eq!(array_f32, table);
+	assert_eq!(array_value, table);
+	table
+}
@@ -0,0 +1,220 @@
+/*!
+This module contains the description of a gradient boosted tree ensemble that the LightGBM and XGBoost readers produce, and the conversion of its trees to [`modelfox_tree::Tree`]s.
+*/
+
+use anyhow::{bail, Result};
+use bitvec::prelude::*;
+use num::ToPrimitive;
+
+pub struct Ensemble {
+	pub objective: Objective,
+	/// This is the number of features the model was trained on.
+	pub n_features: usize,
+	/// This is the initial value of each output, before the values of the trees are added.
+	pub biases: Vec<f32>,
+	pub trees: Vec<Tree>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Objective {
+	Regression,
+	BinaryClassification,
+	MulticlassClassification { n_classes: usize },
+}
+
+impl Objective {
+	pub fn n_outputs(&self) -> usize {
+		match self {
+			Objective::Regression | Objective::BinaryClassification => 1,
+			Objective::MulticlassClassification { n_classes } => *n_classes,
+		}
+	}
+}
+
+pub struct Tree {
+	/// This is the output, or class for multiclass classifiers, that this tree's value is added to.
+	pub output: usize,
+	/// These are the nodes of the tree. The root is the first node.
+	pub nodes: Vec<Node>,
+}
+
+pub enum Node {
+	Branch {
+		split: Split,
+		left_child_index: usize,
+		right_child_index: usize,
+		/// This is the number of training examples, or sum of hessians, that reached this node. It is used to compute the fraction of examples that reached each node.
+		weight: f64,
+	},
+	Leaf {
+		value: f64,
+		weight: f64,
+	},
+}
+
+pub enum Split {
+	/// Examples whose value is less than or equal to `threshold` are sent left and all others are sent right. Examples whose value is missing are sent left if `missing_values_left` is true.
+	Numerical {
+		feature_index: usize,
+		threshold: f32,
+		missing_values_left: bool,
+	},
+	/// Examples whose category is one of `categories` are sent left if `categories_left` is true and right otherwise. Examples with any other category are sent the other way. Examples whose category is missing are sent left if `missing_values_left` is true.
+	Categorical {
+		feature_index: usize,
+		categories: Vec<usize>,
+		categories_left: bool,
+		missing_values_left: bool,
+	},
+}
+
+/// This describes the column for a feature, which determines which splits are allowed on it.
+pub enum FeatureColumn<'a> {
+	Number { name: &'a str },
+	Enum { name: &'a str, n_variants: usize },
+}
+
+impl Tree {
+	/// Convert this tree to a [`modelfox_tree::Tree`]. Category `c` is the variant at index `c` of the enum column, which modelfox numbers `c + 1` because `0` is reserved for invalid values.
+	pub fn to_modelfox(&self, features: &[FeatureColumn]) -> Result<modelfox_tree::Tree> {
+		let root_weight = match &self.nodes[0] {
+			Node::Branch { weight, .. } | Node::Leaf { weight, .. } => *weight,
+		};
+		let examples_fraction = |weight: f64| {
+			if root_weight > 0.0 {
+				(weight / root_weight).to_f32().unwrap()
+			} else {
+				0.0
+			}
+		};
+		let nodes = self
+			.nodes
+			.iter()
+			.map(|node| {
+				let node = match node {
+					Node::Leaf { value, weight } => {
+						modelfox_tree::Node::Leaf(modelfox_tree::LeafNode {
+							value: *value,
+							examples_fraction: examples_fraction(*weight),
+						})
+					}
+					Node::Branch {
+						split,
+						left_child_index,
+						right_child_index,
+						weight,
+					} => modelfox_tree::Node::Branch(modelfox_tree::BranchNode {
+						left_child_index: *left_child_index,
+						right_child_index: *right_child_index,
+						split: split.to_modelfox(features)?,
+						examples_fraction: examples_fraction(*weight),
+					}),
+				};
+				Ok(node)
+			})
+			.collect::<Result<_>>()?;
+		Ok(modelfox_tree::Tree { nodes, weight: 1.0 })
+	}
+}
+
+impl Split {
+	fn to_modelfox(&self, features: &[FeatureColumn]) -> Result<modelfox_tree::BranchSplit> {
+		let feature_index = match self {
+			Split::Numerical { feature_index, .. } | Split::Categorical { feature_index, .. } => {
+				*feature_index
+			}
+		};
+		if feature_index >= features.len() {
+			bail!(
+				"The model has a split on the invalid feature {}.",
+				feature_index
+			);
+		}
+		let split = match self {
+			Split::Numerical {
+				feature_index,
+				threshold,
+				missing_values_left,
+			} => {
+				if let FeatureColumn::Enum { name, .. } = &features[*feature_index] {
+					bail!(
+						"The model has a numerical split on the column \"{}\", but it is an enum column in the schema.",
+						name
+					);
+				}
+				modelfox_tree::BranchSplit::Continuous(modelfox_tree::BranchSplitContinuous {
+					feature_index: *feature_index,
+					split_value: *threshold,
+					invalid_values_direction: direction(*missing_values_left),
+				})
+			}
+			Split::Categorical {
+				feature_index,
+				categories,
+				categories_left,
+				missing_values_left,
+			} => {
+				let n_variants = match &features[*feature_index] {
+					FeatureColumn::Enum { n_variants, .. } => *n_variants,
+					FeatureColumn::Number { name } => bail!(
+						"The model has a categorical split on the column \"{}\", but it is a number column in the schema.",
+						name
+					),
+				};
+				// A set bit sends the example right. Categories without a variant in the schema can never occur, so they are ignored.
+				let mut directions = bitvec![u8, Lsb0; *categories_left as isize; n_variants + 1];
+				for category in categories.iter().filter(|category| **category < n_variants) {
+					directions.set(category + 1, !*categories_left);
+				}
+				directions.set(0, !*missing_values_left);
+				modelfox_tree::BranchSplit::Discrete(modelfox_tree::BranchSplitDiscrete {
+					feature_index: *feature_index,
+					directions,
+				})
+			}
+		};
+		Ok(split)
+	}
+}
+
+fn direction(left: bool) -> modelfox_tree::SplitDirection {
+	if left {
+		modelfox_tree::SplitDirection::Left
+	} else {
+		modelfox_tree::SplitDirection::Right
+	}
+}
+
+/// Return the largest `f32` that is less than or equal to `value`, so that `x <= value` and `x <= f32_at_most(value)` agree for every `f32` `x`.
+pub fn f32_at_most(value: f64) -> f32 {
+	// Values outside the range of `f32` round to infinity.
+	let rounded = value as f32;
+	if f64::from(rounded) > value {
+		next_down(rounded)
+	} else {
+		rounded
+	}
+}
+
+/// Return the largest `f32` that is less than `value`.
+pub fn next_down(value: f32) -> f32 {
+	if value.is_nan() || value == f32::NEG_INFINITY {
+		value
+	} else if value == 0.0 {
+		-f32::from_bits(1)
+	} else if value > 0.0 {
+		f32::from_bits(value.to_bits() - 1)
+	} else {
+		f32::from_bits(value.to_bits() + 1)
+	}
+}
+
+#[test]
+fn test_f32_at_most() {
+	assert_eq!(f32_at_most(1.5), 1.5);
+	assert_eq!(f32_at_most(0.1), 0.099999994);
+	assert_eq!(f32_at_most(1e300), f32::MAX);
+	assert_eq!(next_down(1.0), 0.99999994);
+	assert!(next_down(0.0) < 0.0);
+	assert_eq!(next_down(-1.0), -1.0000001);
+}
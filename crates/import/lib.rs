@@ -0,0 +1,688 @@
+/*!
+This crate imports gradient boosted tree models trained with [LightGBM](https://lightgbm.readthedocs.io) and [XGBoost](https://xgboost.readthedocs.io) into ModelFox models, so they can be served, inspected, and monitored like models trained with ModelFox.
+
+LightGBM models are read from the text format and XGBoost models are read from the JSON format. Because these files do not describe the columns a model was trained on, a [`Schema`] must be provided that lists the model's features in order and its target column. Regression, binary classification, and multiclass classification models with numerical and categorical splits are supported. The imported model has an identity feature group for each column. It has no training dataset, so its column stats and metrics are empty.
+*/
+
+use self::ensemble::{Ensemble, FeatureColumn, Objective};
+use anyhow::{bail, Result};
+use modelfox_core::{
+	config::Column,
+	explain::ExplanationsOutput,
+	model::{
+		BinaryClassificationComparisonMetric, BinaryClassificationModel, BinaryClassifier, Metrics,
+		Model, ModelInner, MulticlassClassificationComparisonMetric, MulticlassClassificationModel,
+		MulticlassClassifier, RegressionComparisonMetric, RegressionModel, Regressor,
+		TreeBinaryClassificationModel, TreeMulticlassClassificationModel, TreeRegressionModel,
+	},
+	stats::{ColumnStatsOutput, EnumColumnStatsOutput, NumberColumnStatsOutput, StatsSettings},
+	train::{
+		TrainGridItemOutput, TrainModelOutput, TreeBinaryClassifierTrainModelOutput,
+		TreeMulticlassClassifierTrainModelOutput, TreeRegressorTrainModelOutput,
+	},
+};
+use ndarray::prelude::*;
+use std::time::Duration;
+
+mod ensemble;
+mod lightgbm;
+mod xgboost;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportFormat {
+	LightGbm,
+	XgBoost,
+}
+
+/// This describes the columns of the dataset an imported model was trained on. The columns use the same format as the columns in the `dataset` section of a training config.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schema {
+	/// These are the columns for the model's features, in the same order as the model's features. Number columns may only be used in numerical splits and enum columns may only be used in categorical splits, where category `i` is the enum's variant at index `i`.
+	pub columns: Vec<Column>,
+	/// This is the target column. It must be a number column for regressors and an enum column for classifiers, whose variants are the classes in the order the model numbers them. For binary classifiers, the second variant is the positive class.
+	pub target: Column,
+}
+
+/// Import a LightGBM or XGBoost model using the columns described by `schema`.
+pub fn import(format: ImportFormat, model: &[u8], schema: &Schema) -> Result<Model> {
+	let ensemble = match format {
+		ImportFormat::LightGbm => lightgbm::read(std::str::from_utf8(model)?)?,
+		ImportFormat::XgBoost => xgboost::read(model)?,
+	};
+	if ensemble.n_features != schema.columns.len() {
+		bail!(
+			"The model has {} features, but the schema has {} columns.",
+			ensemble.n_features,
+			schema.columns.len()
+		);
+	}
+	let feature_columns = schema
+		.columns
+		.iter()
+		.map(|column| match column {
+			Column::Number(column) => Ok(FeatureColumn::Number { name: &column.name }),
+			Column::Enum(column) => Ok(FeatureColumn::Enum {
+				name: &column.name,
+				n_variants: column.variants.len(),
+			}),
+			_ => bail!("Only number and enum columns can be used in the schema."),
+		})
+		.collect::<Result<Vec<_>>>()?;
+	// The stats are created for each of the overall, train, and test datasets.
+	let column_stats = || {
+		schema
+			.columns
+			.iter()
+			.map(column_stats_output)
+			.collect::<Result<Vec<_>>>()
+	};
+	let target_column_stats = || column_stats_output(&schema.target);
+	// Each column is used as a feature as is.
+	let feature_groups = schema
+		.columns
+		.iter()
+		.map(|column| {
+			modelfox_features::FeatureGroup::Identity(modelfox_features::IdentityFeatureGroup {
+				source_column_name: column_name(column).to_owned(),
+				impute: None,
+				missing_indicator: false,
+			})
+		})
+		.collect::<Vec<_>>();
+	let target_column_name = column_name(&schema.target).to_owned();
+	let target_column_index = schema.columns.len();
+	let train_options = modelfox_tree::TrainOptions::default();
+	let inner = match ensemble.objective {
+		Objective::Regression => {
+			if !matches!(schema.target, Column::Number(_)) {
+				bail!("The target column of a regressor must be a number column.");
+			}
+			let (bias, trees) = single_output_trees(&ensemble, &feature_columns)?;
+			let model = modelfox_tree::Regressor { bias, trees };
+			let feature_importances = compute_feature_importances(&model.trees, &feature_groups);
+			let metrics = || modelfox_metrics::RegressionMetrics::default().finalize();
+			ModelInner::Regressor(Regressor {
+				target_column_name,
+				train_row_count: 0,
+				test_row_count: 0,
+				overall_row_count: 0,
+				stats_settings: StatsSettings::default(),
+				overall_column_stats: column_stats()?,
+				overall_target_column_stats: target_column_stats()?,
+				train_column_stats: column_stats()?,
+				train_target_column_stats: target_column_stats()?,
+				test_column_stats: column_stats()?,
+				test_target_column_stats: target_column_stats()?,
+				baseline_metrics: metrics(),
+				comparison_metric: RegressionComparisonMetric::RootMeanSquaredError,
+				train_grid_item_outputs: vec![TrainGridItemOutput {
+					train_model_output: TrainModelOutput::TreeRegressor(
+						TreeRegressorTrainModelOutput {
+							model: model.clone(),
+							feature_groups: feature_groups.clone(),
+							target_column_index,
+							target_transform: None,
+							losses: None,
+							train_options: train_options.clone(),
+							feature_importances: feature_importances.clone(),
+						},
+					),
+					comparison_metrics: Metrics::Regression(metrics()),
+					comparison_metric_value: metrics().rmse,
+					duration: Duration::ZERO,
+				}],
+				best_grid_item_index: 0,
+				model: RegressionModel::Tree(TreeRegressionModel {
+					model,
+					train_options,
+					feature_groups,
+					losses: None,
+					feature_importances,
+				}),
+				test_metrics: metrics(),
+				explanations: empty_explanations(),
+				target_transform: None,
+				dropped_columns: Vec::new(),
+				data_quality_issues: Vec::new(),
+			})
+		}
+		Objective::BinaryClassification => {
+			let classes = target_classes(&schema.target, 2)?;
+			let (bias, trees) = single_output_trees(&ensemble, &feature_columns)?;
+			let model = modelfox_tree::BinaryClassifier { bias, trees };
+			let feature_importances = compute_feature_importances(&model.trees, &feature_groups);
+			let metrics = || modelfox_metrics::BinaryClassificationMetrics::new(99).finalize();
+			ModelInner::BinaryClassifier(BinaryClassifier {
+				target_column_name,
+				negative_class: classes[0].clone(),
+				positive_class: classes[1].clone(),
+				train_row_count: 0,
+				test_row_count: 0,
+				overall_row_count: 0,
+				stats_settings: StatsSettings::default(),
+				overall_column_stats: column_stats()?,
+				overall_target_column_stats: target_column_stats()?,
+				train_column_stats: column_stats()?,
+				train_target_column_stats: target_column_stats()?,
+				test_column_stats: column_stats()?,
+				test_target_column_stats: target_column_stats()?,
+				baseline_metrics: metrics(),
+				comparison_metric: BinaryClassificationComparisonMetric::AucRoc,
+				train_grid_item_outputs: vec![TrainGridItemOutput {
+					train_model_output: TrainModelOutput::TreeBinaryClassifier(
+						TreeBinaryClassifierTrainModelOutput {
+							model: model.clone(),
+							feature_groups: feature_groups.clone(),
+							target_column_index,
+							losses: None,
+							train_options: train_options.clone(),
+							feature_importances: feature_importances.clone(),
+						},
+					),
+					comparison_metrics: Metrics::BinaryClassification(metrics()),
+					comparison_metric_value: metrics().auc_roc_approx,
+					duration: Duration::ZERO,
+				}],
+				best_grid_item_index: 0,
+				model: BinaryClassificationModel::Tree(TreeBinaryClassificationModel {
+					model,
+					train_options,
+					feature_groups,
+					losses: None,
+					feature_importances,
+				}),
+				test_metrics: metrics(),
+				explanations: empty_explanations(),
+				dropped_columns: Vec::new(),
+				data_quality_issues: Vec::new(),
+			})
+		}
+		Objective::MulticlassClassification { n_classes } => {
+			let classes = target_classes(&schema.target, n_classes)?;
+			let model = multiclass_classifier(&ensemble, &feature_columns)?;
+			let feature_importances =
+				compute_feature_importances(model.trees.as_slice().unwrap_or(&[]), &feature_groups);
+			let metrics =
+				|| modelfox_metrics::MulticlassClassificationMetrics::new(n_classes).finalize();
+			ModelInner::MulticlassClassifier(MulticlassClassifier {
+				target_column_name,
+				classes,
+				train_row_count: 0,
+				test_row_count: 0,
+				overall_row_count: 0,
+				stats_settings: StatsSettings::default(),
+				overall_column_stats: column_stats()?,
+				overall_target_column_stats: target_column_stats()?,
+				train_column_stats: column_stats()?,
+				train_target_column_stats: target_column_stats()?,
+				test_column_stats: column_stats()?,
+				test_target_column_stats: target_column_stats()?,
+				baseline_metrics: metrics(),
+				comparison_metric: MulticlassClassificationComparisonMetric::Accuracy,
+				train_grid_item_outputs: vec![TrainGridItemOutput {
+					train_model_output: TrainModelOutput::TreeMulticlassClassifier(
+						TreeMulticlassClassifierTrainModelOutput {
+							model: model.clone(),
+							feature_groups: feature_groups.clone(),
+							target_column_index,
+							losses: None,
+							train_options: train_options.clone(),
+							feature_importances: feature_importances.clone(),
+						},
+					),
+					comparison_metrics: Metrics::MulticlassClassification(metrics()),
+					comparison_metric_value: metrics().accuracy,
+					duration: Duration::ZERO,
+				}],
+				best_grid_item_index: 0,
+				model: MulticlassClassificationModel::Tree(TreeMulticlassClassificationModel {
+					model,
+					train_options,
+					feature_groups,
+					losses: None,
+					feature_importances,
+				}),
+				test_metrics: metrics(),
+				explanations: empty_explanations(),
+				dropped_columns: Vec::new(),
+				data_quality_issues: Vec::new(),
+			})
+		}
+	};
+	Ok(Model {
+		id: modelfox_id::Id::generate(),
+		version: env!("CARGO_PKG_VERSION").to_owned(),
+		date: chrono::Utc::now().to_rfc3339(),
+		inner,
+		parent_id: None,
+	})
+}
+
+/// Convert the trees of a regressor or binary classifier, which have a single output.
+fn single_output_trees(
+	ensemble: &Ensemble,
+	feature_columns: &[FeatureColumn],
+) -> Result<(f32, Vec<modelfox_tree::Tree>)> {
+	let trees = ensemble
+		.trees
+		.iter()
+		.map(|tree| tree.to_modelfox(feature_columns))
+		.collect::<Result<_>>()?;
+	Ok((ensemble.biases[0], trees))
+}
+
+/// Convert the trees of a multiclass classifier. ModelFox multiclass classifiers have one tree per class in each round, so classes with fewer trees than others are padded with trees that output zero.
+fn multiclass_classifier(
+	ensemble: &Ensemble,
+	feature_columns: &[FeatureColumn],
+) -> Result<modelfox_tree::MulticlassClassifier> {
+	let n_classes = ensemble.objective.n_outputs();
+	let mut trees_for_classes = vec![Vec::new(); n_classes];
+	for tree in ensemble.trees.iter() {
+		trees_for_classes[tree.output].push(tree.to_modelfox(feature_columns)?);
+	}
+	let n_rounds = trees_for_classes.iter().map(Vec::len).max().unwrap_or(0);
+	let mut trees = Vec::with_capacity(n_rounds * n_classes);
+	for round in 0..n_rounds {
+		for trees_for_class in trees_for_classes.iter() {
+			let tree = trees_for_class
+				.get(round)
+				.cloned()
+				.unwrap_or_else(|| modelfox_tree::Tree {
+					nodes: vec![modelfox_tree::Node::Leaf(modelfox_tree::LeafNode {
+						value: 0.0,
+						examples_fraction: 1.0,
+					})],
+					weight: 1.0,
+				});
+			trees.push(tree);
+		}
+	}
+	Ok(modelfox_tree::MulticlassClassifier {
+		biases: Array::from(ensemble.biases.clone()),
+		trees: Array::from_shape_vec((n_rounds, n_classes), trees).unwrap(),
+	})
+}
+
+/// Compute feature importances using the "split" method like [`modelfox_tree`] does during training, where a feature's importance is proportional to the number of branches that use it to split.
+fn compute_feature_importances(
+	trees: &[modelfox_tree::Tree],
+	feature_groups: &[modelfox_features::FeatureGroup],
+) -> Vec<f32> {
+	let mut feature_importances = vec![0.0; feature_groups.len()];
+	for tree in trees.iter() {
+		for node in tree.nodes.iter() {
+			if let modelfox_tree::Node::Branch(branch) = node {
+				feature_importances[branch.split.feature_index()] += 1.0;
+			}
+		}
+	}
+	let total = feature_importances.iter().sum::<f32>();
+	if total > 0.0 {
+		for feature_importance in feature_importances.iter_mut() {
+			*feature_importance /= total;
+		}
+	}
+	feature_importances
+}
+
+fn target_classes(target: &Column, n_classes: usize) -> Result<Vec<String>> {
+	match target {
+		Column::Enum(column) if column.variants.len() == n_classes => Ok(column.variants.clone()),
+		Column::Enum(column) => bail!(
+			"The model has {} classes, but the target column has {} variants.",
+			n_classes,
+			column.variants.len()
+		),
+		_ => bail!("The target column of a classifier must be an enum column."),
+	}
+}
+
+fn column_name(column: &Column) -> &str {
+	match column {
+		Column::Unknown(column) => &column.name,
+		Column::Number(column) => &column.name,
+		Column::Enum(column) => &column.name,
+		Column::Text(column) => &column.name,
+	}
+}
+
+/// Create the stats for a column with no values. The variants of an enum column are stored in its histogram.
+fn column_stats_output(column: &Column) -> Result<ColumnStatsOutput> {
+	let column_stats = match column {
+		Column::Number(column) => ColumnStatsOutput::Number(NumberColumnStatsOutput {
+			column_name: column.name.clone(),
+			count: 0,
+			histogram: None,
+			unique_count: 0,
+			max: f32::NAN,
+			mean: f32::NAN,
+			min: f32::NAN,
+			invalid_count: 0,
+			variance: f32::NAN,
+			std: f32::NAN,
+			p25: f32::NAN,
+			p50: f32::NAN,
+			p75: f32::NAN,
+			quantiles: Vec::new(),
+		}),
+		Column::Enum(column) => ColumnStatsOutput::Enum(EnumColumnStatsOutput {
+			column_name: column.name.clone(),
+			count: 0,
+			histogram: column
+				.variants
+				.iter()
+				.map(|variant| (variant.clone(), 0))
+				.collect(),
+			invalid_count: 0,
+			unique_count: column.variants.len(),
+		}),
+		_ => bail!("Only number and enum columns can be used in the schema."),
+	};
+	Ok(column_stats)
+}
+
+fn empty_explanations() -> ExplanationsOutput {
+	ExplanationsOutput {
+		shap_feature_importances: Vec::new(),
+		shap_column_importances: Vec::new(),
+		partial_dependences: Vec::new(),
+		feature_interactions: None,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use modelfox_core::predict::{PredictInput, PredictInputValue, PredictOptions, PredictOutput};
+
+	/// Import a model, write it to a `.modelfox` file, read it back, and make a prediction for each example.
+	fn import_and_predict(
+		format: ImportFormat,
+		model: &str,
+		schema: &str,
+		examples: &[&[(&str, PredictInputValue)]],
+	) -> Vec<PredictOutput> {
+		let schema: Schema = serde_json::from_str(schema).unwrap();
+		let model = import(format, model.as_bytes(), &schema).unwrap();
+		let dir = tempfile::tempdir().unwrap();
+		let model_path = dir.path().join("model.modelfox");
+		model.to_path(&model_path).unwrap();
+		let bytes = std::fs::read(&model_path).unwrap();
+		let model = modelfox_model::from_bytes(&bytes).unwrap();
+		let model = modelfox_core::predict::Model::from(model);
+		let examples = examples
+			.iter()
+			.map(|example| {
+				PredictInput(
+					example
+						.iter()
+						.map(|(column_name, value)| (column_name.to_string(), value.clone()))
+						.collect(),
+				)
+			})
+			.collect::<Vec<_>>();
+		modelfox_core::predict::predict(&model, &examples, &PredictOptions::default())
+	}
+
+	fn assert_close(actual: f32, expected: f32) {
+		assert!(
+			(actual - expected).abs() <= 1e-6,
+			"expected {}, got {}",
+			expected,
+			actual
+		);
+	}
+
+	fn sigmoid(logit: f32) -> f32 {
+		1.0 / ((-logit).exp() + 1.0)
+	}
+
+	const LIGHTGBM_BINARY_CLASSIFIER: &str = "tree
+version=v3
+num_class=1
+num_tree_per_iteration=1
+label_index=0
+max_feature_idx=1
+objective=binary sigmoid:2
+feature_names=x color
+feature_infos=[0:3] 0:1:2
+tree_sizes=400 100
+
+Tree=0
+num_leaves=3
+num_cat=1
+split_feature=0 1
+split_gain=10 5
+threshold=1.5 0
+decision_type=10 1
+left_child=1 -1
+right_child=-3 -2
+leaf_value=0.5 -0.25 1
+leaf_weight=7.5 5 12.5
+leaf_count=30 20 50
+internal_value=0 0.2
+internal_weight=25 12.5
+internal_count=100 50
+cat_boundaries=0 1
+cat_threshold=5
+is_linear=0
+shrinkage=1
+
+
+Tree=1
+num_leaves=1
+num_cat=0
+split_feature=
+split_gain=
+threshold=
+decision_type=
+left_child=
+right_child=
+leaf_value=0.1
+leaf_weight=
+leaf_count=
+internal_value=
+internal_weight=
+internal_count=
+is_linear=0
+shrinkage=1
+
+
+end of trees
+";
+
+	#[test]
+	fn test_import_lightgbm_binary_classifier() {
+		let schema = r#"{
+			"columns": [
+				{ "name": "x", "type": "number" },
+				{ "name": "color", "type": "enum", "variants": ["red", "green", "blue"] }
+			],
+			"target": { "name": "label", "type": "enum", "variants": ["false", "true"] }
+		}"#;
+		let number = |value| ("x", PredictInputValue::Number(value));
+		let color = |value: &str| ("color", PredictInputValue::String(value.to_owned()));
+		let outputs = import_and_predict(
+			ImportFormat::LightGbm,
+			LIGHTGBM_BINARY_CLASSIFIER,
+			schema,
+			&[
+				&[number(1.0), color("red")],
+				&[number(1.0), color("green")],
+				&[number(1.5), color("blue")],
+				&[number(2.0), color("red")],
+				// Missing values of x are sent left, and missing colors are sent right.
+				&[color("blue")],
+				&[number(1.0)],
+			],
+		);
+		// The leaf values are multiplied by the sigmoid parameter.
+		let expected_logits = [1.2, -0.3, 1.2, 2.2, 1.2, -0.3];
+		for (output, expected_logit) in outputs.iter().zip(expected_logits) {
+			let output = match output {
+				PredictOutput::BinaryClassification(output) => output,
+				_ => unreachable!(),
+			};
+			let probability = if output.class_name == "true" {
+				output.probability
+			} else {
+				1.0 - output.probability
+			};
+			assert_close(probability, sigmoid(expected_logit));
+		}
+	}
+
+	const XGBOOST_MULTICLASS_CLASSIFIER: &str = r#"{
+		"learner": {
+			"attributes": {},
+			"feature_names": [],
+			"feature_types": [],
+			"gradient_booster": {
+				"model": {
+					"gbtree_model_param": { "num_parallel_tree": "1", "num_trees": "4" },
+					"iteration_indptr": [0, 3, 4],
+					"tree_info": [0, 1, 2, 0],
+					"trees": [
+						{
+							"base_weights": [0, 0.3, -0.2],
+							"categories": [],
+							"categories_nodes": [],
+							"categories_segments": [],
+							"categories_sizes": [],
+							"default_left": [1, 0, 0],
+							"id": 0,
+							"left_children": [1, -1, -1],
+							"loss_changes": [1, 0, 0],
+							"parents": [2147483647, 0, 0],
+							"right_children": [2, -1, -1],
+							"split_conditions": [1, 0.3, -0.2],
+							"split_indices": [0, 0, 0],
+							"split_type": [0, 0, 0],
+							"sum_hessian": [10, 6, 4],
+							"tree_param": { "num_deleted": "0", "num_feature": "2", "num_nodes": "3", "size_leaf_vector": "1" }
+						},
+						{
+							"base_weights": [0, 0.4, -0.4],
+							"categories": [1],
+							"categories_nodes": [0],
+							"categories_segments": [0],
+							"categories_sizes": [1],
+							"default_left": [0, 0, 0],
+							"id": 1,
+							"left_children": [1, -1, -1],
+							"loss_changes": [1, 0, 0],
+							"parents": [2147483647, 0, 0],
+							"right_children": [2, -1, -1],
+							"split_conditions": [0, 0.4, -0.4],
+							"split_indices": [1, 0, 0],
+							"split_type": [1, 0, 0],
+							"sum_hessian": [10, 5, 5],
+							"tree_param": { "num_deleted": "0", "num_feature": "2", "num_nodes": "3", "size_leaf_vector": "1" }
+						},
+						{
+							"base_weights": [0.1],
+							"categories": [],
+							"categories_nodes": [],
+							"categories_segments": [],
+							"categories_sizes": [],
+							"default_left": [0],
+							"id": 2,
+							"left_children": [-1],
+							"loss_changes": [0],
+							"parents": [2147483647],
+							"right_children": [-1],
+							"split_conditions": [0.1],
+							"split_indices": [0],
+							"split_type": [0],
+							"sum_hessian": [10],
+							"tree_param": { "num_deleted": "0", "num_feature": "2", "num_nodes": "1", "size_leaf_vector": "1" }
+						},
+						{
+							"base_weights": [0.05],
+							"categories": [],
+							"categories_nodes": [],
+							"categories_segments": [],
+							"categories_sizes": [],
+							"default_left": [0],
+							"id": 3,
+							"left_children": [-1],
+							"loss_changes": [0],
+							"parents": [2147483647],
+							"right_children": [-1],
+							"split_conditions": [0.05],
+							"split_indices": [0],
+							"split_type": [0],
+							"sum_hessian": [10],
+							"tree_param": { "num_deleted": "0", "num_feature": "2", "num_nodes": "1", "size_leaf_vector": "1" }
+						}
+					]
+				},
+				"name": "gbtree"
+			},
+			"learner_model_param": { "base_score": "5E-1", "num_class": "3", "num_feature": "2", "num_target": "1" },
+			"objective": { "name": "multi:softprob", "softmax_multiclass_param": { "num_class": "3" } }
+		},
+		"version": [1, 6, 0]
+	}"#;
+
+	#[test]
+	fn test_import_xgboost_multiclass_classifier() {
+		let schema = r#"{
+			"columns": [
+				{ "name": "x", "type": "number" },
+				{ "name": "y", "type": "enum", "variants": ["a", "b"] }
+			],
+			"target": { "name": "label", "type": "enum", "variants": ["one", "two", "three"] }
+		}"#;
+		let x = |value| ("x", PredictInputValue::Number(value));
+		let y = |value: &str| ("y", PredictInputValue::String(value.to_owned()));
+		let outputs = import_and_predict(
+			ImportFormat::XgBoost,
+			XGBOOST_MULTICLASS_CLASSIFIER,
+			schema,
+			&[
+				&[x(0.5), y("a")],
+				// XGBoost sends values less than the split condition left, so 1 is sent right.
+				&[x(1.0), y("b")],
+				// Missing values of x are sent left, and missing values of y are sent right.
+				&[],
+			],
+		);
+		let expected_logits = [[0.85, 0.9, 0.6], [0.35, 0.1, 0.6], [0.85, 0.1, 0.6]];
+		for (output, expected_logits) in outputs.iter().zip(expected_logits) {
+			let output = match output {
+				PredictOutput::MulticlassClassification(output) => output,
+				_ => unreachable!(),
+			};
+			let sum = expected_logits
+				.iter()
+				.map(|logit: &f32| logit.exp())
+				.sum::<f32>();
+			for (class, logit) in ["one", "two", "three"].iter().zip(expected_logits) {
+				assert_close(output.probabilities[*class], logit.exp() / sum);
+			}
+		}
+	}
+
+	#[test]
+	fn test_import_numerical_split_on_enum_column() {
+		let schema: Schema = serde_json::from_str(
+			r#"{
+				"columns": [
+					{ "name": "x", "type": "enum", "variants": ["a", "b"] },
+					{ "name": "color", "type": "enum", "variants": ["red", "green", "blue"] }
+				],
+				"target": { "name": "label", "type": "enum", "variants": ["false", "true"] }
+			}"#,
+		)
+		.unwrap();
+		let result = import(
+			ImportFormat::LightGbm,
+			LIGHTGBM_BINARY_CLASSIFIER.as_bytes(),
+			&schema,
+		);
+		assert!(result.is_err());
+	}
+}
@@ -0,0 +1,280 @@
+/*!
+This module reads LightGBM models saved in the text format by `Booster.save_model`.
+*/
+
+use crate::ensemble::{f32_at_most, Ensemble, Node, Objective, Split, Tree};
+use anyhow::{anyhow, bail, Context, Result};
+use std::{collections::HashMap, str::FromStr};
+
+const CATEGORICAL_MASK: u8 = 1;
+const DEFAULT_LEFT_MASK: u8 = 2;
+const MISSING_TYPE_NONE: u8 = 0;
+const MISSING_TYPE_ZERO: u8 = 1;
+const MISSING_TYPE_NAN: u8 = 2;
+
+pub fn read(model: &str) -> Result<Ensemble> {
+	if model.lines().next().map(str::trim) != Some("tree") {
+		bail!("The model is not a LightGBM model in the text format.");
+	}
+	// The model is a header of `key=value` lines followed by a block of `key=value` lines for each tree, each starting with a `Tree=` line.
+	let mut header = HashMap::new();
+	let mut tree_sections: Vec<HashMap<&str, &str>> = Vec::new();
+	for line in model.lines() {
+		let line = line.trim();
+		if line == "end of trees" {
+			break;
+		}
+		if line == "average_output" {
+			bail!("LightGBM random forest models are not supported.");
+		}
+		let (key, value) = match line.split_once('=') {
+			Some(entry) => entry,
+			None => continue,
+		};
+		if key == "Tree" {
+			tree_sections.push(HashMap::new());
+		}
+		match tree_sections.last_mut() {
+			Some(tree_section) => tree_section.insert(key, value),
+			None => header.insert(key, value),
+		};
+	}
+	let n_features = parse_value::<usize>(&header, "max_feature_idx")? + 1;
+	let n_outputs = match header.get("num_tree_per_iteration") {
+		Some(_) => parse_value::<usize>(&header, "num_tree_per_iteration")?,
+		None => 1,
+	};
+	let objective = header
+		.get("objective")
+		.ok_or_else(|| anyhow!("The model does not have an objective."))?;
+	let mut objective_tokens = objective.split_whitespace();
+	let objective_name = objective_tokens.next().unwrap_or("");
+	let objective_params = objective_tokens
+		.map(|token| token.split_once(':').unwrap_or((token, "")))
+		.collect::<HashMap<_, _>>();
+	// The binary objective computes the sigmoid of the sum of the trees' values multiplied by `sigmoid`, so multiplying the leaf values by it produces the same probabilities.
+	let mut leaf_scale = 1.0;
+	let objective = match objective_name {
+		"regression" | "regression_l1" | "huber" | "fair" | "quantile" | "mape" => {
+			if objective_params.contains_key("sqrt") {
+				bail!("LightGBM regression models trained with reg_sqrt are not supported.");
+			}
+			Objective::Regression
+		}
+		"binary" => {
+			if let Some(sigmoid) = objective_params.get("sigmoid") {
+				leaf_scale = sigmoid
+					.parse::<f64>()
+					.with_context(|| format!("Failed to parse the sigmoid \"{}\".", sigmoid))?;
+			}
+			Objective::BinaryClassification
+		}
+		"multiclass" => Objective::MulticlassClassification {
+			n_classes: parse_value(&header, "num_class")?,
+		},
+		_ => bail!(
+			"The LightGBM objective \"{}\" is not supported.",
+			objective_name
+		),
+	};
+	if objective.n_outputs() != n_outputs {
+		bail!(
+			"The model has {} trees per iteration, but its objective has {} outputs.",
+			n_outputs,
+			objective.n_outputs()
+		);
+	}
+	let trees = tree_sections
+		.iter()
+		.enumerate()
+		.map(|(tree_index, tree_section)| {
+			read_tree(tree_section, leaf_scale)
+				.map(|nodes| Tree {
+					output: tree_index % n_outputs,
+					nodes,
+				})
+				.with_context(|| format!("Failed to read tree {}.", tree_index))
+		})
+		.collect::<Result<Vec<_>>>()?;
+	Ok(Ensemble {
+		objective,
+		n_features,
+		// LightGBM adds the initial score to the first tree of each output.
+		biases: vec![0.0; n_outputs],
+		trees,
+	})
+}
+
+fn read_tree(section: &HashMap<&str, &str>, leaf_scale: f64) -> Result<Vec<Node>> {
+	if section.get("is_linear").copied().unwrap_or("0") != "0" {
+		bail!("LightGBM models with linear trees are not supported.");
+	}
+	let n_leaves = parse_value::<usize>(section, "num_leaves")?;
+	let leaf_values = parse_values::<f64>(section, "leaf_value")?;
+	if n_leaves == 1 {
+		return Ok(vec![Node::Leaf {
+			value: leaf_values[0] * leaf_scale,
+			weight: 1.0,
+		}]);
+	}
+	let n_branches = n_leaves - 1;
+	let split_features = parse_values::<usize>(section, "split_feature")?;
+	let thresholds = parse_values::<f64>(section, "threshold")?;
+	let decision_types = if section.contains_key("decision_type") {
+		parse_values::<u8>(section, "decision_type")?
+	} else {
+		vec![0; n_branches]
+	};
+	let left_children = parse_values::<i64>(section, "left_child")?;
+	let right_children = parse_values::<i64>(section, "right_child")?;
+	let internal_counts = parse_weights(section, "internal_count", "internal_weight")?;
+	let leaf_counts = parse_weights(section, "leaf_count", "leaf_weight")?;
+	let (cat_boundaries, cat_thresholds) = if parse_value::<usize>(section, "num_cat")? > 0 {
+		(
+			parse_values::<usize>(section, "cat_boundaries")?,
+			parse_values::<u32>(section, "cat_threshold")?,
+		)
+	} else {
+		(Vec::new(), Vec::new())
+	};
+	let arrays_have_n_branches = [
+		split_features.len(),
+		thresholds.len(),
+		decision_types.len(),
+		left_children.len(),
+		right_children.len(),
+	]
+	.iter()
+	.all(|len| *len == n_branches);
+	let counts_match = internal_counts
+		.as_ref()
+		.map(|counts| counts.len() == n_branches)
+		.unwrap_or(true)
+		&& leaf_counts
+			.as_ref()
+			.map(|counts| counts.len() == n_leaves)
+			.unwrap_or(true);
+	if !arrays_have_n_branches || leaf_values.len() != n_leaves || !counts_match {
+		bail!("The tree's arrays do not match its number of leaves.");
+	}
+	// Branches keep their indexes, and leaves are placed after the branches. A negative child `c` refers to leaf `!c`.
+	let child_index = |child: i64| -> Result<usize> {
+		let index = if child < 0 {
+			n_branches + (!child) as usize
+		} else {
+			child as usize
+		};
+		if index >= n_branches + n_leaves {
+			bail!("The tree has an invalid child index {}.", child);
+		}
+		Ok(index)
+	};
+	let mut nodes = Vec::with_capacity(n_branches + n_leaves);
+	for branch_index in 0..n_branches {
+		let decision_type = decision_types[branch_index];
+		let feature_index = split_features[branch_index];
+		let threshold = thresholds[branch_index];
+		let split = if decision_type & CATEGORICAL_MASK != 0 {
+			// The threshold is the index of the bitset of categories that are sent left. Missing and unseen categories are sent right.
+			let cat_index = threshold as usize;
+			let words = cat_boundaries
+				.get(cat_index)
+				.zip(cat_boundaries.get(cat_index + 1))
+				.and_then(|(start, end)| cat_thresholds.get(*start..*end))
+				.ok_or_else(|| anyhow!("The tree has an invalid categorical split."))?;
+			let categories = words
+				.iter()
+				.enumerate()
+				.flat_map(|(word_index, word)| {
+					(0..32)
+						.filter(move |bit| word & (1 << bit) != 0)
+						.map(move |bit| word_index * 32 + bit)
+				})
+				.collect();
+			Split::Categorical {
+				feature_index,
+				categories,
+				categories_left: true,
+				missing_values_left: false,
+			}
+		} else {
+			let default_left = decision_type & DEFAULT_LEFT_MASK != 0;
+			// Missing values are treated as zero unless the missing type is NaN.
+			let zero_left = 0.0 <= threshold;
+			let missing_values_left = match (decision_type >> 2) & 3 {
+				MISSING_TYPE_NONE => zero_left,
+				MISSING_TYPE_ZERO => {
+					if default_left != zero_left {
+						bail!("Splits that treat zero as missing are not supported.");
+					}
+					default_left
+				}
+				MISSING_TYPE_NAN => default_left,
+				missing_type => bail!("The tree has an invalid missing type {}.", missing_type),
+			};
+			Split::Numerical {
+				feature_index,
+				threshold: f32_at_most(threshold),
+				missing_values_left,
+			}
+		};
+		nodes.push(Node::Branch {
+			split,
+			left_child_index: child_index(left_children[branch_index])?,
+			right_child_index: child_index(right_children[branch_index])?,
+			weight: internal_counts
+				.as_ref()
+				.map(|counts| counts[branch_index])
+				.unwrap_or(0.0),
+		});
+	}
+	for (leaf_index, value) in leaf_values.iter().enumerate() {
+		nodes.push(Node::Leaf {
+			value: value * leaf_scale,
+			weight: leaf_counts
+				.as_ref()
+				.map(|counts| counts[leaf_index])
+				.unwrap_or(0.0),
+		});
+	}
+	Ok(nodes)
+}
+
+/// Parse the counts of examples that reached each node, or the sum of their hessians if the counts are not present. Older models may have neither.
+fn parse_weights(
+	section: &HashMap<&str, &str>,
+	count_key: &str,
+	weight_key: &str,
+) -> Result<Option<Vec<f64>>> {
+	if section.contains_key(count_key) {
+		Ok(Some(parse_values(section, count_key)?))
+	} else if section.contains_key(weight_key) {
+		Ok(Some(parse_values(section, weight_key)?))
+	} else {
+		Ok(None)
+	}
+}
+
+fn parse_value<T: FromStr>(section: &HashMap<&str, &str>, key: &str) -> Result<T> {
+	let value = section
+		.get(key)
+		.ok_or_else(|| anyhow!("The model is missing the field \"{}\".", key))?;
+	value
+		.trim()
+		.parse()
+		.map_err(|_| anyhow!("Failed to parse the field \"{}\".", key))
+}
+
+fn parse_values<T: FromStr>(section: &HashMap<&str, &str>, key: &str) -> Result<Vec<T>> {
+	let values = section
+		.get(key)
+		.ok_or_else(|| anyhow!("The model is missing the field \"{}\".", key))?;
+	values
+		.split_whitespace()
+		.map(|value| {
+			value
+				.parse()
+				.map_err(|_| anyhow!("Failed to parse the field \"{}\".", key))
+		})
+		.collect()
+}
@@ -0,0 +1,245 @@
+/*!
+This module reads XGBoost models saved in the JSON format by `Booster.save_model`.
+*/
+
+use crate::ensemble::{next_down, Ensemble, Node, Objective, Split, Tree};
+use anyhow::{anyhow, bail, Context, Result};
+use num::ToPrimitive;
+
+#[derive(serde::Deserialize)]
+struct Model {
+	learner: Learner,
+}
+
+#[derive(serde::Deserialize)]
+struct Learner {
+	learner_model_param: LearnerModelParam,
+	gradient_booster: GradientBooster,
+	objective: LearnerObjective,
+}
+
+#[derive(serde::Deserialize)]
+struct LearnerModelParam {
+	base_score: String,
+	num_class: String,
+	num_feature: String,
+}
+
+#[derive(serde::Deserialize)]
+struct LearnerObjective {
+	name: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "name")]
+enum GradientBooster {
+	#[serde(rename = "gbtree")]
+	GbTree(GbTree),
+	#[serde(rename = "dart")]
+	Dart(Dart),
+}
+
+#[derive(serde::Deserialize)]
+struct GbTree {
+	model: GbTreeModel,
+}
+
+#[derive(serde::Deserialize)]
+struct Dart {
+	gbtree: GbTree,
+	/// This is the weight each tree's values are multiplied by.
+	weight_drop: Vec<f64>,
+}
+
+#[derive(serde::Deserialize)]
+struct GbTreeModel {
+	trees: Vec<XgBoostTree>,
+	/// This is the class of each tree.
+	tree_info: Vec<usize>,
+}
+
+#[derive(serde::Deserialize)]
+struct XgBoostTree {
+	left_children: Vec<i64>,
+	right_children: Vec<i64>,
+	split_indices: Vec<usize>,
+	/// This is the split condition for branches and the value for leaves.
+	split_conditions: Vec<f32>,
+	default_left: Vec<Flag>,
+	#[serde(default)]
+	split_type: Vec<u8>,
+	#[serde(default)]
+	sum_hessian: Vec<f64>,
+	#[serde(default)]
+	categories: Vec<usize>,
+	#[serde(default)]
+	categories_nodes: Vec<usize>,
+	#[serde(default)]
+	categories_segments: Vec<usize>,
+	#[serde(default)]
+	categories_sizes: Vec<usize>,
+}
+
+/// Boolean arrays are written as numbers by some versions of XGBoost and as booleans by others.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum Flag {
+	Bool(bool),
+	Number(u8),
+}
+
+impl Flag {
+	fn value(&self) -> bool {
+		match self {
+			Flag::Bool(value) => *value,
+			Flag::Number(value) => *value != 0,
+		}
+	}
+}
+
+const SPLIT_TYPE_CATEGORICAL: u8 = 1;
+
+pub fn read(model: &[u8]) -> Result<Ensemble> {
+	let model: Model = serde_json::from_slice(model)
+		.context("Failed to parse the model as an XGBoost model in the JSON format.")?;
+	let learner = model.learner;
+	let n_features = parse_param::<usize>(&learner.learner_model_param.num_feature)?;
+	let n_classes = parse_param::<usize>(&learner.learner_model_param.num_class)?;
+	// Newer versions of XGBoost write the base score as an array with one value.
+	let base_score = parse_param::<f64>(
+		learner
+			.learner_model_param
+			.base_score
+			.trim_matches(&['[', ']'][..]),
+	)?;
+	let (objective, bias) = match learner.objective.name.as_str() {
+		"reg:squarederror" | "reg:linear" | "reg:absoluteerror" | "reg:pseudohubererror" => {
+			(Objective::Regression, base_score)
+		}
+		// The base score of a binary classifier is a probability, which is converted to a logit.
+		"binary:logistic" => (
+			Objective::BinaryClassification,
+			(base_score / (1.0 - base_score)).ln(),
+		),
+		"multi:softprob" | "multi:softmax" => (
+			Objective::MulticlassClassification { n_classes },
+			base_score,
+		),
+		name => bail!("The XGBoost objective \"{}\" is not supported.", name),
+	};
+	let (gb_tree_model, weight_drop) = match learner.gradient_booster {
+		GradientBooster::GbTree(gb_tree) => (gb_tree.model, None),
+		GradientBooster::Dart(dart) => (dart.gbtree.model, Some(dart.weight_drop)),
+	};
+	if gb_tree_model.tree_info.len() != gb_tree_model.trees.len() {
+		bail!("The model's tree_info does not match its number of trees.");
+	}
+	let trees = gb_tree_model
+		.trees
+		.iter()
+		.zip(gb_tree_model.tree_info.iter())
+		.enumerate()
+		.map(|(tree_index, (tree, output))| {
+			if *output >= objective.n_outputs() {
+				bail!("Tree {} has an invalid class {}.", tree_index, output);
+			}
+			let weight = match &weight_drop {
+				Some(weight_drop) => *weight_drop
+					.get(tree_index)
+					.ok_or_else(|| anyhow!("Tree {} does not have a weight.", tree_index))?,
+				None => 1.0,
+			};
+			let nodes = read_tree(tree, weight)
+				.with_context(|| format!("Failed to read tree {}.", tree_index))?;
+			Ok(Tree {
+				output: *output,
+				nodes,
+			})
+		})
+		.collect::<Result<Vec<_>>>()?;
+	Ok(Ensemble {
+		objective,
+		n_features,
+		biases: vec![bias.to_f32().unwrap(); objective.n_outputs()],
+		trees,
+	})
+}
+
+fn read_tree(tree: &XgBoostTree, weight: f64) -> Result<Vec<Node>> {
+	let n_nodes = tree.left_children.len();
+	let arrays_have_n_nodes = [
+		tree.right_children.len(),
+		tree.split_indices.len(),
+		tree.split_conditions.len(),
+		tree.default_left.len(),
+	]
+	.iter()
+	.all(|len| *len == n_nodes);
+	if n_nodes == 0 || !arrays_have_n_nodes {
+		bail!("The tree's arrays do not have the same length.");
+	}
+	let child_index = |child: i64| -> Result<usize> {
+		child
+			.to_usize()
+			.filter(|child| *child < n_nodes)
+			.ok_or_else(|| anyhow!("The tree has an invalid child index {}.", child))
+	};
+	(0..n_nodes)
+		.map(|node_index| {
+			let node_weight = tree.sum_hessian.get(node_index).copied().unwrap_or(0.0);
+			// Leaves have no children, and their split condition is their value.
+			if tree.left_children[node_index] == -1 {
+				return Ok(Node::Leaf {
+					value: f64::from(tree.split_conditions[node_index]) * weight,
+					weight: node_weight,
+				});
+			}
+			let feature_index = tree.split_indices[node_index];
+			let missing_values_left = tree.default_left[node_index].value();
+			let is_categorical =
+				tree.split_type.get(node_index).copied().unwrap_or(0) == SPLIT_TYPE_CATEGORICAL;
+			let split = if is_categorical {
+				// The categories listed for a categorical split are sent right.
+				let position = tree
+					.categories_nodes
+					.iter()
+					.position(|categories_node| *categories_node == node_index)
+					.ok_or_else(|| {
+						anyhow!("The tree has a categorical split without categories.")
+					})?;
+				let categories = tree
+					.categories_segments
+					.get(position)
+					.zip(tree.categories_sizes.get(position))
+					.and_then(|(start, size)| tree.categories.get(*start..start + size))
+					.ok_or_else(|| anyhow!("The tree has an invalid categorical split."))?
+					.to_owned();
+				Split::Categorical {
+					feature_index,
+					categories,
+					categories_left: false,
+					missing_values_left,
+				}
+			} else {
+				// XGBoost sends values less than the split condition left.
+				Split::Numerical {
+					feature_index,
+					threshold: next_down(tree.split_conditions[node_index]),
+					missing_values_left,
+				}
+			};
+			Ok(Node::Branch {
+				split,
+				left_child_index: child_index(tree.left_children[node_index])?,
+				right_child_index: child_index(tree.right_children[node_index])?,
+				weight: node_weight,
+			})
+		})
+		.collect()
+}
+
+fn parse_param<T: std::str::FromStr>(value: &str) -> Result<T> {
+	value
+		.parse()
+		.map_err(|_| anyhow!("Failed to parse the model parameter \"{}\".", value))
+}
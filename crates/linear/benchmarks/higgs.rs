@@ -74,6 +74,8 @@ fn main() {
 						source_column_name: column.name().clone().unwrap(),
 						mean: mean_variance.mean,
 						variance: mean_variance.variance,
+						impute: None,
+						missing_indicator: false,
 					},
 				)
 			}
@@ -5,8 +5,10 @@ pub use self::{
 	regressor::Regressor,
 };
 use modelfox_progress_counter::ProgressCounter;
+use modelfox_zip::zip;
 use ndarray::prelude::*;
 use num::ToPrimitive;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 mod binary_classifier;
 mod multiclass_classifier;
@@ -21,14 +23,20 @@ pub struct TrainOptions {
 	pub compute_losses: bool,
 	/// Specify options for early stopping. If the value is `Some`, early stopping will be enabled. If it is `None`, early stopping will be disabled.
 	pub early_stopping_options: Option<EarlyStoppingOptions>,
+	/// This is the L1 regularization value to use when updating the model parameters. L1 regularization is applied with a proximal update after each step, which sets weights that are close to zero to exactly zero. Set both `l1_regularization` and `l2_regularization` to train with elastic net regularization.
+	pub l1_regularization: f32,
 	/// This is the L2 regularization value to use when updating the model parameters.
 	pub l2_regularization: f32,
 	/// This is the learning rate to use when updating the model parameters.
 	pub learning_rate: f32,
+	/// This is the schedule used to decay the learning rate after each epoch.
+	pub learning_rate_schedule: LearningRateSchedule,
 	/// This is the maximum number of epochs to train.
 	pub max_epochs: usize,
 	/// This is the number of examples to use for each batch of training.
 	pub n_examples_per_batch: usize,
+	/// This is the optimizer used to update the model parameters from their gradients.
+	pub optimizer: Optimizer,
 }
 
 impl Default for TrainOptions {
@@ -36,14 +44,59 @@ impl Default for TrainOptions {
 		TrainOptions {
 			compute_losses: false,
 			early_stopping_options: None,
+			l1_regularization: 0.0,
 			l2_regularization: 0.0,
 			learning_rate: 0.1,
+			learning_rate_schedule: LearningRateSchedule::Constant,
 			max_epochs: 100,
 			n_examples_per_batch: 32,
+			optimizer: Optimizer::Sgd,
 		}
 	}
 }
 
+impl TrainOptions {
+	/// Compute the learning rate for the epoch with index `epoch`.
+	pub fn learning_rate_for_epoch(&self, epoch: usize) -> f32 {
+		let epoch = epoch.to_f32().unwrap();
+		match self.learning_rate_schedule {
+			LearningRateSchedule::Constant => self.learning_rate,
+			LearningRateSchedule::InverseTimeDecay { decay_rate } => {
+				self.learning_rate / (1.0 + decay_rate * epoch)
+			}
+			LearningRateSchedule::ExponentialDecay { decay_rate } => {
+				self.learning_rate * decay_rate.powf(epoch)
+			}
+		}
+	}
+}
+
+/// This enum specifies the optimizer used to update the model parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Optimizer {
+	/// Plain minibatch stochastic gradient descent.
+	Sgd,
+	/// Stochastic gradient descent with momentum. Each step is `momentum` times the previous step plus the current gradient.
+	Momentum { momentum: f32 },
+	/// The Adam optimizer, which scales each parameter's step by running estimates of the first and second moments of its gradient.
+	Adam {
+		beta_1: f32,
+		beta_2: f32,
+		epsilon: f32,
+	},
+}
+
+/// This enum specifies how the learning rate changes over the course of training.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LearningRateSchedule {
+	/// Use `learning_rate` for every epoch.
+	Constant,
+	/// Use `learning_rate / (1 + decay_rate * epoch)`.
+	InverseTimeDecay { decay_rate: f32 },
+	/// Use `learning_rate * decay_rate ^ epoch`.
+	ExponentialDecay { decay_rate: f32 },
+}
+
 /// The parameters in this struct control how to determine whether training should stop early after each round or epoch.
 #[derive(Clone, Debug)]
 pub struct EarlyStoppingOptions {
@@ -55,6 +108,17 @@ pub struct EarlyStoppingOptions {
 	pub min_decrease_in_loss_for_significant_change: f32,
 }
 
+/// This enum controls how weights are stored when a model is written with `to_writer_with_weight_encoding`. Encodings other than `F32` make the model smaller at the cost of the precision of its predictions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeightEncoding {
+	/// Store each weight as a 32 bit float.
+	F32,
+	/// Store each weight as a 16 bit float.
+	F16,
+	/// Store each weight as an 8 bit integer, which is multiplied by a single scale stored with the weights.
+	I8,
+}
+
 pub struct Progress<'a> {
 	pub kill_chip: &'a modelfox_kill_chip::KillChip,
 	pub handle_progress_event: &'a mut dyn FnMut(TrainProgressEvent),
@@ -67,6 +131,91 @@ pub enum TrainProgressEvent {
 	TrainDone,
 }
 
+/**
+The `OptimizerState` holds the per parameter state of the optimizer, such as the momentum or the moment estimates for Adam, and applies updates to the model parameters. Parameters are identified by an index that each model assigns to its weights and biases.
+*/
+struct OptimizerState {
+	optimizer: Optimizer,
+	l1_regularization: f32,
+	l2_regularization: f32,
+	first_moments: Vec<f32>,
+	second_moments: Vec<f32>,
+	n_steps: AtomicUsize,
+}
+
+impl OptimizerState {
+	pub fn new(n_parameters: usize, train_options: &TrainOptions) -> OptimizerState {
+		let (n_first_moments, n_second_moments) = match train_options.optimizer {
+			Optimizer::Sgd => (0, 0),
+			Optimizer::Momentum { .. } => (n_parameters, 0),
+			Optimizer::Adam { .. } => (n_parameters, n_parameters),
+		};
+		OptimizerState {
+			optimizer: train_options.optimizer,
+			l1_regularization: train_options.l1_regularization,
+			l2_regularization: train_options.l2_regularization,
+			first_moments: vec![0.0; n_first_moments],
+			second_moments: vec![0.0; n_second_moments],
+			n_steps: AtomicUsize::new(0),
+		}
+	}
+
+	/// Return true if the optimizer keeps no per parameter state, so batches can be trained in parallel, racing to update the parameters. Momentum and Adam update their moment estimates with every batch, so their batches must be trained serially for the estimates to be correct and training to be deterministic.
+	pub fn supports_parallel_batches(&self) -> bool {
+		matches!(self.optimizer, Optimizer::Sgd)
+	}
+
+	/// Call this function once for each batch before updating the parameters. It returns the step number to pass to `update`.
+	pub fn step(&self) -> usize {
+		self.n_steps.fetch_add(1, Ordering::Relaxed) + 1
+	}
+
+	/// Update the parameter with index `index` given its `gradient`. Weights are regularized, and biases should be updated with `regularize` set to false.
+	pub fn update(
+		&mut self,
+		index: usize,
+		parameter: &mut f32,
+		gradient: f32,
+		learning_rate: f32,
+		step: usize,
+		regularize: bool,
+	) {
+		let gradient = if regularize {
+			gradient + self.l2_regularization * *parameter
+		} else {
+			gradient
+		};
+		let delta = match self.optimizer {
+			Optimizer::Sgd => learning_rate * gradient,
+			Optimizer::Momentum { momentum } => {
+				let velocity = &mut self.first_moments[index];
+				*velocity = momentum * *velocity + gradient;
+				learning_rate * *velocity
+			}
+			Optimizer::Adam {
+				beta_1,
+				beta_2,
+				epsilon,
+			} => {
+				let first_moment = &mut self.first_moments[index];
+				*first_moment = beta_1 * *first_moment + (1.0 - beta_1) * gradient;
+				let second_moment = &mut self.second_moments[index];
+				*second_moment = beta_2 * *second_moment + (1.0 - beta_2) * gradient * gradient;
+				let step = step.to_i32().unwrap_or(i32::MAX);
+				let first_moment = self.first_moments[index] / (1.0 - beta_1.powi(step));
+				let second_moment = self.second_moments[index] / (1.0 - beta_2.powi(step));
+				learning_rate * first_moment / (second_moment.sqrt() + epsilon)
+			}
+		};
+		*parameter -= delta;
+		// Apply the proximal operator for L1 regularization, which shrinks the parameter toward zero and truncates it at zero.
+		if regularize && self.l1_regularization > 0.0 {
+			let threshold = learning_rate * self.l1_regularization;
+			*parameter = parameter.signum() * (parameter.abs() - threshold).max(0.0);
+		}
+	}
+}
+
 /// This function splits the `features` and `labels` arrays into training and early stopping arrays, where the size of the early stopping stopping array will be `features.len() * early_stopping_fraction`.
 fn train_early_stopping_split<'features, 'labels, Label>(
 	features: ArrayView2<'features, f32>,
@@ -91,6 +240,71 @@ fn train_early_stopping_split<'features, 'labels, Label>(
 	)
 }
 
+/// This is a chunk of features and labels read by the `train_out_of_core` functions.
+pub type TrainChunk<Label> = (Array2<f32>, Array1<Label>);
+
+/**
+This function runs the epochs for `Regressor::train_out_of_core`, `BinaryClassifier::train_out_of_core`, and `MulticlassClassifier::train_out_of_core`. `feature_chunks` is called at the start of each epoch and must return the same chunks in the same order each time. `train_chunk` trains the model on a single chunk with the learning rate for the current epoch and returns the loss on that chunk. The mean of each feature is computed during the first epoch, and it is returned along with the loss for each epoch if `compute_losses` is enabled.
+*/
+fn train_out_of_core<Label, E, I>(
+	n_features: usize,
+	mut feature_chunks: impl FnMut() -> I,
+	train_options: &TrainOptions,
+	progress: Progress,
+	mut train_chunk: impl FnMut(ArrayView2<f32>, ArrayView1<Label>, f32) -> f32,
+) -> Result<(Vec<f32>, Option<Vec<f32>>), E>
+where
+	I: Iterator<Item = Result<TrainChunk<Label>, E>>,
+{
+	let progress_counter = ProgressCounter::new(train_options.max_epochs.to_u64().unwrap());
+	(progress.handle_progress_event)(TrainProgressEvent::Train(progress_counter.clone()));
+	let mut sums = vec![0.0f64; n_features];
+	let mut n_examples = 0;
+	let mut losses = if train_options.compute_losses {
+		Some(Vec::new())
+	} else {
+		None
+	};
+	for epoch in 0..train_options.max_epochs {
+		progress_counter.inc(1);
+		let learning_rate = train_options.learning_rate_for_epoch(epoch);
+		let mut loss_sum = 0.0f64;
+		let mut n_examples_in_epoch = 0;
+		for chunk in feature_chunks() {
+			let (features, labels) = chunk?;
+			if epoch == 0 {
+				for (sum, column) in zip!(sums.iter_mut(), features.axis_iter(Axis(1))) {
+					*sum += column.iter().map(|value| f64::from(*value)).sum::<f64>();
+				}
+				n_examples += features.nrows();
+			}
+			let loss = train_chunk(features.view(), labels.view(), learning_rate);
+			loss_sum += f64::from(loss) * features.nrows().to_f64().unwrap();
+			n_examples_in_epoch += features.nrows();
+			if progress.kill_chip.is_activated() {
+				break;
+			}
+		}
+		if let Some(losses) = &mut losses {
+			losses.push(
+				(loss_sum / n_examples_in_epoch.to_f64().unwrap())
+					.to_f32()
+					.unwrap(),
+			);
+		}
+		// Check if we should stop training.
+		if progress.kill_chip.is_activated() {
+			break;
+		}
+	}
+	(progress.handle_progress_event)(TrainProgressEvent::TrainDone);
+	let means = sums
+		.iter()
+		.map(|sum| (sum / n_examples.to_f64().unwrap()).to_f32().unwrap())
+		.collect();
+	Ok((means, losses))
+}
+
 /**
 The `EarlyStoppingMonitor` keeps track of the values of an early stopping metric for each epoch, and if enough epochs have passed without a significant improvement in the metric, the `update()` function will return `true` to indicate that training should be stopped.
 */
@@ -131,3 +345,171 @@ impl EarlyStoppingMonitor {
 		result
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use modelfox_kill_chip::KillChip;
+	use modelfox_table::{EnumTableColumn, NumberTableColumn};
+	use std::num::NonZeroUsize;
+
+	/// Create `n_examples` examples with two features. The first feature is spread evenly over [-1, 1], and the second is a pattern that is uncorrelated with the first.
+	fn features(n_examples: usize) -> Array2<f32> {
+		Array2::from_shape_fn((n_examples, 2), |(i, j)| {
+			let i = i.to_f32().unwrap();
+			let n = n_examples.to_f32().unwrap();
+			match j {
+				0 => 2.0 * i / n - 1.0,
+				_ => [0.5, -0.5, -0.5, 0.5][i.to_usize().unwrap() % 4],
+			}
+		})
+	}
+
+	#[test]
+	fn test_warm_start_starts_from_init_model() {
+		let features = features(256);
+		let labels = features.column(0).map(|value| 3.0 * value + 1.0).to_vec();
+		let labels = NumberTableColumn::new(None, labels);
+		let init_model = Regressor::train(
+			features.view(),
+			labels.view(),
+			&TrainOptions::default(),
+			Progress {
+				kill_chip: &KillChip::default(),
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		// With no epochs, the model makes the same predictions as the init model.
+		let model = Regressor::train_warm_start(
+			&init_model,
+			features.view(),
+			labels.view(),
+			&TrainOptions {
+				max_epochs: 0,
+				..Default::default()
+			},
+			Progress {
+				kill_chip: &KillChip::default(),
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		assert_eq!(model.weights, init_model.weights);
+		assert_eq!(model.bias, init_model.bias);
+		let mut init_predictions = Array1::zeros(features.nrows());
+		init_model.predict(features.view(), init_predictions.view_mut());
+		let mut predictions = Array1::zeros(features.nrows());
+		model.predict(features.view(), predictions.view_mut());
+		assert_eq!(predictions, init_predictions);
+		// One epoch from the init model's weights gets closer to the labels than one epoch from zeros.
+		let train_options = TrainOptions {
+			max_epochs: 1,
+			..Default::default()
+		};
+		let model = Regressor::train_warm_start(
+			&init_model,
+			features.view(),
+			labels.view(),
+			&train_options,
+			Progress {
+				kill_chip: &KillChip::default(),
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		let cold_model = Regressor::train(
+			features.view(),
+			labels.view(),
+			&train_options,
+			Progress {
+				kill_chip: &KillChip::default(),
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		assert!((model.weights[0] - 3.0).abs() < (cold_model.weights[0] - 3.0).abs());
+		assert!((model.bias - 1.0).abs() < (cold_model.bias - 1.0).abs());
+	}
+
+	fn train_binary_classifier(optimizer: Optimizer, learning_rate: f32) -> BinaryClassifier {
+		let features = features(256);
+		let labels = features
+			.column(0)
+			.iter()
+			.map(|value| NonZeroUsize::new(if *value > 0.0 { 2 } else { 1 }))
+			.collect();
+		let labels =
+			EnumTableColumn::new(None, vec!["false".to_owned(), "true".to_owned()], labels);
+		let train_options = TrainOptions {
+			learning_rate,
+			optimizer,
+			..Default::default()
+		};
+		BinaryClassifier::train(
+			features.view(),
+			labels.view(),
+			&train_options,
+			Progress {
+				kill_chip: &KillChip::default(),
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model
+	}
+
+	fn accuracy(model: &BinaryClassifier) -> f32 {
+		let features = features(256);
+		let mut probabilities = Array1::zeros(features.nrows());
+		model.predict(features.view(), probabilities.view_mut());
+		let n_correct = zip!(features.column(0), probabilities.iter())
+			.filter(|(value, probability)| (**value > 0.0) == (**probability > 0.5))
+			.count();
+		n_correct.to_f32().unwrap() / features.nrows().to_f32().unwrap()
+	}
+
+	#[test]
+	fn test_l1_regularization_zeroes_irrelevant_weights() {
+		let features = features(256);
+		let labels = features.column(0).map(|value| 3.0 * value).to_vec();
+		let labels = NumberTableColumn::new(None, labels);
+		let train_options = TrainOptions {
+			l1_regularization: 0.05,
+			..Default::default()
+		};
+		let model = Regressor::train(
+			features.view(),
+			labels.view(),
+			&train_options,
+			Progress {
+				kill_chip: &KillChip::default(),
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		assert!(model.weights[0] > 2.0);
+		assert_eq!(model.weights[1], 0.0);
+		assert_eq!(model.n_nonzero_weights(), 1);
+	}
+
+	#[test]
+	fn test_momentum_converges() {
+		let model = train_binary_classifier(Optimizer::Momentum { momentum: 0.9 }, 0.1);
+		assert!(accuracy(&model) > 0.95);
+	}
+
+	#[test]
+	fn test_adam_converges() {
+		let optimizer = Optimizer::Adam {
+			beta_1: 0.9,
+			beta_2: 0.999,
+			epsilon: 1e-8,
+		};
+		let model = train_binary_classifier(optimizer, 0.05);
+		assert!(accuracy(&model) > 0.95);
+		// Batches are trained serially with Adam, so training is deterministic.
+		let other_model = train_binary_classifier(optimizer, 0.05);
+		assert_eq!(model.weights, other_model.weights);
+		assert_eq!(model.bias, other_model.bias);
+	}
+}
@@ -2,7 +2,8 @@ use crate::Progress;
 
 use super::{
 	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
-	train_early_stopping_split, EarlyStoppingMonitor, TrainOptions, TrainProgressEvent,
+	train_early_stopping_split, train_out_of_core, EarlyStoppingMonitor, OptimizerState,
+	TrainChunk, TrainOptions, TrainProgressEvent,
 };
 use modelfox_metrics::{CrossEntropy, CrossEntropyInput};
 use modelfox_progress_counter::ProgressCounter;
@@ -41,6 +42,33 @@ impl MulticlassClassifier {
 		labels: EnumTableColumnView,
 		train_options: &TrainOptions,
 		progress: Progress,
+	) -> MulticlassClassifierTrainOutput {
+		MulticlassClassifier::train_with_init_model(features, labels, None, train_options, progress)
+	}
+
+	/// Continue training `init_model` on new data. Training starts from the weights and biases of `init_model` instead of zeros. `features` must have the same columns as the features `init_model` was trained on.
+	pub fn train_warm_start(
+		init_model: &MulticlassClassifier,
+		features: ArrayView2<f32>,
+		labels: EnumTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> MulticlassClassifierTrainOutput {
+		MulticlassClassifier::train_with_init_model(
+			features,
+			labels,
+			Some(init_model),
+			train_options,
+			progress,
+		)
+	}
+
+	fn train_with_init_model(
+		features: ArrayView2<f32>,
+		labels: EnumTableColumnView,
+		init_model: Option<&MulticlassClassifier>,
+		train_options: &TrainOptions,
+		progress: Progress,
 	) -> MulticlassClassifierTrainOutput {
 		let n_classes = labels.variants().len();
 		let n_features = features.ncols();
@@ -58,10 +86,20 @@ impl MulticlassClassifier {
 			.axis_iter(Axis(1))
 			.map(|column| column.mean().unwrap())
 			.collect();
-		let mut model = MulticlassClassifier {
-			biases: <Array1<f32>>::zeros(n_classes),
-			weights: <Array2<f32>>::zeros((n_features, n_classes)),
-			means,
+		let mut model = match init_model {
+			Some(init_model) => {
+				assert_eq!(init_model.weights.dim(), (n_features, n_classes));
+				MulticlassClassifier {
+					biases: init_model.biases.clone(),
+					weights: init_model.weights.clone(),
+					means,
+				}
+			}
+			None => MulticlassClassifier {
+				biases: <Array1<f32>>::zeros(n_classes),
+				weights: <Array2<f32>>::zeros((n_features, n_classes)),
+				means,
+			},
 		};
 		let mut early_stopping_monitor =
 			train_options
@@ -82,30 +120,49 @@ impl MulticlassClassifier {
 			None
 		};
 		let kill_chip = progress.kill_chip;
-		for _ in 0..train_options.max_epochs {
+		let mut optimizer_state = OptimizerState::new((n_features + 1) * n_classes, train_options);
+		for epoch in 0..train_options.max_epochs {
 			progress_counter.inc(1);
 			let n_examples_per_batch = train_options.n_examples_per_batch;
-			struct MulticlassClassifierPtr(*mut MulticlassClassifier);
+			let learning_rate = train_options.learning_rate_for_epoch(epoch);
+			struct MulticlassClassifierPtr(*mut MulticlassClassifier, *mut OptimizerState);
 			unsafe impl Send for MulticlassClassifierPtr {}
 			unsafe impl Sync for MulticlassClassifierPtr {}
-			let model_ptr = MulticlassClassifierPtr(&mut model);
-			pzip!(
-				features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
-				labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
-				probabilities_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch),
-			)
-			.for_each(|(features, labels, probabilities)| {
+			let is_parallel = optimizer_state.supports_parallel_batches();
+			let model_ptr = MulticlassClassifierPtr(&mut model, &mut optimizer_state);
+			let train_batch = |(features, labels, probabilities): (
+				ArrayView2<f32>,
+				ArrayView1<Option<NonZeroUsize>>,
+				ArrayViewMut2<f32>,
+			)| {
 				let model_ptr = &model_ptr;
 				let model = unsafe { &mut *model_ptr.0 };
+				let optimizer_state = unsafe { &mut *model_ptr.1 };
 				MulticlassClassifier::train_batch(
 					model,
 					features,
 					labels,
 					probabilities,
-					train_options,
+					optimizer_state,
+					learning_rate,
 					kill_chip,
 				);
-			});
+			};
+			if is_parallel {
+				pzip!(
+					features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+					labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+					probabilities_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch)
+				)
+				.for_each(train_batch);
+			} else {
+				zip!(
+					features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+					labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+					probabilities_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch)
+				)
+				.for_each(train_batch);
+			}
 			if let Some(losses) = &mut losses {
 				let loss =
 					MulticlassClassifier::compute_loss(probabilities_buffer.view(), labels_train);
@@ -138,6 +195,92 @@ impl MulticlassClassifier {
 		}
 	}
 
+	/// Train a linear multiclass classifier on features that are too large to hold in memory. See `train_out_of_core` in `lib.rs` for how `feature_chunks` is used. Early stopping is not supported.
+	pub fn train_out_of_core<E, I>(
+		n_features: usize,
+		n_classes: usize,
+		feature_chunks: impl FnMut() -> I,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> Result<MulticlassClassifierTrainOutput, E>
+	where
+		I: Iterator<Item = Result<TrainChunk<Option<NonZeroUsize>>, E>>,
+	{
+		let mut model = MulticlassClassifier {
+			biases: <Array1<f32>>::zeros(n_classes),
+			weights: <Array2<f32>>::zeros((n_features, n_classes)),
+			means: Vec::new(),
+		};
+		let kill_chip = progress.kill_chip;
+		let mut optimizer_state = OptimizerState::new((n_features + 1) * n_classes, train_options);
+		let (means, losses) = train_out_of_core(
+			n_features,
+			feature_chunks,
+			train_options,
+			progress,
+			|features_train, labels_train, learning_rate| {
+				let n_examples_per_batch = train_options.n_examples_per_batch;
+				let mut probabilities_buffer: Array2<f32> =
+					Array2::zeros((labels_train.len(), n_classes));
+				struct MulticlassClassifierPtr(*mut MulticlassClassifier, *mut OptimizerState);
+				unsafe impl Send for MulticlassClassifierPtr {}
+				unsafe impl Sync for MulticlassClassifierPtr {}
+				let is_parallel = optimizer_state.supports_parallel_batches();
+				let model_ptr = MulticlassClassifierPtr(&mut model, &mut optimizer_state);
+				let train_batch = |(features, labels, probabilities): (
+					ArrayView2<f32>,
+					ArrayView1<Option<NonZeroUsize>>,
+					ArrayViewMut2<f32>,
+				)| {
+					let model_ptr = &model_ptr;
+					let model = unsafe { &mut *model_ptr.0 };
+					let optimizer_state = unsafe { &mut *model_ptr.1 };
+					MulticlassClassifier::train_batch(
+						model,
+						features,
+						labels,
+						probabilities,
+						optimizer_state,
+						learning_rate,
+						kill_chip,
+					);
+				};
+				if is_parallel {
+					pzip!(
+						features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+						labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+						probabilities_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch)
+					)
+					.for_each(train_batch);
+				} else {
+					zip!(
+						features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+						labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+						probabilities_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch)
+					)
+					.for_each(train_batch);
+				}
+				if train_options.compute_losses {
+					MulticlassClassifier::compute_loss(probabilities_buffer.view(), labels_train)
+				} else {
+					0.0
+				}
+			},
+		)?;
+		model.means = means;
+		let feature_importances = MulticlassClassifier::compute_feature_importances(&model);
+		Ok(MulticlassClassifierTrainOutput {
+			model,
+			losses,
+			feature_importances: Some(feature_importances),
+		})
+	}
+
+	/// Compute the number of weights that are not zero. Training with `l1_regularization` sets the weights of uninformative features to exactly zero.
+	pub fn n_nonzero_weights(&self) -> usize {
+		self.weights.iter().filter(|weight| **weight != 0.0).count()
+	}
+
 	fn compute_feature_importances(model: &MulticlassClassifier) -> Vec<f32> {
 		// Compute the absolute value of each of the weights.
 		let mut feature_importances = model
@@ -163,13 +306,13 @@ impl MulticlassClassifier {
 		features: ArrayView2<f32>,
 		labels: ArrayView1<Option<NonZeroUsize>>,
 		mut probabilities: ArrayViewMut2<f32>,
-		train_options: &TrainOptions,
+		optimizer_state: &mut OptimizerState,
+		learning_rate: f32,
 		kill_chip: &modelfox_kill_chip::KillChip,
 	) {
 		if kill_chip.is_activated() {
 			return;
 		}
-		let learning_rate = train_options.learning_rate;
 		let n_classes = self.weights.ncols();
 		let mut logits = features.dot(&self.weights) + &self.biases;
 		softmax(logits.view_mut());
@@ -187,22 +330,34 @@ impl MulticlassClassifier {
 			}
 		}
 		let py = predictions;
+		let step = optimizer_state.step();
+		let n_weights = self.weights.len();
 		for class_index in 0..n_classes {
 			let weight_gradients = (&features * &py.column(class_index).insert_axis(Axis(1)))
 				.mean_axis(Axis(0))
 				.unwrap();
-			for (weight, weight_gradient) in zip!(
+			for (feature_index, (weight, weight_gradient)) in zip!(
 				self.weights.column_mut(class_index),
 				weight_gradients.iter()
-			) {
-				*weight += -learning_rate * weight_gradient
+			)
+			.enumerate()
+			{
+				let index = feature_index * n_classes + class_index;
+				optimizer_state.update(index, weight, *weight_gradient, learning_rate, step, true);
 			}
 			let bias_gradients = py
 				.column(class_index)
 				.insert_axis(Axis(1))
 				.mean_axis(Axis(0))
 				.unwrap();
-			self.biases[class_index] += -learning_rate * bias_gradients[0];
+			optimizer_state.update(
+				n_weights + class_index,
+				&mut self.biases[class_index],
+				bias_gradients[0],
+				learning_rate,
+				step,
+				false,
+			);
 		}
 	}
 
@@ -306,7 +461,15 @@ impl MulticlassClassifier {
 		&self,
 		writer: &mut buffalo::Writer,
 	) -> buffalo::Position<crate::serialize::MulticlassClassifierWriter> {
-		crate::serialize::serialize_multiclass_classifier(self, writer)
+		self.to_writer_with_weight_encoding(writer, crate::WeightEncoding::F32)
+	}
+
+	pub fn to_writer_with_weight_encoding(
+		&self,
+		writer: &mut buffalo::Writer,
+		weight_encoding: crate::WeightEncoding,
+	) -> buffalo::Position<crate::serialize::MulticlassClassifierWriter> {
+		crate::serialize::serialize_multiclass_classifier(self, writer, weight_encoding)
 	}
 
 	#[must_use]
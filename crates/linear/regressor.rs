@@ -2,7 +2,8 @@ use crate::Progress;
 
 use super::{
 	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
-	train_early_stopping_split, EarlyStoppingMonitor, TrainOptions, TrainProgressEvent,
+	train_early_stopping_split, train_out_of_core, EarlyStoppingMonitor, OptimizerState,
+	TrainChunk, TrainOptions, TrainProgressEvent,
 };
 use modelfox_metrics::MeanSquaredError;
 use modelfox_progress_counter::ProgressCounter;
@@ -40,6 +41,33 @@ impl Regressor {
 		labels: NumberTableColumnView,
 		train_options: &TrainOptions,
 		progress: Progress,
+	) -> RegressorTrainOutput {
+		Regressor::train_with_init_model(features, labels, None, train_options, progress)
+	}
+
+	/// Continue training `init_model` on new data. Training starts from the weights and bias of `init_model` instead of zeros. `features` must have the same columns as the features `init_model` was trained on.
+	pub fn train_warm_start(
+		init_model: &Regressor,
+		features: ArrayView2<f32>,
+		labels: NumberTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> RegressorTrainOutput {
+		Regressor::train_with_init_model(
+			features,
+			labels,
+			Some(init_model),
+			train_options,
+			progress,
+		)
+	}
+
+	fn train_with_init_model(
+		features: ArrayView2<f32>,
+		labels: NumberTableColumnView,
+		init_model: Option<&Regressor>,
+		train_options: &TrainOptions,
+		progress: Progress,
 	) -> RegressorTrainOutput {
 		let n_features = features.ncols();
 		let (features_train, labels_train, features_early_stopping, labels_early_stopping) =
@@ -56,10 +84,20 @@ impl Regressor {
 			.axis_iter(Axis(1))
 			.map(|column| column.mean().unwrap())
 			.collect();
-		let mut model = Regressor {
-			bias: 0.0,
-			weights: <Array1<f32>>::zeros(n_features),
-			means,
+		let mut model = match init_model {
+			Some(init_model) => {
+				assert_eq!(init_model.weights.len(), n_features);
+				Regressor {
+					bias: init_model.bias,
+					weights: init_model.weights.clone(),
+					means,
+				}
+			}
+			None => Regressor {
+				bias: 0.0,
+				weights: <Array1<f32>>::zeros(n_features),
+				means,
+			},
 		};
 		let mut early_stopping_monitor =
 			train_options
@@ -80,30 +118,49 @@ impl Regressor {
 			None
 		};
 		let kill_chip = progress.kill_chip;
-		for _ in 0..train_options.max_epochs {
+		let mut optimizer_state = OptimizerState::new(n_features + 1, train_options);
+		for epoch in 0..train_options.max_epochs {
 			progress_counter.inc(1);
 			let n_examples_per_batch = train_options.n_examples_per_batch;
-			struct RegressorPtr(*mut Regressor);
+			let learning_rate = train_options.learning_rate_for_epoch(epoch);
+			struct RegressorPtr(*mut Regressor, *mut OptimizerState);
 			unsafe impl Send for RegressorPtr {}
 			unsafe impl Sync for RegressorPtr {}
-			let model_ptr = RegressorPtr(&mut model);
-			pzip!(
-				features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
-				labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
-				predictions_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch),
-			)
-			.for_each(|(features, labels, predictions)| {
+			let is_parallel = optimizer_state.supports_parallel_batches();
+			let model_ptr = RegressorPtr(&mut model, &mut optimizer_state);
+			let train_batch = |(features, labels, predictions): (
+				ArrayView2<f32>,
+				ArrayView1<f32>,
+				ArrayViewMut1<f32>,
+			)| {
 				let model_ptr = &model_ptr;
 				let model = unsafe { &mut *model_ptr.0 };
+				let optimizer_state = unsafe { &mut *model_ptr.1 };
 				Regressor::train_batch(
 					model,
 					features,
 					labels,
 					predictions,
-					train_options,
+					optimizer_state,
+					learning_rate,
 					kill_chip,
 				);
-			});
+			};
+			if is_parallel {
+				pzip!(
+					features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+					labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+					predictions_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch)
+				)
+				.for_each(train_batch);
+			} else {
+				zip!(
+					features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+					labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+					predictions_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch)
+				)
+				.for_each(train_batch);
+			}
 			if let Some(losses) = &mut losses {
 				let loss = Regressor::compute_loss(predictions_buffer.view(), labels_train);
 				losses.push(loss);
@@ -134,6 +191,90 @@ impl Regressor {
 		}
 	}
 
+	/// Train a linear regressor on features that are too large to hold in memory. See `train_out_of_core` in `lib.rs` for how `feature_chunks` is used. Early stopping is not supported.
+	pub fn train_out_of_core<E, I>(
+		n_features: usize,
+		feature_chunks: impl FnMut() -> I,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> Result<RegressorTrainOutput, E>
+	where
+		I: Iterator<Item = Result<TrainChunk<f32>, E>>,
+	{
+		let mut model = Regressor {
+			bias: 0.0,
+			weights: <Array1<f32>>::zeros(n_features),
+			means: Vec::new(),
+		};
+		let kill_chip = progress.kill_chip;
+		let mut optimizer_state = OptimizerState::new(n_features + 1, train_options);
+		let (means, losses) = train_out_of_core(
+			n_features,
+			feature_chunks,
+			train_options,
+			progress,
+			|features_train, labels_train, learning_rate| {
+				let n_examples_per_batch = train_options.n_examples_per_batch;
+				let mut predictions_buffer: Array1<f32> = Array1::zeros(labels_train.len());
+				struct RegressorPtr(*mut Regressor, *mut OptimizerState);
+				unsafe impl Send for RegressorPtr {}
+				unsafe impl Sync for RegressorPtr {}
+				let is_parallel = optimizer_state.supports_parallel_batches();
+				let model_ptr = RegressorPtr(&mut model, &mut optimizer_state);
+				let train_batch = |(features, labels, predictions): (
+					ArrayView2<f32>,
+					ArrayView1<f32>,
+					ArrayViewMut1<f32>,
+				)| {
+					let model_ptr = &model_ptr;
+					let model = unsafe { &mut *model_ptr.0 };
+					let optimizer_state = unsafe { &mut *model_ptr.1 };
+					Regressor::train_batch(
+						model,
+						features,
+						labels,
+						predictions,
+						optimizer_state,
+						learning_rate,
+						kill_chip,
+					);
+				};
+				if is_parallel {
+					pzip!(
+						features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+						labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+						predictions_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch)
+					)
+					.for_each(train_batch);
+				} else {
+					zip!(
+						features_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+						labels_train.axis_chunks_iter(Axis(0), n_examples_per_batch),
+						predictions_buffer.axis_chunks_iter_mut(Axis(0), n_examples_per_batch)
+					)
+					.for_each(train_batch);
+				}
+				if train_options.compute_losses {
+					Regressor::compute_loss(predictions_buffer.view(), labels_train)
+				} else {
+					0.0
+				}
+			},
+		)?;
+		model.means = means;
+		let feature_importances = Regressor::compute_feature_importances(&model);
+		Ok(RegressorTrainOutput {
+			model,
+			losses,
+			feature_importances: Some(feature_importances),
+		})
+	}
+
+	/// Compute the number of weights that are not zero. Training with `l1_regularization` sets the weights of uninformative features to exactly zero.
+	pub fn n_nonzero_weights(&self) -> usize {
+		self.weights.iter().filter(|weight| **weight != 0.0).count()
+	}
+
 	fn compute_feature_importances(model: &Regressor) -> Vec<f32> {
 		// Compute the absolute value of each of the weights.
 		let mut feature_importances = model
@@ -154,13 +295,13 @@ impl Regressor {
 		features: ArrayView2<f32>,
 		labels: ArrayView1<f32>,
 		mut predictions: ArrayViewMut1<f32>,
-		train_options: &TrainOptions,
+		optimizer_state: &mut OptimizerState,
+		learning_rate: f32,
 		kill_chip: &modelfox_kill_chip::KillChip,
 	) {
 		if kill_chip.is_activated() {
 			return;
 		}
-		let learning_rate = train_options.learning_rate;
 		let p = features.dot(&self.weights) + self.bias;
 		for (prediction, p) in zip!(predictions.iter_mut(), p.iter()) {
 			*prediction = *p;
@@ -168,10 +309,21 @@ impl Regressor {
 		let py = (p - labels).insert_axis(Axis(1));
 		let weight_gradients = (&features * &py).mean_axis(Axis(0)).unwrap();
 		let bias_gradient = py.mean_axis(Axis(0)).unwrap()[0];
-		for (weight, weight_gradient) in zip!(self.weights.iter_mut(), weight_gradients.iter()) {
-			*weight += -learning_rate * weight_gradient;
+		let step = optimizer_state.step();
+		for (index, (weight, weight_gradient)) in
+			zip!(self.weights.iter_mut(), weight_gradients.iter()).enumerate()
+		{
+			optimizer_state.update(index, weight, *weight_gradient, learning_rate, step, true);
 		}
-		self.bias += -learning_rate * bias_gradient;
+		let bias_index = self.weights.len();
+		optimizer_state.update(
+			bias_index,
+			&mut self.bias,
+			bias_gradient,
+			learning_rate,
+			step,
+			false,
+		);
 	}
 
 	fn compute_loss(predictions: ArrayView1<f32>, labels: ArrayView1<f32>) -> f32 {
@@ -250,7 +402,15 @@ impl Regressor {
 		&self,
 		writer: &mut buffalo::Writer,
 	) -> buffalo::Position<crate::serialize::RegressorWriter> {
-		crate::serialize::serialize_regressor(self, writer)
+		self.to_writer_with_weight_encoding(writer, crate::WeightEncoding::F32)
+	}
+
+	pub fn to_writer_with_weight_encoding(
+		&self,
+		writer: &mut buffalo::Writer,
+		weight_encoding: crate::WeightEncoding,
+	) -> buffalo::Position<crate::serialize::RegressorWriter> {
+		crate::serialize::serialize_regressor(self, writer, weight_encoding)
 	}
 
 	#[must_use]
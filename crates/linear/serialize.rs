@@ -1,14 +1,18 @@
 use ndarray::prelude::*;
+use num::ToPrimitive;
 
 #[derive(Clone, Debug, buffalo::Read, buffalo::Write)]
 #[buffalo(size = "dynamic")]
 pub struct Regressor {
 	#[buffalo(id = 0, required)]
 	pub bias: f32,
+	/// This is empty if the weights are quantized.
 	#[buffalo(id = 1, required)]
 	pub weights: Array1<f32>,
 	#[buffalo(id = 2, required)]
 	pub means: Vec<f32>,
+	#[buffalo(id = 3)]
+	pub quantized_weights: Option<QuantizedWeights>,
 }
 
 #[derive(Clone, Debug, buffalo::Read, buffalo::Write)]
@@ -16,10 +20,13 @@ pub struct Regressor {
 pub struct BinaryClassifier {
 	#[buffalo(id = 0, required)]
 	pub bias: f32,
+	/// This is empty if the weights are quantized.
 	#[buffalo(id = 1, required)]
 	pub weights: Array1<f32>,
 	#[buffalo(id = 2, required)]
 	pub means: Vec<f32>,
+	#[buffalo(id = 3)]
+	pub quantized_weights: Option<QuantizedWeights>,
 }
 
 #[derive(Clone, Debug, buffalo::Read, buffalo::Write)]
@@ -27,33 +34,79 @@ pub struct BinaryClassifier {
 pub struct MulticlassClassifier {
 	#[buffalo(id = 0, required)]
 	pub biases: Array1<f32>,
+	/// This is empty if the weights are quantized.
 	#[buffalo(id = 1, required)]
 	pub weights: Array2<f32>,
 	#[buffalo(id = 2, required)]
 	pub means: Vec<f32>,
+	/// The quantized weights are stored in row major order, with one row for each feature.
+	#[buffalo(id = 3)]
+	pub quantized_weights: Option<QuantizedWeights>,
+}
+
+#[derive(Clone, Debug, buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 8)]
+pub enum QuantizedWeights {
+	#[buffalo(id = 0)]
+	F16(F16Weights),
+	#[buffalo(id = 1)]
+	I8(I8Weights),
+}
+
+#[derive(Clone, Debug, buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct F16Weights {
+	/// These are the bits of 16 bit floats.
+	#[buffalo(id = 0, required)]
+	pub values: Vec<u16>,
+}
+
+#[derive(Clone, Debug, buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct I8Weights {
+	/// Each weight is its value multiplied by this scale.
+	#[buffalo(id = 0, required)]
+	pub scale: f32,
+	#[buffalo(id = 1, required)]
+	pub values: Vec<i8>,
 }
 
 pub(crate) fn serialize_regressor(
 	regressor: &crate::Regressor,
 	writer: &mut buffalo::Writer,
+	weight_encoding: crate::WeightEncoding,
 ) -> buffalo::Position<RegressorWriter> {
-	let weights = writer.write(&regressor.weights);
+	let quantized_weights = serialize_quantized_weights(
+		regressor.weights.as_slice().unwrap(),
+		weight_encoding,
+		writer,
+	);
+	let weights = if quantized_weights.is_none() {
+		writer.write(&regressor.weights)
+	} else {
+		writer.write(&Array1::<f32>::zeros(0))
+	};
 	let means = writer.write(regressor.means.as_slice());
 	writer.write(&RegressorWriter {
 		bias: regressor.bias,
 		weights,
 		means,
+		quantized_weights,
 	})
 }
 
 pub(crate) fn deserialize_regressor(regressor: RegressorReader) -> crate::Regressor {
 	let bias = regressor.bias();
-	let weights = regressor
-		.weights()
-		.iter()
-		.map(|weights| weights.to_owned())
-		.collect::<Vec<_>>()
-		.into();
+	let weights = if let Some(quantized_weights) = regressor.quantized_weights().flatten() {
+		deserialize_quantized_weights(quantized_weights).into()
+	} else {
+		regressor
+			.weights()
+			.iter()
+			.map(|weights| weights.to_owned())
+			.collect::<Vec<_>>()
+			.into()
+	};
 	let means = regressor
 		.means()
 		.iter()
@@ -69,13 +122,24 @@ pub(crate) fn deserialize_regressor(regressor: RegressorReader) -> crate::Regres
 pub(crate) fn serialize_binary_classifier(
 	binary_classifier: &crate::BinaryClassifier,
 	writer: &mut buffalo::Writer,
+	weight_encoding: crate::WeightEncoding,
 ) -> buffalo::Position<BinaryClassifierWriter> {
-	let weights = writer.write(&binary_classifier.weights);
+	let quantized_weights = serialize_quantized_weights(
+		binary_classifier.weights.as_slice().unwrap(),
+		weight_encoding,
+		writer,
+	);
+	let weights = if quantized_weights.is_none() {
+		writer.write(&binary_classifier.weights)
+	} else {
+		writer.write(&Array1::<f32>::zeros(0))
+	};
 	let means = writer.write(binary_classifier.means.as_slice());
 	writer.write(&BinaryClassifierWriter {
 		bias: binary_classifier.bias,
 		weights,
 		means,
+		quantized_weights,
 	})
 }
 
@@ -83,12 +147,16 @@ pub(crate) fn deserialize_binary_classifier(
 	binary_classifier: BinaryClassifierReader,
 ) -> crate::BinaryClassifier {
 	let bias = binary_classifier.bias();
-	let weights = binary_classifier
-		.weights()
-		.iter()
-		.map(|weights| weights.to_owned())
-		.collect::<Vec<_>>()
-		.into();
+	let weights = if let Some(quantized_weights) = binary_classifier.quantized_weights().flatten() {
+		deserialize_quantized_weights(quantized_weights).into()
+	} else {
+		binary_classifier
+			.weights()
+			.iter()
+			.map(|weights| weights.to_owned())
+			.collect::<Vec<_>>()
+			.into()
+	};
 	let means = binary_classifier
 		.means()
 		.iter()
@@ -104,14 +172,27 @@ pub(crate) fn deserialize_binary_classifier(
 pub(crate) fn serialize_multiclass_classifier(
 	multiclass_classifier: &crate::MulticlassClassifier,
 	writer: &mut buffalo::Writer,
+	weight_encoding: crate::WeightEncoding,
 ) -> buffalo::Position<MulticlassClassifierWriter> {
-	let weights = writer.write(&multiclass_classifier.weights);
+	let weights_row_major = multiclass_classifier
+		.weights
+		.iter()
+		.cloned()
+		.collect::<Vec<_>>();
+	let quantized_weights =
+		serialize_quantized_weights(&weights_row_major, weight_encoding, writer);
+	let weights = if quantized_weights.is_none() {
+		writer.write(&multiclass_classifier.weights)
+	} else {
+		writer.write(&Array2::<f32>::zeros((0, 0)))
+	};
 	let biases = writer.write(&multiclass_classifier.biases);
 	let means = writer.write(multiclass_classifier.means.as_slice());
 	writer.write(&MulticlassClassifierWriter {
 		biases,
 		weights,
 		means,
+		quantized_weights,
 	})
 }
 
@@ -123,15 +204,83 @@ pub(crate) fn deserialize_multiclass_classifier(
 		.iter()
 		.map(|bias| bias.to_owned())
 		.collect::<Vec<_>>();
-	let weights = multiclass_classifier.weights();
 	let means = multiclass_classifier
 		.means()
 		.iter()
 		.map(|mean| mean.to_owned())
 		.collect::<Vec<_>>();
+	let weights =
+		if let Some(quantized_weights) = multiclass_classifier.quantized_weights().flatten() {
+			let weights = deserialize_quantized_weights(quantized_weights);
+			Array2::from_shape_vec((means.len(), biases.len()), weights).unwrap()
+		} else {
+			multiclass_classifier.weights()
+		};
 	crate::MulticlassClassifier {
 		biases: biases.into(),
 		weights,
 		means,
 	}
 }
+
+/// Quantize `weights` with `weight_encoding`, or return `None` if the weights should be stored unquantized.
+fn serialize_quantized_weights(
+	weights: &[f32],
+	weight_encoding: crate::WeightEncoding,
+	writer: &mut buffalo::Writer,
+) -> Option<QuantizedWeightsWriter> {
+	match weight_encoding {
+		crate::WeightEncoding::F32 => None,
+		crate::WeightEncoding::F16 => {
+			let values = weights
+				.iter()
+				.map(|weight| half::f16::from_f32(*weight).to_bits())
+				.collect::<Vec<_>>();
+			let values = writer.write(values.as_slice());
+			let weights = writer.write(&F16WeightsWriter { values });
+			Some(QuantizedWeightsWriter::F16(weights))
+		}
+		crate::WeightEncoding::I8 => {
+			// Choose a scale that maps the weight with the largest magnitude to the largest 8 bit integer.
+			let max_abs_weight = weights
+				.iter()
+				.map(|weight| weight.abs())
+				.fold(0.0, f32::max);
+			let scale = max_abs_weight / i8::MAX as f32;
+			let scale = if scale > 0.0 { scale } else { 1.0 };
+			let values = weights
+				.iter()
+				.map(|weight| {
+					(weight / scale)
+						.round()
+						.clamp(i8::MIN as f32, i8::MAX as f32)
+						.to_i8()
+						.unwrap()
+				})
+				.collect::<Vec<_>>();
+			let values = writer.write(values.as_slice());
+			let weights = writer.write(&I8WeightsWriter { scale, values });
+			Some(QuantizedWeightsWriter::I8(weights))
+		}
+	}
+}
+
+fn deserialize_quantized_weights(quantized_weights: QuantizedWeightsReader) -> Vec<f32> {
+	match quantized_weights {
+		QuantizedWeightsReader::F16(weights) => weights
+			.read()
+			.values()
+			.iter()
+			.map(|value| half::f16::from_bits(value).to_f32())
+			.collect(),
+		QuantizedWeightsReader::I8(weights) => {
+			let weights = weights.read();
+			let scale = weights.scale();
+			weights
+				.values()
+				.iter()
+				.map(|value| value as f32 * scale)
+				.collect()
+		}
+	}
+}
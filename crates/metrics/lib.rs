@@ -19,6 +19,13 @@ pub use self::{
 		ClassMetrics, MulticlassClassificationMetrics, MulticlassClassificationMetricsInput,
 		MulticlassClassificationMetricsOutput,
 	},
+	optimal_threshold::{
+		compute_optimal_threshold, BinaryClassificationCostMatrix, ThresholdObjective,
+	},
+	ranking::{
+		average_precision, dcg, group_ranges, ideal_dcg, ndcg, ndcg_discount, ndcg_gain, rank,
+		RankingMetrics, RankingMetricsInput, RankingMetricsOutput,
+	},
 	regression::{RegressionMetrics, RegressionMetricsInput, RegressionMetricsOutput},
 };
 
@@ -32,4 +39,6 @@ mod mean_squared_error;
 mod mean_variance;
 mod mode;
 mod multiclass_classification;
+mod optimal_threshold;
+mod ranking;
 mod regression;
@@ -0,0 +1,133 @@
+use crate::BinaryClassificationMetricsOutputForThreshold;
+use num::ToPrimitive;
+
+/// This enum describes what [`compute_optimal_threshold`] should optimize for when choosing a threshold.
+#[derive(Clone, Debug)]
+pub enum ThresholdObjective {
+	/// Choose the threshold that minimizes the total cost given by the cost matrix.
+	Cost(BinaryClassificationCostMatrix),
+	/// Choose the threshold with the highest recall among the thresholds whose precision is at least this value.
+	MinPrecision(f32),
+	/// Choose the threshold with the highest precision among the thresholds whose recall is at least this value.
+	MinRecall(f32),
+}
+
+/// A `BinaryClassificationCostMatrix` assigns a cost to each cell of the confusion matrix. Use negative values to express a benefit, for example the revenue from a correctly predicted positive example.
+#[derive(Clone, Debug, Default)]
+pub struct BinaryClassificationCostMatrix {
+	/// The cost of each example whose label is the positive class that the model predicted as the positive class.
+	pub true_positive_cost: f32,
+	/// The cost of each example whose label is the negative class that the model predicted as the positive class.
+	pub false_positive_cost: f32,
+	/// The cost of each example whose label is the negative class that the model predicted as the negative class.
+	pub true_negative_cost: f32,
+	/// The cost of each example whose label is the positive class that the model predicted as the negative class.
+	pub false_negative_cost: f32,
+}
+
+impl BinaryClassificationCostMatrix {
+	/// Compute the total cost of the predictions summarized by `metrics`.
+	pub fn total_cost(&self, metrics: &BinaryClassificationMetricsOutputForThreshold) -> f64 {
+		self.true_positive_cost.to_f64().unwrap() * metrics.true_positives.to_f64().unwrap()
+			+ self.false_positive_cost.to_f64().unwrap() * metrics.false_positives.to_f64().unwrap()
+			+ self.true_negative_cost.to_f64().unwrap() * metrics.true_negatives.to_f64().unwrap()
+			+ self.false_negative_cost.to_f64().unwrap() * metrics.false_negatives.to_f64().unwrap()
+	}
+}
+
+/// Choose the threshold from `thresholds` that best satisfies `objective`. This returns `None` if no threshold satisfies the objective's constraint. When multiple thresholds are equally good, the one closest to 0.5 is chosen.
+pub fn compute_optimal_threshold<'a>(
+	thresholds: &'a [BinaryClassificationMetricsOutputForThreshold],
+	objective: &ThresholdObjective,
+) -> Option<&'a BinaryClassificationMetricsOutputForThreshold> {
+	// Each candidate is scored so that a higher score is better.
+	let score = |metrics: &BinaryClassificationMetricsOutputForThreshold| -> Option<f64> {
+		match objective {
+			ThresholdObjective::Cost(cost_matrix) => Some(-cost_matrix.total_cost(metrics)),
+			ThresholdObjective::MinPrecision(min_precision) => {
+				let precision = metrics.precision?;
+				if precision >= *min_precision {
+					metrics.recall.map(f64::from)
+				} else {
+					None
+				}
+			}
+			ThresholdObjective::MinRecall(min_recall) => {
+				let recall = metrics.recall?;
+				if recall >= *min_recall {
+					metrics.precision.map(f64::from)
+				} else {
+					None
+				}
+			}
+		}
+	};
+	let mut best: Option<(f64, &BinaryClassificationMetricsOutputForThreshold)> = None;
+	for metrics in thresholds.iter() {
+		let score = match score(metrics) {
+			Some(score) => score,
+			None => continue,
+		};
+		let is_better = match best {
+			None => true,
+			Some((best_score, best_metrics)) => {
+				score > best_score
+					|| (score == best_score
+						&& (metrics.threshold - 0.5).abs() < (best_metrics.threshold - 0.5).abs())
+			}
+		};
+		if is_better {
+			best = Some((score, metrics));
+		}
+	}
+	best.map(|(_, metrics)| metrics)
+}
+
+#[test]
+fn test() {
+	use crate::{BinaryClassificationMetrics, BinaryClassificationMetricsInput};
+	use std::num::NonZeroUsize;
+	let mut metrics = BinaryClassificationMetrics::new(9);
+	let labels = &[
+		Some(NonZeroUsize::new(2).unwrap()),
+		Some(NonZeroUsize::new(1).unwrap()),
+		Some(NonZeroUsize::new(2).unwrap()),
+		Some(NonZeroUsize::new(1).unwrap()),
+		Some(NonZeroUsize::new(2).unwrap()),
+		Some(NonZeroUsize::new(1).unwrap()),
+	];
+	let probabilities = &[0.9, 0.6, 0.75, 0.2, 0.35, 0.1];
+	metrics.update(BinaryClassificationMetricsInput {
+		probabilities,
+		labels,
+	});
+	let metrics = metrics.finalize();
+	// Missing a positive example is ten times as costly as a false alarm, so the threshold should drop low enough to catch every positive example.
+	let objective = ThresholdObjective::Cost(BinaryClassificationCostMatrix {
+		false_positive_cost: 1.0,
+		false_negative_cost: 10.0,
+		..Default::default()
+	});
+	let optimal = compute_optimal_threshold(&metrics.thresholds, &objective).unwrap();
+	assert_eq!(optimal.false_negatives, 0);
+	assert_eq!(optimal.false_positives, 1);
+	// Requiring perfect precision should choose a threshold above the highest scoring negative example.
+	let objective = ThresholdObjective::MinPrecision(1.0);
+	let optimal = compute_optimal_threshold(&metrics.thresholds, &objective).unwrap();
+	assert_eq!(optimal.precision, Some(1.0));
+	assert_eq!(optimal.true_positives, 2);
+	// Requiring perfect recall should choose the highest threshold at or below the lowest scoring positive example, which has the best precision.
+	let objective = ThresholdObjective::MinRecall(1.0);
+	let optimal = compute_optimal_threshold(&metrics.thresholds, &objective).unwrap();
+	assert_eq!(optimal.recall, Some(1.0));
+	assert_eq!(optimal.precision, Some(0.75));
+	// A constraint that cannot be satisfied produces no threshold.
+	let objective = ThresholdObjective::MinPrecision(1.1);
+	assert!(compute_optimal_threshold(&metrics.thresholds, &objective).is_none());
+	let objective = ThresholdObjective::MinRecall(1.1);
+	assert!(compute_optimal_threshold(&metrics.thresholds, &objective).is_none());
+	// When every threshold costs the same, the threshold closest to 0.5 is chosen.
+	let objective = ThresholdObjective::Cost(BinaryClassificationCostMatrix::default());
+	let optimal = compute_optimal_threshold(&metrics.thresholds, &objective).unwrap();
+	assert_eq!(optimal.threshold, 0.5);
+}
@@ -0,0 +1,188 @@
+use num::ToPrimitive;
+use std::ops::Range;
+
+/// RankingMetrics computes metrics used to evaluate rankers. The examples are partitioned into query groups, and each group is ranked by sorting its examples by their predicted scores in descending order. The metrics are computed for each group and averaged over the groups.
+pub struct RankingMetrics {
+	/// If this is set, only the top `k` examples in each group are considered.
+	k: Option<usize>,
+	ndcg: f64,
+	average_precision: f64,
+	n_groups: u64,
+}
+
+/// The input to [`RankingMetrics`].
+pub struct RankingMetricsInput<'a> {
+	pub predictions: &'a [f32],
+	/// These are the relevance labels. A higher label means the example is more relevant. An example is considered relevant for computing the average precision if its label is greater than zero.
+	pub labels: &'a [f32],
+	/// This is the query group of each example. The examples in each group must be contiguous.
+	pub groups: &'a [usize],
+}
+
+/// The output from [`RankingMetrics`].
+#[derive(Debug)]
+pub struct RankingMetricsOutput {
+	/// The mean over the query groups of the normalized discounted cumulative gain. https://en.wikipedia.org/wiki/Discounted_cumulative_gain.
+	pub ndcg: f32,
+	/// The mean over the query groups of the average precision. https://en.wikipedia.org/wiki/Evaluation_measures_(information_retrieval)#Mean_average_precision.
+	pub map: f32,
+	/// The number of query groups.
+	pub n_groups: u64,
+}
+
+impl RankingMetrics {
+	pub fn new(k: Option<usize>) -> RankingMetrics {
+		RankingMetrics {
+			k,
+			ndcg: 0.0,
+			average_precision: 0.0,
+			n_groups: 0,
+		}
+	}
+
+	pub fn update(&mut self, input: RankingMetricsInput) {
+		for range in group_ranges(input.groups) {
+			let predictions = &input.predictions[range.clone()];
+			let labels = &input.labels[range];
+			self.ndcg += ndcg(predictions, labels, self.k) as f64;
+			self.average_precision += average_precision(predictions, labels, self.k) as f64;
+			self.n_groups += 1;
+		}
+	}
+
+	pub fn merge(&mut self, other: RankingMetrics) {
+		self.ndcg += other.ndcg;
+		self.average_precision += other.average_precision;
+		self.n_groups += other.n_groups;
+	}
+
+	/// Average the metrics over the query groups. If there are no groups, the metrics are 0.
+	pub fn finalize(self) -> RankingMetricsOutput {
+		if self.n_groups == 0 {
+			return RankingMetricsOutput {
+				ndcg: 0.0,
+				map: 0.0,
+				n_groups: 0,
+			};
+		}
+		let n_groups = self.n_groups.to_f64().unwrap();
+		RankingMetricsOutput {
+			ndcg: (self.ndcg / n_groups).to_f32().unwrap(),
+			map: (self.average_precision / n_groups).to_f32().unwrap(),
+			n_groups: self.n_groups,
+		}
+	}
+}
+
+/// Return the range of example indexes for each query group in `groups`, where the examples in each group are contiguous.
+pub fn group_ranges(groups: &[usize]) -> Vec<Range<usize>> {
+	let mut ranges = Vec::new();
+	let mut start = 0;
+	for index in 1..=groups.len() {
+		if index == groups.len() || groups[index] != groups[start] {
+			ranges.push(start..index);
+			start = index;
+		}
+	}
+	ranges
+}
+
+/// Return the gain of an example with relevance `label`. Gains grow exponentially with relevance, so ranking a highly relevant example well matters much more than ranking a marginally relevant one.
+pub fn ndcg_gain(label: f32) -> f32 {
+	2.0f32.powf(label) - 1.0
+}
+
+/// Return the discount applied to the gain of the example at the zero-based `rank`.
+pub fn ndcg_discount(rank: usize) -> f32 {
+	1.0 / (rank.to_f32().unwrap() + 2.0).log2()
+}
+
+/// Return the discounted cumulative gain of the top `k` examples when they are sorted in the order of `ranking`.
+pub fn dcg(labels: &[f32], ranking: &[usize], k: Option<usize>) -> f32 {
+	let k = k.unwrap_or(ranking.len());
+	ranking
+		.iter()
+		.take(k)
+		.enumerate()
+		.map(|(rank, index)| ndcg_gain(labels[*index]) * ndcg_discount(rank))
+		.sum()
+}
+
+/// Return the discounted cumulative gain of the best possible ranking of the top `k` examples.
+pub fn ideal_dcg(labels: &[f32], k: Option<usize>) -> f32 {
+	dcg(labels, &rank(labels), k)
+}
+
+/// Compute the normalized discounted cumulative gain for a single query group. If no example in the group is relevant, every ranking is ideal and the NDCG is 1.
+pub fn ndcg(predictions: &[f32], labels: &[f32], k: Option<usize>) -> f32 {
+	let ideal_dcg = ideal_dcg(labels, k);
+	if ideal_dcg == 0.0 {
+		return 1.0;
+	}
+	dcg(labels, &rank(predictions), k) / ideal_dcg
+}
+
+/// Compute the average precision for a single query group. If no example in the group is relevant, the average precision is 1.
+pub fn average_precision(predictions: &[f32], labels: &[f32], k: Option<usize>) -> f32 {
+	let k = k.unwrap_or(labels.len());
+	let n_relevant = labels.iter().filter(|label| **label > 0.0).count();
+	if n_relevant == 0 {
+		return 1.0;
+	}
+	let mut n_relevant_so_far = 0;
+	let mut sum_precisions = 0.0;
+	for (rank, index) in rank(predictions).into_iter().take(k).enumerate() {
+		if labels[index] > 0.0 {
+			n_relevant_so_far += 1;
+			sum_precisions += n_relevant_so_far.to_f32().unwrap() / (rank + 1).to_f32().unwrap();
+		}
+	}
+	sum_precisions / n_relevant.min(k).to_f32().unwrap()
+}
+
+/// Return the indexes of `scores` sorted by score in descending order. Ties keep their original order.
+pub fn rank(scores: &[f32]) -> Vec<usize> {
+	let mut ranking = (0..scores.len()).collect::<Vec<_>>();
+	ranking.sort_by(|a, b| scores[*b].partial_cmp(&scores[*a]).unwrap());
+	ranking
+}
+
+#[test]
+fn test_group_ranges() {
+	assert_eq!(group_ranges(&[0, 0, 3, 3, 3, 1]), vec![0..2, 2..5, 5..6]);
+	assert_eq!(group_ranges(&[]), Vec::<Range<usize>>::new());
+}
+
+#[test]
+fn test_ndcg() {
+	let labels = [3.0, 2.0, 0.0, 1.0];
+	assert!((ndcg(&[4.0, 3.0, 2.0, 1.0], &labels, None) - 0.9926).abs() < 1e-4);
+	assert!((ndcg(&[4.0, 3.0, 1.0, 2.0], &labels, None) - 1.0).abs() < 1e-6);
+	assert!((ndcg(&[4.0, 3.0, 2.0, 1.0], &labels, Some(2)) - 1.0).abs() < 1e-6);
+	assert!((ndcg(&[1.0, 2.0], &[0.0, 0.0], None) - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_average_precision() {
+	let labels = [1.0, 0.0, 1.0, 0.0];
+	// The relevant examples are at ranks 1 and 3, so the precisions are 1 and 2/3.
+	assert!((average_precision(&[4.0, 3.0, 2.0, 1.0], &labels, None) - 0.8333).abs() < 1e-4);
+	assert!((average_precision(&[4.0, 3.0, 2.0, 1.0], &labels, Some(2)) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_ranking_metrics() {
+	let mut metrics = RankingMetrics::new(None);
+	metrics.update(RankingMetricsInput {
+		predictions: &[4.0, 3.0, 2.0, 1.0, 1.0, 2.0],
+		labels: &[1.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+		groups: &[0, 0, 0, 0, 1, 1],
+	});
+	let output = metrics.finalize();
+	assert_eq!(output.n_groups, 2);
+	assert!((output.map - (0.8333 + 1.0) / 2.0).abs() < 1e-4);
+	let output = RankingMetrics::new(None).finalize();
+	assert_eq!(output.n_groups, 0);
+	assert_eq!(output.ndcg, 0.0);
+	assert_eq!(output.map, 0.0);
+}
@@ -1,8 +1,11 @@
 use crate::{
-	ColumnStats, FeatureGroup, LinearModelTrainOptions, StatsSettings, TrainGridItemOutput,
-	TreeModelTrainOptions,
+	ColumnStats, DataQualityIssue, DroppedColumn, Explanations, FeatureGroup,
+	LinearModelTrainOptions, StatsSettings, TrainGridItemOutput, TreeModelTrainOptions,
 };
 
+/// This is the threshold used by binary classifiers that do not have a threshold saved.
+pub const DEFAULT_THRESHOLD: f32 = 0.5;
+
 #[derive(buffalo::Read, buffalo::Write)]
 #[buffalo(size = "dynamic")]
 pub struct BinaryClassifier {
@@ -44,6 +47,18 @@ pub struct BinaryClassifier {
 	pub model: BinaryClassificationModel,
 	#[buffalo(id = 18, required)]
 	pub test_metrics: BinaryClassificationMetrics,
+	/// This is the threshold used to choose between the negative and positive class when none is passed at prediction time. If it is absent, `DEFAULT_THRESHOLD` is used.
+	#[buffalo(id = 19)]
+	pub threshold: f32,
+	/// This is absent for models trained before explanations were computed during training, and `None` for models rewritten with `strip`.
+	#[buffalo(id = 20)]
+	pub explanations: Option<Explanations>,
+	/// These are the columns that feature selection dropped before training. This is absent for models trained before feature selection was added, and empty for models rewritten with `strip`.
+	#[buffalo(id = 21)]
+	pub dropped_columns: Vec<DroppedColumn>,
+	/// These are the issues found by the target leakage and data quality checks. This is absent for models trained before the checks were added, and empty for models rewritten with `strip`.
+	#[buffalo(id = 22)]
+	pub data_quality_issues: Vec<DataQualityIssue>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
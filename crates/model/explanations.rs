@@ -0,0 +1,75 @@
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct Explanations {
+	/// The mean absolute SHAP value of each feature over the test dataset, in the same order as the model's features.
+	#[buffalo(id = 0, required)]
+	pub shap_feature_importances: Vec<f32>,
+	/// The SHAP feature importances summed over the features computed from each column, sorted from most to least important.
+	#[buffalo(id = 1, required)]
+	pub shap_column_importances: Vec<(String, f32)>,
+	#[buffalo(id = 2, required)]
+	pub partial_dependences: Vec<PartialDependence>,
+	/// The pairs of features with the largest mean absolute SHAP interaction value over the test dataset, sorted from most to least interacting. This is absent for linear models and for models with too many features.
+	#[buffalo(id = 3)]
+	pub feature_interactions: Option<Vec<FeatureInteraction>>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct FeatureInteraction {
+	#[buffalo(id = 0, required)]
+	pub feature_name_a: String,
+	#[buffalo(id = 1, required)]
+	pub feature_name_b: String,
+	/// The mean absolute SHAP interaction value of the pair of features over the test dataset.
+	#[buffalo(id = 2, required)]
+	pub value: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct PartialDependence {
+	#[buffalo(id = 0, required)]
+	pub column_name: String,
+	/// For multiclass classifiers, this is the class whose probability the curves describe.
+	#[buffalo(id = 1, required)]
+	pub class: Option<String>,
+	#[buffalo(id = 2, required)]
+	pub grid: PartialDependenceGrid,
+	/// The average model output over the test examples at each grid value.
+	#[buffalo(id = 3, required)]
+	pub average: Vec<f32>,
+	/// The model output for a handful of individual test examples at each grid value.
+	#[buffalo(id = 4, required)]
+	pub individual: Vec<IndividualConditionalExpectation>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 8)]
+pub enum PartialDependenceGrid {
+	#[buffalo(id = 0)]
+	Number(NumberPartialDependenceGrid),
+	#[buffalo(id = 1)]
+	Enum(EnumPartialDependenceGrid),
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct NumberPartialDependenceGrid {
+	#[buffalo(id = 0, required)]
+	pub values: Vec<f32>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct EnumPartialDependenceGrid {
+	#[buffalo(id = 0, required)]
+	pub variants: Vec<String>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct IndividualConditionalExpectation {
+	#[buffalo(id = 0, required)]
+	pub values: Vec<f32>,
+}
@@ -15,6 +15,16 @@ pub enum FeatureGroup {
 	WordEmbedding(WordEmbeddingFeatureGroup),
 	#[buffalo(id = 5)]
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureGroup),
+	#[buffalo(id = 6)]
+	HashedBagOfWords(HashedBagOfWordsFeatureGroup),
+	#[buffalo(id = 7)]
+	HashedEnum(HashedEnumFeatureGroup),
+	#[buffalo(id = 8)]
+	Interaction(InteractionFeatureGroup),
+	#[buffalo(id = 9)]
+	Polynomial(PolynomialFeatureGroup),
+	#[buffalo(id = 10)]
+	BinnedNumber(BinnedNumberFeatureGroup),
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -22,6 +32,10 @@ pub enum FeatureGroup {
 pub struct IdentityFeatureGroup {
 	#[buffalo(id = 0, required)]
 	pub source_column_name: String,
+	#[buffalo(id = 1)]
+	pub impute: Option<Impute>,
+	#[buffalo(id = 2)]
+	pub missing_indicator: bool,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -33,6 +47,33 @@ pub struct NormalizedFeatureGroup {
 	pub mean: f32,
 	#[buffalo(id = 2, required)]
 	pub variance: f32,
+	#[buffalo(id = 3)]
+	pub impute: Option<Impute>,
+	#[buffalo(id = 4)]
+	pub missing_indicator: bool,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct Impute {
+	#[buffalo(id = 0, required)]
+	pub strategy: ImputeStrategy,
+	/// Invalid values are replaced with this value. For enum columns, this is the index of a variant, starting at 1.
+	#[buffalo(id = 1, required)]
+	pub value: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 0)]
+pub enum ImputeStrategy {
+	#[buffalo(id = 0)]
+	Mean,
+	#[buffalo(id = 1)]
+	Median,
+	#[buffalo(id = 2)]
+	Mode,
+	#[buffalo(id = 3)]
+	Constant,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -121,3 +162,105 @@ pub struct WordEmbeddingModel {
 	#[buffalo(id = 2, required)]
 	pub values: Vec<f32>,
 }
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct HashedBagOfWordsFeatureGroup {
+	#[buffalo(id = 0, required)]
+	pub source_column_name: String,
+	#[buffalo(id = 1, required)]
+	pub tokenizer: Tokenizer,
+	#[buffalo(id = 2, required)]
+	pub strategy: HashedBagOfWordsFeatureGroupStrategy,
+	#[buffalo(id = 3, required)]
+	pub ngram_types: Vec<NGramType>,
+	#[buffalo(id = 4, required)]
+	pub n_buckets: u64,
+	#[buffalo(id = 5, required)]
+	pub signed: bool,
+	/// There is one entry for each bucket.
+	#[buffalo(id = 6, required)]
+	pub buckets: Vec<HashedBagOfWordsFeatureGroupBucket>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 0)]
+pub enum HashedBagOfWordsFeatureGroupStrategy {
+	/// The feature values will be the sum of the signs of the distinct ngrams in the source column value that hash to each bucket.
+	#[buffalo(id = 0)]
+	Present,
+	/// The feature values will be the sum of the signs of every occurrence of the ngrams in the source column value that hash to each bucket.
+	#[buffalo(id = 1)]
+	Count,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct HashedBagOfWordsFeatureGroupBucket {
+	/// These are the most frequent ngrams in the training data that hash to this bucket.
+	#[buffalo(id = 0, required)]
+	pub ngrams: Vec<NGram>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct HashedEnumFeatureGroup {
+	#[buffalo(id = 0, required)]
+	pub source_column_name: String,
+	#[buffalo(id = 1, required)]
+	pub n_buckets: u64,
+	#[buffalo(id = 2, required)]
+	pub signed: bool,
+	/// There is one entry for each bucket.
+	#[buffalo(id = 3, required)]
+	pub buckets: Vec<HashedEnumFeatureGroupBucket>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct HashedEnumFeatureGroupBucket {
+	/// These are the most frequent values in the training data that hash to this bucket.
+	#[buffalo(id = 0, required)]
+	pub values: Vec<String>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct InteractionFeatureGroup {
+	#[buffalo(id = 0, required)]
+	pub source_a: InteractionFeatureGroupSource,
+	#[buffalo(id = 1, required)]
+	pub source_b: InteractionFeatureGroupSource,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 8)]
+pub enum InteractionFeatureGroupSource {
+	#[buffalo(id = 0)]
+	Normalized(NormalizedFeatureGroup),
+	#[buffalo(id = 1)]
+	OneHotEncoded(OneHotEncodedFeatureGroup),
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct PolynomialFeatureGroup {
+	#[buffalo(id = 0, required)]
+	pub source_column_name: String,
+	#[buffalo(id = 1, required)]
+	pub mean: f32,
+	#[buffalo(id = 2, required)]
+	pub variance: f32,
+	#[buffalo(id = 3, required)]
+	pub degree: u64,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct BinnedNumberFeatureGroup {
+	#[buffalo(id = 0, required)]
+	pub source_column_name: String,
+	/// These are the boundaries between the bins in ascending order.
+	#[buffalo(id = 1, required)]
+	pub thresholds: Vec<f32>,
+}
@@ -3,8 +3,8 @@ This crate defines the structure of `.modelfox` files using the `buffalo` crate.
 */
 
 pub use self::{
-	binary_classifier::*, features::*, grid::*, model_train_options::*, multiclass_classifier::*,
-	regressor::*, stats::*,
+	binary_classifier::*, explanations::*, features::*, grid::*, model_train_options::*,
+	multiclass_classifier::*, ranker::*, regressor::*, rewrite::*, stats::*,
 };
 use anyhow::{bail, Result};
 use fnv::FnvHashMap;
@@ -12,17 +12,30 @@ use num::ToPrimitive;
 use std::{io::prelude::*, path::Path};
 
 mod binary_classifier;
+mod explanations;
 mod features;
 mod grid;
 mod model_train_options;
 mod multiclass_classifier;
+mod ranker;
 mod regressor;
+mod rewrite;
 mod stats;
 
 /// A .modelfox file is prefixed with this magic number followed by a 4-byte little endian revision number.
 const MAGIC_NUMBER: &[u8] = b"tangram\0";
-/// This is the revision number that this version of modelfox_model writes.
-const CURRENT_REVISION: u32 = 0;
+/// A model is written with `BASE_REVISION` so older versions of modelfox can still read it, unless it contains anything a reader of `BASE_REVISION` cannot decode, such as a new enum variant or a section that reader requires being left out. Those models are written with `CURRENT_REVISION`, so older versions of modelfox refuse them with a clear error instead of misreading them. Whenever such a feature is added, it must be listed here and checked where the revision is chosen.
+///
+/// Revision 1 adds:
+/// - Linear weights and tree leaf values stored quantized.
+/// - Models stripped of the parts that only describe training.
+/// - Character n-grams in text column stats and bag of words feature groups.
+/// - Hashed bag of words and hashed enum feature groups.
+/// - Interaction, polynomial, and binned number feature groups.
+/// - Rankers.
+const BASE_REVISION: u32 = 0;
+/// This is the latest revision number that this version of modelfox_model can read and write.
+const CURRENT_REVISION: u32 = 1;
 /// This is the oldest revision number that this version of modelfox_model can read.
 const MIN_SUPPORTED_REVISION: u32 = 0;
 
@@ -53,12 +66,136 @@ pub fn to_path(path: &Path, bytes: &[u8]) -> Result<()> {
 	// Write the magic number.
 	file.write_all(MAGIC_NUMBER)?;
 	// Write the revision number.
-	file.write_all(&CURRENT_REVISION.to_le_bytes())?;
+	file.write_all(&required_revision(buffalo::read::<ModelReader>(bytes)).to_le_bytes())?;
 	// Write the bytes.
 	file.write_all(bytes)?;
 	Ok(())
 }
 
+pub fn to_bytes(bytes: &[u8]) -> Vec<u8> {
+	let mut model_bytes = Vec::with_capacity(MAGIC_NUMBER.len() + 4 + bytes.len());
+	// Write the magic number.
+	model_bytes.extend_from_slice(MAGIC_NUMBER);
+	// Write the revision number.
+	model_bytes
+		.extend_from_slice(&required_revision(buffalo::read::<ModelReader>(bytes)).to_le_bytes());
+	// Write the bytes.
+	model_bytes.extend_from_slice(bytes);
+	model_bytes
+}
+
+/// Return the oldest revision whose readers can decode `model`, so models that use none of the features added since `BASE_REVISION` remain readable by older versions of modelfox.
+fn required_revision(model: ModelReader) -> u32 {
+	let (column_stats, feature_groups) = match model.inner() {
+		ModelInnerReader::Regressor(regressor) => {
+			let regressor = regressor.read();
+			let feature_groups = match regressor.model() {
+				RegressionModelReader::Linear(model) => model.read().feature_groups(),
+				RegressionModelReader::Tree(model) => model.read().feature_groups(),
+			};
+			(regressor.overall_column_stats(), feature_groups)
+		}
+		ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			let binary_classifier = binary_classifier.read();
+			let feature_groups = match binary_classifier.model() {
+				BinaryClassificationModelReader::Linear(model) => model.read().feature_groups(),
+				BinaryClassificationModelReader::Tree(model) => model.read().feature_groups(),
+			};
+			(binary_classifier.overall_column_stats(), feature_groups)
+		}
+		ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let multiclass_classifier = multiclass_classifier.read();
+			let feature_groups = match multiclass_classifier.model() {
+				MulticlassClassificationModelReader::Linear(model) => model.read().feature_groups(),
+				MulticlassClassificationModelReader::Tree(model) => model.read().feature_groups(),
+			};
+			(multiclass_classifier.overall_column_stats(), feature_groups)
+		}
+		ModelInnerReader::Ranker(_) => return CURRENT_REVISION,
+	};
+	let column_stats_require_current_revision =
+		column_stats.iter().any(|column_stats| match column_stats {
+			ColumnStatsReader::TextColumn(column_stats) => {
+				has_character_ngrams(column_stats.read().ngram_types())
+			}
+			_ => false,
+		});
+	let feature_groups_require_current_revision =
+		feature_groups
+			.iter()
+			.any(|feature_group| match feature_group {
+				FeatureGroupReader::BagOfWords(feature_group) => {
+					has_character_ngrams(feature_group.read().ngram_types())
+				}
+				FeatureGroupReader::BagOfWordsCosineSimilarity(feature_group) => {
+					has_character_ngrams(feature_group.read().ngram_types())
+				}
+				FeatureGroupReader::HashedBagOfWords(_)
+				| FeatureGroupReader::HashedEnum(_)
+				| FeatureGroupReader::Interaction(_)
+				| FeatureGroupReader::Polynomial(_)
+				| FeatureGroupReader::BinnedNumber(_) => true,
+				_ => false,
+			});
+	if column_stats_require_current_revision || feature_groups_require_current_revision {
+		CURRENT_REVISION
+	} else {
+		BASE_REVISION
+	}
+}
+
+fn has_character_ngrams<'a>(ngram_types: buffalo::VecReader<'a, NGramTypeReader<'a>>) -> bool {
+	ngram_types.iter().any(|ngram_type| {
+		!matches!(
+			ngram_type,
+			NGramTypeReader::Unigram(_) | NGramTypeReader::Bigram(_)
+		)
+	})
+}
+
+/// Overwrite the threshold saved in a binary classifier's `.modelfox` file in place, leaving every other byte untouched.
+pub fn set_threshold(bytes: &mut [u8], threshold: f32) -> Result<()> {
+	let model = from_bytes(bytes)?;
+	if model.inner().as_binary_classifier().is_none() {
+		bail!("Only binary classifiers have a threshold.");
+	}
+	// Walk from the root of the buffalo data to the binary classifier's threshold field. Every pointer is a little endian u64 that is subtracted from its own position.
+	let bytes = &mut bytes[MAGIC_NUMBER.len() + 4..];
+	let read_u16 = |bytes: &[u8], position: usize| {
+		u16::from_le_bytes(bytes[position..position + 2].try_into().unwrap())
+			.to_usize()
+			.unwrap()
+	};
+	let read_pointer = |bytes: &[u8], position: usize| {
+		position
+			- u64::from_le_bytes(bytes[position..position + 8].try_into().unwrap())
+				.to_usize()
+				.unwrap()
+	};
+	// A dynamic struct begins with a pointer to its index, which is a u16 field count followed by a u16 offset from the start of the struct for each field. An offset of zero means the field is absent.
+	let field_position = |bytes: &[u8], struct_position: usize, field_id: usize| {
+		let index_position = read_pointer(bytes, struct_position);
+		if field_id >= read_u16(bytes, index_position) {
+			return None;
+		}
+		match read_u16(bytes, index_position + 2 + 2 * field_id) {
+			0 => None,
+			offset => Some(struct_position + offset),
+		}
+	};
+	let model_position = read_pointer(bytes, bytes.len() - 8);
+	// `Model::inner` has id 3. It is a static enum, so its value is a u8 discriminant followed by a pointer to the `BinaryClassifier`.
+	let inner_position = field_position(bytes, model_position, 3).unwrap();
+	let binary_classifier_position = read_pointer(bytes, inner_position + 1);
+	// `BinaryClassifier::threshold` has id 19.
+	let threshold_position = match field_position(bytes, binary_classifier_position, 19) {
+		Some(threshold_position) => threshold_position,
+		None => bail!("This model was trained before binary classifiers saved their threshold. Please retrain it to save a threshold."),
+	};
+	bytes[threshold_position..threshold_position + 4].copy_from_slice(&threshold.to_le_bytes());
+	Ok(())
+}
+
 #[derive(buffalo::Read, buffalo::Write)]
 #[buffalo(size = "dynamic")]
 pub struct Model {
@@ -70,6 +207,9 @@ pub struct Model {
 	pub date: String,
 	#[buffalo(id = 3, required)]
 	pub inner: ModelInner,
+	/// This is the id of the model that this model's training continued from with `--init-model`, if any.
+	#[buffalo(id = 4)]
+	pub parent_id: Option<String>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -82,6 +222,8 @@ pub enum ModelInner {
 	BinaryClassifier(BinaryClassifier),
 	#[buffalo(id = 2)]
 	MulticlassClassifier(MulticlassClassifier),
+	#[buffalo(id = 3)]
+	Ranker(Ranker),
 }
 
 impl<'a> ColumnStatsReader<'a> {
@@ -106,6 +248,10 @@ impl<'a> std::fmt::Display for NGramReader<'a> {
 				let token = token.read();
 				write!(f, "{} {}", token.0, token.1)
 			}
+			NGramReader::Character(ngram) => {
+				let ngram = ngram.read();
+				write!(f, "{}", ngram)
+			}
 		}
 	}
 }
@@ -115,6 +261,25 @@ impl<'a> From<TokenizerReader<'a>> for modelfox_text::Tokenizer {
 		modelfox_text::Tokenizer {
 			lowercase: value.lowercase(),
 			alphanumeric: value.alphanumeric(),
+			nfkc: value.nfkc().unwrap_or(false),
+			fold_accents: value.fold_accents().unwrap_or(false),
+			stop_words: value
+				.stop_words()
+				.map(|stop_words| {
+					stop_words
+						.iter()
+						.map(|stop_word| stop_word.to_owned())
+						.collect()
+				})
+				.unwrap_or_default(),
+			stemmer: value.stemmer().flatten().map(|stemmer| match stemmer {
+				StemmerReader::English(_) => modelfox_text::Stemmer::English,
+			}),
+			// The token pattern was checked when the model was trained.
+			token_pattern: value
+				.token_pattern()
+				.flatten()
+				.map(|token_pattern| modelfox_text::TokenPattern::new(token_pattern).unwrap()),
 		}
 	}
 }
@@ -130,6 +295,10 @@ impl<'a> From<NGramReader<'a>> for modelfox_text::NGramRef<'a> {
 				let bigram = bigram.read();
 				modelfox_text::NGramRef::Bigram(bigram.0.into(), bigram.1.into())
 			}
+			NGramReader::Character(ngram) => {
+				let ngram = ngram.read();
+				modelfox_text::NGramRef::Character((*ngram).into())
+			}
 		}
 	}
 }
@@ -22,6 +22,66 @@ pub struct LinearModelTrainOptions {
 	pub n_examples_per_batch: u64,
 	#[buffalo(id = 5, required)]
 	pub early_stopping_options: Option<LinearEarlyStoppingOptions>,
+	#[buffalo(id = 6)]
+	pub l1_regularization: f32,
+	#[buffalo(id = 7)]
+	pub optimizer: LinearOptimizer,
+	#[buffalo(id = 8)]
+	pub learning_rate_schedule: LinearLearningRateSchedule,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 8)]
+pub enum LinearOptimizer {
+	#[buffalo(id = 0)]
+	Sgd,
+	#[buffalo(id = 1)]
+	Momentum(MomentumOptimizer),
+	#[buffalo(id = 2)]
+	Adam(AdamOptimizer),
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct MomentumOptimizer {
+	#[buffalo(id = 0, required)]
+	pub momentum: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct AdamOptimizer {
+	#[buffalo(id = 0, required)]
+	pub beta_1: f32,
+	#[buffalo(id = 1, required)]
+	pub beta_2: f32,
+	#[buffalo(id = 2, required)]
+	pub epsilon: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 8)]
+pub enum LinearLearningRateSchedule {
+	#[buffalo(id = 0)]
+	Constant,
+	#[buffalo(id = 1)]
+	InverseTimeDecay(InverseTimeDecayLearningRateSchedule),
+	#[buffalo(id = 2)]
+	ExponentialDecay(ExponentialDecayLearningRateSchedule),
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct InverseTimeDecayLearningRateSchedule {
+	#[buffalo(id = 0, required)]
+	pub decay_rate: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct ExponentialDecayLearningRateSchedule {
+	#[buffalo(id = 0, required)]
+	pub decay_rate: f32,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -68,6 +128,20 @@ pub struct TreeModelTrainOptions {
 	pub min_sum_hessians_per_node: f32,
 	#[buffalo(id = 14, required)]
 	pub smoothing_factor_for_discrete_bin_sorting: f32,
+	#[buffalo(id = 15)]
+	pub bagging_fraction: f32,
+	#[buffalo(id = 16)]
+	pub bagging_frequency: u64,
+	#[buffalo(id = 17)]
+	pub feature_fraction_per_node: f32,
+	#[buffalo(id = 18)]
+	pub feature_fraction_per_tree: f32,
+	#[buffalo(id = 19)]
+	pub goss_options: Option<TreeGossOptions>,
+	#[buffalo(id = 20)]
+	pub seed: u64,
+	#[buffalo(id = 21)]
+	pub dart_options: Option<TreeDartOptions>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -79,6 +153,26 @@ pub enum BinnedFeaturesLayout {
 	ColumnMajor,
 }
 
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct TreeGossOptions {
+	#[buffalo(id = 0, required)]
+	pub top_rate: f32,
+	#[buffalo(id = 1, required)]
+	pub other_rate: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct TreeDartOptions {
+	#[buffalo(id = 0, required)]
+	pub drop_rate: f32,
+	#[buffalo(id = 1, required)]
+	pub max_drop: u64,
+	#[buffalo(id = 2, required)]
+	pub skip_drop: f32,
+}
+
 #[derive(buffalo::Read, buffalo::Write)]
 #[buffalo(size = "dynamic")]
 pub struct TreeEarlyStoppingOptions {
@@ -1,6 +1,6 @@
 use crate::{
-	ColumnStats, FeatureGroup, LinearModelTrainOptions, StatsSettings, TrainGridItemOutput,
-	TreeModelTrainOptions,
+	ColumnStats, DataQualityIssue, DroppedColumn, Explanations, FeatureGroup,
+	LinearModelTrainOptions, StatsSettings, TrainGridItemOutput, TreeModelTrainOptions,
 };
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -42,6 +42,15 @@ pub struct MulticlassClassifier {
 	pub model: MulticlassClassificationModel,
 	#[buffalo(id = 17, required)]
 	pub test_metrics: MulticlassClassificationMetrics,
+	/// This is absent for models trained before explanations were computed during training, and `None` for models rewritten with `strip`.
+	#[buffalo(id = 18)]
+	pub explanations: Option<Explanations>,
+	/// These are the columns that feature selection dropped before training. This is absent for models trained before feature selection was added, and empty for models rewritten with `strip`.
+	#[buffalo(id = 19)]
+	pub dropped_columns: Vec<DroppedColumn>,
+	/// These are the issues found by the target leakage and data quality checks. This is absent for models trained before the checks were added, and empty for models rewritten with `strip`.
+	#[buffalo(id = 20)]
+	pub data_quality_issues: Vec<DataQualityIssue>,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -0,0 +1,97 @@
+use crate::{
+	ColumnStats, DataQualityIssue, DroppedColumn, Explanations, FeatureGroup, StatsSettings,
+	TrainGridItemOutput, TreeModelTrainOptions,
+};
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct Ranker {
+	#[buffalo(id = 0, required)]
+	pub target_column_name: String,
+	/// This is the column whose values identify the query group of each example.
+	#[buffalo(id = 1, required)]
+	pub group_column_name: String,
+	#[buffalo(id = 2, required)]
+	pub train_row_count: u64,
+	#[buffalo(id = 3, required)]
+	pub test_row_count: u64,
+	#[buffalo(id = 4, required)]
+	pub overall_row_count: u64,
+	#[buffalo(id = 5, required)]
+	pub stats_settings: StatsSettings,
+	#[buffalo(id = 6, required)]
+	pub overall_column_stats: Vec<ColumnStats>,
+	#[buffalo(id = 7, required)]
+	pub overall_target_column_stats: ColumnStats,
+	#[buffalo(id = 8, required)]
+	pub train_column_stats: Vec<ColumnStats>,
+	#[buffalo(id = 9, required)]
+	pub train_target_column_stats: ColumnStats,
+	#[buffalo(id = 10, required)]
+	pub test_column_stats: Vec<ColumnStats>,
+	#[buffalo(id = 11, required)]
+	pub test_target_column_stats: ColumnStats,
+	#[buffalo(id = 12, required)]
+	pub baseline_metrics: RankingMetrics,
+	#[buffalo(id = 13, required)]
+	pub comparison_metric: RankingComparisonMetric,
+	#[buffalo(id = 14, required)]
+	pub train_grid_item_outputs: Vec<TrainGridItemOutput>,
+	#[buffalo(id = 15, required)]
+	pub best_grid_item_index: u64,
+	#[buffalo(id = 16, required)]
+	pub model: RankingModel,
+	#[buffalo(id = 17, required)]
+	pub test_metrics: RankingMetrics,
+	/// If this is present, the model was trained and evaluated on the NDCG of only the top `ndcg_k` examples of each query group.
+	#[buffalo(id = 18)]
+	pub ndcg_k: Option<u64>,
+	#[buffalo(id = 19)]
+	pub explanations: Option<Explanations>,
+	#[buffalo(id = 20)]
+	pub dropped_columns: Vec<DroppedColumn>,
+	#[buffalo(id = 21)]
+	pub data_quality_issues: Vec<DataQualityIssue>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 8)]
+pub enum RankingModel {
+	#[buffalo(id = 0)]
+	Tree(TreeRanker),
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct TreeRanker {
+	#[buffalo(id = 0, required)]
+	pub model: modelfox_tree::serialize::Ranker,
+	#[buffalo(id = 1, required)]
+	pub train_options: TreeModelTrainOptions,
+	#[buffalo(id = 2, required)]
+	pub feature_groups: Vec<FeatureGroup>,
+	#[buffalo(id = 3, required)]
+	pub losses: Option<Vec<f32>>,
+	#[buffalo(id = 4, required)]
+	pub feature_importances: Vec<f32>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 0)]
+pub enum RankingComparisonMetric {
+	#[buffalo(id = 0)]
+	Ndcg,
+	#[buffalo(id = 1)]
+	Map,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct RankingMetrics {
+	#[buffalo(id = 0, required)]
+	pub ndcg: f32,
+	#[buffalo(id = 1, required)]
+	pub map: f32,
+	#[buffalo(id = 2, required)]
+	pub n_groups: u64,
+}
@@ -1,6 +1,6 @@
 use crate::{
-	ColumnStats, FeatureGroup, LinearModelTrainOptions, StatsSettings, TrainGridItemOutput,
-	TreeModelTrainOptions,
+	ColumnStats, DataQualityIssue, DroppedColumn, Explanations, FeatureGroup,
+	LinearModelTrainOptions, StatsSettings, TrainGridItemOutput, TreeModelTrainOptions,
 };
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -40,6 +40,45 @@ pub struct Regressor {
 	pub model: RegressionModel,
 	#[buffalo(id = 16, required)]
 	pub test_metrics: RegressionMetrics,
+	/// This is absent for models trained before explanations were computed during training, and `None` for models rewritten with `strip`.
+	#[buffalo(id = 17)]
+	pub explanations: Option<Explanations>,
+	/// If this is present, the model predicts the target column transformed with this transform, and its predictions must be transformed back to the original scale.
+	#[buffalo(id = 18)]
+	pub target_transform: Option<TargetTransform>,
+	/// These are the columns that feature selection dropped before training. This is absent for models trained before feature selection was added, and empty for models rewritten with `strip`.
+	#[buffalo(id = 19)]
+	pub dropped_columns: Vec<DroppedColumn>,
+	/// These are the issues found by the target leakage and data quality checks. This is absent for models trained before the checks were added, and empty for models rewritten with `strip`.
+	#[buffalo(id = 20)]
+	pub data_quality_issues: Vec<DataQualityIssue>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 8)]
+pub enum TargetTransform {
+	#[buffalo(id = 0)]
+	Log1p,
+	#[buffalo(id = 1)]
+	BoxCox(BoxCoxTargetTransform),
+	#[buffalo(id = 2)]
+	Standardize(StandardizeTargetTransform),
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct BoxCoxTargetTransform {
+	#[buffalo(id = 0, required)]
+	pub lambda: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct StandardizeTargetTransform {
+	#[buffalo(id = 0, required)]
+	pub mean: f32,
+	#[buffalo(id = 1, required)]
+	pub std: f32,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
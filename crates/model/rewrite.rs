@@ -0,0 +1,1935 @@
+use crate::*;
+use anyhow::Result;
+use fnv::FnvHashSet;
+use ndarray::Axis;
+use num::ToPrimitive;
+
+/// These options control how `rewrite` changes a model as it copies it.
+#[derive(Clone, Debug, Default)]
+pub struct RewriteOptions {
+	/// If this is true, bag of words features that the model does not use are removed along with their ngrams. Tree models do not use features that no branch splits on, and linear models do not use features whose weights are all zero. Bag of words features with the tf-idf strategy are never removed, because each of their values depends on all of the group's ngrams.
+	pub prune_features: bool,
+	/// If this is `Some`, tree leaf values and linear weights are stored quantized, which makes the model smaller at the cost of the precision of its predictions.
+	pub quantization: Option<Quantization>,
+	/// If this is true, the parts of the model that only describe training are dropped, leaving only what is needed to make predictions. These are the overall and test column stats, histograms and top ngrams in the remaining column stats, the grid items other than the best one, the losses, the explanations, the columns dropped by feature selection, and the issues found by the data quality checks.
+	pub strip: bool,
+}
+
+/// This enum selects how many bits `rewrite` uses to store each tree leaf value and linear weight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantization {
+	/// Store each value as a 16 bit float.
+	F16,
+	/// Store each value as an 8 bit integer, which is multiplied by a scale stored with the tree or weights.
+	I8,
+}
+
+/// Read the .modelfox file in `bytes`, copy it to a new .modelfox file while applying `options`, and return the bytes of the new file.
+pub fn rewrite(bytes: &[u8], options: &RewriteOptions) -> Result<Vec<u8>> {
+	let model = from_bytes(bytes)?;
+	let mut writer = buffalo::Writer::new();
+	let model = copy_model(model, &mut writer, options);
+	writer.write(&model);
+	let model_bytes = writer.into_bytes();
+	// Quantized and stripped models require revision 1, and other models require whatever their contents do.
+	let revision = if options.quantization.is_some() || options.strip {
+		CURRENT_REVISION
+	} else {
+		required_revision(buffalo::read::<ModelReader>(&model_bytes))
+	};
+	let mut bytes = Vec::with_capacity(MAGIC_NUMBER.len() + 4 + model_bytes.len());
+	bytes.extend_from_slice(MAGIC_NUMBER);
+	bytes.extend_from_slice(&revision.to_le_bytes());
+	bytes.extend_from_slice(&model_bytes);
+	Ok(bytes)
+}
+
+fn copy_model(
+	model: ModelReader,
+	writer: &mut buffalo::Writer,
+	options: &RewriteOptions,
+) -> buffalo::Position<ModelWriter> {
+	let id = writer.write(model.id());
+	let version = writer.write(model.version());
+	let date = writer.write(model.date());
+	let inner = copy_model_inner(model.inner(), writer, options);
+	let parent_id = model
+		.parent_id()
+		.flatten()
+		.map(|parent_id| writer.write(parent_id));
+	writer.write(&ModelWriter {
+		id,
+		version,
+		date,
+		inner,
+		parent_id,
+	})
+}
+
+fn copy_model_inner(
+	model_inner: ModelInnerReader,
+	writer: &mut buffalo::Writer,
+	options: &RewriteOptions,
+) -> ModelInnerWriter {
+	match model_inner {
+		ModelInnerReader::Regressor(regressor) => {
+			let regressor = copy_regressor(regressor.read(), writer, options);
+			ModelInnerWriter::Regressor(regressor)
+		}
+		ModelInnerReader::BinaryClassifier(binary_classifier) => {
+			let binary_classifier =
+				copy_binary_classifier(binary_classifier.read(), writer, options);
+			ModelInnerWriter::BinaryClassifier(binary_classifier)
+		}
+		ModelInnerReader::MulticlassClassifier(multiclass_classifier) => {
+			let multiclass_classifier =
+				copy_multiclass_classifier(multiclass_classifier.read(), writer, options);
+			ModelInnerWriter::MulticlassClassifier(multiclass_classifier)
+		}
+		ModelInnerReader::Ranker(ranker) => {
+			let ranker = copy_ranker(ranker.read(), writer, options);
+			ModelInnerWriter::Ranker(ranker)
+		}
+	}
+}
+
+fn copy_regressor(
+	regressor: RegressorReader,
+	writer: &mut buffalo::Writer,
+	options: &RewriteOptions,
+) -> buffalo::Position<RegressorWriter> {
+	let target_column_name = writer.write(regressor.target_column_name());
+	let stats_settings = copy_stats_settings(regressor.stats_settings(), writer);
+	let overall_column_stats = if options.strip {
+		Vec::new()
+	} else {
+		regressor
+			.overall_column_stats()
+			.iter()
+			.map(|column_stats| copy_column_stats(column_stats, writer, options))
+			.collect::<Vec<_>>()
+	};
+	let overall_column_stats = writer.write(&overall_column_stats);
+	let overall_target_column_stats =
+		copy_column_stats(regressor.overall_target_column_stats(), writer, options);
+	let train_column_stats = regressor
+		.train_column_stats()
+		.iter()
+		.map(|column_stats| copy_column_stats(column_stats, writer, options))
+		.collect::<Vec<_>>();
+	let train_column_stats = writer.write(&train_column_stats);
+	let train_target_column_stats =
+		copy_column_stats(regressor.train_target_column_stats(), writer, options);
+	let test_column_stats = if options.strip {
+		Vec::new()
+	} else {
+		regressor
+			.test_column_stats()
+			.iter()
+			.map(|column_stats| copy_column_stats(column_stats, writer, options))
+			.collect::<Vec<_>>()
+	};
+	let test_column_stats = writer.write(&test_column_stats);
+	let test_target_column_stats =
+		copy_column_stats(regressor.test_target_column_stats(), writer, options);
+	let baseline_metrics = copy_regression_metrics(regressor.baseline_metrics(), writer);
+	let comparison_metric = match regressor.comparison_metric() {
+		RegressionComparisonMetricReader::MeanAbsoluteError(_) => {
+			RegressionComparisonMetricWriter::MeanAbsoluteError
+		}
+		RegressionComparisonMetricReader::MeanSquaredError(_) => {
+			RegressionComparisonMetricWriter::MeanSquaredError
+		}
+		RegressionComparisonMetricReader::RootMeanSquaredError(_) => {
+			RegressionComparisonMetricWriter::RootMeanSquaredError
+		}
+		RegressionComparisonMetricReader::R2(_) => RegressionComparisonMetricWriter::R2,
+	};
+	// When stripping, only the best grid item is kept, so it becomes the first.
+	let best_grid_item_index = regressor.best_grid_item_index();
+	let train_grid_item_outputs = regressor
+		.train_grid_item_outputs()
+		.iter()
+		.enumerate()
+		.filter(|(index, _)| !options.strip || index.to_u64().unwrap() == best_grid_item_index)
+		.map(|(_, train_grid_item_output)| {
+			copy_train_grid_item_output(train_grid_item_output, writer)
+		})
+		.collect::<Vec<_>>();
+	let best_grid_item_index = if options.strip {
+		0
+	} else {
+		best_grid_item_index
+	};
+	let train_grid_item_outputs = writer.write(&train_grid_item_outputs);
+	let (model, keep_features) = match regressor.model() {
+		RegressionModelReader::Linear(model) => {
+			let model = model.read();
+			let train_options = copy_linear_train_options(model.train_options(), writer);
+			let feature_groups = model.feature_groups().iter().collect::<Vec<_>>();
+			let mut linear_model = modelfox_linear::Regressor::from_reader(model.model());
+			let keep_features = keep_features(&feature_groups, options, |feature_index| {
+				linear_model.weights[feature_index] != 0.0
+			});
+			linear_model.weights =
+				filter_features(linear_model.weights.iter().cloned(), &keep_features).into();
+			linear_model.means =
+				filter_features(linear_model.means.iter().cloned(), &keep_features);
+			let feature_groups = copy_feature_groups(&feature_groups, &keep_features, writer);
+			let feature_groups = writer.write(&feature_groups);
+			let losses = if options.strip {
+				None
+			} else {
+				model
+					.losses()
+					.map(|losses| losses.iter().collect::<Vec<_>>())
+			};
+			let losses = losses.map(|losses| writer.write(losses.as_slice()));
+			let feature_importances =
+				filter_features(model.feature_importances().iter(), &keep_features);
+			let feature_importances = writer.write(feature_importances.as_slice());
+			let model =
+				linear_model.to_writer_with_weight_encoding(writer, weight_encoding(options));
+			let model = writer.write(&LinearRegressorWriter {
+				model,
+				train_options,
+				feature_groups,
+				losses,
+				feature_importances,
+			});
+			(RegressionModelWriter::Linear(model), keep_features)
+		}
+		RegressionModelReader::Tree(model) => {
+			let model = model.read();
+			let train_options = copy_tree_train_options(model.train_options(), writer);
+			let feature_groups = model.feature_groups().iter().collect::<Vec<_>>();
+			let mut tree_model = modelfox_tree::Regressor::from_reader(model.model());
+			let split_features = split_features(tree_model.trees.iter());
+			let keep_features = keep_features(&feature_groups, options, |feature_index| {
+				split_features.contains(&feature_index)
+			});
+			remap_split_features(tree_model.trees.iter_mut(), &keep_features);
+			let feature_groups = copy_feature_groups(&feature_groups, &keep_features, writer);
+			let feature_groups = writer.write(&feature_groups);
+			let losses = if options.strip {
+				None
+			} else {
+				model
+					.losses()
+					.map(|losses| losses.iter().collect::<Vec<_>>())
+			};
+			let losses = losses.map(|losses| writer.write(losses.as_slice()));
+			let feature_importances =
+				filter_features(model.feature_importances().iter(), &keep_features);
+			let feature_importances = writer.write(feature_importances.as_slice());
+			let model =
+				tree_model.to_writer_with_leaf_value_encoding(writer, leaf_value_encoding(options));
+			let model = writer.write(&TreeRegressorWriter {
+				model,
+				train_options,
+				feature_groups,
+				losses,
+				feature_importances,
+			});
+			(RegressionModelWriter::Tree(model), keep_features)
+		}
+	};
+	let test_metrics = copy_regression_metrics(regressor.test_metrics(), writer);
+	let explanations = if options.strip {
+		None
+	} else {
+		regressor
+			.explanations()
+			.flatten()
+			.map(|explanations| copy_explanations(explanations, &keep_features, writer))
+	};
+	let target_transform = regressor
+		.target_transform()
+		.flatten()
+		.map(|target_transform| copy_target_transform(target_transform, writer));
+	let dropped_columns = match regressor.dropped_columns() {
+		Some(dropped_columns) if !options.strip => {
+			copy_dropped_columns(dropped_columns.iter(), writer)
+		}
+		_ => copy_dropped_columns(std::iter::empty(), writer),
+	};
+	let data_quality_issues = match regressor.data_quality_issues() {
+		Some(data_quality_issues) if !options.strip => {
+			copy_data_quality_issues(data_quality_issues.iter(), writer)
+		}
+		_ => copy_data_quality_issues(std::iter::empty(), writer),
+	};
+	writer.write(&RegressorWriter {
+		target_column_name,
+		train_row_count: regressor.train_row_count(),
+		test_row_count: regressor.test_row_count(),
+		overall_row_count: regressor.overall_row_count(),
+		stats_settings,
+		overall_column_stats,
+		overall_target_column_stats,
+		train_column_stats,
+		train_target_column_stats,
+		test_column_stats,
+		test_target_column_stats,
+		baseline_metrics,
+		comparison_metric,
+		train_grid_item_outputs,
+		best_grid_item_index,
+		model,
+		test_metrics,
+		explanations,
+		target_transform,
+		dropped_columns,
+		data_quality_issues,
+	})
+}
+
+fn copy_target_transform(
+	target_transform: TargetTransformReader,
+	writer: &mut buffalo::Writer,
+) -> TargetTransformWriter {
+	match target_transform {
+		TargetTransformReader::Log1p(_) => TargetTransformWriter::Log1p,
+		TargetTransformReader::BoxCox(target_transform) => {
+			TargetTransformWriter::BoxCox(writer.write(&BoxCoxTargetTransformWriter {
+				lambda: target_transform.read().lambda(),
+			}))
+		}
+		TargetTransformReader::Standardize(target_transform) => {
+			let target_transform = target_transform.read();
+			TargetTransformWriter::Standardize(writer.write(&StandardizeTargetTransformWriter {
+				mean: target_transform.mean(),
+				std: target_transform.std(),
+			}))
+		}
+	}
+}
+
+fn copy_binary_classifier(
+	binary_classifier: BinaryClassifierReader,
+	writer: &mut buffalo::Writer,
+	options: &RewriteOptions,
+) -> buffalo::Position<BinaryClassifierWriter> {
+	let target_column_name = writer.write(binary_classifier.target_column_name());
+	let negative_class = writer.write(binary_classifier.negative_class());
+	let positive_class = writer.write(binary_classifier.positive_class());
+	let stats_settings = copy_stats_settings(binary_classifier.stats_settings(), writer);
+	let overall_column_stats = if options.strip {
+		Vec::new()
+	} else {
+		binary_classifier
+			.overall_column_stats()
+			.iter()
+			.map(|column_stats| copy_column_stats(column_stats, writer, options))
+			.collect::<Vec<_>>()
+	};
+	let overall_column_stats = writer.write(&overall_column_stats);
+	let overall_target_column_stats = copy_column_stats(
+		binary_classifier.overall_target_column_stats(),
+		writer,
+		options,
+	);
+	let train_column_stats = binary_classifier
+		.train_column_stats()
+		.iter()
+		.map(|column_stats| copy_column_stats(column_stats, writer, options))
+		.collect::<Vec<_>>();
+	let train_column_stats = writer.write(&train_column_stats);
+	let train_target_column_stats = copy_column_stats(
+		binary_classifier.train_target_column_stats(),
+		writer,
+		options,
+	);
+	let test_column_stats = if options.strip {
+		Vec::new()
+	} else {
+		binary_classifier
+			.test_column_stats()
+			.iter()
+			.map(|column_stats| copy_column_stats(column_stats, writer, options))
+			.collect::<Vec<_>>()
+	};
+	let test_column_stats = writer.write(&test_column_stats);
+	let test_target_column_stats = copy_column_stats(
+		binary_classifier.test_target_column_stats(),
+		writer,
+		options,
+	);
+	let baseline_metrics =
+		copy_binary_classification_metrics(binary_classifier.baseline_metrics(), writer);
+	let comparison_metric = match binary_classifier.comparison_metric() {
+		BinaryClassificationComparisonMetricReader::Aucroc(_) => {
+			BinaryClassificationComparisonMetricWriter::Aucroc
+		}
+	};
+	// When stripping, only the best grid item is kept, so it becomes the first.
+	let best_grid_item_index = binary_classifier.best_grid_item_index();
+	let train_grid_item_outputs = binary_classifier
+		.train_grid_item_outputs()
+		.iter()
+		.enumerate()
+		.filter(|(index, _)| !options.strip || index.to_u64().unwrap() == best_grid_item_index)
+		.map(|(_, train_grid_item_output)| {
+			copy_train_grid_item_output(train_grid_item_output, writer)
+		})
+		.collect::<Vec<_>>();
+	let best_grid_item_index = if options.strip {
+		0
+	} else {
+		best_grid_item_index
+	};
+	let train_grid_item_outputs = writer.write(&train_grid_item_outputs);
+	let (model, keep_features) = match binary_classifier.model() {
+		BinaryClassificationModelReader::Linear(model) => {
+			let model = model.read();
+			let train_options = copy_linear_train_options(model.train_options(), writer);
+			let feature_groups = model.feature_groups().iter().collect::<Vec<_>>();
+			let mut linear_model = modelfox_linear::BinaryClassifier::from_reader(model.model());
+			let keep_features = keep_features(&feature_groups, options, |feature_index| {
+				linear_model.weights[feature_index] != 0.0
+			});
+			linear_model.weights =
+				filter_features(linear_model.weights.iter().cloned(), &keep_features).into();
+			linear_model.means =
+				filter_features(linear_model.means.iter().cloned(), &keep_features);
+			let feature_groups = copy_feature_groups(&feature_groups, &keep_features, writer);
+			let feature_groups = writer.write(&feature_groups);
+			let losses = if options.strip {
+				None
+			} else {
+				model
+					.losses()
+					.map(|losses| losses.iter().collect::<Vec<_>>())
+			};
+			let losses = losses.map(|losses| writer.write(losses.as_slice()));
+			let feature_importances =
+				filter_features(model.feature_importances().iter(), &keep_features);
+			let feature_importances = writer.write(feature_importances.as_slice());
+			let model =
+				linear_model.to_writer_with_weight_encoding(writer, weight_encoding(options));
+			let model = writer.write(&LinearBinaryClassifierWriter {
+				model,
+				train_options,
+				feature_groups,
+				losses,
+				feature_importances,
+			});
+			(
+				BinaryClassificationModelWriter::Linear(model),
+				keep_features,
+			)
+		}
+		BinaryClassificationModelReader::Tree(model) => {
+			let model = model.read();
+			let train_options = copy_tree_train_options(model.train_options(), writer);
+			let feature_groups = model.feature_groups().iter().collect::<Vec<_>>();
+			let mut tree_model = modelfox_tree::BinaryClassifier::from_reader(model.model());
+			let split_features = split_features(tree_model.trees.iter());
+			let keep_features = keep_features(&feature_groups, options, |feature_index| {
+				split_features.contains(&feature_index)
+			});
+			remap_split_features(tree_model.trees.iter_mut(), &keep_features);
+			let feature_groups = copy_feature_groups(&feature_groups, &keep_features, writer);
+			let feature_groups = writer.write(&feature_groups);
+			let losses = if options.strip {
+				None
+			} else {
+				model
+					.losses()
+					.map(|losses| losses.iter().collect::<Vec<_>>())
+			};
+			let losses = losses.map(|losses| writer.write(losses.as_slice()));
+			let feature_importances =
+				filter_features(model.feature_importances().iter(), &keep_features);
+			let feature_importances = writer.write(feature_importances.as_slice());
+			let model =
+				tree_model.to_writer_with_leaf_value_encoding(writer, leaf_value_encoding(options));
+			let model = writer.write(&TreeBinaryClassifierWriter {
+				model,
+				train_options,
+				feature_groups,
+				losses,
+				feature_importances,
+			});
+			(BinaryClassificationModelWriter::Tree(model), keep_features)
+		}
+	};
+	let test_metrics = copy_binary_classification_metrics(binary_classifier.test_metrics(), writer);
+	let threshold = binary_classifier.threshold().unwrap_or(DEFAULT_THRESHOLD);
+	let explanations = if options.strip {
+		None
+	} else {
+		binary_classifier
+			.explanations()
+			.flatten()
+			.map(|explanations| copy_explanations(explanations, &keep_features, writer))
+	};
+	let dropped_columns = match binary_classifier.dropped_columns() {
+		Some(dropped_columns) if !options.strip => {
+			copy_dropped_columns(dropped_columns.iter(), writer)
+		}
+		_ => copy_dropped_columns(std::iter::empty(), writer),
+	};
+	let data_quality_issues = match binary_classifier.data_quality_issues() {
+		Some(data_quality_issues) if !options.strip => {
+			copy_data_quality_issues(data_quality_issues.iter(), writer)
+		}
+		_ => copy_data_quality_issues(std::iter::empty(), writer),
+	};
+	writer.write(&BinaryClassifierWriter {
+		target_column_name,
+		negative_class,
+		positive_class,
+		train_row_count: binary_classifier.train_row_count(),
+		test_row_count: binary_classifier.test_row_count(),
+		overall_row_count: binary_classifier.overall_row_count(),
+		stats_settings,
+		overall_column_stats,
+		overall_target_column_stats,
+		train_column_stats,
+		train_target_column_stats,
+		test_column_stats,
+		test_target_column_stats,
+		baseline_metrics,
+		comparison_metric,
+		train_grid_item_outputs,
+		best_grid_item_index,
+		model,
+		test_metrics,
+		threshold,
+		explanations,
+		dropped_columns,
+		data_quality_issues,
+	})
+}
+
+fn copy_multiclass_classifier(
+	multiclass_classifier: MulticlassClassifierReader,
+	writer: &mut buffalo::Writer,
+	options: &RewriteOptions,
+) -> buffalo::Position<MulticlassClassifierWriter> {
+	let target_column_name = writer.write(multiclass_classifier.target_column_name());
+	let classes = multiclass_classifier
+		.classes()
+		.iter()
+		.map(|class| writer.write(class))
+		.collect::<Vec<_>>();
+	let classes = writer.write(&classes);
+	let stats_settings = copy_stats_settings(multiclass_classifier.stats_settings(), writer);
+	let overall_column_stats = if options.strip {
+		Vec::new()
+	} else {
+		multiclass_classifier
+			.overall_column_stats()
+			.iter()
+			.map(|column_stats| copy_column_stats(column_stats, writer, options))
+			.collect::<Vec<_>>()
+	};
+	let overall_column_stats = writer.write(&overall_column_stats);
+	let overall_target_column_stats = copy_column_stats(
+		multiclass_classifier.overall_target_column_stats(),
+		writer,
+		options,
+	);
+	let train_column_stats = multiclass_classifier
+		.train_column_stats()
+		.iter()
+		.map(|column_stats| copy_column_stats(column_stats, writer, options))
+		.collect::<Vec<_>>();
+	let train_column_stats = writer.write(&train_column_stats);
+	let train_target_column_stats = copy_column_stats(
+		multiclass_classifier.train_target_column_stats(),
+		writer,
+		options,
+	);
+	let test_column_stats = if options.strip {
+		Vec::new()
+	} else {
+		multiclass_classifier
+			.test_column_stats()
+			.iter()
+			.map(|column_stats| copy_column_stats(column_stats, writer, options))
+			.collect::<Vec<_>>()
+	};
+	let test_column_stats = writer.write(&test_column_stats);
+	let test_target_column_stats = copy_column_stats(
+		multiclass_classifier.test_target_column_stats(),
+		writer,
+		options,
+	);
+	let baseline_metrics =
+		copy_multiclass_classification_metrics(multiclass_classifier.baseline_metrics(), writer);
+	let comparison_metric = match multiclass_classifier.comparison_metric() {
+		MulticlassClassificationComparisonMetricReader::Accuracy(_) => {
+			MulticlassClassificationComparisonMetricWriter::Accuracy
+		}
+	};
+	// When stripping, only the best grid item is kept, so it becomes the first.
+	let best_grid_item_index = multiclass_classifier.best_grid_item_index();
+	let train_grid_item_outputs = multiclass_classifier
+		.train_grid_item_outputs()
+		.iter()
+		.enumerate()
+		.filter(|(index, _)| !options.strip || index.to_u64().unwrap() == best_grid_item_index)
+		.map(|(_, train_grid_item_output)| {
+			copy_train_grid_item_output(train_grid_item_output, writer)
+		})
+		.collect::<Vec<_>>();
+	let best_grid_item_index = if options.strip {
+		0
+	} else {
+		best_grid_item_index
+	};
+	let train_grid_item_outputs = writer.write(&train_grid_item_outputs);
+	let (model, keep_features) = match multiclass_classifier.model() {
+		MulticlassClassificationModelReader::Linear(model) => {
+			let model = model.read();
+			let train_options = copy_linear_train_options(model.train_options(), writer);
+			let feature_groups = model.feature_groups().iter().collect::<Vec<_>>();
+			let mut linear_model =
+				modelfox_linear::MulticlassClassifier::from_reader(model.model());
+			let keep_features = keep_features(&feature_groups, options, |feature_index| {
+				linear_model
+					.weights
+					.row(feature_index)
+					.iter()
+					.any(|weight| *weight != 0.0)
+			});
+			let kept_feature_indexes = filter_features(0..keep_features.len(), &keep_features);
+			linear_model.weights = linear_model.weights.select(Axis(0), &kept_feature_indexes);
+			linear_model.means =
+				filter_features(linear_model.means.iter().cloned(), &keep_features);
+			let feature_groups = copy_feature_groups(&feature_groups, &keep_features, writer);
+			let feature_groups = writer.write(&feature_groups);
+			let losses = if options.strip {
+				None
+			} else {
+				model
+					.losses()
+					.map(|losses| losses.iter().collect::<Vec<_>>())
+			};
+			let losses = losses.map(|losses| writer.write(losses.as_slice()));
+			let feature_importances =
+				filter_features(model.feature_importances().iter(), &keep_features);
+			let feature_importances = writer.write(feature_importances.as_slice());
+			let model =
+				linear_model.to_writer_with_weight_encoding(writer, weight_encoding(options));
+			let model = writer.write(&LinearMulticlassClassifierWriter {
+				model,
+				train_options,
+				feature_groups,
+				losses,
+				feature_importances,
+			});
+			(
+				MulticlassClassificationModelWriter::Linear(model),
+				keep_features,
+			)
+		}
+		MulticlassClassificationModelReader::Tree(model) => {
+			let model = model.read();
+			let train_options = copy_tree_train_options(model.train_options(), writer);
+			let feature_groups = model.feature_groups().iter().collect::<Vec<_>>();
+			let mut tree_model = modelfox_tree::MulticlassClassifier::from_reader(model.model());
+			let split_features = split_features(tree_model.trees.iter());
+			let keep_features = keep_features(&feature_groups, options, |feature_index| {
+				split_features.contains(&feature_index)
+			});
+			remap_split_features(tree_model.trees.iter_mut(), &keep_features);
+			let feature_groups = copy_feature_groups(&feature_groups, &keep_features, writer);
+			let feature_groups = writer.write(&feature_groups);
+			let losses = if options.strip {
+				None
+			} else {
+				model
+					.losses()
+					.map(|losses| losses.iter().collect::<Vec<_>>())
+			};
+			let losses = losses.map(|losses| writer.write(losses.as_slice()));
+			let feature_importances =
+				filter_features(model.feature_importances().iter(), &keep_features);
+			let feature_importances = writer.write(feature_importances.as_slice());
+			let model =
+				tree_model.to_writer_with_leaf_value_encoding(writer, leaf_value_encoding(options));
+			let model = writer.write(&TreeMulticlassClassifierWriter {
+				model,
+				train_options,
+				feature_groups,
+				losses,
+				feature_importances,
+			});
+			(
+				MulticlassClassificationModelWriter::Tree(model),
+				keep_features,
+			)
+		}
+	};
+	let test_metrics =
+		copy_multiclass_classification_metrics(multiclass_classifier.test_metrics(), writer);
+	let explanations = if options.strip {
+		None
+	} else {
+		multiclass_classifier
+			.explanations()
+			.flatten()
+			.map(|explanations| copy_explanations(explanations, &keep_features, writer))
+	};
+	let dropped_columns = match multiclass_classifier.dropped_columns() {
+		Some(dropped_columns) if !options.strip => {
+			copy_dropped_columns(dropped_columns.iter(), writer)
+		}
+		_ => copy_dropped_columns(std::iter::empty(), writer),
+	};
+	let data_quality_issues = match multiclass_classifier.data_quality_issues() {
+		Some(data_quality_issues) if !options.strip => {
+			copy_data_quality_issues(data_quality_issues.iter(), writer)
+		}
+		_ => copy_data_quality_issues(std::iter::empty(), writer),
+	};
+	writer.write(&MulticlassClassifierWriter {
+		target_column_name,
+		classes,
+		train_row_count: multiclass_classifier.train_row_count(),
+		test_row_count: multiclass_classifier.test_row_count(),
+		overall_row_count: multiclass_classifier.overall_row_count(),
+		stats_settings,
+		overall_column_stats,
+		overall_target_column_stats,
+		train_column_stats,
+		train_target_column_stats,
+		test_column_stats,
+		test_target_column_stats,
+		baseline_metrics,
+		comparison_metric,
+		train_grid_item_outputs,
+		best_grid_item_index,
+		model,
+		test_metrics,
+		explanations,
+		dropped_columns,
+		data_quality_issues,
+	})
+}
+
+fn copy_ranker(
+	ranker: RankerReader,
+	writer: &mut buffalo::Writer,
+	options: &RewriteOptions,
+) -> buffalo::Position<RankerWriter> {
+	let target_column_name = writer.write(ranker.target_column_name());
+	let group_column_name = writer.write(ranker.group_column_name());
+	let stats_settings = copy_stats_settings(ranker.stats_settings(), writer);
+	let overall_column_stats = if options.strip {
+		Vec::new()
+	} else {
+		ranker
+			.overall_column_stats()
+			.iter()
+			.map(|column_stats| copy_column_stats(column_stats, writer, options))
+			.collect::<Vec<_>>()
+	};
+	let overall_column_stats = writer.write(&overall_column_stats);
+	let overall_target_column_stats =
+		copy_column_stats(ranker.overall_target_column_stats(), writer, options);
+	let train_column_stats = ranker
+		.train_column_stats()
+		.iter()
+		.map(|column_stats| copy_column_stats(column_stats, writer, options))
+		.collect::<Vec<_>>();
+	let train_column_stats = writer.write(&train_column_stats);
+	let train_target_column_stats =
+		copy_column_stats(ranker.train_target_column_stats(), writer, options);
+	let test_column_stats = if options.strip {
+		Vec::new()
+	} else {
+		ranker
+			.test_column_stats()
+			.iter()
+			.map(|column_stats| copy_column_stats(column_stats, writer, options))
+			.collect::<Vec<_>>()
+	};
+	let test_column_stats = writer.write(&test_column_stats);
+	let test_target_column_stats =
+		copy_column_stats(ranker.test_target_column_stats(), writer, options);
+	let baseline_metrics = copy_ranking_metrics(ranker.baseline_metrics(), writer);
+	let comparison_metric = match ranker.comparison_metric() {
+		RankingComparisonMetricReader::Ndcg(_) => RankingComparisonMetricWriter::Ndcg,
+		RankingComparisonMetricReader::Map(_) => RankingComparisonMetricWriter::Map,
+	};
+	// When stripping, only the best grid item is kept, so it becomes the first.
+	let best_grid_item_index = ranker.best_grid_item_index();
+	let train_grid_item_outputs = ranker
+		.train_grid_item_outputs()
+		.iter()
+		.enumerate()
+		.filter(|(index, _)| !options.strip || index.to_u64().unwrap() == best_grid_item_index)
+		.map(|(_, train_grid_item_output)| {
+			copy_train_grid_item_output(train_grid_item_output, writer)
+		})
+		.collect::<Vec<_>>();
+	let best_grid_item_index = if options.strip {
+		0
+	} else {
+		best_grid_item_index
+	};
+	let train_grid_item_outputs = writer.write(&train_grid_item_outputs);
+	let (model, keep_features) = match ranker.model() {
+		RankingModelReader::Tree(model) => {
+			let model = model.read();
+			let train_options = copy_tree_train_options(model.train_options(), writer);
+			let feature_groups = model.feature_groups().iter().collect::<Vec<_>>();
+			let mut tree_model = modelfox_tree::Ranker::from_reader(model.model());
+			let split_features = split_features(tree_model.trees.iter());
+			let keep_features = keep_features(&feature_groups, options, |feature_index| {
+				split_features.contains(&feature_index)
+			});
+			remap_split_features(tree_model.trees.iter_mut(), &keep_features);
+			let feature_groups = copy_feature_groups(&feature_groups, &keep_features, writer);
+			let feature_groups = writer.write(&feature_groups);
+			let losses = if options.strip {
+				None
+			} else {
+				model
+					.losses()
+					.map(|losses| losses.iter().collect::<Vec<_>>())
+			};
+			let losses = losses.map(|losses| writer.write(losses.as_slice()));
+			let feature_importances =
+				filter_features(model.feature_importances().iter(), &keep_features);
+			let feature_importances = writer.write(feature_importances.as_slice());
+			let model =
+				tree_model.to_writer_with_leaf_value_encoding(writer, leaf_value_encoding(options));
+			let model = writer.write(&TreeRankerWriter {
+				model,
+				train_options,
+				feature_groups,
+				losses,
+				feature_importances,
+			});
+			(RankingModelWriter::Tree(model), keep_features)
+		}
+	};
+	let test_metrics = copy_ranking_metrics(ranker.test_metrics(), writer);
+	let explanations = if options.strip {
+		None
+	} else {
+		ranker
+			.explanations()
+			.flatten()
+			.map(|explanations| copy_explanations(explanations, &keep_features, writer))
+	};
+	let dropped_columns = match ranker.dropped_columns() {
+		Some(dropped_columns) if !options.strip => {
+			copy_dropped_columns(dropped_columns.iter(), writer)
+		}
+		_ => copy_dropped_columns(std::iter::empty(), writer),
+	};
+	let data_quality_issues = match ranker.data_quality_issues() {
+		Some(data_quality_issues) if !options.strip => {
+			copy_data_quality_issues(data_quality_issues.iter(), writer)
+		}
+		_ => copy_data_quality_issues(std::iter::empty(), writer),
+	};
+	writer.write(&RankerWriter {
+		target_column_name,
+		group_column_name,
+		train_row_count: ranker.train_row_count(),
+		test_row_count: ranker.test_row_count(),
+		overall_row_count: ranker.overall_row_count(),
+		stats_settings,
+		overall_column_stats,
+		overall_target_column_stats,
+		train_column_stats,
+		train_target_column_stats,
+		test_column_stats,
+		test_target_column_stats,
+		baseline_metrics,
+		comparison_metric,
+		train_grid_item_outputs,
+		best_grid_item_index,
+		model,
+		test_metrics,
+		ndcg_k: ranker.ndcg_k().flatten(),
+		explanations,
+		dropped_columns,
+		data_quality_issues,
+	})
+}
+
+fn copy_dropped_columns<'a>(
+	dropped_columns: impl Iterator<Item = DroppedColumnReader<'a>>,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<[buffalo::Position<DroppedColumnWriter>]> {
+	let dropped_columns = dropped_columns
+		.map(|dropped_column| {
+			let column_name = writer.write(dropped_column.column_name());
+			let reason = match dropped_column.reason() {
+				DroppedColumnReasonReader::NearConstant(reason) => {
+					DroppedColumnReasonWriter::NearConstant(writer.write(
+						&NearConstantDroppedColumnReasonWriter {
+							most_frequent_fraction: reason.read().most_frequent_fraction(),
+						},
+					))
+				}
+				DroppedColumnReasonReader::InvalidFraction(reason) => {
+					DroppedColumnReasonWriter::InvalidFraction(writer.write(
+						&InvalidFractionDroppedColumnReasonWriter {
+							invalid_fraction: reason.read().invalid_fraction(),
+						},
+					))
+				}
+				DroppedColumnReasonReader::Collinear(reason) => {
+					let reason = reason.read();
+					let column_name = writer.write(reason.column_name());
+					DroppedColumnReasonWriter::Collinear(writer.write(
+						&CollinearDroppedColumnReasonWriter {
+							column_name,
+							correlation: reason.correlation(),
+						},
+					))
+				}
+				DroppedColumnReasonReader::LowImportance(reason) => {
+					DroppedColumnReasonWriter::LowImportance(writer.write(
+						&LowImportanceDroppedColumnReasonWriter {
+							importance: reason.read().importance(),
+						},
+					))
+				}
+			};
+			writer.write(&DroppedColumnWriter {
+				column_name,
+				reason,
+			})
+		})
+		.collect::<Vec<_>>();
+	writer.write(&dropped_columns)
+}
+
+fn copy_data_quality_issues<'a>(
+	data_quality_issues: impl Iterator<Item = DataQualityIssueReader<'a>>,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<[DataQualityIssueWriter]> {
+	let data_quality_issues = data_quality_issues
+		.map(|data_quality_issue| match data_quality_issue {
+			DataQualityIssueReader::IdColumn(issue) => {
+				let issue = issue.read();
+				let column_name = writer.write(issue.column_name());
+				DataQualityIssueWriter::IdColumn(writer.write(&IdColumnDataQualityIssueWriter {
+					column_name,
+					unique_fraction: issue.unique_fraction(),
+				}))
+			}
+			DataQualityIssueReader::TargetLeakage(issue) => {
+				let issue = issue.read();
+				let column_name = writer.write(issue.column_name());
+				DataQualityIssueWriter::TargetLeakage(writer.write(
+					&TargetLeakageDataQualityIssueWriter {
+						column_name,
+						score: issue.score(),
+					},
+				))
+			}
+			DataQualityIssueReader::DuplicateRows(issue) => {
+				let issue = issue.read();
+				DataQualityIssueWriter::DuplicateRows(writer.write(
+					&DuplicateRowsDataQualityIssueWriter {
+						duplicate_count: issue.duplicate_count(),
+						duplicate_fraction: issue.duplicate_fraction(),
+					},
+				))
+			}
+			DataQualityIssueReader::DistributionMismatch(issue) => {
+				let issue = issue.read();
+				let column_name = issue
+					.column_name()
+					.map(|column_name| writer.write(column_name));
+				DataQualityIssueWriter::DistributionMismatch(writer.write(
+					&DistributionMismatchDataQualityIssueWriter {
+						auc_roc: issue.auc_roc(),
+						column_name,
+					},
+				))
+			}
+		})
+		.collect::<Vec<_>>();
+	writer.write(&data_quality_issues)
+}
+
+fn copy_stats_settings(
+	stats_settings: StatsSettingsReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<StatsSettingsWriter> {
+	writer.write(&StatsSettingsWriter {
+		number_histogram_max_size: stats_settings.number_histogram_max_size(),
+	})
+}
+
+fn copy_column_stats(
+	column_stats: ColumnStatsReader,
+	writer: &mut buffalo::Writer,
+	options: &RewriteOptions,
+) -> ColumnStatsWriter {
+	match column_stats {
+		ColumnStatsReader::UnknownColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			let column_name = writer.write(column_stats.column_name());
+			let column_stats = writer.write(&UnknownColumnStatsWriter { column_name });
+			ColumnStatsWriter::UnknownColumn(column_stats)
+		}
+		ColumnStatsReader::NumberColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			let column_name = writer.write(column_stats.column_name());
+			let histogram = column_stats
+				.histogram()
+				.filter(|_| !options.strip)
+				.map(|histogram| histogram.iter().collect::<Vec<_>>());
+			let histogram = histogram.map(|histogram| writer.write(histogram.as_slice()));
+			let column_stats = writer.write(&NumberColumnStatsWriter {
+				column_name,
+				invalid_count: column_stats.invalid_count(),
+				unique_count: column_stats.unique_count(),
+				histogram,
+				min: column_stats.min(),
+				max: column_stats.max(),
+				mean: column_stats.mean(),
+				variance: column_stats.variance(),
+				std: column_stats.std(),
+				p25: column_stats.p25(),
+				p50: column_stats.p50(),
+				p75: column_stats.p75(),
+			});
+			ColumnStatsWriter::NumberColumn(column_stats)
+		}
+		ColumnStatsReader::EnumColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			let column_name = writer.write(column_stats.column_name());
+			let histogram = column_stats
+				.histogram()
+				.iter()
+				.map(|(value, count)| (writer.write(value), count))
+				.collect::<Vec<_>>();
+			let histogram = writer.write(&histogram);
+			let column_stats = writer.write(&EnumColumnStatsWriter {
+				column_name,
+				invalid_count: column_stats.invalid_count(),
+				histogram,
+				unique_count: column_stats.unique_count(),
+			});
+			ColumnStatsWriter::EnumColumn(column_stats)
+		}
+		ColumnStatsReader::TextColumn(column_stats) => {
+			let column_stats = column_stats.read();
+			let column_name = writer.write(column_stats.column_name());
+			let tokenizer = copy_tokenizer(column_stats.tokenizer(), writer);
+			let ngram_types = column_stats
+				.ngram_types()
+				.iter()
+				.map(copy_ngram_type)
+				.collect::<Vec<_>>();
+			let ngram_types = writer.write(&ngram_types);
+			let top_ngrams = column_stats
+				.top_ngrams()
+				.iter()
+				.filter(|_| !options.strip)
+				.map(|(ngram, entry)| {
+					let ngram = copy_ngram(ngram, writer);
+					let entry = writer.write(&TextColumnStatsTopNGramsEntryWriter {
+						row_count: entry.row_count(),
+						occurrence_count: entry.occurrence_count(),
+					});
+					(ngram, entry)
+				})
+				.collect::<Vec<_>>();
+			let top_ngrams = writer.write(&top_ngrams);
+			let column_stats = writer.write(&TextColumnStatsWriter {
+				column_name,
+				tokenizer,
+				ngram_types,
+				ngrams_count: column_stats.ngrams_count(),
+				top_ngrams,
+			});
+			ColumnStatsWriter::TextColumn(column_stats)
+		}
+	}
+}
+
+fn copy_tokenizer(
+	tokenizer: TokenizerReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<TokenizerWriter> {
+	let stop_words = tokenizer
+		.stop_words()
+		.map(|stop_words| {
+			stop_words
+				.iter()
+				.map(|stop_word| writer.write(stop_word))
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default();
+	let stop_words = writer.write(&stop_words);
+	let stemmer = tokenizer.stemmer().flatten().map(|stemmer| match stemmer {
+		StemmerReader::English(_) => StemmerWriter::English,
+	});
+	let token_pattern = tokenizer
+		.token_pattern()
+		.flatten()
+		.map(|token_pattern| writer.write(token_pattern));
+	writer.write(&TokenizerWriter {
+		lowercase: tokenizer.lowercase(),
+		alphanumeric: tokenizer.alphanumeric(),
+		nfkc: tokenizer.nfkc().unwrap_or(false),
+		fold_accents: tokenizer.fold_accents().unwrap_or(false),
+		stop_words,
+		stemmer,
+		token_pattern,
+	})
+}
+
+fn copy_impute(
+	impute: ImputeReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<ImputeWriter> {
+	let strategy = match impute.strategy() {
+		ImputeStrategyReader::Mean(_) => ImputeStrategyWriter::Mean,
+		ImputeStrategyReader::Median(_) => ImputeStrategyWriter::Median,
+		ImputeStrategyReader::Mode(_) => ImputeStrategyWriter::Mode,
+		ImputeStrategyReader::Constant(_) => ImputeStrategyWriter::Constant,
+	};
+	writer.write(&ImputeWriter {
+		strategy,
+		value: impute.value(),
+	})
+}
+
+fn copy_ngram_type(ngram_type: NGramTypeReader) -> NGramTypeWriter {
+	match ngram_type {
+		NGramTypeReader::Unigram(_) => NGramTypeWriter::Unigram,
+		NGramTypeReader::Bigram(_) => NGramTypeWriter::Bigram,
+		NGramTypeReader::CharacterTrigram(_) => NGramTypeWriter::CharacterTrigram,
+		NGramTypeReader::CharacterQuadgram(_) => NGramTypeWriter::CharacterQuadgram,
+		NGramTypeReader::CharacterPentagram(_) => NGramTypeWriter::CharacterPentagram,
+	}
+}
+
+fn copy_ngram(ngram: NGramReader, writer: &mut buffalo::Writer) -> buffalo::Position<NGramWriter> {
+	match ngram {
+		NGramReader::Unigram(token) => {
+			let token = writer.write(token.read());
+			writer.write(&NGramWriter::Unigram(token))
+		}
+		NGramReader::Bigram(bigram) => {
+			let bigram = bigram.read();
+			let token_a = writer.write(bigram.0);
+			let token_b = writer.write(bigram.1);
+			writer.write(&NGramWriter::Bigram((token_a, token_b)))
+		}
+		NGramReader::Character(ngram) => {
+			let ngram = writer.write(ngram.read());
+			writer.write(&NGramWriter::Character(ngram))
+		}
+	}
+}
+
+fn copy_regression_metrics(
+	metrics: RegressionMetricsReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<RegressionMetricsWriter> {
+	writer.write(&RegressionMetricsWriter {
+		mse: metrics.mse(),
+		rmse: metrics.rmse(),
+		mae: metrics.mae(),
+		r2: metrics.r2(),
+	})
+}
+
+fn copy_ranking_metrics(
+	metrics: RankingMetricsReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<RankingMetricsWriter> {
+	writer.write(&RankingMetricsWriter {
+		ndcg: metrics.ndcg(),
+		map: metrics.map(),
+		n_groups: metrics.n_groups(),
+	})
+}
+
+fn copy_binary_classification_metrics(
+	metrics: BinaryClassificationMetricsReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<BinaryClassificationMetricsWriter> {
+	let default_threshold =
+		copy_binary_classification_metrics_for_threshold(metrics.default_threshold(), writer);
+	let thresholds = metrics
+		.thresholds()
+		.iter()
+		.map(|metrics| copy_binary_classification_metrics_for_threshold(metrics, writer))
+		.collect::<Vec<_>>();
+	let thresholds = writer.write(&thresholds);
+	writer.write(&BinaryClassificationMetricsWriter {
+		auc_roc: metrics.auc_roc(),
+		default_threshold,
+		thresholds,
+	})
+}
+
+fn copy_binary_classification_metrics_for_threshold(
+	metrics: BinaryClassificationMetricsForThresholdReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<BinaryClassificationMetricsForThresholdWriter> {
+	writer.write(&BinaryClassificationMetricsForThresholdWriter {
+		threshold: metrics.threshold(),
+		true_positives: metrics.true_positives(),
+		false_positives: metrics.false_positives(),
+		true_negatives: metrics.true_negatives(),
+		false_negatives: metrics.false_negatives(),
+		accuracy: metrics.accuracy(),
+		precision: metrics.precision(),
+		recall: metrics.recall(),
+		f1_score: metrics.f1_score(),
+		true_positive_rate: metrics.true_positive_rate(),
+		false_positive_rate: metrics.false_positive_rate(),
+	})
+}
+
+fn copy_multiclass_classification_metrics(
+	metrics: MulticlassClassificationMetricsReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<MulticlassClassificationMetricsWriter> {
+	let class_metrics = metrics
+		.class_metrics()
+		.iter()
+		.map(|class_metrics| {
+			writer.write(&ClassMetricsWriter {
+				true_positives: class_metrics.true_positives(),
+				false_positives: class_metrics.false_positives(),
+				true_negatives: class_metrics.true_negatives(),
+				false_negatives: class_metrics.false_negatives(),
+				accuracy: class_metrics.accuracy(),
+				precision: class_metrics.precision(),
+				recall: class_metrics.recall(),
+				f1_score: class_metrics.f1_score(),
+			})
+		})
+		.collect::<Vec<_>>();
+	let class_metrics = writer.write(&class_metrics);
+	writer.write(&MulticlassClassificationMetricsWriter {
+		class_metrics,
+		accuracy: metrics.accuracy(),
+		precision_unweighted: metrics.precision_unweighted(),
+		precision_weighted: metrics.precision_weighted(),
+		recall_unweighted: metrics.recall_unweighted(),
+		recall_weighted: metrics.recall_weighted(),
+	})
+}
+
+fn copy_explanations(
+	explanations: ExplanationsReader,
+	keep_features: &[bool],
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<ExplanationsWriter> {
+	let shap_feature_importances = filter_features(
+		explanations.shap_feature_importances().iter(),
+		keep_features,
+	);
+	let shap_feature_importances = writer.write(shap_feature_importances.as_slice());
+	let shap_column_importances = explanations
+		.shap_column_importances()
+		.iter()
+		.map(|(column_name, importance)| (writer.write(column_name), importance))
+		.collect::<Vec<_>>();
+	let shap_column_importances = writer.write(&shap_column_importances);
+	let partial_dependences = explanations
+		.partial_dependences()
+		.iter()
+		.map(|partial_dependence| {
+			let column_name = writer.write(partial_dependence.column_name());
+			let class = partial_dependence.class().map(|class| writer.write(class));
+			let grid = match partial_dependence.grid() {
+				PartialDependenceGridReader::Number(grid) => {
+					let values = grid.read().values().iter().collect::<Vec<_>>();
+					let values = writer.write(values.as_slice());
+					let grid = writer.write(&NumberPartialDependenceGridWriter { values });
+					PartialDependenceGridWriter::Number(grid)
+				}
+				PartialDependenceGridReader::Enum(grid) => {
+					let variants = grid
+						.read()
+						.variants()
+						.iter()
+						.map(|variant| writer.write(variant))
+						.collect::<Vec<_>>();
+					let variants = writer.write(&variants);
+					let grid = writer.write(&EnumPartialDependenceGridWriter { variants });
+					PartialDependenceGridWriter::Enum(grid)
+				}
+			};
+			let average = partial_dependence.average().iter().collect::<Vec<_>>();
+			let average = writer.write(average.as_slice());
+			let individual = partial_dependence
+				.individual()
+				.iter()
+				.map(|individual| {
+					let values = individual.values().iter().collect::<Vec<_>>();
+					let values = writer.write(values.as_slice());
+					writer.write(&IndividualConditionalExpectationWriter { values })
+				})
+				.collect::<Vec<_>>();
+			let individual = writer.write(&individual);
+			writer.write(&PartialDependenceWriter {
+				column_name,
+				class,
+				grid,
+				average,
+				individual,
+			})
+		})
+		.collect::<Vec<_>>();
+	let partial_dependences = writer.write(&partial_dependences);
+	let feature_interactions =
+		explanations
+			.feature_interactions()
+			.flatten()
+			.map(|feature_interactions| {
+				let feature_interactions = feature_interactions
+					.iter()
+					.map(|feature_interaction| {
+						let feature_name_a = writer.write(feature_interaction.feature_name_a());
+						let feature_name_b = writer.write(feature_interaction.feature_name_b());
+						writer.write(&FeatureInteractionWriter {
+							feature_name_a,
+							feature_name_b,
+							value: feature_interaction.value(),
+						})
+					})
+					.collect::<Vec<_>>();
+				writer.write(&feature_interactions)
+			});
+	writer.write(&ExplanationsWriter {
+		shap_feature_importances,
+		shap_column_importances,
+		partial_dependences,
+		feature_interactions,
+	})
+}
+
+fn copy_train_grid_item_output(
+	train_grid_item_output: TrainGridItemOutputReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<TrainGridItemOutputWriter> {
+	let hyperparameters = match train_grid_item_output.hyperparameters() {
+		ModelTrainOptionsReader::Linear(train_options) => {
+			let train_options = copy_linear_train_options(train_options.read(), writer);
+			ModelTrainOptionsWriter::Linear(train_options)
+		}
+		ModelTrainOptionsReader::Tree(train_options) => {
+			let train_options = copy_tree_train_options(train_options.read(), writer);
+			ModelTrainOptionsWriter::Tree(train_options)
+		}
+	};
+	writer.write(&TrainGridItemOutputWriter {
+		hyperparameters,
+		comparison_metric_value: train_grid_item_output.comparison_metric_value(),
+		duration: train_grid_item_output.duration(),
+	})
+}
+
+fn copy_linear_train_options(
+	train_options: LinearModelTrainOptionsReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<LinearModelTrainOptionsWriter> {
+	let early_stopping_options =
+		train_options
+			.early_stopping_options()
+			.map(|early_stopping_options| {
+				writer.write(&LinearEarlyStoppingOptionsWriter {
+					early_stopping_fraction: early_stopping_options.early_stopping_fraction(),
+					n_rounds_without_improvement_to_stop: early_stopping_options
+						.n_rounds_without_improvement_to_stop(),
+					min_decrease_in_loss_for_significant_change: early_stopping_options
+						.min_decrease_in_loss_for_significant_change(),
+				})
+			});
+	let optimizer = train_options
+		.optimizer()
+		.map(|optimizer| copy_linear_optimizer(optimizer, writer))
+		.unwrap_or(LinearOptimizerWriter::Sgd);
+	let learning_rate_schedule = train_options
+		.learning_rate_schedule()
+		.map(|learning_rate_schedule| {
+			copy_linear_learning_rate_schedule(learning_rate_schedule, writer)
+		})
+		.unwrap_or(LinearLearningRateScheduleWriter::Constant);
+	writer.write(&LinearModelTrainOptionsWriter {
+		compute_loss: train_options.compute_loss(),
+		l2_regularization: train_options.l2_regularization(),
+		learning_rate: train_options.learning_rate(),
+		max_epochs: train_options.max_epochs(),
+		n_examples_per_batch: train_options.n_examples_per_batch(),
+		early_stopping_options,
+		l1_regularization: train_options.l1_regularization().unwrap_or(0.0),
+		optimizer,
+		learning_rate_schedule,
+	})
+}
+
+fn copy_linear_optimizer(
+	optimizer: LinearOptimizerReader,
+	writer: &mut buffalo::Writer,
+) -> LinearOptimizerWriter {
+	match optimizer {
+		LinearOptimizerReader::Sgd(_) => LinearOptimizerWriter::Sgd,
+		LinearOptimizerReader::Momentum(optimizer) => {
+			LinearOptimizerWriter::Momentum(writer.write(&MomentumOptimizerWriter {
+				momentum: optimizer.read().momentum(),
+			}))
+		}
+		LinearOptimizerReader::Adam(optimizer) => {
+			let optimizer = optimizer.read();
+			LinearOptimizerWriter::Adam(writer.write(&AdamOptimizerWriter {
+				beta_1: optimizer.beta_1(),
+				beta_2: optimizer.beta_2(),
+				epsilon: optimizer.epsilon(),
+			}))
+		}
+	}
+}
+
+fn copy_linear_learning_rate_schedule(
+	learning_rate_schedule: LinearLearningRateScheduleReader,
+	writer: &mut buffalo::Writer,
+) -> LinearLearningRateScheduleWriter {
+	match learning_rate_schedule {
+		LinearLearningRateScheduleReader::Constant(_) => LinearLearningRateScheduleWriter::Constant,
+		LinearLearningRateScheduleReader::InverseTimeDecay(learning_rate_schedule) => {
+			LinearLearningRateScheduleWriter::InverseTimeDecay(writer.write(
+				&InverseTimeDecayLearningRateScheduleWriter {
+					decay_rate: learning_rate_schedule.read().decay_rate(),
+				},
+			))
+		}
+		LinearLearningRateScheduleReader::ExponentialDecay(learning_rate_schedule) => {
+			LinearLearningRateScheduleWriter::ExponentialDecay(writer.write(
+				&ExponentialDecayLearningRateScheduleWriter {
+					decay_rate: learning_rate_schedule.read().decay_rate(),
+				},
+			))
+		}
+	}
+}
+
+fn copy_tree_train_options(
+	train_options: TreeModelTrainOptionsReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<TreeModelTrainOptionsWriter> {
+	let early_stopping_options =
+		train_options
+			.early_stopping_options()
+			.map(|early_stopping_options| {
+				writer.write(&TreeEarlyStoppingOptionsWriter {
+					early_stopping_fraction: early_stopping_options.early_stopping_fraction(),
+					n_rounds_without_improvement_to_stop: early_stopping_options
+						.n_rounds_without_improvement_to_stop(),
+					min_decrease_in_loss_for_significant_change: early_stopping_options
+						.min_decrease_in_loss_for_significant_change(),
+				})
+			});
+	let goss_options = train_options.goss_options().flatten().map(|goss_options| {
+		writer.write(&TreeGossOptionsWriter {
+			top_rate: goss_options.top_rate(),
+			other_rate: goss_options.other_rate(),
+		})
+	});
+	let dart_options = train_options.dart_options().flatten().map(|dart_options| {
+		writer.write(&TreeDartOptionsWriter {
+			drop_rate: dart_options.drop_rate(),
+			max_drop: dart_options.max_drop(),
+			skip_drop: dart_options.skip_drop(),
+		})
+	});
+	// Models trained before the sampling options were added were trained with their defaults.
+	let defaults = modelfox_tree::TrainOptions::default();
+	let binned_features_layout = match train_options.binned_features_layout() {
+		BinnedFeaturesLayoutReader::RowMajor(_) => BinnedFeaturesLayoutWriter::RowMajor,
+		BinnedFeaturesLayoutReader::ColumnMajor(_) => BinnedFeaturesLayoutWriter::ColumnMajor,
+	};
+	writer.write(&TreeModelTrainOptionsWriter {
+		binned_features_layout,
+		compute_loss: train_options.compute_loss(),
+		early_stopping_options,
+		l2_regularization_for_continuous_splits: train_options
+			.l2_regularization_for_continuous_splits(),
+		l2_regularization_for_discrete_splits: train_options
+			.l2_regularization_for_discrete_splits(),
+		learning_rate: train_options.learning_rate(),
+		max_depth: train_options.max_depth(),
+		max_examples_for_computing_bin_thresholds: train_options
+			.max_examples_for_computing_bin_thresholds(),
+		max_leaf_nodes: train_options.max_leaf_nodes(),
+		max_rounds: train_options.max_rounds(),
+		max_valid_bins_for_number_features: train_options.max_valid_bins_for_number_features(),
+		min_examples_per_node: train_options.min_examples_per_node(),
+		min_gain_to_split: train_options.min_gain_to_split(),
+		min_sum_hessians_per_node: train_options.min_sum_hessians_per_node(),
+		smoothing_factor_for_discrete_bin_sorting: train_options
+			.smoothing_factor_for_discrete_bin_sorting(),
+		bagging_fraction: train_options
+			.bagging_fraction()
+			.unwrap_or(defaults.bagging_fraction),
+		bagging_frequency: train_options
+			.bagging_frequency()
+			.unwrap_or_else(|| defaults.bagging_frequency.to_u64().unwrap()),
+		feature_fraction_per_node: train_options
+			.feature_fraction_per_node()
+			.unwrap_or(defaults.feature_fraction_per_node),
+		feature_fraction_per_tree: train_options
+			.feature_fraction_per_tree()
+			.unwrap_or(defaults.feature_fraction_per_tree),
+		goss_options,
+		seed: train_options.seed().unwrap_or(defaults.seed),
+		dart_options,
+	})
+}
+
+/// Return whether to keep each feature of the feature groups. `is_used` returns whether the model uses the feature at an index.
+fn keep_features(
+	feature_groups: &[FeatureGroupReader],
+	options: &RewriteOptions,
+	is_used: impl Fn(usize) -> bool,
+) -> Vec<bool> {
+	let mut keep_features = Vec::new();
+	for feature_group in feature_groups {
+		let prunable = match feature_group {
+			FeatureGroupReader::BagOfWords(feature_group) => !matches!(
+				feature_group.read().strategy(),
+				BagOfWordsFeatureGroupStrategyReader::TfIdf(_)
+			),
+			_ => false,
+		};
+		for _ in 0..feature_group_n_features(*feature_group) {
+			let feature_index = keep_features.len();
+			keep_features.push(!(options.prune_features && prunable) || is_used(feature_index));
+		}
+	}
+	keep_features
+}
+
+fn feature_group_n_features(feature_group: FeatureGroupReader) -> usize {
+	match feature_group {
+		FeatureGroupReader::Identity(feature_group) => {
+			1 + usize::from(feature_group.read().missing_indicator().unwrap_or(false))
+		}
+		FeatureGroupReader::Normalized(feature_group) => {
+			1 + usize::from(feature_group.read().missing_indicator().unwrap_or(false))
+		}
+		FeatureGroupReader::OneHotEncoded(feature_group) => {
+			feature_group.read().variants().len() + 1
+		}
+		FeatureGroupReader::BagOfWords(feature_group) => feature_group.read().ngrams().len(),
+		FeatureGroupReader::WordEmbedding(feature_group) => {
+			feature_group.read().model().size().to_usize().unwrap()
+		}
+		FeatureGroupReader::BagOfWordsCosineSimilarity(_) => 1,
+		FeatureGroupReader::HashedBagOfWords(feature_group) => {
+			feature_group.read().n_buckets().to_usize().unwrap()
+		}
+		FeatureGroupReader::HashedEnum(feature_group) => {
+			feature_group.read().n_buckets().to_usize().unwrap()
+		}
+		FeatureGroupReader::Interaction(feature_group) => {
+			let feature_group = feature_group.read();
+			interaction_feature_group_source_n_features(feature_group.source_a())
+				* interaction_feature_group_source_n_features(feature_group.source_b())
+		}
+		FeatureGroupReader::Polynomial(feature_group) => {
+			feature_group.read().degree().to_usize().unwrap() - 1
+		}
+		FeatureGroupReader::BinnedNumber(feature_group) => {
+			feature_group.read().thresholds().len() + 1
+		}
+	}
+}
+
+fn interaction_feature_group_source_n_features(
+	source: InteractionFeatureGroupSourceReader,
+) -> usize {
+	match source {
+		InteractionFeatureGroupSourceReader::Normalized(_) => 1,
+		InteractionFeatureGroupSourceReader::OneHotEncoded(feature_group) => {
+			feature_group.read().variants().len() + 1
+		}
+	}
+}
+
+/// Return the indexes of the features that any branch in `trees` splits on.
+fn split_features<'a>(
+	trees: impl IntoIterator<Item = &'a modelfox_tree::Tree>,
+) -> FnvHashSet<usize> {
+	trees
+		.into_iter()
+		.flat_map(|tree| tree.nodes.iter())
+		.filter_map(|node| match node {
+			modelfox_tree::Node::Branch(branch) => Some(branch.split.feature_index()),
+			modelfox_tree::Node::Leaf(_) => None,
+		})
+		.collect()
+}
+
+/// Update the feature indexes of the branches in `trees` to account for the removal of the features that are not kept.
+fn remap_split_features<'a>(
+	trees: impl IntoIterator<Item = &'a mut modelfox_tree::Tree>,
+	keep_features: &[bool],
+) {
+	let feature_indexes = keep_features
+		.iter()
+		.scan(0, |next_feature_index, keep| {
+			let feature_index = *next_feature_index;
+			if *keep {
+				*next_feature_index += 1;
+			}
+			Some(feature_index)
+		})
+		.collect::<Vec<_>>();
+	for tree in trees {
+		for node in tree.nodes.iter_mut() {
+			if let modelfox_tree::Node::Branch(branch) = node {
+				match &mut branch.split {
+					modelfox_tree::BranchSplit::Continuous(split) => {
+						split.feature_index = feature_indexes[split.feature_index];
+					}
+					modelfox_tree::BranchSplit::Discrete(split) => {
+						split.feature_index = feature_indexes[split.feature_index];
+					}
+				}
+			}
+		}
+	}
+}
+
+fn filter_features<T>(values: impl Iterator<Item = T>, keep_features: &[bool]) -> Vec<T> {
+	values
+		.zip(keep_features.iter())
+		.filter(|(_, keep)| **keep)
+		.map(|(value, _)| value)
+		.collect()
+}
+
+fn weight_encoding(options: &RewriteOptions) -> modelfox_linear::WeightEncoding {
+	match options.quantization {
+		None => modelfox_linear::WeightEncoding::F32,
+		Some(Quantization::F16) => modelfox_linear::WeightEncoding::F16,
+		Some(Quantization::I8) => modelfox_linear::WeightEncoding::I8,
+	}
+}
+
+fn leaf_value_encoding(options: &RewriteOptions) -> modelfox_tree::LeafValueEncoding {
+	match options.quantization {
+		None => modelfox_tree::LeafValueEncoding::F64,
+		Some(Quantization::F16) => modelfox_tree::LeafValueEncoding::F16,
+		Some(Quantization::I8) => modelfox_tree::LeafValueEncoding::I8,
+	}
+}
+
+fn copy_feature_groups(
+	feature_groups: &[FeatureGroupReader],
+	keep_features: &[bool],
+	writer: &mut buffalo::Writer,
+) -> Vec<FeatureGroupWriter> {
+	let mut offset = 0;
+	feature_groups
+		.iter()
+		.map(|feature_group| {
+			let n_features = feature_group_n_features(*feature_group);
+			let keep_features = &keep_features[offset..offset + n_features];
+			offset += n_features;
+			copy_feature_group(*feature_group, keep_features, writer)
+		})
+		.collect()
+}
+
+fn copy_feature_group(
+	feature_group: FeatureGroupReader,
+	keep_features: &[bool],
+	writer: &mut buffalo::Writer,
+) -> FeatureGroupWriter {
+	match feature_group {
+		FeatureGroupReader::Identity(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = writer.write(feature_group.source_column_name());
+			let impute = feature_group
+				.impute()
+				.flatten()
+				.map(|impute| copy_impute(impute, writer));
+			let feature_group = writer.write(&IdentityFeatureGroupWriter {
+				source_column_name,
+				impute,
+				missing_indicator: feature_group.missing_indicator().unwrap_or(false),
+			});
+			FeatureGroupWriter::Identity(feature_group)
+		}
+		FeatureGroupReader::Normalized(feature_group) => FeatureGroupWriter::Normalized(
+			copy_normalized_feature_group(feature_group.read(), writer),
+		),
+		FeatureGroupReader::OneHotEncoded(feature_group) => FeatureGroupWriter::OneHotEncoded(
+			copy_one_hot_encoded_feature_group(feature_group.read(), writer),
+		),
+		FeatureGroupReader::BagOfWords(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = writer.write(feature_group.source_column_name());
+			let tokenizer = copy_tokenizer(feature_group.tokenizer(), writer);
+			let strategy = copy_bag_of_words_feature_group_strategy(feature_group.strategy());
+			let ngram_types = feature_group
+				.ngram_types()
+				.iter()
+				.map(copy_ngram_type)
+				.collect::<Vec<_>>();
+			let ngram_types = writer.write(&ngram_types);
+			let ngrams = feature_group
+				.ngrams()
+				.iter()
+				.zip(keep_features.iter())
+				.filter(|(_, keep)| **keep)
+				.map(|((ngram, entry), _)| {
+					let ngram = copy_ngram(ngram, writer);
+					let entry =
+						writer.write(&BagOfWordsFeatureGroupNGramEntryWriter { idf: entry.idf() });
+					(ngram, entry)
+				})
+				.collect::<Vec<_>>();
+			let ngrams = writer.write(&ngrams);
+			let feature_group = writer.write(&BagOfWordsFeatureGroupWriter {
+				source_column_name,
+				tokenizer,
+				strategy,
+				ngram_types,
+				ngrams,
+			});
+			FeatureGroupWriter::BagOfWords(feature_group)
+		}
+		FeatureGroupReader::WordEmbedding(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = writer.write(feature_group.source_column_name());
+			let tokenizer = copy_tokenizer(feature_group.tokenizer(), writer);
+			let model = feature_group.model();
+			let words = model
+				.words()
+				.iter()
+				.map(|(word, index)| (writer.write(word), index))
+				.collect::<Vec<_>>();
+			let words = writer.write(&words);
+			let values = model.values().iter().collect::<Vec<_>>();
+			let values = writer.write(values.as_slice());
+			let model = writer.write(&WordEmbeddingModelWriter {
+				size: model.size(),
+				words,
+				values,
+			});
+			let feature_group = writer.write(&WordEmbeddingFeatureGroupWriter {
+				source_column_name,
+				tokenizer,
+				model,
+			});
+			FeatureGroupWriter::WordEmbedding(feature_group)
+		}
+		FeatureGroupReader::BagOfWordsCosineSimilarity(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name_a = writer.write(feature_group.source_column_name_a());
+			let source_column_name_b = writer.write(feature_group.source_column_name_b());
+			let tokenizer = copy_tokenizer(feature_group.tokenizer(), writer);
+			let strategy = copy_bag_of_words_feature_group_strategy(feature_group.strategy());
+			let ngram_types = feature_group
+				.ngram_types()
+				.iter()
+				.map(copy_ngram_type)
+				.collect::<Vec<_>>();
+			let ngram_types = writer.write(&ngram_types);
+			let ngrams = feature_group
+				.ngrams()
+				.iter()
+				.map(|(ngram, entry)| {
+					let ngram = copy_ngram(ngram, writer);
+					let entry =
+						writer.write(&BagOfWordsFeatureGroupNGramEntryWriter { idf: entry.idf() });
+					(ngram, entry)
+				})
+				.collect::<Vec<_>>();
+			let ngrams = writer.write(&ngrams);
+			let feature_group = writer.write(&BagOfWordsCosineSimilarityFeatureGroupWriter {
+				source_column_name_a,
+				source_column_name_b,
+				tokenizer,
+				strategy,
+				ngram_types,
+				ngrams,
+			});
+			FeatureGroupWriter::BagOfWordsCosineSimilarity(feature_group)
+		}
+		FeatureGroupReader::HashedBagOfWords(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = writer.write(feature_group.source_column_name());
+			let tokenizer = copy_tokenizer(feature_group.tokenizer(), writer);
+			let strategy = match feature_group.strategy() {
+				HashedBagOfWordsFeatureGroupStrategyReader::Present(_) => {
+					HashedBagOfWordsFeatureGroupStrategyWriter::Present
+				}
+				HashedBagOfWordsFeatureGroupStrategyReader::Count(_) => {
+					HashedBagOfWordsFeatureGroupStrategyWriter::Count
+				}
+			};
+			let ngram_types = feature_group
+				.ngram_types()
+				.iter()
+				.map(copy_ngram_type)
+				.collect::<Vec<_>>();
+			let ngram_types = writer.write(&ngram_types);
+			let buckets = feature_group
+				.buckets()
+				.iter()
+				.map(|bucket| {
+					let ngrams = bucket
+						.ngrams()
+						.iter()
+						.map(|ngram| copy_ngram(ngram, writer))
+						.collect::<Vec<_>>();
+					let ngrams = writer.write(&ngrams);
+					writer.write(&HashedBagOfWordsFeatureGroupBucketWriter { ngrams })
+				})
+				.collect::<Vec<_>>();
+			let buckets = writer.write(&buckets);
+			let feature_group = writer.write(&HashedBagOfWordsFeatureGroupWriter {
+				source_column_name,
+				tokenizer,
+				strategy,
+				ngram_types,
+				n_buckets: feature_group.n_buckets(),
+				signed: feature_group.signed(),
+				buckets,
+			});
+			FeatureGroupWriter::HashedBagOfWords(feature_group)
+		}
+		FeatureGroupReader::HashedEnum(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = writer.write(feature_group.source_column_name());
+			let buckets = feature_group
+				.buckets()
+				.iter()
+				.map(|bucket| {
+					let values = bucket
+						.values()
+						.iter()
+						.map(|value| writer.write(value))
+						.collect::<Vec<_>>();
+					let values = writer.write(&values);
+					writer.write(&HashedEnumFeatureGroupBucketWriter { values })
+				})
+				.collect::<Vec<_>>();
+			let buckets = writer.write(&buckets);
+			let feature_group = writer.write(&HashedEnumFeatureGroupWriter {
+				source_column_name,
+				n_buckets: feature_group.n_buckets(),
+				signed: feature_group.signed(),
+				buckets,
+			});
+			FeatureGroupWriter::HashedEnum(feature_group)
+		}
+		FeatureGroupReader::Interaction(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_a = copy_interaction_feature_group_source(feature_group.source_a(), writer);
+			let source_b = copy_interaction_feature_group_source(feature_group.source_b(), writer);
+			let feature_group = writer.write(&InteractionFeatureGroupWriter { source_a, source_b });
+			FeatureGroupWriter::Interaction(feature_group)
+		}
+		FeatureGroupReader::Polynomial(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = writer.write(feature_group.source_column_name());
+			let feature_group = writer.write(&PolynomialFeatureGroupWriter {
+				source_column_name,
+				mean: feature_group.mean(),
+				variance: feature_group.variance(),
+				degree: feature_group.degree(),
+			});
+			FeatureGroupWriter::Polynomial(feature_group)
+		}
+		FeatureGroupReader::BinnedNumber(feature_group) => {
+			let feature_group = feature_group.read();
+			let source_column_name = writer.write(feature_group.source_column_name());
+			let thresholds = feature_group.thresholds().iter().collect::<Vec<_>>();
+			let thresholds = writer.write(thresholds.as_slice());
+			let feature_group = writer.write(&BinnedNumberFeatureGroupWriter {
+				source_column_name,
+				thresholds,
+			});
+			FeatureGroupWriter::BinnedNumber(feature_group)
+		}
+	}
+}
+
+fn copy_normalized_feature_group(
+	feature_group: NormalizedFeatureGroupReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<NormalizedFeatureGroupWriter> {
+	let source_column_name = writer.write(feature_group.source_column_name());
+	let impute = feature_group
+		.impute()
+		.flatten()
+		.map(|impute| copy_impute(impute, writer));
+	writer.write(&NormalizedFeatureGroupWriter {
+		source_column_name,
+		mean: feature_group.mean(),
+		variance: feature_group.variance(),
+		impute,
+		missing_indicator: feature_group.missing_indicator().unwrap_or(false),
+	})
+}
+
+fn copy_one_hot_encoded_feature_group(
+	feature_group: OneHotEncodedFeatureGroupReader,
+	writer: &mut buffalo::Writer,
+) -> buffalo::Position<OneHotEncodedFeatureGroupWriter> {
+	let source_column_name = writer.write(feature_group.source_column_name());
+	let variants = feature_group
+		.variants()
+		.iter()
+		.map(|variant| writer.write(variant))
+		.collect::<Vec<_>>();
+	let variants = writer.write(&variants);
+	writer.write(&OneHotEncodedFeatureGroupWriter {
+		source_column_name,
+		variants,
+	})
+}
+
+fn copy_interaction_feature_group_source(
+	source: InteractionFeatureGroupSourceReader,
+	writer: &mut buffalo::Writer,
+) -> InteractionFeatureGroupSourceWriter {
+	match source {
+		InteractionFeatureGroupSourceReader::Normalized(feature_group) => {
+			InteractionFeatureGroupSourceWriter::Normalized(copy_normalized_feature_group(
+				feature_group.read(),
+				writer,
+			))
+		}
+		InteractionFeatureGroupSourceReader::OneHotEncoded(feature_group) => {
+			InteractionFeatureGroupSourceWriter::OneHotEncoded(copy_one_hot_encoded_feature_group(
+				feature_group.read(),
+				writer,
+			))
+		}
+	}
+}
+
+fn copy_bag_of_words_feature_group_strategy(
+	strategy: BagOfWordsFeatureGroupStrategyReader,
+) -> BagOfWordsFeatureGroupStrategyWriter {
+	match strategy {
+		BagOfWordsFeatureGroupStrategyReader::Present(_) => {
+			BagOfWordsFeatureGroupStrategyWriter::Present
+		}
+		BagOfWordsFeatureGroupStrategyReader::Count(_) => {
+			BagOfWordsFeatureGroupStrategyWriter::Count
+		}
+		BagOfWordsFeatureGroupStrategyReader::TfIdf(_) => {
+			BagOfWordsFeatureGroupStrategyWriter::TfIdf
+		}
+	}
+}
@@ -89,6 +89,24 @@ pub struct Tokenizer {
 	pub lowercase: bool,
 	#[buffalo(id = 1, required)]
 	pub alphanumeric: bool,
+	/// The fields below are absent for models trained before tokenization was configurable, which is the same as disabling them.
+	#[buffalo(id = 2)]
+	pub nfkc: bool,
+	#[buffalo(id = 3)]
+	pub fold_accents: bool,
+	#[buffalo(id = 4)]
+	pub stop_words: Vec<String>,
+	#[buffalo(id = 5)]
+	pub stemmer: Option<Stemmer>,
+	#[buffalo(id = 6)]
+	pub token_pattern: Option<String>,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 0)]
+pub enum Stemmer {
+	#[buffalo(id = 0)]
+	English,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -109,6 +127,12 @@ pub enum NGramType {
 	Unigram,
 	#[buffalo(id = 1)]
 	Bigram,
+	#[buffalo(id = 2)]
+	CharacterTrigram,
+	#[buffalo(id = 3)]
+	CharacterQuadgram,
+	#[buffalo(id = 4)]
+	CharacterPentagram,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -118,4 +142,110 @@ pub enum NGram {
 	Unigram(String),
 	#[buffalo(id = 1)]
 	Bigram((String, String)),
+	#[buffalo(id = 2)]
+	Character(String),
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct DroppedColumn {
+	#[buffalo(id = 0, required)]
+	pub column_name: String,
+	#[buffalo(id = 1, required)]
+	pub reason: DroppedColumnReason,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 8)]
+pub enum DroppedColumnReason {
+	#[buffalo(id = 0)]
+	NearConstant(NearConstantDroppedColumnReason),
+	#[buffalo(id = 1)]
+	InvalidFraction(InvalidFractionDroppedColumnReason),
+	#[buffalo(id = 2)]
+	Collinear(CollinearDroppedColumnReason),
+	#[buffalo(id = 3)]
+	LowImportance(LowImportanceDroppedColumnReason),
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct NearConstantDroppedColumnReason {
+	#[buffalo(id = 0, required)]
+	pub most_frequent_fraction: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct InvalidFractionDroppedColumnReason {
+	#[buffalo(id = 0, required)]
+	pub invalid_fraction: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct CollinearDroppedColumnReason {
+	/// This is the name of the column that was kept.
+	#[buffalo(id = 0, required)]
+	pub column_name: String,
+	#[buffalo(id = 1, required)]
+	pub correlation: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct LowImportanceDroppedColumnReason {
+	#[buffalo(id = 0, required)]
+	pub importance: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "static", value_size = 8)]
+pub enum DataQualityIssue {
+	#[buffalo(id = 0)]
+	IdColumn(IdColumnDataQualityIssue),
+	#[buffalo(id = 1)]
+	TargetLeakage(TargetLeakageDataQualityIssue),
+	#[buffalo(id = 2)]
+	DuplicateRows(DuplicateRowsDataQualityIssue),
+	#[buffalo(id = 3)]
+	DistributionMismatch(DistributionMismatchDataQualityIssue),
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct IdColumnDataQualityIssue {
+	#[buffalo(id = 0, required)]
+	pub column_name: String,
+	#[buffalo(id = 1, required)]
+	pub unique_fraction: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct TargetLeakageDataQualityIssue {
+	#[buffalo(id = 0, required)]
+	pub column_name: String,
+	/// This is the accuracy for classifiers and the r-squared for regressors of a model that uses only this column, computed on the comparison dataset.
+	#[buffalo(id = 1, required)]
+	pub score: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct DuplicateRowsDataQualityIssue {
+	#[buffalo(id = 0, required)]
+	pub duplicate_count: u64,
+	#[buffalo(id = 1, required)]
+	pub duplicate_fraction: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct DistributionMismatchDataQualityIssue {
+	#[buffalo(id = 0, required)]
+	pub auc_roc: f32,
+	/// This is the column that differs the most between the train and test datasets.
+	#[buffalo(id = 1, required)]
+	pub column_name: Option<String>,
 }
@@ -4,7 +4,7 @@ This crate implements two dimensional collections where each column can have a d
 
 #![warn(clippy::pedantic)]
 
-pub use self::load::{FromCsvOptions, ProgressEvent};
+pub use self::load::{FromCsvOptions, InferOptions, ProgressEvent, TableChunks};
 use fnv::FnvHashMap;
 use modelfox_zip::zip;
 use ndarray::prelude::*;
@@ -191,6 +191,52 @@ impl Table {
 		}
 	}
 
+	/// Append the row at `index` in `table` to this table. `table` must have the same column types as this table.
+	///
+	/// # Panics
+	///
+	/// This function panics if the column types of the tables do not match.
+	pub fn push_row(&mut self, table: &TableView, index: usize) {
+		for (column, other_column) in zip!(self.columns.iter_mut(), table.columns.iter()) {
+			match (column, other_column) {
+				(TableColumn::Unknown(column), TableColumnView::Unknown(_)) => column.len += 1,
+				(TableColumn::Number(column), TableColumnView::Number(other_column)) => {
+					column.data.push(other_column.data[index]);
+				}
+				(TableColumn::Enum(column), TableColumnView::Enum(other_column)) => {
+					column.data.push(other_column.data[index]);
+				}
+				(TableColumn::Text(column), TableColumnView::Text(other_column)) => {
+					column.data.push(other_column.data[index].clone());
+				}
+				_ => panic!("the column types of the tables do not match"),
+			}
+		}
+	}
+
+	/// Replace the row at `row_index` in this table with the row at `index` in `table`. `table` must have the same column types as this table.
+	///
+	/// # Panics
+	///
+	/// This function panics if the column types of the tables do not match.
+	pub fn replace_row(&mut self, row_index: usize, table: &TableView, index: usize) {
+		for (column, other_column) in zip!(self.columns.iter_mut(), table.columns.iter()) {
+			match (column, other_column) {
+				(TableColumn::Unknown(_), TableColumnView::Unknown(_)) => {}
+				(TableColumn::Number(column), TableColumnView::Number(other_column)) => {
+					column.data[row_index] = other_column.data[index];
+				}
+				(TableColumn::Enum(column), TableColumnView::Enum(other_column)) => {
+					column.data[row_index] = other_column.data[index];
+				}
+				(TableColumn::Text(column), TableColumnView::Text(other_column)) => {
+					column.data[row_index] = other_column.data[index].clone();
+				}
+				_ => panic!("the column types of the tables do not match"),
+			}
+		}
+	}
+
 	#[must_use]
 	pub fn ncols(&self) -> usize {
 		self.columns.len()
@@ -549,6 +595,11 @@ impl TextTableColumn {
 }
 
 impl<'a> TableView<'a> {
+	#[must_use]
+	pub fn from_columns(columns: Vec<TableColumnView<'a>>) -> TableView<'a> {
+		TableView { columns }
+	}
+
 	#[must_use]
 	pub fn columns(&self) -> &Vec<TableColumnView<'a>> {
 		&self.columns
@@ -1,4 +1,7 @@
-use super::{Table, TableColumn, TableColumnType};
+use super::{
+	EnumTableColumn, NumberTableColumn, Table, TableColumn, TableColumnType, TextTableColumn,
+	UnknownTableColumn,
+};
 use anyhow::Result;
 use modelfox_progress_counter::ProgressCounter;
 use modelfox_zip::zip;
@@ -217,33 +220,221 @@ impl Table {
 		handle_progress_event(ProgressEvent::LoadStarted(progress_counter.clone()));
 		while reader.read_byte_record(&mut record)? {
 			progress_counter.set(record.position().unwrap().byte());
-			for (column, value) in zip!(table.columns.iter_mut(), record.iter()) {
-				match column {
-					TableColumn::Unknown(column) => {
-						column.len += 1;
-					}
-					TableColumn::Number(column) => {
-						let value = match fast_float::parse::<f32, &[u8]>(value) {
-							Ok(value) if value.is_finite() => value,
-							_ => std::f32::NAN,
-						};
-						column.data.push(value);
-					}
-					TableColumn::Enum(column) => {
-						let value = std::str::from_utf8(value)
-							.ok()
-							.and_then(|value| column.value_for_variant(value));
-						column.data.push(value);
-					}
-					TableColumn::Text(column) => {
-						column.data.push(std::str::from_utf8(value)?.to_owned());
-					}
-				}
-			}
+			table.push_record(&record)?;
 		}
 		handle_progress_event(ProgressEvent::LoadDone);
 		Ok(table)
 	}
+
+	/// Infer the type of each column in a csv file without loading its values. Use this with `TableChunks` to read a csv file that is too large to fit in memory. The column types in `options` are used for the columns they include.
+	///
+	/// # Errors
+	///
+	/// Returns an error if unable to read the CSV file.
+	#[allow(clippy::missing_panics_doc)]
+	pub fn infer_column_types_from_path(
+		path: &Path,
+		options: FromCsvOptions,
+		handle_progress_event: &mut impl FnMut(ProgressEvent),
+	) -> Result<BTreeMap<String, TableColumnType>> {
+		let len = std::fs::metadata(path)?.len();
+		let mut reader = csv::Reader::from_path(path)?;
+		let column_names: Vec<String> = reader
+			.headers()?
+			.into_iter()
+			.map(std::borrow::ToOwned::to_owned)
+			.collect();
+		let column_types = options.column_types.unwrap_or_default();
+		let mut infer_stats: Vec<(usize, InferStats)> = column_names
+			.iter()
+			.enumerate()
+			.filter(|(_, column_name)| !column_types.contains_key(*column_name))
+			.map(|(index, _)| (index, InferStats::new(&options.infer_options)))
+			.collect();
+		let mut record = csv::StringRecord::new();
+		let progress_counter = ProgressCounter::new(len);
+		handle_progress_event(ProgressEvent::InferStarted(progress_counter.clone()));
+		if !infer_stats.is_empty() {
+			while reader.read_record(&mut record)? {
+				progress_counter.set(record.position().unwrap().byte());
+				for (index, infer_stats) in &mut infer_stats {
+					let value = record.get(*index).unwrap();
+					infer_stats.update(value);
+				}
+			}
+		}
+		handle_progress_event(ProgressEvent::InferDone);
+		let mut inferred_column_types = column_types;
+		for (index, infer_stats) in infer_stats {
+			inferred_column_types.insert(column_names[index].clone(), infer_stats.finalize());
+		}
+		Ok(inferred_column_types)
+	}
+
+	/// Append the values in a csv record to the columns of this table.
+	fn push_record(&mut self, record: &csv::ByteRecord) -> Result<()> {
+		for (column, value) in zip!(self.columns.iter_mut(), record.iter()) {
+			match column {
+				TableColumn::Unknown(column) => {
+					column.len += 1;
+				}
+				TableColumn::Number(column) => {
+					let value = match fast_float::parse::<f32, &[u8]>(value) {
+						Ok(value) if value.is_finite() => value,
+						_ => std::f32::NAN,
+					};
+					column.data.push(value);
+				}
+				TableColumn::Enum(column) => {
+					let value = std::str::from_utf8(value)
+						.ok()
+						.and_then(|value| column.value_for_variant(value));
+					column.data.push(value);
+				}
+				TableColumn::Text(column) => {
+					column.data.push(std::str::from_utf8(value)?.to_owned());
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+impl TableColumn {
+	/// Create a column from string values, inferring its type the same way `Table::from_csv` does. Use this to build a table from data that is not in a csv file, passing an empty string for each missing value.
+	#[must_use]
+	pub fn from_strings(
+		name: Option<String>,
+		values: &[&str],
+		infer_options: &InferOptions,
+	) -> TableColumn {
+		let mut infer_stats = InferStats::new(infer_options);
+		for value in values {
+			infer_stats.update(value);
+		}
+		match infer_stats.finalize() {
+			TableColumnType::Unknown => TableColumn::Unknown(UnknownTableColumn {
+				name,
+				len: values.len(),
+			}),
+			TableColumnType::Number => {
+				let data = values
+					.iter()
+					.map(|value| match fast_float::parse::<f32, &str>(value) {
+						Ok(value) if value.is_finite() => value,
+						_ => std::f32::NAN,
+					})
+					.collect();
+				TableColumn::Number(NumberTableColumn::new(name, data))
+			}
+			TableColumnType::Enum { variants } => {
+				let mut column = EnumTableColumn::new(name, variants, Vec::new());
+				column.data = values
+					.iter()
+					.map(|value| column.value_for_variant(value))
+					.collect();
+				TableColumn::Enum(column)
+			}
+			TableColumnType::Text => TableColumn::Text(TextTableColumn::new(
+				name,
+				values.iter().map(|value| (*value).to_owned()).collect(),
+			)),
+		}
+	}
+}
+
+/// This iterator reads a csv file in chunks of rows, so that a file too large to fit in memory can be processed one chunk at a time. Every chunk is a table with the same column types.
+pub struct TableChunks {
+	reader: csv::Reader<std::fs::File>,
+	column_names: Vec<String>,
+	column_types: Vec<TableColumnType>,
+	chunk_size: usize,
+	record: csv::ByteRecord,
+	done: bool,
+}
+
+impl TableChunks {
+	/// Open a csv file to read in chunks of `chunk_size` rows. `column_types` must have a type for every column in the file, such as those returned by `Table::infer_column_types_from_path`.
+	///
+	/// # Errors
+	///
+	/// Returns an error if unable to open the CSV file or if a column does not have a type.
+	pub fn from_path(
+		path: &Path,
+		column_types: &BTreeMap<String, TableColumnType>,
+		chunk_size: usize,
+	) -> Result<TableChunks> {
+		let mut reader = csv::Reader::from_path(path)?;
+		let column_names: Vec<String> = reader
+			.headers()?
+			.into_iter()
+			.map(std::borrow::ToOwned::to_owned)
+			.collect();
+		let column_types = column_names
+			.iter()
+			.map(|column_name| {
+				column_types.get(column_name).cloned().ok_or_else(|| {
+					anyhow::anyhow!("no column type was given for column \"{}\"", column_name)
+				})
+			})
+			.collect::<Result<_>>()?;
+		Ok(TableChunks {
+			reader,
+			column_names,
+			column_types,
+			chunk_size: chunk_size.max(1),
+			record: csv::ByteRecord::new(),
+			done: false,
+		})
+	}
+
+	/// Create an empty table with the same columns as the chunks.
+	#[must_use]
+	pub fn empty_table(&self) -> Table {
+		Table::new(
+			self.column_names.iter().cloned().map(Some).collect(),
+			self.column_types.clone(),
+		)
+	}
+
+	/// This is the number of bytes of the csv file that have been read.
+	#[must_use]
+	pub fn position(&self) -> u64 {
+		self.reader.position().byte()
+	}
+
+	fn read_chunk(&mut self) -> Result<Option<Table>> {
+		let mut table = self.empty_table();
+		while table.nrows() < self.chunk_size {
+			if !self.reader.read_byte_record(&mut self.record)? {
+				self.done = true;
+				break;
+			}
+			table.push_record(&self.record)?;
+		}
+		if table.nrows() == 0 {
+			Ok(None)
+		} else {
+			Ok(Some(table))
+		}
+	}
+}
+
+impl Iterator for TableChunks {
+	type Item = Result<Table>;
+
+	fn next(&mut self) -> Option<Result<Table>> {
+		if self.done {
+			return None;
+		}
+		match self.read_chunk() {
+			Ok(table) => table.map(Ok),
+			Err(error) => {
+				self.done = true;
+				Some(Err(error))
+			}
+		}
+	}
 }
 
 #[derive(Clone, Debug)]
@@ -401,6 +592,35 @@ fn test_infer() {
  "###);
 }
 
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_from_strings() {
+	let infer_options = InferOptions {
+		enum_max_unique_values: 2,
+	};
+	let column =
+		TableColumn::from_strings(Some("number".to_owned()), &["1.5", "", "2"], &infer_options);
+	assert_eq!(column.as_number().unwrap().view().data()[0], 1.5);
+	assert!(column.as_number().unwrap().view().data()[1].is_nan());
+	let column = TableColumn::from_strings(
+		Some("enum".to_owned()),
+		&["yes", "no", "NA"],
+		&infer_options,
+	);
+	let column = column.as_enum().unwrap();
+	assert_eq!(column.variants(), ["no", "yes"]);
+	assert_eq!(
+		column
+			.iter()
+			.map(|value| value.map(std::num::NonZeroUsize::get))
+			.collect::<Vec<_>>(),
+		[Some(2), Some(1), None],
+	);
+	let column =
+		TableColumn::from_strings(Some("text".to_owned()), &["a", "b", "c"], &infer_options);
+	assert!(column.as_text().is_some());
+}
+
 #[test]
 fn test_column_types() {
 	let csv = r#"number,text,enum
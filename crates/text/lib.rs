@@ -1,9 +1,11 @@
 pub use self::{
 	ngram::{NGram, NGramRef, NGramType},
-	tokenizer::Tokenizer,
+	stop_words::ENGLISH_STOP_WORDS,
+	tokenizer::{Stemmer, TokenPattern, Tokenizer},
 	word_embedding::WordEmbeddingModel,
 };
 
 mod ngram;
+mod stop_words;
 mod tokenizer;
 mod word_embedding;
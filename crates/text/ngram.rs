@@ -5,6 +5,31 @@ use std::{borrow::Cow, fmt::Display, hash::Hash};
 pub enum NGram {
 	Unigram(String),
 	Bigram(String, String),
+	/// Character ngrams are serialized as `{ "character": ngram }`, so they are not confused with unigrams.
+	#[serde(with = "character_ngram_serde")]
+	Character(String),
+}
+
+mod character_ngram_serde {
+	#[derive(serde::Serialize, serde::Deserialize)]
+	struct CharacterNGram<T> {
+		character: T,
+	}
+
+	pub fn serialize<S>(ngram: &str, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serde::Serialize::serialize(&CharacterNGram { character: ngram }, serializer)
+	}
+
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let ngram: CharacterNGram<String> = serde::Deserialize::deserialize(deserializer)?;
+		Ok(ngram.character)
+	}
 }
 
 impl PartialEq for NGram {
@@ -15,6 +40,9 @@ impl PartialEq for NGram {
 				NGram::Bigram(self_token_a, self_token_b),
 				NGram::Bigram(other_token_a, other_token_b),
 			) => self_token_a == other_token_a && self_token_b == other_token_b,
+			(NGram::Character(self_ngram), NGram::Character(other_ngram)) => {
+				self_ngram == other_ngram
+			}
 			_ => false,
 		}
 	}
@@ -32,6 +60,10 @@ impl Hash for NGram {
 				token_a.hash(state);
 				token_b.hash(state);
 			}
+			NGram::Character(ngram) => {
+				2usize.hash(state);
+				ngram.hash(state)
+			}
 		}
 	}
 }
@@ -45,6 +77,9 @@ impl Display for NGram {
 			NGram::Bigram(token_a, token_b) => {
 				write!(f, "{} {}", token_a, token_b)
 			}
+			NGram::Character(ngram) => {
+				write!(f, "{}", ngram)
+			}
 		}
 	}
 }
@@ -53,6 +88,7 @@ impl Display for NGram {
 pub enum NGramRef<'a> {
 	Unigram(Cow<'a, str>),
 	Bigram(Cow<'a, str>, Cow<'a, str>),
+	Character(Cow<'a, str>),
 }
 
 impl<'a> PartialEq for NGramRef<'a> {
@@ -65,6 +101,9 @@ impl<'a> PartialEq for NGramRef<'a> {
 				NGramRef::Bigram(self_token_a, self_token_b),
 				NGramRef::Bigram(other_token_a, other_token_b),
 			) => self_token_a == other_token_a && self_token_b == other_token_b,
+			(NGramRef::Character(self_ngram), NGramRef::Character(other_ngram)) => {
+				self_ngram == other_ngram
+			}
 			_ => false,
 		}
 	}
@@ -82,6 +121,10 @@ impl<'a> Hash for NGramRef<'a> {
 				token_a.hash(state);
 				token_b.hash(state);
 			}
+			NGramRef::Character(ngram) => {
+				2usize.hash(state);
+				ngram.hash(state)
+			}
 		}
 	}
 }
@@ -93,11 +136,24 @@ impl<'a> indexmap::Equivalent<NGram> for NGramRef<'a> {
 			(NGramRef::Bigram(bigram_a_ref, bigram_b_ref), NGram::Bigram(bigram_a, bigram_b)) => {
 				bigram_a_ref == bigram_a && bigram_b_ref == bigram_b
 			}
+			(NGramRef::Character(ngram_ref), NGram::Character(ngram)) => ngram_ref == ngram,
 			_ => false,
 		}
 	}
 }
 
+impl NGram {
+	pub fn as_ngram_ref(&self) -> NGramRef {
+		match self {
+			NGram::Unigram(token) => NGramRef::Unigram(Cow::Borrowed(token)),
+			NGram::Bigram(token_a, token_b) => {
+				NGramRef::Bigram(Cow::Borrowed(token_a), Cow::Borrowed(token_b))
+			}
+			NGram::Character(ngram) => NGramRef::Character(Cow::Borrowed(ngram)),
+		}
+	}
+}
+
 impl<'a> NGramRef<'a> {
 	pub fn to_ngram(&self) -> NGram {
 		match self {
@@ -105,12 +161,29 @@ impl<'a> NGramRef<'a> {
 			NGramRef::Bigram(token_a, token_b) => {
 				NGram::Bigram(token_a.as_ref().to_owned(), token_b.as_ref().to_owned())
 			}
+			NGramRef::Character(ngram) => NGram::Character(ngram.as_ref().to_owned()),
 		}
 	}
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum NGramType {
 	Unigram,
 	Bigram,
+	/// Character ngrams are computed within each token, which is padded with a space on each side.
+	CharacterTrigram,
+	CharacterQuadgram,
+	CharacterPentagram,
+}
+
+impl NGramType {
+	/// Return the number of characters in ngrams of this type, if it is a character ngram type.
+	pub fn character_ngram_len(&self) -> Option<usize> {
+		match self {
+			NGramType::Unigram | NGramType::Bigram => None,
+			NGramType::CharacterTrigram => Some(3),
+			NGramType::CharacterQuadgram => Some(4),
+			NGramType::CharacterPentagram => Some(5),
+		}
+	}
 }
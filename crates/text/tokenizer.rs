@@ -1,9 +1,75 @@
+use crate::{NGramRef, NGramType};
+use fnv::FnvHashSet;
+use itertools::Itertools;
 use std::borrow::Cow;
+use unicode_normalization::{
+	char::is_combining_mark, is_nfkc_quick, IsNormalized, UnicodeNormalization,
+};
 
-#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Tokenizer {
 	pub lowercase: bool,
 	pub alphanumeric: bool,
+	/// If this is true, each token is converted to Unicode normalization form NFKC, which replaces characters such as full width letters and ligatures with their common equivalents.
+	#[serde(default)]
+	pub nfkc: bool,
+	/// If this is true, accents and other combining marks are removed from each token.
+	#[serde(default)]
+	pub fold_accents: bool,
+	/// Tokens in this set are dropped. They are compared with each token after it is normalized and lowercased.
+	#[serde(default)]
+	pub stop_words: FnvHashSet<String>,
+	/// If this is set, each token that is not a stop word is reduced to its stem.
+	#[serde(default)]
+	pub stemmer: Option<Stemmer>,
+	/// If this is set, the tokens are the matches of this regular expression. Otherwise, the tokens are runs of alphanumeric characters and each other character that is not whitespace.
+	#[serde(default)]
+	pub token_pattern: Option<TokenPattern>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Stemmer {
+	/// This is the Snowball stemmer for English, also known as Porter2. It expects lowercase tokens.
+	English,
+}
+
+/// This is a regular expression whose matches are the tokens.
+#[derive(Clone, Debug)]
+pub struct TokenPattern(regex::Regex);
+
+impl TokenPattern {
+	pub fn new(pattern: &str) -> Result<TokenPattern, regex::Error> {
+		Ok(TokenPattern(regex::Regex::new(pattern)?))
+	}
+
+	pub fn as_str(&self) -> &str {
+		self.0.as_str()
+	}
+}
+
+impl PartialEq for TokenPattern {
+	fn eq(&self, other: &Self) -> bool {
+		self.as_str() == other.as_str()
+	}
+}
+
+impl serde::Serialize for TokenPattern {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.serialize_str(self.as_str())
+	}
+}
+
+impl<'de> serde::Deserialize<'de> for TokenPattern {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let pattern = String::deserialize(deserializer)?;
+		TokenPattern::new(&pattern).map_err(serde::de::Error::custom)
+	}
 }
 
 impl Default for Tokenizer {
@@ -11,55 +77,155 @@ impl Default for Tokenizer {
 		Tokenizer {
 			lowercase: true,
 			alphanumeric: true,
+			nfkc: false,
+			fold_accents: false,
+			stop_words: FnvHashSet::default(),
+			stemmer: None,
+			token_pattern: None,
 		}
 	}
 }
 
 impl Tokenizer {
 	pub fn tokenize<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Cow<'a, str>> {
-		TokenizerIterator {
-			cursor: StrCursor::new(text),
-			tokenizer: self,
+		let tokens = match &self.token_pattern {
+			Some(token_pattern) => Tokens::Pattern(token_pattern.0.find_iter(text)),
+			None => Tokens::Default {
+				cursor: StrCursor::new(text),
+				// Combining marks only continue a token when normalization is enabled, so tokenizers without normalization split text the same way they always have.
+				include_combining_marks: self.nfkc || self.fold_accents,
+			},
+		};
+		tokens.filter_map(move |token| self.process_token(token))
+	}
+
+	/// Compute the ngrams of each of the `ngram_types` in `text`.
+	pub fn ngrams<'a>(
+		&'a self,
+		text: &'a str,
+		ngram_types: impl IntoIterator<Item = &'a NGramType> + 'a,
+	) -> impl Iterator<Item = NGramRef<'a>> {
+		ngram_types
+			.into_iter()
+			.flat_map(move |ngram_type| self.ngrams_of_type(text, *ngram_type))
+	}
+
+	fn ngrams_of_type<'a>(
+		&'a self,
+		text: &'a str,
+		ngram_type: NGramType,
+	) -> Box<dyn Iterator<Item = NGramRef<'a>> + 'a> {
+		match ngram_type {
+			NGramType::Unigram => Box::new(self.tokenize(text).map(NGramRef::Unigram)),
+			NGramType::Bigram => Box::new(
+				self.tokenize(text)
+					.tuple_windows()
+					.map(|(token_a, token_b)| NGramRef::Bigram(token_a, token_b)),
+			),
+			NGramType::CharacterTrigram
+			| NGramType::CharacterQuadgram
+			| NGramType::CharacterPentagram => {
+				let n = ngram_type.character_ngram_len().unwrap();
+				Box::new(
+					self.tokenize(text)
+						.flat_map(move |token| character_ngrams(&token, n)),
+				)
+			}
 		}
 	}
+
+	/// Normalize, lowercase, and stem a token, or return `None` if it is a stop word.
+	fn process_token<'a>(&self, token: &'a str) -> Option<Cow<'a, str>> {
+		let mut token = Cow::Borrowed(token);
+		if self.nfkc && !token.is_ascii() && is_nfkc_quick(token.chars()) != IsNormalized::Yes {
+			token = Cow::Owned(token.nfkc().collect());
+		}
+		if self.fold_accents && !token.is_ascii() {
+			token = Cow::Owned(
+				token
+					.nfd()
+					.filter(|c| !is_combining_mark(*c))
+					.nfc()
+					.collect(),
+			);
+		}
+		if self.lowercase && token.chars().any(char::is_uppercase) {
+			token = Cow::Owned(token.to_lowercase());
+		}
+		if self.stop_words.contains(token.as_ref()) {
+			return None;
+		}
+		if let Some(stemmer) = self.stemmer {
+			let algorithm = match stemmer {
+				Stemmer::English => rust_stemmers::Algorithm::English,
+			};
+			let stemmer = rust_stemmers::Stemmer::create(algorithm);
+			token = match token {
+				Cow::Borrowed(token) => stemmer.stem(token),
+				Cow::Owned(token) => Cow::Owned(stemmer.stem(&token).into_owned()),
+			};
+		}
+		Some(token)
+	}
 }
 
-#[derive(Clone, Debug)]
-pub struct TokenizerIterator<'a> {
-	cursor: StrCursor<'a>,
-	tokenizer: &'a Tokenizer,
+/// Compute the character ngrams of length `n` in `token`, which is padded with a space on each side so ngrams at the start and end of the token are distinguished from those in the middle.
+fn character_ngrams<'a>(token: &str, n: usize) -> impl Iterator<Item = NGramRef<'a>> {
+	let chars: Vec<char> = std::iter::once(' ')
+		.chain(token.chars())
+		.chain(std::iter::once(' '))
+		.collect();
+	let ngrams_count = (chars.len() + 1).saturating_sub(n);
+	(0..ngrams_count)
+		.map(move |start| NGramRef::Character(Cow::Owned(chars[start..start + n].iter().collect())))
+}
+
+#[derive(Debug)]
+enum Tokens<'a> {
+	Default {
+		cursor: StrCursor<'a>,
+		include_combining_marks: bool,
+	},
+	Pattern(regex::Matches<'a, 'a>),
 }
 
-impl<'a> Iterator for TokenizerIterator<'a> {
-	type Item = Cow<'a, str>;
+impl<'a> Iterator for Tokens<'a> {
+	type Item = &'a str;
 	fn next(&mut self) -> Option<Self::Item> {
-		// Pass over any leading whitespace.
-		while self.cursor.peek()?.is_whitespace() {
-			self.cursor.next();
-		}
-		// Mark the start of the token.
-		let token_start_index = self.cursor.index();
-		let first_char = self.cursor.next().unwrap();
-		let mut token_contains_uppercase = first_char.is_uppercase();
-		// If the first char is alphanumeric, include any subsequent alphanumeric chars.
-		if first_char.is_alphanumeric() {
-			while self
-				.cursor
-				.peek()
-				.map(|c| c.is_alphanumeric())
-				.unwrap_or(false)
-			{
-				let c = self.cursor.next().unwrap();
-				token_contains_uppercase |= c.is_uppercase();
+		match self {
+			Tokens::Default {
+				cursor,
+				include_combining_marks,
+			} => {
+				// Pass over any leading whitespace.
+				while cursor.peek()?.is_whitespace() {
+					cursor.next();
+				}
+				// Mark the start of the token.
+				let token_start_index = cursor.index();
+				let first_char = cursor.next().unwrap();
+				// If the first char is alphanumeric, include any subsequent alphanumeric chars.
+				if first_char.is_alphanumeric() {
+					while cursor
+						.peek()
+						.map(|c| {
+							c.is_alphanumeric()
+								|| (*include_combining_marks && is_combining_mark(c))
+						})
+						.unwrap_or(false)
+					{
+						cursor.next();
+					}
+				}
+				Some(&cursor.string[token_start_index..cursor.index()])
 			}
+			Tokens::Pattern(matches) => loop {
+				let token = matches.next()?.as_str();
+				if !token.is_empty() {
+					return Some(token);
+				}
+			},
 		}
-		let token = &self.cursor.string[token_start_index..self.cursor.index()];
-		let token = if self.tokenizer.lowercase && token_contains_uppercase {
-			Cow::Owned(token.to_lowercase())
-		} else {
-			Cow::Borrowed(token)
-		};
-		Some(token)
 	}
 }
 
@@ -132,4 +298,50 @@ fn test_tokenizer() {
 		"iOS Developer",
 		vec!["ios", "developer"],
 	);
+	test(
+		Tokenizer {
+			stop_words: vec!["the".to_owned(), "of".to_owned()]
+				.into_iter()
+				.collect(),
+			..Default::default()
+		},
+		"The Lord of the Rings",
+		vec!["lord", "rings"],
+	);
+	test(
+		Tokenizer {
+			stemmer: Some(Stemmer::English),
+			..Default::default()
+		},
+		"Running shoes",
+		vec!["run", "shoe"],
+	);
+	test(
+		Tokenizer {
+			nfkc: true,
+			fold_accents: true,
+			..Default::default()
+		},
+		"Ｃafé Crème",
+		vec!["cafe", "creme"],
+	);
+	test(
+		Tokenizer {
+			token_pattern: Some(TokenPattern::new(r"[A-Za-z0-9-]+").unwrap()),
+			..Default::default()
+		},
+		"SKU-1234, size XL",
+		vec!["sku-1234", "size", "xl"],
+	);
+}
+
+#[test]
+fn test_character_ngrams() {
+	let tokenizer = Tokenizer::default();
+	let ngram_types = vec![NGramType::CharacterTrigram];
+	let ngrams: Vec<String> = tokenizer
+		.ngrams("Hi you", ngram_types.iter())
+		.map(|ngram| ngram.to_ngram().to_string())
+		.collect();
+	assert_eq!(ngrams, vec![" hi", "hi ", " yo", "you", "ou "]);
 }
@@ -1,4 +1,5 @@
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
+use std::io::{BufRead, Error, ErrorKind, Result};
 
 #[derive(Clone, Debug)]
 pub struct WordEmbeddingModel {
@@ -16,4 +17,185 @@ impl WordEmbeddingModel {
 			.get(index * self.size..index * self.size + self.size)?;
 		Some(embedding)
 	}
+
+	/// Read word embeddings in the text format used by fastText and GloVe. Each line contains a word followed by the values of its embedding, separated by spaces. The header line that fastText writes with the number of words and the size of the embeddings is skipped.
+	///
+	/// If `vocabulary` is provided, only the embeddings of words in it are kept. If a word appears more than once, its first embedding is kept.
+	pub fn from_text(
+		reader: impl BufRead,
+		vocabulary: Option<&FnvHashSet<String>>,
+	) -> Result<WordEmbeddingModel> {
+		let mut size = None;
+		let mut words = FnvHashMap::default();
+		let mut values = Vec::new();
+		for (line_index, line) in reader.lines().enumerate() {
+			let line = line?;
+			let mut fields = line.trim_end().split(' ');
+			let word = match fields.next() {
+				Some(word) if !word.is_empty() => word,
+				_ => continue,
+			};
+			// Skip the fastText header.
+			if line_index == 0 && is_text_header(&line) {
+				continue;
+			}
+			if !should_keep(word, vocabulary, &words) {
+				continue;
+			}
+			let embedding = fields
+				.map(|value| value.parse::<f32>())
+				.collect::<std::result::Result<Vec<_>, _>>()
+				.map_err(|_| {
+					invalid_data(format!(
+						"Line {} contains a value that is not a number.",
+						line_index + 1
+					))
+				})?;
+			if embedding.is_empty() {
+				return Err(invalid_data(format!(
+					"Line {} does not contain an embedding.",
+					line_index + 1
+				)));
+			}
+			let size = *size.get_or_insert(embedding.len());
+			if embedding.len() != size {
+				return Err(invalid_data(format!(
+					"Line {} has an embedding with {} values, but expected {}.",
+					line_index + 1,
+					embedding.len(),
+					size,
+				)));
+			}
+			words.insert(word.to_owned(), words.len());
+			values.extend(embedding);
+		}
+		let size = size.ok_or_else(|| invalid_data("No word embeddings were found.".to_owned()))?;
+		Ok(WordEmbeddingModel {
+			size,
+			words,
+			values,
+		})
+	}
+
+	/// Read word embeddings in the binary format used by word2vec and fastText. The file starts with a line containing the number of words and the size of the embeddings. Each word is followed by a space and the values of its embedding as little endian 32 bit floats.
+	///
+	/// If `vocabulary` is provided, only the embeddings of words in it are kept. If a word appears more than once, its first embedding is kept.
+	pub fn from_binary(
+		mut reader: impl BufRead,
+		vocabulary: Option<&FnvHashSet<String>>,
+	) -> Result<WordEmbeddingModel> {
+		let mut header = String::new();
+		reader.read_line(&mut header)?;
+		let header = header
+			.split_whitespace()
+			.map(|field| field.parse::<usize>())
+			.collect::<std::result::Result<Vec<_>, _>>();
+		let (n_words, size) =
+			match header.as_deref() {
+				Ok([n_words, size]) if *size > 0 => (*n_words, *size),
+				_ => return Err(invalid_data(
+					"The header must contain the number of words and the size of the embeddings."
+						.to_owned(),
+				)),
+			};
+		let mut words = FnvHashMap::default();
+		let mut values = Vec::new();
+		let mut word = Vec::new();
+		let mut embedding = vec![0u8; size * std::mem::size_of::<f32>()];
+		for _ in 0..n_words {
+			word.clear();
+			reader.read_until(b' ', &mut word)?;
+			if word.pop() != Some(b' ') {
+				return Err(Error::new(
+					ErrorKind::UnexpectedEof,
+					"The file ended before all of the word embeddings were read.",
+				));
+			}
+			reader.read_exact(&mut embedding)?;
+			// word2vec writes a newline after each embedding, which is read as the start of the next word.
+			let word = String::from_utf8_lossy(&word);
+			let word = word.trim_start_matches('\n');
+			if !should_keep(word, vocabulary, &words) {
+				continue;
+			}
+			words.insert(word.to_owned(), words.len());
+			values.extend(
+				embedding
+					.chunks_exact(std::mem::size_of::<f32>())
+					.map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap())),
+			);
+		}
+		if words.is_empty() {
+			return Err(invalid_data("No word embeddings were found.".to_owned()));
+		}
+		Ok(WordEmbeddingModel {
+			size,
+			words,
+			values,
+		})
+	}
+}
+
+/// A fastText header consists of the number of words and the size of the embeddings.
+fn is_text_header(line: &str) -> bool {
+	let fields = line.split_whitespace().collect::<Vec<_>>();
+	fields.len() == 2 && fields.iter().all(|field| field.parse::<usize>().is_ok())
+}
+
+fn should_keep(
+	word: &str,
+	vocabulary: Option<&FnvHashSet<String>>,
+	words: &FnvHashMap<String, usize>,
+) -> bool {
+	let in_vocabulary = vocabulary
+		.map(|vocabulary| vocabulary.contains(word))
+		.unwrap_or(true);
+	in_vocabulary && !words.contains_key(word)
+}
+
+fn invalid_data(message: String) -> Error {
+	Error::new(ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn test_from_text() {
+		let file = "3 2\nthe 0.1 0.2\nquick -1 1\nfox 2.5 0\n";
+		let model = WordEmbeddingModel::from_text(file.as_bytes(), None).unwrap();
+		assert_eq!(model.size, 2);
+		assert_eq!(model.words.len(), 3);
+		assert_eq!(model.get("quick"), Some([-1.0, 1.0].as_slice()));
+		assert_eq!(model.get("dog"), None);
+	}
+
+	#[test]
+	fn test_from_text_without_header() {
+		let file = "the 0.1 0.2 0.3\nfox 1 2 3\n";
+		let vocabulary = ["fox".to_owned()].into_iter().collect();
+		let model = WordEmbeddingModel::from_text(file.as_bytes(), Some(&vocabulary)).unwrap();
+		assert_eq!(model.size, 3);
+		assert_eq!(model.words.len(), 1);
+		assert_eq!(model.get("fox"), Some([1.0, 2.0, 3.0].as_slice()));
+		assert_eq!(model.get("the"), None);
+	}
+
+	#[test]
+	fn test_from_binary() {
+		let mut file = b"2 2\n".to_vec();
+		for (word, embedding) in [("the", [0.5f32, -0.5]), ("fox", [1.0, 2.0])] {
+			file.extend(word.as_bytes());
+			file.push(b' ');
+			for value in embedding {
+				file.extend(value.to_le_bytes());
+			}
+			file.push(b'\n');
+		}
+		let model = WordEmbeddingModel::from_binary(file.as_slice(), None).unwrap();
+		assert_eq!(model.size, 2);
+		assert_eq!(model.get("the"), Some([0.5, -0.5].as_slice()));
+		assert_eq!(model.get("fox"), Some([1.0, 2.0].as_slice()));
+	}
 }
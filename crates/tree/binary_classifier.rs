@@ -1,6 +1,9 @@
 use crate::{
-	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
-	train::{train, Task, TrainOutput},
+	shap::{
+		compute_shap_interaction_values_for_example, compute_shap_values_for_example,
+		ComputeShapInteractionValuesForExampleOutput, ComputeShapValuesForExampleOutput,
+	},
+	train::{train, train_out_of_core, InitModel, Task, TrainOutput},
 	train_tree::TrainTree,
 	Progress, TrainOptions, Tree,
 };
@@ -47,6 +50,7 @@ impl BinaryClassifier {
 			task,
 			features,
 			TableColumnView::Enum(labels),
+			None,
 			train_options,
 			progress,
 		);
@@ -56,6 +60,55 @@ impl BinaryClassifier {
 		}
 	}
 
+	/// Continue training `init_model` on new data. The returned model keeps the bias and trees of `init_model` and appends up to `max_rounds` new trees trained to improve on its predictions. `features` must have the same columns as the features `init_model` was trained on.
+	pub fn train_warm_start(
+		init_model: &BinaryClassifier,
+		features: TableView,
+		labels: EnumTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> BinaryClassifierTrainOutput {
+		let task = Task::BinaryClassification;
+		let train_output = train(
+			task,
+			features,
+			TableColumnView::Enum(labels),
+			Some(InitModel::BinaryClassifier(init_model)),
+			train_options,
+			progress,
+		);
+		match train_output {
+			TrainOutput::BinaryClassifier(train_output) => train_output,
+			_ => unreachable!(),
+		}
+	}
+
+	/// Train a binary classifier on features that are too large to hold in memory. See `train_out_of_core` in `train.rs` for how `features_sample` and `feature_chunks` are used.
+	pub fn train_out_of_core<E>(
+		features_sample: TableView,
+		feature_chunks: impl Iterator<Item = Result<Table, E>>,
+		labels: EnumTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> Result<BinaryClassifierTrainOutput, E>
+	where
+		E: From<std::io::Error>,
+	{
+		let task = Task::BinaryClassification;
+		let train_output = train_out_of_core(
+			task,
+			features_sample,
+			feature_chunks,
+			TableColumnView::Enum(labels),
+			train_options,
+			progress,
+		)?;
+		match train_output {
+			TrainOutput::BinaryClassifier(train_output) => Ok(train_output),
+			_ => unreachable!(),
+		}
+	}
+
 	/// Make predictions.
 	pub fn predict(&self, features: ArrayView2<TableValue>, mut probabilities: ArrayViewMut1<f32>) {
 		probabilities.fill(self.bias);
@@ -86,6 +139,24 @@ impl BinaryClassifier {
 			.collect()
 	}
 
+	/// Compute SHAP interaction values.
+	pub fn compute_feature_interactions(
+		&self,
+		features: ArrayView2<TableValue>,
+	) -> Vec<ComputeShapInteractionValuesForExampleOutput> {
+		let trees = ArrayView1::from_shape(self.trees.len(), &self.trees).unwrap();
+		features
+			.axis_iter(Axis(0))
+			.map(|features| {
+				compute_shap_interaction_values_for_example(
+					features.as_slice().unwrap(),
+					trees,
+					self.bias,
+				)
+			})
+			.collect()
+	}
+
 	pub fn from_reader(
 		binary_classifier: crate::serialize::BinaryClassifierReader,
 	) -> BinaryClassifier {
@@ -96,7 +167,15 @@ impl BinaryClassifier {
 		&self,
 		writer: &mut buffalo::Writer,
 	) -> buffalo::Position<crate::serialize::BinaryClassifierWriter> {
-		crate::serialize::serialize_binary_classifier(self, writer)
+		self.to_writer_with_leaf_value_encoding(writer, crate::LeafValueEncoding::F64)
+	}
+
+	pub fn to_writer_with_leaf_value_encoding(
+		&self,
+		writer: &mut buffalo::Writer,
+		leaf_value_encoding: crate::LeafValueEncoding,
+	) -> buffalo::Position<crate::serialize::BinaryClassifierWriter> {
+		crate::serialize::serialize_binary_classifier(self, writer, leaf_value_encoding)
 	}
 
 	#[must_use]
@@ -116,6 +195,7 @@ impl BinaryClassifier {
 /// This function is used by the common train function to update the logits after each tree is trained for binary classification.
 pub fn update_logits(
 	trees_for_round: &[TrainTree],
+	tree_weight: f32,
 	binned_features: ArrayView2<TableValue>,
 	mut predictions: ArrayViewMut2<f32>,
 ) {
@@ -123,7 +203,7 @@ pub fn update_logits(
 		for (prediction, features) in
 			zip!(predictions.iter_mut(), binned_features.axis_iter(Axis(0)))
 		{
-			*prediction += tree.predict(features.as_slice().unwrap());
+			*prediction += tree_weight * tree.predict(features.as_slice().unwrap());
 		}
 	}
 }
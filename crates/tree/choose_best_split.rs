@@ -24,9 +24,13 @@ pub struct ChooseBestSplitRootOptions<'a> {
 	pub binned_features_row_major: &'a Option<BinnedFeaturesRowMajor>,
 	pub binning_instructions: &'a [BinningInstruction],
 	pub examples_index: &'a [u32],
+	pub gradients_ordered_buffer: &'a mut [f32],
 	pub gradients: &'a [f32],
 	pub hessians_are_constant: bool,
+	pub hessians_ordered_buffer: &'a mut [f32],
 	pub hessians: &'a [f32],
+	pub sampled_features_for_node: Option<&'a [bool]>,
+	pub sampled_features_for_tree: Option<&'a [bool]>,
 	#[cfg(feature = "timing")]
 	pub timing: &'a Timing,
 	pub train_options: &'a TrainOptions,
@@ -45,12 +49,14 @@ pub struct ChooseBestSplitsNotRootOptions<'a> {
 	pub hessians: &'a [f32],
 	pub left_child_examples_index: &'a [u32],
 	pub left_child_n_examples: usize,
+	pub left_child_sampled_features: Option<&'a [bool]>,
 	pub left_child_sum_gradients: f64,
 	pub left_child_sum_hessians: f64,
 	pub parent_bin_stats: PoolItem<BinStats>,
 	pub parent_depth: usize,
 	pub right_child_examples_index: &'a [u32],
 	pub right_child_n_examples: usize,
+	pub right_child_sampled_features: Option<&'a [bool]>,
 	pub right_child_sum_gradients: f64,
 	pub right_child_sum_hessians: f64,
 	#[cfg(feature = "timing")]
@@ -103,34 +109,58 @@ pub fn choose_best_split_root(options: ChooseBestSplitRootOptions) -> ChooseBest
 		binned_features_row_major,
 		binning_instructions,
 		examples_index,
+		gradients_ordered_buffer,
 		gradients,
 		hessians_are_constant,
+		hessians_ordered_buffer,
 		hessians,
+		sampled_features_for_node,
+		sampled_features_for_tree,
 		train_options,
 		..
 	} = options;
 	#[cfg(feature = "timing")]
 	let timing = options.timing;
+	// If the tree is trained on a sample of the examples, the root only contains the examples in the examples index.
+	let n_examples = examples_index.len();
+	let is_sample = n_examples < gradients.len();
 	// Compute the sums of gradients and hessians.
 	#[cfg(feature = "timing")]
 	let start = std::time::Instant::now();
-	let sum_gradients = gradients
-		.par_iter()
-		.map(|gradient| *gradient as f64)
-		.sum::<f64>();
-	let sum_hessians = if hessians_are_constant {
-		hessians.len().to_f64().unwrap()
+	let (sum_gradients, sum_hessians) = if is_sample {
+		let sum_gradients = examples_index
+			.par_iter()
+			.map(|example_index| gradients[example_index.to_usize().unwrap()] as f64)
+			.sum::<f64>();
+		let sum_hessians = if hessians_are_constant {
+			n_examples.to_f64().unwrap()
+		} else {
+			examples_index
+				.par_iter()
+				.map(|example_index| hessians[example_index.to_usize().unwrap()] as f64)
+				.sum::<f64>()
+		};
+		(sum_gradients, sum_hessians)
 	} else {
-		hessians
+		let sum_gradients = gradients
 			.par_iter()
-			.map(|hessian| *hessian as f64)
-			.sum::<f64>()
+			.map(|gradient| *gradient as f64)
+			.sum::<f64>();
+		let sum_hessians = if hessians_are_constant {
+			hessians.len().to_f64().unwrap()
+		} else {
+			hessians
+				.par_iter()
+				.map(|hessian| *hessian as f64)
+				.sum::<f64>()
+		};
+		(sum_gradients, sum_hessians)
 	};
 	#[cfg(feature = "timing")]
 	timing.sum_gradients_and_hessians_root.inc(start.elapsed());
 
 	// Determine if we should try to split the root.
-	let should_try_to_split_root = gradients.len() >= 2 * train_options.min_examples_per_node
+	let should_try_to_split_root = n_examples >= 2 * train_options.min_examples_per_node
 		&& sum_hessians >= 2.0 * train_options.min_sum_hessians_per_node as f64;
 	if !should_try_to_split_root {
 		return ChooseBestSplitOutput::Failure(ChooseBestSplitFailure {
@@ -147,13 +177,32 @@ pub fn choose_best_split_root(options: ChooseBestSplitRootOptions) -> ChooseBest
 		match train_options.binned_features_layout {
 			BinnedFeaturesLayout::ColumnMajor => {
 				let bin_stats = bin_stats.as_column_major_mut().unwrap();
+				// If the tree is trained on a sample of the examples, fill the gradients and hessians ordered buffers so the bin stats can be computed from the examples index as they are for the other nodes.
+				let sample_examples_index = if is_sample {
+					fill_gradients_and_hessians_ordered_buffers(
+						examples_index,
+						gradients,
+						hessians,
+						gradients_ordered_buffer,
+						hessians_ordered_buffer,
+						hessians_are_constant,
+					);
+					Some(examples_index)
+				} else {
+					None
+				};
 				choose_best_split_root_column_major(ChooseBestSplitRootColumnMajorOptions {
 					bin_stats,
 					binned_features_column_major,
 					binning_instructions,
+					gradients_ordered_buffer,
 					gradients,
 					hessians_are_constant,
+					hessians_ordered_buffer,
 					hessians,
+					sample_examples_index,
+					sampled_features_for_node,
+					sampled_features_for_tree,
 					sum_gradients,
 					sum_hessians,
 					train_options,
@@ -169,6 +218,8 @@ pub fn choose_best_split_root(options: ChooseBestSplitRootOptions) -> ChooseBest
 					gradients,
 					hessians_are_constant,
 					hessians,
+					sampled_features_for_node,
+					sampled_features_for_tree,
 					sum_gradients,
 					sum_hessians,
 					train_options,
@@ -207,9 +258,14 @@ struct ChooseBestSplitRootColumnMajorOptions<'a> {
 	bin_stats: &'a mut Vec<Vec<BinStatsEntry>>,
 	binned_features_column_major: &'a BinnedFeaturesColumnMajor,
 	binning_instructions: &'a [BinningInstruction],
+	gradients_ordered_buffer: &'a [f32],
 	gradients: &'a [f32],
 	hessians_are_constant: bool,
+	hessians_ordered_buffer: &'a [f32],
 	hessians: &'a [f32],
+	sample_examples_index: Option<&'a [u32]>,
+	sampled_features_for_node: Option<&'a [bool]>,
+	sampled_features_for_tree: Option<&'a [bool]>,
 	sum_gradients: f64,
 	sum_hessians: f64,
 	train_options: &'a TrainOptions,
@@ -222,9 +278,14 @@ fn choose_best_split_root_column_major(
 		bin_stats,
 		binned_features_column_major,
 		binning_instructions,
+		gradients_ordered_buffer,
 		gradients,
 		hessians_are_constant,
+		hessians_ordered_buffer,
 		hessians,
+		sample_examples_index,
+		sampled_features_for_node,
+		sampled_features_for_tree,
 		sum_gradients,
 		sum_hessians,
 		train_options,
@@ -247,21 +308,38 @@ fn choose_best_split_root_column_major(
 				is_feature_splittable,
 			),
 		)| {
+			// Features that were not sampled for this tree are never split.
+			if !is_feature_sampled(sampled_features_for_tree, feature_index) {
+				return None;
+			}
 			// Compute the bin stats.
-			compute_bin_stats_column_major::<true>(
-				bin_stats_for_feature,
-				&[],
-				binned_feature_column,
-				gradients,
-				hessians,
-				hessians_are_constant,
-			);
+			let n_examples = if let Some(examples_index) = sample_examples_index {
+				compute_bin_stats_column_major::<false>(
+					bin_stats_for_feature,
+					examples_index,
+					binned_feature_column,
+					gradients_ordered_buffer,
+					hessians_ordered_buffer,
+					hessians_are_constant,
+				);
+				examples_index.len()
+			} else {
+				compute_bin_stats_column_major::<true>(
+					bin_stats_for_feature,
+					&[],
+					binned_feature_column,
+					gradients,
+					hessians,
+					hessians_are_constant,
+				);
+				binned_feature_column.len()
+			};
 			// Choose the best split for this featue.
 			let best_split_for_feature = choose_best_split_for_feature(
 				feature_index,
 				binning_instructions,
 				bin_stats_for_feature,
-				binned_feature_column.len(),
+				n_examples,
 				sum_gradients,
 				sum_hessians,
 				train_options,
@@ -269,6 +347,10 @@ fn choose_best_split_root_column_major(
 			if best_split_for_feature.is_some() {
 				*is_feature_splittable = true;
 			}
+			// Only the features sampled for this node compete to split it, but the others remain splittable by its descendants.
+			if !is_feature_sampled(sampled_features_for_node, feature_index) {
+				return None;
+			}
 			best_split_for_feature
 		},
 	)
@@ -285,6 +367,8 @@ struct ChooseBestSplitRootRowMajorOptions<'a> {
 	gradients: &'a [f32],
 	hessians_are_constant: bool,
 	hessians: &'a [f32],
+	sampled_features_for_node: Option<&'a [bool]>,
+	sampled_features_for_tree: Option<&'a [bool]>,
 	sum_gradients: f64,
 	sum_hessians: f64,
 	train_options: &'a TrainOptions,
@@ -301,15 +385,14 @@ fn choose_best_split_root_row_major(
 		gradients,
 		hessians_are_constant,
 		hessians,
+		sampled_features_for_node,
+		sampled_features_for_tree,
 		sum_gradients,
 		sum_hessians,
 		train_options,
 	} = options;
-	// Compute the bin stats for the child with fewer examples.
-	let n_examples = match binned_features_row_major {
-		BinnedFeaturesRowMajor::U16(binned_features) => binned_features.values_with_offsets.nrows(),
-		BinnedFeaturesRowMajor::U32(binned_features) => binned_features.values_with_offsets.nrows(),
-	};
+	// Compute the bin stats for the examples in the root, which are all of the examples unless the tree is trained on a sample of them.
+	let n_examples = examples_index.len();
 	let n_threads = rayon::current_num_threads();
 	let chunk_size = (n_examples + n_threads - 1) / n_threads;
 	*bin_stats = examples_index
@@ -347,6 +430,8 @@ fn choose_best_split_root_row_major(
 				binning_instructions,
 				binned_features_row_major_inner,
 				n_examples,
+				sampled_features_for_node,
+				sampled_features_for_tree,
 				sum_gradients,
 				sum_hessians,
 				train_options,
@@ -359,6 +444,8 @@ fn choose_best_split_root_row_major(
 				binning_instructions,
 				binned_features_row_major_inner,
 				n_examples,
+				sampled_features_for_node,
+				sampled_features_for_tree,
 				sum_gradients,
 				sum_hessians,
 				train_options,
@@ -376,6 +463,8 @@ where
 	binning_instructions: &'a [BinningInstruction],
 	binned_features_row_major_inner: &'a BinnedFeaturesRowMajorInner<T>,
 	n_examples: usize,
+	sampled_features_for_node: Option<&'a [bool]>,
+	sampled_features_for_tree: Option<&'a [bool]>,
 	sum_gradients: f64,
 	sum_hessians: f64,
 	train_options: &'a TrainOptions,
@@ -392,6 +481,8 @@ where
 		binning_instructions,
 		binned_features_row_major_inner,
 		n_examples,
+		sampled_features_for_node,
+		sampled_features_for_tree,
 		sum_gradients,
 		sum_hessians,
 		train_options,
@@ -408,6 +499,10 @@ where
 	.map(
 		|(feature_index, (binning_instructions, offset, is_feature_splittable))| {
 			let _ = &bin_stats;
+			// Features that were not sampled for this tree are never split.
+			if !is_feature_sampled(sampled_features_for_tree, feature_index) {
+				return None;
+			}
 			let bin_stats = unsafe { &mut *bin_stats.0 };
 			let offset = offset.to_usize().unwrap();
 			let bin_stats_range = offset..offset + binning_instructions.n_bins();
@@ -424,6 +519,10 @@ where
 			if best_split_for_feature.is_some() {
 				*is_feature_splittable = true;
 			}
+			// Only the features sampled for this node compete to split it, but the others remain splittable by its descendants.
+			if !is_feature_sampled(sampled_features_for_node, feature_index) {
+				return None;
+			}
 			best_split_for_feature
 		},
 	)
@@ -447,12 +546,14 @@ pub fn choose_best_splits_not_root(
 		hessians,
 		left_child_examples_index,
 		left_child_n_examples,
+		left_child_sampled_features,
 		left_child_sum_gradients,
 		left_child_sum_hessians,
 		parent_bin_stats,
 		parent_depth,
 		right_child_examples_index,
 		right_child_n_examples,
+		right_child_sampled_features,
 		right_child_sum_gradients,
 		right_child_sum_hessians,
 		splittable_features,
@@ -575,6 +676,18 @@ pub fn choose_best_splits_not_root(
 	let (left_child_splittable_features, right_child_splittable_features) =
 		compute_splittable_features_for_children(&children_best_splits_for_features);
 
+	// Only the features sampled for each child compete to split it.
+	let children_best_splits_for_features = children_best_splits_for_features
+		.into_iter()
+		.enumerate()
+		.map(|(feature_index, (left, right))| {
+			(
+				left.filter(|_| is_feature_sampled(left_child_sampled_features, feature_index)),
+				right.filter(|_| is_feature_sampled(right_child_sampled_features, feature_index)),
+			)
+		})
+		.collect();
+
 	// Choose the splits for the left and right children with the highest gain.
 	let (left_child_best_split, right_child_best_split) =
 		choose_splits_with_highest_gain(children_best_splits_for_features);
@@ -1435,6 +1548,13 @@ fn compute_splittable_features_for_children(
 	)
 }
 
+/// Determine whether a feature was sampled. If no features were sampled, all of them are used.
+fn is_feature_sampled(sampled_features: Option<&[bool]>, feature_index: usize) -> bool {
+	sampled_features
+		.map(|sampled_features| sampled_features[feature_index])
+		.unwrap_or(true)
+}
+
 struct BinStatsPtr(*mut Vec<BinStatsEntry>);
 unsafe impl Send for BinStatsPtr {}
 unsafe impl Sync for BinStatsPtr {}
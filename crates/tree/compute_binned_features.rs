@@ -1,11 +1,17 @@
 use crate::compute_binning_instructions::BinningInstruction;
 use crate::TrainOptions;
-use modelfox_table::{TableColumnView, TableView};
+use memmap::Mmap;
+use modelfox_table::{Table, TableColumnView, TableView};
 use modelfox_zip::pzip;
 use ndarray::prelude::*;
 use num::{Num, NumCast, ToPrimitive};
 use rayon::{self, prelude::*};
-use std::collections::BTreeMap;
+use std::{
+	collections::BTreeMap,
+	io::{BufWriter, Write},
+	marker::PhantomData,
+	ops::Deref,
+};
 
 #[derive(Debug)]
 pub enum BinnedFeaturesRowMajor {
@@ -29,8 +35,47 @@ pub struct BinnedFeaturesColumnMajor {
 
 #[derive(Debug)]
 pub enum BinnedFeaturesColumnMajorColumn {
-	U8(Vec<u8>),
-	U16(Vec<u16>),
+	U8(BinnedValues<u8>),
+	U16(BinnedValues<u16>),
+}
+
+/// These are the binned values for a single feature. They are either held in memory or, when training out of core, memory mapped from a temporary file so the operating system can page them in and out as needed.
+#[derive(Debug)]
+pub enum BinnedValues<T> {
+	Memory(Vec<T>),
+	Mapped(MappedBinnedValues<T>),
+}
+
+#[derive(Debug)]
+pub struct MappedBinnedValues<T> {
+	mmap: Mmap,
+	len: usize,
+	marker: PhantomData<T>,
+}
+
+impl<T> BinnedValues<T> {
+	pub fn as_slice(&self) -> &[T] {
+		match self {
+			BinnedValues::Memory(values) => values.as_slice(),
+			// The memory map is page aligned, so it is aligned for `u8` and `u16`, and it holds exactly `len` values written by `BinnedValuesWriter`.
+			BinnedValues::Mapped(values) => unsafe {
+				std::slice::from_raw_parts(values.mmap.as_ptr() as *const T, values.len)
+			},
+		}
+	}
+}
+
+impl<T> Deref for BinnedValues<T> {
+	type Target = [T];
+	fn deref(&self) -> &[T] {
+		self.as_slice()
+	}
+}
+
+impl<T> From<Vec<T>> for BinnedValues<T> {
+	fn from(values: Vec<T>) -> BinnedValues<T> {
+		BinnedValues::Memory(values)
+	}
 }
 
 impl BinnedFeaturesColumnMajorColumn {
@@ -40,6 +85,17 @@ impl BinnedFeaturesColumnMajorColumn {
 			BinnedFeaturesColumnMajorColumn::U16(values) => values.len(),
 		}
 	}
+
+	pub fn get(&self, example_index: usize) -> usize {
+		match self {
+			BinnedFeaturesColumnMajorColumn::U8(values) => {
+				values[example_index].to_usize().unwrap()
+			}
+			BinnedFeaturesColumnMajorColumn::U16(values) => {
+				values[example_index].to_usize().unwrap()
+			}
+		}
+	}
 }
 
 pub fn compute_binned_features_row_major(
@@ -160,7 +216,7 @@ pub fn compute_binned_features_column_major(
 					progress,
 				);
 				output.binned_feature_column.map(|binned_feature_column| {
-					BinnedFeaturesColumnMajorColumn::U8(binned_feature_column)
+					BinnedFeaturesColumnMajorColumn::U8(binned_feature_column.into())
 				})
 			}
 			BinningInstruction::Enum { n_variants } => {
@@ -169,14 +225,14 @@ pub fn compute_binned_features_column_major(
 						feature, progress,
 					);
 					Some(BinnedFeaturesColumnMajorColumn::U8(
-						output.binned_feature_column,
+						output.binned_feature_column.into(),
 					))
 				} else if *n_variants <= 65535 {
 					let output = compute_binned_features_column_major_for_enum_feature_inner(
 						feature, progress,
 					);
 					Some(BinnedFeaturesColumnMajorColumn::U16(
-						output.binned_feature_column,
+						output.binned_feature_column.into(),
 					))
 				} else {
 					panic!("enum column has too many variants")
@@ -221,12 +277,7 @@ fn compute_binned_features_column_major_for_number_feature(
 			if !feature_value.is_finite() {
 				return 0;
 			}
-			// Use binary search on the thresholds to find the bin for the feature value.
-			let bin = thresholds
-				.binary_search_by(|threshold| threshold.partial_cmp(feature_value).unwrap())
-				.unwrap_or_else(|bin| bin)
-				.to_u8()
-				.unwrap() + 1;
+			let bin = compute_number_bin(thresholds, *feature_value);
 			if let Some(entry) = n_examples_per_bin.get_mut(&bin) {
 				*entry += 1;
 			} else {
@@ -296,3 +347,189 @@ where
 		binned_feature_column,
 	}
 }
+
+/// Compute the bin for a finite value of a number feature. Bin 0 is reserved for invalid values.
+fn compute_number_bin(thresholds: &[f32], value: f32) -> u8 {
+	// Use binary search on the thresholds to find the bin for the feature value.
+	thresholds
+		.binary_search_by(|threshold| threshold.partial_cmp(&value).unwrap())
+		.unwrap_or_else(|bin| bin)
+		.to_u8()
+		.unwrap()
+		+ 1
+}
+
+/// Compute the binned features for features that are read in chunks, which is used to train on datasets that do not fit in memory. The bins for each feature are appended to a temporary file as each chunk is read, and the files are memory mapped after the last chunk, so only the chunk being binned is held in memory. Every chunk must have the same columns as the features the binning instructions were computed from.
+pub fn compute_binned_features_column_major_out_of_core<E>(
+	feature_chunks: impl Iterator<Item = Result<Table, E>>,
+	binning_instructions: &[BinningInstruction],
+	train_options: &TrainOptions,
+	progress: &(impl Fn(u64) + Sync),
+) -> Result<ComputeBinnedFeaturesColumnMajorOutput, E>
+where
+	E: From<std::io::Error>,
+{
+	let mut writers = binning_instructions
+		.iter()
+		.map(|binning_instruction| {
+			let n_bins = binning_instruction.n_bins();
+			let writer = match binning_instruction {
+				BinningInstruction::Number { .. } => {
+					BinnedFeaturesColumnMajorColumnWriter::U8(BinnedValuesWriter::new(n_bins)?)
+				}
+				BinningInstruction::Enum { n_variants } => {
+					if *n_variants <= 255 {
+						BinnedFeaturesColumnMajorColumnWriter::U8(BinnedValuesWriter::new(n_bins)?)
+					} else if *n_variants <= 65535 {
+						BinnedFeaturesColumnMajorColumnWriter::U16(BinnedValuesWriter::new(n_bins)?)
+					} else {
+						panic!("enum column has too many variants")
+					}
+				}
+			};
+			Ok(writer)
+		})
+		.collect::<std::io::Result<Vec<_>>>()?;
+	let mut n_examples = 0;
+	for feature_chunk in feature_chunks {
+		let feature_chunk = feature_chunk?;
+		let feature_chunk = feature_chunk.view();
+		pzip!(
+			writers.as_mut_slice(),
+			feature_chunk.columns().as_slice(),
+			binning_instructions,
+		)
+		.try_for_each(|(writer, feature, binning_instruction)| match writer {
+			BinnedFeaturesColumnMajorColumnWriter::U8(writer) => {
+				writer.write(feature, binning_instruction)
+			}
+			BinnedFeaturesColumnMajorColumnWriter::U16(writer) => {
+				writer.write(feature, binning_instruction)
+			}
+		})?;
+		n_examples += feature_chunk.nrows();
+		progress(feature_chunk.nrows().to_u64().unwrap());
+	}
+	let mut splittable_features = Vec::new();
+	let mut train_feature_index_to_feature_index = Vec::new();
+	for (feature_index, (writer, binning_instruction)) in
+		writers.into_iter().zip(binning_instructions).enumerate()
+	{
+		// As in `compute_binned_features_column_major`, number features that cannot be split are dropped.
+		if let BinningInstruction::Number { .. } = binning_instruction {
+			let n_examples_per_bin = match &writer {
+				BinnedFeaturesColumnMajorColumnWriter::U8(writer) => &writer.n_examples_per_bin,
+				BinnedFeaturesColumnMajorColumnWriter::U16(writer) => &writer.n_examples_per_bin,
+			};
+			let n_examples_per_bin = n_examples_per_bin
+				.iter()
+				.enumerate()
+				.skip(1)
+				.filter(|(_, n_examples_in_bin)| **n_examples_in_bin > 0)
+				.map(|(bin, n_examples_in_bin)| (bin.to_u8().unwrap(), *n_examples_in_bin))
+				.collect::<BTreeMap<_, _>>();
+			if n_examples_per_bin.is_empty()
+				|| !compute_is_splittable(&n_examples_per_bin, n_examples, train_options)
+			{
+				continue;
+			}
+		}
+		let column = match writer {
+			BinnedFeaturesColumnMajorColumnWriter::U8(writer) => {
+				BinnedFeaturesColumnMajorColumn::U8(writer.finish()?)
+			}
+			BinnedFeaturesColumnMajorColumnWriter::U16(writer) => {
+				BinnedFeaturesColumnMajorColumn::U16(writer.finish()?)
+			}
+		};
+		train_feature_index_to_feature_index.push(feature_index);
+		splittable_features.push(column);
+	}
+	Ok(ComputeBinnedFeaturesColumnMajorOutput {
+		binned_features: BinnedFeaturesColumnMajor {
+			columns: splittable_features,
+		},
+		used_feature_indexes: train_feature_index_to_feature_index,
+	})
+}
+
+enum BinnedFeaturesColumnMajorColumnWriter {
+	U8(BinnedValuesWriter<u8>),
+	U16(BinnedValuesWriter<u16>),
+}
+
+/// This struct appends the binned values for a single feature to a temporary file.
+struct BinnedValuesWriter<T> {
+	file: BufWriter<std::fs::File>,
+	len: usize,
+	n_examples_per_bin: Vec<usize>,
+	marker: PhantomData<T>,
+}
+
+impl<T> BinnedValuesWriter<T>
+where
+	T: Send + NumCast + ToPrimitive + Copy,
+{
+	fn new(n_bins: usize) -> std::io::Result<BinnedValuesWriter<T>> {
+		Ok(BinnedValuesWriter {
+			file: BufWriter::new(tempfile::tempfile()?),
+			len: 0,
+			n_examples_per_bin: vec![0; n_bins],
+			marker: PhantomData,
+		})
+	}
+
+	fn write(
+		&mut self,
+		feature: &TableColumnView,
+		binning_instruction: &BinningInstruction,
+	) -> std::io::Result<()> {
+		let bins: Vec<T> = match binning_instruction {
+			BinningInstruction::Number { thresholds } => feature
+				.as_number()
+				.unwrap()
+				.iter()
+				.map(|feature_value| {
+					// Invalid values go to the first bin.
+					if !feature_value.is_finite() {
+						T::from(0).unwrap()
+					} else {
+						T::from(compute_number_bin(thresholds, *feature_value)).unwrap()
+					}
+				})
+				.collect(),
+			BinningInstruction::Enum { .. } => feature
+				.as_enum()
+				.unwrap()
+				.iter()
+				.map(|feature_value| T::from(feature_value.map(|v| v.get()).unwrap_or(0)).unwrap())
+				.collect(),
+		};
+		for bin in bins.iter() {
+			self.n_examples_per_bin[bin.to_usize().unwrap()] += 1;
+		}
+		let bytes = unsafe {
+			std::slice::from_raw_parts(
+				bins.as_ptr() as *const u8,
+				bins.len() * std::mem::size_of::<T>(),
+			)
+		};
+		self.file.write_all(bytes)?;
+		self.len += bins.len();
+		Ok(())
+	}
+
+	fn finish(self) -> std::io::Result<BinnedValues<T>> {
+		// Empty files cannot be memory mapped.
+		if self.len == 0 {
+			return Ok(BinnedValues::Memory(Vec::new()));
+		}
+		let file = self.file.into_inner().map_err(|error| error.into_error())?;
+		let mmap = unsafe { Mmap::map(&file)? };
+		Ok(BinnedValues::Mapped(MappedBinnedValues {
+			mmap,
+			len: self.len,
+			marker: PhantomData,
+		}))
+	}
+}
@@ -1,5 +1,5 @@
 /*!
-This crate implements machine learning models for regression and classification using ensembles of decision trees. It has many similarities to [LightGBM](github.com/microsoft/lightgbm), [XGBoost](github.com/xgboost/xgboost), and others, but is written in pure Rust.
+This crate implements machine learning models for regression, classification, and ranking using ensembles of decision trees. It has many similarities to [LightGBM](github.com/microsoft/lightgbm), [XGBoost](github.com/xgboost/xgboost), and others, but is written in pure Rust.
 
 For an example of regression, see `benchmarks/boston.rs`.rs`. For an example of binary classification, see `benchmarks/heart_disease.rs`. For an example of multiclass classification, see `benchmarks/iris.rs`.
 */
@@ -7,6 +7,7 @@ For an example of regression, see `benchmarks/boston.rs`.rs`. For an example of
 pub use self::{
 	binary_classifier::{BinaryClassifier, BinaryClassifierTrainOutput},
 	multiclass_classifier::{MulticlassClassifier, MulticlassClassifierTrainOutput},
+	ranker::{Ranker, RankerTrainOutput},
 	regressor::{Regressor, RegressorTrainOutput},
 };
 use bitvec::prelude::*;
@@ -20,8 +21,10 @@ mod compute_binning_instructions;
 mod compute_feature_importances;
 mod multiclass_classifier;
 mod pool;
+mod ranker;
 mod rearrange_examples_index;
 mod regressor;
+mod sample;
 pub mod serialize;
 mod shap;
 #[cfg(feature = "timing")]
@@ -34,15 +37,27 @@ pub struct Progress<'a> {
 	pub handle_progress_event: &'a mut dyn FnMut(TrainProgressEvent),
 }
 
-/// These are the options passed to `Regressor::train`, `BinaryClassifier::train`, and `MulticlassClassifier::train`.
+/// These are the options passed to `Regressor::train`, `BinaryClassifier::train`, `MulticlassClassifier::train`, and `Ranker::train`.
 #[derive(Clone, Debug)]
 pub struct TrainOptions {
+	/// This is the fraction of the training examples sampled without replacement to train each tree. If it is less than 1, each tree sees a different random subset of the examples, which reduces overfitting and speeds up training.
+	pub bagging_fraction: f32,
+	/// The examples are resampled every `bagging_frequency` rounds. If this value is 0, bagging is disabled.
+	pub bagging_frequency: usize,
 	/// This option controls whether binned features will be laid out in row major or column major order. Each will produce the same result, but row major will be faster for datasets with more rows and fewer columns, while column major will be faster for datasets with fewer rows and more columns.
 	pub binned_features_layout: BinnedFeaturesLayout,
 	/// If true, the model will include the loss on the training data after each round.
 	pub compute_losses: bool,
 	/// This option controls early stopping. If it is `Some`, then early stopping will be enabled. If it is `None`, then early stopping will be disabled.
 	pub early_stopping_options: Option<EarlyStoppingOptions>,
+	/// This option enables DART boosting. If it is `Some`, a random subset of the previously trained trees is dropped when computing the gradients for each round, and the weights of the new and dropped trees are renormalized.
+	pub dart_options: Option<DartOptions>,
+	/// This is the fraction of the features, chosen from those sampled for the tree, that are considered when splitting each node.
+	pub feature_fraction_per_node: f32,
+	/// This is the fraction of the features randomly sampled to be considered for splits in each tree.
+	pub feature_fraction_per_tree: f32,
+	/// This option controls gradient-based one-side sampling. If it is `Some`, each tree is trained on the examples with the largest gradients and a random sample of the rest, and bagging is disabled.
+	pub goss_options: Option<GossOptions>,
 	/// This option sets the L2 regularization value for continuous splits, which helps avoid overfitting.
 	pub l2_regularization_for_continuous_splits: f32,
 	/// This option sets the L2 regularization value for discrete splits, which helps avoid overfitting.
@@ -65,6 +80,8 @@ pub struct TrainOptions {
 	pub min_gain_to_split: f32,
 	/// A split will only be considered valid if the sum of hessians in each of the resulting children is at least this value.
 	pub min_sum_hessians_per_node: f32,
+	/// This is the seed for the random number generator used to sample examples and features, which makes training reproducible.
+	pub seed: u64,
 	/// When choosing which direction each enum variant should be sent in a discrete split, the enum variants are sorted by a score computed from the sum of gradients and hessians for examples with that enum variant. This smoothing factor is added to the denominator of that score.
 	pub smoothing_factor_for_discrete_bin_sorting: f32,
 }
@@ -72,9 +89,15 @@ pub struct TrainOptions {
 impl Default for TrainOptions {
 	fn default() -> TrainOptions {
 		TrainOptions {
+			bagging_fraction: 1.0,
+			bagging_frequency: 0,
 			binned_features_layout: BinnedFeaturesLayout::ColumnMajor,
 			compute_losses: false,
 			early_stopping_options: None,
+			dart_options: None,
+			feature_fraction_per_node: 1.0,
+			feature_fraction_per_tree: 1.0,
+			goss_options: None,
 			l2_regularization_for_continuous_splits: 0.0,
 			l2_regularization_for_discrete_splits: 10.0,
 			learning_rate: 0.1,
@@ -86,6 +109,7 @@ impl Default for TrainOptions {
 			min_gain_to_split: 0.0,
 			min_sum_hessians_per_node: 1e-3,
 			max_examples_for_computing_bin_thresholds: 200_000,
+			seed: 42,
 			smoothing_factor_for_discrete_bin_sorting: 10.0,
 		}
 	}
@@ -109,6 +133,26 @@ pub struct EarlyStoppingOptions {
 	pub min_decrease_in_loss_for_significant_change: f32,
 }
 
+/// The parameters in this struct control DART boosting, which drops trees like dropout in neural networks to keep the later trees from only fitting the residuals of the earlier ones.
+#[derive(Clone, Debug)]
+pub struct DartOptions {
+	/// This is the probability that each previously trained round of trees is dropped.
+	pub drop_rate: f32,
+	/// This is the maximum number of rounds dropped at once. If it is 0, the number is not limited.
+	pub max_drop: usize,
+	/// This is the probability of skipping dropout in a round, which then trains like plain gradient boosting.
+	pub skip_drop: f32,
+}
+
+/// The parameters in this struct control gradient-based one-side sampling, which keeps the examples with the largest gradients and samples from the rest, amplifying the sampled examples to keep the gradient sums unbiased.
+#[derive(Clone, Debug)]
+pub struct GossOptions {
+	/// This is the fraction of the examples with the largest gradients that are always kept.
+	pub top_rate: f32,
+	/// This is the fraction of the examples that are randomly sampled from the rest.
+	pub other_rate: f32,
+}
+
 /// This struct describes the training progress.
 #[derive(Clone, Debug)]
 pub enum TrainProgressEvent {
@@ -118,10 +162,23 @@ pub enum TrainProgressEvent {
 	TrainDone,
 }
 
+/// This enum controls how leaf values are stored when a model is written with `to_writer_with_leaf_value_encoding`. Encodings other than `F64` make the model smaller at the cost of the precision of its predictions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeafValueEncoding {
+	/// Store each leaf value as a 64 bit float.
+	F64,
+	/// Store each leaf value as a 16 bit float.
+	F16,
+	/// Store each leaf value as an 8 bit integer, which is multiplied by a scale stored once for each tree.
+	I8,
+}
+
 /// Trees are stored as a `Vec` of `Node`s. Each branch in the tree has two indexes into the `Vec`, one for each of its children.
 #[derive(Clone, Debug)]
 pub struct Tree {
 	pub nodes: Vec<Node>,
+	/// The tree's output is its leaf value multiplied by this weight. It is 1 unless the tree was trained with DART, which renormalizes the weights of the trees each time some are dropped.
+	pub weight: f32,
 }
 
 impl Tree {
@@ -133,8 +190,8 @@ impl Tree {
 		unsafe {
 			loop {
 				match self.nodes.get_unchecked(node_index) {
-					// We made it to a leaf! The prediction is the leaf's value.
-					Node::Leaf(LeafNode { value, .. }) => return *value as f32,
+					// We made it to a leaf! The prediction is the leaf's value scaled by the tree's weight.
+					Node::Leaf(LeafNode { value, .. }) => return self.weight * *value as f32,
 					// This branch uses a continuous split.
 					Node::Branch(BranchNode {
 						left_child_index,
@@ -143,16 +200,22 @@ impl Tree {
 							BranchSplit::Continuous(BranchSplitContinuous {
 								feature_index,
 								split_value,
-								..
+								invalid_values_direction,
 							}),
 						..
 					}) => {
-						node_index = if example.get_unchecked(*feature_index).as_number().unwrap()
-							<= split_value
-						{
-							*left_child_index
+						let value = *example.get_unchecked(*feature_index).as_number().unwrap();
+						// Invalid values are sent in the direction chosen during training.
+						let direction = if value.is_nan() {
+							*invalid_values_direction
+						} else if value <= *split_value {
+							SplitDirection::Left
 						} else {
-							*right_child_index
+							SplitDirection::Right
+						};
+						node_index = match direction {
+							SplitDirection::Left => *left_child_index,
+							SplitDirection::Right => *right_child_index,
 						};
 					}
 					// This branch uses a discrete split.
@@ -1,6 +1,9 @@
 use crate::{
-	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
-	train::TrainOutput,
+	shap::{
+		compute_shap_interaction_values_for_example, compute_shap_values_for_example,
+		ComputeShapInteractionValuesForExampleOutput, ComputeShapValuesForExampleOutput,
+	},
+	train::{InitModel, TrainOutput},
 	train_tree::TrainTree,
 	Progress, TrainOptions, Tree,
 };
@@ -46,6 +49,7 @@ impl MulticlassClassifier {
 			task,
 			features,
 			TableColumnView::Enum(labels),
+			None,
 			train_options,
 			progress,
 		);
@@ -55,6 +59,59 @@ impl MulticlassClassifier {
 		}
 	}
 
+	/// Continue training `init_model` on new data. The returned model keeps the biases and trees of `init_model` and appends up to `max_rounds` new rounds of trees trained to improve on its predictions. `features` must have the same columns as the features `init_model` was trained on.
+	pub fn train_warm_start(
+		init_model: &MulticlassClassifier,
+		features: TableView,
+		labels: EnumTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> MulticlassClassifierTrainOutput {
+		let n_classes = labels.variants().len();
+		assert_eq!(init_model.biases.len(), n_classes);
+		let task = crate::train::Task::MulticlassClassification { n_classes };
+		let train_output = crate::train::train(
+			task,
+			features,
+			TableColumnView::Enum(labels),
+			Some(InitModel::MulticlassClassifier(init_model)),
+			train_options,
+			progress,
+		);
+		match train_output {
+			TrainOutput::MulticlassClassifier(train_output) => train_output,
+			_ => unreachable!(),
+		}
+	}
+
+	// Train a multiclass classifier on features that are too large to hold in memory. See `train_out_of_core` in `train.rs` for how `features_sample` and `feature_chunks` are used.
+	pub fn train_out_of_core<E>(
+		features_sample: TableView,
+		feature_chunks: impl Iterator<Item = Result<Table, E>>,
+		labels: EnumTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> Result<MulticlassClassifierTrainOutput, E>
+	where
+		E: From<std::io::Error>,
+	{
+		let task = crate::train::Task::MulticlassClassification {
+			n_classes: labels.variants().len(),
+		};
+		let train_output = crate::train::train_out_of_core(
+			task,
+			features_sample,
+			feature_chunks,
+			TableColumnView::Enum(labels),
+			train_options,
+			progress,
+		)?;
+		match train_output {
+			TrainOutput::MulticlassClassifier(train_output) => Ok(train_output),
+			_ => unreachable!(),
+		}
+	}
+
 	// Make predictions.
 	pub fn predict(&self, features: ArrayView2<TableValue>, mut probabilities: ArrayViewMut2<f32>) {
 		zip!(
@@ -89,6 +146,27 @@ impl MulticlassClassifier {
 			.collect()
 	}
 
+	/// Compute SHAP interaction values.
+	pub fn compute_feature_interactions(
+		&self,
+		features: ArrayView2<TableValue>,
+	) -> Vec<Vec<ComputeShapInteractionValuesForExampleOutput>> {
+		features
+			.axis_iter(Axis(0))
+			.map(|features| {
+				zip!(self.trees.axis_iter(Axis(1)), self.biases.iter())
+					.map(|(tree, bias)| {
+						compute_shap_interaction_values_for_example(
+							features.as_slice().unwrap(),
+							tree,
+							*bias,
+						)
+					})
+					.collect()
+			})
+			.collect()
+	}
+
 	pub fn from_reader(
 		multiclass_classifier: crate::serialize::MulticlassClassifierReader,
 	) -> MulticlassClassifier {
@@ -99,7 +177,15 @@ impl MulticlassClassifier {
 		&self,
 		writer: &mut buffalo::Writer,
 	) -> buffalo::Position<crate::serialize::MulticlassClassifierWriter> {
-		crate::serialize::serialize_multiclass_classifier(self, writer)
+		self.to_writer_with_leaf_value_encoding(writer, crate::LeafValueEncoding::F64)
+	}
+
+	pub fn to_writer_with_leaf_value_encoding(
+		&self,
+		writer: &mut buffalo::Writer,
+		leaf_value_encoding: crate::LeafValueEncoding,
+	) -> buffalo::Position<crate::serialize::MulticlassClassifierWriter> {
+		crate::serialize::serialize_multiclass_classifier(self, writer, leaf_value_encoding)
 	}
 
 	#[must_use]
@@ -119,6 +205,7 @@ impl MulticlassClassifier {
 /// This function is used by the common train function to update the logits after each round of trees is trained for multiclass classification.
 pub fn update_logits(
 	trees_for_round: &[TrainTree],
+	tree_weight: f32,
 	binned_features: ArrayView2<TableValue>,
 	mut predictions: ArrayViewMut2<f32>,
 ) {
@@ -126,7 +213,7 @@ pub fn update_logits(
 	let logits_rows = predictions.axis_iter_mut(Axis(0));
 	for (features, mut logits) in zip!(features_rows, logits_rows) {
 		for (logit, tree) in zip!(logits.iter_mut(), trees_for_round.iter()) {
-			*logit += tree.predict(features.as_slice().unwrap());
+			*logit += tree_weight * tree.predict(features.as_slice().unwrap());
 		}
 	}
 }
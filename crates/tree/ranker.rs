@@ -0,0 +1,284 @@
+use crate::{
+	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
+	train::{train, Task, TrainOutput},
+	train_tree::TrainTree,
+	Progress, TrainOptions, Tree,
+};
+use modelfox_metrics::{group_ranges, ideal_dcg, ndcg, ndcg_discount, ndcg_gain, rank};
+use modelfox_table::prelude::*;
+use modelfox_zip::zip;
+use ndarray::prelude::*;
+use num::ToPrimitive;
+use rayon::prelude::*;
+
+/// `Ranker`s predict a score for each example such that sorting the examples in a query group by their scores in descending order ranks the most relevant examples first, for example the results of a search query. They are trained with LambdaMART, which optimizes the normalized discounted cumulative gain of the ranking of each query group.
+#[derive(Clone, Debug)]
+pub struct Ranker {
+	/// The initial score of the model given no trained trees. Only the order of the scores within a query group matters, so this is always zero for trained models.
+	pub bias: f32,
+	/// The trees for this model.
+	pub trees: Vec<Tree>,
+}
+
+/// This struct is returned by `Ranker::train`.
+#[derive(Debug)]
+pub struct RankerTrainOutput {
+	/// This is the model you just trained.
+	pub model: Ranker,
+	/// These are the loss values for each epoch. The loss is one minus the mean NDCG over the query groups.
+	pub losses: Option<Vec<f32>>,
+	/// These are the importances of each feature as measured by the number of times each feature was used in a branch node.
+	pub feature_importances: Option<Vec<f32>>,
+}
+
+impl Ranker {
+	/// Train a ranker. `labels` holds the relevance of each example and `groups` holds the query group of each example. The examples in each query group must be contiguous. If `ndcg_k` is set, the model optimizes the NDCG of the top `ndcg_k` examples of each query group.
+	pub fn train(
+		features: TableView,
+		labels: NumberTableColumnView,
+		groups: &[usize],
+		ndcg_k: Option<usize>,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> RankerTrainOutput {
+		let task = Task::Ranking { groups, ndcg_k };
+		let train_output = train(
+			task,
+			features,
+			TableColumnView::Number(labels),
+			None,
+			train_options,
+			progress,
+		);
+		match train_output {
+			TrainOutput::Ranker(train_output) => train_output,
+			_ => unreachable!(),
+		}
+	}
+
+	/// Make predictions.
+	pub fn predict(&self, features: ArrayView2<TableValue>, mut predictions: ArrayViewMut1<f32>) {
+		predictions.fill(self.bias);
+		let predictions = predictions.as_slice_mut().unwrap();
+		for tree in self.trees.iter() {
+			zip!(features.axis_iter(Axis(0)), predictions.iter_mut()).for_each(
+				|(example, prediction)| {
+					*prediction += tree.predict(example.as_slice().unwrap());
+				},
+			)
+		}
+	}
+
+	/// Compute SHAP values.
+	pub fn compute_feature_contributions(
+		&self,
+		features: ArrayView2<TableValue>,
+	) -> Vec<ComputeShapValuesForExampleOutput> {
+		let trees = ArrayView1::from_shape(self.trees.len(), &self.trees).unwrap();
+		features
+			.axis_iter(Axis(0))
+			.map(|features| {
+				compute_shap_values_for_example(features.as_slice().unwrap(), trees, self.bias)
+			})
+			.collect()
+	}
+
+	pub fn from_reader(ranker: crate::serialize::RankerReader) -> Ranker {
+		crate::serialize::deserialize_ranker(ranker)
+	}
+
+	pub fn to_writer(
+		&self,
+		writer: &mut buffalo::Writer,
+	) -> buffalo::Position<crate::serialize::RankerWriter> {
+		self.to_writer_with_leaf_value_encoding(writer, crate::LeafValueEncoding::F64)
+	}
+
+	pub fn to_writer_with_leaf_value_encoding(
+		&self,
+		writer: &mut buffalo::Writer,
+		leaf_value_encoding: crate::LeafValueEncoding,
+	) -> buffalo::Position<crate::serialize::RankerWriter> {
+		crate::serialize::serialize_ranker(self, writer, leaf_value_encoding)
+	}
+
+	#[must_use]
+	pub fn from_bytes(&self, bytes: &[u8]) -> Ranker {
+		let reader = buffalo::read::<crate::serialize::RankerReader>(bytes);
+		Self::from_reader(reader)
+	}
+
+	pub fn to_bytes(&self) -> Vec<u8> {
+		// Create the writer.
+		let mut writer = buffalo::Writer::new();
+		self.to_writer(&mut writer);
+		writer.into_bytes()
+	}
+}
+
+/// This function is used by the common train function to update the logits after each round of trees is trained for ranking.
+pub fn update_logits(
+	trees_for_round: &[TrainTree],
+	tree_weight: f32,
+	features: ArrayView2<TableValue>,
+	mut predictions: ArrayViewMut2<f32>,
+) {
+	for (prediction, features) in zip!(predictions.row_mut(0), features.axis_iter(Axis(0))) {
+		for tree in trees_for_round {
+			*prediction += tree_weight * tree.predict(features.as_slice().unwrap());
+		}
+	}
+}
+
+/// This function is used by the common train function to compute the loss after each tree is trained for ranking. The loss is one minus the mean NDCG over the query groups, so that lower is better.
+pub fn compute_loss(
+	predictions: ArrayView2<f32>,
+	labels: &[f32],
+	groups: &[usize],
+	ndcg_k: Option<usize>,
+) -> f32 {
+	let predictions = predictions.column(0);
+	let predictions = predictions.as_slice().unwrap();
+	let ranges = group_ranges(groups);
+	let sum_ndcg: f32 = ranges
+		.iter()
+		.map(|range| ndcg(&predictions[range.clone()], &labels[range.clone()], ndcg_k))
+		.sum();
+	1.0 - sum_ndcg / ranges.len().to_f32().unwrap()
+}
+
+/// This function is used by the common train function to compute the biases for ranking. Only the order of the scores within a query group matters, so the bias is zero.
+pub fn compute_biases() -> Array1<f32> {
+	arr1(&[0.0])
+}
+
+/// This function is used by the common train function to compute the gradients and hessians after each round. These are the LambdaMART gradients: for each pair of examples in a query group with different relevance, the more relevant example is pushed up and the less relevant example is pushed down, weighted by how much swapping the two examples would change the NDCG.
+pub fn compute_gradients_and_hessians(
+	// (n_examples)
+	gradients: &mut [f32],
+	// (n_examples)
+	hessians: &mut [f32],
+	// (n_examples)
+	labels: &[f32],
+	// (n_examples)
+	predictions: &[f32],
+	// (n_examples)
+	groups: &[usize],
+	ndcg_k: Option<usize>,
+) {
+	// Split the gradients and hessians into a slice for each query group so the groups can be processed in parallel.
+	let mut group_slices = Vec::new();
+	let mut gradients_rest = gradients;
+	let mut hessians_rest = hessians;
+	for range in group_ranges(groups) {
+		let (gradients, rest) = gradients_rest.split_at_mut(range.len());
+		gradients_rest = rest;
+		let (hessians, rest) = hessians_rest.split_at_mut(range.len());
+		hessians_rest = rest;
+		group_slices.push((gradients, hessians, range));
+	}
+	group_slices
+		.into_par_iter()
+		.for_each(|(gradients, hessians, range)| {
+			compute_gradients_and_hessians_for_group(
+				gradients,
+				hessians,
+				&labels[range.clone()],
+				&predictions[range],
+				ndcg_k,
+			);
+		});
+}
+
+fn compute_gradients_and_hessians_for_group(
+	gradients: &mut [f32],
+	hessians: &mut [f32],
+	labels: &[f32],
+	predictions: &[f32],
+	ndcg_k: Option<usize>,
+) {
+	gradients.fill(0.0);
+	hessians.fill(0.0);
+	// If no example in the group is relevant, every ranking has the same NDCG, so there is nothing to learn from this group.
+	let ideal_dcg = ideal_dcg(labels, ndcg_k);
+	if ideal_dcg == 0.0 {
+		return;
+	}
+	let n_examples = labels.len();
+	let k = ndcg_k.unwrap_or(n_examples);
+	// Sort the group by the current predictions.
+	let ranking = rank(predictions);
+	// Examples ranked below the top k do not contribute to the NDCG.
+	let discount = |rank: usize| if rank < k { ndcg_discount(rank) } else { 0.0 };
+	// Visit each pair of examples once. Swapping two examples that are both below the top k does not change the NDCG, so only the pairs with at least one example in the top k are visited. This takes O(k * n) time for a group of n examples, which is O(n^2) if `ndcg_k` is not set.
+	for (rank_a, &a) in ranking.iter().enumerate().take(k) {
+		for (rank_b, &b) in ranking.iter().enumerate().skip(rank_a + 1) {
+			let (high, rank_high, low, rank_low) = if labels[a] > labels[b] {
+				(a, rank_a, b, rank_b)
+			} else if labels[b] > labels[a] {
+				(b, rank_b, a, rank_a)
+			} else {
+				continue;
+			};
+			let delta_ndcg = (ndcg_gain(labels[high]) - ndcg_gain(labels[low])).abs()
+				* (discount(rank_high) - discount(rank_low)).abs()
+				/ ideal_dcg;
+			// This is the probability under the model that the pair is ranked incorrectly.
+			let rho = 1.0 / (1.0 + (predictions[high] - predictions[low]).exp());
+			let lambda = rho * delta_ndcg;
+			gradients[high] -= lambda;
+			gradients[low] += lambda;
+			let hessian = rho * (1.0 - rho) * delta_ndcg;
+			hessians[high] += hessian;
+			hessians[low] += hessian;
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn gradients_and_hessians(
+		labels: &[f32],
+		predictions: &[f32],
+		groups: &[usize],
+		ndcg_k: Option<usize>,
+	) -> (Vec<f32>, Vec<f32>) {
+		let mut gradients = vec![f32::NAN; labels.len()];
+		let mut hessians = vec![f32::NAN; labels.len()];
+		compute_gradients_and_hessians(
+			&mut gradients,
+			&mut hessians,
+			labels,
+			predictions,
+			groups,
+			ndcg_k,
+		);
+		(gradients, hessians)
+	}
+
+	#[test]
+	fn test_gradients_push_relevant_examples_up() {
+		let (gradients, hessians) =
+			gradients_and_hessians(&[0.0, 2.0, 1.0], &[0.5, 0.1, 0.3], &[0, 0, 0], None);
+		assert!(gradients[1] < 0.0);
+		assert!(gradients[0] > 0.0);
+		assert!(gradients.iter().sum::<f32>().abs() < 1e-6);
+		assert!(hessians.iter().all(|hessian| *hessian > 0.0));
+	}
+
+	#[test]
+	fn test_gradients_without_pairs_are_zero() {
+		// A group with a single example and a group whose labels are all equal have no pairs to rank, and a group with no relevant examples has an ideal DCG of zero.
+		let labels = [3.0, 1.0, 1.0, 1.0, 0.0, 0.0];
+		let predictions = [0.2, 0.5, -0.5, 0.5, 1.0, 2.0];
+		let groups = [0, 1, 1, 1, 2, 2];
+		for ndcg_k in [None, Some(1)] {
+			let (gradients, hessians) =
+				gradients_and_hessians(&labels, &predictions, &groups, ndcg_k);
+			assert_eq!(gradients, vec![0.0; 6]);
+			assert_eq!(hessians, vec![0.0; 6]);
+		}
+	}
+}
@@ -1,6 +1,9 @@
 use crate::{
-	shap::{compute_shap_values_for_example, ComputeShapValuesForExampleOutput},
-	train::{train, Task, TrainOutput},
+	shap::{
+		compute_shap_interaction_values_for_example, compute_shap_values_for_example,
+		ComputeShapInteractionValuesForExampleOutput, ComputeShapValuesForExampleOutput,
+	},
+	train::{train, train_out_of_core, InitModel, Task, TrainOutput},
 	train_tree::TrainTree,
 	Progress, TrainOptions, Tree,
 };
@@ -43,6 +46,7 @@ impl Regressor {
 			task,
 			features,
 			TableColumnView::Number(labels),
+			None,
 			train_options,
 			progress,
 		);
@@ -52,6 +56,55 @@ impl Regressor {
 		}
 	}
 
+	/// Continue training `init_model` on new data. The returned model keeps the bias and trees of `init_model` and appends up to `max_rounds` new trees trained to improve on its predictions. `features` must have the same columns as the features `init_model` was trained on.
+	pub fn train_warm_start(
+		init_model: &Regressor,
+		features: TableView,
+		labels: NumberTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> RegressorTrainOutput {
+		let task = Task::Regression;
+		let train_output = train(
+			task,
+			features,
+			TableColumnView::Number(labels),
+			Some(InitModel::Regressor(init_model)),
+			train_options,
+			progress,
+		);
+		match train_output {
+			TrainOutput::Regressor(train_output) => train_output,
+			_ => unreachable!(),
+		}
+	}
+
+	/// Train a regressor on features that are too large to hold in memory. See `train_out_of_core` in `train.rs` for how `features_sample` and `feature_chunks` are used.
+	pub fn train_out_of_core<E>(
+		features_sample: TableView,
+		feature_chunks: impl Iterator<Item = Result<Table, E>>,
+		labels: NumberTableColumnView,
+		train_options: &TrainOptions,
+		progress: Progress,
+	) -> Result<RegressorTrainOutput, E>
+	where
+		E: From<std::io::Error>,
+	{
+		let task = Task::Regression;
+		let train_output = train_out_of_core(
+			task,
+			features_sample,
+			feature_chunks,
+			TableColumnView::Number(labels),
+			train_options,
+			progress,
+		)?;
+		match train_output {
+			TrainOutput::Regressor(train_output) => Ok(train_output),
+			_ => unreachable!(),
+		}
+	}
+
 	/// Make predictions.
 	pub fn predict(&self, features: ArrayView2<TableValue>, mut predictions: ArrayViewMut1<f32>) {
 		predictions.fill(self.bias);
@@ -79,6 +132,24 @@ impl Regressor {
 			.collect()
 	}
 
+	/// Compute SHAP interaction values.
+	pub fn compute_feature_interactions(
+		&self,
+		features: ArrayView2<TableValue>,
+	) -> Vec<ComputeShapInteractionValuesForExampleOutput> {
+		let trees = ArrayView1::from_shape(self.trees.len(), &self.trees).unwrap();
+		features
+			.axis_iter(Axis(0))
+			.map(|features| {
+				compute_shap_interaction_values_for_example(
+					features.as_slice().unwrap(),
+					trees,
+					self.bias,
+				)
+			})
+			.collect()
+	}
+
 	pub fn from_reader(regressor: crate::serialize::RegressorReader) -> Regressor {
 		crate::serialize::deserialize_regressor(regressor)
 	}
@@ -87,7 +158,15 @@ impl Regressor {
 		&self,
 		writer: &mut buffalo::Writer,
 	) -> buffalo::Position<crate::serialize::RegressorWriter> {
-		crate::serialize::serialize_regressor(self, writer)
+		self.to_writer_with_leaf_value_encoding(writer, crate::LeafValueEncoding::F64)
+	}
+
+	pub fn to_writer_with_leaf_value_encoding(
+		&self,
+		writer: &mut buffalo::Writer,
+		leaf_value_encoding: crate::LeafValueEncoding,
+	) -> buffalo::Position<crate::serialize::RegressorWriter> {
+		crate::serialize::serialize_regressor(self, writer, leaf_value_encoding)
 	}
 
 	#[must_use]
@@ -107,12 +186,13 @@ impl Regressor {
 /// This function is used by the common train function to update the logits after each round of trees is trained for regression.
 pub fn update_logits(
 	trees_for_round: &[TrainTree],
+	tree_weight: f32,
 	features: ArrayView2<TableValue>,
 	mut predictions: ArrayViewMut2<f32>,
 ) {
 	for (prediction, features) in zip!(predictions.row_mut(0), features.axis_iter(Axis(0))) {
 		for tree in trees_for_round {
-			*prediction += tree.predict(features.as_slice().unwrap());
+			*prediction += tree_weight * tree.predict(features.as_slice().unwrap());
 		}
 	}
 }
@@ -0,0 +1,229 @@
+use crate::{DartOptions, GossOptions};
+use modelfox_zip::zip;
+use num::ToPrimitive;
+use rand::{seq::index, seq::SliceRandom, Rng};
+use rand_xoshiro::Xoshiro256Plus;
+
+/// Randomly choose the examples used to train the next trees. Each example is kept with probability `bagging_fraction`.
+pub fn sample_examples_bagging(
+	in_bag: &mut [bool],
+	bagging_fraction: f32,
+	rng: &mut Xoshiro256Plus,
+) {
+	for in_bag in in_bag.iter_mut() {
+		*in_bag = rng.gen::<f32>() < bagging_fraction;
+	}
+}
+
+/// Choose the examples used to train the next tree with gradient-based one-side sampling. The examples with the largest gradients are always kept. The rest are sampled, and the gradients and hessians of the sampled examples are amplified so the sums of gradients and hessians remain unbiased. If the hessians are constant, they are filled with the amplification factors so that the caller can treat them as weights.
+pub fn sample_examples_goss(
+	in_bag: &mut [bool],
+	gradients: &mut [f32],
+	hessians: &mut [f32],
+	hessians_are_constant: bool,
+	goss_options: &GossOptions,
+	rng: &mut Xoshiro256Plus,
+) {
+	let GossOptions {
+		top_rate,
+		other_rate,
+	} = *goss_options;
+	let n_examples = gradients.len();
+	let n_top = (top_rate * n_examples.to_f32().unwrap())
+		.ceil()
+		.to_usize()
+		.unwrap()
+		.min(n_examples);
+	// Find the magnitude of the gradient at the boundary of the top examples.
+	let threshold = if n_top == 0 {
+		f32::INFINITY
+	} else {
+		let mut magnitudes: Vec<f32> = gradients.iter().map(|gradient| gradient.abs()).collect();
+		let (_, threshold, _) =
+			magnitudes.select_nth_unstable_by(n_top - 1, |a, b| b.partial_cmp(a).unwrap());
+		*threshold
+	};
+	let other_probability = other_rate / (1.0 - top_rate);
+	let amplification = (1.0 - top_rate) / other_rate;
+	for (in_bag, gradient, hessian) in zip!(in_bag.iter_mut(), gradients, hessians) {
+		let weight = if gradient.abs() >= threshold {
+			*in_bag = true;
+			1.0
+		} else if rng.gen::<f32>() < other_probability {
+			*in_bag = true;
+			amplification
+		} else {
+			*in_bag = false;
+			continue;
+		};
+		*gradient *= weight;
+		if hessians_are_constant {
+			*hessian = weight;
+		} else {
+			*hessian *= weight;
+		}
+	}
+}
+
+/// Randomly choose `feature_fraction` of the features, always choosing at least one. If `features` is `Some`, only the features it marks are candidates.
+pub fn sample_features(
+	n_features: usize,
+	features: Option<&[bool]>,
+	feature_fraction: f32,
+	rng: &mut Xoshiro256Plus,
+) -> Vec<bool> {
+	let candidates: Vec<usize> = match features {
+		Some(features) => features
+			.iter()
+			.enumerate()
+			.filter(|(_, is_candidate)| **is_candidate)
+			.map(|(feature_index, _)| feature_index)
+			.collect(),
+		None => (0..n_features).collect(),
+	};
+	let n_sampled = (feature_fraction * candidates.len().to_f32().unwrap())
+		.round()
+		.to_usize()
+		.unwrap()
+		.clamp(1.min(candidates.len()), candidates.len());
+	let mut sampled_features = vec![false; n_features];
+	for index in index::sample(rng, candidates.len(), n_sampled).iter() {
+		sampled_features[candidates[index]] = true;
+	}
+	sampled_features
+}
+
+/// Randomly choose the rounds whose trees are dropped while training the next round with DART. With probability `skip_drop` no rounds are dropped. Otherwise each round is dropped with probability `drop_rate`, keeping at most `max_drop` rounds if it is nonzero.
+pub fn sample_dropped_rounds(
+	n_rounds: usize,
+	dart_options: &DartOptions,
+	rng: &mut Xoshiro256Plus,
+) -> Vec<usize> {
+	if n_rounds == 0 || rng.gen::<f32>() < dart_options.skip_drop {
+		return Vec::new();
+	}
+	let mut dropped_rounds: Vec<usize> = (0..n_rounds)
+		.filter(|_| rng.gen::<f32>() < dart_options.drop_rate)
+		.collect();
+	if dart_options.max_drop > 0 && dropped_rounds.len() > dart_options.max_drop {
+		dropped_rounds.shuffle(rng);
+		dropped_rounds.truncate(dart_options.max_drop);
+		dropped_rounds.sort_unstable();
+	}
+	dropped_rounds
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use rand::SeedableRng;
+
+	#[test]
+	fn test_sample_examples_bagging() {
+		let mut rng = Xoshiro256Plus::seed_from_u64(0);
+		let mut in_bag = vec![false; 10_000];
+		sample_examples_bagging(&mut in_bag, 0.3, &mut rng);
+		let n_in_bag = in_bag.iter().filter(|in_bag| **in_bag).count();
+		assert!((2_800..3_200).contains(&n_in_bag), "{}", n_in_bag);
+		// The same seed chooses the same examples.
+		let mut other_in_bag = vec![false; 10_000];
+		sample_examples_bagging(
+			&mut other_in_bag,
+			0.3,
+			&mut Xoshiro256Plus::seed_from_u64(0),
+		);
+		assert_eq!(other_in_bag, in_bag);
+	}
+
+	#[test]
+	fn test_sample_examples_goss() {
+		let mut rng = Xoshiro256Plus::seed_from_u64(0);
+		let n_examples = 1000;
+		let mut in_bag = vec![false; n_examples];
+		let mut gradients: Vec<f32> = (0..n_examples).map(|i| i.to_f32().unwrap()).collect();
+		let mut hessians = vec![1.0; n_examples];
+		let goss_options = GossOptions {
+			top_rate: 0.2,
+			other_rate: 0.1,
+		};
+		sample_examples_goss(
+			&mut in_bag,
+			&mut gradients,
+			&mut hessians,
+			true,
+			&goss_options,
+			&mut rng,
+		);
+		// The 200 examples with the largest gradients are kept unchanged.
+		for i in 800..n_examples {
+			assert!(in_bag[i]);
+			assert_eq!(gradients[i], i.to_f32().unwrap());
+			assert_eq!(hessians[i], 1.0);
+		}
+		// The rest are sampled with probability 0.1 / 0.8 and amplified by 0.8 / 0.1.
+		let amplification = (1.0 - 0.2) / 0.1;
+		let n_other = in_bag[..800].iter().filter(|in_bag| **in_bag).count();
+		assert!((70..130).contains(&n_other), "{}", n_other);
+		for i in (0..800).filter(|i| in_bag[*i]) {
+			assert_eq!(gradients[i], amplification * i.to_f32().unwrap());
+			assert_eq!(hessians[i], amplification);
+		}
+	}
+
+	#[test]
+	fn test_sample_features() {
+		let mut rng = Xoshiro256Plus::seed_from_u64(0);
+		let sampled_features = sample_features(10, None, 0.3, &mut rng);
+		assert_eq!(
+			sampled_features.iter().filter(|sampled| **sampled).count(),
+			3
+		);
+		// Only candidates are sampled.
+		let candidates = [
+			true, false, true, false, true, false, true, false, true, false,
+		];
+		let sampled_features = sample_features(10, Some(&candidates), 0.4, &mut rng);
+		assert_eq!(
+			sampled_features.iter().filter(|sampled| **sampled).count(),
+			2
+		);
+		for (sampled, candidate) in zip!(sampled_features, candidates) {
+			assert!(!sampled || candidate);
+		}
+		// At least one feature is always sampled.
+		let sampled_features = sample_features(10, None, 0.01, &mut rng);
+		assert_eq!(
+			sampled_features.iter().filter(|sampled| **sampled).count(),
+			1
+		);
+	}
+
+	#[test]
+	fn test_sample_dropped_rounds() {
+		let mut rng = Xoshiro256Plus::seed_from_u64(0);
+		// No rounds are dropped before the first round or when dropout is always skipped.
+		let dart_options = DartOptions {
+			drop_rate: 1.0,
+			max_drop: 0,
+			skip_drop: 0.0,
+		};
+		assert!(sample_dropped_rounds(0, &dart_options, &mut rng).is_empty());
+		assert_eq!(
+			sample_dropped_rounds(5, &dart_options, &mut rng),
+			vec![0, 1, 2, 3, 4]
+		);
+		let skip_options = DartOptions {
+			skip_drop: 1.0,
+			..dart_options
+		};
+		assert!(sample_dropped_rounds(5, &skip_options, &mut rng).is_empty());
+		// At most max_drop rounds are dropped, and they are sorted.
+		let max_drop_options = DartOptions {
+			max_drop: 3,
+			..dart_options
+		};
+		let dropped_rounds = sample_dropped_rounds(10, &max_drop_options, &mut rng);
+		assert_eq!(dropped_rounds.len(), 3);
+		assert!(dropped_rounds.windows(2).all(|pair| pair[0] < pair[1]));
+	}
+}
@@ -29,11 +29,25 @@ pub struct MulticlassClassifier {
 	pub trees: Array2<Tree>,
 }
 
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct Ranker {
+	#[buffalo(id = 0, required)]
+	pub bias: f32,
+	#[buffalo(id = 1, required)]
+	pub trees: Vec<Tree>,
+}
+
 #[derive(buffalo::Read, buffalo::Write)]
 #[buffalo(size = "dynamic")]
 pub struct Tree {
 	#[buffalo(id = 0, required)]
 	pub nodes: Vec<Node>,
+	#[buffalo(id = 1)]
+	pub weight: f32,
+	/// The value of each `LeafI8` node is its 8 bit integer value multiplied by this scale. It is 1 for trees without `LeafI8` nodes.
+	#[buffalo(id = 2)]
+	pub leaf_value_scale: f32,
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -43,6 +57,10 @@ pub enum Node {
 	Branch(BranchNode),
 	#[buffalo(id = 1)]
 	Leaf(LeafNode),
+	#[buffalo(id = 2)]
+	LeafF16(LeafNodeF16),
+	#[buffalo(id = 3)]
+	LeafI8(LeafNodeI8),
 }
 
 #[derive(buffalo::Read, buffalo::Write)]
@@ -105,15 +123,36 @@ pub struct LeafNode {
 	pub examples_fraction: f32,
 }
 
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct LeafNodeF16 {
+	/// This is the bits of a 16 bit float.
+	#[buffalo(id = 0, required)]
+	pub value: u16,
+	#[buffalo(id = 1, required)]
+	pub examples_fraction: f32,
+}
+
+#[derive(buffalo::Read, buffalo::Write)]
+#[buffalo(size = "dynamic")]
+pub struct LeafNodeI8 {
+	/// This is multiplied by the tree's `leaf_value_scale` to get the leaf's value.
+	#[buffalo(id = 0, required)]
+	pub value: i8,
+	#[buffalo(id = 1, required)]
+	pub examples_fraction: f32,
+}
+
 pub(crate) fn serialize_regressor(
 	regressor: &crate::Regressor,
 	writer: &mut buffalo::Writer,
+	leaf_value_encoding: crate::LeafValueEncoding,
 ) -> buffalo::Position<RegressorWriter> {
 	let trees = regressor
 		.trees
 		.iter()
 		.map(|tree| {
-			let tree = serialize_tree(tree, writer);
+			let tree = serialize_tree(tree, writer, leaf_value_encoding);
 			writer.write(&tree)
 		})
 		.collect::<Vec<_>>();
@@ -127,12 +166,13 @@ pub(crate) fn serialize_regressor(
 pub(crate) fn serialize_binary_classifier(
 	binary_classifier: &crate::BinaryClassifier,
 	writer: &mut buffalo::Writer,
+	leaf_value_encoding: crate::LeafValueEncoding,
 ) -> buffalo::Position<BinaryClassifierWriter> {
 	let trees = binary_classifier
 		.trees
 		.iter()
 		.map(|tree| {
-			let tree = serialize_tree(tree, writer);
+			let tree = serialize_tree(tree, writer, leaf_value_encoding);
 			writer.write(&tree)
 		})
 		.collect::<Vec<_>>();
@@ -146,27 +186,75 @@ pub(crate) fn serialize_binary_classifier(
 pub(crate) fn serialize_multiclass_classifier(
 	multiclass_classifier: &crate::MulticlassClassifier,
 	writer: &mut buffalo::Writer,
+	leaf_value_encoding: crate::LeafValueEncoding,
 ) -> buffalo::Position<MulticlassClassifierWriter> {
 	let biases = writer.write(&multiclass_classifier.biases);
 	let trees = multiclass_classifier.trees.map(|tree| {
-		let tree = serialize_tree(tree, writer);
+		let tree = serialize_tree(tree, writer, leaf_value_encoding);
 		writer.write(&tree)
 	});
 	let trees = writer.write(&trees);
 	writer.write(&MulticlassClassifierWriter { biases, trees })
 }
 
-fn serialize_tree(tree: &crate::Tree, writer: &mut buffalo::Writer) -> TreeWriter {
+pub(crate) fn serialize_ranker(
+	ranker: &crate::Ranker,
+	writer: &mut buffalo::Writer,
+	leaf_value_encoding: crate::LeafValueEncoding,
+) -> buffalo::Position<RankerWriter> {
+	let trees = ranker
+		.trees
+		.iter()
+		.map(|tree| {
+			let tree = serialize_tree(tree, writer, leaf_value_encoding);
+			writer.write(&tree)
+		})
+		.collect::<Vec<_>>();
+	let trees = writer.write(&trees);
+	writer.write(&RankerWriter {
+		bias: ranker.bias,
+		trees,
+	})
+}
+
+fn serialize_tree(
+	tree: &crate::Tree,
+	writer: &mut buffalo::Writer,
+	leaf_value_encoding: crate::LeafValueEncoding,
+) -> TreeWriter {
+	// Choose a scale that maps the leaf value with the largest magnitude to the largest 8 bit integer.
+	let leaf_value_scale = match leaf_value_encoding {
+		crate::LeafValueEncoding::I8 => {
+			let max_abs_value = tree
+				.nodes
+				.iter()
+				.filter_map(|node| node.as_leaf())
+				.map(|leaf| leaf.value.abs())
+				.fold(0.0, f64::max);
+			let scale = max_abs_value / i8::MAX as f64;
+			Some(if scale > 0.0 { scale } else { 1.0 })
+		}
+		_ => None,
+	};
 	let nodes = tree
 		.nodes
 		.iter()
-		.map(|node| serialize_node(node, writer))
+		.map(|node| serialize_node(node, writer, leaf_value_encoding, leaf_value_scale))
 		.collect::<Vec<_>>();
 	let nodes = writer.write(&nodes);
-	TreeWriter { nodes }
+	TreeWriter {
+		nodes,
+		weight: tree.weight,
+		leaf_value_scale: leaf_value_scale.unwrap_or(1.0).to_f32().unwrap(),
+	}
 }
 
-fn serialize_node(node: &crate::Node, writer: &mut buffalo::Writer) -> NodeWriter {
+fn serialize_node(
+	node: &crate::Node,
+	writer: &mut buffalo::Writer,
+	leaf_value_encoding: crate::LeafValueEncoding,
+	leaf_value_scale: Option<f64>,
+) -> NodeWriter {
 	match node {
 		crate::Node::Branch(node) => {
 			let split = serialize_branch_split(&node.split, writer);
@@ -178,13 +266,34 @@ fn serialize_node(node: &crate::Node, writer: &mut buffalo::Writer) -> NodeWrite
 			});
 			NodeWriter::Branch(node)
 		}
-		crate::Node::Leaf(node) => {
-			let node = writer.write(&LeafNodeWriter {
-				value: node.value,
-				examples_fraction: node.examples_fraction,
-			});
-			NodeWriter::Leaf(node)
-		}
+		crate::Node::Leaf(node) => match leaf_value_encoding {
+			crate::LeafValueEncoding::F64 => {
+				let node = writer.write(&LeafNodeWriter {
+					value: node.value,
+					examples_fraction: node.examples_fraction,
+				});
+				NodeWriter::Leaf(node)
+			}
+			crate::LeafValueEncoding::F16 => {
+				let node = writer.write(&LeafNodeF16Writer {
+					value: half::f16::from_f64(node.value).to_bits(),
+					examples_fraction: node.examples_fraction,
+				});
+				NodeWriter::LeafF16(node)
+			}
+			crate::LeafValueEncoding::I8 => {
+				// Round with the scale as it will be read back, so the rounding error is at most half a step.
+				let scale = leaf_value_scale.unwrap().to_f32().unwrap() as f64;
+				let value = (node.value / scale)
+					.round()
+					.clamp(i8::MIN as f64, i8::MAX as f64);
+				let node = writer.write(&LeafNodeI8Writer {
+					value: value.to_i8().unwrap(),
+					examples_fraction: node.examples_fraction,
+				});
+				NodeWriter::LeafI8(node)
+			}
+		},
 	}
 }
 
@@ -254,16 +363,29 @@ pub(crate) fn deserialize_multiclass_classifier(
 	crate::MulticlassClassifier { biases, trees }
 }
 
+pub(crate) fn deserialize_ranker(model: RankerReader) -> crate::Ranker {
+	let bias = model.bias();
+	let trees = model
+		.trees()
+		.iter()
+		.map(deserialize_tree)
+		.collect::<Vec<_>>();
+	crate::Ranker { bias, trees }
+}
+
 fn deserialize_tree(tree: TreeReader) -> crate::Tree {
+	let leaf_value_scale = tree.leaf_value_scale().unwrap_or(1.0);
 	let nodes = tree
 		.nodes()
 		.iter()
-		.map(deserialize_node)
+		.map(|node| deserialize_node(node, leaf_value_scale))
 		.collect::<Vec<_>>();
-	crate::Tree { nodes }
+	// Trees serialized before tree weights were added have a weight of 1.
+	let weight = tree.weight().unwrap_or(1.0);
+	crate::Tree { nodes, weight }
 }
 
-fn deserialize_node(node: NodeReader) -> crate::Node {
+fn deserialize_node(node: NodeReader, leaf_value_scale: f32) -> crate::Node {
 	match node {
 		NodeReader::Branch(node) => {
 			let node = node.read();
@@ -287,6 +409,24 @@ fn deserialize_node(node: NodeReader) -> crate::Node {
 				examples_fraction,
 			})
 		}
+		NodeReader::LeafF16(node) => {
+			let node = node.read();
+			let value = half::f16::from_bits(node.value()).to_f64();
+			let examples_fraction = node.examples_fraction();
+			crate::Node::Leaf(crate::LeafNode {
+				value,
+				examples_fraction,
+			})
+		}
+		NodeReader::LeafI8(node) => {
+			let node = node.read();
+			let value = node.value() as f64 * leaf_value_scale as f64;
+			let examples_fraction = node.examples_fraction();
+			crate::Node::Leaf(crate::LeafNode {
+				value,
+				examples_fraction,
+			})
+		}
 	}
 }
 
@@ -22,7 +22,12 @@ pub fn compute_shap_values_for_example(
 	}
 	let mut feature_contribution_values = vec![0.0; example.len()];
 	for tree in trees {
-		tree_shap(example, tree, feature_contribution_values.as_mut_slice());
+		tree_shap(
+			example,
+			tree,
+			Condition::None,
+			feature_contribution_values.as_mut_slice(),
+		);
 	}
 	let output_value = baseline_value + feature_contribution_values.iter().sum::<f64>();
 	ComputeShapValuesForExampleOutput {
@@ -35,8 +40,106 @@ pub fn compute_shap_values_for_example(
 	}
 }
 
+pub struct ComputeShapInteractionValuesForExampleOutput {
+	pub baseline_value: f32,
+	pub output_value: f32,
+	/// The SHAP interaction values with shape (n_features, n_features). The matrix is symmetric, the off diagonal entries hold half of the interaction effect between each pair of features, and the diagonal holds each feature's main effect, so each row sums to that feature's SHAP value.
+	pub feature_interaction_values: Array2<f32>,
+}
+
+/// Compute the SHAP interaction values for a single class for a single example.
+pub fn compute_shap_interaction_values_for_example(
+	example: &[modelfox_table::TableValue],
+	trees: ArrayView1<Tree>,
+	bias: f32,
+) -> ComputeShapInteractionValuesForExampleOutput {
+	let n_features = example.len();
+	let mut baseline_value = bias as f64;
+	for tree in trees {
+		baseline_value += compute_expectation(tree, 0);
+	}
+	let mut feature_contribution_values = vec![0.0; n_features];
+	let mut feature_interaction_values = Array2::<f64>::zeros((n_features, n_features));
+	let mut phi_on = vec![0.0; n_features];
+	let mut phi_off = vec![0.0; n_features];
+	for tree in trees {
+		tree_shap(
+			example,
+			tree,
+			Condition::None,
+			feature_contribution_values.as_mut_slice(),
+		);
+		// Only features the tree splits on can have nonzero interactions in this tree.
+		let tree_feature_indexes = tree_feature_indexes(tree);
+		for feature_index in tree_feature_indexes.iter().cloned() {
+			for other_feature_index in tree_feature_indexes.iter().cloned() {
+				phi_on[other_feature_index] = 0.0;
+				phi_off[other_feature_index] = 0.0;
+			}
+			tree_shap(
+				example,
+				tree,
+				Condition::On(feature_index),
+				phi_on.as_mut_slice(),
+			);
+			tree_shap(
+				example,
+				tree,
+				Condition::Off(feature_index),
+				phi_off.as_mut_slice(),
+			);
+			for other_feature_index in tree_feature_indexes.iter().cloned() {
+				if other_feature_index != feature_index {
+					feature_interaction_values[[feature_index, other_feature_index]] +=
+						(phi_on[other_feature_index] - phi_off[other_feature_index]) / 2.0;
+				}
+			}
+		}
+	}
+	// The main effect of each feature is whatever remains of its SHAP value after removing its interactions.
+	for feature_index in 0..n_features {
+		let interactions_sum = feature_interaction_values
+			.row(feature_index)
+			.iter()
+			.enumerate()
+			.filter(|(other_feature_index, _)| *other_feature_index != feature_index)
+			.map(|(_, value)| value)
+			.sum::<f64>();
+		feature_interaction_values[[feature_index, feature_index]] =
+			feature_contribution_values[feature_index] - interactions_sum;
+	}
+	let output_value = baseline_value + feature_contribution_values.iter().sum::<f64>();
+	ComputeShapInteractionValuesForExampleOutput {
+		baseline_value: baseline_value.to_f32().unwrap(),
+		output_value: output_value.to_f32().unwrap(),
+		feature_interaction_values: feature_interaction_values.mapv(|f| f.to_f32().unwrap()),
+	}
+}
+
+/// When computing interaction values, TreeSHAP is run with one feature conditioned to always follow the example's path through the tree, or to never be known and follow both paths weighted by their fractions of the training examples.
+#[derive(Clone, Copy)]
+enum Condition {
+	None,
+	On(usize),
+	Off(usize),
+}
+
+impl Condition {
+	fn feature_index(&self) -> Option<usize> {
+		match self {
+			Condition::None => None,
+			Condition::On(feature_index) | Condition::Off(feature_index) => Some(*feature_index),
+		}
+	}
+}
+
 /// This function, and the helper functions below it, are a direct port from https://github.com/slundberg/shap.
-fn tree_shap(example: &[modelfox_table::TableValue], tree: &Tree, phi: &mut [f64]) {
+fn tree_shap(
+	example: &[modelfox_table::TableValue],
+	tree: &Tree,
+	condition: Condition,
+	phi: &mut [f64],
+) {
 	let max_depth = max_depth(tree, 0, 0) + 2;
 	let mut unique_path = vec![PathItem::new(); max_depth * (max_depth + 1) / 2];
 	tree_shap_recursive(TreeShapRecursiveOptions {
@@ -49,6 +152,8 @@ fn tree_shap(example: &[modelfox_table::TableValue], tree: &Tree, phi: &mut [f64
 		parent_zero_fraction: 1.0,
 		parent_one_fraction: 1.0,
 		parent_feature_index: None,
+		condition,
+		condition_fraction: 1.0,
 	});
 }
 
@@ -72,6 +177,8 @@ impl PathItem {
 }
 
 struct TreeShapRecursiveOptions<'a> {
+	condition: Condition,
+	condition_fraction: f64,
 	example: &'a [modelfox_table::TableValue<'a>],
 	node_index: usize,
 	parent_feature_index: Option<usize>,
@@ -85,6 +192,8 @@ struct TreeShapRecursiveOptions<'a> {
 
 fn tree_shap_recursive(options: TreeShapRecursiveOptions) {
 	let TreeShapRecursiveOptions {
+		condition,
+		condition_fraction,
 		example,
 		node_index,
 		parent_feature_index,
@@ -95,13 +204,19 @@ fn tree_shap_recursive(options: TreeShapRecursiveOptions) {
 		unique_depth,
 		unique_path,
 	} = options;
-	extend_path(ExtendPathOptions {
-		unique_path,
-		unique_depth,
-		zero_fraction: parent_zero_fraction,
-		one_fraction: parent_one_fraction,
-		feature_index: parent_feature_index,
-	});
+	if condition_fraction == 0.0 {
+		return;
+	}
+	// The conditioned feature is never added to the path.
+	if condition.feature_index().is_none() || condition.feature_index() != parent_feature_index {
+		extend_path(ExtendPathOptions {
+			unique_path,
+			unique_depth,
+			zero_fraction: parent_zero_fraction,
+			one_fraction: parent_one_fraction,
+			feature_index: parent_feature_index,
+		});
+	}
 	let mut unique_depth = unique_depth;
 	let node = &tree.nodes[node_index];
 	match node {
@@ -110,7 +225,8 @@ fn tree_shap_recursive(options: TreeShapRecursiveOptions) {
 				let weight = unwound_path_sum(unique_path, unique_depth, path_index);
 				let path_item = &unique_path[path_index];
 				let scale = weight * (path_item.one_fraction - path_item.zero_fraction);
-				phi[path_item.feature_index.unwrap()] += scale * node.value as f64;
+				phi[path_item.feature_index.unwrap()] +=
+					scale * tree.weight as f64 * node.value as f64 * condition_fraction;
 			}
 		}
 		Node::Branch(node) => {
@@ -131,27 +247,50 @@ fn tree_shap_recursive(options: TreeShapRecursiveOptions) {
 				unique_depth -= 1;
 			};
 			let feature_index = node.split.feature_index();
+			// Divide up the condition fraction among the children. If this node splits on the conditioned feature, the children do not extend the path, so they stay at the current depth.
+			let (hot_condition_fraction, cold_condition_fraction, child_unique_depth) =
+				match condition {
+					Condition::On(condition_feature_index)
+						if condition_feature_index == feature_index =>
+					{
+						(condition_fraction, 0.0, unique_depth)
+					}
+					Condition::Off(condition_feature_index)
+						if condition_feature_index == feature_index =>
+					{
+						(
+							condition_fraction * hot_zero_fraction,
+							condition_fraction * cold_zero_fraction,
+							unique_depth,
+						)
+					}
+					_ => (condition_fraction, condition_fraction, unique_depth + 1),
+				};
 			let (parent_path, child_path) = unique_path.split_at_mut(unique_depth + 1);
 			child_path[0..parent_path.len()].clone_from_slice(parent_path);
 			tree_shap_recursive(TreeShapRecursiveOptions {
+				condition,
+				condition_fraction: hot_condition_fraction,
 				phi,
 				example,
 				tree,
 				node_index: hot_child_index,
 				unique_path: child_path,
-				unique_depth: unique_depth + 1,
+				unique_depth: child_unique_depth,
 				parent_zero_fraction: hot_zero_fraction * incoming_zero_fraction,
 				parent_one_fraction: incoming_one_fraction,
 				parent_feature_index: Some(feature_index),
 			});
 			child_path[0..parent_path.len()].clone_from_slice(parent_path);
 			tree_shap_recursive(TreeShapRecursiveOptions {
+				condition,
+				condition_fraction: cold_condition_fraction,
 				phi,
 				example,
 				tree,
 				node_index: cold_child_index,
 				unique_path: child_path,
-				unique_depth: unique_depth + 1,
+				unique_depth: child_unique_depth,
 				parent_zero_fraction: cold_zero_fraction * incoming_zero_fraction,
 				parent_one_fraction: 0.0,
 				parent_feature_index: Some(feature_index),
@@ -281,6 +420,18 @@ fn compute_hot_cold_child(
 	}
 }
 
+fn tree_feature_indexes(tree: &Tree) -> Vec<usize> {
+	let mut feature_indexes = tree
+		.nodes
+		.iter()
+		.filter_map(|node| node.as_branch())
+		.map(|node| node.split.feature_index())
+		.collect::<Vec<_>>();
+	feature_indexes.sort_unstable();
+	feature_indexes.dedup();
+	feature_indexes
+}
+
 fn max_depth(tree: &Tree, node_index: usize, depth: usize) -> usize {
 	let current_node = &tree.nodes[node_index];
 	if let Node::Leaf(_) = current_node {
@@ -297,7 +448,7 @@ fn max_depth(tree: &Tree, node_index: usize, depth: usize) -> usize {
 fn compute_expectation(tree: &Tree, node_index: usize) -> f64 {
 	let current_node = &tree.nodes[node_index];
 	if let Node::Leaf(n) = current_node {
-		return n.value;
+		return tree.weight as f64 * n.value;
 	}
 	let current_node = current_node.as_branch().unwrap();
 	let left_child_index = current_node.left_child_index;
@@ -310,3 +461,171 @@ fn compute_expectation(tree: &Tree, node_index: usize) -> f64 {
 		+ (right_child.examples_fraction() as f64 / current_node.examples_fraction as f64)
 			* right_value
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::LeafNode;
+	use bitvec::prelude::*;
+	use modelfox_table::TableValue;
+	use std::num::NonZeroUsize;
+
+	fn branch(
+		feature_index: usize,
+		split_value: f32,
+		left_child_index: usize,
+		right_child_index: usize,
+		examples_fraction: f32,
+	) -> Node {
+		Node::Branch(BranchNode {
+			left_child_index,
+			right_child_index,
+			split: BranchSplit::Continuous(BranchSplitContinuous {
+				feature_index,
+				split_value,
+				invalid_values_direction: SplitDirection::Left,
+			}),
+			examples_fraction,
+		})
+	}
+
+	fn leaf(value: f64, examples_fraction: f32) -> Node {
+		Node::Leaf(LeafNode {
+			value,
+			examples_fraction,
+		})
+	}
+
+	/// This tree splits on feature 0 and then on feature 1, so its SHAP values can be computed by hand.
+	fn small_tree() -> Tree {
+		Tree {
+			nodes: vec![
+				branch(0, 0.5, 1, 2, 1.0),
+				leaf(0.0, 0.5),
+				branch(1, 0.5, 3, 4, 0.5),
+				leaf(1.0, 0.25),
+				leaf(3.0, 0.25),
+			],
+			weight: 1.0,
+		}
+	}
+
+	/// This tree splits on feature 0 twice, uses a discrete split on feature 2, and has a DART weight.
+	fn larger_tree() -> Tree {
+		Tree {
+			nodes: vec![
+				branch(0, 0.0, 1, 2, 1.0),
+				Node::Branch(BranchNode {
+					left_child_index: 3,
+					right_child_index: 4,
+					split: BranchSplit::Discrete(BranchSplitDiscrete {
+						feature_index: 2,
+						directions: bitvec![u8, Lsb0; 0, 1, 0],
+					}),
+					examples_fraction: 0.6,
+				}),
+				branch(1, 2.0, 5, 6, 0.4),
+				branch(0, -1.0, 7, 8, 0.35),
+				leaf(2.0, 0.25),
+				leaf(-1.0, 0.3),
+				leaf(4.0, 0.1),
+				leaf(0.5, 0.15),
+				leaf(-2.0, 0.2),
+			],
+			weight: 0.8,
+		}
+	}
+
+	fn examples() -> Vec<Vec<TableValue<'static>>> {
+		vec![
+			vec![
+				TableValue::Number(-0.5),
+				TableValue::Number(3.0),
+				TableValue::Enum(NonZeroUsize::new(2)),
+			],
+			vec![
+				TableValue::Number(1.0),
+				TableValue::Number(1.0),
+				TableValue::Enum(NonZeroUsize::new(1)),
+			],
+			vec![
+				TableValue::Number(-2.0),
+				TableValue::Number(f32::NAN),
+				TableValue::Enum(None),
+			],
+			vec![
+				TableValue::Number(3.0),
+				TableValue::Number(5.0),
+				TableValue::Enum(NonZeroUsize::new(1)),
+			],
+		]
+	}
+
+	fn assert_close(a: f32, b: f32) {
+		assert!((a - b).abs() < 1e-5, "{} != {}", a, b);
+	}
+
+	#[test]
+	fn test_shap_values_small_tree() {
+		// With x = (1, 1) the prediction is 3 and the expectation is 1. Feature 0 gets 1/2 (2 - 1) + 1/2 (3 - 1.5) = 1.25 and feature 1 gets 1/2 (1.5 - 1) + 1/2 (3 - 2) = 0.75.
+		let trees = arr1(&[small_tree()]);
+		let example = vec![TableValue::Number(1.0), TableValue::Number(1.0)];
+		let output = compute_shap_values_for_example(&example, trees.view(), 0.0);
+		assert_close(output.baseline_value, 1.0);
+		assert_close(output.output_value, 3.0);
+		assert_close(output.feature_contribution_values[0], 1.25);
+		assert_close(output.feature_contribution_values[1], 0.75);
+	}
+
+	#[test]
+	fn test_shap_interaction_values_small_tree() {
+		// The interaction effect is 1/2 (3 - 2 - 1.5 + 1) = 0.25 and the main effects are what remains of the SHAP values.
+		let trees = arr1(&[small_tree()]);
+		let example = vec![TableValue::Number(1.0), TableValue::Number(1.0)];
+		let output = compute_shap_interaction_values_for_example(&example, trees.view(), 0.0);
+		assert_close(output.baseline_value, 1.0);
+		assert_close(output.output_value, 3.0);
+		let values = output.feature_interaction_values;
+		assert_close(values[[0, 0]], 1.0);
+		assert_close(values[[0, 1]], 0.25);
+		assert_close(values[[1, 0]], 0.25);
+		assert_close(values[[1, 1]], 0.5);
+	}
+
+	#[test]
+	fn test_shap_interaction_values_invariants() {
+		let trees = arr1(&[small_tree(), larger_tree()]);
+		let bias = 0.3;
+		for example in examples() {
+			let shap_values = compute_shap_values_for_example(&example, trees.view(), bias);
+			let interaction_values =
+				compute_shap_interaction_values_for_example(&example, trees.view(), bias);
+			let values = &interaction_values.feature_interaction_values;
+			// Each row sums to that feature's SHAP value.
+			for (row, shap_value) in values
+				.rows()
+				.into_iter()
+				.zip(shap_values.feature_contribution_values.iter())
+			{
+				assert_close(row.sum(), *shap_value);
+			}
+			// The matrix is symmetric.
+			for i in 0..example.len() {
+				for j in 0..example.len() {
+					assert_close(values[[i, j]], values[[j, i]]);
+				}
+			}
+			// Everything sums to the output minus the baseline, and the output is the model's prediction.
+			let prediction = bias + trees.iter().map(|tree| tree.predict(&example)).sum::<f32>();
+			assert_close(interaction_values.output_value, prediction);
+			assert_close(
+				values.sum(),
+				interaction_values.output_value - interaction_values.baseline_value,
+			);
+			assert_close(
+				interaction_values.baseline_value,
+				shap_values.baseline_value,
+			);
+		}
+	}
+}
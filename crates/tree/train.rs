@@ -4,13 +4,19 @@ use crate::{
 	binary_classifier::{BinaryClassifier, BinaryClassifierTrainOutput},
 	compute_bin_stats::{BinStats, BinStatsEntry},
 	compute_binned_features::{
-		compute_binned_features_column_major, compute_binned_features_row_major,
+		compute_binned_features_column_major, compute_binned_features_column_major_out_of_core,
+		compute_binned_features_row_major, BinnedFeaturesColumnMajor, BinnedFeaturesRowMajor,
+		ComputeBinnedFeaturesColumnMajorOutput,
 	},
-	compute_binning_instructions::compute_binning_instructions,
+	compute_binning_instructions::{compute_binning_instructions, BinningInstruction},
 	compute_feature_importances::compute_feature_importances,
 	multiclass_classifier::{MulticlassClassifier, MulticlassClassifierTrainOutput},
 	pool::Pool,
+	ranker::{Ranker, RankerTrainOutput},
 	regressor::{Regressor, RegressorTrainOutput},
+	sample::{
+		sample_dropped_rounds, sample_examples_bagging, sample_examples_goss, sample_features,
+	},
 	train_tree::{
 		train_tree, TrainBranchNode, TrainBranchSplit, TrainBranchSplitContinuous,
 		TrainBranchSplitDiscrete, TrainLeafNode, TrainNode, TrainTree, TrainTreeOptions,
@@ -20,16 +26,26 @@ use crate::{
 };
 use modelfox_progress_counter::ProgressCounter;
 use modelfox_table::prelude::*;
+use modelfox_zip::{pzip, zip};
 use ndarray::prelude::*;
 use num::ToPrimitive;
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256Plus;
 use rayon::prelude::*;
 
 /// This enum is used by the common `train` function below to customize the training code slightly for each task.
 #[derive(Clone, Copy, Debug)]
-pub enum Task {
+pub enum Task<'a> {
 	Regression,
 	BinaryClassification,
-	MulticlassClassification { n_classes: usize },
+	MulticlassClassification {
+		n_classes: usize,
+	},
+	/// Ranking holds the query group of each example, where the examples in each group are contiguous, and the number of top examples in each group whose NDCG is optimized.
+	Ranking {
+		groups: &'a [usize],
+		ndcg_k: Option<usize>,
+	},
 }
 
 /// This is the return type of the common `train` function.
@@ -38,13 +54,78 @@ pub enum TrainOutput {
 	Regressor(RegressorTrainOutput),
 	BinaryClassifier(BinaryClassifierTrainOutput),
 	MulticlassClassifier(MulticlassClassifierTrainOutput),
+	Ranker(RankerTrainOutput),
+}
+
+/// This is a previously trained model to continue training from, passed to the common `train` function by `Regressor::train_warm_start`, `BinaryClassifier::train_warm_start`, and `MulticlassClassifier::train_warm_start`.
+#[derive(Clone, Copy, Debug)]
+pub enum InitModel<'a> {
+	Regressor(&'a Regressor),
+	BinaryClassifier(&'a BinaryClassifier),
+	MulticlassClassifier(&'a MulticlassClassifier),
+}
+
+impl<'a> InitModel<'a> {
+	fn biases(&self) -> Array1<f32> {
+		match self {
+			InitModel::Regressor(model) => arr1(&[model.bias]),
+			InitModel::BinaryClassifier(model) => arr1(&[model.bias]),
+			InitModel::MulticlassClassifier(model) => model.biases.clone(),
+		}
+	}
+
+	/// Get the trees in round-major order.
+	fn trees(&self) -> Vec<Tree> {
+		match self {
+			InitModel::Regressor(model) => model.trees.clone(),
+			InitModel::BinaryClassifier(model) => model.trees.clone(),
+			InitModel::MulticlassClassifier(model) => model.trees.iter().cloned().collect(),
+		}
+	}
+
+	/// Compute the model's predictions before any activation function is applied, with one column for each tree per round.
+	fn compute_logits(&self, features: ArrayView2<TableValue>) -> Array2<f32> {
+		let biases = self.biases();
+		let mut logits = Array2::zeros((features.nrows(), biases.len()));
+		zip!(logits.axis_iter_mut(Axis(0)), features.axis_iter(Axis(0))).for_each(
+			|(mut logits, example)| {
+				logits.assign(&biases);
+				let example = example.as_slice().unwrap();
+				match self {
+					InitModel::Regressor(Regressor { trees, .. })
+					| InitModel::BinaryClassifier(BinaryClassifier { trees, .. }) => {
+						for tree in trees {
+							logits[0] += tree.predict(example);
+						}
+					}
+					InitModel::MulticlassClassifier(model) => {
+						for trees in model.trees.axis_iter(Axis(0)) {
+							for (logit, tree) in zip!(logits.iter_mut(), trees.iter()) {
+								*logit += tree.predict(example);
+							}
+						}
+					}
+				}
+			},
+		);
+		logits
+	}
+}
+
+/// This is the state that training continues from when there is an init model.
+struct Init {
+	biases: Array1<f32>,
+	trees: Vec<Tree>,
+	predictions_train: Array2<f32>,
+	predictions_early_stopping: Option<Array2<f32>>,
 }
 
-/// To avoid code duplication, this shared `train` function is called by `Regressor::train`, `BinaryClassifier::train`, and `MulticlassClassifier::train`.
+/// To avoid code duplication, this shared `train` function is called by `Regressor::train`, `BinaryClassifier::train`, `MulticlassClassifier::train`, and `Ranker::train`, as well as their `train_warm_start` counterparts.
 pub fn train(
 	task: Task,
 	features: TableView,
 	labels: TableColumnView,
+	init_model: Option<InitModel>,
 	train_options: &TrainOptions,
 	progress: Progress,
 ) -> TrainOutput {
@@ -52,37 +133,56 @@ pub fn train(
 	let timing = Timing::new();
 
 	// If early stopping is enabled, split the features and labels into train and early stopping sets.
-	let early_stopping_enabled = train_options.early_stopping_options.is_some();
 	let (
+		task_train,
 		features_train,
 		labels_train,
+		task_early_stopping,
 		features_early_stopping,
 		labels_early_stopping,
-		mut early_stopping_monitor,
+		early_stopping_monitor,
 	) = if let Some(early_stopping_options) = &train_options.early_stopping_options {
-		let (features_train, labels_train, features_early_stopping, labels_early_stopping) =
-			train_early_stopping_split(
-				features,
-				labels,
-				early_stopping_options.early_stopping_fraction,
-			);
+		let (
+			task_train,
+			features_train,
+			labels_train,
+			task_early_stopping,
+			features_early_stopping,
+			labels_early_stopping,
+		) = train_early_stopping_split(
+			task,
+			features,
+			labels,
+			early_stopping_options.early_stopping_fraction,
+		);
 		let early_stopping_monitor = EarlyStoppingMonitor::new(
 			early_stopping_options.min_decrease_in_loss_for_significant_change,
 			early_stopping_options.n_rounds_without_improvement_to_stop,
 		);
 		(
+			task_train,
 			features_train,
 			labels_train,
+			Some(task_early_stopping),
 			Some(features_early_stopping),
 			Some(labels_early_stopping),
 			Some(early_stopping_monitor),
 		)
 	} else {
-		(features, labels, None, None, None)
+		(task, features, labels, None, None, None, None)
 	};
 
 	let n_features = features_train.ncols();
-	let n_examples_train = features_train.nrows();
+
+	// If there is an init model, compute its predictions. The new trees will be trained to improve on them. This has to happen before the unused features are dropped below, because the init model's trees use the original feature indexes.
+	let init = init_model.map(|init_model| Init {
+		biases: init_model.biases(),
+		trees: init_model.trees(),
+		predictions_train: init_model.compute_logits(features_train.to_rows().view()),
+		predictions_early_stopping: features_early_stopping
+			.as_ref()
+			.map(|features| init_model.compute_logits(features.to_rows().view())),
+	});
 
 	// Determine how to bin each feature.
 	#[cfg(feature = "timing")]
@@ -92,7 +192,6 @@ pub fn train(
 	timing.compute_binning_instructions.inc(start.elapsed());
 
 	// Use the binning instructions from the previous step to compute the binned features.
-	let binned_features_layout = train_options.binned_features_layout;
 	let progress_counter = ProgressCounter::new(features_train.nrows().to_u64().unwrap());
 	(progress.handle_progress_event)(TrainProgressEvent::Initialize(progress_counter.clone()));
 	#[cfg(feature = "timing")]
@@ -131,11 +230,153 @@ pub fn train(
 	#[cfg(feature = "timing")]
 	timing.compute_binned_features.inc(start.elapsed());
 
-	// Regression and binary classification train one tree per round. Multiclass classification trains one tree per class per round.
+	let early_stopping = features_early_stopping.map(|features| EarlyStopping {
+		task: task_early_stopping.unwrap(),
+		features,
+		labels: labels_early_stopping.unwrap(),
+		monitor: early_stopping_monitor.unwrap(),
+	});
+	train_binned(
+		task_train,
+		n_features,
+		used_features_binning_instructions,
+		compute_binned_features_column_major_output,
+		binned_features_row_major,
+		labels_train,
+		early_stopping,
+		init,
+		train_options,
+		progress,
+		#[cfg(feature = "timing")]
+		timing,
+	)
+}
+
+/// This is the out of core counterpart to `train`, for datasets whose features do not fit in memory. The features are read in chunks from `feature_chunks` and only their bins are kept, in memory mapped temporary files. The binning instructions are computed from `features_sample`, which should be a uniform sample of the rows in `feature_chunks`. The binned features are always laid out in column major order, and early stopping is disabled because it needs the unbinned features for the early stopping examples.
+pub fn train_out_of_core<E>(
+	task: Task,
+	features_sample: TableView,
+	feature_chunks: impl Iterator<Item = Result<Table, E>>,
+	labels: TableColumnView,
+	train_options: &TrainOptions,
+	progress: Progress,
+) -> Result<TrainOutput, E>
+where
+	E: From<std::io::Error>,
+{
+	#[cfg(feature = "timing")]
+	let timing = Timing::new();
+
+	let train_options = &TrainOptions {
+		binned_features_layout: BinnedFeaturesLayout::ColumnMajor,
+		early_stopping_options: None,
+		..train_options.clone()
+	};
+	let n_features = features_sample.ncols();
+
+	// Determine how to bin each feature using the sample.
+	#[cfg(feature = "timing")]
+	let start = std::time::Instant::now();
+	let binning_instructions = compute_binning_instructions(&features_sample, train_options);
+	#[cfg(feature = "timing")]
+	timing.compute_binning_instructions.inc(start.elapsed());
+
+	// Bin the features one chunk at a time.
+	let progress_counter = ProgressCounter::new(labels.len().to_u64().unwrap());
+	(progress.handle_progress_event)(TrainProgressEvent::Initialize(progress_counter.clone()));
+	#[cfg(feature = "timing")]
+	let start = std::time::Instant::now();
+	let compute_binned_features_column_major_output =
+		compute_binned_features_column_major_out_of_core(
+			feature_chunks,
+			&binning_instructions,
+			train_options,
+			&|n_examples| progress_counter.inc(n_examples),
+		)?;
+	#[cfg(feature = "timing")]
+	timing.compute_binned_features.inc(start.elapsed());
+	let n_examples = compute_binned_features_column_major_output
+		.binned_features
+		.columns
+		.first()
+		.map(|column| column.len())
+		.unwrap_or_else(|| labels.len());
+	assert_eq!(
+		n_examples,
+		labels.len(),
+		"the feature chunks must have one row for each label"
+	);
+	let used_features_binning_instructions = compute_binned_features_column_major_output
+		.used_feature_indexes
+		.iter()
+		.map(|original_feature_index| binning_instructions[*original_feature_index].clone())
+		.collect::<Vec<_>>();
+
+	Ok(train_binned(
+		task,
+		n_features,
+		used_features_binning_instructions,
+		compute_binned_features_column_major_output,
+		None,
+		labels,
+		None,
+		None,
+		train_options,
+		progress,
+		#[cfg(feature = "timing")]
+		timing,
+	))
+}
+
+/// These are the examples held out for early stopping and the monitor that decides when to stop.
+struct EarlyStopping<'a> {
+	/// For ranking, this holds the query groups of the early stopping examples.
+	task: Task<'a>,
+	features: Array2<TableValue<'a>>,
+	labels: TableColumnView<'a>,
+	monitor: EarlyStoppingMonitor,
+}
+
+/// Train on features that have already been binned. This is shared by `train` and `train_out_of_core`.
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_lines)]
+fn train_binned(
+	task: Task,
+	n_features: usize,
+	used_features_binning_instructions: Vec<BinningInstruction>,
+	compute_binned_features_column_major_output: ComputeBinnedFeaturesColumnMajorOutput,
+	binned_features_row_major: Option<BinnedFeaturesRowMajor>,
+	labels_train: TableColumnView,
+	early_stopping: Option<EarlyStopping>,
+	init: Option<Init>,
+	train_options: &TrainOptions,
+	progress: Progress,
+	#[cfg(feature = "timing")] timing: Timing,
+) -> TrainOutput {
+	let n_examples_train = labels_train.len();
+	let binned_features_layout = train_options.binned_features_layout;
+	let early_stopping_enabled = early_stopping.is_some();
+	let (
+		task_early_stopping,
+		features_early_stopping,
+		labels_early_stopping,
+		mut early_stopping_monitor,
+	) = match early_stopping {
+		Some(EarlyStopping {
+			task,
+			features,
+			labels,
+			monitor,
+		}) => (Some(task), Some(features), Some(labels), Some(monitor)),
+		None => (None, None, None, None),
+	};
+
+	// Regression, binary classification, and ranking train one tree per round. Multiclass classification trains one tree per class per round.
 	let n_trees_per_round = match task {
 		Task::Regression => 1,
 		Task::BinaryClassification => 1,
 		Task::MulticlassClassification { n_classes } => n_classes,
+		Task::Ranking { .. } => 1,
 	};
 
 	// The mean square error loss used in regression has a constant second derivative, so there is no need to use hessians for regression tasks.
@@ -143,10 +384,32 @@ pub fn train(
 		Task::Regression => true,
 		Task::BinaryClassification => false,
 		Task::MulticlassClassification { .. } => false,
+		Task::Ranking { .. } => false,
 	};
 
-	// Compute the biases. A tree model's prediction will be a bias plus the sum of the outputs of each tree. The bias will produce the baseline prediction.
-	let biases = match task {
+	// Gradient-based one-side sampling weights the examples it samples, so when it is enabled the hessians hold the weights and are not constant.
+	let goss_enabled = train_options.goss_options.is_some();
+	let bagging_enabled = !goss_enabled
+		&& train_options.bagging_frequency > 0
+		&& train_options.bagging_fraction < 1.0;
+	let tree_hessians_are_constant = hessians_are_constant && !goss_enabled;
+
+	// Compute the biases. A tree model's prediction will be a bias plus the sum of the outputs of each tree. The bias will produce the baseline prediction. If there is an init model, its biases are kept.
+	let (biases, init_trees, init_predictions_train, init_predictions_early_stopping) = match init {
+		Some(Init {
+			biases,
+			trees,
+			predictions_train,
+			predictions_early_stopping,
+		}) => (
+			Some(biases),
+			trees,
+			Some(predictions_train),
+			predictions_early_stopping,
+		),
+		None => (None, Vec::new(), None, None),
+	};
+	let biases = biases.unwrap_or_else(|| match task {
 		// For regression, the bias is the mean of the labels.
 		Task::Regression => {
 			let labels_train = labels_train.as_number().unwrap();
@@ -165,7 +428,9 @@ pub fn train(
 			let labels_train = labels_train.as_slice().into();
 			crate::multiclass_classifier::compute_biases(labels_train, n_trees_per_round)
 		}
-	};
+		// For ranking, only the order of the predictions within each query group matters, so the bias is zero.
+		Task::Ranking { .. } => crate::ranker::compute_biases(),
+	});
 
 	// Pre-allocate memory to be used in training.
 	let mut predictions =
@@ -177,6 +442,19 @@ pub fn train(
 	let mut examples_index = unsafe { Array::uninit(n_examples_train).assume_init() };
 	let mut examples_index_left_buffer = unsafe { Array::uninit(n_examples_train).assume_init() };
 	let mut examples_index_right_buffer = unsafe { Array::uninit(n_examples_train).assume_init() };
+	// If bagging or gradient-based one-side sampling is enabled, this marks the examples in the sample used to train the next tree.
+	let mut in_bag = if bagging_enabled || goss_enabled {
+		Some(vec![true; n_examples_train])
+	} else {
+		None
+	};
+	let mut rng = Xoshiro256Plus::seed_from_u64(train_options.seed);
+	// If DART is enabled, this holds the sum of the outputs of the trees dropped in the current round for each example.
+	let mut dropped_predictions = if train_options.dart_options.is_some() {
+		Some(Array::zeros((n_examples_train, n_trees_per_round).f()))
+	} else {
+		None
+	};
 	let mut predictions_early_stopping = if early_stopping_enabled {
 		let mut predictions_early_stopping = unsafe {
 			Array::uninit((
@@ -185,8 +463,12 @@ pub fn train(
 			))
 			.assume_init()
 		};
-		for mut predictions in predictions_early_stopping.axis_iter_mut(Axis(0)) {
-			predictions.assign(&biases);
+		if let Some(init_predictions_early_stopping) = &init_predictions_early_stopping {
+			predictions_early_stopping.assign(init_predictions_early_stopping);
+		} else {
+			for mut predictions in predictions_early_stopping.axis_iter_mut(Axis(0)) {
+				predictions.assign(&biases);
+			}
 		}
 		Some(predictions_early_stopping)
 	} else {
@@ -226,6 +508,8 @@ pub fn train(
 	let mut n_rounds_trained = 0;
 	// These are the trees in round-major order. After training this will be converted to an array of shape (n_rounds, n_trees_per_round).
 	let mut trees: Vec<TrainTree> = Vec::new();
+	// These are the weights of the trees in round-major order. They are all 1 unless DART is enabled.
+	let mut tree_weights: Vec<f32> = Vec::new();
 	// Collect the loss on the training dataset for each round if enabled.
 	let mut losses: Option<Vec<f32>> = if train_options.compute_losses {
 		Some(Vec::new())
@@ -233,9 +517,13 @@ pub fn train(
 		None
 	};
 
-	// Before the first round, fill the predictions with the biases, which are the baseline predictions.
-	for mut predictions in predictions.axis_iter_mut(Axis(0)) {
-		predictions.assign(&biases)
+	// Before the first round, fill the predictions with the biases, which are the baseline predictions, or with the init model's predictions if there is one.
+	if let Some(init_predictions_train) = &init_predictions_train {
+		predictions.assign(init_predictions_train);
+	} else {
+		for mut predictions in predictions.axis_iter_mut(Axis(0)) {
+			predictions.assign(&biases)
+		}
 	}
 
 	(progress.handle_progress_event)(TrainProgressEvent::InitializeDone);
@@ -243,8 +531,36 @@ pub fn train(
 	// Train rounds of trees until we hit max_rounds or the early stopping monitor indicates we should stop early.
 	let round_counter = ProgressCounter::new(train_options.max_rounds.to_u64().unwrap());
 	(progress.handle_progress_event)(TrainProgressEvent::Train(round_counter.clone()));
-	for _ in 0..train_options.max_rounds {
+	for round_index in 0..train_options.max_rounds {
 		round_counter.inc(1);
+		// If bagging is enabled, resample the examples every `bagging_frequency` rounds.
+		if bagging_enabled && round_index % train_options.bagging_frequency == 0 {
+			sample_examples_bagging(
+				in_bag.as_mut().unwrap(),
+				train_options.bagging_fraction,
+				&mut rng,
+			);
+		}
+		// If DART is enabled, choose the rounds to drop and remove their trees' outputs from the predictions, so the trees for this round are fit without them.
+		let dropped_rounds = match train_options.dart_options.as_ref() {
+			Some(dart_options) => sample_dropped_rounds(n_rounds_trained, dart_options, &mut rng),
+			None => Vec::new(),
+		};
+		if !dropped_rounds.is_empty() {
+			let dropped_predictions = dropped_predictions.as_mut().unwrap();
+			compute_dropped_predictions(
+				dropped_predictions.view_mut(),
+				&dropped_rounds,
+				&trees,
+				&tree_weights,
+				&compute_binned_features_column_major_output.binned_features,
+			);
+			predictions -= &*dropped_predictions;
+		}
+		// The new trees are weighted to be as important as each of the dropped trees, whose weights are shrunk so that together they have the effect of a single tree.
+		let n_dropped_rounds = dropped_rounds.len().to_f32().unwrap();
+		let new_tree_weight = 1.0 / (n_dropped_rounds + 1.0);
+		let dropped_tree_scale = n_dropped_rounds / (n_dropped_rounds + 1.0);
 		// Train n_trees_per_round trees.
 		let mut trees_for_round = Vec::with_capacity(n_trees_per_round);
 		for tree_per_round_index in 0..n_trees_per_round {
@@ -280,18 +596,64 @@ pub fn train(
 						predictions.view(),
 					);
 				}
+				Task::Ranking { groups, ndcg_k } => {
+					let labels_train = labels_train.as_number().unwrap();
+					crate::ranker::compute_gradients_and_hessians(
+						gradients.as_slice_mut().unwrap(),
+						hessians.as_slice_mut().unwrap(),
+						labels_train.as_slice(),
+						predictions.column(0).as_slice().unwrap(),
+						groups,
+						ndcg_k,
+					);
+				}
 			};
 			#[cfg(feature = "timing")]
 			timing.compute_gradients_and_hessians.inc(start.elapsed());
-			// Reset the examples_index.
-			examples_index
-				.as_slice_mut()
-				.unwrap()
-				.par_iter_mut()
-				.enumerate()
-				.for_each(|(index, value)| {
-					*value = index.to_u32().unwrap();
-				});
+			// If gradient-based one-side sampling is enabled, sample the examples for this tree using the gradients.
+			if let Some(goss_options) = train_options.goss_options.as_ref() {
+				sample_examples_goss(
+					in_bag.as_mut().unwrap(),
+					gradients.as_slice_mut().unwrap(),
+					hessians.as_slice_mut().unwrap(),
+					hessians_are_constant,
+					goss_options,
+					&mut rng,
+				);
+			}
+			// Reset the examples_index. If the tree is trained on a sample of the examples, the examples_index holds only the examples in the sample.
+			let n_examples_in_bag = match in_bag.as_ref() {
+				Some(in_bag) if in_bag.iter().any(|in_bag| *in_bag) => {
+					let mut n_examples_in_bag = 0;
+					for (index, _) in in_bag.iter().enumerate().filter(|(_, in_bag)| **in_bag) {
+						examples_index[n_examples_in_bag] = index.to_u32().unwrap();
+						n_examples_in_bag += 1;
+					}
+					n_examples_in_bag
+				}
+				_ => {
+					examples_index
+						.as_slice_mut()
+						.unwrap()
+						.par_iter_mut()
+						.enumerate()
+						.for_each(|(index, value)| {
+							*value = index.to_u32().unwrap();
+						});
+					n_examples_train
+				}
+			};
+			// If feature fraction per tree is enabled, sample the features this tree may split on.
+			let sampled_features_for_tree = if train_options.feature_fraction_per_tree < 1.0 {
+				Some(sample_features(
+					used_features_binning_instructions.len(),
+					None,
+					train_options.feature_fraction_per_tree,
+					&mut rng,
+				))
+			} else {
+				None
+			};
 			// Train the tree.
 			let tree = train_tree(TrainTreeOptions {
 				binning_instructions: &used_features_binning_instructions,
@@ -302,11 +664,16 @@ pub fn train(
 				hessians: hessians.as_slice().unwrap(),
 				gradients_ordered_buffer: gradients_ordered_buffer.as_slice_mut().unwrap(),
 				hessians_ordered_buffer: hessians_ordered_buffer.as_slice_mut().unwrap(),
-				examples_index: examples_index.as_slice_mut().unwrap(),
-				examples_index_left_buffer: examples_index_left_buffer.as_slice_mut().unwrap(),
-				examples_index_right_buffer: examples_index_right_buffer.as_slice_mut().unwrap(),
+				examples_index: &mut examples_index.as_slice_mut().unwrap()[..n_examples_in_bag],
+				examples_index_left_buffer: &mut examples_index_left_buffer.as_slice_mut().unwrap()
+					[..n_examples_in_bag],
+				examples_index_right_buffer: &mut examples_index_right_buffer
+					.as_slice_mut()
+					.unwrap()[..n_examples_in_bag],
 				bin_stats_pool: &bin_stats_pool,
-				hessians_are_constant,
+				hessians_are_constant: tree_hessians_are_constant,
+				rng: &mut rng,
+				sampled_features_for_tree: sampled_features_for_tree.as_deref(),
 				train_options,
 				#[cfg(feature = "timing")]
 				timing: &timing,
@@ -317,13 +684,32 @@ pub fn train(
 					.column_mut(tree_per_round_index)
 					.as_slice_mut()
 					.unwrap(),
-				examples_index.as_slice().unwrap(),
+				&examples_index.as_slice().unwrap()[..n_examples_in_bag],
 				&tree,
+				new_tree_weight,
 				#[cfg(feature = "timing")]
 				&timing,
 			);
+			// The examples left out of the sample are not in the leaf value ranges, so run them through the tree.
+			if n_examples_in_bag < n_examples_train {
+				update_out_of_bag_predictions_with_tree(
+					predictions
+						.column_mut(tree_per_round_index)
+						.as_slice_mut()
+						.unwrap(),
+					in_bag.as_ref().unwrap(),
+					&compute_binned_features_column_major_output.binned_features,
+					&tree,
+					new_tree_weight,
+				);
+			}
 			trees_for_round.push(tree);
 		}
+		// Add the dropped trees' outputs back to the predictions scaled by the amount their weights shrink.
+		if !dropped_rounds.is_empty() {
+			let dropped_predictions = dropped_predictions.as_ref().unwrap();
+			predictions.scaled_add(dropped_tree_scale, dropped_predictions);
+		}
 		// If loss computation is enabled, compute the loss for this round.
 		if let Some(losses) = losses.as_mut() {
 			let loss = match task {
@@ -342,6 +728,15 @@ pub fn train(
 					let labels_train = labels_train.as_slice().into();
 					crate::multiclass_classifier::compute_loss(predictions.view(), labels_train)
 				}
+				Task::Ranking { groups, ndcg_k } => {
+					let labels_train = labels_train.as_number().unwrap();
+					crate::ranker::compute_loss(
+						predictions.view(),
+						labels_train.as_slice(),
+						groups,
+						ndcg_k,
+					)
+				}
 			};
 			losses.push(loss);
 		}
@@ -351,9 +746,21 @@ pub fn train(
 			let labels_early_stopping = labels_early_stopping.as_ref().unwrap();
 			let predictions_early_stopping = predictions_early_stopping.as_mut().unwrap();
 			let early_stopping_monitor = early_stopping_monitor.as_mut().unwrap();
+			// Remove the amount the dropped trees' weights shrink from the early stopping predictions.
+			if !dropped_rounds.is_empty() {
+				remove_dropped_predictions_early_stopping(
+					predictions_early_stopping.view_mut(),
+					features_early_stopping.view(),
+					&dropped_rounds,
+					&trees,
+					&tree_weights,
+					1.0 - dropped_tree_scale,
+				);
+			}
 			let value = compute_early_stopping_metric(
-				&task,
+				task_early_stopping.as_ref().unwrap(),
 				trees_for_round.as_slice(),
+				new_tree_weight,
 				features_early_stopping.view(),
 				labels_early_stopping.view(),
 				predictions_early_stopping.view_mut(),
@@ -362,8 +769,18 @@ pub fn train(
 		} else {
 			false
 		};
+		// Shrink the weights of the dropped trees.
+		for dropped_round_index in dropped_rounds.iter() {
+			for tree_weight in tree_weights[dropped_round_index * n_trees_per_round
+				..(dropped_round_index + 1) * n_trees_per_round]
+				.iter_mut()
+			{
+				*tree_weight *= dropped_tree_scale;
+			}
+		}
 		// Add the trees for this round to the list of trees.
 		trees.extend(trees_for_round);
+		tree_weights.extend(std::iter::repeat(new_tree_weight).take(n_trees_per_round));
 		n_rounds_trained += 1;
 		// Exit the training loop if we should stop.
 		if should_stop {
@@ -384,17 +801,19 @@ pub fn train(
 	#[cfg(feature = "timing")]
 	eprintln!("{:?}", timing);
 
-	// Assemble the model.
-	let trees: Vec<Tree> = trees
+	// Assemble the model. The new trees are appended to the init model's trees.
+	let n_rounds_trained = init_trees.len() / n_trees_per_round + n_rounds_trained;
+	let trees: Vec<Tree> = init_trees
 		.into_iter()
-		.map(|train_tree| {
+		.chain(zip!(trees, tree_weights).map(|(train_tree, tree_weight)| {
 			tree_from_train_tree(
 				train_tree,
+				tree_weight,
 				compute_binned_features_column_major_output
 					.used_feature_indexes
 					.as_slice(),
 			)
-		})
+		}))
 		.collect();
 	match task {
 		Task::Regression => TrainOutput::Regressor(RegressorTrainOutput {
@@ -422,6 +841,14 @@ pub fn train(
 				losses,
 			})
 		}
+		Task::Ranking { .. } => TrainOutput::Ranker(RankerTrainOutput {
+			model: Ranker {
+				bias: *biases.get(0).unwrap(),
+				trees,
+			},
+			feature_importances,
+			losses,
+		}),
 	}
 }
 
@@ -429,6 +856,7 @@ fn update_predictions_with_tree(
 	predictions: &mut [f32],
 	examples_index: &[u32],
 	tree: &TrainTree,
+	tree_weight: f32,
 	#[cfg(feature = "timing")] timing: &Timing,
 ) {
 	#[cfg(feature = "timing")]
@@ -445,7 +873,7 @@ fn update_predictions_with_tree(
 				unsafe {
 					let predictions = &mut *predictions_ptr.0;
 					let example_index = example_index.to_usize().unwrap();
-					*predictions.get_unchecked_mut(example_index) += *value as f32;
+					*predictions.get_unchecked_mut(example_index) += tree_weight * *value as f32;
 				}
 			});
 	});
@@ -453,6 +881,79 @@ fn update_predictions_with_tree(
 	timing.update_predictions.inc(start.elapsed());
 }
 
+fn update_out_of_bag_predictions_with_tree(
+	predictions: &mut [f32],
+	in_bag: &[bool],
+	binned_features_column_major: &BinnedFeaturesColumnMajor,
+	tree: &TrainTree,
+	tree_weight: f32,
+) {
+	pzip!(predictions, in_bag)
+		.enumerate()
+		.filter(|(_, (_, in_bag))| !**in_bag)
+		.for_each(|(example_index, (prediction, _))| {
+			*prediction +=
+				tree_weight * tree.predict_binned(binned_features_column_major, example_index);
+		});
+}
+
+/// Compute the sum of the weighted outputs of the trees in the dropped rounds for each training example.
+fn compute_dropped_predictions(
+	mut dropped_predictions: ArrayViewMut2<f32>,
+	dropped_rounds: &[usize],
+	trees: &[TrainTree],
+	tree_weights: &[f32],
+	binned_features_column_major: &BinnedFeaturesColumnMajor,
+) {
+	let n_trees_per_round = dropped_predictions.ncols();
+	for (tree_per_round_index, mut dropped_predictions) in
+		dropped_predictions.axis_iter_mut(Axis(1)).enumerate()
+	{
+		dropped_predictions
+			.as_slice_mut()
+			.unwrap()
+			.par_iter_mut()
+			.enumerate()
+			.for_each(|(example_index, dropped_prediction)| {
+				*dropped_prediction = dropped_rounds
+					.iter()
+					.map(|dropped_round_index| {
+						let tree_index =
+							dropped_round_index * n_trees_per_round + tree_per_round_index;
+						tree_weights[tree_index]
+							* trees[tree_index]
+								.predict_binned(binned_features_column_major, example_index)
+					})
+					.sum();
+			});
+	}
+}
+
+/// Subtract `fraction` of the weighted outputs of the trees in the dropped rounds from the early stopping predictions.
+fn remove_dropped_predictions_early_stopping(
+	mut predictions: ArrayViewMut2<f32>,
+	features: ArrayView2<TableValue>,
+	dropped_rounds: &[usize],
+	trees: &[TrainTree],
+	tree_weights: &[f32],
+	fraction: f32,
+) {
+	let n_trees_per_round = predictions.ncols();
+	for (mut predictions, features) in zip!(
+		predictions.axis_iter_mut(Axis(0)),
+		features.axis_iter(Axis(0))
+	) {
+		for dropped_round_index in dropped_rounds.iter() {
+			for (tree_per_round_index, prediction) in predictions.iter_mut().enumerate() {
+				let tree_index = dropped_round_index * n_trees_per_round + tree_per_round_index;
+				*prediction -= fraction
+					* tree_weights[tree_index]
+					* trees[tree_index].predict(features.as_slice().unwrap());
+			}
+		}
+	}
+}
+
 #[derive(Clone)]
 pub struct EarlyStoppingMonitor {
 	tolerance: f32,
@@ -493,25 +994,53 @@ impl EarlyStoppingMonitor {
 	}
 }
 
-/// Split the feature and labels into train and early stopping datasets, where the early stopping dataset will have `early_stopping_fraction * features.nrows()` rows.
-fn train_early_stopping_split<'features, 'labels>(
+/// Split the feature and labels into train and early stopping datasets, where the early stopping dataset will have `early_stopping_fraction * features.nrows()` rows. For ranking, the split is moved forward to the end of the query group it falls in, so that no query group is split between the two datasets.
+#[allow(clippy::type_complexity)]
+fn train_early_stopping_split<'task, 'features, 'labels>(
+	task: Task<'task>,
 	features: TableView<'features>,
 	labels: TableColumnView<'labels>,
 	early_stopping_fraction: f32,
 ) -> (
+	Task<'task>,
 	TableView<'features>,
 	TableColumnView<'labels>,
+	Task<'task>,
 	TableView<'features>,
 	TableColumnView<'labels>,
 ) {
-	let split_index = (early_stopping_fraction * labels.len().to_f32().unwrap())
+	let mut split_index = (early_stopping_fraction * labels.len().to_f32().unwrap())
 		.to_usize()
 		.unwrap();
+	let (task_train, task_early_stopping) = match task {
+		Task::Ranking { groups, ndcg_k } => {
+			while split_index > 0
+				&& split_index < groups.len()
+				&& groups[split_index] == groups[split_index - 1]
+			{
+				split_index += 1;
+			}
+			let (groups_early_stopping, groups_train) = groups.split_at(split_index);
+			(
+				Task::Ranking {
+					groups: groups_train,
+					ndcg_k,
+				},
+				Task::Ranking {
+					groups: groups_early_stopping,
+					ndcg_k,
+				},
+			)
+		}
+		task => (task, task),
+	};
 	let (features_early_stopping, features_train) = features.split_at_row(split_index);
 	let (labels_early_stopping, labels_train) = labels.split_at_row(split_index);
 	(
+		task_train,
 		features_train,
 		labels_train,
+		task_early_stopping,
 		features_early_stopping,
 		labels_early_stopping,
 	)
@@ -521,6 +1050,7 @@ fn train_early_stopping_split<'features, 'labels>(
 fn compute_early_stopping_metric(
 	task: &Task,
 	trees_for_round: &[TrainTree],
+	tree_weight: f32,
 	features: ArrayView2<TableValue>,
 	labels: TableColumnView,
 	mut predictions: ArrayViewMut2<f32>,
@@ -531,6 +1061,7 @@ fn compute_early_stopping_metric(
 			let labels = labels.as_slice().into();
 			crate::regressor::update_logits(
 				trees_for_round,
+				tree_weight,
 				features.view(),
 				predictions.view_mut(),
 			);
@@ -541,6 +1072,7 @@ fn compute_early_stopping_metric(
 			let labels = labels.as_slice().into();
 			crate::binary_classifier::update_logits(
 				trees_for_round,
+				tree_weight,
 				features.view(),
 				predictions.view_mut(),
 			);
@@ -551,16 +1083,28 @@ fn compute_early_stopping_metric(
 			let labels = labels.as_slice().into();
 			crate::multiclass_classifier::update_logits(
 				trees_for_round,
+				tree_weight,
 				features.view(),
 				predictions.view_mut(),
 			);
 			crate::multiclass_classifier::compute_loss(predictions.view(), labels)
 		}
+		Task::Ranking { groups, ndcg_k } => {
+			let labels = labels.as_number().unwrap();
+			crate::ranker::update_logits(
+				trees_for_round,
+				tree_weight,
+				features.view(),
+				predictions.view_mut(),
+			);
+			crate::ranker::compute_loss(predictions.view(), labels.as_slice(), groups, *ndcg_k)
+		}
 	}
 }
 
 fn tree_from_train_tree(
 	train_tree: TrainTree,
+	weight: f32,
 	train_feature_index_to_feature_index: &[usize],
 ) -> Tree {
 	let nodes = train_tree
@@ -568,7 +1112,7 @@ fn tree_from_train_tree(
 		.into_iter()
 		.map(|node| node_from_train_node(node, train_feature_index_to_feature_index))
 		.collect();
-	Tree { nodes }
+	Tree { nodes, weight }
 }
 
 fn node_from_train_node(
@@ -617,3 +1161,499 @@ fn node_from_train_node(
 		}),
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::compute_binned_features::BinnedFeaturesColumnMajorColumn;
+	use crate::{DartOptions, GossOptions};
+	use modelfox_kill_chip::KillChip;
+	use modelfox_zip::zip;
+	use std::{num::NonZeroUsize, ops::Range};
+
+	const N_EXAMPLES: usize = 1000;
+	const CHUNK_SIZE: usize = 128;
+
+	/// Make the rows in `rows` of a table with a number feature that has invalid values, a number feature that cannot be split, and an enum feature that has invalid values.
+	fn features(rows: Range<usize>) -> Table {
+		let mut table = Table::new(
+			vec![
+				Some("number".to_owned()),
+				Some("constant".to_owned()),
+				Some("enum".to_owned()),
+			],
+			vec![
+				TableColumnType::Number,
+				TableColumnType::Number,
+				TableColumnType::Enum {
+					variants: vec!["a".to_owned(), "b".to_owned(), "c".to_owned()],
+				},
+			],
+		);
+		for i in rows {
+			let number = if i % 13 == 0 {
+				f32::NAN
+			} else {
+				((i * 37) % 101) as f32
+			};
+			let variant = if i % 11 == 0 {
+				None
+			} else {
+				NonZeroUsize::new(i % 3 + 1)
+			};
+			let columns = table.columns_mut();
+			columns[0].as_number_mut().unwrap().data_mut().push(number);
+			columns[1].as_number_mut().unwrap().data_mut().push(1.0);
+			columns[2].as_enum_mut().unwrap().data_mut().push(variant);
+		}
+		table
+	}
+
+	fn feature_chunks() -> impl Iterator<Item = Result<Table, std::io::Error>> {
+		(0..N_EXAMPLES)
+			.step_by(CHUNK_SIZE)
+			.map(|start| Ok(features(start..usize::min(start + CHUNK_SIZE, N_EXAMPLES))))
+	}
+
+	fn labels(rows: Range<usize>) -> Vec<f32> {
+		rows.map(|i| ((i * 37) % 101) as f32 / 10.0 + (i % 3) as f32)
+			.collect()
+	}
+
+	#[test]
+	fn test_out_of_core_binned_features_match_in_memory() {
+		let features = features(0..N_EXAMPLES);
+		let train_options = TrainOptions::default();
+		let binning_instructions = compute_binning_instructions(&features.view(), &train_options);
+		let in_memory = compute_binned_features_column_major(
+			&features.view(),
+			&binning_instructions,
+			&train_options,
+			&|| {},
+		);
+		let out_of_core = compute_binned_features_column_major_out_of_core(
+			feature_chunks(),
+			&binning_instructions,
+			&train_options,
+			&|_| {},
+		)
+		.unwrap();
+		// The constant feature cannot be split, so both drop it.
+		assert_eq!(in_memory.used_feature_indexes, vec![0, 2]);
+		assert_eq!(
+			out_of_core.used_feature_indexes,
+			in_memory.used_feature_indexes
+		);
+		for (in_memory, out_of_core) in zip!(
+			in_memory.binned_features.columns.iter(),
+			out_of_core.binned_features.columns.iter(),
+		) {
+			assert_eq!(out_of_core.len(), N_EXAMPLES);
+			match (in_memory, out_of_core) {
+				(
+					BinnedFeaturesColumnMajorColumn::U8(in_memory),
+					BinnedFeaturesColumnMajorColumn::U8(out_of_core),
+				) => assert_eq!(out_of_core.as_slice(), in_memory.as_slice()),
+				(
+					BinnedFeaturesColumnMajorColumn::U16(in_memory),
+					BinnedFeaturesColumnMajorColumn::U16(out_of_core),
+				) => assert_eq!(out_of_core.as_slice(), in_memory.as_slice()),
+				_ => panic!("the binned feature types differ"),
+			}
+		}
+	}
+
+	#[test]
+	fn test_out_of_core_model_matches_in_memory() {
+		let features = features(0..N_EXAMPLES);
+		let labels = NumberTableColumn::new(None, labels(0..N_EXAMPLES));
+		let labels = labels.view();
+		let train_options = TrainOptions {
+			max_rounds: 10,
+			..Default::default()
+		};
+		let kill_chip = KillChip::default();
+		let in_memory = Regressor::train(
+			features.view(),
+			labels.clone(),
+			&train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		);
+		let out_of_core = Regressor::train_out_of_core(
+			features.view(),
+			feature_chunks(),
+			labels,
+			&train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.unwrap();
+		assert_eq!(out_of_core.model.trees.len(), 10);
+		assert_eq!(out_of_core.model.to_bytes(), in_memory.model.to_bytes());
+		assert_eq!(
+			out_of_core.feature_importances,
+			in_memory.feature_importances
+		);
+	}
+
+	fn enum_labels(rows: Range<usize>, n_classes: usize) -> EnumTableColumn {
+		let variants = (0..n_classes).map(|class| class.to_string()).collect();
+		let labels = rows
+			.map(|i| NonZeroUsize::new((i * 37) % 101 * n_classes / 101 + 1))
+			.collect();
+		EnumTableColumn::new(None, variants, labels)
+	}
+
+	#[test]
+	fn test_warm_start_with_zero_rounds_reproduces_init_model() {
+		let features = features(0..N_EXAMPLES);
+		let rows = features.to_rows();
+		let init_train_options = TrainOptions {
+			max_rounds: 5,
+			..Default::default()
+		};
+		let warm_start_train_options = TrainOptions {
+			max_rounds: 0,
+			..Default::default()
+		};
+		let kill_chip = KillChip::default();
+
+		let labels = NumberTableColumn::new(None, labels(0..N_EXAMPLES));
+		let init_model = Regressor::train(
+			features.view(),
+			labels.view(),
+			&init_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		let model = Regressor::train_warm_start(
+			&init_model,
+			features.view(),
+			labels.view(),
+			&warm_start_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		let mut init_predictions = Array1::zeros(N_EXAMPLES);
+		init_model.predict(rows.view(), init_predictions.view_mut());
+		let mut predictions = Array1::zeros(N_EXAMPLES);
+		model.predict(rows.view(), predictions.view_mut());
+		assert_eq!(predictions, init_predictions);
+
+		let labels = enum_labels(0..N_EXAMPLES, 2);
+		let init_model = BinaryClassifier::train(
+			features.view(),
+			labels.view(),
+			&init_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		let model = BinaryClassifier::train_warm_start(
+			&init_model,
+			features.view(),
+			labels.view(),
+			&warm_start_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		let mut init_probabilities = Array1::zeros(N_EXAMPLES);
+		init_model.predict(rows.view(), init_probabilities.view_mut());
+		let mut probabilities = Array1::zeros(N_EXAMPLES);
+		model.predict(rows.view(), probabilities.view_mut());
+		assert_eq!(probabilities, init_probabilities);
+
+		let labels = enum_labels(0..N_EXAMPLES, 3);
+		let init_model = MulticlassClassifier::train(
+			features.view(),
+			labels.view(),
+			&init_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		let model = MulticlassClassifier::train_warm_start(
+			&init_model,
+			features.view(),
+			labels.view(),
+			&warm_start_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		let mut init_probabilities = Array2::zeros((N_EXAMPLES, 3));
+		init_model.predict(rows.view(), init_probabilities.view_mut());
+		let mut probabilities = Array2::zeros((N_EXAMPLES, 3));
+		model.predict(rows.view(), probabilities.view_mut());
+		assert_eq!(probabilities, init_probabilities);
+	}
+
+	#[test]
+	fn test_warm_start_keeps_init_trees_as_prefix() {
+		// The init model is trained on the first half of the examples and training continues on all of them.
+		let init_features = features(0..N_EXAMPLES / 2);
+		let features = features(0..N_EXAMPLES);
+		let init_train_options = TrainOptions {
+			max_rounds: 5,
+			..Default::default()
+		};
+		let warm_start_train_options = TrainOptions {
+			max_rounds: 3,
+			..Default::default()
+		};
+		let kill_chip = KillChip::default();
+
+		let init_labels = NumberTableColumn::new(None, labels(0..N_EXAMPLES / 2));
+		let labels = NumberTableColumn::new(None, labels(0..N_EXAMPLES));
+		let init_model = Regressor::train(
+			init_features.view(),
+			init_labels.view(),
+			&init_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		let model = Regressor::train_warm_start(
+			&init_model,
+			features.view(),
+			labels.view(),
+			&warm_start_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		assert_eq!(model.trees.len(), 8);
+		let prefix = Regressor {
+			bias: model.bias,
+			trees: model.trees[..5].to_vec(),
+		};
+		assert_eq!(prefix.to_bytes(), init_model.to_bytes());
+
+		let init_labels = enum_labels(0..N_EXAMPLES / 2, 2);
+		let labels = enum_labels(0..N_EXAMPLES, 2);
+		let init_model = BinaryClassifier::train(
+			init_features.view(),
+			init_labels.view(),
+			&init_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		let model = BinaryClassifier::train_warm_start(
+			&init_model,
+			features.view(),
+			labels.view(),
+			&warm_start_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		assert_eq!(model.trees.len(), 8);
+		let prefix = BinaryClassifier {
+			bias: model.bias,
+			trees: model.trees[..5].to_vec(),
+		};
+		assert_eq!(prefix.to_bytes(), init_model.to_bytes());
+
+		let init_labels = enum_labels(0..N_EXAMPLES / 2, 3);
+		let labels = enum_labels(0..N_EXAMPLES, 3);
+		let init_model = MulticlassClassifier::train(
+			init_features.view(),
+			init_labels.view(),
+			&init_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		let model = MulticlassClassifier::train_warm_start(
+			&init_model,
+			features.view(),
+			labels.view(),
+			&warm_start_train_options,
+			Progress {
+				kill_chip: &kill_chip,
+				handle_progress_event: &mut |_| {},
+			},
+		)
+		.model;
+		assert_eq!(model.trees.nrows(), 8);
+		let prefix = MulticlassClassifier {
+			biases: model.biases.clone(),
+			trees: model.trees.slice(s![..5, ..]).to_owned(),
+		};
+		assert_eq!(prefix.to_bytes(), init_model.to_bytes());
+	}
+
+	fn train_regressor(train_options: &TrainOptions) -> RegressorTrainOutput {
+		let features = features(0..N_EXAMPLES);
+		let labels = NumberTableColumn::new(None, labels(0..N_EXAMPLES));
+		Regressor::train(
+			features.view(),
+			labels.view(),
+			train_options,
+			Progress {
+				kill_chip: &KillChip::default(),
+				handle_progress_event: &mut |_| {},
+			},
+		)
+	}
+
+	/// Assert that training with `train_options` gives the same model every time with the same seed, and a different model with a different seed.
+	fn assert_seeded(train_options: TrainOptions) {
+		let model = train_regressor(&train_options).model;
+		let same_seed_model = train_regressor(&train_options).model;
+		assert_eq!(same_seed_model.to_bytes(), model.to_bytes());
+		let other_seed_model = train_regressor(&TrainOptions {
+			seed: train_options.seed + 1,
+			..train_options
+		})
+		.model;
+		assert_ne!(other_seed_model.to_bytes(), model.to_bytes());
+	}
+
+	#[test]
+	fn test_bagging_is_seeded() {
+		assert_seeded(TrainOptions {
+			bagging_fraction: 0.5,
+			bagging_frequency: 1,
+			max_rounds: 10,
+			..Default::default()
+		});
+	}
+
+	#[test]
+	fn test_feature_fraction_per_tree_is_seeded() {
+		assert_seeded(TrainOptions {
+			feature_fraction_per_tree: 0.5,
+			max_rounds: 10,
+			..Default::default()
+		});
+	}
+
+	#[test]
+	fn test_feature_fraction_per_node_is_seeded() {
+		assert_seeded(TrainOptions {
+			feature_fraction_per_node: 0.5,
+			max_rounds: 10,
+			..Default::default()
+		});
+	}
+
+	#[test]
+	fn test_goss_is_seeded() {
+		assert_seeded(TrainOptions {
+			goss_options: Some(GossOptions {
+				top_rate: 0.2,
+				other_rate: 0.1,
+			}),
+			max_rounds: 10,
+			..Default::default()
+		});
+	}
+
+	#[test]
+	fn test_dart_is_seeded() {
+		assert_seeded(TrainOptions {
+			dart_options: Some(DartOptions {
+				drop_rate: 0.3,
+				max_drop: 0,
+				skip_drop: 0.0,
+			}),
+			max_rounds: 10,
+			..Default::default()
+		});
+	}
+
+	#[test]
+	fn test_dart_rescales_tree_weights() {
+		// If every round is dropped, each new tree gets weight 1 / (n + 1) and the n previous trees are shrunk by n / (n + 1), so all the trees end with the same weight.
+		let train_output = train_regressor(&TrainOptions {
+			dart_options: Some(DartOptions {
+				drop_rate: 1.0,
+				max_drop: 0,
+				skip_drop: 0.0,
+			}),
+			max_rounds: 4,
+			..Default::default()
+		});
+		for tree in train_output.model.trees.iter() {
+			assert!((tree.weight - 0.25).abs() < 1e-6, "{}", tree.weight);
+		}
+		// If dropout is always skipped, DART trains the same model as plain gradient boosting.
+		let train_options = TrainOptions {
+			max_rounds: 10,
+			..Default::default()
+		};
+		let model = train_regressor(&train_options).model;
+		let skip_model = train_regressor(&TrainOptions {
+			dart_options: Some(DartOptions {
+				drop_rate: 0.5,
+				max_drop: 0,
+				skip_drop: 1.0,
+			}),
+			..train_options
+		})
+		.model;
+		assert_eq!(skip_model.to_bytes(), model.to_bytes());
+	}
+
+	#[test]
+	fn test_dart_training_predictions_match_model() {
+		// The predictions used while training are rescaled along with the tree weights, so the final training loss is the loss of the model's predictions.
+		let train_output = train_regressor(&TrainOptions {
+			compute_losses: true,
+			dart_options: Some(DartOptions {
+				drop_rate: 0.5,
+				max_drop: 0,
+				skip_drop: 0.0,
+			}),
+			max_rounds: 10,
+			..Default::default()
+		});
+		let features = features(0..N_EXAMPLES);
+		let features = features.to_rows();
+		let labels = Array1::from(labels(0..N_EXAMPLES));
+		let mut predictions = Array2::zeros((1, N_EXAMPLES));
+		train_output
+			.model
+			.predict(features.view(), predictions.row_mut(0));
+		let loss = crate::regressor::compute_loss(predictions.view(), labels.view());
+		let training_loss = *train_output.losses.unwrap().last().unwrap();
+		assert!(
+			(loss - training_loss).abs() < 1e-4 * loss,
+			"{} != {}",
+			loss,
+			training_loss
+		);
+	}
+}
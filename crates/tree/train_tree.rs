@@ -8,10 +8,12 @@ use crate::{
 	compute_binning_instructions::BinningInstruction,
 	pool::{Pool, PoolItem},
 	rearrange_examples_index::rearrange_examples_index,
+	sample::sample_features,
 	SplitDirection, TrainOptions,
 };
 use bitvec::prelude::*;
 use num::ToPrimitive;
+use rand_xoshiro::Xoshiro256Plus;
 use std::{cmp::Ordering, collections::BinaryHeap, ops::Range};
 
 #[derive(Debug)]
@@ -74,6 +76,65 @@ impl TrainTree {
 			}
 		}
 	}
+
+	/// Make a prediction for a training example using its binned features. This is used to update the predictions for the examples that were left out of the sample used to train the tree.
+	pub fn predict_binned(
+		&self,
+		binned_features_column_major: &BinnedFeaturesColumnMajor,
+		example_index: usize,
+	) -> f32 {
+		// Start at the root node.
+		let mut node_index = 0;
+		// Traverse the tree until we get to a leaf.
+		loop {
+			match &self.nodes.get(node_index).unwrap() {
+				TrainNode::Branch(TrainBranchNode {
+					left_child_index,
+					right_child_index,
+					split,
+					..
+				}) => {
+					let direction = match split {
+						// This branch uses a continuous split. Examples whose bin is at or below the split's bin go to the left.
+						TrainBranchSplit::Continuous(TrainBranchSplitContinuous {
+							feature_index,
+							bin_index,
+							..
+						}) => {
+							let binned_feature_value = binned_features_column_major
+								.columns
+								.get(*feature_index)
+								.unwrap()
+								.get(example_index);
+							if binned_feature_value <= *bin_index {
+								SplitDirection::Left
+							} else {
+								SplitDirection::Right
+							}
+						}
+						// This branch uses a discrete split.
+						TrainBranchSplit::Discrete(TrainBranchSplitDiscrete {
+							feature_index,
+							directions,
+						}) => {
+							let binned_feature_value = binned_features_column_major
+								.columns
+								.get(*feature_index)
+								.unwrap()
+								.get(example_index);
+							(*directions.get(binned_feature_value).unwrap()).into()
+						}
+					};
+					node_index = match direction {
+						SplitDirection::Left => left_child_index.unwrap(),
+						SplitDirection::Right => right_child_index.unwrap(),
+					};
+				}
+				// We made it to a leaf! The prediction is the leaf's value.
+				TrainNode::Leaf(TrainLeafNode { value, .. }) => return *value as f32,
+			}
+		}
+	}
 }
 
 #[derive(Debug)]
@@ -193,6 +254,8 @@ pub struct TrainTreeOptions<'a> {
 	pub hessians_are_constant: bool,
 	pub hessians_ordered_buffer: &'a mut [f32],
 	pub hessians: &'a [f32],
+	pub rng: &'a mut Xoshiro256Plus,
+	pub sampled_features_for_tree: Option<&'a [bool]>,
 	#[cfg(feature = "timing")]
 	pub timing: &'a crate::timing::Timing,
 	pub train_options: &'a TrainOptions,
@@ -213,6 +276,8 @@ pub fn train_tree(options: TrainTreeOptions) -> TrainTree {
 		hessians_are_constant,
 		hessians_ordered_buffer,
 		hessians,
+		rng,
+		sampled_features_for_tree,
 		train_options,
 		..
 	} = options;
@@ -228,16 +293,35 @@ pub fn train_tree(options: TrainTreeOptions) -> TrainTree {
 	let n_examples_root = examples_index.len();
 	let examples_index_range_root = 0..n_examples_root;
 
+	// If feature fraction per node is enabled, each node considers a random sample of the features sampled for the tree.
+	let sample_features_for_node = |rng: &mut Xoshiro256Plus| {
+		if train_options.feature_fraction_per_node < 1.0 {
+			Some(sample_features(
+				binning_instructions.len(),
+				sampled_features_for_tree,
+				train_options.feature_fraction_per_node,
+				rng,
+			))
+		} else {
+			None
+		}
+	};
+
 	// Choose the best split for the root node.
+	let sampled_features_for_root = sample_features_for_node(rng);
 	let choose_best_split_output_root = choose_best_split_root(ChooseBestSplitRootOptions {
 		bin_stats_pool,
 		binned_features_column_major,
 		binned_features_row_major,
 		binning_instructions,
 		examples_index,
+		gradients_ordered_buffer,
 		gradients,
 		hessians_are_constant,
+		hessians_ordered_buffer,
 		hessians,
+		sampled_features_for_node: sampled_features_for_root.as_deref(),
+		sampled_features_for_tree,
 		#[cfg(feature = "timing")]
 		timing,
 		train_options,
@@ -344,6 +428,8 @@ pub fn train_tree(options: TrainTreeOptions) -> TrainTree {
 		// Choose the best splits for each of the right and left children of this new branch.
 		#[cfg(feature = "timing")]
 		let start = std::time::Instant::now();
+		let left_child_sampled_features = sample_features_for_node(rng);
+		let right_child_sampled_features = sample_features_for_node(rng);
 		let (left_child_best_split_output, right_child_best_split_output) =
 			choose_best_splits_not_root(ChooseBestSplitsNotRootOptions {
 				bin_stats_pool,
@@ -358,12 +444,14 @@ pub fn train_tree(options: TrainTreeOptions) -> TrainTree {
 				left_child_examples_index,
 				splittable_features: queue_item.splittable_features.as_slice(),
 				left_child_n_examples: queue_item.left_n_examples,
+				left_child_sampled_features: left_child_sampled_features.as_deref(),
 				left_child_sum_gradients: queue_item.left_sum_gradients,
 				left_child_sum_hessians: queue_item.left_sum_hessians,
 				parent_bin_stats: queue_item.bin_stats,
 				parent_depth: queue_item.depth,
 				right_child_examples_index,
 				right_child_n_examples: queue_item.right_n_examples,
+				right_child_sampled_features: right_child_sampled_features.as_deref(),
 				right_child_sum_gradients: queue_item.right_sum_gradients,
 				right_child_sum_hessians: queue_item.right_sum_hessians,
 				#[cfg(feature = "timing")]
@@ -353,6 +353,7 @@ impl Component for TuningCode {
 					let options = modelfox::PredictOptions {
 						threshold: Some(0.5),
 						compute_feature_contributions: Some(true),
+						compute_feature_interactions: None,
 					};
 					let output = model.predict_one(input.clone(), Some(options.clone()));
 				"#
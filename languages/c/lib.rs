@@ -121,12 +121,13 @@ pub unsafe extern "C" fn modelfox_model_get_id(
 	*id_ptr = (*model).0.id.as_str().into();
 }
 
-/// A `modelfox_task` identifies the task a model performs, one of regression, binary classification, or multiclass classification.
+/// A `modelfox_task` identifies the task a model performs, one of regression, binary classification, multiclass classification, or ranking.
 #[repr(C)]
 pub enum modelfox_task {
 	REGRESSION,
 	BINARY_CLASSIFICATION,
 	MULTICLASS_CLASSIFICATION,
+	RANKING,
 }
 
 /// Retrieve the task of the model. On success, the task will be written to `task_ptr`.
@@ -143,6 +144,7 @@ pub unsafe extern "C" fn modelfox_model_get_task(
 		modelfox_core::predict::ModelInner::MulticlassClassifier(_) => {
 			modelfox_task::MULTICLASS_CLASSIFICATION
 		}
+		modelfox_core::predict::ModelInner::Ranker(_) => modelfox_task::RANKING,
 	};
 }
 
@@ -223,6 +225,95 @@ pub unsafe extern "C" fn modelfox_predict_input_vec_push(
 	(*predict_input_vec).0.push(Box::from_raw(predict_input).0);
 }
 
+/// A `modelfox_predict_table` value is an opaque handle to a batch of rows stored column by column, to be passed to `modelfox_model_predict_table`.
+pub struct modelfox_predict_table(modelfox_table::Table);
+
+/// Create a new predict table with no columns. You must call `modelfox_predict_table_delete` when you are done with it.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_predict_table_new(
+	predict_table_ptr: *mut *const modelfox_predict_table,
+) {
+	let table = modelfox_table::Table::new(Vec::new(), Vec::new());
+	*predict_table_ptr = Box::into_raw(Box::new(modelfox_predict_table(table)));
+}
+
+/// Delete a predict table.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_predict_table_delete(predict_table: *mut modelfox_predict_table) {
+	drop(Box::from_raw(predict_table));
+}
+
+/// Add a number column named `column_name` with the `len` values pointed to by `values`. The values are copied. `len` must be the same for every column in the table.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_predict_table_add_number_column(
+	predict_table: *mut modelfox_predict_table,
+	column_name: *const c_char,
+	values: *const c_float,
+	len: size_t,
+) -> *mut modelfox_error {
+	handle_error(|| {
+		let column_name = CStr::from_ptr(column_name).to_str()?.to_owned();
+		let values = if len == 0 {
+			Vec::new()
+		} else {
+			std::slice::from_raw_parts(values, len).to_owned()
+		};
+		let table = &mut (*predict_table).0;
+		check_predict_table_column_len(table, len)?;
+		table
+			.columns_mut()
+			.push(modelfox_table::TableColumn::Number(
+				modelfox_table::NumberTableColumn::new(Some(column_name), values),
+			));
+		Ok(())
+	})
+}
+
+/// Add a string column named `column_name` with the `len` null terminated strings pointed to by `values`. A null pointer is a missing value. The values are copied. `len` must be the same for every column in the table.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_predict_table_add_string_column(
+	predict_table: *mut modelfox_predict_table,
+	column_name: *const c_char,
+	values: *const *const c_char,
+	len: size_t,
+) -> *mut modelfox_error {
+	handle_error(|| {
+		let column_name = CStr::from_ptr(column_name).to_str()?.to_owned();
+		let values: &[*const c_char] = if len == 0 {
+			&[]
+		} else {
+			std::slice::from_raw_parts(values, len)
+		};
+		let values = values
+			.iter()
+			.map(|value| {
+				if value.is_null() {
+					Ok(String::new())
+				} else {
+					Ok(CStr::from_ptr(*value).to_str()?.to_owned())
+				}
+			})
+			.collect::<anyhow::Result<Vec<_>>>()?;
+		let table = &mut (*predict_table).0;
+		check_predict_table_column_len(table, len)?;
+		table.columns_mut().push(modelfox_table::TableColumn::Text(
+			modelfox_table::TextTableColumn::new(Some(column_name), values),
+		));
+		Ok(())
+	})
+}
+
+fn check_predict_table_column_len(table: &modelfox_table::Table, len: usize) -> anyhow::Result<()> {
+	if table.ncols() > 0 && table.nrows() != len {
+		anyhow::bail!(
+			"expected a column with {} values but got {}",
+			table.nrows(),
+			len
+		);
+	}
+	Ok(())
+}
+
 /// A `modelfox_predict_options` value is an opaque handle to predict options to be passed to `modelfox_model_predict`.
 pub struct modelfox_predict_options(modelfox_core::predict::PredictOptions);
 
@@ -242,13 +333,13 @@ pub unsafe extern "C" fn modelfox_predict_options_delete(
 	drop(Box::from_raw(predict_options));
 }
 
-/// Set the classification threshold.
+/// Set the classification threshold. If this is not called, the threshold saved in the model is used.
 #[no_mangle]
 pub unsafe extern "C" fn modelfox_predict_options_set_threshold(
 	predict_options: *mut modelfox_predict_options,
 	threshold: c_float,
 ) {
-	(*predict_options).0.threshold = threshold;
+	(*predict_options).0.threshold = Some(threshold);
 }
 
 /// Enable or disable computing feature contributions.
@@ -279,6 +370,9 @@ pub struct modelfox_multiclass_classification_predict_output(
 	modelfox_core::predict::MulticlassClassificationPredictOutput,
 );
 
+/// A `modelfox_ranking_predict_output` is an opaque handle to a ranking predict output returned by `modelfox_model_predict`.
+pub struct modelfox_ranking_predict_output(modelfox_core::predict::RankingPredictOutput);
+
 /// `modelfox_feature_contributions` is an opaque handle to the feature contributions returned from `modelfox_model_predict`.
 pub struct modelfox_feature_contributions(modelfox_core::predict::FeatureContributions);
 
@@ -315,6 +409,31 @@ pub struct modelfox_word_embedding_feature_contribution(
 	modelfox_core::predict::WordEmbeddingFeatureContribution,
 );
 
+/// `modelfox_hashed_bag_of_words_feature_contribution` is an opaque handle to a single modelfox hashed bag of words feature contribution.
+pub struct modelfox_hashed_bag_of_words_feature_contribution(
+	modelfox_core::predict::HashedBagOfWordsFeatureContribution,
+);
+
+/// `modelfox_hashed_enum_feature_contribution` is an opaque handle to a single modelfox hashed enum feature contribution.
+pub struct modelfox_hashed_enum_feature_contribution(
+	modelfox_core::predict::HashedEnumFeatureContribution,
+);
+
+/// `modelfox_interaction_feature_contribution` is an opaque handle to a single modelfox interaction feature contribution.
+pub struct modelfox_interaction_feature_contribution(
+	modelfox_core::predict::InteractionFeatureContribution,
+);
+
+/// `modelfox_polynomial_feature_contribution` is an opaque handle to a single modelfox polynomial feature contribution.
+pub struct modelfox_polynomial_feature_contribution(
+	modelfox_core::predict::PolynomialFeatureContribution,
+);
+
+/// `modelfox_binned_number_feature_contribution` is an opaque handle to a single modelfox binned number feature contribution.
+pub struct modelfox_binned_number_feature_contribution(
+	modelfox_core::predict::BinnedNumberFeatureContribution,
+);
+
 /// `modelfox_ngram` is an opaque handle to an ngram.
 pub struct modelfox_ngram(modelfox_core::predict::NGram);
 
@@ -323,6 +442,7 @@ pub struct modelfox_ngram(modelfox_core::predict::NGram);
 pub enum modelfox_ngram_type {
 	UNIGRAM,
 	BIGRAM,
+	CHARACTER,
 }
 
 /// `modelfox_unigram` is an opaque handle to unigram ngram.
@@ -350,6 +470,25 @@ pub unsafe extern "C" fn modelfox_model_predict(
 	})
 }
 
+/// Make a prediction for each row of `table`. This is much faster than `modelfox_model_predict` for large batches because no map is built for each row. `model` should point to a model loaded with `modelfox_model_load`, `table` should be a `modelfox_predict_table` value, and `options` should be a `modelfox_predict_options` value. On success, a pointer to a `modelfox_predict_output_vec` will be written to `output_ptr`. You must call `modelfox_predict_output_vec_delete` when you are done with it.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_model_predict_table(
+	model: *const modelfox_model,
+	table: *const modelfox_predict_table,
+	options: *const modelfox_predict_options,
+	output_ptr: *mut *const modelfox_predict_output_vec,
+) -> *mut modelfox_error {
+	handle_error(|| {
+		let output = modelfox_core::predict::predict_table(
+			&(*model).0,
+			table.as_ref().unwrap().0.view(),
+			&options.as_ref().unwrap().0,
+		);
+		*output_ptr = Box::into_raw(Box::new(modelfox_predict_output_vec(output)));
+		Ok(())
+	})
+}
+
 /// Delete a predict output.
 #[no_mangle]
 pub unsafe extern "C" fn modelfox_predict_output_delete(
@@ -400,6 +539,7 @@ pub unsafe extern "C" fn modelfox_predict_output_as_regression(
 		}
 		modelfox_core::predict::PredictOutput::BinaryClassification(_) => null(),
 		modelfox_core::predict::PredictOutput::MulticlassClassification(_) => null(),
+		modelfox_core::predict::PredictOutput::Ranking(_) => null(),
 	};
 }
 
@@ -416,6 +556,7 @@ pub unsafe extern "C" fn modelfox_predict_output_as_binary_classification(
 				as *const modelfox_binary_classification_predict_output
 		}
 		modelfox_core::predict::PredictOutput::MulticlassClassification(_) => null(),
+		modelfox_core::predict::PredictOutput::Ranking(_) => null(),
 	};
 }
 
@@ -432,6 +573,24 @@ pub unsafe extern "C" fn modelfox_predict_output_as_multiclass_classification(
 			p as *const modelfox_core::predict::MulticlassClassificationPredictOutput
 				as *const modelfox_multiclass_classification_predict_output
 		}
+		modelfox_core::predict::PredictOutput::Ranking(_) => null(),
+	};
+}
+
+/// Cast the predict output as `modelfox_ranking_predict_output`. If this predict output is not for ranking, null will be written to `ranking_predict_output_ptr`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_predict_output_as_ranking(
+	predict_output: *const modelfox_predict_output,
+	predict_output_ptr: *mut *const modelfox_ranking_predict_output,
+) {
+	*predict_output_ptr = match &(*predict_output).0 {
+		modelfox_core::predict::PredictOutput::Regression(_) => null(),
+		modelfox_core::predict::PredictOutput::BinaryClassification(_) => null(),
+		modelfox_core::predict::PredictOutput::MulticlassClassification(_) => null(),
+		modelfox_core::predict::PredictOutput::Ranking(p) => {
+			p as *const modelfox_core::predict::RankingPredictOutput
+				as *const modelfox_ranking_predict_output
+		}
 	};
 }
 
@@ -462,6 +621,33 @@ pub unsafe extern "C" fn modelfox_regression_predict_output_get_feature_contribu
 	};
 }
 
+/// Retrieve the score from a ranking predict output.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_ranking_predict_output_get_score(
+	predict_output: *const modelfox_ranking_predict_output,
+	output_score: *mut c_float,
+) {
+	*output_score = (*predict_output).0.score;
+}
+
+/// Retrieve the feature contributions from a ranking predict output. If feature contributions were not computed for this prediction, null will be written to `feature_contributions_ptr`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_ranking_predict_output_get_feature_contributions(
+	predict_output: *const modelfox_ranking_predict_output,
+	feature_contributions_ptr: *mut *const modelfox_feature_contributions,
+) {
+	match &(*predict_output).0.feature_contributions {
+		Some(feature_contributions) => {
+			*feature_contributions_ptr = feature_contributions
+				as *const modelfox_core::predict::FeatureContributions
+				as *const modelfox_feature_contributions
+		}
+		None => {
+			*feature_contributions_ptr = null();
+		}
+	};
+}
+
 /// Retrieve the class name from a binary classification predict output.
 #[no_mangle]
 pub unsafe extern "C" fn modelfox_binary_classification_predict_output_get_class_name(
@@ -674,6 +860,11 @@ pub enum modelfox_feature_contribution_entry_type {
 	BAG_OF_WORDS,
 	BAG_OF_WORDS_COSINE_SIMILARITY,
 	WORD_EMBEDDING,
+	HASHED_BAG_OF_WORDS,
+	HASHED_ENUM,
+	INTERACTION,
+	POLYNOMIAL,
+	BINNED_NUMBER,
 }
 
 /// Retrieve the type of the feature contribution entry.
@@ -701,6 +892,21 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_get_type(
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => {
 			modelfox_feature_contribution_entry_type::WORD_EMBEDDING
 		}
+		modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(_) => {
+			modelfox_feature_contribution_entry_type::HASHED_BAG_OF_WORDS
+		}
+		modelfox_core::predict::FeatureContributionEntry::HashedEnum(_) => {
+			modelfox_feature_contribution_entry_type::HASHED_ENUM
+		}
+		modelfox_core::predict::FeatureContributionEntry::Interaction(_) => {
+			modelfox_feature_contribution_entry_type::INTERACTION
+		}
+		modelfox_core::predict::FeatureContributionEntry::Polynomial(_) => {
+			modelfox_feature_contribution_entry_type::POLYNOMIAL
+		}
+		modelfox_core::predict::FeatureContributionEntry::BinnedNumber(_) => {
+			modelfox_feature_contribution_entry_type::BINNED_NUMBER
+		}
 	}
 }
 
@@ -720,6 +926,11 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_identity(
 		modelfox_core::predict::FeatureContributionEntry::BagOfWords(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::BagOfWordsCosineSimilarity(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::HashedEnum(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Interaction(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Polynomial(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::BinnedNumber(_) => null(),
 	};
 }
 
@@ -739,6 +950,11 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_normalized(
 		modelfox_core::predict::FeatureContributionEntry::BagOfWords(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::BagOfWordsCosineSimilarity(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::HashedEnum(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Interaction(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Polynomial(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::BinnedNumber(_) => null(),
 	};
 }
 
@@ -758,6 +974,11 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_one_hot_encoded(
 		modelfox_core::predict::FeatureContributionEntry::BagOfWords(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::BagOfWordsCosineSimilarity(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::HashedEnum(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Interaction(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Polynomial(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::BinnedNumber(_) => null(),
 	};
 }
 
@@ -777,6 +998,11 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_bag_of_words(
 		}
 		modelfox_core::predict::FeatureContributionEntry::BagOfWordsCosineSimilarity(_) => null(),
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::HashedEnum(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Interaction(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Polynomial(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::BinnedNumber(_) => null(),
 	};
 }
 
@@ -796,6 +1022,11 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_bag_of_words_cos
 				as *const modelfox_bag_of_words_cosine_similarity_feature_contribution
 		}
 		modelfox_core::predict::FeatureContributionEntry::WordEmbedding(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::HashedEnum(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Interaction(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Polynomial(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::BinnedNumber(_) => null(),
 	};
 }
 
@@ -815,6 +1046,86 @@ pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_word_embedding(
 			f as *const modelfox_core::predict::WordEmbeddingFeatureContribution
 				as *const modelfox_word_embedding_feature_contribution
 		}
+		modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::HashedEnum(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Interaction(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::Polynomial(_) => null(),
+		modelfox_core::predict::FeatureContributionEntry::BinnedNumber(_) => null(),
+	};
+}
+
+/// Cast the feature contribution entry as `modelfox_hashed_bag_of_words_feature_contribution`. If this feature contribution is not a hashed bag of words feature contribution, null will be written to `feature_contribution_ouput_ptr`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_hashed_bag_of_words(
+	feature_contribution_entry: *const modelfox_feature_contribution_entry,
+	feature_contribution_ptr: *mut *const modelfox_hashed_bag_of_words_feature_contribution,
+) {
+	*feature_contribution_ptr = match &(*feature_contribution_entry).0 {
+		modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(f) => {
+			f as *const modelfox_core::predict::HashedBagOfWordsFeatureContribution
+				as *const modelfox_hashed_bag_of_words_feature_contribution
+		}
+		_ => null(),
+	};
+}
+
+/// Cast the feature contribution entry as `modelfox_hashed_enum_feature_contribution`. If this feature contribution is not a hashed enum feature contribution, null will be written to `feature_contribution_ouput_ptr`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_hashed_enum(
+	feature_contribution_entry: *const modelfox_feature_contribution_entry,
+	feature_contribution_ptr: *mut *const modelfox_hashed_enum_feature_contribution,
+) {
+	*feature_contribution_ptr = match &(*feature_contribution_entry).0 {
+		modelfox_core::predict::FeatureContributionEntry::HashedEnum(f) => {
+			f as *const modelfox_core::predict::HashedEnumFeatureContribution
+				as *const modelfox_hashed_enum_feature_contribution
+		}
+		_ => null(),
+	};
+}
+
+/// Cast the feature contribution entry as `modelfox_interaction_feature_contribution`. If this feature contribution is not an interaction feature contribution, null will be written to `feature_contribution_ouput_ptr`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_interaction(
+	feature_contribution_entry: *const modelfox_feature_contribution_entry,
+	feature_contribution_ptr: *mut *const modelfox_interaction_feature_contribution,
+) {
+	*feature_contribution_ptr = match &(*feature_contribution_entry).0 {
+		modelfox_core::predict::FeatureContributionEntry::Interaction(f) => {
+			f as *const modelfox_core::predict::InteractionFeatureContribution
+				as *const modelfox_interaction_feature_contribution
+		}
+		_ => null(),
+	};
+}
+
+/// Cast the feature contribution entry as `modelfox_polynomial_feature_contribution`. If this feature contribution is not a polynomial feature contribution, null will be written to `feature_contribution_ouput_ptr`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_polynomial(
+	feature_contribution_entry: *const modelfox_feature_contribution_entry,
+	feature_contribution_ptr: *mut *const modelfox_polynomial_feature_contribution,
+) {
+	*feature_contribution_ptr = match &(*feature_contribution_entry).0 {
+		modelfox_core::predict::FeatureContributionEntry::Polynomial(f) => {
+			f as *const modelfox_core::predict::PolynomialFeatureContribution
+				as *const modelfox_polynomial_feature_contribution
+		}
+		_ => null(),
+	};
+}
+
+/// Cast the feature contribution entry as `modelfox_binned_number_feature_contribution`. If this feature contribution is not a binned number feature contribution, null will be written to `feature_contribution_ouput_ptr`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_feature_contribution_entry_as_binned_number(
+	feature_contribution_entry: *const modelfox_feature_contribution_entry,
+	feature_contribution_ptr: *mut *const modelfox_binned_number_feature_contribution,
+) {
+	*feature_contribution_ptr = match &(*feature_contribution_entry).0 {
+		modelfox_core::predict::FeatureContributionEntry::BinnedNumber(f) => {
+			f as *const modelfox_core::predict::BinnedNumberFeatureContribution
+				as *const modelfox_binned_number_feature_contribution
+		}
+		_ => null(),
 	};
 }
 
@@ -939,6 +1250,7 @@ pub unsafe extern "C" fn modelfox_ngram_get_type(
 	*ngram_type = match (*ngram).0 {
 		modelfox_core::predict::NGram::Unigram(_) => modelfox_ngram_type::UNIGRAM,
 		modelfox_core::predict::NGram::Bigram(_, _) => modelfox_ngram_type::BIGRAM,
+		modelfox_core::predict::NGram::Character(_) => modelfox_ngram_type::CHARACTER,
 	}
 }
 
@@ -951,6 +1263,7 @@ pub unsafe extern "C" fn modelfox_unigram_get_token(
 	*token_ptr = match &(*ngram).0 {
 		modelfox_core::predict::NGram::Unigram(token) => token.as_str().into(),
 		modelfox_core::predict::NGram::Bigram(_, _) => modelfox_string_view::null(),
+		modelfox_core::predict::NGram::Character(_) => modelfox_string_view::null(),
 	};
 }
 
@@ -963,6 +1276,7 @@ pub unsafe extern "C" fn modelfox_bigram_get_token_a(
 	*token_ptr = match &(*ngram).0 {
 		modelfox_core::predict::NGram::Unigram(_) => modelfox_string_view::null(),
 		modelfox_core::predict::NGram::Bigram(token_a, _) => token_a.as_str().into(),
+		modelfox_core::predict::NGram::Character(_) => modelfox_string_view::null(),
 	};
 }
 
@@ -975,6 +1289,19 @@ pub unsafe extern "C" fn modelfox_bigram_get_token_b(
 	*token_ptr = match &(*ngram).0 {
 		modelfox_core::predict::NGram::Unigram(_) => modelfox_string_view::null(),
 		modelfox_core::predict::NGram::Bigram(_, token_b) => token_b.as_str().into(),
+		modelfox_core::predict::NGram::Character(_) => modelfox_string_view::null(),
+	};
+}
+
+/// Retrieve the characters of a character ngram.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_character_ngram_get_characters(
+	ngram: *const modelfox_ngram,
+	characters_ptr: *mut modelfox_string_view,
+) {
+	*characters_ptr = match &(*ngram).0 {
+		modelfox_core::predict::NGram::Character(characters) => characters.as_str().into(),
+		_ => modelfox_string_view::null(),
 	};
 }
 
@@ -1059,6 +1386,268 @@ pub unsafe extern "C" fn modelfox_word_embedding_feature_contribution_get_featur
 	*feature_contribution_value = (*feature_contribution).0.feature_contribution_value;
 }
 
+/// Retrieve the column name.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_bag_of_words_feature_contribution_get_column_name(
+	feature_contribution: *const modelfox_hashed_bag_of_words_feature_contribution,
+	column_name_ptr: *mut modelfox_string_view,
+) {
+	*column_name_ptr = (*feature_contribution).0.column_name.as_str().into();
+}
+
+/// Retrieve the bucket.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_bag_of_words_feature_contribution_get_bucket(
+	feature_contribution: *const modelfox_hashed_bag_of_words_feature_contribution,
+	bucket_ptr: *mut size_t,
+) {
+	*bucket_ptr = (*feature_contribution).0.bucket;
+}
+
+/// Retrieve the number of example ngrams for the bucket.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_bag_of_words_feature_contribution_get_ngrams_len(
+	feature_contribution: *const modelfox_hashed_bag_of_words_feature_contribution,
+	len_ptr: *mut size_t,
+) {
+	*len_ptr = (*feature_contribution).0.ngrams.len();
+}
+
+/// Retrieve the example ngram at `index`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_bag_of_words_feature_contribution_get_ngram_at_index(
+	feature_contribution: *const modelfox_hashed_bag_of_words_feature_contribution,
+	index: size_t,
+	ngram_ptr: *mut *const modelfox_ngram,
+) {
+	*ngram_ptr = (*feature_contribution)
+		.0
+		.ngrams
+		.get(index)
+		.map(|ngram| ngram as *const modelfox_core::predict::NGram as *const modelfox_ngram)
+		.unwrap_or_else(null);
+}
+
+/// Retrieve the feature value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_bag_of_words_feature_contribution_get_feature_value(
+	feature_contribution: *const modelfox_hashed_bag_of_words_feature_contribution,
+	feature_value: *mut c_float,
+) {
+	*feature_value = (*feature_contribution).0.feature_value;
+}
+
+/// Retrieve the feature contribution value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_bag_of_words_feature_contribution_get_feature_contribution_value(
+	feature_contribution: *const modelfox_hashed_bag_of_words_feature_contribution,
+	feature_contribution_value: *mut c_float,
+) {
+	*feature_contribution_value = (*feature_contribution).0.feature_contribution_value;
+}
+
+/// Retrieve the column name.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_enum_feature_contribution_get_column_name(
+	feature_contribution: *const modelfox_hashed_enum_feature_contribution,
+	column_name_ptr: *mut modelfox_string_view,
+) {
+	*column_name_ptr = (*feature_contribution).0.column_name.as_str().into();
+}
+
+/// Retrieve the bucket.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_enum_feature_contribution_get_bucket(
+	feature_contribution: *const modelfox_hashed_enum_feature_contribution,
+	bucket_ptr: *mut size_t,
+) {
+	*bucket_ptr = (*feature_contribution).0.bucket;
+}
+
+/// Retrieve the number of example values for the bucket.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_enum_feature_contribution_get_values_len(
+	feature_contribution: *const modelfox_hashed_enum_feature_contribution,
+	len_ptr: *mut size_t,
+) {
+	*len_ptr = (*feature_contribution).0.values.len();
+}
+
+/// Retrieve the example value at `index`.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_enum_feature_contribution_get_value_at_index(
+	feature_contribution: *const modelfox_hashed_enum_feature_contribution,
+	index: size_t,
+	value_ptr: *mut modelfox_string_view,
+) {
+	*value_ptr = match (*feature_contribution).0.values.get(index) {
+		Some(value) => value.as_str().into(),
+		None => modelfox_string_view::null(),
+	};
+}
+
+/// Retrieve the feature value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_enum_feature_contribution_get_feature_value(
+	feature_contribution: *const modelfox_hashed_enum_feature_contribution,
+	feature_value: *mut c_float,
+) {
+	*feature_value = (*feature_contribution).0.feature_value;
+}
+
+/// Retrieve the feature contribution value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_hashed_enum_feature_contribution_get_feature_contribution_value(
+	feature_contribution: *const modelfox_hashed_enum_feature_contribution,
+	feature_contribution_value: *mut c_float,
+) {
+	*feature_contribution_value = (*feature_contribution).0.feature_contribution_value;
+}
+
+/// Retrieve the name of the first column.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_interaction_feature_contribution_get_column_name_a(
+	feature_contribution: *const modelfox_interaction_feature_contribution,
+	column_name_ptr: *mut modelfox_string_view,
+) {
+	*column_name_ptr = (*feature_contribution).0.column_name_a.as_str().into();
+}
+
+/// Retrieve the variant of the first column. If the first column is not an enum column or the feature is for invalid values, a null string view will be written.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_interaction_feature_contribution_get_variant_a(
+	feature_contribution: *const modelfox_interaction_feature_contribution,
+	variant_ptr: *mut modelfox_string_view,
+) {
+	*variant_ptr = match &(*feature_contribution).0.variant_a {
+		Some(variant) => variant.as_str().into(),
+		None => modelfox_string_view::null(),
+	};
+}
+
+/// Retrieve the name of the second column.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_interaction_feature_contribution_get_column_name_b(
+	feature_contribution: *const modelfox_interaction_feature_contribution,
+	column_name_ptr: *mut modelfox_string_view,
+) {
+	*column_name_ptr = (*feature_contribution).0.column_name_b.as_str().into();
+}
+
+/// Retrieve the variant of the second column. If the second column is not an enum column or the feature is for invalid values, a null string view will be written.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_interaction_feature_contribution_get_variant_b(
+	feature_contribution: *const modelfox_interaction_feature_contribution,
+	variant_ptr: *mut modelfox_string_view,
+) {
+	*variant_ptr = match &(*feature_contribution).0.variant_b {
+		Some(variant) => variant.as_str().into(),
+		None => modelfox_string_view::null(),
+	};
+}
+
+/// Retrieve the feature value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_interaction_feature_contribution_get_feature_value(
+	feature_contribution: *const modelfox_interaction_feature_contribution,
+	feature_value: *mut c_float,
+) {
+	*feature_value = (*feature_contribution).0.feature_value;
+}
+
+/// Retrieve the feature contribution value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_interaction_feature_contribution_get_feature_contribution_value(
+	feature_contribution: *const modelfox_interaction_feature_contribution,
+	feature_contribution_value: *mut c_float,
+) {
+	*feature_contribution_value = (*feature_contribution).0.feature_contribution_value;
+}
+
+/// Retrieve the column name.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_polynomial_feature_contribution_get_column_name(
+	feature_contribution: *const modelfox_polynomial_feature_contribution,
+	column_name_ptr: *mut modelfox_string_view,
+) {
+	*column_name_ptr = (*feature_contribution).0.column_name.as_str().into();
+}
+
+/// Retrieve the power.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_polynomial_feature_contribution_get_power(
+	feature_contribution: *const modelfox_polynomial_feature_contribution,
+	power_ptr: *mut size_t,
+) {
+	*power_ptr = (*feature_contribution).0.power;
+}
+
+/// Retrieve the feature value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_polynomial_feature_contribution_get_feature_value(
+	feature_contribution: *const modelfox_polynomial_feature_contribution,
+	feature_value: *mut c_float,
+) {
+	*feature_value = (*feature_contribution).0.feature_value;
+}
+
+/// Retrieve the feature contribution value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_polynomial_feature_contribution_get_feature_contribution_value(
+	feature_contribution: *const modelfox_polynomial_feature_contribution,
+	feature_contribution_value: *mut c_float,
+) {
+	*feature_contribution_value = (*feature_contribution).0.feature_contribution_value;
+}
+
+/// Retrieve the column name.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_binned_number_feature_contribution_get_column_name(
+	feature_contribution: *const modelfox_binned_number_feature_contribution,
+	column_name_ptr: *mut modelfox_string_view,
+) {
+	*column_name_ptr = (*feature_contribution).0.column_name.as_str().into();
+}
+
+/// Retrieve the lower bound of the bin. The first bin has no lower bound, so negative infinity will be written.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_binned_number_feature_contribution_get_bin_start(
+	feature_contribution: *const modelfox_binned_number_feature_contribution,
+	bin_start_ptr: *mut c_float,
+) {
+	*bin_start_ptr = (*feature_contribution)
+		.0
+		.bin_start
+		.unwrap_or(f32::NEG_INFINITY);
+}
+
+/// Retrieve the upper bound of the bin. The last bin has no upper bound, so infinity will be written.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_binned_number_feature_contribution_get_bin_end(
+	feature_contribution: *const modelfox_binned_number_feature_contribution,
+	bin_end_ptr: *mut c_float,
+) {
+	*bin_end_ptr = (*feature_contribution).0.bin_end.unwrap_or(f32::INFINITY);
+}
+
+/// Retrieve the feature value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_binned_number_feature_contribution_get_feature_value(
+	feature_contribution: *const modelfox_binned_number_feature_contribution,
+	feature_value: *mut bool,
+) {
+	*feature_value = (*feature_contribution).0.feature_value;
+}
+
+/// Retrieve the feature contribution value.
+#[no_mangle]
+pub unsafe extern "C" fn modelfox_binned_number_feature_contribution_get_feature_contribution_value(
+	feature_contribution: *const modelfox_binned_number_feature_contribution,
+	feature_contribution_value: *mut c_float,
+) {
+	*feature_contribution_value = (*feature_contribution).0.feature_contribution_value;
+}
+
 /// This function exposes the allocator used by libmodelfox. It is used by the wasm build of libmodelfox because WebAssembly does not include its own allocator.
 #[no_mangle]
 pub unsafe extern "C" fn modelfox_alloc(size: size_t, align: size_t) -> *mut c_void {
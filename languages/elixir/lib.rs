@@ -157,10 +157,10 @@ impl<'a> erl_nif::FromErlNif<'a> for PredictOptions {
 
 impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
 	fn from(value: PredictOptions) -> modelfox_core::predict::PredictOptions {
-		let mut options = modelfox_core::predict::PredictOptions::default();
-		if let Some(threshold) = value.threshold {
-			options.threshold = threshold;
-		}
+		let mut options = modelfox_core::predict::PredictOptions {
+			threshold: value.threshold,
+			..Default::default()
+		};
 		if let Some(compute_feature_contributions) = value.compute_feature_contributions {
 			options.compute_feature_contributions = compute_feature_contributions;
 		}
@@ -187,6 +187,7 @@ enum PredictOutput {
 	Regression(RegressionPredictOutput),
 	BinaryClassification(BinaryClassificationPredictOutput),
 	MulticlassClassification(MulticlassClassificationPredictOutput),
+	Ranking(RankingPredictOutput),
 }
 
 type PredictOutputMultiple = Vec<PredictOutput>;
@@ -203,6 +204,9 @@ impl From<modelfox_core::predict::PredictOutput> for PredictOutput {
 			modelfox_core::predict::PredictOutput::MulticlassClassification(value) => {
 				PredictOutput::MulticlassClassification(value.into())
 			}
+			modelfox_core::predict::PredictOutput::Ranking(value) => {
+				PredictOutput::Ranking(value.into())
+			}
 		}
 	}
 }
@@ -270,6 +274,22 @@ impl From<modelfox_core::predict::MulticlassClassificationPredictOutput>
 	}
 }
 
+#[derive(serde::Serialize)]
+#[serde(rename = "Elixir.ModelFox.RankingPredictOutput")]
+struct RankingPredictOutput {
+	pub score: f32,
+	pub feature_contributions: Option<FeatureContributions>,
+}
+
+impl From<modelfox_core::predict::RankingPredictOutput> for RankingPredictOutput {
+	fn from(value: modelfox_core::predict::RankingPredictOutput) -> Self {
+		RankingPredictOutput {
+			score: value.score,
+			feature_contributions: value.feature_contributions.map(Into::into),
+		}
+	}
+}
+
 #[derive(serde::Serialize)]
 #[serde(rename = "Elixir.ModelFox.FeatureContributions")]
 struct FeatureContributions {
@@ -302,6 +322,16 @@ enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "hashed_bag_of_words")]
+	HashedBagOfWords(HashedBagOfWordsFeatureContribution),
+	#[serde(rename = "hashed_enum")]
+	HashedEnum(HashedEnumFeatureContribution),
+	#[serde(rename = "interaction")]
+	Interaction(InteractionFeatureContribution),
+	#[serde(rename = "polynomial")]
+	Polynomial(PolynomialFeatureContribution),
+	#[serde(rename = "binned_number")]
+	BinnedNumber(BinnedNumberFeatureContribution),
 }
 
 impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContributionEntry {
@@ -325,6 +355,21 @@ impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContribut
 			modelfox_core::predict::FeatureContributionEntry::WordEmbedding(value) => {
 				FeatureContributionEntry::WordEmbedding(value.into())
 			}
+			modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(value) => {
+				FeatureContributionEntry::HashedBagOfWords(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::HashedEnum(value) => {
+				FeatureContributionEntry::HashedEnum(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::Interaction(value) => {
+				FeatureContributionEntry::Interaction(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::Polynomial(value) => {
+				FeatureContributionEntry::Polynomial(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::BinnedNumber(value) => {
+				FeatureContributionEntry::BinnedNumber(value.into())
+			}
 		}
 	}
 }
@@ -410,6 +455,7 @@ impl From<modelfox_core::predict::BagOfWordsFeatureContribution> for BagOfWordsF
 enum NGram {
 	Unigram(String),
 	Bigram(String, String),
+	Character { character: String },
 }
 
 impl From<modelfox_core::predict::NGram> for NGram {
@@ -419,6 +465,7 @@ impl From<modelfox_core::predict::NGram> for NGram {
 			modelfox_core::predict::NGram::Bigram(token_a, token_b) => {
 				NGram::Bigram(token_a, token_b)
 			}
+			modelfox_core::predict::NGram::Character(character) => NGram::Character { character },
 		}
 	}
 }
@@ -464,3 +511,119 @@ impl From<modelfox_core::predict::WordEmbeddingFeatureContribution>
 		}
 	}
 }
+
+#[derive(serde::Serialize)]
+#[serde(rename = "Elixir.ModelFox.HashedBagOfWordsFeatureContribution")]
+struct HashedBagOfWordsFeatureContribution {
+	column_name: String,
+	bucket: usize,
+	ngrams: Vec<NGram>,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::HashedBagOfWordsFeatureContribution>
+	for HashedBagOfWordsFeatureContribution
+{
+	fn from(value: modelfox_core::predict::HashedBagOfWordsFeatureContribution) -> Self {
+		HashedBagOfWordsFeatureContribution {
+			column_name: value.column_name,
+			bucket: value.bucket,
+			ngrams: value.ngrams.into_iter().map(Into::into).collect(),
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename = "Elixir.ModelFox.HashedEnumFeatureContribution")]
+struct HashedEnumFeatureContribution {
+	column_name: String,
+	bucket: usize,
+	values: Vec<String>,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::HashedEnumFeatureContribution> for HashedEnumFeatureContribution {
+	fn from(value: modelfox_core::predict::HashedEnumFeatureContribution) -> Self {
+		HashedEnumFeatureContribution {
+			column_name: value.column_name,
+			bucket: value.bucket,
+			values: value.values,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename = "Elixir.ModelFox.InteractionFeatureContribution")]
+struct InteractionFeatureContribution {
+	column_name_a: String,
+	variant_a: Option<String>,
+	column_name_b: String,
+	variant_b: Option<String>,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::InteractionFeatureContribution>
+	for InteractionFeatureContribution
+{
+	fn from(value: modelfox_core::predict::InteractionFeatureContribution) -> Self {
+		InteractionFeatureContribution {
+			column_name_a: value.column_name_a,
+			variant_a: value.variant_a,
+			column_name_b: value.column_name_b,
+			variant_b: value.variant_b,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename = "Elixir.ModelFox.PolynomialFeatureContribution")]
+struct PolynomialFeatureContribution {
+	column_name: String,
+	power: usize,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::PolynomialFeatureContribution> for PolynomialFeatureContribution {
+	fn from(value: modelfox_core::predict::PolynomialFeatureContribution) -> Self {
+		PolynomialFeatureContribution {
+			column_name: value.column_name,
+			power: value.power,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename = "Elixir.ModelFox.BinnedNumberFeatureContribution")]
+struct BinnedNumberFeatureContribution {
+	column_name: String,
+	bin_start: Option<f32>,
+	bin_end: Option<f32>,
+	feature_value: bool,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::BinnedNumberFeatureContribution>
+	for BinnedNumberFeatureContribution
+{
+	fn from(value: modelfox_core::predict::BinnedNumberFeatureContribution) -> Self {
+		BinnedNumberFeatureContribution {
+			column_name: value.column_name,
+			bin_start: value.bin_start,
+			bin_end: value.bin_end,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
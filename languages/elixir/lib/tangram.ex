@@ -44,18 +44,18 @@ defmodule ModelFox do
     These are the options passed to `ModelFox.predict`.
 
     ## `threshold`
-    If your model is a binary classifier, use this field to make predictions using the threshold you chose on the tuning page of the app. The default value is `0.5`.
+    If your model is a binary classifier, use this field to make predictions using the threshold you chose on the tuning page of the app. If not specified, the threshold saved in the model is used, which is `0.5` unless you changed it on the tuning page.
 
     ## `compute_feature_contributions`
     Computing feature contributions is disabled by default. If you set this field to `true`, you will be able to access the feature contributions with the `feature_contributions` field of the predict output.
     """
     @type t :: %__MODULE__{
-            threshold: float,
+            threshold: float | nil,
             compute_feature_contributions: boolean
           }
     @derive Jason.Encoder
     defstruct [
-      threshold: 0.5,
+      threshold: nil,
       compute_feature_contributions: false
     ]
   end
@@ -67,6 +67,7 @@ defmodule ModelFox do
           {:regression, RegressionPredictOutput.t()}
           | {:binary_classification, BinaryClassificationPredictOutput.t()}
           | {:multiclass_classification, MulticlassClassificationPredictOutput.t()}
+          | {:ranking, RankingPredictOutput.t()}
 
   defmodule RegressionPredictOutput do
     @moduledoc """
@@ -146,6 +147,27 @@ defmodule ModelFox do
     ]
   end
 
+  defmodule RankingPredictOutput do
+    @moduledoc """
+    `ModelFox.predict` outputs `{:ranking, RankingPredictOutput.t()}` when the model's task is ranking.
+
+    ## `score`
+    This is the predicted score. To rank the examples in a query group, sort them by their scores in descending order. Scores are only comparable within a query group.
+
+    ## `feature_contributions`
+    If computing feature contributions was enabled in the predict options, this value will explain the model's output, showing how much each feature contributed to the output.
+    """
+    @type t :: %__MODULE__{
+            score: float,
+            feature_contributions: FeatureContributions.t() | nil
+          }
+    @derive {Jason.Encoder, except: [:feature_contributions]}
+    defstruct [
+      :score,
+      :feature_contributions
+    ]
+  end
+
   defmodule FeatureContributions do
     @moduledoc """
     This is a description of the feature contributions for the prediction if the task is regression or binary classification, or for a single class if the task is multiclass classification.
@@ -181,6 +203,11 @@ defmodule ModelFox do
           | {:bag_of_words, BagOfWordsFeatureContribution.t()}
           | {:bag_of_words_cosine_similarity, BagOfWordsCosineSimilarityFeatureContribution.t()}
           | {:word_embedding, WordEmbeddingFeatureContribution.t()}
+          | {:hashed_bag_of_words, HashedBagOfWordsFeatureContribution.t()}
+          | {:hashed_enum, HashedEnumFeatureContribution.t()}
+          | {:interaction, InteractionFeatureContribution.t()}
+          | {:polynomial, PolynomialFeatureContribution.t()}
+          | {:binned_number, BinnedNumberFeatureContribution.t()}
 
   defmodule IdentityFeatureContribution do
     @moduledoc """
@@ -350,6 +377,182 @@ defmodule ModelFox do
     ]
   end
 
+  defmodule HashedBagOfWordsFeatureContribution do
+    @moduledoc """
+    This describes the contribution of a feature from a hashed bag of words feature group.
+
+    ## `column_name`
+    This is the name of the source column for the hashed bag of words feature group.
+
+    ## `bucket`
+    This is the index of the bucket that this feature corresponds to.
+
+    ## `ngrams`
+    These are the most frequent ngrams in the training data that hash to this bucket.
+
+    ## `feature_value`
+    This is the value of the feature.
+
+    ## `feature_contribution_value`
+    This is the amount that the feature contributed to the output.
+    """
+    @type ngram :: String.t() | {String.t(), String.t()}
+    @type t :: %__MODULE__{
+            column_name: String.t(),
+            bucket: integer,
+            ngrams: [ngram],
+            feature_value: float,
+            feature_contribution_value: float
+          }
+    defstruct [
+      :column_name,
+      :bucket,
+      :ngrams,
+      :feature_value,
+      :feature_contribution_value
+    ]
+  end
+
+  defmodule HashedEnumFeatureContribution do
+    @moduledoc """
+    This describes the contribution of a feature from a hashed enum feature group.
+
+    ## `column_name`
+    This is the name of the source column for the hashed enum feature group.
+
+    ## `bucket`
+    This is the index of the bucket that this feature corresponds to.
+
+    ## `values`
+    These are the most frequent values in the training data that hash to this bucket.
+
+    ## `feature_value`
+    This is the value of the feature.
+
+    ## `feature_contribution_value`
+    This is the amount that the feature contributed to the output.
+    """
+    @type t :: %__MODULE__{
+            column_name: String.t(),
+            bucket: integer,
+            values: [String.t()],
+            feature_value: float,
+            feature_contribution_value: float
+          }
+    defstruct [
+      :column_name,
+      :bucket,
+      :values,
+      :feature_value,
+      :feature_contribution_value
+    ]
+  end
+
+  defmodule InteractionFeatureContribution do
+    @moduledoc """
+    This describes the contribution of a feature from an interaction feature group.
+
+    ## `column_name_a`
+    This is the name of the first source column for the feature group.
+
+    ## `variant_a`
+    This is the enum variant of the first source column that the feature corresponds to, if it is an enum column.
+
+    ## `column_name_b`
+    This is the name of the second source column for the feature group.
+
+    ## `variant_b`
+    This is the enum variant of the second source column that the feature corresponds to, if it is an enum column.
+
+    ## `feature_value`
+    This is the value of the feature.
+
+    ## `feature_contribution_value`
+    This is the amount that the feature contributed to the output.
+    """
+    @type t :: %__MODULE__{
+            column_name_a: String.t(),
+            variant_a: String.t() | nil,
+            column_name_b: String.t(),
+            variant_b: String.t() | nil,
+            feature_value: float,
+            feature_contribution_value: float
+          }
+    defstruct [
+      :column_name_a,
+      :variant_a,
+      :column_name_b,
+      :variant_b,
+      :feature_value,
+      :feature_contribution_value
+    ]
+  end
+
+  defmodule PolynomialFeatureContribution do
+    @moduledoc """
+    This describes the contribution of a feature from a polynomial feature group.
+
+    ## `column_name`
+    This is the name of the source column for the feature group.
+
+    ## `power`
+    This is the power that the normalized column value is raised to.
+
+    ## `feature_value`
+    This is the value of the feature.
+
+    ## `feature_contribution_value`
+    This is the amount that the feature contributed to the output.
+    """
+    @type t :: %__MODULE__{
+            column_name: String.t(),
+            power: integer,
+            feature_value: float,
+            feature_contribution_value: float
+          }
+    defstruct [
+      :column_name,
+      :power,
+      :feature_value,
+      :feature_contribution_value
+    ]
+  end
+
+  defmodule BinnedNumberFeatureContribution do
+    @moduledoc """
+    This describes the contribution of a feature from a binned number feature group.
+
+    ## `column_name`
+    This is the name of the source column for the feature group.
+
+    ## `bin_start`
+    This is the lower bound of the bin, or `nil` for the first bin.
+
+    ## `bin_end`
+    This is the upper bound of the bin, or `nil` for the last bin.
+
+    ## `feature_value`
+    This is the value of the feature.
+
+    ## `feature_contribution_value`
+    This is the amount that the feature contributed to the output.
+    """
+    @type t :: %__MODULE__{
+            column_name: String.t(),
+            bin_start: float | nil,
+            bin_end: float | nil,
+            feature_value: boolean,
+            feature_contribution_value: float
+          }
+    defstruct [
+      :column_name,
+      :bin_start,
+      :bin_end,
+      :feature_value,
+      :feature_contribution_value
+    ]
+  end
+
   @type true_value :: String.t() | float
 
   defmodule LogPredictionArgs do
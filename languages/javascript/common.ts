@@ -13,6 +13,7 @@ export enum Task {
 	Regression = "regression",
 	BinaryClassification = "binary_classification",
 	MulticlassClassification = "multiclass_classification",
+	Ranking = "ranking",
 }
 
 /**
@@ -32,12 +33,22 @@ export type PredictInput = {
 	[key: string]: string | number | null | undefined
 }
 
+/**
+ * This is the input type of [[`Model.predictTable`]]. It maps the name of each column to its values. Number columns are typed arrays, and other columns are arrays of strings, where `null` and `undefined` are missing values. All columns must have the same length.
+ */
+export type PredictTable = {
+	[key: string]:
+		| Float32Array
+		| Float64Array
+		| Array<string | null | undefined>
+}
+
 /**
  * These are the options passed to [[`Model.predict`]].
  */
 export type PredictOptions = {
 	/**
-	 * If your model is a binary classifier, use this field to make predictions using a threshold chosen on the tuning page of the app. The default value is `0.5`.
+	 * If your model is a binary classifier, use this field to make predictions using a threshold chosen on the tuning page of the app. If not specified, the threshold saved in the model is used, which is `0.5` unless you changed it on the tuning page.
 	 */
 	threshold?: number
 	/**
@@ -53,6 +64,7 @@ export type PredictOutput<TaskType extends Task> = {
 	[Task.Regression]: RegressionPredictOutput
 	[Task.BinaryClassification]: BinaryClassificationPredictOutput
 	[Task.MulticlassClassification]: MulticlassClassificationPredictOutput
+	[Task.Ranking]: RankingPredictOutput
 }[TaskType]
 
 /**
@@ -112,6 +124,21 @@ export type MulticlassClassificationPredictOutput<Classes = string> = {
 	featureContributions?: { [K in keyof Classes]: FeatureContributions }
 }
 
+/**
+ * This is the output of calling [[`Model.predict`]] on a `Model` whose `Task` is `Task.Ranking`.
+ */
+export type RankingPredictOutput = {
+	type: Task.Ranking
+	/**
+	 * This is the predicted score. To rank the examples in a query group, sort them by their scores in descending order. Scores are only comparable within a query group.
+	 */
+	score: number
+	/**
+	 * If computing feature contributions was enabled in the predict options, this value will explain the model's output, showing how much each feature contributed to the output.
+	 */
+	featureContributions?: FeatureContributions
+}
+
 /**
  * This is a description of the feature contributions for the prediction if the task is regression or binary classification, or for a single class if the task is multiclass classification.
  */
@@ -137,6 +164,11 @@ export type FeatureContributionEntry =
 	| BagOfWordsFeatureContribution
 	| BagOfWordsCosineSimilarityFeatureContribution
 	| WordEmbeddingFeatureContribution
+	| HashedBagOfWordsFeatureContribution
+	| HashedEnumFeatureContribution
+	| InteractionFeatureContribution
+	| PolynomialFeatureContribution
+	| BinnedNumberFeatureContribution
 
 /**
  * This identifies the type of a feature contribution.
@@ -148,6 +180,11 @@ export enum FeatureContributionType {
 	BagOfWords = "bag_of_words",
 	BagOfWordsCosineSimilarity = "bag_of_words_cosine_similarity",
 	WordEmbedding = "word_embedding",
+	HashedBagOfWords = "hashed_bag_of_words",
+	HashedEnum = "hashed_enum",
+	Interaction = "interaction",
+	Polynomial = "polynomial",
+	BinnedNumber = "binned_number",
 }
 
 /**
@@ -281,6 +318,141 @@ export type WordEmbeddingFeatureContribution = {
 	featureContributionValue: number
 }
 
+/**
+ * This describes the contribution of a feature from a hashed bag of words feature group.
+ */
+export type HashedBagOfWordsFeatureContribution = {
+	type: FeatureContributionType.HashedBagOfWords
+	/**
+	 * This is the name of the source column for the feature group.
+	 */
+	columnName: string
+	/**
+	 * This is the index of the bucket that this feature corresponds to.
+	 */
+	bucket: number
+	/**
+	 * These are the most frequent ngrams in the training data that hash to this bucket.
+	 */
+	ngrams: Array<NGram>
+	/**
+	 * This is the value of the feature.
+	 */
+	featureValue: number
+	/**
+	 * This is the amount that the feature contributed to the output.
+	 */
+	featureContributionValue: number
+}
+
+/**
+ * This describes the contribution of a feature from a hashed enum feature group.
+ */
+export type HashedEnumFeatureContribution = {
+	type: FeatureContributionType.HashedEnum
+	/**
+	 * This is the name of the source column for the feature group.
+	 */
+	columnName: string
+	/**
+	 * This is the index of the bucket that this feature corresponds to.
+	 */
+	bucket: number
+	/**
+	 * These are the most frequent values in the training data that hash to this bucket.
+	 */
+	values: Array<string>
+	/**
+	 * This is the value of the feature.
+	 */
+	featureValue: number
+	/**
+	 * This is the amount that the feature contributed to the output.
+	 */
+	featureContributionValue: number
+}
+
+/**
+ * This describes the contribution of a feature from an interaction feature group.
+ */
+export type InteractionFeatureContribution = {
+	type: FeatureContributionType.Interaction
+	/**
+	 * This is the name of the first source column for the feature group.
+	 */
+	columnNameA: string
+	/**
+	 * This is the enum variant of the first source column that the feature corresponds to, if it is an enum column.
+	 */
+	variantA: string | null
+	/**
+	 * This is the name of the second source column for the feature group.
+	 */
+	columnNameB: string
+	/**
+	 * This is the enum variant of the second source column that the feature corresponds to, if it is an enum column.
+	 */
+	variantB: string | null
+	/**
+	 * This is the value of the feature.
+	 */
+	featureValue: number
+	/**
+	 * This is the amount that the feature contributed to the output.
+	 */
+	featureContributionValue: number
+}
+
+/**
+ * This describes the contribution of a feature from a polynomial feature group.
+ */
+export type PolynomialFeatureContribution = {
+	type: FeatureContributionType.Polynomial
+	/**
+	 * This is the name of the source column for the feature group.
+	 */
+	columnName: string
+	/**
+	 * This is the power that the normalized column value is raised to.
+	 */
+	power: number
+	/**
+	 * This is the value of the feature.
+	 */
+	featureValue: number
+	/**
+	 * This is the amount that the feature contributed to the output.
+	 */
+	featureContributionValue: number
+}
+
+/**
+ * This describes the contribution of a feature from a binned number feature group.
+ */
+export type BinnedNumberFeatureContribution = {
+	type: FeatureContributionType.BinnedNumber
+	/**
+	 * This is the name of the source column for the feature group.
+	 */
+	columnName: string
+	/**
+	 * This is the lower bound of the bin, or `null` for the first bin.
+	 */
+	binStart: number | null
+	/**
+	 * This is the upper bound of the bin, or `null` for the last bin.
+	 */
+	binEnd: number | null
+	/**
+	 * This is the value of the feature.
+	 */
+	featureValue: boolean
+	/**
+	 * This is the amount that the feature contributed to the output.
+	 */
+	featureContributionValue: number
+}
+
 /**
  * This is the type of the argument to [[`Model.logPrediction`]] and [[`Model.enqueueLogPrediction`]] which specifies the details of the prediction to log.
  */
@@ -398,6 +570,45 @@ export class Model<
 		return native.predict(this.model, input, options)
 	}
 
+	/**
+	 * Make predictions for a batch of rows stored column by column. This is much faster than [[`Model.predict`]] for large batches because the number columns are passed to the model without converting each value.
+	 * @param table The columns to make predictions for.
+	 * @param options An optional [[`PredictOptions`]] value to set options for the prediction.
+	 * @returns An array of [[`PredictOutput`]]s, one for each row.
+	 */
+	public predictTable(
+		table: PredictTable,
+		options?: PredictOptions,
+	): OutputType[] {
+		let numberColumnNames: string[] = []
+		let numberColumns: Array<Float32Array | Float64Array> = []
+		let stringColumns: Array<{ name: string; values: Array<string | null> }> =
+			[]
+		for (let [name, values] of Object.entries(table)) {
+			if (values instanceof Float32Array || values instanceof Float64Array) {
+				numberColumnNames.push(name)
+				numberColumns.push(values)
+			} else {
+				stringColumns.push({ name, values: values.map(value => value ?? null) })
+			}
+		}
+		// Copy the number columns one after another into a single buffer of 32 bit floats.
+		let nRows = numberColumns[0]?.length ?? 0
+		let numberData = new Float32Array(nRows * numberColumns.length)
+		numberColumns.forEach((values, index) => {
+			if (values.length !== nRows) {
+				throw new Error("All columns must have the same length.")
+			}
+			numberData.set(values, index * nRows)
+		})
+		return native.predictTable(
+			this.model,
+			{ numberColumnNames, stringColumns },
+			numberData.buffer,
+			options,
+		)
+	}
+
 	/**
 	 * Send a prediction event to the app. If you want to batch events, you can use [[`Model.enqueueLogTrueValue`]] instead.
 	 * @param args The arguments to use to produce the prediction event.
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use memmap::Mmap;
 use std::collections::BTreeMap;
 
@@ -22,6 +22,9 @@ fn init<'a>(env: node_api::Env<'a>, exports: node_api::Value<'a>) -> Result<node
 	let predict_key = node_api::String::new(env, "predict")?;
 	let predict_value = node_api::Function::new(env, "predict", predict)?;
 	exports.set(predict_key, predict_value)?;
+	let key = node_api::String::new(env, "predictTable")?;
+	let value = node_api::Function::new(env, "predictTable", predict_table)?;
+	exports.set(key, value)?;
 	Ok(exports.value())
 }
 
@@ -87,6 +90,90 @@ fn predict<'a>(
 	}
 }
 
+#[node_api::function]
+fn predict_table<'a>(
+	env: node_api::Env<'a>,
+	model: node_api::External<'a, modelfox_core::predict::Model>,
+	columns: PredictTableColumns,
+	number_data: node_api::ArrayBuffer<'a>,
+	options: Option<PredictOptions>,
+) -> Result<PredictOutputSingleOrMultiple> {
+	let model = model.get()?;
+	let options = options.map(Into::into).unwrap_or_default();
+	let table = columns.into_table(number_data.get()?)?;
+	let output = modelfox_core::predict::predict_table(model, table.view(), &options);
+	let output = output.into_iter().map(Into::into).collect();
+	Ok(PredictOutputSingleOrMultiple::Multiple(output))
+}
+
+/// These are the columns passed to `predictTable`. The values of the number columns are passed separately in a single buffer of 32 bit floats, one column after another.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PredictTableColumns {
+	number_column_names: Vec<String>,
+	string_columns: Vec<PredictTableStringColumn>,
+}
+
+#[derive(serde::Deserialize)]
+struct PredictTableStringColumn {
+	name: String,
+	values: Vec<Option<String>>,
+}
+
+impl<'a> node_api::FromNodeAPI<'a> for PredictTableColumns {
+	fn from_node_api(value: node_api::Value<'a>) -> node_api::Result<Self> {
+		serde::Deserialize::deserialize(value)
+	}
+}
+
+impl PredictTableColumns {
+	fn into_table(self, number_data: &[u8]) -> Result<modelfox_table::Table> {
+		let number_data = number_data
+			.chunks_exact(4)
+			.map(|bytes| f32::from_ne_bytes(bytes.try_into().unwrap()))
+			.collect::<Vec<_>>();
+		let n_rows = if self.number_column_names.is_empty() {
+			self.string_columns
+				.first()
+				.map(|column| column.values.len())
+				.unwrap_or(0)
+		} else {
+			number_data.len() / self.number_column_names.len()
+		};
+		if number_data.len() != n_rows * self.number_column_names.len()
+			|| self
+				.string_columns
+				.iter()
+				.any(|column| column.values.len() != n_rows)
+		{
+			bail!("All columns must have the same length.");
+		}
+		let mut table = modelfox_table::Table::new(Vec::new(), Vec::new());
+		for (column_name, data) in self
+			.number_column_names
+			.into_iter()
+			.zip(number_data.chunks(n_rows.max(1)))
+		{
+			table
+				.columns_mut()
+				.push(modelfox_table::TableColumn::Number(
+					modelfox_table::NumberTableColumn::new(Some(column_name), data.to_owned()),
+				));
+		}
+		for column in self.string_columns {
+			let data = column
+				.values
+				.into_iter()
+				.map(Option::unwrap_or_default)
+				.collect();
+			table.columns_mut().push(modelfox_table::TableColumn::Text(
+				modelfox_table::TextTableColumn::new(Some(column.name), data),
+			));
+		}
+		Ok(table)
+	}
+}
+
 #[derive(serde::Deserialize)]
 #[serde(untagged)]
 enum PredictInputSingleOrMultiple {
@@ -152,10 +239,10 @@ impl<'a> node_api::FromNodeAPI<'a> for PredictOptions {
 
 impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
 	fn from(value: PredictOptions) -> modelfox_core::predict::PredictOptions {
-		let mut options = modelfox_core::predict::PredictOptions::default();
-		if let Some(threshold) = value.threshold {
-			options.threshold = threshold;
-		}
+		let mut options = modelfox_core::predict::PredictOptions {
+			threshold: value.threshold,
+			..Default::default()
+		};
 		if let Some(compute_feature_contributions) = value.compute_feature_contributions {
 			options.compute_feature_contributions = compute_feature_contributions;
 		}
@@ -185,6 +272,8 @@ enum PredictOutput {
 	BinaryClassification(BinaryClassificationPredictOutput),
 	#[serde(rename = "multiclass_classification")]
 	MulticlassClassification(MulticlassClassificationPredictOutput),
+	#[serde(rename = "ranking")]
+	Ranking(RankingPredictOutput),
 }
 
 type PredictOutputMultiple = Vec<PredictOutput>;
@@ -201,6 +290,9 @@ impl From<modelfox_core::predict::PredictOutput> for PredictOutput {
 			modelfox_core::predict::PredictOutput::MulticlassClassification(value) => {
 				PredictOutput::MulticlassClassification(value.into())
 			}
+			modelfox_core::predict::PredictOutput::Ranking(value) => {
+				PredictOutput::Ranking(value.into())
+			}
 		}
 	}
 }
@@ -268,6 +360,22 @@ impl From<modelfox_core::predict::MulticlassClassificationPredictOutput>
 	}
 }
 
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RankingPredictOutput {
+	pub score: f32,
+	pub feature_contributions: Option<FeatureContributions>,
+}
+
+impl From<modelfox_core::predict::RankingPredictOutput> for RankingPredictOutput {
+	fn from(value: modelfox_core::predict::RankingPredictOutput) -> Self {
+		RankingPredictOutput {
+			score: value.score,
+			feature_contributions: value.feature_contributions.map(Into::into),
+		}
+	}
+}
+
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 struct FeatureContributions {
@@ -301,6 +409,16 @@ enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "hashed_bag_of_words")]
+	HashedBagOfWords(HashedBagOfWordsFeatureContribution),
+	#[serde(rename = "hashed_enum")]
+	HashedEnum(HashedEnumFeatureContribution),
+	#[serde(rename = "interaction")]
+	Interaction(InteractionFeatureContribution),
+	#[serde(rename = "polynomial")]
+	Polynomial(PolynomialFeatureContribution),
+	#[serde(rename = "binned_number")]
+	BinnedNumber(BinnedNumberFeatureContribution),
 }
 
 impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContributionEntry {
@@ -324,6 +442,21 @@ impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContribut
 			modelfox_core::predict::FeatureContributionEntry::WordEmbedding(value) => {
 				FeatureContributionEntry::WordEmbedding(value.into())
 			}
+			modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(value) => {
+				FeatureContributionEntry::HashedBagOfWords(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::HashedEnum(value) => {
+				FeatureContributionEntry::HashedEnum(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::Interaction(value) => {
+				FeatureContributionEntry::Interaction(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::Polynomial(value) => {
+				FeatureContributionEntry::Polynomial(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::BinnedNumber(value) => {
+				FeatureContributionEntry::BinnedNumber(value.into())
+			}
 		}
 	}
 }
@@ -409,6 +542,7 @@ impl From<modelfox_core::predict::BagOfWordsFeatureContribution> for BagOfWordsF
 enum NGram {
 	Unigram(String),
 	Bigram(String, String),
+	Character { character: String },
 }
 
 impl From<modelfox_core::predict::NGram> for NGram {
@@ -418,6 +552,7 @@ impl From<modelfox_core::predict::NGram> for NGram {
 			modelfox_core::predict::NGram::Bigram(token_a, token_b) => {
 				NGram::Bigram(token_a, token_b)
 			}
+			modelfox_core::predict::NGram::Character(character) => NGram::Character { character },
 		}
 	}
 }
@@ -463,3 +598,119 @@ impl From<modelfox_core::predict::WordEmbeddingFeatureContribution>
 		}
 	}
 }
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HashedBagOfWordsFeatureContribution {
+	column_name: String,
+	bucket: usize,
+	ngrams: Vec<NGram>,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::HashedBagOfWordsFeatureContribution>
+	for HashedBagOfWordsFeatureContribution
+{
+	fn from(value: modelfox_core::predict::HashedBagOfWordsFeatureContribution) -> Self {
+		HashedBagOfWordsFeatureContribution {
+			column_name: value.column_name,
+			bucket: value.bucket,
+			ngrams: value.ngrams.into_iter().map(Into::into).collect(),
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HashedEnumFeatureContribution {
+	column_name: String,
+	bucket: usize,
+	values: Vec<String>,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::HashedEnumFeatureContribution> for HashedEnumFeatureContribution {
+	fn from(value: modelfox_core::predict::HashedEnumFeatureContribution) -> Self {
+		HashedEnumFeatureContribution {
+			column_name: value.column_name,
+			bucket: value.bucket,
+			values: value.values,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InteractionFeatureContribution {
+	column_name_a: String,
+	variant_a: Option<String>,
+	column_name_b: String,
+	variant_b: Option<String>,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::InteractionFeatureContribution>
+	for InteractionFeatureContribution
+{
+	fn from(value: modelfox_core::predict::InteractionFeatureContribution) -> Self {
+		InteractionFeatureContribution {
+			column_name_a: value.column_name_a,
+			variant_a: value.variant_a,
+			column_name_b: value.column_name_b,
+			variant_b: value.variant_b,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PolynomialFeatureContribution {
+	column_name: String,
+	power: usize,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::PolynomialFeatureContribution> for PolynomialFeatureContribution {
+	fn from(value: modelfox_core::predict::PolynomialFeatureContribution) -> Self {
+		PolynomialFeatureContribution {
+			column_name: value.column_name,
+			power: value.power,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BinnedNumberFeatureContribution {
+	column_name: String,
+	bin_start: Option<f32>,
+	bin_end: Option<f32>,
+	feature_value: bool,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::BinnedNumberFeatureContribution>
+	for BinnedNumberFeatureContribution
+{
+	fn from(value: modelfox_core::predict::BinnedNumberFeatureContribution) -> Self {
+		BinnedNumberFeatureContribution {
+			column_name: value.column_name,
+			bin_start: value.bin_start,
+			bin_end: value.bin_end,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
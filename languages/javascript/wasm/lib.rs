@@ -48,9 +48,93 @@ pub fn predict(model: &Model, input: JsValue, options: JsValue) -> Result<JsValu
 	}
 }
 
+#[wasm_bindgen(js_name = "predictTable")]
+pub fn predict_table(
+	model: &Model,
+	columns: JsValue,
+	number_data: JsValue,
+	options: JsValue,
+) -> Result<JsValue, JsValue> {
+	let columns: PredictTableColumns = columns.into_serde().map_err(|e| e.to_string())?;
+	let number_data: serde_bytes::ByteBuf =
+		serde_wasm_bindgen::from_value(number_data).map_err(|e| e.to_string())?;
+	let options: Option<PredictOptions> = options.into_serde().map_err(|e| e.to_string())?;
+	let options = options.map(Into::into).unwrap_or_default();
+	let table = columns
+		.into_table(&number_data)
+		.map_err(|e| e.to_string())?;
+	let output = modelfox_core::predict::predict_table(&model.0, table.view(), &options);
+	let output = output.into_iter().map(Into::into).collect();
+	let output = PredictOutputSingleOrMultiple::Multiple(output);
+	let output = JsValue::from_serde(&output).map_err(|e| e.to_string())?;
+	Ok(output)
+}
+
 #[wasm_bindgen]
 pub struct Model(modelfox_core::predict::Model);
 
+/// These are the columns passed to `predictTable`. The values of the number columns are passed separately in a single buffer of 32 bit floats, one column after another.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PredictTableColumns {
+	number_column_names: Vec<String>,
+	string_columns: Vec<PredictTableStringColumn>,
+}
+
+#[derive(serde::Deserialize)]
+struct PredictTableStringColumn {
+	name: String,
+	values: Vec<Option<String>>,
+}
+
+impl PredictTableColumns {
+	fn into_table(self, number_data: &[u8]) -> anyhow::Result<modelfox_table::Table> {
+		let number_data = number_data
+			.chunks_exact(4)
+			.map(|bytes| f32::from_ne_bytes(bytes.try_into().unwrap()))
+			.collect::<Vec<_>>();
+		let n_rows = if self.number_column_names.is_empty() {
+			self.string_columns
+				.first()
+				.map(|column| column.values.len())
+				.unwrap_or(0)
+		} else {
+			number_data.len() / self.number_column_names.len()
+		};
+		if number_data.len() != n_rows * self.number_column_names.len()
+			|| self
+				.string_columns
+				.iter()
+				.any(|column| column.values.len() != n_rows)
+		{
+			anyhow::bail!("All columns must have the same length.");
+		}
+		let mut table = modelfox_table::Table::new(Vec::new(), Vec::new());
+		for (column_name, data) in self
+			.number_column_names
+			.into_iter()
+			.zip(number_data.chunks(n_rows.max(1)))
+		{
+			table
+				.columns_mut()
+				.push(modelfox_table::TableColumn::Number(
+					modelfox_table::NumberTableColumn::new(Some(column_name), data.to_owned()),
+				));
+		}
+		for column in self.string_columns {
+			let data = column
+				.values
+				.into_iter()
+				.map(Option::unwrap_or_default)
+				.collect();
+			table.columns_mut().push(modelfox_table::TableColumn::Text(
+				modelfox_table::TextTableColumn::new(Some(column.name), data),
+			));
+		}
+		Ok(table)
+	}
+}
+
 #[derive(serde::Deserialize)]
 #[serde(untagged)]
 enum PredictInputSingleOrMultiple {
@@ -104,10 +188,10 @@ struct PredictOptions {
 
 impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
 	fn from(value: PredictOptions) -> modelfox_core::predict::PredictOptions {
-		let mut options = modelfox_core::predict::PredictOptions::default();
-		if let Some(threshold) = value.threshold {
-			options.threshold = threshold;
-		}
+		let mut options = modelfox_core::predict::PredictOptions {
+			threshold: value.threshold,
+			..Default::default()
+		};
 		if let Some(compute_feature_contributions) = value.compute_feature_contributions {
 			options.compute_feature_contributions = compute_feature_contributions;
 		}
@@ -131,6 +215,8 @@ enum PredictOutput {
 	BinaryClassification(BinaryClassificationPredictOutput),
 	#[serde(rename = "multiclass_classification")]
 	MulticlassClassification(MulticlassClassificationPredictOutput),
+	#[serde(rename = "ranking")]
+	Ranking(RankingPredictOutput),
 }
 
 type PredictOutputMultiple = Vec<PredictOutput>;
@@ -147,6 +233,9 @@ impl From<modelfox_core::predict::PredictOutput> for PredictOutput {
 			modelfox_core::predict::PredictOutput::MulticlassClassification(value) => {
 				PredictOutput::MulticlassClassification(value.into())
 			}
+			modelfox_core::predict::PredictOutput::Ranking(value) => {
+				PredictOutput::Ranking(value.into())
+			}
 		}
 	}
 }
@@ -214,6 +303,22 @@ impl From<modelfox_core::predict::MulticlassClassificationPredictOutput>
 	}
 }
 
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RankingPredictOutput {
+	pub score: f32,
+	pub feature_contributions: Option<FeatureContributions>,
+}
+
+impl From<modelfox_core::predict::RankingPredictOutput> for RankingPredictOutput {
+	fn from(value: modelfox_core::predict::RankingPredictOutput) -> Self {
+		RankingPredictOutput {
+			score: value.score,
+			feature_contributions: value.feature_contributions.map(Into::into),
+		}
+	}
+}
+
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 struct FeatureContributions {
@@ -247,6 +352,16 @@ enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "hashed_bag_of_words")]
+	HashedBagOfWords(HashedBagOfWordsFeatureContribution),
+	#[serde(rename = "hashed_enum")]
+	HashedEnum(HashedEnumFeatureContribution),
+	#[serde(rename = "interaction")]
+	Interaction(InteractionFeatureContribution),
+	#[serde(rename = "polynomial")]
+	Polynomial(PolynomialFeatureContribution),
+	#[serde(rename = "binned_number")]
+	BinnedNumber(BinnedNumberFeatureContribution),
 }
 
 impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContributionEntry {
@@ -270,6 +385,21 @@ impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContribut
 			modelfox_core::predict::FeatureContributionEntry::WordEmbedding(value) => {
 				FeatureContributionEntry::WordEmbedding(value.into())
 			}
+			modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(value) => {
+				FeatureContributionEntry::HashedBagOfWords(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::HashedEnum(value) => {
+				FeatureContributionEntry::HashedEnum(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::Interaction(value) => {
+				FeatureContributionEntry::Interaction(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::Polynomial(value) => {
+				FeatureContributionEntry::Polynomial(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::BinnedNumber(value) => {
+				FeatureContributionEntry::BinnedNumber(value.into())
+			}
 		}
 	}
 }
@@ -355,6 +485,7 @@ impl From<modelfox_core::predict::BagOfWordsFeatureContribution> for BagOfWordsF
 enum NGram {
 	Unigram(String),
 	Bigram(String, String),
+	Character { character: String },
 }
 
 impl From<modelfox_core::predict::NGram> for NGram {
@@ -364,6 +495,7 @@ impl From<modelfox_core::predict::NGram> for NGram {
 			modelfox_core::predict::NGram::Bigram(token_a, token_b) => {
 				NGram::Bigram(token_a, token_b)
 			}
+			modelfox_core::predict::NGram::Character(character) => NGram::Character { character },
 		}
 	}
 }
@@ -409,3 +541,119 @@ impl From<modelfox_core::predict::WordEmbeddingFeatureContribution>
 		}
 	}
 }
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HashedBagOfWordsFeatureContribution {
+	column_name: String,
+	bucket: usize,
+	ngrams: Vec<NGram>,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::HashedBagOfWordsFeatureContribution>
+	for HashedBagOfWordsFeatureContribution
+{
+	fn from(value: modelfox_core::predict::HashedBagOfWordsFeatureContribution) -> Self {
+		HashedBagOfWordsFeatureContribution {
+			column_name: value.column_name,
+			bucket: value.bucket,
+			ngrams: value.ngrams.into_iter().map(Into::into).collect(),
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HashedEnumFeatureContribution {
+	column_name: String,
+	bucket: usize,
+	values: Vec<String>,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::HashedEnumFeatureContribution> for HashedEnumFeatureContribution {
+	fn from(value: modelfox_core::predict::HashedEnumFeatureContribution) -> Self {
+		HashedEnumFeatureContribution {
+			column_name: value.column_name,
+			bucket: value.bucket,
+			values: value.values,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InteractionFeatureContribution {
+	column_name_a: String,
+	variant_a: Option<String>,
+	column_name_b: String,
+	variant_b: Option<String>,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::InteractionFeatureContribution>
+	for InteractionFeatureContribution
+{
+	fn from(value: modelfox_core::predict::InteractionFeatureContribution) -> Self {
+		InteractionFeatureContribution {
+			column_name_a: value.column_name_a,
+			variant_a: value.variant_a,
+			column_name_b: value.column_name_b,
+			variant_b: value.variant_b,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PolynomialFeatureContribution {
+	column_name: String,
+	power: usize,
+	feature_value: f32,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::PolynomialFeatureContribution> for PolynomialFeatureContribution {
+	fn from(value: modelfox_core::predict::PolynomialFeatureContribution) -> Self {
+		PolynomialFeatureContribution {
+			column_name: value.column_name,
+			power: value.power,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BinnedNumberFeatureContribution {
+	column_name: String,
+	bin_start: Option<f32>,
+	bin_end: Option<f32>,
+	feature_value: bool,
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::BinnedNumberFeatureContribution>
+	for BinnedNumberFeatureContribution
+{
+	fn from(value: modelfox_core::predict::BinnedNumberFeatureContribution) -> Self {
+		BinnedNumberFeatureContribution {
+			column_name: value.column_name,
+			bin_start: value.bin_start,
+			bin_end: value.bin_end,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
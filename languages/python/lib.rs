@@ -1,7 +1,14 @@
 use anyhow::anyhow;
 use memmap::Mmap;
-use pyo3::{prelude::*, type_object::PyTypeObject, types::PyType};
-use std::collections::BTreeMap;
+use modelfox_table::prelude::*;
+use num::ToPrimitive;
+use pyo3::{
+	prelude::*,
+	type_object::PyTypeObject,
+	types::{IntoPyDict, PyDict, PyType},
+	wrap_pyfunction,
+};
+use std::{collections::BTreeMap, num::NonZeroUsize, path::PathBuf};
 use url::Url;
 
 #[pymodule]
@@ -13,6 +20,7 @@ fn modelfox(py: Python, m: &PyModule) -> PyResult<()> {
 	m.add_class::<RegressionPredictOutput>()?;
 	m.add_class::<BinaryClassificationPredictOutput>()?;
 	m.add_class::<MulticlassClassificationPredictOutput>()?;
+	m.add_class::<RankingPredictOutput>()?;
 	m.add_class::<FeatureContributions>()?;
 	m.add_class::<IdentityFeatureContribution>()?;
 	m.add_class::<NormalizedFeatureContribution>()?;
@@ -20,6 +28,15 @@ fn modelfox(py: Python, m: &PyModule) -> PyResult<()> {
 	m.add_class::<BagOfWordsFeatureContribution>()?;
 	m.add_class::<BagOfWordsCosineSimilarityFeatureContribution>()?;
 	m.add_class::<WordEmbeddingFeatureContribution>()?;
+	m.add_class::<HashedBagOfWordsFeatureContribution>()?;
+	m.add_class::<HashedEnumFeatureContribution>()?;
+	m.add_class::<InteractionFeatureContribution>()?;
+	m.add_class::<PolynomialFeatureContribution>()?;
+	m.add_class::<BinnedNumberFeatureContribution>()?;
+	m.add_class::<TrainProgressEvent>()?;
+	m.add_class::<TrainReport>()?;
+	m.add_class::<TrainGridItemReport>()?;
+	m.add_function(wrap_pyfunction!(train, m)?)?;
 	m.add("PredictInput", predict_input(py)?)?;
 	m.add("PredictOutput", predict_output(py)?)?;
 	m.add("FeatureContributionEntry", feature_contribution_entry(py)?)?;
@@ -153,6 +170,28 @@ impl Model {
 		}
 	}
 
+	/**
+	Make predictions for a batch of rows stored column by column. This is much faster than `predict` for large batches because no dict is built for each row.
+
+	Args:
+		data (Union[pandas.DataFrame, pyarrow.Table, Dict[str, Union[numpy.ndarray, List]]]): The columns to make predictions for. The column names should match the columns in the CSV file you trained your model with.
+		options (Optional[`PredictOptions`]): These are the predict options.
+
+	Returns:
+		[List[`PredictOutput`]]. Return one output for each row.
+	*/
+	#[pyo3(text_signature = "(data, options=None)")]
+	fn predict_table(
+		&self,
+		data: &PyAny,
+		options: Option<&PredictOptions>,
+	) -> PyResult<PredictOutputMultiple> {
+		let table = table_from_columns(data)?;
+		let options = options.map(Into::into).unwrap_or_default();
+		let output = modelfox_core::predict::predict_table(&self.model, table.view(), &options);
+		Ok(output.into_iter().map(Into::into).collect())
+	}
+
 	/**
 	Send a prediction event to the app. If you want to batch events, you can use `enqueue_log_prediction` instead.
 
@@ -285,6 +324,552 @@ impl Model {
 	}
 }
 
+/**
+Train a model.
+
+Args:
+	data (Union[str, pandas.DataFrame, pyarrow.Table]): The data to train on, either the path to a csv file or a data frame. The comparison and test datasets are split off from it unless `test_data` is provided.
+	target (str): The name of the column to predict.
+	config (Optional[Union[str, Dict[str, Any]]]): The training configuration, either the path to a `.json` or `.yaml` config file or a dict with the same structure.
+	test_data (Optional[Union[str, pandas.DataFrame, pyarrow.Table]]): The data to test the best model on. It must have the same columns as `data`, and it must be a path if `data` is a path or a data frame if `data` is a data frame.
+	on_progress (Optional[Callable[[`TrainProgressEvent`], None]]): This function is called with each progress event during training.
+
+Returns:
+	(Tuple[`Model`, `TrainReport`]): The best model and a report with its test metrics and the results for each model in the hyperparameter grid.
+*/
+#[pyfunction(config = "None", test_data = "None", on_progress = "None")]
+#[pyo3(text_signature = "(data, target, config=None, test_data=None, on_progress=None)")]
+fn train(
+	py: Python,
+	data: &PyAny,
+	target: String,
+	config: Option<&PyAny>,
+	test_data: Option<&PyAny>,
+	on_progress: Option<PyObject>,
+) -> PyResult<(Model, TrainReport)> {
+	let config = match config {
+		None => modelfox_core::config::Config::default(),
+		Some(config) => match config.extract::<PathBuf>() {
+			Ok(config_path) => {
+				modelfox_core::train::load_config(Some(&config_path)).map_err(ModelFoxError)?
+			}
+			Err(_) => {
+				let config: String = py
+					.import("json")?
+					.call_method1("dumps", (config,))?
+					.extract()?;
+				serde_json::from_str(&config).map_err(|error| ModelFoxError(error.into()))?
+			}
+		},
+	};
+	let input = match (
+		TrainingData::extract(data)?,
+		test_data.map(TrainingData::extract).transpose()?,
+	) {
+		(TrainingData::Path(path), None) => modelfox_core::train::TrainingDataSource::File(path),
+		(TrainingData::Path(train), Some(TrainingData::Path(test))) => {
+			modelfox_core::train::TrainingDataSource::TrainAndTest { train, test }
+		}
+		(TrainingData::Table(table), None) => {
+			modelfox_core::train::TrainingDataSource::Table(table)
+		}
+		(TrainingData::Table(train), Some(TrainingData::Table(test))) => {
+			modelfox_core::train::TrainingDataSource::TrainAndTestTables { train, test }
+		}
+		_ => {
+			return Err(ModelFoxError(anyhow!(
+				"data and test_data must both be paths or both be data frames"
+			))
+			.into())
+		}
+	};
+	// Errors raised by the progress callback are returned after training finishes.
+	let mut on_progress_error = None;
+	let mut handle_progress_event = |progress_event: modelfox_core::progress::ProgressEvent| {
+		if let (Some(on_progress), None) = (on_progress.as_ref(), on_progress_error.as_ref()) {
+			let progress_event = TrainProgressEvent::from(progress_event);
+			if let Err(error) = on_progress.call1(py, (progress_event,)) {
+				on_progress_error = Some(error);
+			}
+		}
+	};
+	let mut trainer = modelfox_core::train::Trainer::prepare(
+		modelfox_id::Id::generate(),
+		input,
+		&target,
+		config,
+		None,
+		&mut handle_progress_event,
+	)
+	.map_err(ModelFoxError)?;
+	let kill_chip = modelfox_kill_chip::KillChip::default();
+	let train_grid_item_outputs = trainer
+		.train_grid(&kill_chip, &mut handle_progress_event)
+		.map_err(ModelFoxError)?;
+	let model = trainer
+		.test_and_assemble_model(train_grid_item_outputs, &mut handle_progress_event)
+		.map_err(ModelFoxError)?;
+	if let Some(error) = on_progress_error {
+		return Err(error);
+	}
+	let report = TrainReport::from(&model);
+	let bytes = model.to_bytes();
+	let model = modelfox_model::from_bytes(&bytes).map_err(ModelFoxError)?;
+	let model = Model {
+		model: modelfox_core::predict::Model::from(model),
+		log_queue: Vec::new(),
+		modelfox_url: "https://app.modelfox.dev".parse().unwrap(),
+	};
+	Ok((model, report))
+}
+
+enum TrainingData {
+	Path(PathBuf),
+	Table(Table),
+}
+
+impl TrainingData {
+	/// Extract a path, a pandas data frame, or a pyarrow table.
+	fn extract(data: &PyAny) -> PyResult<TrainingData> {
+		if let Ok(path) = data.extract::<PathBuf>() {
+			return Ok(TrainingData::Path(path));
+		}
+		Ok(TrainingData::Table(table_from_columns(data)?))
+	}
+}
+
+/// Convert a pandas data frame, a pyarrow table, or a dict from column names to numpy arrays or lists to a table column by column, inferring the column types the same way as when loading a csv file.
+fn table_from_columns(data: &PyAny) -> PyResult<Table> {
+	let py = data.py();
+	let mut table = Table::new(Vec::new(), Vec::new());
+	if let Ok(data) = data.downcast::<PyDict>() {
+		let numpy = py.import("numpy")?;
+		for (column_name, values) in data.iter() {
+			let column_name = column_name.str()?.to_str()?.to_owned();
+			let values = numpy.call_method1("asarray", (values,))?;
+			let column = table_column_from_series(column_name, values)?;
+			table.columns_mut().push(column);
+		}
+		return Ok(table);
+	}
+	// Convert pyarrow tables to pandas data frames.
+	let data = if data.hasattr("to_pandas")? && !data.hasattr("iloc")? {
+		data.call_method0("to_pandas")?
+	} else {
+		data
+	};
+	if !data.hasattr("columns")? {
+		return Err(ModelFoxError(anyhow!(
+			"data must be a path, a pandas DataFrame, a pyarrow Table, or a dict of columns"
+		))
+		.into());
+	}
+	for column_name in data.getattr("columns")?.iter()? {
+		let column_name = column_name?;
+		let series = data.get_item(column_name)?;
+		let column_name = column_name.str()?.to_str()?.to_owned();
+		let column = table_column_from_series(column_name, series)?;
+		table.columns_mut().push(column);
+	}
+	Ok(table)
+}
+
+fn table_column_from_series(column_name: String, series: &PyAny) -> PyResult<TableColumn> {
+	let dtype = series.getattr("dtype")?;
+	let dtype_name: String = dtype.getattr("name")?.extract()?;
+	let dtype_kind: String = dtype.getattr("kind")?.extract()?;
+	// Categorical columns are enum columns with the same categories.
+	if dtype_name == "category" {
+		let categorical = series.getattr("cat")?;
+		let variants: Vec<String> = categorical
+			.getattr("categories")?
+			.call_method1("astype", ("str",))?
+			.call_method0("tolist")?
+			.extract()?;
+		let codes: Vec<i64> = categorical
+			.getattr("codes")?
+			.call_method0("tolist")?
+			.extract()?;
+		let data = codes
+			.into_iter()
+			.map(|code| code.to_usize().and_then(|code| NonZeroUsize::new(code + 1)))
+			.collect();
+		return Ok(TableColumn::Enum(EnumTableColumn::new(
+			Some(column_name),
+			variants,
+			data,
+		)));
+	}
+	match dtype_kind.as_str() {
+		"i" | "u" | "f" => {
+			let data: Vec<f64> = series
+				.call_method1("astype", ("float64",))?
+				.call_method0("tolist")?
+				.extract()?;
+			let data: Vec<f32> = data
+				.into_iter()
+				.map(|value| match value.to_f32() {
+					Some(value) if value.is_finite() => value,
+					_ => f32::NAN,
+				})
+				.collect();
+			// Just like when loading a csv file, integer columns whose values are all zero or one are enum columns.
+			let is_zero_or_one = |value: &f32| value.is_nan() || *value == 0.0 || *value == 1.0;
+			if dtype_kind != "f"
+				&& data.iter().all(is_zero_or_one)
+				&& data.iter().any(|value| *value == 0.0)
+				&& data.iter().any(|value| *value == 1.0)
+			{
+				let data = data
+					.into_iter()
+					.map(|value| {
+						if value.is_nan() {
+							None
+						} else {
+							NonZeroUsize::new(value.to_usize().unwrap() + 1)
+						}
+					})
+					.collect();
+				return Ok(TableColumn::Enum(EnumTableColumn::new(
+					Some(column_name),
+					vec!["0".to_owned(), "1".to_owned()],
+					data,
+				)));
+			}
+			Ok(TableColumn::Number(NumberTableColumn::new(
+				Some(column_name),
+				data,
+			)))
+		}
+		_ => {
+			// Missing values become empty strings, which are treated as invalid values.
+			let values = series
+				.call_method0("tolist")?
+				.iter()?
+				.map(|value| {
+					let value = value?;
+					let is_missing = value.is_none()
+						|| value
+							.extract::<f64>()
+							.map(|value| value.is_nan())
+							.unwrap_or(false);
+					if is_missing {
+						Ok(String::new())
+					} else {
+						Ok(value.str()?.to_str()?.to_owned())
+					}
+				})
+				.collect::<PyResult<Vec<String>>>()?;
+			let values: Vec<&str> = values.iter().map(String::as_str).collect();
+			Ok(TableColumn::from_strings(
+				Some(column_name),
+				&values,
+				&modelfox_table::InferOptions::default(),
+			))
+		}
+	}
+}
+
+/**
+`train` calls its `on_progress` function with a `TrainProgressEvent` as training proceeds.
+
+Attributes:
+	type (str): This is the stage of training the event is from, one of "info", "warning", "load", "stats", "compute_baseline_metrics", "train", "test", or "finalize".
+	message (Optional[str]): This is a message describing the event.
+	grid_item_index (Optional[int]): For "train" events, this is the index of the model in the hyperparameter grid that is being trained.
+	grid_item_count (Optional[int]): For "train" events, this is the number of models in the hyperparameter grid.
+*/
+#[pyclass]
+#[derive(Clone, Debug)]
+struct TrainProgressEvent {
+	#[pyo3(get)]
+	r#type: String,
+	#[pyo3(get)]
+	message: Option<String>,
+	#[pyo3(get)]
+	grid_item_index: Option<usize>,
+	#[pyo3(get)]
+	grid_item_count: Option<usize>,
+}
+
+impl From<modelfox_core::progress::ProgressEvent> for TrainProgressEvent {
+	fn from(value: modelfox_core::progress::ProgressEvent) -> Self {
+		use modelfox_core::progress::{
+			LoadProgressEvent, ModelTestProgressEvent, ProgressEvent, StatsProgressEvent,
+			TrainGridItemProgressEvent,
+		};
+		let event = |r#type: &str, message: &str| TrainProgressEvent {
+			r#type: r#type.to_owned(),
+			message: Some(message.to_owned()),
+			grid_item_index: None,
+			grid_item_count: None,
+		};
+		match value {
+			ProgressEvent::Info(message) => event("info", &message),
+			ProgressEvent::Warning(message) => event("warning", &message),
+			ProgressEvent::Load(progress_event) => match progress_event {
+				LoadProgressEvent::Train(_) => event("load", "Loading the train dataset."),
+				LoadProgressEvent::Test(_) => event("load", "Loading the test dataset."),
+				LoadProgressEvent::Shuffle => event("load", "Shuffling."),
+				LoadProgressEvent::ShuffleDone => event("load", "Done shuffling."),
+			},
+			ProgressEvent::Stats(progress_event) => match progress_event {
+				StatsProgressEvent::ComputeTrainStats(_) => {
+					event("stats", "Computing train stats.")
+				}
+				StatsProgressEvent::ComputeTrainStatsDone => {
+					event("stats", "Done computing train stats.")
+				}
+				StatsProgressEvent::ComputeTestStats(_) => event("stats", "Computing test stats."),
+				StatsProgressEvent::ComputeTestStatsDone => {
+					event("stats", "Done computing test stats.")
+				}
+				StatsProgressEvent::Finalize => event("stats", "Finalizing stats."),
+				StatsProgressEvent::FinalizeDone => event("stats", "Done finalizing stats."),
+			},
+			ProgressEvent::ComputeBaselineMetrics(_) => {
+				event("compute_baseline_metrics", "Computing baseline metrics.")
+			}
+			ProgressEvent::ComputeBaselineMetricsDone => event(
+				"compute_baseline_metrics",
+				"Done computing baseline metrics.",
+			),
+			ProgressEvent::Train(progress_event) => {
+				let message = match progress_event.grid_item_progress_event {
+					TrainGridItemProgressEvent::ComputeFeatures(_) => "Computing features.",
+					TrainGridItemProgressEvent::ComputeFeaturesDone => "Done computing features.",
+					TrainGridItemProgressEvent::TrainModel(_) => "Training model.",
+					TrainGridItemProgressEvent::ComputeModelComparisonMetrics(_) => {
+						"Computing comparison metrics."
+					}
+				};
+				TrainProgressEvent {
+					grid_item_index: Some(progress_event.grid_item_index),
+					grid_item_count: Some(progress_event.grid_item_count),
+					..event("train", message)
+				}
+			}
+			ProgressEvent::Test(progress_event) => match progress_event {
+				ModelTestProgressEvent::ComputeFeatures(_) => event("test", "Computing features."),
+				ModelTestProgressEvent::ComputeFeaturesDone => {
+					event("test", "Done computing features.")
+				}
+				ModelTestProgressEvent::Test(_) => event("test", "Testing the best model."),
+				ModelTestProgressEvent::TestDone => event("test", "Done testing the best model."),
+			},
+			ProgressEvent::Finalize => event("finalize", "Finalizing the model."),
+			ProgressEvent::FinalizeDone => event("finalize", "Done finalizing the model."),
+		}
+	}
+}
+
+/**
+`train` returns a `TrainReport` describing how the model was chosen and how well it performs.
+
+Attributes:
+	comparison_metric (str): This is the name of the metric used to choose the best model from the hyperparameter grid.
+	baseline_metrics (Dict[str, float]): These are the metrics on the test dataset for a baseline model that always predicts the mean or the majority class.
+	test_metrics (Dict[str, float]): These are the metrics for the best model on the test dataset. For binary classification, the metrics that depend on a threshold use a threshold of 0.5.
+	grid (List[`TrainGridItemReport`]): This list has one entry for each model in the hyperparameter grid.
+	best_grid_item_index (int): This is the index in `grid` of the model that was chosen.
+*/
+#[pyclass]
+#[derive(Clone, Debug)]
+struct TrainReport {
+	#[pyo3(get)]
+	comparison_metric: String,
+	#[pyo3(get)]
+	baseline_metrics: BTreeMap<String, f32>,
+	#[pyo3(get)]
+	test_metrics: BTreeMap<String, f32>,
+	#[pyo3(get)]
+	grid: Vec<TrainGridItemReport>,
+	#[pyo3(get)]
+	best_grid_item_index: usize,
+}
+
+/**
+This is the result of training a single model in the hyperparameter grid.
+
+Attributes:
+	model_type (str): This is either "linear" or "tree".
+	comparison_metric_value (float): This is the value of the comparison metric on the comparison dataset.
+	comparison_metrics (Dict[str, float]): These are all the metrics on the comparison dataset.
+	duration (float): This is the number of seconds it took to train the model.
+*/
+#[pyclass]
+#[derive(Clone, Debug)]
+struct TrainGridItemReport {
+	#[pyo3(get)]
+	model_type: String,
+	#[pyo3(get)]
+	comparison_metric_value: f32,
+	#[pyo3(get)]
+	comparison_metrics: BTreeMap<String, f32>,
+	#[pyo3(get)]
+	duration: f64,
+}
+
+impl From<&modelfox_core::model::Model> for TrainReport {
+	fn from(value: &modelfox_core::model::Model) -> Self {
+		use modelfox_core::model::{
+			BinaryClassificationComparisonMetric, ModelInner,
+			MulticlassClassificationComparisonMetric, RankingComparisonMetric,
+			RegressionComparisonMetric,
+		};
+		match &value.inner {
+			ModelInner::Regressor(model) => TrainReport {
+				comparison_metric: match model.comparison_metric {
+					RegressionComparisonMetric::MeanAbsoluteError => "mae",
+					RegressionComparisonMetric::MeanSquaredError => "mse",
+					RegressionComparisonMetric::RootMeanSquaredError => "rmse",
+					RegressionComparisonMetric::R2 => "r2",
+				}
+				.to_owned(),
+				baseline_metrics: regression_metrics(&model.baseline_metrics),
+				test_metrics: regression_metrics(&model.test_metrics),
+				grid: model
+					.train_grid_item_outputs
+					.iter()
+					.map(Into::into)
+					.collect(),
+				best_grid_item_index: model.best_grid_item_index,
+			},
+			ModelInner::BinaryClassifier(model) => TrainReport {
+				comparison_metric: match model.comparison_metric {
+					BinaryClassificationComparisonMetric::AucRoc => "auc_roc",
+				}
+				.to_owned(),
+				baseline_metrics: binary_classification_metrics(&model.baseline_metrics),
+				test_metrics: binary_classification_metrics(&model.test_metrics),
+				grid: model
+					.train_grid_item_outputs
+					.iter()
+					.map(Into::into)
+					.collect(),
+				best_grid_item_index: model.best_grid_item_index,
+			},
+			ModelInner::MulticlassClassifier(model) => TrainReport {
+				comparison_metric: match model.comparison_metric {
+					MulticlassClassificationComparisonMetric::Accuracy => "accuracy",
+				}
+				.to_owned(),
+				baseline_metrics: multiclass_classification_metrics(&model.baseline_metrics),
+				test_metrics: multiclass_classification_metrics(&model.test_metrics),
+				grid: model
+					.train_grid_item_outputs
+					.iter()
+					.map(Into::into)
+					.collect(),
+				best_grid_item_index: model.best_grid_item_index,
+			},
+			ModelInner::Ranker(model) => TrainReport {
+				comparison_metric: match model.comparison_metric {
+					RankingComparisonMetric::Ndcg => "ndcg",
+					RankingComparisonMetric::Map => "map",
+				}
+				.to_owned(),
+				baseline_metrics: ranking_metrics(&model.baseline_metrics),
+				test_metrics: ranking_metrics(&model.test_metrics),
+				grid: model
+					.train_grid_item_outputs
+					.iter()
+					.map(Into::into)
+					.collect(),
+				best_grid_item_index: model.best_grid_item_index,
+			},
+		}
+	}
+}
+
+impl From<&modelfox_core::train::TrainGridItemOutput> for TrainGridItemReport {
+	fn from(value: &modelfox_core::train::TrainGridItemOutput) -> Self {
+		use modelfox_core::{model::Metrics, train::TrainModelOutput};
+		let model_type = match value.train_model_output {
+			TrainModelOutput::LinearRegressor(_)
+			| TrainModelOutput::LinearBinaryClassifier(_)
+			| TrainModelOutput::LinearMulticlassClassifier(_) => "linear",
+			TrainModelOutput::TreeRegressor(_)
+			| TrainModelOutput::TreeBinaryClassifier(_)
+			| TrainModelOutput::TreeMulticlassClassifier(_)
+			| TrainModelOutput::TreeRanker(_) => "tree",
+		};
+		let comparison_metrics = match &value.comparison_metrics {
+			Metrics::Regression(metrics) => regression_metrics(metrics),
+			Metrics::BinaryClassification(metrics) => binary_classification_metrics(metrics),
+			Metrics::MulticlassClassification(metrics) => {
+				multiclass_classification_metrics(metrics)
+			}
+			Metrics::Ranking(metrics) => ranking_metrics(metrics),
+		};
+		TrainGridItemReport {
+			model_type: model_type.to_owned(),
+			comparison_metric_value: value.comparison_metric_value,
+			comparison_metrics,
+			duration: value.duration.as_secs_f64(),
+		}
+	}
+}
+
+fn regression_metrics(
+	metrics: &modelfox_metrics::RegressionMetricsOutput,
+) -> BTreeMap<String, f32> {
+	[
+		("mse", metrics.mse),
+		("rmse", metrics.rmse),
+		("mae", metrics.mae),
+		("r2", metrics.r2),
+	]
+	.into_iter()
+	.map(|(name, value)| (name.to_owned(), value))
+	.collect()
+}
+
+fn ranking_metrics(metrics: &modelfox_metrics::RankingMetricsOutput) -> BTreeMap<String, f32> {
+	[("ndcg", metrics.ndcg), ("map", metrics.map)]
+		.into_iter()
+		.map(|(name, value)| (name.to_owned(), value))
+		.collect()
+}
+
+fn binary_classification_metrics(
+	metrics: &modelfox_metrics::BinaryClassificationMetricsOutput,
+) -> BTreeMap<String, f32> {
+	// The thresholds are centered at 0.5, so the middle threshold is 0.5.
+	let metrics_for_threshold = &metrics.thresholds[metrics.thresholds.len() / 2];
+	[
+		("auc_roc", metrics.auc_roc_approx),
+		("accuracy", metrics_for_threshold.accuracy),
+		(
+			"precision",
+			metrics_for_threshold.precision.unwrap_or(f32::NAN),
+		),
+		("recall", metrics_for_threshold.recall.unwrap_or(f32::NAN)),
+		(
+			"f1_score",
+			metrics_for_threshold.f1_score.unwrap_or(f32::NAN),
+		),
+	]
+	.into_iter()
+	.map(|(name, value)| (name.to_owned(), value))
+	.collect()
+}
+
+fn multiclass_classification_metrics(
+	metrics: &modelfox_metrics::MulticlassClassificationMetricsOutput,
+) -> BTreeMap<String, f32> {
+	[
+		("accuracy", metrics.accuracy),
+		("precision_unweighted", metrics.precision_unweighted),
+		("precision_weighted", metrics.precision_weighted),
+		("recall_unweighted", metrics.recall_unweighted),
+		("recall_weighted", metrics.recall_weighted),
+	]
+	.into_iter()
+	.map(|(name, value)| (name.to_owned(), value))
+	.collect()
+}
+
 /**
 These are the options passed when loading a model.
 
@@ -353,7 +938,7 @@ impl From<PredictInputValue> for modelfox_core::predict::PredictInputValue {
 These are the options passed to `Model.predict`.
 
 Attributes:
-	threshold (Optional[float]): If your model is a binary classifier, use this field to make predictions using a threshold chosen on the tuning page of the app. The default value is `0.5`.
+	threshold (Optional[float]): If your model is a binary classifier, use this field to make predictions using a threshold chosen on the tuning page of the app. If not specified, the threshold saved in the model is used, which is `0.5` unless you changed it on the tuning page.
 
 	compute_feature_contributions (Optional[bool]): Computing feature contributions is disabled by default. If you set this field to `true`, you will be able to access the feature contributions with the `featureContributions` field of the predict output.
 */
@@ -379,10 +964,10 @@ impl PredictOptions {
 
 impl From<&PredictOptions> for modelfox_core::predict::PredictOptions {
 	fn from(value: &PredictOptions) -> modelfox_core::predict::PredictOptions {
-		let mut options = modelfox_core::predict::PredictOptions::default();
-		if let Some(threshold) = value.threshold {
-			options.threshold = threshold;
-		}
+		let mut options = modelfox_core::predict::PredictOptions {
+			threshold: value.threshold,
+			..Default::default()
+		};
 		if let Some(compute_feature_contributions) = value.compute_feature_contributions {
 			options.compute_feature_contributions = compute_feature_contributions;
 		}
@@ -410,6 +995,7 @@ enum PredictOutput {
 	Regression(RegressionPredictOutput),
 	BinaryClassification(BinaryClassificationPredictOutput),
 	MulticlassClassification(MulticlassClassificationPredictOutput),
+	Ranking(RankingPredictOutput),
 }
 
 type PredictOutputMultiple = Vec<PredictOutput>;
@@ -420,6 +1006,7 @@ impl IntoPy<PyObject> for PredictOutput {
 			PredictOutput::Regression(s) => s.into_py(py),
 			PredictOutput::BinaryClassification(s) => s.into_py(py),
 			PredictOutput::MulticlassClassification(s) => s.into_py(py),
+			PredictOutput::Ranking(s) => s.into_py(py),
 		}
 	}
 }
@@ -436,6 +1023,9 @@ impl From<modelfox_core::predict::PredictOutput> for PredictOutput {
 			modelfox_core::predict::PredictOutput::MulticlassClassification(value) => {
 				PredictOutput::MulticlassClassification(value.into())
 			}
+			modelfox_core::predict::PredictOutput::Ranking(value) => {
+				PredictOutput::Ranking(value.into())
+			}
 		}
 	}
 }
@@ -466,6 +1056,32 @@ impl From<modelfox_core::predict::RegressionPredictOutput> for RegressionPredict
 	}
 }
 
+/**
+`Model.predict` outputs `RankingPredictOutput` when the model's task is ranking.
+
+Attributes:
+	score: This is the predicted score. To rank the examples in a query group, sort them by their scores in descending order. Scores are only comparable within a query group.
+	feature_contributions (`FeatureContributions`): If computing feature contributions was enabled in the predict options, this value will explain the model's output, showing how much each feature contributed to the output.
+*/
+#[pyclass]
+#[derive(Clone, Debug, serde::Serialize)]
+struct RankingPredictOutput {
+	#[pyo3(get)]
+	score: f32,
+	#[pyo3(get)]
+	#[serde(skip_serializing)]
+	feature_contributions: Option<FeatureContributions>,
+}
+
+impl From<modelfox_core::predict::RankingPredictOutput> for RankingPredictOutput {
+	fn from(value: modelfox_core::predict::RankingPredictOutput) -> Self {
+		RankingPredictOutput {
+			score: value.score,
+			feature_contributions: value.feature_contributions.map(Into::into),
+		}
+	}
+}
+
 /**
 `Model.predict` outputs `BinaryClassificationPredictOutput` when the model's task is binary classification.
 
@@ -584,6 +1200,16 @@ enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "hashed_bag_of_words")]
+	HashedBagOfWords(HashedBagOfWordsFeatureContribution),
+	#[serde(rename = "hashed_enum")]
+	HashedEnum(HashedEnumFeatureContribution),
+	#[serde(rename = "interaction")]
+	Interaction(InteractionFeatureContribution),
+	#[serde(rename = "polynomial")]
+	Polynomial(PolynomialFeatureContribution),
+	#[serde(rename = "binned_number")]
+	BinnedNumber(BinnedNumberFeatureContribution),
 }
 
 impl IntoPy<PyObject> for FeatureContributionEntry {
@@ -595,6 +1221,11 @@ impl IntoPy<PyObject> for FeatureContributionEntry {
 			FeatureContributionEntry::BagOfWords(s) => s.into_py(py),
 			FeatureContributionEntry::BagOfWordsCosineSimilarity(s) => s.into_py(py),
 			FeatureContributionEntry::WordEmbedding(s) => s.into_py(py),
+			FeatureContributionEntry::HashedBagOfWords(s) => s.into_py(py),
+			FeatureContributionEntry::HashedEnum(s) => s.into_py(py),
+			FeatureContributionEntry::Interaction(s) => s.into_py(py),
+			FeatureContributionEntry::Polynomial(s) => s.into_py(py),
+			FeatureContributionEntry::BinnedNumber(s) => s.into_py(py),
 		}
 	}
 }
@@ -620,6 +1251,21 @@ impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContribut
 			modelfox_core::predict::FeatureContributionEntry::WordEmbedding(value) => {
 				FeatureContributionEntry::WordEmbedding(value.into())
 			}
+			modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(value) => {
+				FeatureContributionEntry::HashedBagOfWords(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::HashedEnum(value) => {
+				FeatureContributionEntry::HashedEnum(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::Interaction(value) => {
+				FeatureContributionEntry::Interaction(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::Polynomial(value) => {
+				FeatureContributionEntry::Polynomial(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::BinnedNumber(value) => {
+				FeatureContributionEntry::BinnedNumber(value.into())
+			}
 		}
 	}
 }
@@ -755,6 +1401,7 @@ impl From<modelfox_core::predict::BagOfWordsFeatureContribution> for BagOfWordsF
 enum NGram {
 	Unigram(String),
 	Bigram(String, String),
+	Character { character: String },
 }
 
 impl IntoPy<PyObject> for NGram {
@@ -762,6 +1409,9 @@ impl IntoPy<PyObject> for NGram {
 		match self {
 			NGram::Unigram(token) => token.into_py(py),
 			NGram::Bigram(token_a, token_b) => vec![token_a, token_b].into_py(py),
+			NGram::Character { character } => {
+				[("character", character)].into_py_dict(py).into_py(py)
+			}
 		}
 	}
 }
@@ -773,6 +1423,7 @@ impl From<modelfox_core::predict::NGram> for NGram {
 			modelfox_core::predict::NGram::Bigram(token_a, token_b) => {
 				NGram::Bigram(token_a, token_b)
 			}
+			modelfox_core::predict::NGram::Character(character) => NGram::Character { character },
 		}
 	}
 }
@@ -844,6 +1495,197 @@ impl From<modelfox_core::predict::WordEmbeddingFeatureContribution>
 	}
 }
 
+/**
+This describes the contribution of a feature from a hashed bag of words feature group.
+
+Attributes:
+	column_name (str): This is the name of the source column for the feature group.
+	bucket (int): This is the index of the bucket that this feature corresponds to.
+	ngrams (List[`NGram`]): These are the most frequent ngrams in the training data that hash to this bucket.
+	feature_value (float): This is the value of the feature.
+	feature_contribution_value (float): This is the amount that the feature contributed to the output.
+*/
+#[pyclass]
+#[derive(Clone, Debug, serde::Serialize)]
+struct HashedBagOfWordsFeatureContribution {
+	#[pyo3(get)]
+	column_name: String,
+	#[pyo3(get)]
+	bucket: usize,
+	#[pyo3(get)]
+	ngrams: Vec<NGram>,
+	#[pyo3(get)]
+	feature_value: f32,
+	#[pyo3(get)]
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::HashedBagOfWordsFeatureContribution>
+	for HashedBagOfWordsFeatureContribution
+{
+	fn from(value: modelfox_core::predict::HashedBagOfWordsFeatureContribution) -> Self {
+		HashedBagOfWordsFeatureContribution {
+			column_name: value.column_name,
+			bucket: value.bucket,
+			ngrams: value.ngrams.into_iter().map(Into::into).collect(),
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+/**
+This describes the contribution of a feature from a hashed enum feature group.
+
+Attributes:
+	column_name (str): This is the name of the source column for the feature group.
+	bucket (int): This is the index of the bucket that this feature corresponds to.
+	values (List[str]): These are the most frequent values in the training data that hash to this bucket.
+	feature_value (float): This is the value of the feature.
+	feature_contribution_value (float): This is the amount that the feature contributed to the output.
+*/
+#[pyclass]
+#[derive(Clone, Debug, serde::Serialize)]
+struct HashedEnumFeatureContribution {
+	#[pyo3(get)]
+	column_name: String,
+	#[pyo3(get)]
+	bucket: usize,
+	#[pyo3(get)]
+	values: Vec<String>,
+	#[pyo3(get)]
+	feature_value: f32,
+	#[pyo3(get)]
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::HashedEnumFeatureContribution> for HashedEnumFeatureContribution {
+	fn from(value: modelfox_core::predict::HashedEnumFeatureContribution) -> Self {
+		HashedEnumFeatureContribution {
+			column_name: value.column_name,
+			bucket: value.bucket,
+			values: value.values,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+/**
+This describes the contribution of a feature from an interaction feature group.
+
+Attributes:
+	column_name_a (str): This is the name of the first source column for the feature group.
+	variant_a (Optional[str]): This is the enum variant of the first source column that the feature corresponds to, if it is an enum column.
+	column_name_b (str): This is the name of the second source column for the feature group.
+	variant_b (Optional[str]): This is the enum variant of the second source column that the feature corresponds to, if it is an enum column.
+	feature_value (float): This is the value of the feature.
+	feature_contribution_value (float): This is the amount that the feature contributed to the output.
+*/
+#[pyclass]
+#[derive(Clone, Debug, serde::Serialize)]
+struct InteractionFeatureContribution {
+	#[pyo3(get)]
+	column_name_a: String,
+	#[pyo3(get)]
+	variant_a: Option<String>,
+	#[pyo3(get)]
+	column_name_b: String,
+	#[pyo3(get)]
+	variant_b: Option<String>,
+	#[pyo3(get)]
+	feature_value: f32,
+	#[pyo3(get)]
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::InteractionFeatureContribution>
+	for InteractionFeatureContribution
+{
+	fn from(value: modelfox_core::predict::InteractionFeatureContribution) -> Self {
+		InteractionFeatureContribution {
+			column_name_a: value.column_name_a,
+			variant_a: value.variant_a,
+			column_name_b: value.column_name_b,
+			variant_b: value.variant_b,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+/**
+This describes the contribution of a feature from a polynomial feature group.
+
+Attributes:
+	column_name (str): This is the name of the source column for the feature group.
+	power (int): This is the power that the normalized column value is raised to.
+	feature_value (float): This is the value of the feature.
+	feature_contribution_value (float): This is the amount that the feature contributed to the output.
+*/
+#[pyclass]
+#[derive(Clone, Debug, serde::Serialize)]
+struct PolynomialFeatureContribution {
+	#[pyo3(get)]
+	column_name: String,
+	#[pyo3(get)]
+	power: usize,
+	#[pyo3(get)]
+	feature_value: f32,
+	#[pyo3(get)]
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::PolynomialFeatureContribution> for PolynomialFeatureContribution {
+	fn from(value: modelfox_core::predict::PolynomialFeatureContribution) -> Self {
+		PolynomialFeatureContribution {
+			column_name: value.column_name,
+			power: value.power,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+/**
+This describes the contribution of a feature from a binned number feature group.
+
+Attributes:
+	column_name (str): This is the name of the source column for the feature group.
+	bin_start (Optional[float]): This is the lower bound of the bin, or `None` for the first bin.
+	bin_end (Optional[float]): This is the upper bound of the bin, or `None` for the last bin.
+	feature_value (bool): This is the value of the feature.
+	feature_contribution_value (float): This is the amount that the feature contributed to the output.
+*/
+#[pyclass]
+#[derive(Clone, Debug, serde::Serialize)]
+struct BinnedNumberFeatureContribution {
+	#[pyo3(get)]
+	column_name: String,
+	#[pyo3(get)]
+	bin_start: Option<f32>,
+	#[pyo3(get)]
+	bin_end: Option<f32>,
+	#[pyo3(get)]
+	feature_value: bool,
+	#[pyo3(get)]
+	feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::BinnedNumberFeatureContribution>
+	for BinnedNumberFeatureContribution
+{
+	fn from(value: modelfox_core::predict::BinnedNumberFeatureContribution) -> Self {
+		BinnedNumberFeatureContribution {
+			column_name: value.column_name,
+			bin_start: value.bin_start,
+			bin_end: value.bin_end,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
 #[derive(Debug, serde::Serialize)]
 #[serde(tag = "type")]
 enum Event {
@@ -895,6 +1737,7 @@ fn predict_output(py: Python) -> PyResult<PyObject> {
 		RegressionPredictOutput::type_object(py),
 		BinaryClassificationPredictOutput::type_object(py),
 		MulticlassClassificationPredictOutput::type_object(py),
+		RankingPredictOutput::type_object(py),
 	))?;
 	Ok(predict_output.into())
 }
@@ -909,6 +1752,11 @@ fn feature_contribution_entry(py: Python) -> PyResult<PyObject> {
 		BagOfWordsFeatureContribution::type_object(py),
 		BagOfWordsCosineSimilarityFeatureContribution::type_object(py),
 		WordEmbeddingFeatureContribution::type_object(py),
+		HashedBagOfWordsFeatureContribution::type_object(py),
+		HashedEnumFeatureContribution::type_object(py),
+		InteractionFeatureContribution::type_object(py),
+		PolynomialFeatureContribution::type_object(py),
+		BinnedNumberFeatureContribution::type_object(py),
 	))?;
 	Ok(feature_contribution_entry.into())
 }
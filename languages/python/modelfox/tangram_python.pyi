@@ -1,5 +1,6 @@
 from typing import (
     Any,
+    Callable,
     cast,
     Dict,
     List,
@@ -38,6 +39,11 @@ class Model:
         input: List[PredictInput],
         options: Optional[PredictOptions] = None,
     ) -> List[PredictOutput]: ...
+    def predict_table(
+        self,
+        data: Any,
+        options: Optional[PredictOptions] = None,
+    ) -> List[PredictOutput]: ...
     def log_prediction(
         self,
         identifier: Identifier,
@@ -154,6 +160,33 @@ class WordEmbeddingFeatureContribution:
     feature_contribution_value: float
     value_index: int
 
+def train(
+    data: Any,
+    target: str,
+    config: Optional[Union[str, Dict[str, Any]]] = None,
+    test_data: Optional[Any] = None,
+    on_progress: Optional[Callable[[TrainProgressEvent], None]] = None,
+) -> Tuple[Model, TrainReport]: ...
+
+class TrainProgressEvent:
+    type: str
+    message: Optional[str]
+    grid_item_index: Optional[int]
+    grid_item_count: Optional[int]
+
+class TrainReport:
+    comparison_metric: str
+    baseline_metrics: Dict[str, float]
+    test_metrics: Dict[str, float]
+    grid: List[TrainGridItemReport]
+    best_grid_item_index: int
+
+class TrainGridItemReport:
+    model_type: str
+    comparison_metric_value: float
+    comparison_metrics: Dict[str, float]
+    duration: float
+
 Event = Union[PredictionEvent, TrueValueEvent]
 
 Identifier = Union[str, float]
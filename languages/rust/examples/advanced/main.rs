@@ -17,91 +17,97 @@ pub struct Input {
 	pub thallium_stress_test: ThalliumStressTest,
 }
 
-#[derive(Clone, Debug, modelfox::PredictInputValue)]
+#[derive(Clone, Debug, modelfox::PredictInput)]
 pub enum Gender {
-	#[modelfox(value = "male")]
+	#[modelfox(rename = "male")]
 	Male,
-	#[modelfox(value = "female")]
+	#[modelfox(rename = "female")]
 	Female,
 }
 
-#[derive(Clone, Debug, modelfox::PredictInputValue)]
+#[derive(Clone, Debug, modelfox::PredictInput)]
 pub enum ChestPain {
-	#[modelfox(value = "asymptomatic")]
+	#[modelfox(rename = "asymptomatic")]
 	Asymptomatic,
-	#[modelfox(value = "non-angina pain")]
+	#[modelfox(rename = "non-angina pain")]
 	NonAnginaPain,
-	#[modelfox(value = "atypical angina")]
+	#[modelfox(rename = "atypical angina")]
 	AtypicalAngina,
-	#[modelfox(value = "typical angina")]
+	#[modelfox(rename = "typical angina")]
 	TypicalAngina,
 }
 
-#[derive(Clone, Debug, modelfox::PredictInputValue)]
+#[derive(Clone, Debug, modelfox::PredictInput)]
 pub enum FastingBloodSugarGreaterThan120 {
-	#[modelfox(value = "false")]
+	#[modelfox(rename = "false")]
 	False,
-	#[modelfox(value = "true")]
+	#[modelfox(rename = "true")]
 	True,
 }
 
-#[derive(Clone, Debug, modelfox::PredictInputValue)]
+#[derive(Clone, Debug, modelfox::PredictInput)]
 pub enum RestingEcgResult {
-	#[modelfox(value = "normal")]
+	#[modelfox(rename = "normal")]
 	Normal,
-	#[modelfox(value = "probable or definite left ventricular hypertrophy")]
+	#[modelfox(rename = "probable or definite left ventricular hypertrophy")]
 	Lvh,
-	#[modelfox(value = "ST-T wave abnormality")]
+	#[modelfox(rename = "ST-T wave abnormality")]
 	SttWaveAbnormality,
 }
 
-#[derive(Clone, Debug, modelfox::PredictInputValue)]
+#[derive(Clone, Debug, modelfox::PredictInput)]
 pub enum ExerciseInducedAngina {
-	#[modelfox(value = "no")]
+	#[modelfox(rename = "no")]
 	No,
-	#[modelfox(value = "yes")]
+	#[modelfox(rename = "yes")]
 	Yes,
 }
 
-#[derive(Clone, Debug, modelfox::PredictInputValue)]
+#[derive(Clone, Debug, modelfox::PredictInput)]
 pub enum ExerciseStSlope {
-	#[modelfox(value = "upsloping")]
+	#[modelfox(rename = "upsloping")]
 	Upsloping,
-	#[modelfox(value = "flat")]
+	#[modelfox(rename = "flat")]
 	Flat,
-	#[modelfox(value = "downsloping")]
+	#[modelfox(rename = "downsloping")]
 	Downsloping,
 }
 
-#[derive(Clone, Debug, modelfox::PredictInputValue)]
+#[derive(Clone, Debug, modelfox::PredictInput)]
 pub enum FluoroscopyVesselsColored {
-	#[modelfox(value = "0")]
+	#[modelfox(rename = "0")]
 	Zero,
-	#[modelfox(value = "1")]
+	#[modelfox(rename = "1")]
 	One,
-	#[modelfox(value = "2")]
+	#[modelfox(rename = "2")]
 	Two,
-	#[modelfox(value = "3")]
+	#[modelfox(rename = "3")]
 	Three,
 }
 
-#[derive(Clone, Debug, modelfox::PredictInputValue)]
+#[derive(Clone, Debug, modelfox::PredictInput)]
 pub enum ThalliumStressTest {
-	#[modelfox(value = "normal")]
+	#[modelfox(rename = "normal")]
 	Normal,
-	#[modelfox(value = "reversible defect")]
+	#[modelfox(rename = "reversible defect")]
 	ReversibleDefect,
-	#[modelfox(value = "fixed defect")]
+	#[modelfox(rename = "fixed defect")]
 	FixedDefect,
 }
 
-type Output = modelfox::BinaryClassificationPredictOutput<Diagnosis>;
+#[derive(Debug, modelfox::PredictOutput)]
+pub struct Output {
+	#[modelfox(rename = "class_name")]
+	pub diagnosis: Diagnosis,
+	pub probability: f32,
+	pub feature_contributions: Option<modelfox::FeatureContributions>,
+}
 
-#[derive(Clone, Debug, modelfox::ClassificationOutputValue)]
-enum Diagnosis {
-	#[modelfox(value = "Negative")]
+#[derive(Clone, Debug, modelfox::PredictOutput)]
+pub enum Diagnosis {
+	#[modelfox(rename = "Negative")]
 	Negative,
-	#[modelfox(value = "Positive")]
+	#[modelfox(rename = "Positive")]
 	Positive,
 }
 
@@ -118,6 +124,9 @@ fn main() -> Result<()> {
 	let mut model =
 		modelfox::Model::<Input, Output>::from_path("heart_disease.modelfox", Some(options))?;
 
+	// Check that the fields of `Input` match the columns the model was trained with, and that `Output` matches the model's classes.
+	modelfox::check_schema::<Input, Output>(&model)?;
+
 	// Create an example input matching the schema of the CSV file the model was trained on. Here the data is just hard-coded, but in your application you will probably get this from a database or user input.
 	let input = Input {
 		age: 63.0,
@@ -139,6 +148,7 @@ fn main() -> Result<()> {
 	let options = modelfox::PredictOptions {
 		threshold: Some(0.25),
 		compute_feature_contributions: Some(true),
+		compute_feature_interactions: None,
 	};
 	let output = model.predict_one(input.clone(), Some(options.clone()));
 
@@ -0,0 +1,59 @@
+fn main() {
+	// Load the training data into a table. Here it is read from a CSV file, but in your application you can build the table from any source, or collect it from an iterator of rows.
+	let table = modelfox::Table::from_path(
+		std::path::Path::new("heart_disease.csv"),
+		Default::default(),
+		&mut |_| {},
+	)
+	.unwrap();
+
+	// Only train tree models.
+	let config = modelfox::config::Config {
+		train: modelfox::config::Train {
+			autogrid: Some(modelfox::config::AutoGridOptions {
+				model_types: Some(vec![modelfox::config::ModelType::Tree]),
+			}),
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	// Train the model! Pressing ctrl-c is not handled here, but you can activate the kill chip from another thread to stop training early.
+	let kill_chip = modelfox::KillChip::default();
+	let output = modelfox::train(table, "diagnosis")
+		.config(config)
+		.kill_chip(&kill_chip)
+		.on_progress(|progress_event| {
+			if let modelfox::ProgressEvent::Info(message) = progress_event {
+				println!("{}", message);
+			}
+		})
+		.run()
+		.unwrap();
+
+	// Print the metrics for the best model on the test dataset.
+	if let modelfox::Metrics::BinaryClassification(test_metrics) = &output.summary.test_metrics {
+		println!("AUC ROC: {}", test_metrics.auc_roc_approx);
+	}
+
+	// Save the model and load it to make a prediction.
+	std::fs::write("heart_disease.modelfox", &output.model_bytes).unwrap();
+	let model: modelfox::Model = modelfox::Model::from_bytes(&output.model_bytes, None).unwrap();
+	let input = modelfox::predict_input! {
+		"age": 63.0,
+		"gender": "male",
+		"chest_pain": "typical angina",
+		"resting_blood_pressure": 145.0,
+		"cholesterol": 233.0,
+		"fasting_blood_sugar_greater_than_120": "true",
+		"resting_ecg_result": "probable or definite left ventricular hypertrophy",
+		"exercise_max_heart_rate": 150.0,
+		"exercise_induced_angina": "no",
+		"exercise_st_depression": 2.3,
+		"exercise_st_slope": "downsloping",
+		"fluoroscopy_vessels_colored": 0.0,
+		"thallium_stress_test": "fixed defect",
+	};
+	let output = model.predict_one(input, None);
+	println!("Output: {:?}", output);
+}
@@ -1,5 +1,5 @@
 /*!
-The ModelFox crate makes it easy to make predictions with your ModelFox machine learning model from Rust.
+The ModelFox crate makes it easy to make predictions with your ModelFox machine learning model from Rust. You can also use it to train models with [`train`].
 
 ## Usage
 
@@ -23,15 +23,21 @@ let output = model.predict_one(input, None);
 For more information, [read the docs](https://www.modelfox.dev/docs).
 */
 
-use anyhow::Result;
+pub use self::train::{
+	config, train, KillChip, Metrics, ProgressEvent, Table, Train, TrainData, TrainGridItemSummary,
+	TrainOutput, TrainSummary,
+};
+use anyhow::{anyhow, bail, Result};
 use memmap::Mmap;
 pub use modelfox_macro::{
-	predict_input, ClassificationOutputValue, PredictInput, PredictInputValue,
+	predict_input, ClassificationOutputValue, PredictInput, PredictInputValue, PredictOutput,
 };
 use std::path::Path;
 use std::{collections::BTreeMap, marker::PhantomData};
 use url::Url;
 
+mod train;
+
 /// Use this struct to load a model, make predictions, and log events to the app.
 pub struct Model<Input = PredictInput, Output = PredictOutput>
 where
@@ -146,24 +152,310 @@ impl From<&str> for PredictInputValue {
 	}
 }
 
+/// This trait is implemented by `#[derive(PredictInput)]` on structs. It describes the columns the struct's fields are converted to, so they can be checked against a model with [`check_schema`].
+pub trait PredictInputSchema {
+	fn columns() -> Vec<ColumnSchema>;
+}
+
+// A predict input map can have any columns, so none of them are checked.
+impl PredictInputSchema for PredictInput {
+	fn columns() -> Vec<ColumnSchema> {
+		Vec::new()
+	}
+}
+
+/// This trait is implemented for the types of fields in a struct that derives [`PredictInputSchema`]. It is implemented by `#[derive(PredictInput)]` on enums.
+pub trait PredictInputValueSchema {
+	fn column_type() -> ColumnSchemaType;
+}
+
+macro_rules! impl_predict_input_value_schema {
+	($column_type:expr, $($ty:ty),*) => {
+		$(
+			impl PredictInputValueSchema for $ty {
+				fn column_type() -> ColumnSchemaType {
+					$column_type
+				}
+			}
+		)*
+	};
+}
+
+impl_predict_input_value_schema!(
+	ColumnSchemaType::Number,
+	f64,
+	f32,
+	i32,
+	u32,
+	i16,
+	u16,
+	i8,
+	u8
+);
+impl_predict_input_value_schema!(ColumnSchemaType::Text, String, &str);
+
+impl<T> PredictInputValueSchema for Option<T>
+where
+	T: PredictInputValueSchema,
+{
+	fn column_type() -> ColumnSchemaType {
+		T::column_type()
+	}
+}
+
+/// This is the name and type of a column, either of a model or of a struct that derives [`PredictInputSchema`].
+#[derive(Clone, Debug)]
+pub struct ColumnSchema {
+	pub name: String,
+	pub column_type: ColumnSchemaType,
+}
+
+#[derive(Clone, Debug)]
+pub enum ColumnSchemaType {
+	Unknown,
+	Number,
+	Enum { variants: Vec<String> },
+	Text,
+}
+
+/// This trait is implemented for the output types of a [`Model`], including structs that derive [`PredictOutput`]. It describes the predict outputs the type can be converted from, so they can be checked against a model with [`check_schema`].
+pub trait PredictOutputSchema {
+	fn outputs() -> Vec<OutputSchema>;
+}
+
+/// This is the task of a model, or of a predict output a type can be converted from. The classes of a classification output are `None` if any class name can be converted.
+#[derive(Clone, Debug)]
+pub enum OutputSchema {
+	Regression,
+	BinaryClassification { classes: Option<Vec<String>> },
+	MulticlassClassification { classes: Option<Vec<String>> },
+	Ranking,
+}
+
+impl OutputSchema {
+	fn task(&self) -> &'static str {
+		match self {
+			OutputSchema::Regression => "regression",
+			OutputSchema::BinaryClassification { .. } => "binary classification",
+			OutputSchema::MulticlassClassification { .. } => "multiclass classification",
+			OutputSchema::Ranking => "ranking",
+		}
+	}
+
+	fn classes(&self) -> Option<&[String]> {
+		match self {
+			OutputSchema::BinaryClassification { classes }
+			| OutputSchema::MulticlassClassification { classes } => classes.as_deref(),
+			_ => None,
+		}
+	}
+}
+
+impl PredictOutputSchema for PredictOutput {
+	fn outputs() -> Vec<OutputSchema> {
+		vec![
+			OutputSchema::Regression,
+			OutputSchema::BinaryClassification { classes: None },
+			OutputSchema::MulticlassClassification { classes: None },
+			OutputSchema::Ranking,
+		]
+	}
+}
+
+impl PredictOutputSchema for RegressionPredictOutput {
+	fn outputs() -> Vec<OutputSchema> {
+		vec![OutputSchema::Regression]
+	}
+}
+
+impl PredictOutputSchema for RankingPredictOutput {
+	fn outputs() -> Vec<OutputSchema> {
+		vec![OutputSchema::Ranking]
+	}
+}
+
+impl<T> PredictOutputSchema for BinaryClassificationPredictOutput<T>
+where
+	T: ClassificationOutputValue,
+{
+	fn outputs() -> Vec<OutputSchema> {
+		vec![OutputSchema::BinaryClassification {
+			classes: T::variants(),
+		}]
+	}
+}
+
+impl<T> PredictOutputSchema for MulticlassClassificationPredictOutput<T>
+where
+	T: ClassificationOutputValue,
+{
+	fn outputs() -> Vec<OutputSchema> {
+		vec![OutputSchema::MulticlassClassification {
+			classes: T::variants(),
+		}]
+	}
+}
+
+/// This trait is implemented by [`Model`] to retrieve the columns it was trained with and its task, regardless of its input and output types.
+pub trait ModelSchema {
+	fn columns(&self) -> Vec<ColumnSchema>;
+	fn output(&self) -> OutputSchema;
+}
+
+/// Check that every field of `Input` matches a column the model was trained with, and that every variant of each enum field is a variant of the column. Then check that `Output` can be converted from the predict outputs of the model's task, and that the variants of its class name type are the model's classes. Call this right after loading a model to catch mismatches that would otherwise silently become missing values or panics when making predictions.
+///
+/// ```rust no_run
+/// #[derive(modelfox::PredictInput)]
+/// struct Input {
+///     age: f32,
+///     gender: Gender,
+/// }
+///
+/// #[derive(modelfox::PredictInput)]
+/// enum Gender {
+///     #[modelfox(rename = "male")]
+///     Male,
+///     #[modelfox(rename = "female")]
+///     Female,
+/// }
+///
+/// #[derive(modelfox::PredictOutput)]
+/// struct Output {
+///     class_name: Diagnosis,
+///     probability: f32,
+/// }
+///
+/// #[derive(modelfox::PredictOutput)]
+/// enum Diagnosis {
+///     Negative,
+///     Positive,
+/// }
+///
+/// let model: modelfox::Model<Input, Output> = modelfox::Model::from_path("heart_disease.modelfox", None).unwrap();
+/// modelfox::check_schema::<Input, Output>(&model).unwrap();
+/// ```
+pub fn check_schema<Input, Output>(model: &dyn ModelSchema) -> Result<()>
+where
+	Input: PredictInputSchema,
+	Output: PredictOutputSchema,
+{
+	check_input_schema::<Input>(model)?;
+	check_output_schema::<Output>(model)?;
+	Ok(())
+}
+
+fn check_input_schema<Input>(model: &dyn ModelSchema) -> Result<()>
+where
+	Input: PredictInputSchema,
+{
+	let model_columns = model.columns();
+	for column in Input::columns() {
+		let model_column = model_columns
+			.iter()
+			.find(|model_column| model_column.name == column.name)
+			.ok_or_else(|| {
+				anyhow!(
+					"The field \"{}\" does not match any column in the model.",
+					column.name
+				)
+			})?;
+		match (&column.column_type, &model_column.column_type) {
+			(_, ColumnSchemaType::Unknown)
+			| (ColumnSchemaType::Unknown, _)
+			| (ColumnSchemaType::Text, _) => {}
+			(ColumnSchemaType::Number, ColumnSchemaType::Number) => {}
+			(
+				ColumnSchemaType::Enum { variants },
+				ColumnSchemaType::Enum {
+					variants: model_variants,
+				},
+			) => {
+				for variant in variants {
+					if !model_variants.contains(variant) {
+						bail!(
+							"The variant \"{}\" of the field \"{}\" is not one of the variants of the column in the model: {}.",
+							variant,
+							column.name,
+							model_variants.join(", "),
+						);
+					}
+				}
+			}
+			(ColumnSchemaType::Enum { .. }, ColumnSchemaType::Text) => {}
+			(ColumnSchemaType::Number, _) => bail!(
+				"The field \"{}\" is a number, but the column in the model is not a number column.",
+				column.name
+			),
+			(ColumnSchemaType::Enum { .. }, _) => bail!(
+				"The field \"{}\" is an enum, but the column in the model is not an enum column.",
+				column.name
+			),
+		}
+	}
+	Ok(())
+}
+
+fn check_output_schema<Output>(model: &dyn ModelSchema) -> Result<()>
+where
+	Output: PredictOutputSchema,
+{
+	let model_output = model.output();
+	let outputs = Output::outputs();
+	let output = outputs
+		.iter()
+		.find(|output| output.task() == model_output.task())
+		.ok_or_else(|| {
+			anyhow!(
+				"The output type does not read {} predict outputs, which the model makes.",
+				model_output.task()
+			)
+		})?;
+	if let (Some(classes), Some(model_classes)) = (output.classes(), model_output.classes()) {
+		for class in model_classes {
+			if !classes.contains(class) {
+				bail!(
+					"The class \"{}\" of the model is not one of the variants of the output's class name: {}.",
+					class,
+					classes.join(", "),
+				);
+			}
+		}
+		for class in classes {
+			if !model_classes.contains(class) {
+				bail!(
+					"The variant \"{}\" of the output's class name is not one of the classes of the model: {}.",
+					class,
+					model_classes.join(", "),
+				);
+			}
+		}
+	}
+	Ok(())
+}
+
 /// These are the options passed to [`Model::predict`].
 #[derive(Clone, Debug, serde::Serialize)]
 pub struct PredictOptions {
-	/// If your model is a binary classifier, use this field to make predictions using a threshold chosen on the tuning page of the app. The default value is `0.5`.
+	/// If your model is a binary classifier, use this field to make predictions using a threshold chosen on the tuning page of the app. If not specified, the threshold saved in the model is used, which is `0.5` unless you changed it on the tuning page.
 	pub threshold: Option<f32>,
 	/// Computing feature contributions is disabled by default. If you set this field to `true`, you will be able to access the feature contributions with the `feature_contributions` field of the predict output.
 	pub compute_feature_contributions: Option<bool>,
+	/// Computing feature interactions is disabled by default. If you set this field to `true`, you will be able to access the SHAP interaction values with the `feature_interactions` field of the predict output. Feature interactions are only computed for tree models.
+	pub compute_feature_interactions: Option<bool>,
 }
 
 impl From<PredictOptions> for modelfox_core::predict::PredictOptions {
 	fn from(value: PredictOptions) -> modelfox_core::predict::PredictOptions {
-		let mut options = modelfox_core::predict::PredictOptions::default();
-		if let Some(threshold) = value.threshold {
-			options.threshold = threshold;
-		}
+		let mut options = modelfox_core::predict::PredictOptions {
+			threshold: value.threshold,
+			..Default::default()
+		};
 		if let Some(compute_feature_contributions) = value.compute_feature_contributions {
 			options.compute_feature_contributions = compute_feature_contributions;
 		}
+		if let Some(compute_feature_interactions) = value.compute_feature_interactions {
+			options.compute_feature_interactions = compute_feature_interactions;
+		}
 		options
 	}
 }
@@ -175,6 +467,7 @@ pub enum PredictOutput {
 	Regression(RegressionPredictOutput),
 	BinaryClassification(BinaryClassificationPredictOutput),
 	MulticlassClassification(MulticlassClassificationPredictOutput),
+	Ranking(RankingPredictOutput),
 }
 
 impl From<RegressionPredictOutput> for PredictOutput {
@@ -183,6 +476,12 @@ impl From<RegressionPredictOutput> for PredictOutput {
 	}
 }
 
+impl From<RankingPredictOutput> for PredictOutput {
+	fn from(value: RankingPredictOutput) -> Self {
+		PredictOutput::Ranking(value)
+	}
+}
+
 impl<T> From<BinaryClassificationPredictOutput<T>> for PredictOutput
 where
 	T: ClassificationOutputValue,
@@ -192,6 +491,7 @@ where
 			class_name: value.class_name.as_str().to_owned(),
 			probability: value.probability,
 			feature_contributions: value.feature_contributions,
+			feature_interactions: value.feature_interactions,
 		})
 	}
 }
@@ -206,6 +506,7 @@ where
 			probability: value.probability,
 			probabilities: value.probabilities,
 			feature_contributions: value.feature_contributions,
+			feature_interactions: value.feature_interactions,
 		})
 	}
 }
@@ -222,6 +523,9 @@ impl From<modelfox_core::predict::PredictOutput> for PredictOutput {
 			modelfox_core::predict::PredictOutput::MulticlassClassification(value) => {
 				PredictOutput::MulticlassClassification(value.into())
 			}
+			modelfox_core::predict::PredictOutput::Ranking(value) => {
+				PredictOutput::Ranking(value.into())
+			}
 		}
 	}
 }
@@ -233,6 +537,8 @@ pub struct RegressionPredictOutput {
 	pub value: f32,
 	/// If computing feature contributions was enabled in the predict options, this value will explain the model's output, showing how much each feature contributed to the output.
 	pub feature_contributions: Option<FeatureContributions>,
+	/// If computing feature interactions was enabled in the predict options and the model is a tree model, this value will show how much each pair of features contributed to the output together.
+	pub feature_interactions: Option<FeatureInteractions>,
 }
 
 impl From<modelfox_core::predict::RegressionPredictOutput> for RegressionPredictOutput {
@@ -240,6 +546,7 @@ impl From<modelfox_core::predict::RegressionPredictOutput> for RegressionPredict
 		RegressionPredictOutput {
 			value: value.value,
 			feature_contributions: value.feature_contributions.map(Into::into),
+			feature_interactions: value.feature_interactions.map(Into::into),
 		}
 	}
 }
@@ -253,6 +560,33 @@ impl From<PredictOutput> for RegressionPredictOutput {
 	}
 }
 
+/// This is the output of calling [`Model::predict`] on a `Model` whose task is ranking.
+#[derive(Debug, serde::Serialize)]
+pub struct RankingPredictOutput {
+	/// This is the predicted score. To rank the examples in a query group, sort them by their scores in descending order. Scores are only comparable within a query group.
+	pub score: f32,
+	/// If computing feature contributions was enabled in the predict options, this value will explain the model's output, showing how much each feature contributed to the output.
+	pub feature_contributions: Option<FeatureContributions>,
+}
+
+impl From<modelfox_core::predict::RankingPredictOutput> for RankingPredictOutput {
+	fn from(value: modelfox_core::predict::RankingPredictOutput) -> Self {
+		RankingPredictOutput {
+			score: value.score,
+			feature_contributions: value.feature_contributions.map(Into::into),
+		}
+	}
+}
+
+impl From<PredictOutput> for RankingPredictOutput {
+	fn from(value: PredictOutput) -> Self {
+		match value {
+			PredictOutput::Ranking(value) => value,
+			_ => panic!("expected ranking predict output"),
+		}
+	}
+}
+
 impl<T> From<PredictOutput> for MulticlassClassificationPredictOutput<T>
 where
 	T: ClassificationOutputValue,
@@ -265,6 +599,7 @@ where
 					probability: value.probability,
 					probabilities: value.probabilities,
 					feature_contributions: value.feature_contributions,
+					feature_interactions: value.feature_interactions,
 				}
 			}
 			_ => panic!("expected multiclass classification predict output"),
@@ -275,6 +610,10 @@ where
 pub trait ClassificationOutputValue {
 	fn from_str(value: &str) -> Self;
 	fn as_str(&self) -> &str;
+	/// These are the class names this type can be converted from, or `None` if it can be converted from any class name.
+	fn variants() -> Option<Vec<String>> {
+		None
+	}
 }
 
 impl ClassificationOutputValue for String {
@@ -298,6 +637,8 @@ where
 	pub probability: f32,
 	/// If computing feature contributions was enabled in the predict options, this value will explain the model's output, showing how much each feature contributed to the output.
 	pub feature_contributions: Option<FeatureContributions>,
+	/// If computing feature interactions was enabled in the predict options and the model is a tree model, this value will show how much each pair of features contributed to the output together.
+	pub feature_interactions: Option<FeatureInteractions>,
 }
 
 impl<T> From<modelfox_core::predict::BinaryClassificationPredictOutput>
@@ -310,6 +651,7 @@ where
 			class_name: T::from_str(&value.class_name),
 			probability: value.probability,
 			feature_contributions: value.feature_contributions.map(Into::into),
+			feature_interactions: value.feature_interactions.map(Into::into),
 		}
 	}
 }
@@ -336,6 +678,7 @@ where
 				class_name: T::from_str(&value.class_name),
 				probability: value.probability,
 				feature_contributions: value.feature_contributions,
+				feature_interactions: value.feature_interactions,
 			},
 			_ => panic!("expected binary classification predict output"),
 		}
@@ -356,6 +699,8 @@ where
 	pub probabilities: BTreeMap<String, f32>,
 	/// If computing feature contributions was enabled in the predict options, this value will explain the model's output, showing how much each feature contributed to the output. This value maps from class names to `FeatureContributions` values for each class. The class with the `FeatureContributions` value with the highest `output_value` is the predicted class.
 	pub feature_contributions: Option<BTreeMap<String, FeatureContributions>>,
+	/// If computing feature interactions was enabled in the predict options and the model is a tree model, this value maps from class names to `FeatureInteractions` values for each class.
+	pub feature_interactions: Option<BTreeMap<String, FeatureInteractions>>,
 }
 
 impl<T> From<modelfox_core::predict::MulticlassClassificationPredictOutput>
@@ -374,6 +719,12 @@ where
 					.map(|(key, value)| (key, value.into()))
 					.collect()
 			}),
+			feature_interactions: value.feature_interactions.map(|feature_interactions| {
+				feature_interactions
+					.into_iter()
+					.map(|(key, value)| (key, value.into()))
+					.collect()
+			}),
 		}
 	}
 }
@@ -411,6 +762,69 @@ impl From<modelfox_core::predict::FeatureContributions> for FeatureContributions
 	}
 }
 
+/// This is a description of the SHAP interaction values for the prediction if the task is regression or binary classification, or for a single class if the task is multiclass classification.
+#[derive(Debug, serde::Serialize)]
+pub struct FeatureInteractions {
+	/// This is the value the model would output if all features had baseline values.
+	pub baseline_value: f32,
+	/// This is the value the model output. It is the sum of the `baseline_value`, the main effects, and the interaction values.
+	pub output_value: f32,
+	/// This vec will contain the main effect of each of the model's features, which is its contribution with its interactions with other features removed.
+	pub main_effects: Vec<FeatureMainEffect>,
+	/// This vec will contain one entry for each pair of features that interact, sorted from the largest to the smallest absolute interaction value.
+	pub entries: Vec<FeatureInteractionEntry>,
+}
+
+impl From<modelfox_core::predict::FeatureInteractions> for FeatureInteractions {
+	fn from(value: modelfox_core::predict::FeatureInteractions) -> Self {
+		FeatureInteractions {
+			baseline_value: value.baseline_value,
+			output_value: value.output_value,
+			main_effects: value.main_effects.into_iter().map(Into::into).collect(),
+			entries: value.entries.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// This describes the main effect of a single feature.
+#[derive(Debug, serde::Serialize)]
+pub struct FeatureMainEffect {
+	/// This is the name of the feature.
+	pub feature_name: String,
+	/// This is the amount that the feature contributed to the output on its own.
+	pub main_effect_value: f32,
+}
+
+impl From<modelfox_core::predict::FeatureMainEffect> for FeatureMainEffect {
+	fn from(value: modelfox_core::predict::FeatureMainEffect) -> Self {
+		FeatureMainEffect {
+			feature_name: value.feature_name,
+			main_effect_value: value.main_effect_value,
+		}
+	}
+}
+
+/// This describes the interaction between a pair of features.
+#[derive(Debug, serde::Serialize)]
+pub struct FeatureInteractionEntry {
+	/// This is the name of the first feature.
+	pub feature_name_a: String,
+	/// This is the name of the second feature.
+	pub feature_name_b: String,
+	/// This is the amount that the two features contributed to the output together, beyond their main effects.
+	pub feature_interaction_value: f32,
+}
+
+impl From<modelfox_core::predict::FeatureInteractionEntry> for FeatureInteractionEntry {
+	fn from(value: modelfox_core::predict::FeatureInteractionEntry) -> Self {
+		FeatureInteractionEntry {
+			feature_name_a: value.feature_name_a,
+			feature_name_b: value.feature_name_b,
+			feature_interaction_value: value.feature_interaction_value,
+		}
+	}
+}
+
 /// This identifies the type of a feature contribution.
 #[derive(Debug, serde::Serialize)]
 #[serde(tag = "type")]
@@ -427,6 +841,16 @@ pub enum FeatureContributionEntry {
 	BagOfWordsCosineSimilarity(BagOfWordsCosineSimilarityFeatureContribution),
 	#[serde(rename = "word_embedding")]
 	WordEmbedding(WordEmbeddingFeatureContribution),
+	#[serde(rename = "hashed_bag_of_words")]
+	HashedBagOfWords(HashedBagOfWordsFeatureContribution),
+	#[serde(rename = "hashed_enum")]
+	HashedEnum(HashedEnumFeatureContribution),
+	#[serde(rename = "interaction")]
+	Interaction(InteractionFeatureContribution),
+	#[serde(rename = "polynomial")]
+	Polynomial(PolynomialFeatureContribution),
+	#[serde(rename = "binned_number")]
+	BinnedNumber(BinnedNumberFeatureContribution),
 }
 
 impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContributionEntry {
@@ -450,6 +874,21 @@ impl From<modelfox_core::predict::FeatureContributionEntry> for FeatureContribut
 			modelfox_core::predict::FeatureContributionEntry::WordEmbedding(value) => {
 				FeatureContributionEntry::WordEmbedding(value.into())
 			}
+			modelfox_core::predict::FeatureContributionEntry::HashedBagOfWords(value) => {
+				FeatureContributionEntry::HashedBagOfWords(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::HashedEnum(value) => {
+				FeatureContributionEntry::HashedEnum(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::Interaction(value) => {
+				FeatureContributionEntry::Interaction(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::Polynomial(value) => {
+				FeatureContributionEntry::Polynomial(value.into())
+			}
+			modelfox_core::predict::FeatureContributionEntry::BinnedNumber(value) => {
+				FeatureContributionEntry::BinnedNumber(value.into())
+			}
 		}
 	}
 }
@@ -545,12 +984,13 @@ impl From<modelfox_core::predict::BagOfWordsFeatureContribution> for BagOfWordsF
 	}
 }
 
-/// This is a sequence of `n` tokens. ModelFox currently supports unigrams and bigrams.
+/// This is a sequence of `n` tokens, or a sequence of characters within a token. ModelFox currently supports unigrams, bigrams, and character ngrams.
 #[derive(Debug, serde::Serialize)]
 #[serde(untagged)]
 pub enum NGram {
 	Unigram(String),
 	Bigram(String, String),
+	Character { character: String },
 }
 
 impl From<modelfox_core::predict::NGram> for NGram {
@@ -560,6 +1000,7 @@ impl From<modelfox_core::predict::NGram> for NGram {
 			modelfox_core::predict::NGram::Bigram(token_a, token_b) => {
 				NGram::Bigram(token_a, token_b)
 			}
+			modelfox_core::predict::NGram::Character(character) => NGram::Character { character },
 		}
 	}
 }
@@ -613,6 +1054,147 @@ impl From<modelfox_core::predict::WordEmbeddingFeatureContribution>
 	}
 }
 
+/// This describes the contribution of a feature from a hashed bag of words feature group.
+#[derive(Debug, serde::Serialize)]
+pub struct HashedBagOfWordsFeatureContribution {
+	/// This is the name of the source column for the feature group.
+	pub column_name: String,
+	/// This is the index of the bucket that this feature corresponds to.
+	pub bucket: usize,
+	/// These are the most frequent ngrams in the training data that hash to this bucket.
+	pub ngrams: Vec<NGram>,
+	/// This is the value of the feature.
+	pub feature_value: f32,
+	/// This is the amount that the feature contributed to the output.
+	pub feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::HashedBagOfWordsFeatureContribution>
+	for HashedBagOfWordsFeatureContribution
+{
+	fn from(value: modelfox_core::predict::HashedBagOfWordsFeatureContribution) -> Self {
+		HashedBagOfWordsFeatureContribution {
+			column_name: value.column_name,
+			bucket: value.bucket,
+			ngrams: value.ngrams.into_iter().map(Into::into).collect(),
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+/// This describes the contribution of a feature from a hashed enum feature group.
+#[derive(Debug, serde::Serialize)]
+pub struct HashedEnumFeatureContribution {
+	/// This is the name of the source column for the feature group.
+	pub column_name: String,
+	/// This is the index of the bucket that this feature corresponds to.
+	pub bucket: usize,
+	/// These are the most frequent values in the training data that hash to this bucket.
+	pub values: Vec<String>,
+	/// This is the value of the feature.
+	pub feature_value: f32,
+	/// This is the amount that the feature contributed to the output.
+	pub feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::HashedEnumFeatureContribution> for HashedEnumFeatureContribution {
+	fn from(value: modelfox_core::predict::HashedEnumFeatureContribution) -> Self {
+		HashedEnumFeatureContribution {
+			column_name: value.column_name,
+			bucket: value.bucket,
+			values: value.values,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+/// This describes the contribution of a feature from an interaction feature group.
+#[derive(Debug, serde::Serialize)]
+pub struct InteractionFeatureContribution {
+	/// This is the name of the first source column for the feature group.
+	pub column_name_a: String,
+	/// This is the enum variant of the first source column that the feature corresponds to, if it is an enum column.
+	pub variant_a: Option<String>,
+	/// This is the name of the second source column for the feature group.
+	pub column_name_b: String,
+	/// This is the enum variant of the second source column that the feature corresponds to, if it is an enum column.
+	pub variant_b: Option<String>,
+	/// This is the value of the feature.
+	pub feature_value: f32,
+	/// This is the amount that the feature contributed to the output.
+	pub feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::InteractionFeatureContribution>
+	for InteractionFeatureContribution
+{
+	fn from(value: modelfox_core::predict::InteractionFeatureContribution) -> Self {
+		InteractionFeatureContribution {
+			column_name_a: value.column_name_a,
+			variant_a: value.variant_a,
+			column_name_b: value.column_name_b,
+			variant_b: value.variant_b,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+/// This describes the contribution of a feature from a polynomial feature group.
+#[derive(Debug, serde::Serialize)]
+pub struct PolynomialFeatureContribution {
+	/// This is the name of the source column for the feature group.
+	pub column_name: String,
+	/// This is the power that the normalized column value is raised to.
+	pub power: usize,
+	/// This is the value of the feature.
+	pub feature_value: f32,
+	/// This is the amount that the feature contributed to the output.
+	pub feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::PolynomialFeatureContribution> for PolynomialFeatureContribution {
+	fn from(value: modelfox_core::predict::PolynomialFeatureContribution) -> Self {
+		PolynomialFeatureContribution {
+			column_name: value.column_name,
+			power: value.power,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
+/// This describes the contribution of a feature from a binned number feature group.
+#[derive(Debug, serde::Serialize)]
+pub struct BinnedNumberFeatureContribution {
+	/// This is the name of the source column for the feature group.
+	pub column_name: String,
+	/// This is the lower bound of the bin, or `None` for the first bin.
+	pub bin_start: Option<f32>,
+	/// This is the upper bound of the bin, or `None` for the last bin.
+	pub bin_end: Option<f32>,
+	/// This is the value of the feature.
+	pub feature_value: bool,
+	/// This is the amount that the feature contributed to the output.
+	pub feature_contribution_value: f32,
+}
+
+impl From<modelfox_core::predict::BinnedNumberFeatureContribution>
+	for BinnedNumberFeatureContribution
+{
+	fn from(value: modelfox_core::predict::BinnedNumberFeatureContribution) -> Self {
+		BinnedNumberFeatureContribution {
+			column_name: value.column_name,
+			bin_start: value.bin_start,
+			bin_end: value.bin_end,
+			feature_value: value.feature_value,
+			feature_contribution_value: value.feature_contribution_value,
+		}
+	}
+}
+
 /// This is the type of the argument to [`Model::log_prediction`] and [`Model::enqueue_log_prediction`] which specifies the details of the prediction to log.
 #[derive(Debug)]
 pub struct LogPredictionArgs<Input, Output>
@@ -691,6 +1273,64 @@ impl From<&str> for NumberOrString {
 	}
 }
 
+impl<Input, Output> ModelSchema for Model<Input, Output>
+where
+	Input: Into<PredictInput>,
+	Output: From<PredictOutput> + Into<PredictOutput>,
+{
+	fn columns(&self) -> Vec<ColumnSchema> {
+		let columns = match &self.model.inner {
+			modelfox_core::predict::ModelInner::Regressor(model) => &model.columns,
+			modelfox_core::predict::ModelInner::BinaryClassifier(model) => &model.columns,
+			modelfox_core::predict::ModelInner::MulticlassClassifier(model) => &model.columns,
+			modelfox_core::predict::ModelInner::Ranker(model) => &model.columns,
+		};
+		columns
+			.iter()
+			.map(|column| match column {
+				modelfox_core::predict::Column::Unknown(column) => ColumnSchema {
+					name: column.name.clone(),
+					column_type: ColumnSchemaType::Unknown,
+				},
+				modelfox_core::predict::Column::Number(column) => ColumnSchema {
+					name: column.name.clone(),
+					column_type: ColumnSchemaType::Number,
+				},
+				modelfox_core::predict::Column::Enum(column) => ColumnSchema {
+					name: column.name.clone(),
+					column_type: ColumnSchemaType::Enum {
+						variants: column.variants.clone(),
+					},
+				},
+				modelfox_core::predict::Column::Text(column) => ColumnSchema {
+					name: column.name.clone(),
+					column_type: ColumnSchemaType::Text,
+				},
+			})
+			.collect()
+	}
+
+	fn output(&self) -> OutputSchema {
+		match &self.model.inner {
+			modelfox_core::predict::ModelInner::Regressor(_) => OutputSchema::Regression,
+			modelfox_core::predict::ModelInner::BinaryClassifier(model) => {
+				OutputSchema::BinaryClassification {
+					classes: Some(vec![
+						model.negative_class.clone(),
+						model.positive_class.clone(),
+					]),
+				}
+			}
+			modelfox_core::predict::ModelInner::MulticlassClassifier(model) => {
+				OutputSchema::MulticlassClassification {
+					classes: Some(model.classes.clone()),
+				}
+			}
+			modelfox_core::predict::ModelInner::Ranker(_) => OutputSchema::Ranking,
+		}
+	}
+}
+
 /// Use this struct to load a model, make predictions, and log events to the app.
 impl<Input, Output> Model<Input, Output>
 where
@@ -70,32 +70,48 @@ fn predict_input_derive_macro_impl(
 	input: proc_macro2::TokenStream,
 ) -> syn::Result<proc_macro2::TokenStream> {
 	let input: syn::DeriveInput = syn::parse2(input)?;
-	let ident = &input.ident;
-	let data = match &input.data {
-		syn::Data::Struct(data) => data,
-		_ => {
-			return Err(syn::Error::new_spanned(
-				input,
-				"this macro can only be used on a struct",
-			))
-		}
-	};
-	let insert_statements = data
-		.fields
-		.iter()
-		.map(|field| {
-			let field_ident = field
-				.ident
-				.as_ref()
-				.ok_or_else(|| syn::Error::new(field.span(), "field must have ident"))?;
-			let column_name =
-				predict_input_field_rename(field)?.unwrap_or_else(|| field_ident.to_string());
-			let code = quote! {
+	match &input.data {
+		syn::Data::Struct(data) => predict_input_struct(&input.ident, data),
+		syn::Data::Enum(data) => predict_input_value_enum(&input.ident, data),
+		_ => Err(syn::Error::new_spanned(
+			input,
+			"this macro can only be used on a struct or an enum",
+		)),
+	}
+}
+
+fn predict_input_struct(
+	ident: &syn::Ident,
+	data: &syn::DataStruct,
+) -> syn::Result<proc_macro2::TokenStream> {
+	let mut insert_statements = Vec::new();
+	let mut column_schemas = Vec::new();
+	for field in data.fields.iter() {
+		let field_ident = field
+			.ident
+			.as_ref()
+			.ok_or_else(|| syn::Error::new(field.span(), "field must have ident"))?;
+		let field_ty = &field.ty;
+		let column_name = rename(&field.attrs)?.unwrap_or_else(|| field_ident.to_string());
+		let insert_statement = if is_option(field_ty) {
+			quote! {
+				if let Some(field_value) = value.#field_ident {
+					map.insert(#column_name.to_owned(), field_value.into());
+				}
+			}
+		} else {
+			quote! {
 				map.insert(#column_name.to_owned(), value.#field_ident.into());
-			};
-			Ok(code)
-		})
-		.collect::<syn::Result<Vec<_>>>()?;
+			}
+		};
+		insert_statements.push(insert_statement);
+		column_schemas.push(quote! {
+			modelfox::ColumnSchema {
+				name: #column_name.to_owned(),
+				column_type: <#field_ty as modelfox::PredictInputValueSchema>::column_type(),
+			}
+		});
+	}
 	let code = quote! {
 		impl From<#ident> for modelfox::PredictInput {
 			fn from(value: #ident) -> modelfox::PredictInput {
@@ -104,52 +120,13 @@ fn predict_input_derive_macro_impl(
 				modelfox::PredictInput(map)
 			}
 		}
-	};
-	Ok(code)
-}
-
-fn predict_input_field_rename(field: &syn::Field) -> syn::Result<Option<String>> {
-	let attr = field
-		.attrs
-		.iter()
-		.find(|attr| attr.path.is_ident("modelfox"));
-	let attr = if let Some(attr) = attr {
-		attr
-	} else {
-		return Ok(None);
-	};
-	let meta = attr.parse_meta()?;
-	let list = match meta {
-		syn::Meta::List(list) => list,
-		_ => {
-			return Err(syn::Error::new_spanned(
-				attr,
-				"modelfox attribute must contain a list",
-			))
-		}
-	};
-	let mut rename = None;
-	for item in list.nested.iter() {
-		match item {
-			syn::NestedMeta::Meta(syn::Meta::NameValue(item)) if item.path.is_ident("rename") => {
-				let value = if let syn::Lit::Str(value) = &item.lit {
-					Some(value)
-				} else {
-					None
-				};
-				let value = value.ok_or_else(|| {
-					syn::Error::new_spanned(&item, "value for attribute \"value\" must be a string")
-				})?;
-				rename = Some(value);
+		impl modelfox::PredictInputSchema for #ident {
+			fn columns() -> Vec<modelfox::ColumnSchema> {
+				vec![#(#column_schemas,)*]
 			}
-			_ => {}
 		}
-	}
-	let rename = rename.ok_or_else(|| {
-		syn::Error::new_spanned(&list.nested, "an attribute with key \"value\" is required")
-	})?;
-	let rename = rename.value();
-	Ok(Some(rename))
+	};
+	Ok(code)
 }
 
 #[proc_macro_derive(PredictInputValue, attributes(modelfox))]
@@ -163,27 +140,29 @@ fn predict_input_value_derive_macro_impl(
 	input: proc_macro2::TokenStream,
 ) -> syn::Result<proc_macro2::TokenStream> {
 	let input: syn::DeriveInput = syn::parse2(input)?;
-	let ident = &input.ident;
-	let data = match &input.data {
-		syn::Data::Enum(data) => data,
-		_ => {
-			return Err(syn::Error::new(
-				input.span(),
-				"this macro can only be used on an enum",
-			))
-		}
-	};
+	match &input.data {
+		syn::Data::Enum(data) => predict_input_value_enum(&input.ident, data),
+		_ => Err(syn::Error::new(
+			input.span(),
+			"this macro can only be used on an enum",
+		)),
+	}
+}
+
+fn predict_input_value_enum(
+	ident: &syn::Ident,
+	data: &syn::DataEnum,
+) -> syn::Result<proc_macro2::TokenStream> {
+	let variant_values = variant_values(data)?;
 	let match_arms = data
 		.variants
 		.iter()
-		.map(|variant| {
+		.zip(variant_values.iter())
+		.map(|(variant, variant_value)| {
 			let variant_ident = &variant.ident;
-			let variant_value = predict_input_value_variant_value(variant)?
-				.unwrap_or_else(|| variant_ident.to_string());
-			let code = quote! { #ident::#variant_ident => #variant_value };
-			Ok(code)
+			quote! { #ident::#variant_ident => #variant_value }
 		})
-		.collect::<syn::Result<Vec<_>>>()?;
+		.collect::<Vec<_>>();
 	let code = quote! {
 		impl From<#ident> for modelfox::PredictInputValue {
 			fn from(value: #ident) -> modelfox::PredictInputValue {
@@ -193,52 +172,268 @@ fn predict_input_value_derive_macro_impl(
 				modelfox::PredictInputValue::String(value.to_owned())
 			}
 		}
+		impl modelfox::PredictInputValueSchema for #ident {
+			fn column_type() -> modelfox::ColumnSchemaType {
+				modelfox::ColumnSchemaType::Enum {
+					variants: vec![#(#variant_values.to_owned(),)*],
+				}
+			}
+		}
 	};
 	Ok(code)
 }
 
-fn predict_input_value_variant_value(variant: &syn::Variant) -> syn::Result<Option<String>> {
-	let attr = variant
-		.attrs
-		.iter()
-		.find(|attr| attr.path.is_ident("modelfox"));
-	let attr = if let Some(attr) = attr {
-		attr
-	} else {
-		return Ok(None);
-	};
-	let meta = attr.parse_meta()?;
-	let list = match meta {
-		syn::Meta::List(list) => list,
-		_ => {
+#[proc_macro_derive(PredictOutput, attributes(modelfox))]
+pub fn predict_output_derive_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+	predict_output_derive_macro_impl(input.into())
+		.unwrap_or_else(|e| e.to_compile_error())
+		.into()
+}
+
+fn predict_output_derive_macro_impl(
+	input: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+	let input: syn::DeriveInput = syn::parse2(input)?;
+	match &input.data {
+		syn::Data::Struct(data) => predict_output_struct(&input.ident, data),
+		syn::Data::Enum(data) => classification_output_value_enum(&input.ident, data),
+		_ => Err(syn::Error::new_spanned(
+			input,
+			"this macro can only be used on a struct or an enum",
+		)),
+	}
+}
+
+const REGRESSION_OUTPUT_FIELDS: &[&str] =
+	&["value", "feature_contributions", "feature_interactions"];
+const RANKING_OUTPUT_FIELDS: &[&str] = &["score", "feature_contributions"];
+const BINARY_CLASSIFICATION_OUTPUT_FIELDS: &[&str] = &[
+	"class_name",
+	"probability",
+	"feature_contributions",
+	"feature_interactions",
+];
+const MULTICLASS_CLASSIFICATION_OUTPUT_FIELDS: &[&str] = &[
+	"class_name",
+	"probability",
+	"probabilities",
+	"feature_contributions",
+	"feature_interactions",
+];
+
+fn predict_output_struct(
+	ident: &syn::Ident,
+	data: &syn::DataStruct,
+) -> syn::Result<proc_macro2::TokenStream> {
+	// Map each field to the field of the predict output it is read from.
+	let mut fields = Vec::new();
+	for field in data.fields.iter() {
+		let field_ident = field
+			.ident
+			.as_ref()
+			.ok_or_else(|| syn::Error::new(field.span(), "field must have ident"))?;
+		let output_field_name = rename(&field.attrs)?.unwrap_or_else(|| field_ident.to_string());
+		if !MULTICLASS_CLASSIFICATION_OUTPUT_FIELDS.contains(&output_field_name.as_str())
+			&& !REGRESSION_OUTPUT_FIELDS.contains(&output_field_name.as_str())
+			&& !RANKING_OUTPUT_FIELDS.contains(&output_field_name.as_str())
+		{
 			return Err(syn::Error::new_spanned(
-				attr,
-				"modelfox attribute must contain a list",
-			))
+				field,
+				format!(
+					"\"{}\" is not a predict output field, expected one of \"value\", \"score\", \"class_name\", \"probability\", \"probabilities\", \"feature_contributions\", or \"feature_interactions\"",
+					output_field_name
+				),
+			));
 		}
+		fields.push((field_ident, &field.ty, output_field_name));
+	}
+	let has_field = |name: &str| fields.iter().any(|(_, _, field_name)| field_name == name);
+	let has_only_fields = |names: &[&str]| {
+		fields
+			.iter()
+			.all(|(_, _, field_name)| names.contains(&field_name.as_str()))
 	};
-	let mut input_value = None;
-	for item in list.nested.iter() {
-		match item {
-			syn::NestedMeta::Meta(syn::Meta::NameValue(item)) if item.path.is_ident("value") => {
-				let value = if let syn::Lit::Str(value) = &item.lit {
-					Some(value)
-				} else {
-					None
-				};
-				let value = value.ok_or_else(|| {
-					syn::Error::new_spanned(&item, "value for attribute \"value\" must be a string")
-				})?;
-				input_value = Some(value);
+	let is_regression = has_field("value");
+	let is_classification = has_field("class_name") && has_field("probability");
+	let is_ranking = has_field("score");
+	if [is_regression, is_classification, is_ranking]
+		.iter()
+		.filter(|is_task| **is_task)
+		.count() != 1
+	{
+		return Err(syn::Error::new(
+			ident.span(),
+			"a struct deriving PredictOutput must have either a \"value\" field, a \"score\" field, or \"class_name\" and \"probability\" fields",
+		));
+	}
+	// The feature contributions and interactions of multiclass classification outputs are maps from class names, so a struct with these fields only reads multiclass classification outputs if it also has a "probabilities" field.
+	let has_feature_explanations =
+		has_field("feature_contributions") || has_field("feature_interactions");
+	let is_binary_classification =
+		is_classification && has_only_fields(BINARY_CLASSIFICATION_OUTPUT_FIELDS);
+	let is_multiclass_classification = is_classification
+		&& has_only_fields(MULTICLASS_CLASSIFICATION_OUTPUT_FIELDS)
+		&& (has_field("probabilities") || !has_feature_explanations);
+	let is_regression = is_regression && has_only_fields(REGRESSION_OUTPUT_FIELDS);
+	let is_ranking = is_ranking && has_only_fields(RANKING_OUTPUT_FIELDS);
+	if !is_regression && !is_binary_classification && !is_multiclass_classification && !is_ranking {
+		return Err(syn::Error::new(
+			ident.span(),
+			"the fields of this struct do not match any predict output",
+		));
+	}
+	let from_field_values = fields
+		.iter()
+		.map(|(field_ident, _, output_field_name)| {
+			let output_field_ident = quote::format_ident!("{}", output_field_name);
+			if output_field_name == "class_name" {
+				quote! { #field_ident: modelfox::ClassificationOutputValue::from_str(&value.class_name) }
+			} else {
+				quote! { #field_ident: value.#output_field_ident }
+			}
+		})
+		.collect::<Vec<_>>();
+	let mut from_match_arms = Vec::new();
+	if is_regression {
+		from_match_arms.push(quote! {
+			modelfox::PredictOutput::Regression(value) => #ident { #(#from_field_values,)* }
+		});
+	}
+	if is_binary_classification {
+		from_match_arms.push(quote! {
+			modelfox::PredictOutput::BinaryClassification(value) => #ident { #(#from_field_values,)* }
+		});
+	}
+	if is_multiclass_classification {
+		from_match_arms.push(quote! {
+			modelfox::PredictOutput::MulticlassClassification(value) => #ident { #(#from_field_values,)* }
+		});
+	}
+	if is_ranking {
+		from_match_arms.push(quote! {
+			modelfox::PredictOutput::Ranking(value) => #ident { #(#from_field_values,)* }
+		});
+	}
+	let expected_outputs = [
+		(is_regression, "regression"),
+		(is_binary_classification, "binary classification"),
+		(is_multiclass_classification, "multiclass classification"),
+		(is_ranking, "ranking"),
+	]
+	.iter()
+	.filter(|(is_expected, _)| *is_expected)
+	.map(|(_, name)| *name)
+	.collect::<Vec<_>>()
+	.join(" or ");
+	let panic_message = format!("expected {} predict output", expected_outputs);
+	if from_match_arms.len() < 4 {
+		from_match_arms.push(quote! { _ => panic!(#panic_message) });
+	}
+	let output_field_value = |name: &str| {
+		let field_ident = fields
+			.iter()
+			.find(|(_, _, field_name)| field_name == name)
+			.map(|(field_ident, _, _)| field_ident);
+		match field_ident {
+			Some(field_ident) if name == "class_name" => {
+				quote! { modelfox::ClassificationOutputValue::as_str(&value.#field_ident).to_owned() }
+			}
+			Some(field_ident) => quote! { value.#field_ident },
+			None => quote! { Default::default() },
+		}
+	};
+	// Convert back to the most specific predict output the fields allow.
+	let into_output = if is_regression {
+		let value = output_field_value("value");
+		let feature_contributions = output_field_value("feature_contributions");
+		let feature_interactions = output_field_value("feature_interactions");
+		quote! {
+			modelfox::PredictOutput::Regression(modelfox::RegressionPredictOutput {
+				value: #value,
+				feature_contributions: #feature_contributions,
+				feature_interactions: #feature_interactions,
+			})
+		}
+	} else if is_ranking {
+		let score = output_field_value("score");
+		let feature_contributions = output_field_value("feature_contributions");
+		quote! {
+			modelfox::PredictOutput::Ranking(modelfox::RankingPredictOutput {
+				score: #score,
+				feature_contributions: #feature_contributions,
+			})
+		}
+	} else {
+		let class_name = output_field_value("class_name");
+		let probability = output_field_value("probability");
+		let feature_contributions = output_field_value("feature_contributions");
+		let feature_interactions = output_field_value("feature_interactions");
+		if has_field("probabilities") {
+			let probabilities = output_field_value("probabilities");
+			quote! {
+				modelfox::PredictOutput::MulticlassClassification(modelfox::MulticlassClassificationPredictOutput {
+					class_name: #class_name,
+					probability: #probability,
+					probabilities: #probabilities,
+					feature_contributions: #feature_contributions,
+					feature_interactions: #feature_interactions,
+				})
+			}
+		} else {
+			quote! {
+				modelfox::PredictOutput::BinaryClassification(modelfox::BinaryClassificationPredictOutput {
+					class_name: #class_name,
+					probability: #probability,
+					feature_contributions: #feature_contributions,
+					feature_interactions: #feature_interactions,
+				})
 			}
-			_ => {}
 		}
+	};
+	// The classes of a classification output are the variants of the type of its "class_name" field.
+	let classes = fields
+		.iter()
+		.find(|(_, _, field_name)| field_name == "class_name")
+		.map(
+			|(_, field_ty, _)| quote! { <#field_ty as modelfox::ClassificationOutputValue>::variants() },
+		)
+		.unwrap_or_else(|| quote! { None });
+	let mut output_schemas = Vec::new();
+	if is_regression {
+		output_schemas.push(quote! { modelfox::OutputSchema::Regression });
 	}
-	let input_value = input_value.ok_or_else(|| {
-		syn::Error::new_spanned(&list.nested, "an attribute with key \"value\" is required")
-	})?;
-	let input_value = input_value.value();
-	Ok(Some(input_value))
+	if is_binary_classification {
+		output_schemas
+			.push(quote! { modelfox::OutputSchema::BinaryClassification { classes: #classes } });
+	}
+	if is_multiclass_classification {
+		output_schemas.push(
+			quote! { modelfox::OutputSchema::MulticlassClassification { classes: #classes } },
+		);
+	}
+	if is_ranking {
+		output_schemas.push(quote! { modelfox::OutputSchema::Ranking });
+	}
+	let code = quote! {
+		impl From<modelfox::PredictOutput> for #ident {
+			fn from(value: modelfox::PredictOutput) -> #ident {
+				match value {
+					#(#from_match_arms,)*
+				}
+			}
+		}
+		impl From<#ident> for modelfox::PredictOutput {
+			fn from(value: #ident) -> modelfox::PredictOutput {
+				#into_output
+			}
+		}
+		impl modelfox::PredictOutputSchema for #ident {
+			fn outputs() -> Vec<modelfox::OutputSchema> {
+				vec![#(#output_schemas,)*]
+			}
+		}
+	};
+	Ok(code)
 }
 
 #[proc_macro_derive(ClassificationOutputValue, attributes(modelfox))]
@@ -254,38 +449,38 @@ fn classification_output_value_derive_macro_impl(
 	input: proc_macro2::TokenStream,
 ) -> syn::Result<proc_macro2::TokenStream> {
 	let input: syn::DeriveInput = syn::parse2(input)?;
-	let ident = &input.ident;
-	let data = match &input.data {
-		syn::Data::Enum(data) => data,
-		_ => {
-			return Err(syn::Error::new(
-				input.span(),
-				"this macro can only be used on an enum",
-			))
-		}
-	};
+	match &input.data {
+		syn::Data::Enum(data) => classification_output_value_enum(&input.ident, data),
+		_ => Err(syn::Error::new(
+			input.span(),
+			"this macro can only be used on an enum",
+		)),
+	}
+}
+
+fn classification_output_value_enum(
+	ident: &syn::Ident,
+	data: &syn::DataEnum,
+) -> syn::Result<proc_macro2::TokenStream> {
+	let variant_values = variant_values(data)?;
 	let from_str_match_arms = data
 		.variants
 		.iter()
-		.map(|variant| {
+		.zip(variant_values.iter())
+		.map(|(variant, variant_value)| {
 			let variant_ident = &variant.ident;
-			let variant_value = classification_output_value_variant_value(variant)?
-				.unwrap_or_else(|| variant_ident.to_string());
-			let code = quote! { #variant_value => #ident::#variant_ident };
-			Ok(code)
+			quote! { #variant_value => #ident::#variant_ident }
 		})
-		.collect::<syn::Result<Vec<_>>>()?;
+		.collect::<Vec<_>>();
 	let as_str_match_arms = data
 		.variants
 		.iter()
-		.map(|variant| {
+		.zip(variant_values.iter())
+		.map(|(variant, variant_value)| {
 			let variant_ident = &variant.ident;
-			let variant_value = classification_output_value_variant_value(variant)?
-				.unwrap_or_else(|| variant_ident.to_string());
-			let code = quote! { #ident::#variant_ident => #variant_value };
-			Ok(code)
+			quote! { #ident::#variant_ident => #variant_value }
 		})
-		.collect::<syn::Result<Vec<_>>>()?;
+		.collect::<Vec<_>>();
 	let code = quote! {
 		impl modelfox::ClassificationOutputValue for #ident {
 			fn from_str(value: &str) -> Self {
@@ -299,18 +494,33 @@ fn classification_output_value_derive_macro_impl(
 					#(#as_str_match_arms,)*
 				}
 			}
+			fn variants() -> Option<Vec<String>> {
+				Some(vec![#(#variant_values.to_owned(),)*])
+			}
 		}
 	};
 	Ok(code)
 }
 
-fn classification_output_value_variant_value(
-	variant: &syn::Variant,
-) -> syn::Result<Option<String>> {
-	let attr = variant
-		.attrs
+/// Get the value for each variant of an enum, which is its name unless it is renamed with an attribute.
+fn variant_values(data: &syn::DataEnum) -> syn::Result<Vec<String>> {
+	data.variants
 		.iter()
-		.find(|attr| attr.path.is_ident("modelfox"));
+		.map(|variant| {
+			if !matches!(variant.fields, syn::Fields::Unit) {
+				return Err(syn::Error::new_spanned(
+					variant,
+					"variants must not have fields",
+				));
+			}
+			Ok(rename(&variant.attrs)?.unwrap_or_else(|| variant.ident.to_string()))
+		})
+		.collect()
+}
+
+/// Read the name from a `#[modelfox(rename = "...")]` attribute. `#[modelfox(value = "...")]` is accepted as well.
+fn rename(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
+	let attr = attrs.iter().find(|attr| attr.path.is_ident("modelfox"));
 	let attr = if let Some(attr) = attr {
 		attr
 	} else {
@@ -326,26 +536,48 @@ fn classification_output_value_variant_value(
 			))
 		}
 	};
-	let mut input_value = None;
+	let mut rename = None;
 	for item in list.nested.iter() {
 		match item {
-			syn::NestedMeta::Meta(syn::Meta::NameValue(item)) if item.path.is_ident("value") => {
+			syn::NestedMeta::Meta(syn::Meta::NameValue(item))
+				if item.path.is_ident("rename") || item.path.is_ident("value") =>
+			{
 				let value = if let syn::Lit::Str(value) = &item.lit {
 					Some(value)
 				} else {
 					None
 				};
 				let value = value.ok_or_else(|| {
-					syn::Error::new_spanned(&item, "value for attribute \"value\" must be a string")
+					syn::Error::new_spanned(
+						&item,
+						"value for attribute \"rename\" must be a string",
+					)
 				})?;
-				input_value = Some(value);
+				rename = Some(value);
+			}
+			_ => {
+				return Err(syn::Error::new_spanned(
+					item,
+					"unknown attribute, expected \"rename\"",
+				))
 			}
-			_ => {}
 		}
 	}
-	let input_value = input_value.ok_or_else(|| {
-		syn::Error::new_spanned(&list.nested, "an attribute with key \"value\" is required")
+	let rename = rename.ok_or_else(|| {
+		syn::Error::new_spanned(&list.nested, "an attribute with key \"rename\" is required")
 	})?;
-	let input_value = input_value.value();
-	Ok(Some(input_value))
+	Ok(Some(rename.value()))
+}
+
+/// Check if a type is written as `Option<...>`, in which case a `None` value is left out of the predict input.
+fn is_option(ty: &syn::Type) -> bool {
+	match ty {
+		syn::Type::Path(ty) => ty
+			.path
+			.segments
+			.last()
+			.map(|segment| segment.ident == "Option")
+			.unwrap_or(false),
+		_ => false,
+	}
 }
@@ -0,0 +1,189 @@
+use std::path::Path;
+
+#[derive(modelfox::PredictInput)]
+struct Input {
+	age: f32,
+	#[modelfox(rename = "gender")]
+	sex: Gender,
+	chest_pain: Option<ChestPain>,
+}
+
+#[derive(Clone, Copy, modelfox::PredictInput)]
+enum Gender {
+	#[modelfox(rename = "male")]
+	Male,
+	#[modelfox(rename = "female")]
+	Female,
+}
+
+#[derive(modelfox::PredictInput)]
+enum ChestPain {
+	#[modelfox(rename = "asymptomatic")]
+	Asymptomatic,
+	#[modelfox(rename = "non-angina pain")]
+	NonAnginaPain,
+	#[modelfox(rename = "atypical angina")]
+	AtypicalAngina,
+	#[modelfox(rename = "typical angina")]
+	TypicalAngina,
+}
+
+#[derive(modelfox::PredictOutput)]
+struct Output {
+	#[modelfox(rename = "class_name")]
+	diagnosis: Diagnosis,
+	probability: f32,
+}
+
+#[derive(modelfox::PredictOutput)]
+enum Diagnosis {
+	Negative,
+	Positive,
+}
+
+#[derive(modelfox::PredictInput)]
+struct UnknownFieldInput {
+	age: f32,
+	height: f32,
+}
+
+#[derive(modelfox::PredictInput)]
+struct UnknownVariantInput {
+	gender: MisspelledGender,
+}
+
+// This enum is only used to check the schema, so its variants are never constructed.
+#[allow(dead_code)]
+#[derive(modelfox::PredictInput)]
+enum MisspelledGender {
+	#[modelfox(rename = "male")]
+	Male,
+	#[modelfox(rename = "femal")]
+	Female,
+}
+
+#[derive(modelfox::PredictInput)]
+struct NumberForEnumInput {
+	gender: f32,
+}
+
+#[derive(modelfox::PredictOutput)]
+struct MissingClassOutput {
+	class_name: PositiveDiagnosis,
+	probability: f32,
+}
+
+#[derive(modelfox::PredictOutput)]
+enum PositiveDiagnosis {
+	Positive,
+}
+
+#[derive(modelfox::PredictOutput)]
+struct ExtraClassOutput {
+	class_name: UncertainDiagnosis,
+	probability: f32,
+}
+
+#[derive(modelfox::PredictOutput)]
+enum UncertainDiagnosis {
+	Negative,
+	Positive,
+	Unknown,
+}
+
+/// Train a model on the heart disease dataset with a single linear model, and return the bytes of its `.modelfox` file.
+fn train_heart_disease_model(target_column_name: &str) -> Vec<u8> {
+	let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../heart_disease.csv");
+	let table = modelfox::Table::from_path(&path, Default::default(), &mut |_| {}).unwrap();
+	let config = serde_json::from_str(
+		r#"{ "train": { "grid": [{ "model": "linear", "max_epochs": 1 }] } }"#,
+	)
+	.unwrap();
+	let output = modelfox::train(table, target_column_name)
+		.config(config)
+		.run()
+		.unwrap();
+	output.model_bytes
+}
+
+#[test]
+fn test_check_schema_binary_classifier() {
+	let bytes = train_heart_disease_model("diagnosis");
+	let model = modelfox::Model::<Input, Output>::from_bytes(&bytes, None).unwrap();
+	modelfox::check_schema::<Input, Output>(&model).unwrap();
+	// The types that passed the check make predictions with every variant of their enums.
+	let inputs = [Gender::Male, Gender::Female]
+		.into_iter()
+		.flat_map(|sex| {
+			[
+				None,
+				Some(ChestPain::Asymptomatic),
+				Some(ChestPain::NonAnginaPain),
+				Some(ChestPain::AtypicalAngina),
+				Some(ChestPain::TypicalAngina),
+			]
+			.into_iter()
+			.map(move |chest_pain| Input {
+				age: 63.0,
+				sex,
+				chest_pain,
+			})
+		})
+		.collect::<Vec<_>>();
+	// Converting each class name to a `Diagnosis` would panic if the check had missed a class.
+	for output in model.predict(inputs, None) {
+		assert!((0.5..=1.0).contains(&output.probability));
+	}
+	modelfox::check_schema::<Input, modelfox::PredictOutput>(&model).unwrap();
+	modelfox::check_schema::<modelfox::PredictInput, modelfox::BinaryClassificationPredictOutput>(
+		&model,
+	)
+	.unwrap();
+	let error = |result: anyhow::Result<()>| result.unwrap_err().to_string();
+	assert_eq!(
+		error(modelfox::check_schema::<UnknownFieldInput, Output>(&model)),
+		"The field \"height\" does not match any column in the model.",
+	);
+	assert_eq!(
+		error(modelfox::check_schema::<UnknownVariantInput, Output>(&model)),
+		"The variant \"femal\" of the field \"gender\" is not one of the variants of the column in the model: female, male.",
+	);
+	assert_eq!(
+		error(modelfox::check_schema::<NumberForEnumInput, Output>(&model)),
+		"The field \"gender\" is a number, but the column in the model is not a number column.",
+	);
+	assert_eq!(
+		error(modelfox::check_schema::<Input, modelfox::RegressionPredictOutput>(&model)),
+		"The output type does not read binary classification predict outputs, which the model makes.",
+	);
+	assert_eq!(
+		error(modelfox::check_schema::<
+			Input,
+			modelfox::MulticlassClassificationPredictOutput,
+		>(&model)),
+		"The output type does not read binary classification predict outputs, which the model makes.",
+	);
+	assert_eq!(
+		error(modelfox::check_schema::<Input, MissingClassOutput>(&model)),
+		"The class \"Negative\" of the model is not one of the variants of the output's class name: Positive.",
+	);
+	assert_eq!(
+		error(modelfox::check_schema::<Input, ExtraClassOutput>(&model)),
+		"The variant \"Unknown\" of the output's class name is not one of the classes of the model: Negative, Positive.",
+	);
+}
+
+#[test]
+fn test_check_schema_regressor() {
+	let bytes = train_heart_disease_model("age");
+	let model: modelfox::Model = modelfox::Model::from_bytes(&bytes, None).unwrap();
+	modelfox::check_schema::<modelfox::PredictInput, modelfox::RegressionPredictOutput>(&model)
+		.unwrap();
+	modelfox::check_schema::<modelfox::PredictInput, modelfox::PredictOutput>(&model).unwrap();
+	assert_eq!(
+		modelfox::check_schema::<modelfox::PredictInput, Output>(&model)
+			.unwrap_err()
+			.to_string(),
+		"The output type does not read regression predict outputs, which the model makes.",
+	);
+}
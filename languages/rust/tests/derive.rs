@@ -0,0 +1,6 @@
+#[test]
+fn test_derive() {
+	let t = trybuild::TestCases::new();
+	t.pass("tests/derive/pass/*.rs");
+	t.compile_fail("tests/derive/fail/*.rs");
+}
@@ -0,0 +1,6 @@
+#[derive(modelfox::PredictOutput)]
+struct Output {
+	probability: f32,
+}
+
+fn main() {}
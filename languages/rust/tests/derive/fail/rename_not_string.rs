@@ -0,0 +1,7 @@
+#[derive(modelfox::PredictInput)]
+struct Input {
+	#[modelfox(rename = 1)]
+	age: f32,
+}
+
+fn main() {}
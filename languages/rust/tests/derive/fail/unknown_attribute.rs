@@ -0,0 +1,7 @@
+#[derive(modelfox::PredictInput)]
+struct Input {
+	#[modelfox(name = "Age")]
+	age: f32,
+}
+
+fn main() {}
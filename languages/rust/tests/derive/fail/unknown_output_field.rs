@@ -0,0 +1,7 @@
+#[derive(modelfox::PredictOutput)]
+struct Output {
+	value: f32,
+	label: String,
+}
+
+fn main() {}
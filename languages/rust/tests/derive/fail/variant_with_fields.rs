@@ -0,0 +1,7 @@
+#[derive(modelfox::PredictInput)]
+enum Gender {
+	Male,
+	Other(String),
+}
+
+fn main() {}
@@ -0,0 +1,68 @@
+#[derive(modelfox::PredictInput)]
+struct Input {
+	fluoroscopy_vessels_colored: FluoroscopyVesselsColored,
+}
+
+#[derive(modelfox::PredictInputValue)]
+enum FluoroscopyVesselsColored {
+	#[modelfox(rename = "0")]
+	Zero,
+	#[modelfox(rename = "1")]
+	One,
+}
+
+#[derive(modelfox::PredictOutput)]
+struct Output {
+	class_name: Species,
+	probability: f32,
+	probabilities: std::collections::BTreeMap<String, f32>,
+}
+
+#[derive(modelfox::ClassificationOutputValue)]
+enum Species {
+	Setosa,
+	#[modelfox(rename = "Iris Versicolor")]
+	Versicolor,
+	Virginica,
+}
+
+fn main() {
+	let columns = <Input as modelfox::PredictInputSchema>::columns();
+	assert!(matches!(
+		&columns[0].column_type,
+		modelfox::ColumnSchemaType::Enum { variants } if variants == &["0", "1"]
+	));
+	let input: modelfox::PredictInput = Input {
+		fluoroscopy_vessels_colored: FluoroscopyVesselsColored::One,
+	}
+	.into();
+	assert!(matches!(
+		&input.0["fluoroscopy_vessels_colored"],
+		modelfox::PredictInputValue::String(value) if value == "1"
+	));
+	assert_eq!(
+		<Species as modelfox::ClassificationOutputValue>::variants(),
+		Some(vec![
+			"Setosa".to_owned(),
+			"Iris Versicolor".to_owned(),
+			"Virginica".to_owned(),
+		]),
+	);
+	match <Output as modelfox::PredictOutputSchema>::outputs().as_slice() {
+		[modelfox::OutputSchema::MulticlassClassification { classes }] => {
+			assert_eq!(classes.as_ref().unwrap()[1], "Iris Versicolor");
+		}
+		_ => panic!(),
+	}
+	let output: Output = modelfox::PredictOutput::MulticlassClassification(
+		modelfox::MulticlassClassificationPredictOutput {
+			class_name: "Iris Versicolor".to_owned(),
+			probability: 0.5,
+			probabilities: std::collections::BTreeMap::new(),
+			feature_contributions: None,
+			feature_interactions: None,
+		},
+	)
+	.into();
+	assert!(matches!(output.class_name, Species::Versicolor));
+}
@@ -0,0 +1,63 @@
+#[derive(modelfox::PredictInput)]
+struct Input {
+	age: Option<f32>,
+	gender: Option<Gender>,
+	notes: Option<String>,
+}
+
+#[derive(modelfox::PredictInput)]
+enum Gender {
+	#[modelfox(rename = "male")]
+	Male,
+	#[modelfox(rename = "female")]
+	Female,
+}
+
+#[derive(modelfox::PredictOutput)]
+struct Output {
+	value: f32,
+	feature_contributions: Option<modelfox::FeatureContributions>,
+}
+
+fn main() {
+	let columns = <Input as modelfox::PredictInputSchema>::columns();
+	assert!(matches!(
+		columns[0].column_type,
+		modelfox::ColumnSchemaType::Number
+	));
+	assert!(matches!(
+		&columns[1].column_type,
+		modelfox::ColumnSchemaType::Enum { variants } if variants == &["male", "female"]
+	));
+	assert!(matches!(
+		columns[2].column_type,
+		modelfox::ColumnSchemaType::Text
+	));
+	// Fields that are `None` are left out of the predict input.
+	let input: modelfox::PredictInput = Input {
+		age: None,
+		gender: Some(Gender::Female),
+		notes: None,
+	}
+	.into();
+	assert_eq!(input.0.keys().collect::<Vec<_>>(), vec!["gender"]);
+	let input: modelfox::PredictInput = Input {
+		age: Some(63.0),
+		gender: None,
+		notes: Some("none".to_owned()),
+	}
+	.into();
+	assert_eq!(input.0.keys().collect::<Vec<_>>(), vec!["age", "notes"]);
+	let output: Output = modelfox::PredictOutput::Regression(modelfox::RegressionPredictOutput {
+		value: 1.5,
+		feature_contributions: None,
+		feature_interactions: None,
+	})
+	.into();
+	assert_eq!(output.value, 1.5);
+	assert!(output.feature_contributions.is_none());
+	assert!(matches!(
+		<Output as modelfox::PredictOutputSchema>::outputs().as_slice(),
+		[modelfox::OutputSchema::Regression]
+	));
+}
@@ -0,0 +1,74 @@
+#[derive(modelfox::PredictInput)]
+struct Input {
+	#[modelfox(rename = "Age")]
+	age: f32,
+	#[modelfox(rename = "Chest Pain")]
+	chest_pain: ChestPain,
+}
+
+#[derive(modelfox::PredictInput)]
+enum ChestPain {
+	#[modelfox(rename = "typical angina")]
+	TypicalAngina,
+	#[modelfox(value = "asymptomatic")]
+	Asymptomatic,
+}
+
+#[derive(modelfox::PredictOutput)]
+struct Output {
+	#[modelfox(rename = "class_name")]
+	diagnosis: Diagnosis,
+	#[modelfox(rename = "probability")]
+	confidence: f32,
+}
+
+#[derive(modelfox::PredictOutput)]
+enum Diagnosis {
+	#[modelfox(rename = "Negative")]
+	Healthy,
+	#[modelfox(rename = "Positive")]
+	Sick,
+}
+
+fn main() {
+	let columns = <Input as modelfox::PredictInputSchema>::columns();
+	assert_eq!(columns[0].name, "Age");
+	assert_eq!(columns[1].name, "Chest Pain");
+	let input: modelfox::PredictInput = Input {
+		age: 63.0,
+		chest_pain: ChestPain::TypicalAngina,
+	}
+	.into();
+	assert!(matches!(input.0["Age"], modelfox::PredictInputValue::Number(age) if age == 63.0));
+	assert!(matches!(
+		&input.0["Chest Pain"],
+		modelfox::PredictInputValue::String(chest_pain) if chest_pain == "typical angina"
+	));
+	let input: modelfox::PredictInput = Input {
+		age: 63.0,
+		chest_pain: ChestPain::Asymptomatic,
+	}
+	.into();
+	assert!(matches!(
+		&input.0["Chest Pain"],
+		modelfox::PredictInputValue::String(chest_pain) if chest_pain == "asymptomatic"
+	));
+	let output: Output = modelfox::PredictOutput::BinaryClassification(
+		modelfox::BinaryClassificationPredictOutput {
+			class_name: "Positive".to_owned(),
+			probability: 0.75,
+			feature_contributions: None,
+			feature_interactions: None,
+		},
+	)
+	.into();
+	assert!(matches!(output.diagnosis, Diagnosis::Sick));
+	assert_eq!(output.confidence, 0.75);
+	match modelfox::PredictOutput::from(output) {
+		modelfox::PredictOutput::BinaryClassification(output) => {
+			assert_eq!(output.class_name, "Positive");
+			assert_eq!(output.probability, 0.75);
+		}
+		_ => panic!(),
+	}
+}
@@ -0,0 +1,106 @@
+use modelfox::config;
+use std::path::{Path, PathBuf};
+
+fn heart_disease_path() -> PathBuf {
+	Path::new(env!("CARGO_MANIFEST_DIR")).join("../../heart_disease.csv")
+}
+
+fn load_heart_disease() -> modelfox::Table {
+	modelfox::Table::from_path(&heart_disease_path(), Default::default(), &mut |_| {}).unwrap()
+}
+
+fn inputs() -> Vec<modelfox::PredictInput> {
+	vec![
+		modelfox::predict_input! {
+			"age": 63.0,
+			"gender": "male",
+			"chest_pain": "typical angina",
+			"resting_blood_pressure": 145.0,
+			"cholesterol": 233.0,
+			"exercise_max_heart_rate": 150.0,
+			"thallium_stress_test": "fixed defect",
+		},
+		modelfox::predict_input! {
+			"age": 41.0,
+			"gender": "female",
+			"chest_pain": "atypical angina",
+			"resting_blood_pressure": 130.0,
+			"cholesterol": 204.0,
+			"exercise_max_heart_rate": 172.0,
+			"thallium_stress_test": "normal",
+		},
+	]
+}
+
+#[test]
+fn test_train_matches_config() {
+	// Train with a config built in Rust.
+	let config = config::Config {
+		train: config::Train {
+			grid: Some(vec![config::GridItem::Tree(config::TreeGridItem {
+				feature_fraction_per_tree: Some(0.5),
+				learning_rate: Some(0.2),
+				max_rounds: Some(20),
+				..Default::default()
+			})]),
+			..Default::default()
+		},
+		..Default::default()
+	};
+	let output = modelfox::train(load_heart_disease(), "diagnosis")
+		.config(config)
+		.run()
+		.unwrap();
+
+	// Train on the same file with the equivalent config file, as the cli does.
+	let config = serde_json::from_str(
+		r#"{
+			"train": {
+				"grid": [{
+					"model": "tree",
+					"feature_fraction_per_tree": 0.5,
+					"learning_rate": 0.2,
+					"max_rounds": 20
+				}]
+			}
+		}"#,
+	)
+	.unwrap();
+	let mut trainer = modelfox_core::train::Trainer::prepare(
+		modelfox_id::Id::generate(),
+		modelfox_core::train::TrainingDataSource::File(heart_disease_path()),
+		"diagnosis",
+		config,
+		None,
+		&mut |_| {},
+	)
+	.unwrap();
+	let train_grid_item_outputs = trainer
+		.train_grid(&modelfox::KillChip::default(), &mut |_| {})
+		.unwrap();
+	let model = trainer
+		.test_and_assemble_model(train_grid_item_outputs, &mut |_| {})
+		.unwrap();
+	let model_bytes = model.to_bytes();
+	let summary = modelfox::TrainSummary::from(model);
+
+	// The models have different ids, but they were trained the same way.
+	let test_metrics = |summary: &modelfox::TrainSummary| match &summary.test_metrics {
+		modelfox::Metrics::BinaryClassification(test_metrics) => format!("{:?}", test_metrics),
+		_ => panic!("expected binary classification metrics"),
+	};
+	assert_eq!(test_metrics(&output.summary), test_metrics(&summary));
+	assert_eq!(output.summary.grid.len(), 1);
+	assert_eq!(summary.grid.len(), 1);
+	assert_eq!(
+		output.summary.grid[0].comparison_metric_value,
+		summary.grid[0].comparison_metric_value,
+	);
+	let model: modelfox::Model = modelfox::Model::from_bytes(&model_bytes, None).unwrap();
+	let builder_model: modelfox::Model =
+		modelfox::Model::from_bytes(&output.model_bytes, None).unwrap();
+	assert_eq!(
+		format!("{:?}", builder_model.predict(inputs(), None)),
+		format!("{:?}", model.predict(inputs(), None)),
+	);
+}
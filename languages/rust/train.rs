@@ -0,0 +1,271 @@
+use crate::{PredictInput, PredictInputValue};
+use anyhow::{bail, Result};
+pub use modelfox_core::{config, model::Metrics, progress::ProgressEvent};
+pub use modelfox_kill_chip::KillChip;
+pub use modelfox_table::Table;
+use modelfox_table::{InferOptions, TableColumn};
+use std::{collections::BTreeSet, time::Duration};
+
+/// Start training a model on `data` to predict the column named `target_column_name`. Configure training with the methods on [`Train`], then call [`Train::run`].
+///
+/// ```rust no_run
+/// let table = modelfox::Table::from_path(
+///     std::path::Path::new("heart_disease.csv"),
+///     Default::default(),
+///     &mut |_| {},
+/// )
+/// .unwrap();
+/// let output = modelfox::train(table, "diagnosis").run().unwrap();
+/// let model: modelfox::Model = modelfox::Model::from_bytes(&output.model_bytes, None).unwrap();
+/// ```
+pub fn train<'a>(data: impl Into<TrainData>, target_column_name: &str) -> Train<'a> {
+	Train {
+		data: data.into(),
+		test_data: None,
+		target_column_name: target_column_name.to_owned(),
+		config: config::Config::default(),
+		handle_progress_event: None,
+		kill_chip: None,
+	}
+}
+
+/// This is the data to train a model on. Create it from a [`Table`], or collect it from an iterator of rows, each of which is converted to a [`struct@PredictInput`]. The column types for rows are inferred the same way as when loading a csv file.
+pub enum TrainData {
+	Table(Table),
+	Rows(Vec<PredictInput>),
+}
+
+impl From<Table> for TrainData {
+	fn from(value: Table) -> TrainData {
+		TrainData::Table(value)
+	}
+}
+
+impl<T> FromIterator<T> for TrainData
+where
+	T: Into<PredictInput>,
+{
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> TrainData {
+		TrainData::Rows(iter.into_iter().map(Into::into).collect())
+	}
+}
+
+impl TrainData {
+	fn into_table(self) -> Table {
+		match self {
+			TrainData::Table(table) => table,
+			TrainData::Rows(rows) => table_from_rows(&rows),
+		}
+	}
+}
+
+/// This builder is returned by [`train`].
+pub struct Train<'a> {
+	data: TrainData,
+	test_data: Option<TrainData>,
+	target_column_name: String,
+	config: config::Config,
+	handle_progress_event: Option<Box<dyn FnMut(ProgressEvent) + 'a>>,
+	kill_chip: Option<&'a KillChip>,
+}
+
+impl<'a> Train<'a> {
+	/// Test the best model on `test_data` instead of a fraction of the train data. It must have the same columns as the train data.
+	pub fn test_data(mut self, test_data: impl Into<TrainData>) -> Train<'a> {
+		self.test_data = Some(test_data.into());
+		self
+	}
+
+	/// Use `config` to configure training. If not specified, the default config is used.
+	pub fn config(mut self, config: config::Config) -> Train<'a> {
+		self.config = config;
+		self
+	}
+
+	/// Call `handle_progress_event` with each progress event during training.
+	pub fn on_progress(
+		mut self,
+		handle_progress_event: impl FnMut(ProgressEvent) + 'a,
+	) -> Train<'a> {
+		self.handle_progress_event = Some(Box::new(handle_progress_event));
+		self
+	}
+
+	/// Stop training the hyperparameter grid when `kill_chip` is activated. The best model trained so far is tested and returned.
+	pub fn kill_chip(mut self, kill_chip: &'a KillChip) -> Train<'a> {
+		self.kill_chip = Some(kill_chip);
+		self
+	}
+
+	/// Train the model.
+	pub fn run(self) -> Result<TrainOutput> {
+		let Train {
+			data,
+			test_data,
+			target_column_name,
+			config,
+			handle_progress_event,
+			kill_chip,
+		} = self;
+		let input = match test_data {
+			None => modelfox_core::train::TrainingDataSource::Table(data.into_table()),
+			Some(test_data) => modelfox_core::train::TrainingDataSource::TrainAndTestTables {
+				train: data.into_table(),
+				test: test_data.into_table(),
+			},
+		};
+		let mut handle_progress_event = handle_progress_event.unwrap_or_else(|| Box::new(|_| {}));
+		let default_kill_chip = KillChip::default();
+		let kill_chip = kill_chip.unwrap_or(&default_kill_chip);
+		let mut trainer = modelfox_core::train::Trainer::prepare(
+			modelfox_id::Id::generate(),
+			input,
+			&target_column_name,
+			config,
+			None,
+			&mut handle_progress_event,
+		)?;
+		let train_grid_item_outputs = trainer.train_grid(kill_chip, &mut handle_progress_event)?;
+		if train_grid_item_outputs.is_empty() {
+			bail!("Training was stopped before any model was trained.");
+		}
+		let model =
+			trainer.test_and_assemble_model(train_grid_item_outputs, &mut handle_progress_event)?;
+		let model_bytes = model.to_bytes();
+		Ok(TrainOutput {
+			model_bytes,
+			summary: model.into(),
+		})
+	}
+}
+
+/// This is the output of [`Train::run`].
+pub struct TrainOutput {
+	/// These are the bytes of the `.modelfox` file for the best model. Write them to a file or pass them to [`crate::Model::from_bytes`].
+	pub model_bytes: Vec<u8>,
+	/// This summarizes how the best model was chosen and how well it performs.
+	pub summary: TrainSummary,
+}
+
+/// This is a summary of training returned in [`TrainOutput`].
+pub struct TrainSummary {
+	/// These are the metrics on the test dataset for a baseline model that always predicts the mean or the majority class.
+	pub baseline_metrics: Metrics,
+	/// These are the metrics for the best model on the test dataset.
+	pub test_metrics: Metrics,
+	/// This has one entry for each model in the hyperparameter grid.
+	pub grid: Vec<TrainGridItemSummary>,
+	/// This is the index in `grid` of the best model.
+	pub best_grid_item_index: usize,
+}
+
+/// This is the result of training a single model in the hyperparameter grid.
+pub struct TrainGridItemSummary {
+	/// This is the type of the model.
+	pub model_type: config::ModelType,
+	/// This is the value of the comparison metric on the comparison dataset.
+	pub comparison_metric_value: f32,
+	/// These are all the metrics on the comparison dataset.
+	pub comparison_metrics: Metrics,
+	/// This is how long it took to train the model.
+	pub duration: Duration,
+}
+
+impl From<modelfox_core::model::Model> for TrainSummary {
+	fn from(value: modelfox_core::model::Model) -> TrainSummary {
+		match value.inner {
+			modelfox_core::model::ModelInner::Regressor(model) => TrainSummary {
+				baseline_metrics: Metrics::Regression(model.baseline_metrics),
+				test_metrics: Metrics::Regression(model.test_metrics),
+				grid: model
+					.train_grid_item_outputs
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+				best_grid_item_index: model.best_grid_item_index,
+			},
+			modelfox_core::model::ModelInner::BinaryClassifier(model) => TrainSummary {
+				baseline_metrics: Metrics::BinaryClassification(model.baseline_metrics),
+				test_metrics: Metrics::BinaryClassification(model.test_metrics),
+				grid: model
+					.train_grid_item_outputs
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+				best_grid_item_index: model.best_grid_item_index,
+			},
+			modelfox_core::model::ModelInner::MulticlassClassifier(model) => TrainSummary {
+				baseline_metrics: Metrics::MulticlassClassification(model.baseline_metrics),
+				test_metrics: Metrics::MulticlassClassification(model.test_metrics),
+				grid: model
+					.train_grid_item_outputs
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+				best_grid_item_index: model.best_grid_item_index,
+			},
+			modelfox_core::model::ModelInner::Ranker(model) => TrainSummary {
+				baseline_metrics: Metrics::Ranking(model.baseline_metrics),
+				test_metrics: Metrics::Ranking(model.test_metrics),
+				grid: model
+					.train_grid_item_outputs
+					.into_iter()
+					.map(Into::into)
+					.collect(),
+				best_grid_item_index: model.best_grid_item_index,
+			},
+		}
+	}
+}
+
+impl From<modelfox_core::train::TrainGridItemOutput> for TrainGridItemSummary {
+	fn from(value: modelfox_core::train::TrainGridItemOutput) -> TrainGridItemSummary {
+		use modelfox_core::train::TrainModelOutput;
+		let model_type = match value.train_model_output {
+			TrainModelOutput::LinearRegressor(_)
+			| TrainModelOutput::LinearBinaryClassifier(_)
+			| TrainModelOutput::LinearMulticlassClassifier(_) => config::ModelType::Linear,
+			TrainModelOutput::TreeRegressor(_)
+			| TrainModelOutput::TreeBinaryClassifier(_)
+			| TrainModelOutput::TreeMulticlassClassifier(_)
+			| TrainModelOutput::TreeRanker(_) => config::ModelType::Tree,
+		};
+		TrainGridItemSummary {
+			model_type,
+			comparison_metric_value: value.comparison_metric_value,
+			comparison_metrics: value.comparison_metrics,
+			duration: value.duration,
+		}
+	}
+}
+
+/// Create a table from rows, with a column for each key that appears in any row. Missing values are treated as invalid values.
+fn table_from_rows(rows: &[PredictInput]) -> Table {
+	let mut column_names = Vec::new();
+	let mut column_names_set = BTreeSet::new();
+	for row in rows {
+		for column_name in row.0.keys() {
+			if column_names_set.insert(column_name.as_str()) {
+				column_names.push(column_name.as_str());
+			}
+		}
+	}
+	let mut table = Table::new(Vec::new(), Vec::new());
+	for column_name in column_names {
+		let values: Vec<String> = rows
+			.iter()
+			.map(|row| match row.0.get(column_name) {
+				Some(PredictInputValue::Number(value)) => value.to_string(),
+				Some(PredictInputValue::String(value)) => value.clone(),
+				None => String::new(),
+			})
+			.collect();
+		let values: Vec<&str> = values.iter().map(String::as_str).collect();
+		table.columns_mut().push(TableColumn::from_strings(
+			Some(column_name.to_owned()),
+			&values,
+			&InferOptions::default(),
+		));
+	}
+	table
+}